@@ -0,0 +1,114 @@
+// Copyright (c) 2019-2020, MASQ (https://masq.ai) and/or its affiliates. All rights reserved.
+#![cfg(target_os = "linux")]
+
+use std::process;
+
+pub trait CommandRunner {
+    // Returns standard output if the command succeeds; otherwise a description of the failure.
+    fn run(&self, program: &str, args: &[&str]) -> Result<String, String>;
+}
+
+#[derive(Default)]
+pub struct CommandRunnerReal {}
+
+impl CommandRunner for CommandRunnerReal {
+    fn run(&self, program: &str, args: &[&str]) -> Result<String, String> {
+        let output = match process::Command::new(program).args(args).output() {
+            Ok(output) => output,
+            Err(e) => return Err(format!("Could not run {}: {}", program, e)),
+        };
+        if output.status.success() {
+            Ok(String::from_utf8_lossy(&output.stdout).to_string())
+        } else {
+            let mut command_line = vec![program];
+            command_line.extend_from_slice(args);
+            Err(format!(
+                "{} failed: {}",
+                command_line.join(" "),
+                String::from_utf8_lossy(&output.stderr).trim()
+            ))
+        }
+    }
+}
+
+#[cfg(test)]
+pub mod tests_utils {
+    use super::*;
+    use std::cell::RefCell;
+    use std::sync::{Arc, Mutex};
+
+    #[derive(Default)]
+    pub struct CommandRunnerMock {
+        run_params: Arc<Mutex<Vec<String>>>,
+        run_results: RefCell<Vec<Result<String, String>>>,
+    }
+
+    impl CommandRunner for CommandRunnerMock {
+        fn run(&self, program: &str, args: &[&str]) -> Result<String, String> {
+            let mut command_line = vec![program];
+            command_line.extend_from_slice(args);
+            self.run_params
+                .lock()
+                .expect("run couldn't take params")
+                .push(command_line.join(" "));
+            if self.run_results.borrow().is_empty() {
+                panic!("run called without a stub: {}", command_line.join(" "))
+            }
+            self.run_results.borrow_mut().remove(0)
+        }
+    }
+
+    impl CommandRunnerMock {
+        pub fn new() -> Self {
+            Self::default()
+        }
+
+        pub fn run_params(mut self, params: &Arc<Mutex<Vec<String>>>) -> Self {
+            self.run_params = params.clone();
+            self
+        }
+
+        pub fn run_result(self, result: Result<String, String>) -> Self {
+            self.run_results.borrow_mut().push(result);
+            self
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn real_runner_returns_standard_output_on_success() {
+        let subject = CommandRunnerReal::default();
+
+        let result = subject.run("echo", &["booga"]);
+
+        assert_eq!(result, Ok("booga\n".to_string()));
+    }
+
+    #[test]
+    fn real_runner_reports_failure() {
+        let subject = CommandRunnerReal::default();
+
+        let result = subject.run("false", &[]);
+
+        assert_eq!(result, Err("false failed: ".to_string()));
+    }
+
+    #[test]
+    fn real_runner_reports_missing_program() {
+        let subject = CommandRunnerReal::default();
+
+        let result = subject.run("no_such_program_here", &["booga"]);
+
+        let message = result.err().unwrap();
+        assert_eq!(
+            message.starts_with("Could not run no_such_program_here: "),
+            true,
+            "{}",
+            message
+        );
+    }
+}
@@ -5,8 +5,12 @@ use std::fs::File;
 #[cfg(target_os = "linux")]
 use std::path::Path;
 
+#[cfg(target_os = "linux")]
+use crate::network_manager_dns_modifier::NetworkManagerDnsModifier;
 #[cfg(target_os = "linux")]
 use crate::resolv_conf_dns_modifier::ResolvConfDnsModifier;
+#[cfg(target_os = "linux")]
+use crate::systemd_resolved_dns_modifier::SystemdResolvedDnsModifier;
 
 #[cfg(target_os = "macos")]
 use crate::dynamic_store_dns_modifier::DynamicStoreDnsModifier;
@@ -35,9 +39,13 @@ impl DnsModifierFactoryReal {
     }
 }
 
-const QUALIFIER_FACTORIES: [&dyn QualifierFactory; 3] = [
+// On Linux, whatever manages /etc/resolv.conf has to be asked to change it; editing the file
+// directly is the last resort.
+const QUALIFIER_FACTORIES: [&dyn QualifierFactory; 5] = [
     &DynamicStoreQualifierFactory {},
     &WinQualifierFactory {},
+    &NetworkManagerQualifierFactory {},
+    &SystemdResolvedQualifierFactory {},
     &ResolvConfQualifierFactory {},
 ];
 
@@ -46,6 +54,48 @@ trait QualifierFactory {
     fn make(&self) -> Box<dyn DnsModifier>;
 }
 
+struct NetworkManagerQualifierFactory;
+#[cfg(target_os = "linux")]
+impl QualifierFactory for NetworkManagerQualifierFactory {
+    fn system_qualifies(&self) -> bool {
+        NetworkManagerDnsModifier::new().is_in_charge()
+    }
+    fn make(&self) -> Box<dyn DnsModifier> {
+        Box::new(NetworkManagerDnsModifier::new())
+    }
+}
+
+#[cfg(not(target_os = "linux"))]
+impl QualifierFactory for NetworkManagerQualifierFactory {
+    fn system_qualifies(&self) -> bool {
+        false
+    }
+    fn make(&self) -> Box<dyn DnsModifier> {
+        panic!("Should never be called")
+    }
+}
+
+struct SystemdResolvedQualifierFactory;
+#[cfg(target_os = "linux")]
+impl QualifierFactory for SystemdResolvedQualifierFactory {
+    fn system_qualifies(&self) -> bool {
+        SystemdResolvedDnsModifier::new().is_in_charge()
+    }
+    fn make(&self) -> Box<dyn DnsModifier> {
+        Box::new(SystemdResolvedDnsModifier::new())
+    }
+}
+
+#[cfg(not(target_os = "linux"))]
+impl QualifierFactory for SystemdResolvedQualifierFactory {
+    fn system_qualifies(&self) -> bool {
+        false
+    }
+    fn make(&self) -> Box<dyn DnsModifier> {
+        panic!("Should never be called")
+    }
+}
+
 struct ResolvConfQualifierFactory;
 #[cfg(target_os = "linux")]
 impl QualifierFactory for ResolvConfQualifierFactory {
@@ -134,6 +184,40 @@ mod tests {
         }
     }
 
+    #[test]
+    fn network_manager_qualifier_factory_works_on_this_os() {
+        let subject = NetworkManagerQualifierFactory {};
+
+        let result = subject.system_qualifies();
+
+        #[cfg(target_os = "linux")]
+        {
+            assert_eq!(result, NetworkManagerDnsModifier::new().is_in_charge())
+        }
+
+        #[cfg(not(target_os = "linux"))]
+        {
+            assert_eq!(result, false)
+        }
+    }
+
+    #[test]
+    fn systemd_resolved_qualifier_factory_works_on_this_os() {
+        let subject = SystemdResolvedQualifierFactory {};
+
+        let result = subject.system_qualifies();
+
+        #[cfg(target_os = "linux")]
+        {
+            assert_eq!(result, SystemdResolvedDnsModifier::new().is_in_charge())
+        }
+
+        #[cfg(not(target_os = "linux"))]
+        {
+            assert_eq!(result, false)
+        }
+    }
+
     #[test]
     fn win_qualifier_factory_works_on_this_os() {
         let subject = WinQualifierFactory {};
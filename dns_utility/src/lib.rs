@@ -10,15 +10,19 @@ extern crate system_configuration;
 
 #[cfg(target_os = "windows")]
 pub mod adapter_wrapper;
+pub mod command_runner;
 pub mod dns_modifier;
 pub mod dns_modifier_factory;
 pub mod dns_utility;
 pub mod dynamic_store_dns_modifier;
 #[cfg(target_os = "windows")]
 pub mod ipconfig_wrapper;
+pub mod link_dns;
 #[cfg(target_os = "windows")]
 pub mod netsh;
+pub mod network_manager_dns_modifier;
 pub mod resolv_conf_dns_modifier;
+pub mod systemd_resolved_dns_modifier;
 pub mod utils;
 #[cfg(target_os = "windows")]
 pub mod win_dns_modifier;
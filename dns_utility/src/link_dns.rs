@@ -0,0 +1,155 @@
+// Copyright (c) 2019-2020, MASQ (https://masq.ai) and/or its affiliates. All rights reserved.
+#![cfg(target_os = "linux")]
+
+use std::io;
+
+pub const MASQ_NAMESERVER: &str = "127.0.0.1";
+
+// The DNS servers a network manager has assigned to one network link. Managers that keep DNS
+// settings per link are subverted by pointing every connected link at MASQ_NAMESERVER.
+#[derive(Clone, Debug, PartialEq)]
+pub struct LinkDns {
+    pub name: String,
+    pub servers: Vec<String>,
+}
+
+impl LinkDns {
+    pub fn new(name: &str, servers: Vec<&str>) -> Self {
+        Self {
+            name: name.to_string(),
+            servers: servers.into_iter().map(|s| s.to_string()).collect(),
+        }
+    }
+
+    pub fn is_subverted(&self) -> bool {
+        self.servers == vec![MASQ_NAMESERVER.to_string()]
+    }
+}
+
+pub fn links_to_subvert(links: &[LinkDns]) -> Result<Vec<&LinkDns>, String> {
+    let connected = connected_links(links)?;
+    if connected
+        .iter()
+        .any(|link| !link.is_subverted() && link.servers.iter().any(|s| s == MASQ_NAMESERVER))
+    {
+        return Err(String::from(
+            "This system's DNS settings don't make sense; aborting",
+        ));
+    }
+    Ok(connected
+        .into_iter()
+        .filter(|link| !link.is_subverted())
+        .collect())
+}
+
+pub fn links_to_revert(links: &[LinkDns]) -> Vec<&LinkDns> {
+    links.iter().filter(|link| link.is_subverted()).collect()
+}
+
+pub fn inspect_links(links: &[LinkDns], stdout: &mut (dyn io::Write + Send)) -> Result<(), String> {
+    let connected = connected_links(links)?;
+    let mut servers: Vec<&String> = vec![];
+    connected
+        .iter()
+        .flat_map(|link| link.servers.iter())
+        .for_each(|server| {
+            if !servers.contains(&server) {
+                servers.push(server)
+            }
+        });
+    let output_list = servers.into_iter().fold(String::new(), |so_far, server| {
+        format!("{}{}\n", so_far, server)
+    });
+    write!(stdout, "{}", output_list).expect("stdout doesn't work");
+    Ok(())
+}
+
+fn connected_links(links: &[LinkDns]) -> Result<Vec<&LinkDns>, String> {
+    let connected: Vec<&LinkDns> = links
+        .iter()
+        .filter(|link| !link.servers.is_empty())
+        .collect();
+    if connected.is_empty() {
+        Err(String::from(
+            "This system does not appear to be connected to a network",
+        ))
+    } else {
+        Ok(connected)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use masq_lib::test_utils::fake_stream_holder::FakeStreamHolder;
+
+    #[test]
+    fn links_to_subvert_skips_disconnected_and_subverted_links() {
+        let links = vec![
+            LinkDns::new("eth0", vec!["8.8.8.8", "9.9.9.9"]),
+            LinkDns::new("docker0", vec![]),
+            LinkDns::new("wlan0", vec!["127.0.0.1"]),
+            LinkDns::new("eth1", vec!["1.1.1.1"]),
+        ];
+
+        let result = links_to_subvert(&links);
+
+        assert_eq!(result, Ok(vec![&links[0], &links[3]]));
+    }
+
+    #[test]
+    fn links_to_subvert_complains_about_nonsense() {
+        let links = vec![LinkDns::new("eth0", vec!["8.8.8.8", "127.0.0.1"])];
+
+        let result = links_to_subvert(&links);
+
+        assert_eq!(
+            result,
+            Err(String::from(
+                "This system's DNS settings don't make sense; aborting"
+            ))
+        );
+    }
+
+    #[test]
+    fn links_to_subvert_complains_if_nothing_is_connected() {
+        let links = vec![LinkDns::new("eth0", vec![])];
+
+        let result = links_to_subvert(&links);
+
+        assert_eq!(
+            result,
+            Err(String::from(
+                "This system does not appear to be connected to a network"
+            ))
+        );
+    }
+
+    #[test]
+    fn links_to_revert_finds_subverted_links() {
+        let links = vec![
+            LinkDns::new("eth0", vec!["127.0.0.1"]),
+            LinkDns::new("eth1", vec!["1.1.1.1"]),
+            LinkDns::new("wlan0", vec!["127.0.0.1"]),
+        ];
+
+        let result = links_to_revert(&links);
+
+        assert_eq!(result, vec![&links[0], &links[2]]);
+    }
+
+    #[test]
+    fn inspect_links_lists_each_server_once() {
+        let mut holder = FakeStreamHolder::new();
+        let links = vec![
+            LinkDns::new("eth0", vec!["8.8.8.8", "9.9.9.9"]),
+            LinkDns::new("docker0", vec![]),
+            LinkDns::new("wlan0", vec!["9.9.9.9", "1.1.1.1"]),
+        ];
+
+        let result = inspect_links(&links, holder.streams().stdout);
+
+        assert_eq!(result, Ok(()));
+        assert_eq!(holder.stdout.get_string(), "8.8.8.8\n9.9.9.9\n1.1.1.1\n");
+    }
+}
@@ -0,0 +1,377 @@
+// Copyright (c) 2019-2020, MASQ (https://masq.ai) and/or its affiliates. All rights reserved.
+#![cfg(target_os = "linux")]
+use crate::command_runner::{CommandRunner, CommandRunnerReal};
+use crate::dns_modifier::DnsModifier;
+use crate::link_dns::{inspect_links, links_to_revert, links_to_subvert, LinkDns, MASQ_NAMESERVER};
+use std::io;
+
+// Subverts NetworkManager by modifying the settings applied to each connected device. The
+// connection profiles themselves are left alone, so reverting is just reapplying them, and a
+// reboot or reconnection reverts on its own.
+pub struct NetworkManagerDnsModifier {
+    runner: Box<dyn CommandRunner>,
+}
+
+impl DnsModifier for NetworkManagerDnsModifier {
+    fn type_name(&self) -> &'static str {
+        "NetworkManagerDnsModifier"
+    }
+
+    fn subvert(&self) -> Result<(), String> {
+        let links = self.find_links()?;
+        let mut subverted_so_far: Vec<&LinkDns> = vec![];
+        for link in links_to_subvert(&links)? {
+            subverted_so_far.push(link);
+            if let Err(msg) = self.subvert_link(link) {
+                subverted_so_far
+                    .into_iter()
+                    .for_each(|link| self.roll_back_subvert(link));
+                return Err(msg);
+            }
+        }
+        Ok(())
+    }
+
+    fn revert(&self) -> Result<(), String> {
+        let links = self.find_links()?;
+        links_to_revert(&links)
+            .into_iter()
+            .map(|link| self.revert_link(link))
+            .collect()
+    }
+
+    fn inspect(&self, stdout: &mut (dyn io::Write + Send)) -> Result<(), String> {
+        let links = self.find_links()?;
+        inspect_links(&links, stdout)
+    }
+}
+
+impl Default for NetworkManagerDnsModifier {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl NetworkManagerDnsModifier {
+    pub fn new() -> Self {
+        Self {
+            runner: Box::new(CommandRunnerReal::default()),
+        }
+    }
+
+    // NetworkManager is in charge when it's running and hasn't been told to leave DNS alone.
+    pub fn is_in_charge(&self) -> bool {
+        match self.runner.run(
+            "busctl",
+            &[
+                "get-property",
+                "org.freedesktop.NetworkManager",
+                "/org/freedesktop/NetworkManager/DnsManager",
+                "org.freedesktop.NetworkManager.DnsManager",
+                "Mode",
+            ],
+        ) {
+            // busctl shows a string property as: s "systemd-resolved"
+            Ok(output) => {
+                let mode = output
+                    .trim()
+                    .trim_start_matches('s')
+                    .trim()
+                    .trim_matches('"');
+                !mode.is_empty() && mode != "none"
+            }
+            Err(_) => false,
+        }
+    }
+
+    fn find_links(&self) -> Result<Vec<LinkDns>, String> {
+        let output = self.runner.run(
+            "nmcli",
+            &["-t", "-f", "DEVICE,TYPE,STATE", "device", "status"],
+        )?;
+        output
+            .lines()
+            .filter_map(|line| {
+                let fields: Vec<&str> = line.split(':').collect();
+                match fields.as_slice() {
+                    [device, device_type, "connected"] if *device_type != "loopback" => {
+                        Some(device.to_string())
+                    }
+                    _ => None,
+                }
+            })
+            .map(|device| self.find_link(device))
+            .collect()
+    }
+
+    fn find_link(&self, device: String) -> Result<LinkDns, String> {
+        let output = self.runner.run(
+            "nmcli",
+            &["-t", "-f", "IP4.DNS,IP6.DNS", "device", "show", &device],
+        )?;
+        // Terse output looks like IP4.DNS[1]:192.168.1.1, with colons in values escaped
+        let servers = output
+            .lines()
+            .filter_map(|line| {
+                let colon = line.find(':')?;
+                Some(line[(colon + 1)..].replace("\\:", ":"))
+            })
+            .filter(|server| !server.is_empty())
+            .collect();
+        Ok(LinkDns {
+            name: device,
+            servers,
+        })
+    }
+
+    // IPv6 servers are dropped as well, so that no lookups leave through them.
+    fn subvert_link(&self, link: &LinkDns) -> Result<(), String> {
+        self.runner.run(
+            "nmcli",
+            &[
+                "device",
+                "modify",
+                &link.name,
+                "ipv4.dns",
+                MASQ_NAMESERVER,
+                "ipv4.ignore-auto-dns",
+                "yes",
+                "ipv6.dns",
+                "",
+                "ipv6.ignore-auto-dns",
+                "yes",
+            ],
+        )?;
+        Ok(())
+    }
+
+    fn revert_link(&self, link: &LinkDns) -> Result<(), String> {
+        self.runner
+            .run("nmcli", &["device", "reapply", &link.name])?;
+        Ok(())
+    }
+
+    fn roll_back_subvert(&self, link: &LinkDns) {
+        let _ = self.revert_link(link);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::command_runner::tests_utils::CommandRunnerMock;
+    use masq_lib::test_utils::fake_stream_holder::FakeStreamHolder;
+    use std::sync::{Arc, Mutex};
+
+    const DEVICE_STATUS: &str = "wlp2s0:wifi:connected\nenp0s31f6:ethernet:unavailable\nlo:loopback:connected (externally)\ndocker0:bridge:connected (externally)\nwwan0:gsm:connected\n";
+    const DEVICE_STATUS_COMMAND: &str = "nmcli -t -f DEVICE,TYPE,STATE device status";
+
+    fn make_subject(runner: CommandRunnerMock) -> NetworkManagerDnsModifier {
+        NetworkManagerDnsModifier {
+            runner: Box::new(runner),
+        }
+    }
+
+    #[test]
+    fn instance_knows_its_type_name() {
+        let subject = NetworkManagerDnsModifier::new();
+
+        let result = subject.type_name();
+
+        assert_eq!(result, "NetworkManagerDnsModifier");
+    }
+
+    #[test]
+    fn is_in_charge_when_managing_dns() {
+        let run_params_arc = Arc::new(Mutex::new(vec![]));
+        let subject = make_subject(
+            CommandRunnerMock::new()
+                .run_params(&run_params_arc)
+                .run_result(Ok("s \"systemd-resolved\"\n".to_string())),
+        );
+
+        let result = subject.is_in_charge();
+
+        assert_eq!(result, true);
+        assert_eq!(
+            *run_params_arc.lock().unwrap(),
+            vec![
+                "busctl get-property org.freedesktop.NetworkManager \
+                 /org/freedesktop/NetworkManager/DnsManager \
+                 org.freedesktop.NetworkManager.DnsManager Mode"
+            ]
+        );
+    }
+
+    #[test]
+    fn is_not_in_charge_when_told_to_leave_dns_alone() {
+        let subject =
+            make_subject(CommandRunnerMock::new().run_result(Ok("s \"none\"\n".to_string())));
+
+        let result = subject.is_in_charge();
+
+        assert_eq!(result, false);
+    }
+
+    #[test]
+    fn is_not_in_charge_when_not_running() {
+        let subject = make_subject(CommandRunnerMock::new().run_result(Err(
+            "busctl get-property failed: Unit dbus-org.freedesktop.NetworkManager.service not found."
+                .to_string(),
+        )));
+
+        let result = subject.is_in_charge();
+
+        assert_eq!(result, false);
+    }
+
+    #[test]
+    fn subvert_modifies_connected_devices() {
+        let run_params_arc = Arc::new(Mutex::new(vec![]));
+        let subject = make_subject(
+            CommandRunnerMock::new()
+                .run_params(&run_params_arc)
+                .run_result(Ok(DEVICE_STATUS.to_string()))
+                .run_result(Ok(
+                    "IP4.DNS[1]:192.168.1.1\nIP6.DNS[1]:fe80\\:\\:1\n".to_string()
+                ))
+                .run_result(Ok("IP4.DNS[1]:10.0.0.1\n".to_string()))
+                .run_result(Ok(String::new()))
+                .run_result(Ok(String::new())),
+        );
+
+        let result = subject.subvert();
+
+        assert_eq!(result, Ok(()));
+        assert_eq!(
+            *run_params_arc.lock().unwrap(),
+            vec![
+                DEVICE_STATUS_COMMAND,
+                "nmcli -t -f IP4.DNS,IP6.DNS device show wlp2s0",
+                "nmcli -t -f IP4.DNS,IP6.DNS device show wwan0",
+                "nmcli device modify wlp2s0 ipv4.dns 127.0.0.1 ipv4.ignore-auto-dns yes \
+                 ipv6.dns  ipv6.ignore-auto-dns yes",
+                "nmcli device modify wwan0 ipv4.dns 127.0.0.1 ipv4.ignore-auto-dns yes \
+                 ipv6.dns  ipv6.ignore-auto-dns yes",
+            ]
+        );
+    }
+
+    #[test]
+    fn subvert_backs_off_if_dns_is_already_subverted() {
+        let run_params_arc = Arc::new(Mutex::new(vec![]));
+        let subject = make_subject(
+            CommandRunnerMock::new()
+                .run_params(&run_params_arc)
+                .run_result(Ok("wlp2s0:wifi:connected\n".to_string()))
+                .run_result(Ok("IP4.DNS[1]:127.0.0.1\n".to_string())),
+        );
+
+        let result = subject.subvert();
+
+        assert_eq!(result, Ok(()));
+        assert_eq!(run_params_arc.lock().unwrap().len(), 2);
+    }
+
+    #[test]
+    fn subvert_complains_if_no_device_is_connected() {
+        let subject = make_subject(
+            CommandRunnerMock::new().run_result(Ok("enp0s31f6:ethernet:unavailable\n".to_string())),
+        );
+
+        let result = subject.subvert();
+
+        assert_eq!(
+            result,
+            Err("This system does not appear to be connected to a network".to_string())
+        );
+    }
+
+    #[test]
+    fn subvert_rolls_back_when_a_device_fails() {
+        let run_params_arc = Arc::new(Mutex::new(vec![]));
+        let subject = make_subject(
+            CommandRunnerMock::new()
+                .run_params(&run_params_arc)
+                .run_result(Ok(DEVICE_STATUS.to_string()))
+                .run_result(Ok("IP4.DNS[1]:192.168.1.1\n".to_string()))
+                .run_result(Ok("IP4.DNS[1]:10.0.0.1\n".to_string()))
+                .run_result(Ok(String::new()))
+                .run_result(Err("nmcli device modify wwan0 failed: booga".to_string()))
+                .run_result(Ok(String::new()))
+                .run_result(Ok(String::new())),
+        );
+
+        let result = subject.subvert();
+
+        assert_eq!(
+            result,
+            Err("nmcli device modify wwan0 failed: booga".to_string())
+        );
+        let run_params = run_params_arc.lock().unwrap();
+        assert_eq!(
+            run_params[5..].to_vec(),
+            vec![
+                "nmcli device reapply wlp2s0".to_string(),
+                "nmcli device reapply wwan0".to_string(),
+            ]
+        );
+    }
+
+    #[test]
+    fn revert_reapplies_subverted_devices_only() {
+        let run_params_arc = Arc::new(Mutex::new(vec![]));
+        let subject = make_subject(
+            CommandRunnerMock::new()
+                .run_params(&run_params_arc)
+                .run_result(Ok(DEVICE_STATUS.to_string()))
+                .run_result(Ok("IP4.DNS[1]:127.0.0.1\n".to_string()))
+                .run_result(Ok("IP4.DNS[1]:10.0.0.1\n".to_string()))
+                .run_result(Ok(String::new())),
+        );
+
+        let result = subject.revert();
+
+        assert_eq!(result, Ok(()));
+        assert_eq!(
+            run_params_arc.lock().unwrap()[3],
+            "nmcli device reapply wlp2s0".to_string()
+        );
+        assert_eq!(run_params_arc.lock().unwrap().len(), 4);
+    }
+
+    #[test]
+    fn inspect_lists_servers_of_connected_devices() {
+        let mut holder = FakeStreamHolder::new();
+        let subject = make_subject(
+            CommandRunnerMock::new()
+                .run_result(Ok(DEVICE_STATUS.to_string()))
+                .run_result(Ok(
+                    "IP4.DNS[1]:192.168.1.1\nIP4.DNS[2]:8.8.8.8\nIP6.DNS[1]:fe80\\:\\:1\n"
+                        .to_string(),
+                ))
+                .run_result(Ok("IP4.DNS[1]:8.8.8.8\n".to_string())),
+        );
+
+        let result = subject.inspect(holder.streams().stdout);
+
+        assert_eq!(result, Ok(()));
+        assert_eq!(
+            holder.stdout.get_string(),
+            "192.168.1.1\n8.8.8.8\nfe80::1\n"
+        );
+    }
+
+    #[test]
+    fn inspect_reports_nmcli_failure() {
+        let mut holder = FakeStreamHolder::new();
+        let subject = make_subject(
+            CommandRunnerMock::new().run_result(Err("Could not run nmcli".to_string())),
+        );
+
+        let result = subject.inspect(holder.streams().stdout);
+
+        assert_eq!(result, Err("Could not run nmcli".to_string()));
+        assert_eq!(holder.stdout.get_string(), String::new());
+    }
+}
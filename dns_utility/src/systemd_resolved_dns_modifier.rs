@@ -0,0 +1,625 @@
+// Copyright (c) 2019-2020, MASQ (https://masq.ai) and/or its affiliates. All rights reserved.
+#![cfg(target_os = "linux")]
+use crate::command_runner::{CommandRunner, CommandRunnerReal};
+use crate::dns_modifier::DnsModifier;
+use crate::link_dns::{inspect_links, links_to_revert, links_to_subvert, LinkDns, MASQ_NAMESERVER};
+use regex::Regex;
+use std::collections::HashMap;
+use std::fs;
+use std::io;
+use std::path::{Path, PathBuf};
+
+// Subverts systemd-resolved through its per-link DNS settings. Those settings last until the link
+// goes down or resolved restarts. Resetting a link with resolvectl revert would leave it with no
+// DNS at all, because systemd-networkd doesn't push its settings again; so each link's original
+// servers and domains are saved at subversion time and put back on reversion.
+pub struct SystemdResolvedDnsModifier {
+    runner: Box<dyn CommandRunner>,
+    root: PathBuf,
+}
+
+impl DnsModifier for SystemdResolvedDnsModifier {
+    fn type_name(&self) -> &'static str {
+        "SystemdResolvedDnsModifier"
+    }
+
+    fn subvert(&self) -> Result<(), String> {
+        let (global_servers, links) = self.find_links()?;
+        if !global_servers.is_empty() {
+            return Err(format!(
+                "systemd-resolved has global DNS servers ({}) that would still be used; \
+                 remove them from /etc/systemd/resolved.conf first",
+                global_servers.join(", ")
+            ));
+        }
+        let links_to_subvert = links_to_subvert(&links)?;
+        if links_to_subvert.is_empty() {
+            return Ok(());
+        }
+        let (_, domains) = self.find_settings("domain")?;
+        let domains: HashMap<String, Vec<String>> = domains.into_iter().collect();
+        let originals: Vec<SavedLink> = links_to_subvert
+            .into_iter()
+            .map(|link| SavedLink {
+                name: link.name.clone(),
+                servers: link.servers.clone(),
+                domains: domains.get(&link.name).cloned().unwrap_or_default(),
+            })
+            .collect();
+        self.save_links(&originals)?;
+        for (index, original) in originals.iter().enumerate() {
+            if let Err(msg) = self.subvert_link(&original.name) {
+                originals[..=index]
+                    .iter()
+                    .for_each(|original| self.roll_back_subvert(original));
+                return Err(msg);
+            }
+        }
+        Ok(())
+    }
+
+    fn revert(&self) -> Result<(), String> {
+        let (_, links) = self.find_links()?;
+        let saved_links = self.load_saved_links();
+        links_to_revert(&links)
+            .into_iter()
+            .map(
+                |link| match saved_links.iter().find(|saved| saved.name == link.name) {
+                    Some(original) => self.restore_link(original),
+                    None => self.revert_link(link),
+                },
+            )
+            .collect::<Result<(), String>>()?;
+        let _ = fs::remove_file(self.saved_links_path());
+        Ok(())
+    }
+
+    fn inspect(&self, stdout: &mut (dyn io::Write + Send)) -> Result<(), String> {
+        let (global_servers, mut links) = self.find_links()?;
+        let global = LinkDns {
+            name: "Global".to_string(),
+            servers: global_servers,
+        };
+        links.insert(0, global);
+        inspect_links(&links, stdout)
+    }
+}
+
+impl Default for SystemdResolvedDnsModifier {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl SystemdResolvedDnsModifier {
+    pub fn new() -> Self {
+        Self {
+            runner: Box::new(CommandRunnerReal::default()),
+            root: PathBuf::from("/"),
+        }
+    }
+
+    // resolved is in charge when /etc/resolv.conf is one of the files it generates, since edits
+    // to those are overwritten.
+    pub fn is_in_charge(&self) -> bool {
+        let resolv_conf = Path::new(&self.root)
+            .join(Path::new("etc"))
+            .join(Path::new("resolv.conf"));
+        match fs::read_link(resolv_conf) {
+            Ok(target) if target.to_string_lossy().contains("run/systemd/resolve/") => {
+                self.runner.run("resolvectl", &["dns"]).is_ok()
+            }
+            _ => false,
+        }
+    }
+
+    fn find_links(&self) -> Result<(Vec<String>, Vec<LinkDns>), String> {
+        let (global_servers, servers) = self.find_settings("dns")?;
+        let links = servers
+            .into_iter()
+            .map(|(name, servers)| LinkDns { name, servers })
+            .collect();
+        Ok((global_servers, links))
+    }
+
+    // resolvectl dns and resolvectl domain both list their values as Global: ... followed by
+    // one Link <index> (<name>): ... line per link, in link order.
+    fn find_settings(
+        &self,
+        setting: &str,
+    ) -> Result<(Vec<String>, Vec<(String, Vec<String>)>), String> {
+        let output = self.runner.run("resolvectl", &[setting])?;
+        let regex =
+            Regex::new(r"^(Global|Link \d+ \(([^)]+)\)):\s*(.*)$").expect("Regex syntax error");
+        let mut global_values = vec![];
+        let mut links = vec![];
+        output
+            .lines()
+            .filter_map(|line| regex.captures(line.trim()))
+            .for_each(|captures| {
+                let values: Vec<String> = captures
+                    .get(3)
+                    .map(|m| m.as_str())
+                    .unwrap_or("")
+                    .split_whitespace()
+                    .map(|value| value.to_string())
+                    .collect();
+                match captures.get(2) {
+                    Some(name) => links.push((name.as_str().to_string(), values)),
+                    None => global_values = values,
+                }
+            });
+        Ok((global_values, links))
+    }
+
+    // The ~. routing domain sends every lookup to this link rather than to whichever link
+    // resolved would otherwise prefer.
+    fn subvert_link(&self, name: &str) -> Result<(), String> {
+        self.runner
+            .run("resolvectl", &["dns", name, MASQ_NAMESERVER])?;
+        self.runner.run("resolvectl", &["domain", name, "~."])?;
+        Ok(())
+    }
+
+    // An empty value tells resolvectl to clear the setting.
+    fn restore_link(&self, original: &SavedLink) -> Result<(), String> {
+        for (setting, values) in &[("dns", &original.servers), ("domain", &original.domains)] {
+            let mut args = vec![*setting, original.name.as_str()];
+            if values.is_empty() {
+                args.push("");
+            } else {
+                args.extend(values.iter().map(|value| value.as_str()));
+            }
+            self.runner.run("resolvectl", &args)?;
+        }
+        Ok(())
+    }
+
+    // Used only for subverted links nothing was saved for, such as ones subverted by an older
+    // version of this utility.
+    fn revert_link(&self, link: &LinkDns) -> Result<(), String> {
+        self.runner.run("resolvectl", &["revert", &link.name])?;
+        Ok(())
+    }
+
+    fn roll_back_subvert(&self, original: &SavedLink) {
+        let _ = self.restore_link(original);
+    }
+
+    // Kept under /run, like resolved's own runtime settings, so that it doesn't outlive them.
+    fn saved_links_path(&self) -> PathBuf {
+        self.root
+            .join(Path::new("run"))
+            .join(Path::new("masq"))
+            .join(Path::new("systemd-resolved-links"))
+    }
+
+    // Links being subverted replace any earlier entries for the same links; entries for other
+    // links that are still subverted are kept.
+    fn save_links(&self, originals: &[SavedLink]) -> Result<(), String> {
+        let mut saved_links: Vec<SavedLink> = self
+            .load_saved_links()
+            .into_iter()
+            .filter(|saved| !originals.iter().any(|original| original.name == saved.name))
+            .collect();
+        saved_links.extend(originals.iter().cloned());
+        let contents = saved_links.iter().fold(String::new(), |so_far, saved| {
+            format!(
+                "{}{}\t{}\t{}\n",
+                so_far,
+                saved.name,
+                saved.servers.join(" "),
+                saved.domains.join(" ")
+            )
+        });
+        let path = self.saved_links_path();
+        path.parent()
+            .map(fs::create_dir_all)
+            .unwrap_or(Ok(()))
+            .and_then(|_| fs::write(&path, contents))
+            .map_err(|e| {
+                format!(
+                    "Could not save original DNS settings to {}: {}",
+                    path.to_string_lossy(),
+                    e
+                )
+            })
+    }
+
+    fn load_saved_links(&self) -> Vec<SavedLink> {
+        let contents = fs::read_to_string(self.saved_links_path()).unwrap_or_default();
+        contents
+            .lines()
+            .filter_map(|line| {
+                let fields: Vec<&str> = line.split('\t').collect();
+                match fields.as_slice() {
+                    [name, servers, domains] if !name.is_empty() => Some(SavedLink {
+                        name: name.to_string(),
+                        servers: servers.split_whitespace().map(|s| s.to_string()).collect(),
+                        domains: domains.split_whitespace().map(|s| s.to_string()).collect(),
+                    }),
+                    _ => None,
+                }
+            })
+            .collect()
+    }
+}
+
+// A link's DNS settings from before it was subverted.
+#[derive(Clone, Debug, PartialEq)]
+struct SavedLink {
+    name: String,
+    servers: Vec<String>,
+    domains: Vec<String>,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::command_runner::tests_utils::CommandRunnerMock;
+    use masq_lib::test_utils::fake_stream_holder::FakeStreamHolder;
+    use std::env;
+    use std::os::unix::fs::symlink;
+    use std::sync::{Arc, Mutex};
+
+    const RESOLVECTL_DNS: &str = "Global:\nLink 2 (enp0s3): 192.168.1.1 fe80::1%2\nLink 3 (docker0):\nLink 4 (wlan0): 192.168.1.1\n";
+    const RESOLVECTL_DOMAIN: &str =
+        "Global:\nLink 2 (enp0s3): home.lan\nLink 3 (docker0):\nLink 4 (wlan0):\n";
+
+    fn make_subject(runner: CommandRunnerMock) -> SystemdResolvedDnsModifier {
+        let mut subject = SystemdResolvedDnsModifier::new();
+        subject.runner = Box::new(runner);
+        subject
+    }
+
+    #[test]
+    fn instance_knows_its_type_name() {
+        let subject = SystemdResolvedDnsModifier::new();
+
+        let result = subject.type_name();
+
+        assert_eq!(result, "SystemdResolvedDnsModifier");
+    }
+
+    #[test]
+    fn is_in_charge_when_resolv_conf_links_to_resolved_stub() {
+        let root = make_root("is_in_charge_when_resolv_conf_links_to_resolved_stub");
+        symlink(
+            "../run/systemd/resolve/stub-resolv.conf",
+            root.join("etc").join("resolv.conf"),
+        )
+        .unwrap();
+        let mut subject =
+            make_subject(CommandRunnerMock::new().run_result(Ok(RESOLVECTL_DNS.to_string())));
+        subject.root = root;
+
+        let result = subject.is_in_charge();
+
+        assert_eq!(result, true);
+    }
+
+    #[test]
+    fn is_not_in_charge_when_resolv_conf_is_a_plain_file() {
+        let root = make_root("is_not_in_charge_when_resolv_conf_is_a_plain_file");
+        fs::write(root.join("etc").join("resolv.conf"), "nameserver 8.8.8.8\n").unwrap();
+        let mut subject = make_subject(CommandRunnerMock::new());
+        subject.root = root;
+
+        let result = subject.is_in_charge();
+
+        assert_eq!(result, false);
+    }
+
+    #[test]
+    fn is_not_in_charge_when_resolvectl_does_not_work() {
+        let root = make_root("is_not_in_charge_when_resolvectl_does_not_work");
+        symlink(
+            "/run/systemd/resolve/resolv.conf",
+            root.join("etc").join("resolv.conf"),
+        )
+        .unwrap();
+        let mut subject = make_subject(
+            CommandRunnerMock::new().run_result(Err("Could not run resolvectl".to_string())),
+        );
+        subject.root = root;
+
+        let result = subject.is_in_charge();
+
+        assert_eq!(result, false);
+    }
+
+    #[test]
+    fn subvert_points_connected_links_at_masq() {
+        let root = make_root("subvert_points_connected_links_at_masq");
+        let run_params_arc = Arc::new(Mutex::new(vec![]));
+        let mut subject = make_subject(
+            CommandRunnerMock::new()
+                .run_params(&run_params_arc)
+                .run_result(Ok(RESOLVECTL_DNS.to_string()))
+                .run_result(Ok(RESOLVECTL_DOMAIN.to_string()))
+                .run_result(Ok(String::new()))
+                .run_result(Ok(String::new()))
+                .run_result(Ok(String::new()))
+                .run_result(Ok(String::new())),
+        );
+        subject.root = root.clone();
+
+        let result = subject.subvert();
+
+        assert_eq!(result, Ok(()));
+        assert_eq!(
+            *run_params_arc.lock().unwrap(),
+            vec![
+                "resolvectl dns",
+                "resolvectl domain",
+                "resolvectl dns enp0s3 127.0.0.1",
+                "resolvectl domain enp0s3 ~.",
+                "resolvectl dns wlan0 127.0.0.1",
+                "resolvectl domain wlan0 ~.",
+            ]
+        );
+        assert_eq!(
+            fs::read_to_string(root.join("run").join("masq").join("systemd-resolved-links"))
+                .unwrap(),
+            "enp0s3\t192.168.1.1 fe80::1%2\thome.lan\nwlan0\t192.168.1.1\t\n"
+        );
+    }
+
+    #[test]
+    fn subvert_backs_off_if_dns_is_already_subverted() {
+        let run_params_arc = Arc::new(Mutex::new(vec![]));
+        let subject = make_subject(
+            CommandRunnerMock::new()
+                .run_params(&run_params_arc)
+                .run_result(Ok("Global:\nLink 2 (enp0s3): 127.0.0.1\n".to_string())),
+        );
+
+        let result = subject.subvert();
+
+        assert_eq!(result, Ok(()));
+        assert_eq!(*run_params_arc.lock().unwrap(), vec!["resolvectl dns"]);
+    }
+
+    #[test]
+    fn subvert_complains_about_global_servers() {
+        let subject = make_subject(CommandRunnerMock::new().run_result(Ok(
+            "Global: 1.1.1.1 9.9.9.9\nLink 2 (enp0s3): 192.168.1.1\n".to_string(),
+        )));
+
+        let result = subject.subvert();
+
+        assert_eq!(
+            result,
+            Err(
+                "systemd-resolved has global DNS servers (1.1.1.1, 9.9.9.9) that would still be \
+                 used; remove them from /etc/systemd/resolved.conf first"
+                    .to_string()
+            )
+        );
+    }
+
+    #[test]
+    fn subvert_complains_if_there_is_no_connected_link() {
+        let subject = make_subject(
+            CommandRunnerMock::new().run_result(Ok("Global:\nLink 3 (docker0):\n".to_string())),
+        );
+
+        let result = subject.subvert();
+
+        assert_eq!(
+            result,
+            Err("This system does not appear to be connected to a network".to_string())
+        );
+    }
+
+    #[test]
+    fn subvert_rolls_back_when_a_link_fails() {
+        let run_params_arc = Arc::new(Mutex::new(vec![]));
+        let mut subject = make_subject(
+            CommandRunnerMock::new()
+                .run_params(&run_params_arc)
+                .run_result(Ok(RESOLVECTL_DNS.to_string()))
+                .run_result(Ok(RESOLVECTL_DOMAIN.to_string()))
+                .run_result(Ok(String::new()))
+                .run_result(Ok(String::new()))
+                .run_result(Ok(String::new()))
+                .run_result(Err("resolvectl domain wlan0 ~. failed: booga".to_string()))
+                .run_result(Ok(String::new()))
+                .run_result(Ok(String::new()))
+                .run_result(Ok(String::new()))
+                .run_result(Ok(String::new())),
+        );
+        subject.root = make_root("subvert_rolls_back_when_a_link_fails");
+
+        let result = subject.subvert();
+
+        assert_eq!(
+            result,
+            Err("resolvectl domain wlan0 ~. failed: booga".to_string())
+        );
+        assert_eq!(
+            *run_params_arc.lock().unwrap(),
+            vec![
+                "resolvectl dns",
+                "resolvectl domain",
+                "resolvectl dns enp0s3 127.0.0.1",
+                "resolvectl domain enp0s3 ~.",
+                "resolvectl dns wlan0 127.0.0.1",
+                "resolvectl domain wlan0 ~.",
+                "resolvectl dns enp0s3 192.168.1.1 fe80::1%2",
+                "resolvectl domain enp0s3 home.lan",
+                "resolvectl dns wlan0 192.168.1.1",
+                "resolvectl domain wlan0 ",
+            ]
+        );
+    }
+
+    #[test]
+    fn subvert_keeps_saved_settings_of_links_it_does_not_subvert_again() {
+        let root = make_root("subvert_keeps_saved_settings_of_links_it_does_not_subvert_again");
+        let saved_links_path = root.join("run").join("masq").join("systemd-resolved-links");
+        fs::create_dir_all(saved_links_path.parent().unwrap()).unwrap();
+        fs::write(&saved_links_path, "enp0s3\t192.168.1.1\thome.lan\n").unwrap();
+        let mut subject = make_subject(
+            CommandRunnerMock::new()
+                .run_result(Ok(
+                    "Global:\nLink 2 (enp0s3): 127.0.0.1\nLink 4 (wlan0): 10.0.0.1\n".to_string(),
+                ))
+                .run_result(Ok(
+                    "Global:\nLink 2 (enp0s3): ~.\nLink 4 (wlan0):\n".to_string()
+                ))
+                .run_result(Ok(String::new()))
+                .run_result(Ok(String::new())),
+        );
+        subject.root = root;
+
+        let result = subject.subvert();
+
+        assert_eq!(result, Ok(()));
+        assert_eq!(
+            fs::read_to_string(&saved_links_path).unwrap(),
+            "enp0s3\t192.168.1.1\thome.lan\nwlan0\t10.0.0.1\t\n"
+        );
+    }
+
+    #[test]
+    fn subvert_reports_resolvectl_failure() {
+        let subject = make_subject(
+            CommandRunnerMock::new().run_result(Err("Could not run resolvectl".to_string())),
+        );
+
+        let result = subject.subvert();
+
+        assert_eq!(result, Err("Could not run resolvectl".to_string()));
+    }
+
+    #[test]
+    fn revert_restores_original_servers_and_domains() {
+        let root = make_root("revert_restores_original_servers_and_domains");
+        let mut subverter = make_subject(
+            CommandRunnerMock::new()
+                .run_result(Ok(RESOLVECTL_DNS.to_string()))
+                .run_result(Ok(RESOLVECTL_DOMAIN.to_string()))
+                .run_result(Ok(String::new()))
+                .run_result(Ok(String::new()))
+                .run_result(Ok(String::new()))
+                .run_result(Ok(String::new())),
+        );
+        subverter.root = root.clone();
+        subverter.subvert().unwrap();
+        let run_params_arc = Arc::new(Mutex::new(vec![]));
+        let mut subject = make_subject(
+            CommandRunnerMock::new()
+                .run_params(&run_params_arc)
+                .run_result(Ok(
+                    "Global:\nLink 2 (enp0s3): 127.0.0.1\nLink 3 (docker0):\nLink 4 (wlan0): 127.0.0.1\n"
+                        .to_string(),
+                ))
+                .run_result(Ok(String::new()))
+                .run_result(Ok(String::new()))
+                .run_result(Ok(String::new()))
+                .run_result(Ok(String::new())),
+        );
+        subject.root = root.clone();
+
+        let result = subject.revert();
+
+        assert_eq!(result, Ok(()));
+        assert_eq!(
+            *run_params_arc.lock().unwrap(),
+            vec![
+                "resolvectl dns",
+                "resolvectl dns enp0s3 192.168.1.1 fe80::1%2",
+                "resolvectl domain enp0s3 home.lan",
+                "resolvectl dns wlan0 192.168.1.1",
+                "resolvectl domain wlan0 ",
+            ]
+        );
+        assert_eq!(
+            root.join("run")
+                .join("masq")
+                .join("systemd-resolved-links")
+                .exists(),
+            false
+        );
+    }
+
+    #[test]
+    fn revert_resets_subverted_links_it_has_no_saved_settings_for() {
+        let run_params_arc = Arc::new(Mutex::new(vec![]));
+        let mut subject = make_subject(
+            CommandRunnerMock::new()
+                .run_params(&run_params_arc)
+                .run_result(Ok(
+                    "Global:\nLink 2 (enp0s3): 127.0.0.1\nLink 5 (eth1): 10.0.0.1\n".to_string(),
+                ))
+                .run_result(Ok(String::new())),
+        );
+        subject.root = make_root("revert_resets_subverted_links_it_has_no_saved_settings_for");
+
+        let result = subject.revert();
+
+        assert_eq!(result, Ok(()));
+        assert_eq!(
+            *run_params_arc.lock().unwrap(),
+            vec!["resolvectl dns", "resolvectl revert enp0s3"]
+        );
+    }
+
+    #[test]
+    fn revert_reports_failure() {
+        let mut subject = make_subject(
+            CommandRunnerMock::new()
+                .run_result(Ok("Link 2 (enp0s3): 127.0.0.1\n".to_string()))
+                .run_result(Err("resolvectl revert enp0s3 failed: booga".to_string())),
+        );
+        subject.root = make_root("revert_reports_failure");
+
+        let result = subject.revert();
+
+        assert_eq!(
+            result,
+            Err("resolvectl revert enp0s3 failed: booga".to_string())
+        );
+    }
+
+    #[test]
+    fn inspect_lists_global_and_link_servers() {
+        let mut holder = FakeStreamHolder::new();
+        let subject = make_subject(CommandRunnerMock::new().run_result(Ok(
+            "Global: 9.9.9.9\nLink 2 (enp0s3): 192.168.1.1 fe80::1%2\nLink 4 (wlan0): 192.168.1.1\n"
+                .to_string(),
+        )));
+
+        let result = subject.inspect(holder.streams().stdout);
+
+        assert_eq!(result, Ok(()));
+        assert_eq!(
+            holder.stdout.get_string(),
+            "9.9.9.9\n192.168.1.1\nfe80::1%2\n"
+        );
+    }
+
+    #[test]
+    fn inspect_shows_subversion() {
+        let mut holder = FakeStreamHolder::new();
+        let subject = make_subject(CommandRunnerMock::new().run_result(Ok(
+            "Global:\nLink 2 (enp0s3): 127.0.0.1\nLink 4 (wlan0): 127.0.0.1\n".to_string(),
+        )));
+
+        let result = subject.inspect(holder.streams().stdout);
+
+        assert_eq!(result, Ok(()));
+        assert_eq!(holder.stdout.get_string(), "127.0.0.1\n");
+    }
+
+    fn make_root(test_name: &str) -> PathBuf {
+        let cur_dir = env::current_dir().unwrap();
+        let generated_dir = cur_dir.join(Path::new("generated"));
+        let suite_dir = generated_dir.join(Path::new("SystemdResolvedDnsModifier"));
+        let base_dir = suite_dir.join(Path::new(test_name));
+        let _ = fs::remove_dir_all(base_dir.clone()); // don't care if it doesn't exist
+        fs::create_dir_all(base_dir.join(Path::new("etc"))).unwrap();
+        base_dir
+    }
+}
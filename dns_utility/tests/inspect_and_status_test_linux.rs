@@ -7,6 +7,7 @@ mod utils;
 use crate::linux_utils::get_file_contents;
 use crate::linux_utils::get_nameserver_entries;
 use crate::linux_utils::is_subverted;
+use crate::linux_utils::resolv_conf_is_in_charge;
 use crate::utils::TestCommand;
 use dns_utility_lib::resolv_conf_dns_modifier::ResolvConfDnsModifier;
 
@@ -14,8 +15,8 @@ use dns_utility_lib::resolv_conf_dns_modifier::ResolvConfDnsModifier;
 // Any integration tests that should be run without root should have names ending in '_user_integration'
 fn resolv_conf_inspect_and_status_user_integration() {
     let file_contents = match get_file_contents() {
-        Ok(s) => s,
-        Err(_) => {
+        Ok(ref s) if resolv_conf_is_in_charge() => s.clone(),
+        _ => {
             println!("---INTEGRATION TEST CANNOT YET RUN IN THIS ENVIRONMENT---");
             return;
         }
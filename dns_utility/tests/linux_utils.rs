@@ -1,6 +1,7 @@
 // Copyright (c) 2017-2019, Substratum LLC (https://substratum.net) and/or its affiliates. All rights reserved.
 #![cfg(target_os = "linux")]
 
+use dns_utility_lib::dns_modifier_factory::{DnsModifierFactory, DnsModifierFactoryReal};
 use dns_utility_lib::resolv_conf_dns_modifier::ResolvConfDnsModifier;
 use std::fs::File;
 use std::io;
@@ -33,3 +34,11 @@ pub fn is_subverted(entries: &Vec<String>) -> bool {
     };
     ResolvConfDnsModifier::is_masq_ip(&first_entry)
 }
+
+// Where NetworkManager or systemd-resolved manages DNS, dns_utility doesn't touch /etc/resolv.conf
+pub fn resolv_conf_is_in_charge() -> bool {
+    match DnsModifierFactoryReal::new().make() {
+        Some(modifier) => modifier.type_name() == "ResolvConfDnsModifier",
+        None => false,
+    }
+}
@@ -1,17 +1,30 @@
 // Copyright (c) 2019-2020, MASQ (https://masq.ai) and/or its affiliates. All rights reserved.
 
 use crate::command_factory::CommandFactoryError::{CommandSyntax, UnrecognizedSubcommand};
+use crate::commands::ban_command::{BanCommand, UnbanCommand};
+use crate::commands::banned_wallets_command::BannedWalletsCommand;
 use crate::commands::change_password_command::ChangePasswordCommand;
 use crate::commands::check_password_command::CheckPasswordCommand;
 use crate::commands::commands_common::Command;
+use crate::commands::connection_status_command::ConnectionStatusCommand;
 use crate::commands::crash_command::CrashCommand;
+use crate::commands::debut_command::{
+    ApproveDebutCommand, DebutAllowListCommand, PendingDebutsCommand, RejectDebutCommand,
+};
 use crate::commands::descriptor_command::DescriptorCommand;
+use crate::commands::dns_bypass_command::DnsBypassCommand;
+use crate::commands::exit_country_command::ExitCountryCommand;
+use crate::commands::export_accounts_command::ExportAccountsCommand;
 use crate::commands::generate_wallets_command::GenerateWalletsCommand;
+use crate::commands::malefactor_bans_command::{MalefactorBansCommand, MalefactorUnbanCommand};
 use crate::commands::recover_wallets_command::RecoverWalletsCommand;
+use crate::commands::scan_command::ScanCommand;
 use crate::commands::setup_command::SetupCommand;
 use crate::commands::shutdown_command::ShutdownCommand;
 use crate::commands::start_command::StartCommand;
+use crate::commands::status_command::StatusCommand;
 use crate::commands::wallet_addresses::WalletAddressesCommand;
+use crate::commands::wallet_balances_command::WalletBalancesCommand;
 
 #[derive(Debug, PartialEq)]
 pub enum CommandFactoryError {
@@ -29,6 +42,15 @@ pub struct CommandFactoryReal {}
 impl CommandFactory for CommandFactoryReal {
     fn make(&self, pieces: Vec<String>) -> Result<Box<dyn Command>, CommandFactoryError> {
         let boxed_command: Box<dyn Command> = match pieces[0].as_str() {
+            "approve-debut" => match ApproveDebutCommand::new(pieces) {
+                Ok(command) => Box::new(command),
+                Err(msg) => return Err(CommandSyntax(msg)),
+            },
+            "ban" => match BanCommand::new(pieces) {
+                Ok(command) => Box::new(command),
+                Err(msg) => return Err(CommandSyntax(msg)),
+            },
+            "banned-wallets" => Box::new(BannedWalletsCommand::new()),
             "change-password" => match ChangePasswordCommand::new_change(pieces) {
                 Ok(command) => Box::new(command),
                 Err(msg) => return Err(CommandSyntax(msg)),
@@ -37,19 +59,50 @@ impl CommandFactory for CommandFactoryReal {
                 Ok(command) => Box::new(command),
                 Err(msg) => return Err(CommandSyntax(msg)),
             },
+            "connection-status" => Box::new(ConnectionStatusCommand::new()),
             "crash" => match CrashCommand::new(pieces) {
                 Ok(command) => Box::new(command),
                 Err(msg) => return Err(CommandSyntax(msg)),
             },
+            "debut-allow-list" => match DebutAllowListCommand::new(pieces) {
+                Ok(command) => Box::new(command),
+                Err(msg) => return Err(CommandSyntax(msg)),
+            },
             "descriptor" => Box::new(DescriptorCommand::new()),
+            "dns-bypass" => match DnsBypassCommand::new(pieces) {
+                Ok(command) => Box::new(command),
+                Err(msg) => return Err(CommandSyntax(msg)),
+            },
+            "exit-country" => match ExitCountryCommand::new(pieces) {
+                Ok(command) => Box::new(command),
+                Err(msg) => return Err(CommandSyntax(msg)),
+            },
+            "export-accounts" => match ExportAccountsCommand::new(pieces) {
+                Ok(command) => Box::new(command),
+                Err(msg) => return Err(CommandSyntax(msg)),
+            },
             "generate-wallets" => match GenerateWalletsCommand::new(pieces) {
                 Ok(command) => Box::new(command),
                 Err(msg) => return Err(CommandSyntax(msg)),
             },
+            "malefactor-bans" => Box::new(MalefactorBansCommand::new()),
+            "malefactor-unban" => match MalefactorUnbanCommand::new(pieces) {
+                Ok(command) => Box::new(command),
+                Err(msg) => return Err(CommandSyntax(msg)),
+            },
+            "pending-debuts" => Box::new(PendingDebutsCommand::new()),
             "recover-wallets" => match RecoverWalletsCommand::new(pieces) {
                 Ok(command) => Box::new(command),
                 Err(msg) => return Err(CommandSyntax(msg)),
             },
+            "reject-debut" => match RejectDebutCommand::new(pieces) {
+                Ok(command) => Box::new(command),
+                Err(msg) => return Err(CommandSyntax(msg)),
+            },
+            "scan" => match ScanCommand::new(pieces) {
+                Ok(command) => Box::new(command),
+                Err(msg) => return Err(CommandSyntax(msg)),
+            },
             "set-password" => match ChangePasswordCommand::new_set(pieces) {
                 Ok(command) => Box::new(command),
                 Err(msg) => return Err(CommandSyntax(msg)),
@@ -60,10 +113,16 @@ impl CommandFactory for CommandFactoryReal {
             },
             "shutdown" => Box::new(ShutdownCommand::new()),
             "start" => Box::new(StartCommand::new()),
+            "status" => Box::new(StatusCommand::new()),
+            "unban" => match UnbanCommand::new(pieces) {
+                Ok(command) => Box::new(command),
+                Err(msg) => return Err(CommandSyntax(msg)),
+            },
             "wallet-addresses" => match WalletAddressesCommand::new(pieces) {
                 Ok(command) => Box::new(command),
                 Err(msg) => return Err(CommandSyntax(msg)),
             },
+            "wallet-balances" => Box::new(WalletBalancesCommand::new()),
             unrecognized => return Err(UnrecognizedSubcommand(unrecognized.to_string())),
         };
         Ok(boxed_command)
@@ -0,0 +1,320 @@
+// Copyright (c) 2019-2020, MASQ (https://masq.ai) and/or its affiliates. All rights reserved.
+
+use crate::command_context::CommandContext;
+use crate::commands::commands_common::{
+    transaction, Command, CommandError, STANDARD_COMMAND_TIMEOUT_MILLIS,
+};
+use clap::{App, Arg, SubCommand};
+use masq_lib::messages::{
+    UiBanChangedBroadcast, UiBanRequest, UiBanResponse, UiUnbanRequest, UiUnbanResponse,
+};
+use std::any::Any;
+use std::io::Write;
+
+pub const DEFAULT_BAN_REASON: &str = "Banned manually";
+
+#[derive(Debug, PartialEq)]
+pub struct BanCommand {
+    pub wallet: String,
+    pub reason: String,
+}
+
+#[derive(Debug, PartialEq)]
+pub struct UnbanCommand {
+    pub wallet: String,
+}
+
+pub fn ban_subcommand() -> App<'static, 'static> {
+    SubCommand::with_name("ban")
+        .about("Bans a wallet: the running MASQNode will refuse to serve anyone paying from it")
+        .arg(
+            Arg::with_name("wallet")
+                .help("Address of the wallet to ban")
+                .value_name("WALLET")
+                .index(1)
+                .required(true)
+                .case_insensitive(true),
+        )
+        .arg(
+            Arg::with_name("reason")
+                .help("Why the wallet is being banned; recorded with the ban")
+                .value_name("REASON")
+                .index(2)
+                .default_value(DEFAULT_BAN_REASON),
+        )
+}
+
+pub fn unban_subcommand() -> App<'static, 'static> {
+    SubCommand::with_name("unban")
+        .about("Lifts the ban on a wallet, whether it was banned manually or for delinquency")
+        .arg(
+            Arg::with_name("wallet")
+                .help("Address of the wallet to unban")
+                .value_name("WALLET")
+                .index(1)
+                .required(true)
+                .case_insensitive(true),
+        )
+}
+
+impl BanCommand {
+    pub fn new(pieces: Vec<String>) -> Result<Self, String> {
+        match ban_subcommand().get_matches_from_safe(pieces) {
+            Ok(matches) => Ok(Self {
+                wallet: matches
+                    .value_of("wallet")
+                    .expect("wallet is not properly required")
+                    .to_string(),
+                reason: matches
+                    .value_of("reason")
+                    .expect("reason is not properly defaulted")
+                    .to_string(),
+            }),
+            Err(e) => Err(format!("{}", e)),
+        }
+    }
+
+    pub fn handle_broadcast(body: UiBanChangedBroadcast, stdout: &mut dyn Write) {
+        match (body.banned, body.reason_opt) {
+            (true, Some(reason)) => write!(
+                stdout,
+                "\nWallet {} has been banned: {}\n\nmasq> ",
+                body.wallet, reason
+            ),
+            (true, None) => write!(
+                stdout,
+                "\nWallet {} has been banned.\n\nmasq> ",
+                body.wallet
+            ),
+            (false, _) => write!(
+                stdout,
+                "\nWallet {} is no longer banned.\n\nmasq> ",
+                body.wallet
+            ),
+        }
+        .expect("write! failed");
+    }
+}
+
+impl Command for BanCommand {
+    fn execute(&self, context: &mut dyn CommandContext) -> Result<(), CommandError> {
+        let input = UiBanRequest {
+            wallet: self.wallet.clone(),
+            reason: self.reason.clone(),
+        };
+        let _: UiBanResponse = transaction(input, context, STANDARD_COMMAND_TIMEOUT_MILLIS)?;
+        writeln!(context.stdout(), "Wallet {} banned", self.wallet).expect("writeln! failed");
+        Ok(())
+    }
+
+    fn as_any(&self) -> &dyn Any {
+        self
+    }
+}
+
+impl UnbanCommand {
+    pub fn new(pieces: Vec<String>) -> Result<Self, String> {
+        match unban_subcommand().get_matches_from_safe(pieces) {
+            Ok(matches) => Ok(Self {
+                wallet: matches
+                    .value_of("wallet")
+                    .expect("wallet is not properly required")
+                    .to_string(),
+            }),
+            Err(e) => Err(format!("{}", e)),
+        }
+    }
+}
+
+impl Command for UnbanCommand {
+    fn execute(&self, context: &mut dyn CommandContext) -> Result<(), CommandError> {
+        let input = UiUnbanRequest {
+            wallet: self.wallet.clone(),
+        };
+        let _: UiUnbanResponse = transaction(input, context, STANDARD_COMMAND_TIMEOUT_MILLIS)?;
+        writeln!(context.stdout(), "Wallet {} unbanned", self.wallet).expect("writeln! failed");
+        Ok(())
+    }
+
+    fn as_any(&self) -> &dyn Any {
+        self
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::command_context::ContextError;
+    use crate::command_factory::{CommandFactory, CommandFactoryReal};
+    use crate::commands::commands_common::CommandError::Payload;
+    use crate::test_utils::mocks::CommandContextMock;
+    use masq_lib::messages::ToMessageBody;
+    use std::sync::{Arc, Mutex};
+
+    const WALLET: &str = "0x0123456789012345678901234567890123456789";
+
+    #[test]
+    fn factory_produces_ban_with_default_reason() {
+        let factory = CommandFactoryReal::new();
+
+        let command = factory
+            .make(vec!["ban".to_string(), WALLET.to_string()])
+            .unwrap();
+
+        assert_eq!(
+            command.as_any().downcast_ref::<BanCommand>().unwrap(),
+            &BanCommand {
+                wallet: WALLET.to_string(),
+                reason: DEFAULT_BAN_REASON.to_string(),
+            }
+        );
+    }
+
+    #[test]
+    fn factory_produces_ban_with_reason() {
+        let factory = CommandFactoryReal::new();
+
+        let command = factory
+            .make(vec![
+                "ban".to_string(),
+                WALLET.to_string(),
+                "Abusive traffic".to_string(),
+            ])
+            .unwrap();
+
+        assert_eq!(
+            command.as_any().downcast_ref::<BanCommand>().unwrap(),
+            &BanCommand {
+                wallet: WALLET.to_string(),
+                reason: "Abusive traffic".to_string(),
+            }
+        );
+    }
+
+    #[test]
+    fn factory_produces_unban() {
+        let factory = CommandFactoryReal::new();
+
+        let command = factory
+            .make(vec!["unban".to_string(), WALLET.to_string()])
+            .unwrap();
+
+        assert_eq!(
+            command.as_any().downcast_ref::<UnbanCommand>().unwrap(),
+            &UnbanCommand {
+                wallet: WALLET.to_string(),
+            }
+        );
+    }
+
+    #[test]
+    fn ban_command_happy_path() {
+        let transact_params_arc = Arc::new(Mutex::new(vec![]));
+        let mut context = CommandContextMock::new()
+            .transact_params(&transact_params_arc)
+            .transact_result(Ok(UiBanResponse {}.tmb(0)));
+        let stdout_arc = context.stdout_arc();
+        let subject = BanCommand {
+            wallet: WALLET.to_string(),
+            reason: "Abusive traffic".to_string(),
+        };
+
+        let result = subject.execute(&mut context);
+
+        assert_eq!(result, Ok(()));
+        let transact_params = transact_params_arc.lock().unwrap();
+        assert_eq!(
+            *transact_params,
+            vec![(
+                UiBanRequest {
+                    wallet: WALLET.to_string(),
+                    reason: "Abusive traffic".to_string(),
+                }
+                .tmb(0),
+                STANDARD_COMMAND_TIMEOUT_MILLIS
+            )]
+        );
+        assert_eq!(
+            stdout_arc.lock().unwrap().get_string(),
+            format!("Wallet {} banned\n", WALLET)
+        );
+    }
+
+    #[test]
+    fn unban_command_happy_path() {
+        let transact_params_arc = Arc::new(Mutex::new(vec![]));
+        let mut context = CommandContextMock::new()
+            .transact_params(&transact_params_arc)
+            .transact_result(Ok(UiUnbanResponse {}.tmb(0)));
+        let stdout_arc = context.stdout_arc();
+        let subject = UnbanCommand {
+            wallet: WALLET.to_string(),
+        };
+
+        let result = subject.execute(&mut context);
+
+        assert_eq!(result, Ok(()));
+        let transact_params = transact_params_arc.lock().unwrap();
+        assert_eq!(
+            *transact_params,
+            vec![(
+                UiUnbanRequest {
+                    wallet: WALLET.to_string(),
+                }
+                .tmb(0),
+                STANDARD_COMMAND_TIMEOUT_MILLIS
+            )]
+        );
+        assert_eq!(
+            stdout_arc.lock().unwrap().get_string(),
+            format!("Wallet {} unbanned\n", WALLET)
+        );
+    }
+
+    #[test]
+    fn unban_command_passes_along_node_complaints() {
+        let mut context = CommandContextMock::new().transact_result(Err(
+            ContextError::PayloadError(1234, "Wallet is not banned".to_string()),
+        ));
+        let subject = UnbanCommand {
+            wallet: WALLET.to_string(),
+        };
+
+        let result = subject.execute(&mut context);
+
+        assert_eq!(
+            result,
+            Err(Payload(1234, "Wallet is not banned".to_string()))
+        );
+    }
+
+    #[test]
+    fn ban_changed_broadcasts_are_displayed() {
+        let mut stdout = Vec::new();
+
+        BanCommand::handle_broadcast(
+            UiBanChangedBroadcast {
+                wallet: WALLET.to_string(),
+                banned: true,
+                reason_opt: Some("Delinquency".to_string()),
+            },
+            &mut stdout,
+        );
+        BanCommand::handle_broadcast(
+            UiBanChangedBroadcast {
+                wallet: WALLET.to_string(),
+                banned: false,
+                reason_opt: None,
+            },
+            &mut stdout,
+        );
+
+        assert_eq!(
+            String::from_utf8(stdout).unwrap(),
+            format!(
+                "\nWallet {} has been banned: Delinquency\n\nmasq> \nWallet {} is no longer banned.\n\nmasq> ",
+                WALLET, WALLET
+            )
+        );
+    }
+}
@@ -0,0 +1,139 @@
+// Copyright (c) 2019-2020, MASQ (https://masq.ai) and/or its affiliates. All rights reserved.
+
+use crate::command_context::CommandContext;
+use crate::commands::commands_common::{
+    transaction, Command, CommandError, STANDARD_COMMAND_TIMEOUT_MILLIS,
+};
+use clap::{App, SubCommand};
+use masq_lib::messages::{UiBannedWalletsRequest, UiBannedWalletsResponse};
+use std::any::Any;
+use std::fmt::Debug;
+
+#[derive(Debug, PartialEq)]
+pub struct BannedWalletsCommand {}
+
+pub fn banned_wallets_subcommand() -> App<'static, 'static> {
+    SubCommand::with_name("banned-wallets").about(
+        "Lists the wallets the running MASQNode has banned, along with the reason for each ban",
+    )
+}
+
+impl Command for BannedWalletsCommand {
+    fn execute(&self, context: &mut dyn CommandContext) -> Result<(), CommandError> {
+        let input = UiBannedWalletsRequest {};
+        let response: UiBannedWalletsResponse =
+            transaction(input, context, STANDARD_COMMAND_TIMEOUT_MILLIS)?;
+        if response.banned_wallets.is_empty() {
+            writeln!(context.stdout(), "No wallets are banned").expect("writeln! failed");
+        }
+        response.banned_wallets.into_iter().for_each(|banned| {
+            writeln!(
+                context.stdout(),
+                "{}  {}",
+                banned.wallet,
+                banned
+                    .reason_opt
+                    .unwrap_or_else(|| "(no reason recorded)".to_string())
+            )
+            .expect("writeln! failed")
+        });
+        Ok(())
+    }
+
+    fn as_any(&self) -> &dyn Any {
+        self
+    }
+}
+
+impl Default for BannedWalletsCommand {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl BannedWalletsCommand {
+    pub fn new() -> Self {
+        Self {}
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::command_factory::{CommandFactory, CommandFactoryReal};
+    use crate::test_utils::mocks::CommandContextMock;
+    use masq_lib::messages::{ToMessageBody, UiBannedWallet};
+    use std::sync::{Arc, Mutex};
+
+    #[test]
+    fn testing_command_factory_here() {
+        let factory = CommandFactoryReal::new();
+
+        let command = factory.make(vec!["banned-wallets".to_string()]).unwrap();
+
+        assert_eq!(
+            command
+                .as_any()
+                .downcast_ref::<BannedWalletsCommand>()
+                .unwrap(),
+            &BannedWalletsCommand {}
+        );
+    }
+
+    #[test]
+    fn banned_wallets_command_happy_path() {
+        let transact_params_arc = Arc::new(Mutex::new(vec![]));
+        let response = UiBannedWalletsResponse {
+            banned_wallets: vec![
+                UiBannedWallet {
+                    wallet: "0x0123456789012345678901234567890123456789".to_string(),
+                    reason_opt: Some("Delinquency".to_string()),
+                },
+                UiBannedWallet {
+                    wallet: "0x9876543210987654321098765432109876543210".to_string(),
+                    reason_opt: None,
+                },
+            ],
+        };
+        let mut context = CommandContextMock::new()
+            .transact_params(&transact_params_arc)
+            .transact_result(Ok(response.tmb(0)));
+        let stdout_arc = context.stdout_arc();
+        let subject = BannedWalletsCommand::new();
+
+        let result = subject.execute(&mut context);
+
+        assert_eq!(result, Ok(()));
+        let transact_params = transact_params_arc.lock().unwrap();
+        assert_eq!(
+            *transact_params,
+            vec![(
+                UiBannedWalletsRequest {}.tmb(0),
+                STANDARD_COMMAND_TIMEOUT_MILLIS
+            )]
+        );
+        assert_eq!(
+            stdout_arc.lock().unwrap().get_string(),
+            "0x0123456789012345678901234567890123456789  Delinquency\n\
+             0x9876543210987654321098765432109876543210  (no reason recorded)\n"
+        );
+    }
+
+    #[test]
+    fn banned_wallets_command_with_no_bans() {
+        let response = UiBannedWalletsResponse {
+            banned_wallets: vec![],
+        };
+        let mut context = CommandContextMock::new().transact_result(Ok(response.tmb(0)));
+        let stdout_arc = context.stdout_arc();
+        let subject = BannedWalletsCommand::new();
+
+        let result = subject.execute(&mut context);
+
+        assert_eq!(result, Ok(()));
+        assert_eq!(
+            stdout_arc.lock().unwrap().get_string(),
+            "No wallets are banned\n"
+        );
+    }
+}
@@ -0,0 +1,201 @@
+// Copyright (c) 2019-2020, MASQ (https://masq.ai) and/or its affiliates. All rights reserved.
+
+use crate::command_context::CommandContext;
+use crate::commands::commands_common::CommandError::Payload;
+use crate::commands::commands_common::{
+    transaction, Command, CommandError, STANDARD_COMMAND_TIMEOUT_MILLIS,
+};
+use clap::{App, SubCommand};
+use masq_lib::messages::{
+    UiConnectionChangeBroadcast, UiConnectionStage, UiConnectionStatusRequest,
+    UiConnectionStatusResponse, NODE_NOT_RUNNING_ERROR,
+};
+use std::fmt::Debug;
+use std::io::Write;
+
+#[derive(Debug)]
+pub struct ConnectionStatusCommand {}
+
+pub fn connection_status_subcommand() -> App<'static, 'static> {
+    SubCommand::with_name("connection-status").about(
+        "Displays how well the running MASQNode is connected to the MASQ Network. Only valid if Node is already running.",
+    )
+}
+
+fn describe(stage: UiConnectionStage) -> &'static str {
+    match stage {
+        UiConnectionStage::NotConnected => "Not connected: no neighbors are reachable",
+        UiConnectionStage::ConnectedToNeighbor => {
+            "Connected to neighbors, but no three-hop route is available yet"
+        }
+        UiConnectionStage::ThreeHopsRouteFound => {
+            "Connected: three-hop routes through the MASQ Network are available"
+        }
+    }
+}
+
+impl Command for ConnectionStatusCommand {
+    fn execute(&self, context: &mut dyn CommandContext) -> Result<(), CommandError> {
+        let input = UiConnectionStatusRequest {};
+        let output: Result<UiConnectionStatusResponse, CommandError> =
+            transaction(input, context, STANDARD_COMMAND_TIMEOUT_MILLIS);
+        match output {
+            Ok(response) => {
+                writeln!(context.stdout(), "{}", describe(response.stage)).expect("write! failed");
+                Ok(())
+            }
+            Err(Payload(code, message)) if code == NODE_NOT_RUNNING_ERROR => {
+                writeln!(
+                    context.stderr(),
+                    "MASQNode is not running; therefore its connection status cannot be displayed."
+                )
+                .expect("write! failed");
+                Err(Payload(code, message))
+            }
+            Err(e) => {
+                writeln!(
+                    context.stderr(),
+                    "Connection status retrieval failed: {:?}",
+                    e
+                )
+                .expect("write! failed");
+                Err(e)
+            }
+        }
+    }
+}
+
+impl Default for ConnectionStatusCommand {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl ConnectionStatusCommand {
+    pub fn new() -> Self {
+        Self {}
+    }
+
+    pub fn handle_broadcast(body: UiConnectionChangeBroadcast, stdout: &mut dyn Write) {
+        write!(stdout, "\n{}\n\nmasq> ", describe(body.stage)).expect("write! failed");
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::command_context::ContextError;
+    use crate::command_context::ContextError::ConnectionDropped;
+    use crate::command_factory::{CommandFactory, CommandFactoryReal};
+    use crate::commands::commands_common::CommandError::ConnectionProblem;
+    use crate::test_utils::mocks::CommandContextMock;
+    use masq_lib::messages::ToMessageBody;
+    use masq_lib::test_utils::fake_stream_holder::ByteArrayWriter;
+    use std::sync::{Arc, Mutex};
+
+    #[test]
+    fn testing_command_factory_here() {
+        let factory = CommandFactoryReal::new();
+        let mut context =
+            CommandContextMock::new().transact_result(Ok(UiConnectionStatusResponse {
+                stage: UiConnectionStage::NotConnected,
+            }
+            .tmb(0)));
+        let subject = factory.make(vec!["connection-status".to_string()]).unwrap();
+
+        let result = subject.execute(&mut context);
+
+        assert_eq!(result, Ok(()));
+    }
+
+    #[test]
+    fn doesnt_work_if_node_is_not_running() {
+        let mut context = CommandContextMock::new().transact_result(Err(
+            ContextError::PayloadError(NODE_NOT_RUNNING_ERROR, "irrelevant".to_string()),
+        ));
+        let stdout_arc = context.stdout_arc();
+        let stderr_arc = context.stderr_arc();
+        let subject = ConnectionStatusCommand::new();
+
+        let result = subject.execute(&mut context);
+
+        assert_eq!(
+            result,
+            Err(CommandError::Payload(
+                NODE_NOT_RUNNING_ERROR,
+                "irrelevant".to_string()
+            ))
+        );
+        assert_eq!(
+            stderr_arc.lock().unwrap().get_string(),
+            "MASQNode is not running; therefore its connection status cannot be displayed.\n"
+        );
+        assert_eq!(stdout_arc.lock().unwrap().get_string(), String::new());
+    }
+
+    #[test]
+    fn connection_status_command_happy_path() {
+        let transact_params_arc = Arc::new(Mutex::new(vec![]));
+        let expected_response = UiConnectionStatusResponse {
+            stage: UiConnectionStage::ConnectedToNeighbor,
+        };
+        let mut context = CommandContextMock::new()
+            .transact_params(&transact_params_arc)
+            .transact_result(Ok(expected_response.tmb(42)));
+        let stdout_arc = context.stdout_arc();
+        let stderr_arc = context.stderr_arc();
+        let subject = ConnectionStatusCommand::new();
+
+        let result = subject.execute(&mut context);
+
+        assert_eq!(result, Ok(()));
+        let transact_params = transact_params_arc.lock().unwrap();
+        assert_eq!(
+            *transact_params,
+            vec![(
+                UiConnectionStatusRequest {}.tmb(0),
+                STANDARD_COMMAND_TIMEOUT_MILLIS
+            )]
+        );
+        assert_eq!(
+            stdout_arc.lock().unwrap().get_string(),
+            "Connected to neighbors, but no three-hop route is available yet\n"
+        );
+        assert_eq!(stderr_arc.lock().unwrap().get_string(), String::new());
+    }
+
+    #[test]
+    fn connection_status_command_sad_path() {
+        let mut context =
+            CommandContextMock::new().transact_result(Err(ConnectionDropped("Booga".to_string())));
+        let stdout_arc = context.stdout_arc();
+        let stderr_arc = context.stderr_arc();
+        let subject = ConnectionStatusCommand::new();
+
+        let result = subject.execute(&mut context);
+
+        assert_eq!(result, Err(ConnectionProblem("Booga".to_string())));
+        assert_eq!(stdout_arc.lock().unwrap().get_string(), String::new());
+        assert_eq!(
+            stderr_arc.lock().unwrap().get_string(),
+            "Connection status retrieval failed: ConnectionProblem(\"Booga\")\n"
+        );
+    }
+
+    #[test]
+    fn connection_change_broadcast_is_described() {
+        let mut stdout = ByteArrayWriter::new();
+
+        ConnectionStatusCommand::handle_broadcast(
+            UiConnectionChangeBroadcast {
+                stage: UiConnectionStage::ThreeHopsRouteFound,
+            },
+            &mut stdout,
+        );
+
+        assert_eq!(
+            stdout.get_string(),
+            "\nConnected: three-hop routes through the MASQ Network are available\n\nmasq> "
+        );
+    }
+}
@@ -0,0 +1,537 @@
+// Copyright (c) 2019-2020, MASQ (https://masq.ai) and/or its affiliates. All rights reserved.
+
+use crate::command_context::CommandContext;
+use crate::commands::commands_common::{
+    transaction, Command, CommandError, STANDARD_COMMAND_TIMEOUT_MILLIS,
+};
+use crate::commands::export_accounts_command::format_time;
+use clap::{App, Arg, ArgMatches, SubCommand};
+use masq_lib::messages::{
+    UiApproveDebutRequest, UiApproveDebutResponse, UiDebutAllowListRequest,
+    UiDebutAllowListResponse, UiDebutPendingBroadcast, UiPendingDebut, UiPendingDebutsRequest,
+    UiPendingDebutsResponse, UiRejectDebutRequest, UiRejectDebutResponse,
+};
+use std::any::Any;
+use std::io::Write;
+
+#[derive(Debug, PartialEq)]
+pub struct PendingDebutsCommand {}
+
+#[derive(Debug, PartialEq)]
+pub struct ApproveDebutCommand {
+    pub public_key: String,
+}
+
+#[derive(Debug, PartialEq)]
+pub struct RejectDebutCommand {
+    pub public_key: String,
+}
+
+#[derive(Debug, PartialEq)]
+pub struct DebutAllowListCommand {
+    pub add: Vec<String>,
+    pub remove: Vec<String>,
+}
+
+pub fn pending_debuts_subcommand() -> App<'static, 'static> {
+    SubCommand::with_name("pending-debuts").about(
+        "Lists the debuts from new Nodes that the running MASQNode is holding for your approval",
+    )
+}
+
+pub fn approve_debut_subcommand() -> App<'static, 'static> {
+    SubCommand::with_name("approve-debut")
+        .about("Approves a debut that is being held for your approval")
+        .arg(public_key_arg(
+            "Public key of the debuting Node, as shown by pending-debuts",
+        ))
+}
+
+pub fn reject_debut_subcommand() -> App<'static, 'static> {
+    SubCommand::with_name("reject-debut")
+        .about("Rejects a debut that is being held for your approval")
+        .arg(public_key_arg(
+            "Public key of the debuting Node, as shown by pending-debuts",
+        ))
+}
+
+pub fn debut_allow_list_subcommand() -> App<'static, 'static> {
+    SubCommand::with_name("debut-allow-list")
+        .about(
+            "Shows, and optionally changes, the list of Nodes whose debuts the running MASQNode \
+             accepts without asking for approval",
+        )
+        .arg(
+            Arg::with_name("add")
+                .help("Public key to add to the allow-list; may be repeated")
+                .long("add")
+                .value_name("PUBLIC-KEY")
+                .number_of_values(1)
+                .multiple(true)
+                .validator(validate_public_key),
+        )
+        .arg(
+            Arg::with_name("remove")
+                .help("Public key to remove from the allow-list; may be repeated")
+                .long("remove")
+                .value_name("PUBLIC-KEY")
+                .number_of_values(1)
+                .multiple(true)
+                .validator(validate_public_key),
+        )
+}
+
+fn public_key_arg(help: &'static str) -> Arg<'static, 'static> {
+    Arg::with_name("public-key")
+        .help(help)
+        .value_name("PUBLIC-KEY")
+        .index(1)
+        .required(true)
+        .validator(validate_public_key)
+}
+
+fn validate_public_key(public_key: String) -> Result<(), String> {
+    if !public_key.is_empty()
+        && public_key
+            .chars()
+            .all(|c| c.is_ascii_alphanumeric() || c == '+' || c == '/')
+    {
+        Ok(())
+    } else {
+        Err(format!("'{}' is not a valid public key", public_key))
+    }
+}
+
+fn public_key_from(matches: &ArgMatches) -> String {
+    matches
+        .value_of("public-key")
+        .expect("public-key is not properly required")
+        .to_string()
+}
+
+fn describe(debut: UiPendingDebut) -> String {
+    format!(
+        "{}  {}  wallet {}  received {}",
+        debut.public_key,
+        debut.node_address,
+        debut.earning_wallet,
+        format_time(debut.received_at)
+    )
+}
+
+impl Default for PendingDebutsCommand {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl PendingDebutsCommand {
+    pub fn new() -> Self {
+        Self {}
+    }
+
+    pub fn handle_broadcast(body: UiDebutPendingBroadcast, stdout: &mut dyn Write) {
+        write!(
+            stdout,
+            "\nDebut awaiting your approval (use approve-debut or reject-debut):\n{}\n\nmasq> ",
+            describe(body.debut)
+        )
+        .expect("write! failed");
+    }
+}
+
+impl Command for PendingDebutsCommand {
+    fn execute(&self, context: &mut dyn CommandContext) -> Result<(), CommandError> {
+        let input = UiPendingDebutsRequest {};
+        let response: UiPendingDebutsResponse =
+            transaction(input, context, STANDARD_COMMAND_TIMEOUT_MILLIS)?;
+        if response.debuts.is_empty() {
+            writeln!(context.stdout(), "No debuts are awaiting approval").expect("writeln! failed");
+        }
+        response.debuts.into_iter().for_each(|debut| {
+            writeln!(context.stdout(), "{}", describe(debut)).expect("writeln! failed")
+        });
+        Ok(())
+    }
+
+    fn as_any(&self) -> &dyn Any {
+        self
+    }
+}
+
+impl ApproveDebutCommand {
+    pub fn new(pieces: Vec<String>) -> Result<Self, String> {
+        match approve_debut_subcommand().get_matches_from_safe(pieces) {
+            Ok(matches) => Ok(Self {
+                public_key: public_key_from(&matches),
+            }),
+            Err(e) => Err(format!("{}", e)),
+        }
+    }
+}
+
+impl Command for ApproveDebutCommand {
+    fn execute(&self, context: &mut dyn CommandContext) -> Result<(), CommandError> {
+        let input = UiApproveDebutRequest {
+            public_key: self.public_key.clone(),
+        };
+        let _: UiApproveDebutResponse =
+            transaction(input, context, STANDARD_COMMAND_TIMEOUT_MILLIS)?;
+        writeln!(context.stdout(), "Debut from {} approved", self.public_key)
+            .expect("writeln! failed");
+        Ok(())
+    }
+
+    fn as_any(&self) -> &dyn Any {
+        self
+    }
+}
+
+impl RejectDebutCommand {
+    pub fn new(pieces: Vec<String>) -> Result<Self, String> {
+        match reject_debut_subcommand().get_matches_from_safe(pieces) {
+            Ok(matches) => Ok(Self {
+                public_key: public_key_from(&matches),
+            }),
+            Err(e) => Err(format!("{}", e)),
+        }
+    }
+}
+
+impl Command for RejectDebutCommand {
+    fn execute(&self, context: &mut dyn CommandContext) -> Result<(), CommandError> {
+        let input = UiRejectDebutRequest {
+            public_key: self.public_key.clone(),
+        };
+        let _: UiRejectDebutResponse =
+            transaction(input, context, STANDARD_COMMAND_TIMEOUT_MILLIS)?;
+        writeln!(context.stdout(), "Debut from {} rejected", self.public_key)
+            .expect("writeln! failed");
+        Ok(())
+    }
+
+    fn as_any(&self) -> &dyn Any {
+        self
+    }
+}
+
+impl DebutAllowListCommand {
+    pub fn new(pieces: Vec<String>) -> Result<Self, String> {
+        let values = |matches: &ArgMatches, name: &str| -> Vec<String> {
+            match matches.values_of(name) {
+                Some(values) => values.map(|value| value.to_string()).collect(),
+                None => vec![],
+            }
+        };
+        match debut_allow_list_subcommand().get_matches_from_safe(pieces) {
+            Ok(matches) => Ok(Self {
+                add: values(&matches, "add"),
+                remove: values(&matches, "remove"),
+            }),
+            Err(e) => Err(format!("{}", e)),
+        }
+    }
+}
+
+impl Command for DebutAllowListCommand {
+    fn execute(&self, context: &mut dyn CommandContext) -> Result<(), CommandError> {
+        let input = UiDebutAllowListRequest {
+            add: self.add.clone(),
+            remove: self.remove.clone(),
+        };
+        let response: UiDebutAllowListResponse =
+            transaction(input, context, STANDARD_COMMAND_TIMEOUT_MILLIS)?;
+        if response.public_keys.is_empty() {
+            writeln!(context.stdout(), "The debut allow-list is empty").expect("writeln! failed");
+        }
+        response.public_keys.into_iter().for_each(|public_key| {
+            writeln!(context.stdout(), "{}", public_key).expect("writeln! failed")
+        });
+        Ok(())
+    }
+
+    fn as_any(&self) -> &dyn Any {
+        self
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::command_context::ContextError;
+    use crate::command_factory::{CommandFactory, CommandFactoryError, CommandFactoryReal};
+    use crate::commands::commands_common::CommandError::Payload;
+    use crate::test_utils::mocks::CommandContextMock;
+    use masq_lib::messages::ToMessageBody;
+    use std::sync::{Arc, Mutex};
+
+    fn make_pending_debut(public_key: &str) -> UiPendingDebut {
+        UiPendingDebut {
+            public_key: public_key.to_string(),
+            node_address: "1.2.3.4:1234".to_string(),
+            earning_wallet: "0x0123456789012345678901234567890123456789".to_string(),
+            received_at: 1_600_000_000,
+        }
+    }
+
+    #[test]
+    fn factory_produces_debut_commands() {
+        let factory = CommandFactoryReal::new();
+
+        let pending = factory.make(vec!["pending-debuts".to_string()]).unwrap();
+        let approve = factory
+            .make(vec!["approve-debut".to_string(), "AQIDBA".to_string()])
+            .unwrap();
+        let reject = factory
+            .make(vec!["reject-debut".to_string(), "AQIDBA".to_string()])
+            .unwrap();
+        let allow_list = factory
+            .make(vec![
+                "debut-allow-list".to_string(),
+                "--add".to_string(),
+                "AQIDBA".to_string(),
+                "--add".to_string(),
+                "BQYHCA".to_string(),
+                "--remove".to_string(),
+                "CQoLDA".to_string(),
+            ])
+            .unwrap();
+
+        assert_eq!(
+            pending
+                .as_any()
+                .downcast_ref::<PendingDebutsCommand>()
+                .unwrap(),
+            &PendingDebutsCommand {}
+        );
+        assert_eq!(
+            approve
+                .as_any()
+                .downcast_ref::<ApproveDebutCommand>()
+                .unwrap(),
+            &ApproveDebutCommand {
+                public_key: "AQIDBA".to_string()
+            }
+        );
+        assert_eq!(
+            reject
+                .as_any()
+                .downcast_ref::<RejectDebutCommand>()
+                .unwrap(),
+            &RejectDebutCommand {
+                public_key: "AQIDBA".to_string()
+            }
+        );
+        assert_eq!(
+            allow_list
+                .as_any()
+                .downcast_ref::<DebutAllowListCommand>()
+                .unwrap(),
+            &DebutAllowListCommand {
+                add: vec!["AQIDBA".to_string(), "BQYHCA".to_string()],
+                remove: vec!["CQoLDA".to_string()],
+            }
+        );
+    }
+
+    #[test]
+    fn factory_rejects_malformed_public_key() {
+        let factory = CommandFactoryReal::new();
+
+        let result = factory.make(vec!["approve-debut".to_string(), "not-a-key".to_string()]);
+
+        match result {
+            Err(CommandFactoryError::CommandSyntax(msg)) => assert_eq!(
+                msg.contains("'not-a-key' is not a valid public key"),
+                true,
+                "{}",
+                msg
+            ),
+            x => panic!("Expected CommandSyntax error, got {:?}", x.map(|_| ())),
+        }
+    }
+
+    #[test]
+    fn pending_debuts_command_happy_path() {
+        let transact_params_arc = Arc::new(Mutex::new(vec![]));
+        let response = UiPendingDebutsResponse {
+            debuts: vec![make_pending_debut("AQIDBA"), make_pending_debut("BQYHCA")],
+        };
+        let mut context = CommandContextMock::new()
+            .transact_params(&transact_params_arc)
+            .transact_result(Ok(response.tmb(0)));
+        let stdout_arc = context.stdout_arc();
+        let subject = PendingDebutsCommand::new();
+
+        let result = subject.execute(&mut context);
+
+        assert_eq!(result, Ok(()));
+        let transact_params = transact_params_arc.lock().unwrap();
+        assert_eq!(
+            *transact_params,
+            vec![(
+                UiPendingDebutsRequest {}.tmb(0),
+                STANDARD_COMMAND_TIMEOUT_MILLIS
+            )]
+        );
+        assert_eq!(
+            stdout_arc.lock().unwrap().get_string(),
+            "AQIDBA  1.2.3.4:1234  wallet 0x0123456789012345678901234567890123456789  \
+             received 2020-09-13T12:26:40Z\n\
+             BQYHCA  1.2.3.4:1234  wallet 0x0123456789012345678901234567890123456789  \
+             received 2020-09-13T12:26:40Z\n"
+        );
+    }
+
+    #[test]
+    fn pending_debuts_command_with_no_debuts() {
+        let response = UiPendingDebutsResponse { debuts: vec![] };
+        let mut context = CommandContextMock::new().transact_result(Ok(response.tmb(0)));
+        let stdout_arc = context.stdout_arc();
+        let subject = PendingDebutsCommand::new();
+
+        let result = subject.execute(&mut context);
+
+        assert_eq!(result, Ok(()));
+        assert_eq!(
+            stdout_arc.lock().unwrap().get_string(),
+            "No debuts are awaiting approval\n"
+        );
+    }
+
+    #[test]
+    fn approve_debut_command_happy_path() {
+        let transact_params_arc = Arc::new(Mutex::new(vec![]));
+        let mut context = CommandContextMock::new()
+            .transact_params(&transact_params_arc)
+            .transact_result(Ok(UiApproveDebutResponse {}.tmb(0)));
+        let stdout_arc = context.stdout_arc();
+        let subject = ApproveDebutCommand {
+            public_key: "AQIDBA".to_string(),
+        };
+
+        let result = subject.execute(&mut context);
+
+        assert_eq!(result, Ok(()));
+        let transact_params = transact_params_arc.lock().unwrap();
+        assert_eq!(
+            *transact_params,
+            vec![(
+                UiApproveDebutRequest {
+                    public_key: "AQIDBA".to_string()
+                }
+                .tmb(0),
+                STANDARD_COMMAND_TIMEOUT_MILLIS
+            )]
+        );
+        assert_eq!(
+            stdout_arc.lock().unwrap().get_string(),
+            "Debut from AQIDBA approved\n"
+        );
+    }
+
+    #[test]
+    fn reject_debut_command_happy_path() {
+        let transact_params_arc = Arc::new(Mutex::new(vec![]));
+        let mut context = CommandContextMock::new()
+            .transact_params(&transact_params_arc)
+            .transact_result(Ok(UiRejectDebutResponse {}.tmb(0)));
+        let stdout_arc = context.stdout_arc();
+        let subject = RejectDebutCommand {
+            public_key: "AQIDBA".to_string(),
+        };
+
+        let result = subject.execute(&mut context);
+
+        assert_eq!(result, Ok(()));
+        let transact_params = transact_params_arc.lock().unwrap();
+        assert_eq!(
+            *transact_params,
+            vec![(
+                UiRejectDebutRequest {
+                    public_key: "AQIDBA".to_string()
+                }
+                .tmb(0),
+                STANDARD_COMMAND_TIMEOUT_MILLIS
+            )]
+        );
+        assert_eq!(
+            stdout_arc.lock().unwrap().get_string(),
+            "Debut from AQIDBA rejected\n"
+        );
+    }
+
+    #[test]
+    fn reject_debut_command_passes_along_node_complaints() {
+        let mut context = CommandContextMock::new().transact_result(Err(
+            ContextError::PayloadError(1234, "There is no pending debut from AQIDBA".to_string()),
+        ));
+        let subject = RejectDebutCommand {
+            public_key: "AQIDBA".to_string(),
+        };
+
+        let result = subject.execute(&mut context);
+
+        assert_eq!(
+            result,
+            Err(Payload(
+                1234,
+                "There is no pending debut from AQIDBA".to_string()
+            ))
+        );
+    }
+
+    #[test]
+    fn debut_allow_list_command_happy_path() {
+        let transact_params_arc = Arc::new(Mutex::new(vec![]));
+        let response = UiDebutAllowListResponse {
+            public_keys: vec!["AQIDBA".to_string(), "BQYHCA".to_string()],
+        };
+        let mut context = CommandContextMock::new()
+            .transact_params(&transact_params_arc)
+            .transact_result(Ok(response.tmb(0)));
+        let stdout_arc = context.stdout_arc();
+        let subject = DebutAllowListCommand {
+            add: vec!["BQYHCA".to_string()],
+            remove: vec!["CQoLDA".to_string()],
+        };
+
+        let result = subject.execute(&mut context);
+
+        assert_eq!(result, Ok(()));
+        let transact_params = transact_params_arc.lock().unwrap();
+        assert_eq!(
+            *transact_params,
+            vec![(
+                UiDebutAllowListRequest {
+                    add: vec!["BQYHCA".to_string()],
+                    remove: vec!["CQoLDA".to_string()],
+                }
+                .tmb(0),
+                STANDARD_COMMAND_TIMEOUT_MILLIS
+            )]
+        );
+        assert_eq!(stdout_arc.lock().unwrap().get_string(), "AQIDBA\nBQYHCA\n");
+    }
+
+    #[test]
+    fn debut_allow_list_command_with_empty_list() {
+        let response = UiDebutAllowListResponse {
+            public_keys: vec![],
+        };
+        let mut context = CommandContextMock::new().transact_result(Ok(response.tmb(0)));
+        let stdout_arc = context.stdout_arc();
+        let subject = DebutAllowListCommand {
+            add: vec![],
+            remove: vec![],
+        };
+
+        let result = subject.execute(&mut context);
+
+        assert_eq!(result, Ok(()));
+        assert_eq!(
+            stdout_arc.lock().unwrap().get_string(),
+            "The debut allow-list is empty\n"
+        );
+    }
+}
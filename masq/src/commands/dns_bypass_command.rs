@@ -0,0 +1,223 @@
+// Copyright (c) 2019-2020, MASQ (https://masq.ai) and/or its affiliates. All rights reserved.
+
+use crate::command_context::CommandContext;
+use crate::commands::commands_common::{
+    transaction, Command, CommandError, STANDARD_COMMAND_TIMEOUT_MILLIS,
+};
+use clap::{App, Arg, SubCommand};
+use masq_lib::messages::{UiDnsBypassRequest, UiDnsBypassResponse};
+use masq_lib::shared_schema::common_validators::validate_dns_bypass_domain;
+use std::any::Any;
+use std::io::Write;
+
+#[derive(Debug, PartialEq)]
+pub struct DnsBypassCommand {
+    pub add: Vec<String>,
+    pub remove: Vec<String>,
+}
+
+pub fn dns_bypass_subcommand() -> App<'static, 'static> {
+    SubCommand::with_name("dns-bypass")
+        .about(
+            "Edits and displays the list of domains whose DNS lookups bypass the MASQ Network \
+             and are answered by your usual DNS servers. With no arguments, just displays the list. \
+             Changes last until the MASQNode shuts down.",
+        )
+        .arg(
+            Arg::with_name("add")
+                .help(
+                    "Domain to add to the list, such as corp.example.com or *.lan; \
+                     may be repeated",
+                )
+                .long("add")
+                .value_name("DOMAIN")
+                .takes_value(true)
+                .multiple(true)
+                .number_of_values(1)
+                .validator(validate_dns_bypass_domain),
+        )
+        .arg(
+            Arg::with_name("remove")
+                .help("Domain to remove from the list; may be repeated")
+                .long("remove")
+                .value_name("DOMAIN")
+                .takes_value(true)
+                .multiple(true)
+                .number_of_values(1)
+                .validator(validate_dns_bypass_domain),
+        )
+}
+
+impl DnsBypassCommand {
+    pub fn new(pieces: Vec<String>) -> Result<Self, String> {
+        match dns_bypass_subcommand().get_matches_from_safe(pieces) {
+            Ok(matches) => Ok(Self {
+                add: Self::values(&matches, "add"),
+                remove: Self::values(&matches, "remove"),
+            }),
+            Err(e) => Err(format!("{}", e)),
+        }
+    }
+
+    fn values(matches: &clap::ArgMatches, name: &str) -> Vec<String> {
+        match matches.values_of(name) {
+            Some(values) => values.map(|value| value.to_string()).collect(),
+            None => vec![],
+        }
+    }
+}
+
+impl Command for DnsBypassCommand {
+    fn execute(&self, context: &mut dyn CommandContext) -> Result<(), CommandError> {
+        let input = UiDnsBypassRequest {
+            add: self.add.clone(),
+            remove: self.remove.clone(),
+        };
+        let response: UiDnsBypassResponse =
+            transaction(input, context, STANDARD_COMMAND_TIMEOUT_MILLIS)?;
+        if response.domains.is_empty() {
+            writeln!(context.stdout(), "No domains bypass the MASQ Network")
+                .expect("writeln! failed");
+        } else {
+            writeln!(context.stdout(), "Domains that bypass the MASQ Network:")
+                .expect("writeln! failed");
+            response.domains.iter().for_each(|domain| {
+                writeln!(context.stdout(), "  {}", domain).expect("writeln! failed")
+            });
+        }
+        Ok(())
+    }
+
+    fn as_any(&self) -> &dyn Any {
+        self
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::command_context::ContextError;
+    use crate::command_factory::{CommandFactory, CommandFactoryError, CommandFactoryReal};
+    use crate::commands::commands_common::CommandError::Payload;
+    use crate::test_utils::mocks::CommandContextMock;
+    use masq_lib::messages::ToMessageBody;
+    use std::sync::{Arc, Mutex};
+
+    #[test]
+    fn factory_produces_dns_bypass_command() {
+        let factory = CommandFactoryReal::new();
+
+        let result = factory
+            .make(vec![
+                "dns-bypass".to_string(),
+                "--add".to_string(),
+                "corp.example.com".to_string(),
+                "--remove".to_string(),
+                "lan".to_string(),
+                "--add".to_string(),
+                "*.intranet".to_string(),
+            ])
+            .unwrap();
+
+        assert_eq!(
+            result.as_any().downcast_ref::<DnsBypassCommand>().unwrap(),
+            &DnsBypassCommand {
+                add: vec!["corp.example.com".to_string(), "*.intranet".to_string()],
+                remove: vec!["lan".to_string()],
+            }
+        );
+    }
+
+    #[test]
+    fn factory_rejects_malformed_domain() {
+        let factory = CommandFactoryReal::new();
+
+        let result = factory.make(vec![
+            "dns-bypass".to_string(),
+            "--add".to_string(),
+            "bad domain".to_string(),
+        ]);
+
+        match result {
+            Err(CommandFactoryError::CommandSyntax(msg)) => {
+                assert_eq!(msg.contains("bad domain"), true, "{}", msg)
+            }
+            x => panic!("Expected CommandSyntax error, got {:?}", x.map(|_| ())),
+        }
+    }
+
+    #[test]
+    fn dns_bypass_command_happy_path() {
+        let transact_params_arc = Arc::new(Mutex::new(vec![]));
+        let mut context = CommandContextMock::new()
+            .transact_params(&transact_params_arc)
+            .transact_result(Ok(UiDnsBypassResponse {
+                domains: vec!["*.intranet".to_string(), "corp.example.com".to_string()],
+            }
+            .tmb(0)));
+        let stdout_arc = context.stdout_arc();
+        let subject = DnsBypassCommand {
+            add: vec!["corp.example.com".to_string()],
+            remove: vec!["lan".to_string()],
+        };
+
+        let result = subject.execute(&mut context);
+
+        assert_eq!(result, Ok(()));
+        let transact_params = transact_params_arc.lock().unwrap();
+        assert_eq!(
+            *transact_params,
+            vec![(
+                UiDnsBypassRequest {
+                    add: vec!["corp.example.com".to_string()],
+                    remove: vec!["lan".to_string()],
+                }
+                .tmb(0),
+                STANDARD_COMMAND_TIMEOUT_MILLIS
+            )]
+        );
+        assert_eq!(
+            stdout_arc.lock().unwrap().get_string(),
+            "Domains that bypass the MASQ Network:\n  *.intranet\n  corp.example.com\n"
+        );
+    }
+
+    #[test]
+    fn dns_bypass_command_reports_empty_list() {
+        let mut context = CommandContextMock::new()
+            .transact_result(Ok(UiDnsBypassResponse { domains: vec![] }.tmb(0)));
+        let stdout_arc = context.stdout_arc();
+        let subject = DnsBypassCommand {
+            add: vec![],
+            remove: vec![],
+        };
+
+        let result = subject.execute(&mut context);
+
+        assert_eq!(result, Ok(()));
+        assert_eq!(
+            stdout_arc.lock().unwrap().get_string(),
+            "No domains bypass the MASQ Network\n"
+        );
+    }
+
+    #[test]
+    fn dns_bypass_command_sad_path() {
+        let mut context = CommandContextMock::new().transact_result(Err(
+            ContextError::PayloadError(0x0001_0000_0000_000B, "bad domain".to_string()),
+        ));
+        let stdout_arc = context.stdout_arc();
+        let subject = DnsBypassCommand {
+            add: vec!["corp".to_string()],
+            remove: vec![],
+        };
+
+        let result = subject.execute(&mut context);
+
+        assert_eq!(
+            result,
+            Err(Payload(0x0001_0000_0000_000B, "bad domain".to_string()))
+        );
+        assert_eq!(stdout_arc.lock().unwrap().get_string(), String::new());
+    }
+}
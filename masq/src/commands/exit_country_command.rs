@@ -0,0 +1,227 @@
+// Copyright (c) 2019-2020, MASQ (https://masq.ai) and/or its affiliates. All rights reserved.
+
+use crate::command_context::CommandContext;
+use crate::commands::commands_common::{
+    transaction, Command, CommandError, STANDARD_COMMAND_TIMEOUT_MILLIS,
+};
+use clap::{App, Arg, SubCommand};
+use masq_lib::messages::{UiExitCountryRequest, UiExitCountryResponse};
+use std::any::Any;
+use std::io::Write;
+
+#[derive(Debug, PartialEq)]
+pub struct ExitCountryCommand {
+    pub countries: Vec<String>,
+    pub strict: bool,
+}
+
+pub fn exit_country_subcommand() -> App<'static, 'static> {
+    SubCommand::with_name("exit-country")
+        .about(
+            "Chooses the countries where the running MASQNode's traffic leaves the MASQ Network. \
+             With no countries, traffic may leave the Network anywhere.",
+        )
+        .arg(
+            Arg::with_name("country")
+                .help("Two-letter ISO 3166 country code, such as CZ; may be repeated")
+                .value_name("COUNTRY")
+                .index(1)
+                .multiple(true)
+                .validator(validate_country),
+        )
+        .arg(
+            Arg::with_name("prefer")
+                .help(
+                    "Prefer exit Nodes in these countries, but use others when none of them \
+                     can be reached",
+                )
+                .long("prefer")
+                .takes_value(false),
+        )
+}
+
+fn validate_country(country: String) -> Result<(), String> {
+    if country.len() == 2 && country.chars().all(|c| c.is_ascii_alphabetic()) {
+        Ok(())
+    } else {
+        Err(format!("'{}' is not a two-letter country code", country))
+    }
+}
+
+impl ExitCountryCommand {
+    pub fn new(pieces: Vec<String>) -> Result<Self, String> {
+        match exit_country_subcommand().get_matches_from_safe(pieces) {
+            Ok(matches) => Ok(Self {
+                countries: match matches.values_of("country") {
+                    Some(values) => values.map(|value| value.to_uppercase()).collect(),
+                    None => vec![],
+                },
+                strict: !matches.is_present("prefer"),
+            }),
+            Err(e) => Err(format!("{}", e)),
+        }
+    }
+}
+
+impl Command for ExitCountryCommand {
+    fn execute(&self, context: &mut dyn CommandContext) -> Result<(), CommandError> {
+        let input = UiExitCountryRequest {
+            countries: self.countries.clone(),
+            strict: self.strict,
+        };
+        let _: UiExitCountryResponse =
+            transaction(input, context, STANDARD_COMMAND_TIMEOUT_MILLIS)?;
+        let message = if self.countries.is_empty() {
+            "Traffic may now leave the MASQ Network in any country".to_string()
+        } else if self.strict {
+            format!(
+                "Traffic will now leave the MASQ Network only in {}",
+                self.countries.join(", ")
+            )
+        } else {
+            format!(
+                "Traffic will now leave the MASQ Network in {} when possible",
+                self.countries.join(", ")
+            )
+        };
+        writeln!(context.stdout(), "{}", message).expect("writeln! failed");
+        Ok(())
+    }
+
+    fn as_any(&self) -> &dyn Any {
+        self
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::command_context::ContextError;
+    use crate::command_factory::{CommandFactory, CommandFactoryError, CommandFactoryReal};
+    use crate::commands::commands_common::CommandError::Payload;
+    use crate::test_utils::mocks::CommandContextMock;
+    use masq_lib::messages::ToMessageBody;
+    use std::sync::{Arc, Mutex};
+
+    #[test]
+    fn factory_produces_exit_country_command() {
+        let factory = CommandFactoryReal::new();
+
+        let result = factory
+            .make(vec![
+                "exit-country".to_string(),
+                "cz".to_string(),
+                "SK".to_string(),
+                "--prefer".to_string(),
+            ])
+            .unwrap();
+
+        assert_eq!(
+            result
+                .as_any()
+                .downcast_ref::<ExitCountryCommand>()
+                .unwrap(),
+            &ExitCountryCommand {
+                countries: vec!["CZ".to_string(), "SK".to_string()],
+                strict: false,
+            }
+        );
+    }
+
+    #[test]
+    fn factory_rejects_malformed_country() {
+        let factory = CommandFactoryReal::new();
+
+        let result = factory.make(vec!["exit-country".to_string(), "CZE".to_string()]);
+
+        match result {
+            Err(CommandFactoryError::CommandSyntax(msg)) => assert_eq!(
+                msg.contains("'CZE' is not a two-letter country code"),
+                true,
+                "{}",
+                msg
+            ),
+            x => panic!("Expected CommandSyntax error, got {:?}", x.map(|_| ())),
+        }
+    }
+
+    #[test]
+    fn exit_country_command_happy_path() {
+        let transact_params_arc = Arc::new(Mutex::new(vec![]));
+        let mut context = CommandContextMock::new()
+            .transact_params(&transact_params_arc)
+            .transact_result(Ok(UiExitCountryResponse {}.tmb(0)));
+        let stdout_arc = context.stdout_arc();
+        let subject = ExitCountryCommand {
+            countries: vec!["CZ".to_string(), "SK".to_string()],
+            strict: true,
+        };
+
+        let result = subject.execute(&mut context);
+
+        assert_eq!(result, Ok(()));
+        let transact_params = transact_params_arc.lock().unwrap();
+        assert_eq!(
+            *transact_params,
+            vec![(
+                UiExitCountryRequest {
+                    countries: vec!["CZ".to_string(), "SK".to_string()],
+                    strict: true,
+                }
+                .tmb(0),
+                STANDARD_COMMAND_TIMEOUT_MILLIS
+            )]
+        );
+        assert_eq!(
+            stdout_arc.lock().unwrap().get_string(),
+            "Traffic will now leave the MASQ Network only in CZ, SK\n"
+        );
+    }
+
+    #[test]
+    fn exit_country_command_describes_preference_and_clearing() {
+        let mut context = CommandContextMock::new()
+            .transact_result(Ok(UiExitCountryResponse {}.tmb(0)))
+            .transact_result(Ok(UiExitCountryResponse {}.tmb(0)));
+        let stdout_arc = context.stdout_arc();
+
+        ExitCountryCommand {
+            countries: vec!["DE".to_string()],
+            strict: false,
+        }
+        .execute(&mut context)
+        .unwrap();
+        ExitCountryCommand {
+            countries: vec![],
+            strict: true,
+        }
+        .execute(&mut context)
+        .unwrap();
+
+        assert_eq!(
+            stdout_arc.lock().unwrap().get_string(),
+            "Traffic will now leave the MASQ Network in DE when possible\n\
+             Traffic may now leave the MASQ Network in any country\n"
+        );
+    }
+
+    #[test]
+    fn exit_country_command_sad_path() {
+        let mut context = CommandContextMock::new().transact_result(Err(
+            ContextError::PayloadError(0x0004_0000_0000_0004, "bad country".to_string()),
+        ));
+        let stdout_arc = context.stdout_arc();
+        let subject = ExitCountryCommand {
+            countries: vec!["XX".to_string()],
+            strict: true,
+        };
+
+        let result = subject.execute(&mut context);
+
+        assert_eq!(
+            result,
+            Err(Payload(0x0004_0000_0000_0004, "bad country".to_string()))
+        );
+        assert_eq!(stdout_arc.lock().unwrap().get_string(), String::new());
+    }
+}
@@ -0,0 +1,431 @@
+// Copyright (c) 2019-2020, MASQ (https://masq.ai) and/or its affiliates. All rights reserved.
+
+use crate::command_context::CommandContext;
+use crate::commands::commands_common::{transaction, Command, CommandError};
+use chrono::{Datelike, NaiveDate, NaiveDateTime};
+use clap::{App, Arg, SubCommand};
+use masq_lib::messages::{UiExportAccountsRequest, UiExportAccountsResponse, UiExportedAccount};
+use serde_json::json;
+use std::any::Any;
+use std::fmt::Debug;
+
+// Exports of large databases can take a while to assemble
+const EXPORT_ACCOUNTS_TIMEOUT_MILLIS: u64 = 30000;
+
+#[derive(Debug, PartialEq, Clone, Copy)]
+pub enum ExportFormat {
+    Csv,
+    Json,
+}
+
+#[derive(Debug, PartialEq)]
+pub struct ExportAccountsCommand {
+    pub format: ExportFormat,
+    pub since_opt: Option<u64>,
+}
+
+pub fn export_accounts_subcommand() -> App<'static, 'static> {
+    SubCommand::with_name("export-accounts")
+        .about("Writes every payable and receivable account of the running MASQNode to standard output, with \
+        balances, times of last transaction, pending transactions and ban status, for bookkeeping. Banned \
+        wallets with no account to export are listed too. Balances are in Gwub; times are in UTC.")
+        .arg(
+            Arg::with_name("format")
+                .help("Format of the export")
+                .long("format")
+                .value_name("FORMAT")
+                .possible_values(&["csv", "json"])
+                .case_insensitive(true)
+                .default_value("csv"),
+        )
+        .arg(
+            Arg::with_name("since")
+                .help("Only export accounts with transactions on or after this date (YYYY-MM-DD, UTC)")
+                .long("since")
+                .value_name("DATE")
+                .validator(validate_date),
+        )
+}
+
+fn validate_date(date: String) -> Result<(), String> {
+    match parse_date(&date) {
+        Some(_) => Ok(()),
+        None => Err(format!("'{}' is not a date in the form YYYY-MM-DD", date)),
+    }
+}
+
+impl Command for ExportAccountsCommand {
+    fn execute(&self, context: &mut dyn CommandContext) -> Result<(), CommandError> {
+        let input = UiExportAccountsRequest {
+            since_opt: self.since_opt,
+        };
+        let response: UiExportAccountsResponse =
+            transaction(input, context, EXPORT_ACCOUNTS_TIMEOUT_MILLIS)?;
+        let output = match self.format {
+            ExportFormat::Csv => Self::to_csv(&response),
+            ExportFormat::Json => Self::to_json(&response),
+        };
+        write!(context.stdout(), "{}", output).expect("write! failed");
+        Ok(())
+    }
+
+    fn as_any(&self) -> &dyn Any {
+        self
+    }
+}
+
+impl ExportAccountsCommand {
+    pub fn new(pieces: Vec<String>) -> Result<Self, String> {
+        let matches = match export_accounts_subcommand().get_matches_from_safe(pieces) {
+            Ok(matches) => matches,
+            Err(e) => return Err(format!("{}", e)),
+        };
+        let format = match matches
+            .value_of("format")
+            .expect("format is not properly defaulted")
+            .to_lowercase()
+            .as_str()
+        {
+            "json" => ExportFormat::Json,
+            _ => ExportFormat::Csv,
+        };
+        let since_opt = matches.value_of("since").map(|date| {
+            parse_date(date).expect("since parameter is not properly validated") as u64
+        });
+        Ok(Self { format, since_opt })
+    }
+
+    fn to_csv(response: &UiExportAccountsResponse) -> String {
+        let mut csv =
+            "type,wallet,balance_gwub,last_transaction,pending_transaction,banned,ban_reason\n"
+                .to_string();
+        let rows = Self::tagged_accounts(response).map(|(account_type, account)| {
+            format!(
+                "{},{},{},{},{},{},{}\n",
+                account_type,
+                account.wallet,
+                account.balance_gwub,
+                format_time(account.last_transaction_time),
+                account
+                    .pending_transaction_opt
+                    .as_ref()
+                    .map(|tx| tx.as_str())
+                    .unwrap_or(""),
+                account.banned,
+                csv_field(&account.ban_reason_opt)
+            )
+        });
+        csv.extend(rows);
+        let banned_rows = response.banned_wallets.iter().map(|banned| {
+            format!(
+                "banned,{},,,,true,{}\n",
+                banned.wallet,
+                csv_field(&banned.reason_opt)
+            )
+        });
+        csv.extend(banned_rows);
+        csv
+    }
+
+    fn to_json(response: &UiExportAccountsResponse) -> String {
+        let mut accounts = Self::tagged_accounts(response)
+            .map(|(account_type, account)| {
+                json!({
+                    "type": account_type,
+                    "wallet": account.wallet,
+                    "balanceGwub": account.balance_gwub,
+                    "lastTransaction": format_time(account.last_transaction_time),
+                    "pendingTransaction": account.pending_transaction_opt,
+                    "banned": account.banned,
+                    "banReason": account.ban_reason_opt,
+                })
+            })
+            .collect::<Vec<_>>();
+        accounts.extend(response.banned_wallets.iter().map(|banned| {
+            json!({
+                "type": "banned",
+                "wallet": banned.wallet,
+                "balanceGwub": null,
+                "lastTransaction": null,
+                "pendingTransaction": null,
+                "banned": true,
+                "banReason": banned.reason_opt,
+            })
+        }));
+        let mut json = serde_json::to_string_pretty(&accounts).expect("JSON serialization failed");
+        json.push('\n');
+        json
+    }
+
+    fn tagged_accounts(
+        response: &UiExportAccountsResponse,
+    ) -> impl Iterator<Item = (&'static str, &UiExportedAccount)> + '_ {
+        response
+            .payables
+            .iter()
+            .map(|account| ("payable", account))
+            .chain(
+                response
+                    .receivables
+                    .iter()
+                    .map(|account| ("receivable", account)),
+            )
+    }
+}
+
+// Ban reasons are free text, so they're quoted whenever they could break the row apart
+fn csv_field(value_opt: &Option<String>) -> String {
+    match value_opt {
+        None => String::new(),
+        Some(value) if value.contains(|c: char| c == ',' || c == '"' || c == '\n' || c == '\r') => {
+            format!("\"{}\"", value.replace('"', "\"\""))
+        }
+        Some(value) => value.clone(),
+    }
+}
+
+// Seconds since the Unix epoch at midnight UTC of the given YYYY-MM-DD date
+fn parse_date(date: &str) -> Option<i64> {
+    let date = NaiveDate::parse_from_str(date, "%Y-%m-%d").ok()?;
+    if date.year() < 1970 {
+        return None;
+    }
+    Some(date.and_hms(0, 0, 0).timestamp())
+}
+
+pub fn format_time(seconds: u64) -> String {
+    match NaiveDateTime::from_timestamp_opt(seconds as i64, 0) {
+        Some(time) => time.format("%Y-%m-%dT%H:%M:%SZ").to_string(),
+        None => seconds.to_string(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::command_context::ContextError;
+    use crate::command_factory::{CommandFactory, CommandFactoryError, CommandFactoryReal};
+    use crate::commands::commands_common::CommandError::Payload;
+    use crate::test_utils::mocks::CommandContextMock;
+    use masq_lib::messages::{ToMessageBody, UiBannedWallet};
+    use std::sync::{Arc, Mutex};
+
+    fn make_response() -> UiExportAccountsResponse {
+        UiExportAccountsResponse {
+            payables: vec![UiExportedAccount {
+                wallet: "0x0123456789012345678901234567890123456789".to_string(),
+                balance_gwub: 1234,
+                last_transaction_time: 1_600_000_000,
+                pending_transaction_opt: Some("0x7B".to_string()),
+                banned: false,
+                ban_reason_opt: None,
+            }],
+            receivables: vec![UiExportedAccount {
+                wallet: "0x9876543210987654321098765432109876543210".to_string(),
+                balance_gwub: -56,
+                last_transaction_time: 0,
+                pending_transaction_opt: None,
+                banned: true,
+                ban_reason_opt: Some("Delinquency".to_string()),
+            }],
+            banned_wallets: vec![UiBannedWallet {
+                wallet: "0x5555555555555555555555555555555555555555".to_string(),
+                reason_opt: Some("Stiffed me, twice".to_string()),
+            }],
+        }
+    }
+
+    #[test]
+    fn testing_command_factory_here() {
+        let factory = CommandFactoryReal::new();
+
+        let command = factory
+            .make(vec![
+                "export-accounts".to_string(),
+                "--format".to_string(),
+                "JSON".to_string(),
+                "--since".to_string(),
+                "2020-09-13".to_string(),
+            ])
+            .unwrap();
+
+        assert_eq!(
+            command
+                .as_any()
+                .downcast_ref::<ExportAccountsCommand>()
+                .unwrap(),
+            &ExportAccountsCommand {
+                format: ExportFormat::Json,
+                since_opt: Some(1_599_955_200),
+            }
+        );
+    }
+
+    #[test]
+    fn command_factory_defaults_to_csv_for_all_time() {
+        let factory = CommandFactoryReal::new();
+
+        let command = factory.make(vec!["export-accounts".to_string()]).unwrap();
+
+        assert_eq!(
+            command
+                .as_any()
+                .downcast_ref::<ExportAccountsCommand>()
+                .unwrap(),
+            &ExportAccountsCommand {
+                format: ExportFormat::Csv,
+                since_opt: None,
+            }
+        );
+    }
+
+    #[test]
+    fn command_factory_rejects_bad_date() {
+        let factory = CommandFactoryReal::new();
+
+        let result = factory.make(vec![
+            "export-accounts".to_string(),
+            "--since".to_string(),
+            "2021-02-29".to_string(),
+        ]);
+
+        match result {
+            Err(CommandFactoryError::CommandSyntax(msg)) => assert_eq!(
+                msg.contains("'2021-02-29' is not a date in the form YYYY-MM-DD"),
+                true,
+                "{}",
+                msg
+            ),
+            x => panic!("Expected CommandSyntax error, got {:?}", x.map(|_| ())),
+        }
+    }
+
+    #[test]
+    fn dates_are_converted_in_both_directions() {
+        assert_eq!(parse_date("1970-01-01"), Some(0));
+        assert_eq!(parse_date("2000-02-29"), Some(951_782_400));
+        assert_eq!(parse_date("2020-09-13"), Some(1_599_955_200));
+        assert_eq!(parse_date("1900-03-01"), None);
+        assert_eq!(parse_date("2020-13-01"), None);
+        assert_eq!(parse_date("2021-02-29"), None);
+        assert_eq!(parse_date("2020-09"), None);
+        assert_eq!(parse_date("booga"), None);
+        assert_eq!(format_time(0), "1970-01-01T00:00:00Z".to_string());
+        assert_eq!(format_time(951_782_400), "2000-02-29T00:00:00Z".to_string());
+        assert_eq!(
+            format_time(1_600_000_000),
+            "2020-09-13T12:26:40Z".to_string()
+        );
+    }
+
+    #[test]
+    fn csv_fields_are_quoted_when_they_could_break_the_row() {
+        assert_eq!(csv_field(&None), "".to_string());
+        assert_eq!(csv_field(&Some("plain".to_string())), "plain".to_string());
+        assert_eq!(csv_field(&Some("a,b".to_string())), "\"a,b\"".to_string());
+        assert_eq!(
+            csv_field(&Some("say \"hi\"".to_string())),
+            "\"say \"\"hi\"\"\"".to_string()
+        );
+        assert_eq!(csv_field(&Some("a\nb".to_string())), "\"a\nb\"".to_string());
+        assert_eq!(csv_field(&Some("a\rb".to_string())), "\"a\rb\"".to_string());
+    }
+
+    #[test]
+    fn export_accounts_command_produces_csv() {
+        let transact_params_arc = Arc::new(Mutex::new(vec![]));
+        let mut context = CommandContextMock::new()
+            .transact_params(&transact_params_arc)
+            .transact_result(Ok(make_response().tmb(0)));
+        let stdout_arc = context.stdout_arc();
+        let subject = ExportAccountsCommand {
+            format: ExportFormat::Csv,
+            since_opt: Some(1_599_955_200),
+        };
+
+        let result = subject.execute(&mut context);
+
+        assert_eq!(result, Ok(()));
+        let transact_params = transact_params_arc.lock().unwrap();
+        assert_eq!(
+            *transact_params,
+            vec![(
+                UiExportAccountsRequest {
+                    since_opt: Some(1_599_955_200)
+                }
+                .tmb(0),
+                EXPORT_ACCOUNTS_TIMEOUT_MILLIS
+            )]
+        );
+        assert_eq!(
+            stdout_arc.lock().unwrap().get_string(),
+            "type,wallet,balance_gwub,last_transaction,pending_transaction,banned,ban_reason\n\
+             payable,0x0123456789012345678901234567890123456789,1234,2020-09-13T12:26:40Z,0x7B,false,\n\
+             receivable,0x9876543210987654321098765432109876543210,-56,1970-01-01T00:00:00Z,,true,Delinquency\n\
+             banned,0x5555555555555555555555555555555555555555,,,,true,\"Stiffed me, twice\"\n"
+        );
+    }
+
+    #[test]
+    fn export_accounts_command_produces_json() {
+        let mut context = CommandContextMock::new().transact_result(Ok(make_response().tmb(0)));
+        let stdout_arc = context.stdout_arc();
+        let subject = ExportAccountsCommand {
+            format: ExportFormat::Json,
+            since_opt: None,
+        };
+
+        let result = subject.execute(&mut context);
+
+        assert_eq!(result, Ok(()));
+        let output = stdout_arc.lock().unwrap().get_string();
+        let parsed: serde_json::Value = serde_json::from_str(&output).unwrap();
+        assert_eq!(
+            parsed,
+            json!([
+                {
+                    "type": "payable",
+                    "wallet": "0x0123456789012345678901234567890123456789",
+                    "balanceGwub": 1234,
+                    "lastTransaction": "2020-09-13T12:26:40Z",
+                    "pendingTransaction": "0x7B",
+                    "banned": false,
+                    "banReason": null
+                },
+                {
+                    "type": "receivable",
+                    "wallet": "0x9876543210987654321098765432109876543210",
+                    "balanceGwub": -56,
+                    "lastTransaction": "1970-01-01T00:00:00Z",
+                    "pendingTransaction": null,
+                    "banned": true,
+                    "banReason": "Delinquency"
+                },
+                {
+                    "type": "banned",
+                    "wallet": "0x5555555555555555555555555555555555555555",
+                    "balanceGwub": null,
+                    "lastTransaction": null,
+                    "pendingTransaction": null,
+                    "banned": true,
+                    "banReason": "Stiffed me, twice"
+                }
+            ])
+        );
+    }
+
+    #[test]
+    fn export_accounts_command_passes_along_node_complaints() {
+        let mut context = CommandContextMock::new().transact_result(Err(
+            ContextError::PayloadError(1234, "Node is unhappy".to_string()),
+        ));
+        let subject = ExportAccountsCommand {
+            format: ExportFormat::Csv,
+            since_opt: None,
+        };
+
+        let result = subject.execute(&mut context);
+
+        assert_eq!(result, Err(Payload(1234, "Node is unhappy".to_string())));
+    }
+}
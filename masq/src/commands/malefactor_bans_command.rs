@@ -0,0 +1,295 @@
+// Copyright (c) 2019-2020, MASQ (https://masq.ai) and/or its affiliates. All rights reserved.
+
+use crate::command_context::CommandContext;
+use crate::commands::commands_common::{
+    transaction, Command, CommandError, STANDARD_COMMAND_TIMEOUT_MILLIS,
+};
+use crate::commands::export_accounts_command::format_time;
+use clap::{App, Arg, SubCommand};
+use masq_lib::messages::{
+    UiMalefactorBan, UiMalefactorBansRequest, UiMalefactorBansResponse, UiMalefactorUnbanRequest,
+    UiMalefactorUnbanResponse,
+};
+use std::any::Any;
+use std::str::FromStr;
+
+#[derive(Debug, PartialEq)]
+pub struct MalefactorBansCommand {}
+
+#[derive(Debug, PartialEq)]
+pub struct MalefactorUnbanCommand {
+    pub id: u64,
+}
+
+pub fn malefactor_bans_subcommand() -> App<'static, 'static> {
+    SubCommand::with_name("malefactor-bans").about(
+        "Lists the Nodes the running MASQNode has banned for sending evil Gossip, \
+         with the ID you need to lift each ban",
+    )
+}
+
+pub fn malefactor_unban_subcommand() -> App<'static, 'static> {
+    SubCommand::with_name("malefactor-unban")
+        .about("Lifts a malefactor ban before it expires")
+        .arg(
+            Arg::with_name("id")
+                .help("ID of the ban to lift, as shown by malefactor-bans")
+                .value_name("ID")
+                .index(1)
+                .required(true)
+                .validator(validate_id),
+        )
+}
+
+fn validate_id(id: String) -> Result<(), String> {
+    match u64::from_str(&id) {
+        Ok(_) => Ok(()),
+        Err(_) => Err(format!("'{}' is not a valid ban ID", id)),
+    }
+}
+
+impl Default for MalefactorBansCommand {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl MalefactorBansCommand {
+    pub fn new() -> Self {
+        Self {}
+    }
+
+    fn describe(ban: UiMalefactorBan) -> String {
+        format!(
+            "{:>5}  {}  key {}  wallet {}  banned {}  expires {}  {}",
+            ban.id,
+            ban.ip_address_opt.unwrap_or_else(|| "-".to_string()),
+            ban.public_key_opt.unwrap_or_else(|| "-".to_string()),
+            ban.earning_wallet_opt.unwrap_or_else(|| "-".to_string()),
+            format_time(ban.banned_at),
+            ban.expires_at_opt
+                .map(format_time)
+                .unwrap_or_else(|| "never".to_string()),
+            ban.reason
+        )
+    }
+}
+
+impl Command for MalefactorBansCommand {
+    fn execute(&self, context: &mut dyn CommandContext) -> Result<(), CommandError> {
+        let input = UiMalefactorBansRequest {};
+        let response: UiMalefactorBansResponse =
+            transaction(input, context, STANDARD_COMMAND_TIMEOUT_MILLIS)?;
+        if response.bans.is_empty() {
+            writeln!(context.stdout(), "No malefactors are banned").expect("writeln! failed");
+        }
+        response.bans.into_iter().for_each(|ban| {
+            writeln!(context.stdout(), "{}", Self::describe(ban)).expect("writeln! failed")
+        });
+        Ok(())
+    }
+
+    fn as_any(&self) -> &dyn Any {
+        self
+    }
+}
+
+impl MalefactorUnbanCommand {
+    pub fn new(pieces: Vec<String>) -> Result<Self, String> {
+        match malefactor_unban_subcommand().get_matches_from_safe(pieces) {
+            Ok(matches) => Ok(Self {
+                id: u64::from_str(matches.value_of("id").expect("id is not properly required"))
+                    .expect("id is not properly validated"),
+            }),
+            Err(e) => Err(format!("{}", e)),
+        }
+    }
+}
+
+impl Command for MalefactorUnbanCommand {
+    fn execute(&self, context: &mut dyn CommandContext) -> Result<(), CommandError> {
+        let input = UiMalefactorUnbanRequest { id: self.id };
+        let _: UiMalefactorUnbanResponse =
+            transaction(input, context, STANDARD_COMMAND_TIMEOUT_MILLIS)?;
+        writeln!(context.stdout(), "Malefactor ban {} lifted", self.id).expect("writeln! failed");
+        Ok(())
+    }
+
+    fn as_any(&self) -> &dyn Any {
+        self
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::command_context::ContextError;
+    use crate::command_factory::{CommandFactory, CommandFactoryError, CommandFactoryReal};
+    use crate::commands::commands_common::CommandError::Payload;
+    use crate::test_utils::mocks::CommandContextMock;
+    use masq_lib::messages::ToMessageBody;
+    use std::sync::{Arc, Mutex};
+
+    #[test]
+    fn factory_produces_malefactor_bans() {
+        let factory = CommandFactoryReal::new();
+
+        let command = factory.make(vec!["malefactor-bans".to_string()]).unwrap();
+
+        assert_eq!(
+            command
+                .as_any()
+                .downcast_ref::<MalefactorBansCommand>()
+                .unwrap(),
+            &MalefactorBansCommand {}
+        );
+    }
+
+    #[test]
+    fn factory_produces_malefactor_unban() {
+        let factory = CommandFactoryReal::new();
+
+        let command = factory
+            .make(vec!["malefactor-unban".to_string(), "42".to_string()])
+            .unwrap();
+
+        assert_eq!(
+            command
+                .as_any()
+                .downcast_ref::<MalefactorUnbanCommand>()
+                .unwrap(),
+            &MalefactorUnbanCommand { id: 42 }
+        );
+    }
+
+    #[test]
+    fn factory_rejects_malformed_ban_id() {
+        let factory = CommandFactoryReal::new();
+
+        let result = factory.make(vec!["malefactor-unban".to_string(), "booga".to_string()]);
+
+        match result {
+            Err(CommandFactoryError::CommandSyntax(msg)) => {
+                assert_eq!(
+                    msg.contains("'booga' is not a valid ban ID"),
+                    true,
+                    "{}",
+                    msg
+                )
+            }
+            x => panic!("Expected CommandSyntax error, got {:?}", x.map(|_| ())),
+        }
+    }
+
+    #[test]
+    fn malefactor_bans_command_happy_path() {
+        let transact_params_arc = Arc::new(Mutex::new(vec![]));
+        let response = UiMalefactorBansResponse {
+            bans: vec![
+                UiMalefactorBan {
+                    id: 3,
+                    public_key_opt: Some("AQIDBA".to_string()),
+                    ip_address_opt: Some("1.2.3.4".to_string()),
+                    earning_wallet_opt: Some(
+                        "0x0123456789012345678901234567890123456789".to_string(),
+                    ),
+                    reason: "Bad Gossip".to_string(),
+                    banned_at: 1_600_000_000,
+                    expires_at_opt: Some(1_600_086_400),
+                },
+                UiMalefactorBan {
+                    id: 12,
+                    public_key_opt: None,
+                    ip_address_opt: Some("5.6.7.8".to_string()),
+                    earning_wallet_opt: None,
+                    reason: "Worse Gossip".to_string(),
+                    banned_at: 1_600_000_000,
+                    expires_at_opt: None,
+                },
+            ],
+        };
+        let mut context = CommandContextMock::new()
+            .transact_params(&transact_params_arc)
+            .transact_result(Ok(response.tmb(0)));
+        let stdout_arc = context.stdout_arc();
+        let subject = MalefactorBansCommand::new();
+
+        let result = subject.execute(&mut context);
+
+        assert_eq!(result, Ok(()));
+        let transact_params = transact_params_arc.lock().unwrap();
+        assert_eq!(
+            *transact_params,
+            vec![(
+                UiMalefactorBansRequest {}.tmb(0),
+                STANDARD_COMMAND_TIMEOUT_MILLIS
+            )]
+        );
+        assert_eq!(
+            stdout_arc.lock().unwrap().get_string(),
+            "    3  1.2.3.4  key AQIDBA  wallet 0x0123456789012345678901234567890123456789  \
+             banned 2020-09-13T12:26:40Z  expires 2020-09-14T12:26:40Z  Bad Gossip\n   \
+             12  5.6.7.8  key -  wallet -  banned 2020-09-13T12:26:40Z  expires never  Worse Gossip\n"
+        );
+    }
+
+    #[test]
+    fn malefactor_bans_command_with_no_bans() {
+        let response = UiMalefactorBansResponse { bans: vec![] };
+        let mut context = CommandContextMock::new().transact_result(Ok(response.tmb(0)));
+        let stdout_arc = context.stdout_arc();
+        let subject = MalefactorBansCommand::new();
+
+        let result = subject.execute(&mut context);
+
+        assert_eq!(result, Ok(()));
+        assert_eq!(
+            stdout_arc.lock().unwrap().get_string(),
+            "No malefactors are banned\n"
+        );
+    }
+
+    #[test]
+    fn malefactor_unban_command_happy_path() {
+        let transact_params_arc = Arc::new(Mutex::new(vec![]));
+        let mut context = CommandContextMock::new()
+            .transact_params(&transact_params_arc)
+            .transact_result(Ok(UiMalefactorUnbanResponse {}.tmb(0)));
+        let stdout_arc = context.stdout_arc();
+        let subject = MalefactorUnbanCommand { id: 42 };
+
+        let result = subject.execute(&mut context);
+
+        assert_eq!(result, Ok(()));
+        let transact_params = transact_params_arc.lock().unwrap();
+        assert_eq!(
+            *transact_params,
+            vec![(
+                UiMalefactorUnbanRequest { id: 42 }.tmb(0),
+                STANDARD_COMMAND_TIMEOUT_MILLIS
+            )]
+        );
+        assert_eq!(
+            stdout_arc.lock().unwrap().get_string(),
+            "Malefactor ban 42 lifted\n"
+        );
+    }
+
+    #[test]
+    fn malefactor_unban_command_passes_along_node_complaints() {
+        let mut context = CommandContextMock::new().transact_result(Err(
+            ContextError::PayloadError(1234, "There is no malefactor ban with ID 42".to_string()),
+        ));
+        let subject = MalefactorUnbanCommand { id: 42 };
+
+        let result = subject.execute(&mut context);
+
+        assert_eq!(
+            result,
+            Err(Payload(
+                1234,
+                "There is no malefactor ban with ID 42".to_string()
+            ))
+        );
+    }
+}
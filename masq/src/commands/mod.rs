@@ -1,13 +1,24 @@
 // Copyright (c) 2019-2020, MASQ (https://masq.ai) and/or its affiliates. All rights reserved.
 
+pub mod ban_command;
+pub mod banned_wallets_command;
 pub mod change_password_command;
 pub mod check_password_command;
 pub mod commands_common;
+pub mod connection_status_command;
 pub mod crash_command;
+pub mod debut_command;
 pub mod descriptor_command;
+pub mod dns_bypass_command;
+pub mod exit_country_command;
+pub mod export_accounts_command;
 pub mod generate_wallets_command;
+pub mod malefactor_bans_command;
 pub mod recover_wallets_command;
+pub mod scan_command;
 pub mod setup_command;
 pub mod shutdown_command;
 pub mod start_command;
+pub mod status_command;
 pub mod wallet_addresses;
+pub mod wallet_balances_command;
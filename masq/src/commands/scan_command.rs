@@ -0,0 +1,230 @@
+// Copyright (c) 2019-2020, MASQ (https://masq.ai) and/or its affiliates. All rights reserved.
+
+use crate::command_context::CommandContext;
+use crate::commands::commands_common::{
+    transaction, Command, CommandError, STANDARD_COMMAND_TIMEOUT_MILLIS,
+};
+use clap::{App, Arg, SubCommand};
+use masq_lib::messages::{ScanType, UiScanRequest, UiScanResponse, UiScanResultsBroadcast};
+use std::any::Any;
+use std::fmt::Debug;
+use std::io::Write;
+
+#[derive(Debug, PartialEq)]
+pub struct ScanCommand {
+    pub scan_type: ScanType,
+}
+
+pub fn scan_subcommand() -> App<'static, 'static> {
+    SubCommand::with_name("scan")
+        .about("Makes the running MASQNode scan immediately, instead of waiting for its next scheduled scan. \
+        A payables scan pays debts that are due; a receivables scan looks for incoming payments and \
+        bans or unbans delinquent wallets. Results arrive later as a broadcast.")
+        .arg(
+            Arg::with_name("type")
+                .help("Which kind of scan to run")
+                .value_name("TYPE")
+                .index(1)
+                .possible_values(&["payables", "receivables"])
+                .case_insensitive(true)
+                .required(true),
+        )
+}
+
+impl Command for ScanCommand {
+    fn execute(&self, context: &mut dyn CommandContext) -> Result<(), CommandError> {
+        let input = UiScanRequest {
+            scan_type: self.scan_type,
+        };
+        let _: UiScanResponse = transaction(input, context, STANDARD_COMMAND_TIMEOUT_MILLIS)?;
+        writeln!(
+            context.stdout(),
+            "{} scan started",
+            scan_type_name(self.scan_type)
+        )
+        .expect("writeln! failed");
+        Ok(())
+    }
+
+    fn as_any(&self) -> &dyn Any {
+        self
+    }
+}
+
+impl ScanCommand {
+    pub fn new(pieces: Vec<String>) -> Result<Self, String> {
+        let matches = match scan_subcommand().get_matches_from_safe(pieces) {
+            Ok(matches) => matches,
+            Err(e) => return Err(format!("{}", e)),
+        };
+        let scan_type = match matches
+            .value_of("type")
+            .expect("type parameter is not properly required")
+            .to_lowercase()
+            .as_str()
+        {
+            "payables" => ScanType::Payables,
+            "receivables" => ScanType::Receivables,
+            other => panic!("Unexpected scan type '{}' passed clap validation", other),
+        };
+        Ok(Self { scan_type })
+    }
+
+    pub fn handle_broadcast(body: UiScanResultsBroadcast, stdout: &mut dyn Write) {
+        if let Some(error) = body.error_opt {
+            write!(
+                stdout,
+                "\n{} scan failed: {}\n\nmasq> ",
+                scan_type_name(body.scan_type),
+                error
+            )
+            .expect("write! failed");
+            return;
+        }
+        let description = match body.scan_type {
+            ScanType::Payables => "payments sent",
+            ScanType::Receivables => "payments received",
+        };
+        write!(
+            stdout,
+            "\n{} scan complete: {} {}.\n\nmasq> ",
+            scan_type_name(body.scan_type),
+            body.transaction_count,
+            description
+        )
+        .expect("write! failed");
+    }
+}
+
+fn scan_type_name(scan_type: ScanType) -> &'static str {
+    match scan_type {
+        ScanType::Payables => "Payables",
+        ScanType::Receivables => "Receivables",
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::command_context::ContextError;
+    use crate::command_factory::{CommandFactory, CommandFactoryError, CommandFactoryReal};
+    use crate::commands::commands_common::CommandError::Payload;
+    use crate::test_utils::mocks::CommandContextMock;
+    use masq_lib::messages::ToMessageBody;
+    use std::sync::{Arc, Mutex};
+
+    #[test]
+    fn testing_command_factory_here() {
+        let factory = CommandFactoryReal::new();
+
+        let command = factory
+            .make(vec!["scan".to_string(), "Receivables".to_string()])
+            .unwrap();
+
+        assert_eq!(
+            command.as_any().downcast_ref::<ScanCommand>().unwrap(),
+            &ScanCommand {
+                scan_type: ScanType::Receivables
+            }
+        );
+    }
+
+    #[test]
+    fn scan_command_requires_a_known_scan_type() {
+        let factory = CommandFactoryReal::new();
+
+        let result = factory.make(vec!["scan".to_string(), "booga".to_string()]);
+
+        match result {
+            Err(CommandFactoryError::CommandSyntax(msg)) => {
+                assert_eq!(msg.contains("isn't a valid value"), true, "{}", msg)
+            }
+            x => panic!("Expected CommandSyntax error, got {:?}", x.map(|_| ())),
+        }
+    }
+
+    #[test]
+    fn scan_command_happy_path() {
+        let transact_params_arc = Arc::new(Mutex::new(vec![]));
+        let mut context = CommandContextMock::new()
+            .transact_params(&transact_params_arc)
+            .transact_result(Ok(UiScanResponse {}.tmb(0)));
+        let stdout_arc = context.stdout_arc();
+        let stderr_arc = context.stderr_arc();
+        let subject = ScanCommand {
+            scan_type: ScanType::Payables,
+        };
+
+        let result = subject.execute(&mut context);
+
+        assert_eq!(result, Ok(()));
+        let transact_params = transact_params_arc.lock().unwrap();
+        assert_eq!(
+            *transact_params,
+            vec![(
+                UiScanRequest {
+                    scan_type: ScanType::Payables
+                }
+                .tmb(0),
+                STANDARD_COMMAND_TIMEOUT_MILLIS
+            )]
+        );
+        assert_eq!(
+            stdout_arc.lock().unwrap().get_string(),
+            "Payables scan started\n".to_string()
+        );
+        assert_eq!(stderr_arc.lock().unwrap().get_string(), String::new());
+    }
+
+    #[test]
+    fn scan_command_reports_node_complaints() {
+        let mut context = CommandContextMock::new().transact_result(Err(
+            ContextError::PayloadError(1234, "Node is unhappy".to_string()),
+        ));
+        let subject = ScanCommand {
+            scan_type: ScanType::Receivables,
+        };
+
+        let result = subject.execute(&mut context);
+
+        assert_eq!(result, Err(Payload(1234, "Node is unhappy".to_string())));
+    }
+
+    #[test]
+    fn scan_results_broadcast_is_displayed() {
+        let mut stdout = Vec::new();
+
+        ScanCommand::handle_broadcast(
+            UiScanResultsBroadcast {
+                scan_type: ScanType::Receivables,
+                transaction_count: 3,
+                error_opt: None,
+            },
+            &mut stdout,
+        );
+
+        assert_eq!(
+            String::from_utf8(stdout).unwrap(),
+            "\nReceivables scan complete: 3 payments received.\n\nmasq> ".to_string()
+        );
+    }
+
+    #[test]
+    fn failed_scan_results_broadcast_is_displayed() {
+        let mut stdout = Vec::new();
+
+        ScanCommand::handle_broadcast(
+            UiScanResultsBroadcast {
+                scan_type: ScanType::Payables,
+                transaction_count: 0,
+                error_opt: Some("Failed to send transaction".to_string()),
+            },
+            &mut stdout,
+        );
+
+        assert_eq!(
+            String::from_utf8(stdout).unwrap(),
+            "\nPayables scan failed: Failed to send transaction\n\nmasq> ".to_string()
+        );
+    }
+}
@@ -4,44 +4,91 @@ use crate::command_context::CommandContext;
 use crate::commands::commands_common::{
     transaction, Command, CommandError, STANDARD_COMMAND_TIMEOUT_MILLIS,
 };
-use clap::{value_t, App, SubCommand};
+use clap::{value_t, App, Arg, SubCommand};
 use masq_lib::messages::{
-    UiSetupBroadcast, UiSetupInner, UiSetupRequest, UiSetupRequestValue, UiSetupResponse,
-    SETUP_ERROR,
+    UiLoadSetupProfileRequest, UiLoadSetupProfileResponse, UiSaveSetupProfileRequest,
+    UiSaveSetupProfileResponse, UiSetupBroadcast, UiSetupInner, UiSetupRequest,
+    UiSetupRequestValue, UiSetupResponse, SETUP_ERROR, SETUP_PROFILE_ERROR,
 };
 use masq_lib::shared_schema::shared_app;
 use masq_lib::utils::index_of_from;
 use std::fmt::Debug;
 use std::io::Write;
 
+const SAVE_AS_HELP: &str =
+    "After applying any other changes, saves the values you have set as a named setup \
+     profile in the Daemon. Sensitive values, like --db-password, are not saved.";
+const LOAD_HELP: &str =
+    "Before applying any other changes, replaces the values you have set with those from \
+     a named setup profile saved earlier with --save-as.";
+const PROFILE_PARAMETERS: [&str; 2] = ["save-as", "load"];
+
 pub fn setup_subcommand() -> App<'static, 'static> {
     shared_app(SubCommand::with_name("setup")
         .about("Establishes (if Node is not already running) and displays startup parameters for MASQNode."))
+        .arg(
+            Arg::with_name("save-as")
+                .long("save-as")
+                .value_name("PROFILE-NAME")
+                .takes_value(true)
+                .required(false)
+                .help(SAVE_AS_HELP),
+        )
+        .arg(
+            Arg::with_name("load")
+                .long("load")
+                .value_name("PROFILE-NAME")
+                .takes_value(true)
+                .required(false)
+                .help(LOAD_HELP),
+        )
 }
 
 #[derive(Debug, PartialEq)]
 pub struct SetupCommand {
     pub values: Vec<UiSetupRequestValue>,
+    pub save_as_opt: Option<String>,
+    pub load_opt: Option<String>,
 }
 
 impl Command for SetupCommand {
     fn execute(&self, context: &mut dyn CommandContext) -> Result<(), CommandError> {
-        let out_message = UiSetupRequest {
-            values: self.values.clone(),
-        };
-        let result: Result<UiSetupResponse, CommandError> =
-            transaction(out_message, context, STANDARD_COMMAND_TIMEOUT_MILLIS);
-        match result {
-            Ok(response) => {
-                Self::dump_setup(UiSetupInner::from(response), context.stdout());
-                Ok(())
+        let mut inner_opt = None;
+        if let Some(name) = &self.load_opt {
+            let result: Result<UiLoadSetupProfileResponse, CommandError> = transaction(
+                UiLoadSetupProfileRequest { name: name.clone() },
+                context,
+                STANDARD_COMMAND_TIMEOUT_MILLIS,
+            );
+            match result {
+                Ok(response) => inner_opt = Some(UiSetupInner::from(response)),
+                Err(e) => return Self::handle_error(e, context),
             }
-            Err(CommandError::Payload(err, msg)) if err == SETUP_ERROR => {
-                writeln!(context.stderr(), "{}", msg).expect("writeln! failed");
-                Ok(())
+        }
+        if inner_opt.is_none() || !self.values.is_empty() {
+            let out_message = UiSetupRequest {
+                values: self.values.clone(),
+            };
+            let result: Result<UiSetupResponse, CommandError> =
+                transaction(out_message, context, STANDARD_COMMAND_TIMEOUT_MILLIS);
+            match result {
+                Ok(response) => inner_opt = Some(UiSetupInner::from(response)),
+                Err(e) => return Self::handle_error(e, context),
+            }
+        }
+        Self::dump_setup(inner_opt.expect("No setup to display"), context.stdout());
+        if let Some(name) = &self.save_as_opt {
+            let result: Result<UiSaveSetupProfileResponse, CommandError> = transaction(
+                UiSaveSetupProfileRequest { name: name.clone() },
+                context,
+                STANDARD_COMMAND_TIMEOUT_MILLIS,
+            );
+            match result {
+                Ok(response) => Self::report_saved_profile(name, response, context.stdout()),
+                Err(e) => return Self::handle_error(e, context),
             }
-            Err(e) => Err(e),
         }
+        Ok(())
     }
 }
 
@@ -55,6 +102,7 @@ impl SetupCommand {
             .iter()
             .filter(|piece| (*piece).starts_with("--"))
             .map(|piece| piece[2..].to_string())
+            .filter(|key| !PROFILE_PARAMETERS.contains(&key.as_str()))
             .map(|key| {
                 if Self::has_value(&pieces, &key) {
                     let value = value_t!(matches, &key, String).expect("Value disappeared!");
@@ -69,7 +117,11 @@ impl SetupCommand {
                 .partial_cmp(&b.name)
                 .expect("String comparison failed")
         });
-        Ok(Self { values })
+        Ok(Self {
+            values,
+            save_as_opt: matches.value_of("save-as").map(|name| name.to_string()),
+            load_opt: matches.value_of("load").map(|name| name.to_string()),
+        })
     }
 
     pub fn handle_broadcast(response: UiSetupBroadcast, stdout: &mut dyn Write) {
@@ -79,6 +131,32 @@ impl SetupCommand {
         stdout.flush().expect("flush failed");
     }
 
+    fn handle_error(e: CommandError, context: &mut dyn CommandContext) -> Result<(), CommandError> {
+        match e {
+            CommandError::Payload(err, msg) if err == SETUP_ERROR || err == SETUP_PROFILE_ERROR => {
+                writeln!(context.stderr(), "{}", msg).expect("writeln! failed");
+                Ok(())
+            }
+            e => Err(e),
+        }
+    }
+
+    fn report_saved_profile(
+        name: &str,
+        response: UiSaveSetupProfileResponse,
+        stdout: &mut dyn Write,
+    ) {
+        writeln!(stdout, "Setup saved as profile '{}'.", name).expect("writeln! failed");
+        if !response.excluded.is_empty() {
+            writeln!(
+                stdout,
+                "These sensitive values were not saved: {}",
+                response.excluded.join(", ")
+            )
+            .expect("writeln! failed");
+        }
+    }
+
     fn has_value(pieces: &[String], piece: &str) -> bool {
         let dash_dash_piece = format!("--{}", piece);
         match index_of_from(pieces, &dash_dash_piece, 0) {
@@ -128,6 +206,7 @@ impl SetupCommand {
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::command_context::ContextError;
     use crate::command_factory::{CommandFactory, CommandFactoryReal};
     use crate::communications::broadcast_handler::StreamFactory;
     use crate::test_utils::mocks::{CommandContextMock, TestStreamFactory};
@@ -301,4 +380,147 @@ ip                     Nosir, I don't like it.\n\
 \n\
 masq> ");
     }
+
+    #[test]
+    fn setup_command_separates_profile_options_from_setup_values() {
+        let subject = SetupCommand::new(vec![
+            "setup".to_string(),
+            "--load".to_string(),
+            "testnet".to_string(),
+            "--log-level".to_string(),
+            "debug".to_string(),
+            "--save-as".to_string(),
+            "work".to_string(),
+        ])
+        .unwrap();
+
+        assert_eq!(
+            subject,
+            SetupCommand {
+                values: vec![UiSetupRequestValue::new("log-level", "debug")],
+                save_as_opt: Some("work".to_string()),
+                load_opt: Some("testnet".to_string()),
+            }
+        );
+    }
+
+    #[test]
+    fn setup_command_loads_profile_without_sending_empty_setup() {
+        let transact_params_arc = Arc::new(Mutex::new(vec![]));
+        let mut context = CommandContextMock::new()
+            .transact_params(&transact_params_arc)
+            .transact_result(Ok(UiLoadSetupProfileResponse {
+                values: vec![UiSetupResponseValue::new("chain", "mainnet", Set)],
+                errors: vec![],
+            }
+            .tmb(0)));
+        let stdout_arc = context.stdout_arc();
+        let subject = SetupCommand::new(vec![
+            "setup".to_string(),
+            "--load".to_string(),
+            "main".to_string(),
+        ])
+        .unwrap();
+
+        let result = subject.execute(&mut context);
+
+        assert_eq!(result, Ok(()));
+        let transact_params = transact_params_arc.lock().unwrap();
+        assert_eq!(
+            *transact_params,
+            vec![(
+                UiLoadSetupProfileRequest {
+                    name: "main".to_string()
+                }
+                .tmb(0),
+                STANDARD_COMMAND_TIMEOUT_MILLIS
+            )]
+        );
+        assert_eq! (stdout_arc.lock().unwrap().get_string(),
+"NAME                   VALUE                                                            STATUS\n\
+chain                  mainnet                                                          Set\n\
+\n");
+    }
+
+    #[test]
+    fn setup_command_applies_values_then_saves_profile() {
+        let transact_params_arc = Arc::new(Mutex::new(vec![]));
+        let mut context = CommandContextMock::new()
+            .transact_params(&transact_params_arc)
+            .transact_result(Ok(UiSetupResponse {
+                running: false,
+                values: vec![UiSetupResponseValue::new("chain", "ropsten", Set)],
+                errors: vec![],
+            }
+            .tmb(0)))
+            .transact_result(Ok(UiSaveSetupProfileResponse {
+                excluded: vec!["db-password".to_string()],
+            }
+            .tmb(0)));
+        let stdout_arc = context.stdout_arc();
+        let subject = SetupCommand::new(vec![
+            "setup".to_string(),
+            "--chain".to_string(),
+            "ropsten".to_string(),
+            "--save-as".to_string(),
+            "testnet".to_string(),
+        ])
+        .unwrap();
+
+        let result = subject.execute(&mut context);
+
+        assert_eq!(result, Ok(()));
+        let transact_params = transact_params_arc.lock().unwrap();
+        assert_eq!(
+            *transact_params,
+            vec![
+                (
+                    UiSetupRequest {
+                        values: vec![UiSetupRequestValue::new("chain", "ropsten")]
+                    }
+                    .tmb(0),
+                    STANDARD_COMMAND_TIMEOUT_MILLIS
+                ),
+                (
+                    UiSaveSetupProfileRequest {
+                        name: "testnet".to_string()
+                    }
+                    .tmb(0),
+                    STANDARD_COMMAND_TIMEOUT_MILLIS
+                ),
+            ]
+        );
+        assert_eq! (stdout_arc.lock().unwrap().get_string(),
+"NAME                   VALUE                                                            STATUS\n\
+chain                  ropsten                                                          Set\n\
+\n\
+Setup saved as profile 'testnet'.\n\
+These sensitive values were not saved: db-password\n");
+    }
+
+    #[test]
+    fn setup_command_reports_profile_errors_on_stderr() {
+        let mut context =
+            CommandContextMock::new().transact_result(Err(ContextError::PayloadError(
+                SETUP_PROFILE_ERROR,
+                "No setup profile named 'booga'".to_string(),
+            )));
+        let stdout_arc = context.stdout_arc();
+        let stderr_arc = context.stderr_arc();
+        let subject = SetupCommand::new(vec![
+            "setup".to_string(),
+            "--load".to_string(),
+            "booga".to_string(),
+        ])
+        .unwrap();
+
+        let result = subject.execute(&mut context);
+
+        assert_eq!(result, Ok(()));
+        assert_eq!(stdout_arc.lock().unwrap().get_string(), String::new());
+        assert_eq!(
+            stderr_arc.lock().unwrap().get_string(),
+            "No setup profile named 'booga'\n".to_string()
+        );
+    }
 }
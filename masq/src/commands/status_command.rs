@@ -0,0 +1,242 @@
+// Copyright (c) 2019-2020, MASQ (https://masq.ai) and/or its affiliates. All rights reserved.
+
+use crate::command_context::CommandContext;
+use crate::commands::commands_common::{
+    transaction, Command, CommandError, STANDARD_COMMAND_TIMEOUT_MILLIS,
+};
+use clap::{App, SubCommand};
+use masq_lib::messages::{CrashReason, UiStatusRequest, UiStatusResponse};
+use std::fmt::Debug;
+use std::io::Write;
+
+#[derive(Debug)]
+pub struct StatusCommand {}
+
+pub fn status_subcommand() -> App<'static, 'static> {
+    SubCommand::with_name("status").about(
+        "Displays whether MASQNode is running and, if it is, its process ID, uptime, version, chain, \
+         neighborhood mode, and UI port. If it isn't, displays why it last stopped, if it crashed.",
+    )
+}
+
+impl Command for StatusCommand {
+    fn execute(&self, context: &mut dyn CommandContext) -> Result<(), CommandError> {
+        let input = UiStatusRequest {};
+        let output: Result<UiStatusResponse, CommandError> =
+            transaction(input, context, STANDARD_COMMAND_TIMEOUT_MILLIS);
+        match output {
+            Ok(response) => {
+                Self::write_status(response, context.stdout());
+                Ok(())
+            }
+            Err(e) => {
+                writeln!(context.stderr(), "Status retrieval failed: {:?}", e)
+                    .expect("writeln! failed");
+                Err(e)
+            }
+        }
+    }
+}
+
+impl Default for StatusCommand {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl StatusCommand {
+    pub fn new() -> Self {
+        Self {}
+    }
+
+    fn write_status(response: UiStatusResponse, stdout: &mut dyn Write) {
+        let mut lines = vec![];
+        if response.running {
+            lines.push(match response.process_id_opt {
+                Some(process_id) => format!("MASQNode is running as process {}", process_id),
+                None => "MASQNode is running".to_string(),
+            });
+            if let Some(uptime_secs) = response.uptime_secs_opt {
+                lines.push(format!(
+                    "Uptime:            {}",
+                    Self::format_uptime(uptime_secs)
+                ));
+            }
+        } else {
+            lines.push("MASQNode is not running".to_string());
+        }
+        lines.push(format!("Version:           {}", response.version));
+        if let Some(chain) = response.chain_opt {
+            lines.push(format!("Chain:             {}", chain));
+        }
+        if let Some(neighborhood_mode) = response.neighborhood_mode_opt {
+            lines.push(format!("Neighborhood mode: {}", neighborhood_mode));
+        }
+        if let Some(ui_port) = response.ui_port_opt {
+            lines.push(format!("UI port:           {}", ui_port));
+        }
+        if let Some(crash_reason) = response.last_crash_reason_opt {
+            lines.push(format!(
+                "Last crash:        {}",
+                Self::describe_crash_reason(crash_reason)
+            ));
+        }
+        lines
+            .into_iter()
+            .for_each(|line| writeln!(stdout, "{}", line).expect("writeln! failed"));
+    }
+
+    fn format_uptime(uptime_secs: u64) -> String {
+        let days = uptime_secs / 86_400;
+        let hours = (uptime_secs % 86_400) / 3_600;
+        let minutes = (uptime_secs % 3_600) / 60;
+        let seconds = uptime_secs % 60;
+        if days > 0 {
+            format!("{}d {}h {}m {}s", days, hours, minutes, seconds)
+        } else if hours > 0 {
+            format!("{}h {}m {}s", hours, minutes, seconds)
+        } else if minutes > 0 {
+            format!("{}m {}s", minutes, seconds)
+        } else {
+            format!("{}s", seconds)
+        }
+    }
+
+    fn describe_crash_reason(crash_reason: CrashReason) -> String {
+        match crash_reason {
+            CrashReason::ChildWaitFailure(msg) => {
+                format!("the Daemon couldn't wait on the child process: {}", msg)
+            }
+            CrashReason::NoInformation => "no information available".to_string(),
+            CrashReason::Unrecognized(msg) => msg.trim_end().to_string(),
+            CrashReason::DaemonCrashed => "the Daemon crashed".to_string(),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::command_context::ContextError::ConnectionDropped;
+    use crate::command_factory::{CommandFactory, CommandFactoryReal};
+    use crate::commands::commands_common::CommandError::ConnectionProblem;
+    use crate::test_utils::mocks::CommandContextMock;
+    use masq_lib::messages::ToMessageBody;
+    use std::sync::{Arc, Mutex};
+
+    #[test]
+    fn testing_command_factory_here() {
+        let factory = CommandFactoryReal::new();
+        let mut context = CommandContextMock::new().transact_result(Ok(UiStatusResponse {
+            running: false,
+            version: "1.2.3".to_string(),
+            process_id_opt: None,
+            uptime_secs_opt: None,
+            chain_opt: None,
+            neighborhood_mode_opt: None,
+            ui_port_opt: None,
+            last_crash_reason_opt: None,
+        }
+        .tmb(0)));
+        let subject = factory.make(vec!["status".to_string()]).unwrap();
+
+        let result = subject.execute(&mut context);
+
+        assert_eq!(result, Ok(()));
+    }
+
+    #[test]
+    fn status_command_describes_running_node() {
+        let transact_params_arc = Arc::new(Mutex::new(vec![]));
+        let mut context = CommandContextMock::new()
+            .transact_params(&transact_params_arc)
+            .transact_result(Ok(UiStatusResponse {
+                running: true,
+                version: "1.2.3".to_string(),
+                process_id_opt: Some(12345),
+                uptime_secs_opt: Some(93_784),
+                chain_opt: Some("ropsten".to_string()),
+                neighborhood_mode_opt: Some("standard".to_string()),
+                ui_port_opt: Some(5333),
+                last_crash_reason_opt: None,
+            }
+            .tmb(42)));
+        let stdout_arc = context.stdout_arc();
+        let stderr_arc = context.stderr_arc();
+        let subject = StatusCommand::new();
+
+        let result = subject.execute(&mut context);
+
+        assert_eq!(result, Ok(()));
+        let transact_params = transact_params_arc.lock().unwrap();
+        assert_eq!(
+            *transact_params,
+            vec![(UiStatusRequest {}.tmb(0), STANDARD_COMMAND_TIMEOUT_MILLIS)]
+        );
+        assert_eq!(
+            stdout_arc.lock().unwrap().get_string(),
+            "MASQNode is running as process 12345\n\
+             Uptime:            1d 2h 3m 4s\n\
+             Version:           1.2.3\n\
+             Chain:             ropsten\n\
+             Neighborhood mode: standard\n\
+             UI port:           5333\n"
+        );
+        assert_eq!(stderr_arc.lock().unwrap().get_string(), String::new());
+    }
+
+    #[test]
+    fn status_command_describes_crashed_node() {
+        let mut context = CommandContextMock::new().transact_result(Ok(UiStatusResponse {
+            running: false,
+            version: "1.2.3".to_string(),
+            process_id_opt: None,
+            uptime_secs_opt: None,
+            chain_opt: Some("mainnet".to_string()),
+            neighborhood_mode_opt: None,
+            ui_port_opt: None,
+            last_crash_reason_opt: Some(CrashReason::Unrecognized("Out of memory\n".to_string())),
+        }
+        .tmb(42)));
+        let stdout_arc = context.stdout_arc();
+        let subject = StatusCommand::new();
+
+        let result = subject.execute(&mut context);
+
+        assert_eq!(result, Ok(()));
+        assert_eq!(
+            stdout_arc.lock().unwrap().get_string(),
+            "MASQNode is not running\n\
+             Version:           1.2.3\n\
+             Chain:             mainnet\n\
+             Last crash:        Out of memory\n"
+        );
+    }
+
+    #[test]
+    fn status_command_sad_path() {
+        let mut context =
+            CommandContextMock::new().transact_result(Err(ConnectionDropped("Booga".to_string())));
+        let stdout_arc = context.stdout_arc();
+        let stderr_arc = context.stderr_arc();
+        let subject = StatusCommand::new();
+
+        let result = subject.execute(&mut context);
+
+        assert_eq!(result, Err(ConnectionProblem("Booga".to_string())));
+        assert_eq!(stdout_arc.lock().unwrap().get_string(), String::new());
+        assert_eq!(
+            stderr_arc.lock().unwrap().get_string(),
+            "Status retrieval failed: ConnectionProblem(\"Booga\")\n"
+        );
+    }
+
+    #[test]
+    fn uptime_is_formatted_compactly() {
+        assert_eq!(StatusCommand::format_uptime(0), "0s");
+        assert_eq!(StatusCommand::format_uptime(59), "59s");
+        assert_eq!(StatusCommand::format_uptime(60), "1m 0s");
+        assert_eq!(StatusCommand::format_uptime(3_661), "1h 1m 1s");
+        assert_eq!(StatusCommand::format_uptime(86_400), "1d 0h 0m 0s");
+    }
+}
@@ -0,0 +1,290 @@
+// Copyright (c) 2019-2020, MASQ (https://masq.ai) and/or its affiliates. All rights reserved.
+
+use crate::command_context::CommandContext;
+use crate::commands::commands_common::CommandError::Payload;
+use crate::commands::commands_common::{
+    transaction, Command, CommandError, STANDARD_COMMAND_TIMEOUT_MILLIS,
+};
+use clap::{App, SubCommand};
+use masq_lib::messages::{
+    UiWalletBalances, UiWalletBalancesRequest, UiWalletBalancesResponse, NODE_NOT_RUNNING_ERROR,
+};
+use std::any::Any;
+use std::fmt::Debug;
+
+const WEI_DECIMAL_PLACES: usize = 18;
+
+#[derive(Debug, PartialEq)]
+pub struct WalletBalancesCommand {}
+
+pub fn wallet_balances_subcommand() -> App<'static, 'static> {
+    SubCommand::with_name("wallet-balances")
+        .about("Displays the ETH and MASQ balances of the consuming and earning wallets of the running MASQNode. \
+        Only valid if Node is already running and has a blockchain service URL configured.")
+}
+
+impl Command for WalletBalancesCommand {
+    fn execute(&self, context: &mut dyn CommandContext) -> Result<(), CommandError> {
+        let input = UiWalletBalancesRequest {};
+        let output: Result<UiWalletBalancesResponse, CommandError> =
+            transaction(input, context, STANDARD_COMMAND_TIMEOUT_MILLIS);
+        match output {
+            Ok(response) => {
+                match response.consuming_wallet_opt {
+                    Some(balances) => Self::write_balances(context, "Consuming", &balances),
+                    None => writeln!(context.stdout(), "Consuming wallet: not configured")
+                        .expect("writeln! failed"),
+                }
+                Self::write_balances(context, "Earning", &response.earning_wallet);
+                Ok(())
+            }
+            Err(Payload(code, message)) if code == NODE_NOT_RUNNING_ERROR => {
+                writeln!(
+                    context.stderr(),
+                    "MASQNode is not running; therefore its wallet balances cannot be displayed."
+                )
+                .expect("writeln! failed");
+                Err(Payload(code, message))
+            }
+            Err(Payload(code, message)) => {
+                writeln!(
+                    context.stderr(),
+                    "Wallet balances retrieval failed: {}",
+                    message
+                )
+                .expect("writeln! failed");
+                Err(Payload(code, message))
+            }
+            Err(e) => {
+                writeln!(
+                    context.stderr(),
+                    "Wallet balances retrieval failed: {:?}",
+                    e
+                )
+                .expect("writeln! failed");
+                Err(e)
+            }
+        }
+    }
+
+    fn as_any(&self) -> &dyn Any {
+        self
+    }
+}
+
+impl Default for WalletBalancesCommand {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl WalletBalancesCommand {
+    pub fn new() -> Self {
+        Self {}
+    }
+
+    fn write_balances(context: &mut dyn CommandContext, label: &str, balances: &UiWalletBalances) {
+        writeln!(context.stdout(), "{} wallet: {}", label, balances.address)
+            .expect("writeln! failed");
+        writeln!(
+            context.stdout(),
+            "    ETH:  {}",
+            wei_to_decimal(&balances.eth_balance_wei)
+        )
+        .expect("writeln! failed");
+        writeln!(
+            context.stdout(),
+            "    MASQ: {}",
+            wei_to_decimal(&balances.masq_balance_wei)
+        )
+        .expect("writeln! failed");
+    }
+}
+
+fn wei_to_decimal(wei: &str) -> String {
+    let padded = format!("{:0>width$}", wei, width = WEI_DECIMAL_PLACES + 1);
+    let (whole, fraction) = padded.split_at(padded.len() - WEI_DECIMAL_PLACES);
+    let fraction = fraction.trim_end_matches('0');
+    if fraction.is_empty() {
+        whole.to_string()
+    } else {
+        format!("{}.{}", whole, fraction)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::command_context::ContextError;
+    use crate::command_context::ContextError::ConnectionDropped;
+    use crate::command_factory::{CommandFactory, CommandFactoryReal};
+    use crate::commands::commands_common::CommandError::ConnectionProblem;
+    use crate::test_utils::mocks::CommandContextMock;
+    use masq_lib::messages::ToMessageBody;
+    use std::sync::{Arc, Mutex};
+
+    #[test]
+    fn testing_command_factory_here() {
+        let factory = CommandFactoryReal::new();
+
+        let command = factory.make(vec!["wallet-balances".to_string()]).unwrap();
+
+        assert_eq!(
+            command
+                .as_any()
+                .downcast_ref::<WalletBalancesCommand>()
+                .unwrap(),
+            &WalletBalancesCommand {}
+        );
+    }
+
+    #[test]
+    fn wei_to_decimal_works() {
+        assert_eq!(wei_to_decimal("0"), "0".to_string());
+        assert_eq!(wei_to_decimal("1"), "0.000000000000000001".to_string());
+        assert_eq!(wei_to_decimal("1000000000000000000"), "1".to_string());
+        assert_eq!(wei_to_decimal("1234500000000000000"), "1.2345".to_string());
+        assert_eq!(
+            wei_to_decimal("98765000000000000000000"),
+            "98765".to_string()
+        );
+    }
+
+    #[test]
+    fn wallet_balances_command_happy_path() {
+        let transact_params_arc = Arc::new(Mutex::new(vec![]));
+        let expected_response = UiWalletBalancesResponse {
+            consuming_wallet_opt: Some(UiWalletBalances {
+                address: "0x0123456789012345678901234567890123456789".to_string(),
+                eth_balance_wei: "1500000000000000000".to_string(),
+                masq_balance_wei: "2000000000000000000000".to_string(),
+            }),
+            earning_wallet: UiWalletBalances {
+                address: "0x9876543210987654321098765432109876543210".to_string(),
+                eth_balance_wei: "0".to_string(),
+                masq_balance_wei: "12345".to_string(),
+            },
+        };
+        let mut context = CommandContextMock::new()
+            .transact_params(&transact_params_arc)
+            .transact_result(Ok(expected_response.tmb(42)));
+        let stdout_arc = context.stdout_arc();
+        let stderr_arc = context.stderr_arc();
+        let subject = WalletBalancesCommand::new();
+
+        let result = subject.execute(&mut context);
+
+        assert_eq!(result, Ok(()));
+        let transact_params = transact_params_arc.lock().unwrap();
+        assert_eq!(
+            *transact_params,
+            vec![(
+                UiWalletBalancesRequest {}.tmb(0),
+                STANDARD_COMMAND_TIMEOUT_MILLIS
+            )]
+        );
+        assert_eq!(
+            stdout_arc.lock().unwrap().get_string(),
+            "Consuming wallet: 0x0123456789012345678901234567890123456789\n\
+             \x20   ETH:  1.5\n\
+             \x20   MASQ: 2000\n\
+             Earning wallet: 0x9876543210987654321098765432109876543210\n\
+             \x20   ETH:  0\n\
+             \x20   MASQ: 0.000000000000012345\n"
+        );
+        assert_eq!(stderr_arc.lock().unwrap().get_string(), String::new());
+    }
+
+    #[test]
+    fn wallet_balances_command_without_consuming_wallet() {
+        let expected_response = UiWalletBalancesResponse {
+            consuming_wallet_opt: None,
+            earning_wallet: UiWalletBalances {
+                address: "0x9876543210987654321098765432109876543210".to_string(),
+                eth_balance_wei: "1".to_string(),
+                masq_balance_wei: "2".to_string(),
+            },
+        };
+        let mut context = CommandContextMock::new().transact_result(Ok(expected_response.tmb(42)));
+        let stdout_arc = context.stdout_arc();
+        let subject = WalletBalancesCommand::new();
+
+        let result = subject.execute(&mut context);
+
+        assert_eq!(result, Ok(()));
+        assert_eq!(
+            stdout_arc.lock().unwrap().get_string(),
+            "Consuming wallet: not configured\n\
+             Earning wallet: 0x9876543210987654321098765432109876543210\n\
+             \x20   ETH:  0.000000000000000001\n\
+             \x20   MASQ: 0.000000000000000002\n"
+        );
+    }
+
+    #[test]
+    fn wallet_balances_command_doesnt_work_if_node_is_not_running() {
+        let mut context = CommandContextMock::new().transact_result(Err(
+            ContextError::PayloadError(NODE_NOT_RUNNING_ERROR, "irrelevant".to_string()),
+        ));
+        let stdout_arc = context.stdout_arc();
+        let stderr_arc = context.stderr_arc();
+        let subject = WalletBalancesCommand::new();
+
+        let result = subject.execute(&mut context);
+
+        assert_eq!(
+            result,
+            Err(Payload(NODE_NOT_RUNNING_ERROR, "irrelevant".to_string()))
+        );
+        assert_eq!(
+            stderr_arc.lock().unwrap().get_string(),
+            "MASQNode is not running; therefore its wallet balances cannot be displayed.\n"
+        );
+        assert_eq!(stdout_arc.lock().unwrap().get_string(), String::new());
+    }
+
+    #[test]
+    fn wallet_balances_command_reports_node_complaints() {
+        let mut context =
+            CommandContextMock::new().transact_result(Err(ContextError::PayloadError(
+                1234,
+                "No blockchain service URL is configured; wallet balances cannot be retrieved"
+                    .to_string(),
+            )));
+        let stderr_arc = context.stderr_arc();
+        let subject = WalletBalancesCommand::new();
+
+        let result = subject.execute(&mut context);
+
+        assert_eq!(
+            result,
+            Err(Payload(
+                1234,
+                "No blockchain service URL is configured; wallet balances cannot be retrieved"
+                    .to_string()
+            ))
+        );
+        assert_eq!(
+            stderr_arc.lock().unwrap().get_string(),
+            "Wallet balances retrieval failed: No blockchain service URL is configured; wallet balances cannot be retrieved\n"
+        );
+    }
+
+    #[test]
+    fn wallet_balances_command_sad_path() {
+        let mut context =
+            CommandContextMock::new().transact_result(Err(ConnectionDropped("Booga".to_string())));
+        let stdout_arc = context.stdout_arc();
+        let stderr_arc = context.stderr_arc();
+        let subject = WalletBalancesCommand::new();
+
+        let result = subject.execute(&mut context);
+
+        assert_eq!(result, Err(ConnectionProblem("Booga".to_string())));
+        assert_eq!(stdout_arc.lock().unwrap().get_string(), String::new());
+        assert_eq!(
+            stderr_arc.lock().unwrap().get_string(),
+            "Wallet balances retrieval failed: ConnectionProblem(\"Booga\")\n"
+        );
+    }
+}
@@ -1,11 +1,17 @@
 // Copyright (c) 2019-2020, MASQ (https://masq.ai). All rights reserved.
 
+use crate::commands::ban_command::BanCommand;
 use crate::commands::change_password_command::ChangePasswordCommand;
+use crate::commands::connection_status_command::ConnectionStatusCommand;
+use crate::commands::debut_command::PendingDebutsCommand;
+use crate::commands::scan_command::ScanCommand;
 use crate::commands::setup_command::SetupCommand;
 use crate::notifications::crashed_notification::CrashNotifier;
 use crossbeam_channel::{unbounded, Receiver, RecvError, Sender};
 use masq_lib::messages::{
-    FromMessageBody, UiNewPasswordBroadcast, UiNodeCrashedBroadcast, UiSetupBroadcast,
+    FromMessageBody, UiBanChangedBroadcast, UiConnectionChangeBroadcast, UiDebutPendingBroadcast,
+    UiNewPasswordBroadcast, UiNodeCrashedBroadcast, UiNodeRestartAbandonedBroadcast,
+    UiNodeRestartedBroadcast, UiNodeRestartingBroadcast, UiScanResultsBroadcast, UiSetupBroadcast,
 };
 use masq_lib::ui_gateway::MessageBody;
 use std::fmt::Debug;
@@ -70,8 +76,25 @@ impl BroadcastHandlerReal {
                     SetupCommand::handle_broadcast(body, stdout);
                 } else if let Ok((body, _)) = UiNodeCrashedBroadcast::fmb(message_body.clone()) {
                     CrashNotifier::handle_broadcast(body, stdout);
+                } else if let Ok((body, _)) = UiNodeRestartingBroadcast::fmb(message_body.clone()) {
+                    CrashNotifier::handle_restarting_broadcast(body, stdout);
+                } else if let Ok((body, _)) = UiNodeRestartedBroadcast::fmb(message_body.clone()) {
+                    CrashNotifier::handle_restarted_broadcast(body, stdout);
+                } else if let Ok((body, _)) =
+                    UiNodeRestartAbandonedBroadcast::fmb(message_body.clone())
+                {
+                    CrashNotifier::handle_restart_abandoned_broadcast(body, stdout);
                 } else if let Ok((_, _)) = UiNewPasswordBroadcast::fmb(message_body.clone()) {
                     ChangePasswordCommand::handle_broadcast(stdout);
+                } else if let Ok((body, _)) = UiBanChangedBroadcast::fmb(message_body.clone()) {
+                    BanCommand::handle_broadcast(body, stdout);
+                } else if let Ok((body, _)) = UiScanResultsBroadcast::fmb(message_body.clone()) {
+                    ScanCommand::handle_broadcast(body, stdout);
+                } else if let Ok((body, _)) = UiDebutPendingBroadcast::fmb(message_body.clone()) {
+                    PendingDebutsCommand::handle_broadcast(body, stdout);
+                } else if let Ok((body, _)) = UiConnectionChangeBroadcast::fmb(message_body.clone())
+                {
+                    ConnectionStatusCommand::handle_broadcast(body, stdout);
                 } else {
                     write!(
                         stderr,
@@ -125,7 +148,10 @@ mod tests {
     use super::*;
     use crate::test_utils::mocks::TestStreamFactory;
     use masq_lib::messages::UiSetupBroadcast;
-    use masq_lib::messages::{CrashReason, ToMessageBody, UiNodeCrashedBroadcast};
+    use masq_lib::messages::{
+        CrashReason, ScanType, ToMessageBody, UiConnectionStage, UiNodeCrashedBroadcast,
+        UiPendingDebut,
+    };
     use masq_lib::ui_gateway::MessagePath;
 
     #[test]
@@ -189,6 +215,34 @@ mod tests {
         );
     }
 
+    #[test]
+    fn broadcast_of_restarting_triggers_correct_handler() {
+        let (factory, handle) = TestStreamFactory::new();
+        // This thread will leak, and will only stop when the tests stop running.
+        let subject = BroadcastHandlerReal::new().start(Box::new(factory));
+        let message = UiNodeRestartingBroadcast {
+            process_id: 1234,
+            attempt: 1,
+            max_restarts: 3,
+            delay_ms: 1000,
+        }
+        .tmb(0);
+
+        subject.send(message);
+
+        let stdout = handle.stdout_so_far();
+        assert_eq!(
+            stdout,
+            "\nThe Daemon will restart the Node in 1000ms (attempt 1 of 3).\n\nmasq> ".to_string()
+        );
+        assert_eq!(
+            handle.stderr_so_far(),
+            "".to_string(),
+            "stderr: '{}'",
+            stdout
+        );
+    }
+
     #[test]
     fn broadcast_of_new_password_triggers_correct_handler() {
         let (factory, handle) = TestStreamFactory::new();
@@ -211,6 +265,99 @@ mod tests {
         );
     }
 
+    #[test]
+    fn broadcast_of_ban_changed_triggers_correct_handler() {
+        let (factory, handle) = TestStreamFactory::new();
+        // This thread will leak, and will only stop when the tests stop running.
+        let subject = BroadcastHandlerReal::new().start(Box::new(factory));
+        let message = UiBanChangedBroadcast {
+            wallet: "0x0123456789012345678901234567890123456789".to_string(),
+            banned: true,
+            reason_opt: Some("Delinquency".to_string()),
+        }
+        .tmb(0);
+
+        subject.send(message);
+
+        let stdout = handle.stdout_so_far();
+        assert_eq!(
+            stdout,
+            "\nWallet 0x0123456789012345678901234567890123456789 has been banned: Delinquency\n\nmasq> "
+                .to_string()
+        );
+        assert_eq!(handle.stderr_so_far(), "".to_string());
+    }
+
+    #[test]
+    fn broadcast_of_scan_results_triggers_correct_handler() {
+        let (factory, handle) = TestStreamFactory::new();
+        // This thread will leak, and will only stop when the tests stop running.
+        let subject = BroadcastHandlerReal::new().start(Box::new(factory));
+        let message = UiScanResultsBroadcast {
+            scan_type: ScanType::Payables,
+            transaction_count: 2,
+            error_opt: None,
+        }
+        .tmb(0);
+
+        subject.send(message);
+
+        let stdout = handle.stdout_so_far();
+        assert_eq!(
+            stdout,
+            "\nPayables scan complete: 2 payments sent.\n\nmasq> ".to_string()
+        );
+        assert_eq!(handle.stderr_so_far(), "".to_string());
+    }
+
+    #[test]
+    fn broadcast_of_pending_debut_triggers_correct_handler() {
+        let (factory, handle) = TestStreamFactory::new();
+        // This thread will leak, and will only stop when the tests stop running.
+        let subject = BroadcastHandlerReal::new().start(Box::new(factory));
+        let message = UiDebutPendingBroadcast {
+            debut: UiPendingDebut {
+                public_key: "AQIDBA".to_string(),
+                node_address: "1.2.3.4:1234".to_string(),
+                earning_wallet: "0x0123456789012345678901234567890123456789".to_string(),
+                received_at: 1_600_000_000,
+            },
+        }
+        .tmb(0);
+
+        subject.send(message);
+
+        let stdout = handle.stdout_so_far();
+        assert_eq!(
+            stdout,
+            "\nDebut awaiting your approval (use approve-debut or reject-debut):\n\
+             AQIDBA  1.2.3.4:1234  wallet 0x0123456789012345678901234567890123456789  \
+             received 2020-09-13T12:26:40Z\n\nmasq> "
+                .to_string()
+        );
+        assert_eq!(handle.stderr_so_far(), "".to_string());
+    }
+
+    #[test]
+    fn broadcast_of_connection_change_triggers_correct_handler() {
+        let (factory, handle) = TestStreamFactory::new();
+        // This thread will leak, and will only stop when the tests stop running.
+        let subject = BroadcastHandlerReal::new().start(Box::new(factory));
+        let message = UiConnectionChangeBroadcast {
+            stage: UiConnectionStage::NotConnected,
+        }
+        .tmb(0);
+
+        subject.send(message);
+
+        let stdout = handle.stdout_so_far();
+        assert_eq!(
+            stdout,
+            "\nNot connected: no neighbors are reachable\n\nmasq> ".to_string()
+        );
+        assert_eq!(handle.stderr_so_far(), "".to_string());
+    }
+
     #[test]
     fn unexpected_broadcasts_are_ineffectual_but_dont_kill_the_handler() {
         let (factory, handle) = TestStreamFactory::new();
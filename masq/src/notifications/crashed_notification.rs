@@ -1,6 +1,9 @@
 // Copyright (c) 2019-2020, MASQ (https://masq.ai) and/or its affiliates. All rights reserved.
 
-use masq_lib::messages::{CrashReason, UiNodeCrashedBroadcast};
+use masq_lib::messages::{
+    CrashReason, UiNodeCrashedBroadcast, UiNodeRestartAbandonedBroadcast, UiNodeRestartedBroadcast,
+    UiNodeRestartingBroadcast,
+};
 use masq_lib::utils::exit_process;
 use std::io::Write;
 
@@ -22,6 +25,45 @@ impl CrashNotifier {
         stdout.flush().expect("flush failed");
     }
 
+    pub fn handle_restarting_broadcast(body: UiNodeRestartingBroadcast, stdout: &mut dyn Write) {
+        Self::write_notification(
+            stdout,
+            format!(
+                "The Daemon will restart the Node in {}ms (attempt {} of {}).",
+                body.delay_ms, body.attempt, body.max_restarts
+            ),
+        )
+    }
+
+    pub fn handle_restarted_broadcast(body: UiNodeRestartedBroadcast, stdout: &mut dyn Write) {
+        Self::write_notification(
+            stdout,
+            format!(
+                "The Daemon restarted the Node as process {}; it is accepting UI connections on port {}.",
+                body.new_process_id, body.redirect_ui_port
+            ),
+        )
+    }
+
+    pub fn handle_restart_abandoned_broadcast(
+        body: UiNodeRestartAbandonedBroadcast,
+        stdout: &mut dyn Write,
+    ) {
+        Self::write_notification(
+            stdout,
+            format!(
+                "The Daemon has stopped trying to restart the Node: {}",
+                body.reason
+            ),
+        )
+    }
+
+    fn write_notification(stdout: &mut dyn Write, notification: String) {
+        writeln!(stdout, "\n{}\n", notification).expect("writeln! failed");
+        write!(stdout, "masq> ").expect("write! failed");
+        stdout.flush().expect("flush failed");
+    }
+
     fn interpret_reason(reason: CrashReason) -> String {
         match reason {
             CrashReason::ChildWaitFailure(msg) => {
@@ -110,4 +152,54 @@ mod tests {
 
         CrashNotifier::handle_broadcast(msg, &mut stdout);
     }
+
+    #[test]
+    pub fn handles_restarting_broadcast() {
+        let mut stdout = ByteArrayWriter::new();
+        let msg = UiNodeRestartingBroadcast {
+            process_id: 12345,
+            attempt: 2,
+            max_restarts: 5,
+            delay_ms: 2000,
+        };
+
+        CrashNotifier::handle_restarting_broadcast(msg, &mut stdout);
+
+        assert_eq!(
+            stdout.get_string(),
+            "\nThe Daemon will restart the Node in 2000ms (attempt 2 of 5).\n\nmasq> ".to_string()
+        );
+    }
+
+    #[test]
+    pub fn handles_restarted_broadcast() {
+        let mut stdout = ByteArrayWriter::new();
+        let msg = UiNodeRestartedBroadcast {
+            new_process_id: 23456,
+            redirect_ui_port: 5333,
+        };
+
+        CrashNotifier::handle_restarted_broadcast(msg, &mut stdout);
+
+        assert_eq!(
+            stdout.get_string(),
+            "\nThe Daemon restarted the Node as process 23456; it is accepting UI connections on port 5333.\n\nmasq> ".to_string()
+        );
+    }
+
+    #[test]
+    pub fn handles_restart_abandoned_broadcast() {
+        let mut stdout = ByteArrayWriter::new();
+        let msg = UiNodeRestartAbandonedBroadcast {
+            process_id: 12345,
+            reason: "Node crashed 4 times within 300 seconds".to_string(),
+        };
+
+        CrashNotifier::handle_restart_abandoned_broadcast(msg, &mut stdout);
+
+        assert_eq!(
+            stdout.get_string(),
+            "\nThe Daemon has stopped trying to restart the Node: Node crashed 4 times within 300 seconds\n\nmasq> ".to_string()
+        );
+    }
 }
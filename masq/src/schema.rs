@@ -1,15 +1,31 @@
 // Copyright (c) 2019-2020, MASQ (https://masq.ai) and/or its affiliates. All rights reserved.
+use crate::commands::ban_command::{ban_subcommand, unban_subcommand};
+use crate::commands::banned_wallets_command::banned_wallets_subcommand;
 use crate::commands::change_password_command::{
     change_password_subcommand, set_password_subcommand,
 };
 use crate::commands::check_password_command::check_password_subcommand;
+use crate::commands::connection_status_command::connection_status_subcommand;
 use crate::commands::crash_command::crash_subcommand;
+use crate::commands::debut_command::{
+    approve_debut_subcommand, debut_allow_list_subcommand, pending_debuts_subcommand,
+    reject_debut_subcommand,
+};
 use crate::commands::descriptor_command::descriptor_subcommand;
+use crate::commands::dns_bypass_command::dns_bypass_subcommand;
+use crate::commands::exit_country_command::exit_country_subcommand;
+use crate::commands::export_accounts_command::export_accounts_subcommand;
 use crate::commands::generate_wallets_command::generate_wallets_subcommand;
+use crate::commands::malefactor_bans_command::{
+    malefactor_bans_subcommand, malefactor_unban_subcommand,
+};
+use crate::commands::scan_command::scan_subcommand;
 use crate::commands::setup_command::setup_subcommand;
 use crate::commands::shutdown_command::shutdown_subcommand;
 use crate::commands::start_command::start_subcommand;
+use crate::commands::status_command::status_subcommand;
 use crate::commands::wallet_addresses::wallet_addresses_subcommand;
+use crate::commands::wallet_balances_command::wallet_balances_subcommand;
 use clap::{App, AppSettings, Arg};
 use lazy_static::lazy_static;
 use masq_lib::constants::{DEFAULT_UI_PORT, HIGHEST_USABLE_PORT, LOWEST_USABLE_INSECURE_PORT};
@@ -48,16 +64,32 @@ pub fn app() -> App<'static, 'static> {
                 .validator(validate_ui_port)
                 .help(UI_PORT_HELP.as_str()),
         )
+        .subcommand(approve_debut_subcommand())
+        .subcommand(ban_subcommand())
+        .subcommand(banned_wallets_subcommand())
         .subcommand(set_password_subcommand())
         .subcommand(change_password_subcommand())
         .subcommand(check_password_subcommand())
+        .subcommand(connection_status_subcommand())
         .subcommand(crash_subcommand())
+        .subcommand(debut_allow_list_subcommand())
         .subcommand(descriptor_subcommand())
+        .subcommand(dns_bypass_subcommand())
+        .subcommand(exit_country_subcommand())
+        .subcommand(export_accounts_subcommand())
         .subcommand(generate_wallets_subcommand())
+        .subcommand(malefactor_bans_subcommand())
+        .subcommand(malefactor_unban_subcommand())
+        .subcommand(pending_debuts_subcommand())
+        .subcommand(reject_debut_subcommand())
+        .subcommand(scan_subcommand())
         .subcommand(setup_subcommand())
         .subcommand(start_subcommand())
+        .subcommand(status_subcommand())
         .subcommand(shutdown_subcommand())
+        .subcommand(unban_subcommand())
         .subcommand(wallet_addresses_subcommand())
+        .subcommand(wallet_balances_subcommand())
 }
 
 fn validate_ui_port(port: String) -> Result<(), String> {
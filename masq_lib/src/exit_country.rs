@@ -0,0 +1,136 @@
+// Copyright (c) 2019-2020, MASQ (https://masq.ai) and/or its affiliates. All rights reserved.
+
+use itertools::Itertools;
+use std::fmt::{Display, Formatter};
+use std::str::FromStr;
+
+// Which countries this Node's traffic should leave the MASQ Network from. If the preference is
+// strict, routes are built only through exit Nodes in those countries; otherwise such exit Nodes
+// are merely chosen ahead of the others.
+#[derive(Clone, Debug, PartialEq)]
+pub struct ExitCountryPreference {
+    pub countries: Vec<String>,
+    pub strict: bool,
+}
+
+pub const EXIT_COUNTRY_PREFER_PREFIX: &str = "prefer:";
+
+impl ExitCountryPreference {
+    pub fn new(countries: Vec<String>, strict: bool) -> Result<Self, String> {
+        if countries.is_empty() {
+            return Err("At least one exit country must be specified".to_string());
+        }
+        let countries = countries
+            .into_iter()
+            .map(|country| Self::normalize_country_code(&country))
+            .collect::<Result<Vec<String>, String>>()?;
+        Ok(ExitCountryPreference {
+            countries: countries.into_iter().unique().collect(),
+            strict,
+        })
+    }
+
+    pub fn normalize_country_code(country: &str) -> Result<String, String> {
+        let trimmed = country.trim();
+        if trimmed.len() == 2 && trimmed.chars().all(|c| c.is_ascii_alphabetic()) {
+            Ok(trimmed.to_ascii_uppercase())
+        } else {
+            Err(format!(
+                "Exit country must be a two-letter ISO 3166 country code, not '{}'",
+                country
+            ))
+        }
+    }
+
+    pub fn includes(&self, country: &str) -> bool {
+        self.countries
+            .iter()
+            .any(|c| c.eq_ignore_ascii_case(country))
+    }
+}
+
+impl Display for ExitCountryPreference {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        let prefix = if self.strict {
+            ""
+        } else {
+            EXIT_COUNTRY_PREFER_PREFIX
+        };
+        write!(f, "{}{}", prefix, self.countries.join(","))
+    }
+}
+
+impl FromStr for ExitCountryPreference {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let prefix_len = EXIT_COUNTRY_PREFER_PREFIX.len();
+        let (countries, strict) = match s.get(..prefix_len) {
+            Some(prefix) if prefix.eq_ignore_ascii_case(EXIT_COUNTRY_PREFER_PREFIX) => {
+                (&s[prefix_len..], false)
+            }
+            _ => (s, true),
+        };
+        ExitCountryPreference::new(
+            countries
+                .split(',')
+                .filter(|country| !country.trim().is_empty())
+                .map(|country| country.to_string())
+                .collect(),
+            strict,
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn exit_country_preference_parses_strict_and_preferred_forms() {
+        assert_eq!(
+            ExitCountryPreference::from_str("cz, SK,cz"),
+            Ok(ExitCountryPreference {
+                countries: vec!["CZ".to_string(), "SK".to_string()],
+                strict: true,
+            })
+        );
+        assert_eq!(
+            ExitCountryPreference::from_str("Prefer:de"),
+            Ok(ExitCountryPreference {
+                countries: vec!["DE".to_string()],
+                strict: false,
+            })
+        );
+    }
+
+    #[test]
+    fn exit_country_preference_round_trips_through_strings() {
+        vec!["CZ,SK", "prefer:DE,AT"].into_iter().for_each(|s| {
+            assert_eq!(
+                ExitCountryPreference::from_str(s).unwrap().to_string(),
+                s.to_string()
+            )
+        });
+    }
+
+    #[test]
+    fn exit_country_preference_rejects_bad_country_codes() {
+        assert_eq!(
+            ExitCountryPreference::from_str("CZE"),
+            Err("Exit country must be a two-letter ISO 3166 country code, not 'CZE'".to_string())
+        );
+        assert_eq!(
+            ExitCountryPreference::from_str("prefer:"),
+            Err("At least one exit country must be specified".to_string())
+        );
+    }
+
+    #[test]
+    fn exit_country_preference_includes_is_case_insensitive() {
+        let subject = ExitCountryPreference::from_str("CZ").unwrap();
+
+        assert_eq!(subject.includes("cz"), true);
+        assert_eq!(subject.includes("SK"), false);
+    }
+}
@@ -9,8 +9,10 @@ pub mod messages;
 pub mod command;
 pub mod constants;
 pub mod crash_point;
+pub mod exit_country;
 pub mod shared_schema;
 pub mod test_utils;
+pub mod ui_client;
 pub mod ui_gateway;
 pub mod ui_traffic_converter;
 pub mod utils;
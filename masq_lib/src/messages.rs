@@ -21,6 +21,9 @@ pub const NODE_ALREADY_RUNNING_ERROR: u64 = 0x8000_0000_0000_0003;
 pub const UNMARSHAL_ERROR: u64 = 0x8000_0000_0000_0004;
 pub const SETUP_ERROR: u64 = 0x8000_0000_0000_0005;
 pub const TIMEOUT_ERROR: u64 = 0x8000_0000_0000_0006;
+pub const RESTART_POLICY_ERROR: u64 = 0x8000_0000_0000_0007;
+pub const SETUP_PROFILE_ERROR: u64 = 0x8000_0000_0000_0008;
+pub const NODE_INSTANCE_ERROR: u64 = 0x8000_0000_0000_0009;
 
 #[derive(Clone, Debug, PartialEq)]
 pub enum UiMessageError {
@@ -334,6 +337,51 @@ impl UiSetupBroadcast {
     }
 }
 
+#[derive(Serialize, Deserialize, Debug, PartialEq, Clone)]
+pub struct UiSaveSetupProfileRequest {
+    pub name: String,
+}
+conversation_message!(UiSaveSetupProfileRequest, "saveSetupProfile");
+
+#[derive(Serialize, Deserialize, Debug, PartialEq, Clone)]
+pub struct UiSaveSetupProfileResponse {
+    pub excluded: Vec<String>,
+}
+conversation_message!(UiSaveSetupProfileResponse, "saveSetupProfile");
+
+#[derive(Serialize, Deserialize, Debug, PartialEq, Clone)]
+pub struct UiLoadSetupProfileRequest {
+    pub name: String,
+}
+conversation_message!(UiLoadSetupProfileRequest, "loadSetupProfile");
+
+#[derive(Serialize, Deserialize, Debug, PartialEq, Clone)]
+pub struct UiLoadSetupProfileResponse {
+    pub values: Vec<UiSetupResponseValue>,
+    pub errors: Vec<(String, String)>,
+}
+conversation_message!(UiLoadSetupProfileResponse, "loadSetupProfile");
+
+#[derive(Serialize, Deserialize, Debug, PartialEq, Clone)]
+pub struct UiSetupProfilesRequest {}
+conversation_message!(UiSetupProfilesRequest, "setupProfiles");
+
+#[derive(Serialize, Deserialize, Debug, PartialEq, Clone)]
+pub struct UiSetupProfilesResponse {
+    pub profiles: Vec<String>,
+}
+conversation_message!(UiSetupProfilesResponse, "setupProfiles");
+
+#[derive(Serialize, Deserialize, Debug, PartialEq, Clone)]
+pub struct UiDeleteSetupProfileRequest {
+    pub name: String,
+}
+conversation_message!(UiDeleteSetupProfileRequest, "deleteSetupProfile");
+
+#[derive(Serialize, Deserialize, Debug, PartialEq, Clone)]
+pub struct UiDeleteSetupProfileResponse {}
+conversation_message!(UiDeleteSetupProfileResponse, "deleteSetupProfile");
+
 #[derive(Debug, PartialEq, Clone)]
 pub struct UiSetupInner {
     pub running: bool,
@@ -351,6 +399,16 @@ impl From<UiSetupResponse> for UiSetupInner {
     }
 }
 
+impl From<UiLoadSetupProfileResponse> for UiSetupInner {
+    fn from(input: UiLoadSetupProfileResponse) -> Self {
+        Self {
+            running: false,
+            values: input.values,
+            errors: input.errors,
+        }
+    }
+}
+
 impl From<UiSetupBroadcast> for UiSetupInner {
     fn from(input: UiSetupBroadcast) -> Self {
         Self {
@@ -374,7 +432,7 @@ pub struct UiStartResponse {
 }
 conversation_message!(UiStartResponse, "start");
 
-#[derive(Serialize, Deserialize, Debug, PartialEq)]
+#[derive(Serialize, Deserialize, Debug, PartialEq, Clone)]
 pub enum CrashReason {
     ChildWaitFailure(String),
     NoInformation,
@@ -391,6 +449,147 @@ pub struct UiNodeCrashedBroadcast {
 }
 fire_and_forget_message!(UiNodeCrashedBroadcast, "crashed");
 
+#[derive(Serialize, Deserialize, Debug, PartialEq, Clone)]
+pub struct UiRestartPolicy {
+    #[serde(rename = "maxRestarts")]
+    pub max_restarts: u32,
+    #[serde(rename = "initialBackoffMs")]
+    pub initial_backoff_ms: u64,
+    #[serde(rename = "maxBackoffMs")]
+    pub max_backoff_ms: u64,
+    #[serde(rename = "crashLoopWindowSecs")]
+    pub crash_loop_window_secs: u64,
+}
+
+#[derive(Serialize, Deserialize, Debug, PartialEq, Clone)]
+pub struct UiRestartPolicyRequest {
+    pub policy: Option<UiRestartPolicy>,
+}
+conversation_message!(UiRestartPolicyRequest, "restartPolicy");
+
+#[derive(Serialize, Deserialize, Debug, PartialEq, Clone)]
+pub struct UiRestartPolicyResponse {
+    pub policy: UiRestartPolicy,
+}
+conversation_message!(UiRestartPolicyResponse, "restartPolicy");
+
+#[derive(Serialize, Deserialize, Debug, PartialEq, Clone)]
+pub struct UiNodeRestartingBroadcast {
+    #[serde(rename = "processId")]
+    pub process_id: u32,
+    pub attempt: u32,
+    #[serde(rename = "maxRestarts")]
+    pub max_restarts: u32,
+    #[serde(rename = "delayMs")]
+    pub delay_ms: u64,
+}
+fire_and_forget_message!(UiNodeRestartingBroadcast, "restarting");
+
+#[derive(Serialize, Deserialize, Debug, PartialEq, Clone)]
+pub struct UiNodeRestartedBroadcast {
+    #[serde(rename = "newProcessId")]
+    pub new_process_id: u32,
+    #[serde(rename = "redirectUiPort")]
+    pub redirect_ui_port: u16,
+}
+fire_and_forget_message!(UiNodeRestartedBroadcast, "restarted");
+
+#[derive(Serialize, Deserialize, Debug, PartialEq, Clone)]
+pub struct UiNodeRestartAbandonedBroadcast {
+    #[serde(rename = "processId")]
+    pub process_id: u32,
+    pub reason: String,
+}
+fire_and_forget_message!(UiNodeRestartAbandonedBroadcast, "restartAbandoned");
+
+#[derive(Serialize, Deserialize, Debug, PartialEq, Clone)]
+pub enum RestartOutcome {
+    NotAttempted,
+    Restarting,
+    Abandoned,
+}
+
+#[derive(Serialize, Deserialize, Debug, PartialEq, Clone)]
+pub struct UiCrashRecord {
+    pub instance: String,
+    #[serde(rename = "processId")]
+    pub process_id: u32,
+    #[serde(rename = "crashReason")]
+    pub crash_reason: CrashReason,
+    pub timestamp: u64,
+    pub outcome: RestartOutcome,
+}
+
+#[derive(Serialize, Deserialize, Debug, PartialEq, Clone)]
+pub struct UiCrashHistoryRequest {}
+conversation_message!(UiCrashHistoryRequest, "crashHistory");
+
+#[derive(Serialize, Deserialize, Debug, PartialEq, Clone)]
+pub struct UiCrashHistoryResponse {
+    pub crashes: Vec<UiCrashRecord>,
+}
+conversation_message!(UiCrashHistoryResponse, "crashHistory");
+
+#[derive(Serialize, Deserialize, Debug, PartialEq, Clone)]
+pub struct UiSelectInstanceRequest {
+    pub name: String,
+}
+conversation_message!(UiSelectInstanceRequest, "selectInstance");
+
+#[derive(Serialize, Deserialize, Debug, PartialEq, Clone)]
+pub struct UiSelectInstanceResponse {
+    pub created: bool,
+}
+conversation_message!(UiSelectInstanceResponse, "selectInstance");
+
+#[derive(Serialize, Deserialize, Debug, PartialEq, Clone)]
+pub struct UiNodeInstance {
+    pub name: String,
+    pub running: bool,
+    #[serde(rename = "processId")]
+    pub process_id_opt: Option<u32>,
+    #[serde(rename = "redirectUiPort")]
+    pub redirect_ui_port_opt: Option<u16>,
+    #[serde(rename = "chain")]
+    pub chain_opt: Option<String>,
+    #[serde(rename = "dataDirectory")]
+    pub data_directory_opt: Option<String>,
+}
+
+#[derive(Serialize, Deserialize, Debug, PartialEq, Clone)]
+pub struct UiInstancesRequest {}
+conversation_message!(UiInstancesRequest, "instances");
+
+#[derive(Serialize, Deserialize, Debug, PartialEq, Clone)]
+pub struct UiInstancesResponse {
+    pub selected: String,
+    pub instances: Vec<UiNodeInstance>,
+}
+conversation_message!(UiInstancesResponse, "instances");
+
+#[derive(Serialize, Deserialize, Debug, PartialEq, Clone)]
+pub struct UiStatusRequest {}
+conversation_message!(UiStatusRequest, "status");
+
+#[derive(Serialize, Deserialize, Debug, PartialEq, Clone)]
+pub struct UiStatusResponse {
+    pub running: bool,
+    pub version: String,
+    #[serde(rename = "processId")]
+    pub process_id_opt: Option<u32>,
+    #[serde(rename = "uptimeSecs")]
+    pub uptime_secs_opt: Option<u64>,
+    #[serde(rename = "chain")]
+    pub chain_opt: Option<String>,
+    #[serde(rename = "neighborhoodMode")]
+    pub neighborhood_mode_opt: Option<String>,
+    #[serde(rename = "uiPort")]
+    pub ui_port_opt: Option<u16>,
+    #[serde(rename = "lastCrashReason")]
+    pub last_crash_reason_opt: Option<CrashReason>,
+}
+conversation_message!(UiStatusResponse, "status");
+
 #[derive(Serialize, Deserialize, Debug, PartialEq)]
 pub struct UiRedirect {
     pub port: u16,
@@ -413,10 +612,79 @@ pub struct UiUnmarshalError {
 }
 fire_and_forget_message!(UiUnmarshalError, "unmarshalError");
 
+// Subscribing to or unsubscribing from this opcode means every broadcast opcode
+pub const ALL_BROADCASTS: &str = "*";
+
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+pub struct UiSubscribeRequest {
+    pub opcodes: Vec<String>,
+}
+conversation_message!(UiSubscribeRequest, "subscribe");
+
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+pub struct UiSubscribeResponse {
+    #[serde(rename = "allBroadcasts")]
+    pub all_broadcasts: bool,
+    pub opcodes: Vec<String>,
+}
+conversation_message!(UiSubscribeResponse, "subscribe");
+
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+pub struct UiUnsubscribeRequest {
+    pub opcodes: Vec<String>,
+}
+conversation_message!(UiUnsubscribeRequest, "unsubscribe");
+
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+pub struct UiUnsubscribeResponse {
+    #[serde(rename = "allBroadcasts")]
+    pub all_broadcasts: bool,
+    pub opcodes: Vec<String>,
+}
+conversation_message!(UiUnsubscribeResponse, "unsubscribe");
+
 ///////////////////////////////////////////////////////////////////
 // These messages are sent to or by the Node only
 ///////////////////////////////////////////////////////////////////
 
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+pub struct UiBanRequest {
+    pub wallet: String,
+    pub reason: String,
+}
+conversation_message!(UiBanRequest, "ban");
+
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+pub struct UiBanResponse {}
+conversation_message!(UiBanResponse, "ban");
+
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+pub struct UiBanChangedBroadcast {
+    pub wallet: String,
+    pub banned: bool,
+    #[serde(rename = "reasonOpt")]
+    pub reason_opt: Option<String>,
+}
+fire_and_forget_message!(UiBanChangedBroadcast, "banChanged");
+
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+pub struct UiBannedWalletsRequest {}
+conversation_message!(UiBannedWalletsRequest, "bannedWallets");
+
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+pub struct UiBannedWallet {
+    pub wallet: String,
+    #[serde(rename = "reasonOpt")]
+    pub reason_opt: Option<String>,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+pub struct UiBannedWalletsResponse {
+    #[serde(rename = "bannedWallets")]
+    pub banned_wallets: Vec<UiBannedWallet>,
+}
+conversation_message!(UiBannedWalletsResponse, "bannedWallets");
+
 #[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
 pub struct UiChangePasswordRequest {
     #[serde(rename = "oldPasswordOpt")]
@@ -460,6 +728,28 @@ pub struct UiWalletAddressesResponse {
 }
 conversation_message!(UiWalletAddressesResponse, "walletAddresses");
 
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+pub struct UiWalletBalancesRequest {}
+conversation_message!(UiWalletBalancesRequest, "walletBalances");
+
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+pub struct UiWalletBalances {
+    pub address: String,
+    #[serde(rename = "ethBalanceWei")]
+    pub eth_balance_wei: String,
+    #[serde(rename = "masqBalanceWei")]
+    pub masq_balance_wei: String,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+pub struct UiWalletBalancesResponse {
+    #[serde(rename = "consumingWalletOpt")]
+    pub consuming_wallet_opt: Option<UiWalletBalances>,
+    #[serde(rename = "earningWallet")]
+    pub earning_wallet: UiWalletBalances,
+}
+conversation_message!(UiWalletBalancesResponse, "walletBalances");
+
 #[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
 pub struct UiConfigurationChangedBroadcast {}
 fire_and_forget_message!(UiConfigurationChangedBroadcast, "configurationChanged");
@@ -492,6 +782,29 @@ pub struct UiConfigurationResponse {
 }
 conversation_message!(UiConfigurationResponse, "configuration");
 
+#[derive(Serialize, Deserialize, Debug, PartialEq, Clone, Copy)]
+pub enum UiConnectionStage {
+    NotConnected,
+    ConnectedToNeighbor,
+    ThreeHopsRouteFound,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+pub struct UiConnectionChangeBroadcast {
+    pub stage: UiConnectionStage,
+}
+fire_and_forget_message!(UiConnectionChangeBroadcast, "connectionChange");
+
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+pub struct UiConnectionStatusRequest {}
+conversation_message!(UiConnectionStatusRequest, "connectionStatus");
+
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+pub struct UiConnectionStatusResponse {
+    pub stage: UiConnectionStage,
+}
+conversation_message!(UiConnectionStatusResponse, "connectionStatus");
+
 #[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
 pub struct UiConsumingWallet {
     #[serde(rename = "derivationPath")]
@@ -506,6 +819,70 @@ pub struct UiEarningWallet {
     pub address: String,
 }
 
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+pub struct UiPendingDebut {
+    #[serde(rename = "publicKey")]
+    pub public_key: String,
+    #[serde(rename = "nodeAddress")]
+    pub node_address: String,
+    #[serde(rename = "earningWallet")]
+    pub earning_wallet: String,
+    #[serde(rename = "receivedAt")]
+    pub received_at: u64,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+pub struct UiDebutPendingBroadcast {
+    pub debut: UiPendingDebut,
+}
+fire_and_forget_message!(UiDebutPendingBroadcast, "debutPending");
+
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+pub struct UiPendingDebutsRequest {}
+conversation_message!(UiPendingDebutsRequest, "pendingDebuts");
+
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+pub struct UiPendingDebutsResponse {
+    pub debuts: Vec<UiPendingDebut>,
+}
+conversation_message!(UiPendingDebutsResponse, "pendingDebuts");
+
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+pub struct UiApproveDebutRequest {
+    #[serde(rename = "publicKey")]
+    pub public_key: String,
+}
+conversation_message!(UiApproveDebutRequest, "approveDebut");
+
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+pub struct UiApproveDebutResponse {}
+conversation_message!(UiApproveDebutResponse, "approveDebut");
+
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+pub struct UiRejectDebutRequest {
+    #[serde(rename = "publicKey")]
+    pub public_key: String,
+}
+conversation_message!(UiRejectDebutRequest, "rejectDebut");
+
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+pub struct UiRejectDebutResponse {}
+conversation_message!(UiRejectDebutResponse, "rejectDebut");
+
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+pub struct UiDebutAllowListRequest {
+    pub add: Vec<String>,
+    pub remove: Vec<String>,
+}
+conversation_message!(UiDebutAllowListRequest, "debutAllowList");
+
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+pub struct UiDebutAllowListResponse {
+    #[serde(rename = "publicKeys")]
+    pub public_keys: Vec<String>,
+}
+conversation_message!(UiDebutAllowListResponse, "debutAllowList");
+
 #[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
 pub struct UiDescriptorRequest {}
 conversation_message!(UiDescriptorRequest, "descriptor");
@@ -517,6 +894,46 @@ pub struct UiDescriptorResponse {
 }
 conversation_message!(UiDescriptorResponse, "descriptor");
 
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+pub struct UiExitCountryRequest {
+    pub countries: Vec<String>,
+    pub strict: bool,
+}
+conversation_message!(UiExitCountryRequest, "exitCountry");
+
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+pub struct UiExitCountryResponse {}
+conversation_message!(UiExitCountryResponse, "exitCountry");
+
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+pub struct UiDnsBypassRequest {
+    pub add: Vec<String>,
+    pub remove: Vec<String>,
+}
+conversation_message!(UiDnsBypassRequest, "dnsBypass");
+
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+pub struct UiDnsBypassResponse {
+    pub domains: Vec<String>,
+}
+conversation_message!(UiDnsBypassResponse, "dnsBypass");
+
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+pub struct UiDroppedGossipRequest {}
+conversation_message!(UiDroppedGossipRequest, "droppedGossip");
+
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+pub struct UiDroppedGossipResponse {
+    #[serde(rename = "tooManyRecords")]
+    pub too_many_records: u64,
+    pub duplicate: u64,
+    #[serde(rename = "sourceRateExceeded")]
+    pub source_rate_exceeded: u64,
+    #[serde(rename = "globalRateExceeded")]
+    pub global_rate_exceeded: u64,
+}
+conversation_message!(UiDroppedGossipResponse, "droppedGossip");
+
 #[derive(Serialize, Deserialize, Debug, PartialEq)]
 pub struct UiPayableAccount {
     pub wallet: String,
@@ -533,6 +950,36 @@ pub struct UiReceivableAccount {
     pub amount: u64,
 }
 
+#[derive(Serialize, Deserialize, Debug, PartialEq, Clone)]
+pub struct UiExportAccountsRequest {
+    #[serde(rename = "sinceOpt")]
+    pub since_opt: Option<u64>,
+}
+conversation_message!(UiExportAccountsRequest, "exportAccounts");
+
+#[derive(Serialize, Deserialize, Debug, PartialEq, Clone)]
+pub struct UiExportedAccount {
+    pub wallet: String,
+    #[serde(rename = "balanceGwub")]
+    pub balance_gwub: i64,
+    #[serde(rename = "lastTransactionTime")]
+    pub last_transaction_time: u64,
+    #[serde(rename = "pendingTransactionOpt")]
+    pub pending_transaction_opt: Option<String>,
+    pub banned: bool,
+    #[serde(rename = "banReasonOpt")]
+    pub ban_reason_opt: Option<String>,
+}
+
+#[derive(Serialize, Deserialize, Debug, PartialEq, Clone)]
+pub struct UiExportAccountsResponse {
+    pub payables: Vec<UiExportedAccount>,
+    pub receivables: Vec<UiExportedAccount>,
+    #[serde(rename = "bannedWallets")]
+    pub banned_wallets: Vec<UiBannedWallet>,
+}
+conversation_message!(UiExportAccountsResponse, "exportAccounts");
+
 #[derive(Serialize, Deserialize, Debug, PartialEq, Clone)]
 pub struct UiFinancialsRequest {
     #[serde(rename = "payableMinimumAmount")]
@@ -585,6 +1032,42 @@ pub struct UiGenerateWalletsResponse {
 }
 conversation_message!(UiGenerateWalletsResponse, "generateWallets");
 
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+pub struct UiMalefactorBansRequest {}
+conversation_message!(UiMalefactorBansRequest, "malefactorBans");
+
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+pub struct UiMalefactorBan {
+    pub id: u64,
+    #[serde(rename = "publicKeyOpt")]
+    pub public_key_opt: Option<String>,
+    #[serde(rename = "ipAddressOpt")]
+    pub ip_address_opt: Option<String>,
+    #[serde(rename = "earningWalletOpt")]
+    pub earning_wallet_opt: Option<String>,
+    pub reason: String,
+    #[serde(rename = "bannedAt")]
+    pub banned_at: u64,
+    #[serde(rename = "expiresAtOpt")]
+    pub expires_at_opt: Option<u64>,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+pub struct UiMalefactorBansResponse {
+    pub bans: Vec<UiMalefactorBan>,
+}
+conversation_message!(UiMalefactorBansResponse, "malefactorBans");
+
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+pub struct UiMalefactorUnbanRequest {
+    pub id: u64,
+}
+conversation_message!(UiMalefactorUnbanRequest, "malefactorUnban");
+
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+pub struct UiMalefactorUnbanResponse {}
+conversation_message!(UiMalefactorUnbanResponse, "malefactorUnban");
+
 #[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
 pub struct UiNewPasswordBroadcast {}
 fire_and_forget_message!(UiNewPasswordBroadcast, "newPassword");
@@ -610,6 +1093,34 @@ conversation_message!(UiRecoverWalletsRequest, "recoverWallet");
 pub struct UiRecoverWalletsResponse {}
 conversation_message!(UiRecoverWalletsResponse, "recoverWallet");
 
+#[derive(Serialize, Deserialize, Debug, PartialEq, Clone, Copy)]
+pub enum ScanType {
+    Payables,
+    Receivables,
+}
+
+#[derive(Serialize, Deserialize, Debug, PartialEq, Clone)]
+pub struct UiScanRequest {
+    #[serde(rename = "scanType")]
+    pub scan_type: ScanType,
+}
+conversation_message!(UiScanRequest, "scan");
+
+#[derive(Serialize, Deserialize, Debug, PartialEq, Clone)]
+pub struct UiScanResponse {}
+conversation_message!(UiScanResponse, "scan");
+
+#[derive(Serialize, Deserialize, Debug, PartialEq, Clone)]
+pub struct UiScanResultsBroadcast {
+    #[serde(rename = "scanType")]
+    pub scan_type: ScanType,
+    #[serde(rename = "transactionCount")]
+    pub transaction_count: usize,
+    #[serde(rename = "errorOpt")]
+    pub error_opt: Option<String>,
+}
+fire_and_forget_message!(UiScanResultsBroadcast, "scanResults");
+
 #[derive(Serialize, Deserialize, Debug, PartialEq, Clone)]
 pub struct UiShutdownRequest {}
 conversation_message!(UiShutdownRequest, "shutdown");
@@ -618,6 +1129,16 @@ conversation_message!(UiShutdownRequest, "shutdown");
 pub struct UiShutdownResponse {}
 conversation_message!(UiShutdownResponse, "shutdown");
 
+#[derive(Serialize, Deserialize, Debug, PartialEq, Clone)]
+pub struct UiUnbanRequest {
+    pub wallet: String,
+}
+conversation_message!(UiUnbanRequest, "unban");
+
+#[derive(Serialize, Deserialize, Debug, PartialEq, Clone)]
+pub struct UiUnbanResponse {}
+conversation_message!(UiUnbanResponse, "unban");
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -1,5 +1,5 @@
 use crate::constants::{
-    DEFAULT_CHAIN_NAME, DEFAULT_GAS_PRICE, DEFAULT_UI_PORT, HIGHEST_USABLE_PORT,
+    DEFAULT_GAS_PRICE, DEFAULT_PAYMENT_CURVES, DEFAULT_UI_PORT, HIGHEST_USABLE_PORT,
     LOWEST_USABLE_INSECURE_PORT,
 };
 use crate::crash_point::CrashPoint;
@@ -10,9 +10,21 @@ pub const BLOCKCHAIN_SERVICE_HELP: &str =
     "The Ethereum client you wish to use to provide Blockchain \
      exit services from your MASQ Node (e.g. http://localhost:8545, \
      https://ropsten.infura.io/v3/YOUR-PROJECT-ID, https://mainnet.infura.io/v3/YOUR-PROJECT-ID).";
+pub const BOOTSTRAP_PUBLIC_KEY_HELP: &str =
+    "The base64-encoded public key of whoever signs the list at --bootstrap-source. If you supply \
+     this, your MASQ Node will use the list only if its signature matches; if you don't, unsigned \
+     lists are accepted too.";
+pub const BOOTSTRAP_SOURCE_HELP: &str =
+    "A file path or http:// or https:// URL where your MASQ Node can find a list of Node descriptors, \
+     one per line. If your Node has no --neighbors and no usable neighbors from earlier runs, it \
+     will try to debut to the Nodes on this list, in random order, until one of them accepts. \
+     Use --bootstrap-public-key to make sure the list hasn't been tampered with.";
 pub const CHAIN_HELP: &str =
     "The blockchain network MASQ Node will configure itself to use. You must ensure the \
-    Ethereum client specified by --blockchain-service-url communicates with the same blockchain network.";
+    Ethereum client specified by --blockchain-service-url communicates with the same blockchain network. \
+    Built-in networks are mainnet, ropsten, rinkeby, and dev; other networks may be defined in a chains.toml \
+    file in the --data-directory, each with a name, chain-id, contract-address, contract-creation-block, and \
+    optional mainnet flag.";
 pub const CONFIG_FILE_HELP: &str =
     "Optional TOML file containing configuration that doesn't often change. Should contain only \
      scalar items, string or numeric, whose names are exactly the same as the command-line parameters \
@@ -34,6 +46,24 @@ pub const DB_PASSWORD_HELP: &str =
     "A password or phrase to decrypt the encrypted material in the database, to include your \
      mnemonic seed (if applicable) and your list of previous neighbors. If you don't provide this \
      password, none of the encrypted data in your database will be used.";
+pub const DEBUT_APPROVAL_HELP: &str =
+    "Whether your MASQ Node accepts debuts from new neighbors automatically, or holds them until you \
+     approve or reject them through the UI. Debuts from Nodes on your debut allow-list are always \
+     accepted.";
+pub const DNS_BYPASS_HELP: &str =
+    "Domains whose names your MASQ Node should not resolve to itself, separated by commas (e.g. \
+     corp.example.com,lan). A domain covers its subdomains as well; *.corp.example.com covers only \
+     the subdomains. Look-ups for these domains are passed to the servers in --dns-bypass-servers, \
+     so that traffic to them goes straight to its destination instead of through the MASQ Network.";
+pub const DNS_BYPASS_SERVERS_HELP: &str =
+    "IP addresses of the DNS servers that should answer look-ups for the domains in --dns-bypass: \
+     usually the ones your computer used before MASQ took over its DNS (e.g. 192.168.1.1). If you \
+     don't supply these, the --dns-servers are used.";
+pub const DNS_OTHER_RECORDS_HELP: &str =
+    "What your MASQ Node's DNS server does with look-ups it can't answer for itself: those for record \
+     types such as MX or TXT, and those outside the Internet class. With empty, the default, it says \
+     there are no such records; with nxdomain, it says the name doesn't exist; with forward, it passes \
+     them to the servers in --dns-bypass-servers, which reveals those look-ups outside the MASQ Network.";
 pub const DNS_SERVERS_HELP: &str =
     "IP addresses of DNS Servers for host name look-up while providing exit \
      services for other MASQ Nodes (e.g. 1.0.0.1,1.1.1.1,8.8.8.8,9.9.9.9, etc.)";
@@ -42,6 +72,12 @@ pub const EARNING_WALLET_HELP: &str =
      (case-insensitive). If you already have a derivation-path earning wallet, don't supply this. \
      If you have supplied an earning wallet address before, either don't supply it again or be \
      careful to supply exactly the same one you supplied before.";
+pub const EXIT_COUNTRY_HELP: &str =
+    "Two-letter ISO 3166 codes of the countries where your traffic should leave the MASQ Network, \
+     separated by commas (e.g. CZ,SK). Routes will be built only through exit Nodes in those \
+     countries; if you'd rather just prefer them, and accept exit Nodes elsewhere when none of them \
+     can be reached, start the list with prefer: (e.g. prefer:CZ,SK). A Node's country comes from \
+     the geoip.csv database in your data directory, if there is one, or else from the Node itself.";
 pub const IP_ADDRESS_HELP: &str = "The public IP address of your MASQ Node: that is, the IPv4 \
      address at which other Nodes can contact yours. If you're running your Node behind \
      a router, this will be the IP address of the router. If this IP address starts with 192.168 or 10.0, \
@@ -106,6 +142,16 @@ lazy_static! {
        "The Gas Price is the amount of Gwei you will pay per unit of gas used in a transaction. \
        If left unspecified, MASQ Node will use the previously stored value (Default {}). Valid range is 1-99 Gwei.",
        DEFAULT_GAS_PRICE);
+    pub static ref PAYMENT_CURVES_HELP: String = format!(
+       "The thresholds that govern when your Node pays its debts and when it bans Nodes that don't pay theirs, \
+       as six integers separated by '|': seconds after the last payment before a payment is suggested, \
+       seconds of grace after that before a debtor is banned, permanent debt allowed in Gwub, balance in Gwub \
+       from which the payment threshold decreases, seconds over which it decreases to the permanent debt allowed, \
+       and balance in Gwub below which a banned debtor is unbanned. The decrease period must be longer than the \
+       suggestion period, the decreasing balance must be larger than the permanent debt allowed, and the unban \
+       balance must be no larger than the permanent debt allowed. If left unspecified, MASQ Node will use the \
+       previously stored value (Default {}).",
+       DEFAULT_PAYMENT_CURVES);
 }
 
 // These Args are needed in more than one clap schema. To avoid code duplication, they're defined here and referred
@@ -138,7 +184,7 @@ pub fn chain_arg<'a>() -> Arg<'a, 'a> {
         .value_name("CHAIN")
         .min_values(0)
         .max_values(1)
-        .possible_values(&["dev", DEFAULT_CHAIN_NAME, "ropsten", "rinkeby"])
+        .validator(common_validators::validate_chain_name)
         .help(CHAIN_HELP)
 }
 
@@ -210,6 +256,24 @@ pub fn shared_app(head: App<'static, 'static>) -> App<'static, 'static> {
             .max_values(1)
             .help(BLOCKCHAIN_SERVICE_HELP),
     )
+    .arg(
+        Arg::with_name("bootstrap-public-key")
+            .long("bootstrap-public-key")
+            .value_name("PUBLIC-KEY")
+            .min_values(0)
+            .max_values(1)
+            .validator(common_validators::validate_public_key)
+            .help(BOOTSTRAP_PUBLIC_KEY_HELP),
+    )
+    .arg(
+        Arg::with_name("bootstrap-source")
+            .long("bootstrap-source")
+            .empty_values(false)
+            .value_name("PATH-OR-URL")
+            .min_values(0)
+            .max_values(1)
+            .help(BOOTSTRAP_SOURCE_HELP),
+    )
     .arg(
         Arg::with_name("clandestine-port")
             .long("clandestine-port")
@@ -241,6 +305,44 @@ pub fn shared_app(head: App<'static, 'static>) -> App<'static, 'static> {
     )
     .arg(data_directory_arg())
     .arg(db_password_arg(DB_PASSWORD_HELP))
+    .arg(
+        Arg::with_name("debut-approval")
+            .long("debut-approval")
+            .value_name("DEBUT-APPROVAL")
+            .min_values(0)
+            .max_values(1)
+            .possible_values(&["automatic", "manual"])
+            .case_insensitive(true)
+            .help(DEBUT_APPROVAL_HELP),
+    )
+    .arg(
+        Arg::with_name("dns-bypass")
+            .long("dns-bypass")
+            .value_name("DNS-BYPASS")
+            .min_values(0)
+            .max_values(1)
+            .validator(common_validators::validate_dns_bypass_domains)
+            .help(DNS_BYPASS_HELP),
+    )
+    .arg(
+        Arg::with_name("dns-bypass-servers")
+            .long("dns-bypass-servers")
+            .value_name("DNS-BYPASS-SERVERS")
+            .min_values(0)
+            .max_values(1)
+            .validator(common_validators::validate_ip_addresses)
+            .help(DNS_BYPASS_SERVERS_HELP),
+    )
+    .arg(
+        Arg::with_name("dns-other-records")
+            .long("dns-other-records")
+            .value_name("DNS-OTHER-RECORDS")
+            .min_values(0)
+            .max_values(1)
+            .possible_values(&["empty", "forward", "nxdomain"])
+            .case_insensitive(true)
+            .help(DNS_OTHER_RECORDS_HELP),
+    )
     .arg(
         Arg::with_name("dns-servers")
             .long("dns-servers")
@@ -255,6 +357,15 @@ pub fn shared_app(head: App<'static, 'static>) -> App<'static, 'static> {
         common_validators::validate_ethereum_address,
     ))
     .arg(chain_arg())
+    .arg(
+        Arg::with_name("exit-country")
+            .long("exit-country")
+            .value_name("EXIT-COUNTRY")
+            .min_values(0)
+            .max_values(1)
+            .validator(common_validators::validate_exit_country)
+            .help(EXIT_COUNTRY_HELP),
+    )
     .arg(
         Arg::with_name("fake-public-key")
             .long("fake-public-key")
@@ -308,11 +419,21 @@ pub fn shared_app(head: App<'static, 'static>) -> App<'static, 'static> {
             .min_values(0)
             .help(NEIGHBORS_HELP),
     )
+    .arg(
+        Arg::with_name("payment-curves")
+            .long("payment-curves")
+            .value_name("PAYMENT-CURVES")
+            .min_values(0)
+            .max_values(1)
+            .validator(common_validators::validate_payment_curves)
+            .help(&PAYMENT_CURVES_HELP),
+    )
     .arg(real_user_arg())
 }
 
 pub mod common_validators {
     use crate::constants::LOWEST_USABLE_INSECURE_PORT;
+    use crate::exit_country::ExitCountryPreference;
     use regex::Regex;
     use std::net::IpAddr;
     use std::str::FromStr;
@@ -367,6 +488,55 @@ pub mod common_validators {
         }
     }
 
+    pub fn validate_payment_curves(payment_curves: String) -> Result<(), String> {
+        let values = payment_curves.split('|').collect::<Vec<&str>>();
+        let is_nonnegative = |value: &&str| matches!(value.parse::<i64>(), Ok(n) if n >= 0);
+        if values.len() == 6 && values.iter().all(is_nonnegative) {
+            Ok(())
+        } else {
+            Err(payment_curves)
+        }
+    }
+
+    pub fn validate_public_key(key: String) -> Result<(), String> {
+        if Regex::new("^[a-zA-Z0-9+/]+={0,2}$")
+            .expect("Failed to compile regular expression")
+            .is_match(&key)
+        {
+            Ok(())
+        } else {
+            Err(key)
+        }
+    }
+
+    pub fn validate_exit_country(value: String) -> Result<(), String> {
+        match ExitCountryPreference::from_str(&value) {
+            Ok(_) => Ok(()),
+            Err(_) => Err(value),
+        }
+    }
+
+    pub fn validate_dns_bypass_domain(domain: String) -> Result<(), String> {
+        if Regex::new(r"^(\*\.)?[a-zA-Z0-9_-]{1,63}(\.[a-zA-Z0-9_-]{1,63})*\.?$")
+            .expect("Failed to compile regular expression")
+            .is_match(&domain)
+        {
+            Ok(())
+        } else {
+            Err(domain)
+        }
+    }
+
+    pub fn validate_dns_bypass_domains(domains: String) -> Result<(), String> {
+        match domains
+            .split(',')
+            .find(|domain| validate_dns_bypass_domain(domain.to_string()).is_err())
+        {
+            None => Ok(()),
+            Some(_) => Err(domains),
+        }
+    }
+
     pub fn validate_earning_wallet(value: String) -> Result<(), String> {
         validate_ethereum_address(value.clone()).or_else(|_| validate_derivation_path(value))
     }
@@ -420,6 +590,17 @@ pub mod common_validators {
         }
     }
 
+    pub fn validate_chain_name(name: String) -> Result<(), String> {
+        if Regex::new("^[A-Za-z0-9_-]+$")
+            .expect("Failed to compile regular expression")
+            .is_match(&name)
+        {
+            Ok(())
+        } else {
+            Err(name)
+        }
+    }
+
     pub fn validate_ui_port(port: String) -> Result<(), String> {
         match str::parse::<u16>(&port) {
             Ok(port_number) if port_number < LOWEST_USABLE_INSECURE_PORT => Err(port),
@@ -474,6 +655,34 @@ impl ConfiguratorError {
 mod tests {
     use crate::shared_schema::common_validators;
 
+    #[test]
+    fn validate_chain_name_accepts_built_in_and_user_defined_names() {
+        assert_eq!(
+            common_validators::validate_chain_name("mainnet".to_string()),
+            Ok(())
+        );
+        assert_eq!(
+            common_validators::validate_chain_name("Private_DevNet-2".to_string()),
+            Ok(())
+        );
+    }
+
+    #[test]
+    fn validate_chain_name_rejects_names_that_would_make_bad_directories() {
+        assert_eq!(
+            common_validators::validate_chain_name("".to_string()),
+            Err("".to_string())
+        );
+        assert_eq!(
+            common_validators::validate_chain_name("../booga".to_string()),
+            Err("../booga".to_string())
+        );
+        assert_eq!(
+            common_validators::validate_chain_name("dev net".to_string()),
+            Err("dev net".to_string())
+        );
+    }
+
     #[test]
     fn validate_private_key_requires_a_key_that_is_64_characters_long() {
         let result = common_validators::validate_private_key(String::from("42"));
@@ -575,6 +784,133 @@ mod tests {
         assert_eq!(Ok(()), result);
     }
 
+    #[test]
+    fn validate_public_key_accepts_base64_with_or_without_padding() {
+        assert_eq!(
+            common_validators::validate_public_key("AQIDBA".to_string()),
+            Ok(())
+        );
+        assert_eq!(
+            common_validators::validate_public_key("AQIDBA==".to_string()),
+            Ok(())
+        );
+    }
+
+    #[test]
+    fn validate_public_key_rejects_non_base64() {
+        vec!["", "AQ ID", "AQID!A", "=AQIDBA"]
+            .into_iter()
+            .for_each(|value| {
+                assert_eq!(
+                    common_validators::validate_public_key(value.to_string()),
+                    Err(value.to_string()),
+                    "{}",
+                    value
+                )
+            });
+    }
+
+    #[test]
+    fn validate_exit_country_accepts_strict_and_preferred_lists() {
+        assert_eq!(
+            common_validators::validate_exit_country("CZ".to_string()),
+            Ok(())
+        );
+        assert_eq!(
+            common_validators::validate_exit_country("cz,SK".to_string()),
+            Ok(())
+        );
+        assert_eq!(
+            common_validators::validate_exit_country("Prefer:DE,AT".to_string()),
+            Ok(())
+        );
+    }
+
+    #[test]
+    fn validate_exit_country_rejects_malformed_lists() {
+        vec!["", "CZE", ",", "CZ;SK", "prefer:", "anywhere:CZ"]
+            .into_iter()
+            .for_each(|value| {
+                assert_eq!(
+                    common_validators::validate_exit_country(value.to_string()),
+                    Err(value.to_string()),
+                    "{}",
+                    value
+                )
+            });
+    }
+
+    #[test]
+    fn validate_dns_bypass_domains_accepts_domains_and_wildcards() {
+        vec![
+            "lan",
+            "corp.example.com",
+            "*.corp.example.com,lan",
+            "Intranet.",
+            "my_host-1.lan",
+        ]
+        .into_iter()
+        .for_each(|value| {
+            assert_eq!(
+                common_validators::validate_dns_bypass_domains(value.to_string()),
+                Ok(()),
+                "{}",
+                value
+            )
+        });
+    }
+
+    #[test]
+    fn validate_dns_bypass_domains_rejects_malformed_domains() {
+        vec![
+            "",
+            "corp..com",
+            "corp.com,",
+            "*corp.com",
+            "corp.*.com",
+            "corp com",
+            "*",
+        ]
+        .into_iter()
+        .for_each(|value| {
+            assert_eq!(
+                common_validators::validate_dns_bypass_domains(value.to_string()),
+                Err(value.to_string()),
+                "{}",
+                value
+            )
+        });
+    }
+
+    #[test]
+    fn validate_payment_curves_accepts_six_nonnegative_integers() {
+        let result = common_validators::validate_payment_curves(
+            "86400|86400|10000000|1000000000|2592000|0".to_string(),
+        );
+
+        assert_eq!(result, Ok(()));
+    }
+
+    #[test]
+    fn validate_payment_curves_rejects_wrong_count_and_bad_numbers() {
+        assert_eq!(
+            common_validators::validate_payment_curves("1|2|3|4|5".to_string()),
+            Err("1|2|3|4|5".to_string())
+        );
+        assert_eq!(
+            common_validators::validate_payment_curves("1|2|3|4|5|6|7".to_string()),
+            Err("1|2|3|4|5|6|7".to_string())
+        );
+        assert_eq!(
+            common_validators::validate_payment_curves("1|2|-3|4|5|6".to_string()),
+            Err("1|2|-3|4|5|6".to_string())
+        );
+        assert_eq!(
+            common_validators::validate_payment_curves("1|2|booga|4|5|6".to_string()),
+            Err("1|2|booga|4|5|6".to_string())
+        );
+    }
+
     #[test]
     fn validate_gas_price_zero() {
         let result = common_validators::validate_gas_price("0".to_string());
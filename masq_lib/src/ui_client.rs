@@ -0,0 +1,642 @@
+// Copyright (c) 2019-2020, MASQ (https://masq.ai) and/or its affiliates. All rights reserved.
+
+use crate::messages::*;
+use crate::ui_gateway::{MessageBody, MessagePath};
+use crate::ui_traffic_converter::UiTrafficConverter;
+use crate::utils::localhost;
+use crossbeam_channel::{unbounded, Receiver, RecvTimeoutError, Sender};
+use std::collections::HashMap;
+use std::fmt;
+use std::net::TcpStream;
+use std::sync::atomic::{AtomicBool, AtomicU64, Ordering};
+use std::sync::{Arc, Mutex};
+use std::thread;
+use std::time::Duration;
+use websocket::receiver::Reader;
+use websocket::sender::Writer;
+use websocket::sync::Client;
+use websocket::ws::receiver::Receiver as WsReceiver;
+use websocket::ws::sender::Sender as WsSender;
+use websocket::{ClientBuilder, OwnedMessage, WebSocketResult};
+
+pub const DEFAULT_REQUEST_TIMEOUT_MILLIS: u64 = 1000;
+pub const FALLBACK_TIMEOUT_MILLIS: u64 = 500;
+
+#[derive(Clone, Debug, PartialEq)]
+pub enum UiClientError {
+    ConnectionRefused(u16, String),
+    ConnectionDropped(String),
+    Timeout(u64),
+    PayloadError(u64, String),
+    UnexpectedMessage(String),
+    DeserializationError(String),
+}
+
+impl fmt::Display for UiClientError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            UiClientError::ConnectionRefused(port, msg) => {
+                write!(f, "Couldn't connect to port {}: {}", port, msg)
+            }
+            UiClientError::ConnectionDropped(msg) => write!(f, "Connection dropped: {}", msg),
+            UiClientError::Timeout(millis) => write!(f, "No answer after {}ms", millis),
+            UiClientError::PayloadError(code, msg) => write!(
+                f,
+                "Daemon or Node complained about your request. Error code {}: {}",
+                code, msg
+            ),
+            UiClientError::UnexpectedMessage(msg) => write!(f, "{}", msg),
+            UiClientError::DeserializationError(msg) => write!(
+                f,
+                "Could not deserialize message from Daemon or Node: {}",
+                msg
+            ),
+        }
+    }
+}
+
+impl From<UiMessageError> for UiClientError {
+    fn from(error: UiMessageError) -> Self {
+        match error {
+            UiMessageError::PayloadError(code, msg) => UiClientError::PayloadError(code, msg),
+            UiMessageError::DeserializationError(msg) => UiClientError::DeserializationError(msg),
+            e @ UiMessageError::UnexpectedMessage(_, _) => {
+                UiClientError::UnexpectedMessage(e.to_string())
+            }
+        }
+    }
+}
+
+// Pairs a conversational request with the response the Daemon or Node sends back for it.
+pub trait UiRequest: ToMessageBody {
+    type Response: FromMessageBody;
+}
+
+macro_rules! ui_requests {
+    ($($method: ident: $request: ty => $response: ty),* $(,)?) => {
+        $(
+            impl UiRequest for $request {
+                type Response = $response;
+            }
+        )*
+
+        impl UiClient {
+            $(
+                pub fn $method(&self, request: $request) -> Result<$response, UiClientError> {
+                    self.request(request)
+                }
+            )*
+        }
+
+        #[cfg(test)]
+        fn request_opcode_pairs() -> Vec<(&'static str, &'static str)> {
+            vec![$((<$request>::type_opcode(), <$response>::type_opcode())),*]
+        }
+    };
+}
+
+// A client for the UI protocol, for UIs written in Rust. It connects to the Daemon, follows the
+// Daemon's redirects to the Node, and falls back to the Daemon if the Node goes away, the same
+// way masq does. Broadcasts from whichever one it's connected to go to every subscriber.
+pub struct UiClient {
+    shared: Arc<Shared>,
+    timeout_millis: u64,
+    next_context_id: AtomicU64,
+}
+
+impl Drop for UiClient {
+    fn drop(&mut self) {
+        self.close();
+    }
+}
+
+impl UiClient {
+    pub fn connect(daemon_ui_port: u16) -> Result<UiClient, UiClientError> {
+        Self::connect_with_timeout(daemon_ui_port, DEFAULT_REQUEST_TIMEOUT_MILLIS)
+    }
+
+    pub fn connect_with_timeout(
+        daemon_ui_port: u16,
+        timeout_millis: u64,
+    ) -> Result<UiClient, UiClientError> {
+        let shared = Arc::new(Shared {
+            daemon_port: daemon_ui_port,
+            next_connection_id: AtomicU64::new(0),
+            connection: Mutex::new(None),
+            pending: Mutex::new(HashMap::new()),
+            subscribers: Mutex::new(vec![]),
+            closed: AtomicBool::new(false),
+        });
+        connect(&shared, daemon_ui_port, timeout_millis)?;
+        Ok(UiClient {
+            shared,
+            timeout_millis,
+            next_context_id: AtomicU64::new(1),
+        })
+    }
+
+    pub fn active_port(&self) -> Option<u16> {
+        self.shared
+            .connection
+            .lock()
+            .expect("UiClient connection is poisoned")
+            .as_ref()
+            .map(|connection| connection.port)
+    }
+
+    pub fn subscribe(&self) -> Receiver<MessageBody> {
+        let (tx, rx) = unbounded();
+        self.shared
+            .subscribers
+            .lock()
+            .expect("UiClient subscribers are poisoned")
+            .push(tx);
+        rx
+    }
+
+    pub fn request<R: UiRequest>(&self, request: R) -> Result<R::Response, UiClientError> {
+        let context_id = self.next_context_id.fetch_add(1, Ordering::SeqCst);
+        let body = request.tmb(context_id);
+        let mut redirected = false;
+        loop {
+            let response = self.converse(context_id, body.clone())?;
+            match UiRedirect::fmb(response.clone()) {
+                Ok((redirect, _)) if !redirected => {
+                    redirected = true;
+                    connect(&self.shared, redirect.port, self.timeout_millis)?;
+                }
+                Ok(_) => {
+                    return Err(UiClientError::UnexpectedMessage(format!(
+                        "Redirected again after redirect for '{}'",
+                        body.opcode
+                    )))
+                }
+                Err(_) => {
+                    return R::Response::fmb(response)
+                        .map(|(response, _)| response)
+                        .map_err(UiClientError::from)
+                }
+            }
+        }
+    }
+
+    pub fn send<T: ToMessageBody>(&self, message: T) -> Result<(), UiClientError> {
+        self.ensure_connected()?;
+        let mut connection_guard = self
+            .shared
+            .connection
+            .lock()
+            .expect("UiClient connection is poisoned");
+        match connection_guard.as_mut() {
+            Some(connection) => connection.send(message.tmb(0)),
+            None => Err(UiClientError::ConnectionDropped(
+                "No connection to the Daemon or Node".to_string(),
+            )),
+        }
+    }
+
+    pub fn close(&self) {
+        if self.shared.closed.swap(true, Ordering::SeqCst) {
+            return;
+        }
+        if let Some(mut connection) = self
+            .shared
+            .connection
+            .lock()
+            .expect("UiClient connection is poisoned")
+            .take()
+        {
+            connection.close();
+        }
+    }
+
+    fn ensure_connected(&self) -> Result<(), UiClientError> {
+        if self.shared.closed.load(Ordering::SeqCst) {
+            return Err(UiClientError::ConnectionDropped(
+                "UiClient is closed".to_string(),
+            ));
+        }
+        if self.active_port().is_none() {
+            connect(&self.shared, self.shared.daemon_port, self.timeout_millis)?;
+        }
+        Ok(())
+    }
+
+    fn converse(&self, context_id: u64, body: MessageBody) -> Result<MessageBody, UiClientError> {
+        self.ensure_connected()?;
+        let (tx, rx) = unbounded();
+        {
+            let mut connection_guard = self
+                .shared
+                .connection
+                .lock()
+                .expect("UiClient connection is poisoned");
+            let connection = match connection_guard.as_mut() {
+                Some(connection) => connection,
+                None => {
+                    return Err(UiClientError::ConnectionDropped(
+                        "No connection to the Daemon or Node".to_string(),
+                    ))
+                }
+            };
+            self.shared.pending().insert(
+                context_id,
+                PendingConversation {
+                    connection_id: connection.id,
+                    tx,
+                },
+            );
+            if let Err(e) = connection.send(body) {
+                self.shared.pending().remove(&context_id);
+                return Err(e);
+            }
+        }
+        match rx.recv_timeout(Duration::from_millis(self.timeout_millis)) {
+            Ok(result) => result,
+            Err(RecvTimeoutError::Timeout) => {
+                self.shared.pending().remove(&context_id);
+                Err(UiClientError::Timeout(self.timeout_millis))
+            }
+            Err(RecvTimeoutError::Disconnected) => Err(UiClientError::ConnectionDropped(
+                "Listener thread is gone".to_string(),
+            )),
+        }
+    }
+}
+
+ui_requests! {
+    setup: UiSetupRequest => UiSetupResponse,
+    save_setup_profile: UiSaveSetupProfileRequest => UiSaveSetupProfileResponse,
+    load_setup_profile: UiLoadSetupProfileRequest => UiLoadSetupProfileResponse,
+    setup_profiles: UiSetupProfilesRequest => UiSetupProfilesResponse,
+    delete_setup_profile: UiDeleteSetupProfileRequest => UiDeleteSetupProfileResponse,
+    start: UiStartOrder => UiStartResponse,
+    restart_policy: UiRestartPolicyRequest => UiRestartPolicyResponse,
+    crash_history: UiCrashHistoryRequest => UiCrashHistoryResponse,
+    select_instance: UiSelectInstanceRequest => UiSelectInstanceResponse,
+    instances: UiInstancesRequest => UiInstancesResponse,
+    status: UiStatusRequest => UiStatusResponse,
+    subscribe_broadcasts: UiSubscribeRequest => UiSubscribeResponse,
+    unsubscribe_broadcasts: UiUnsubscribeRequest => UiUnsubscribeResponse,
+    ban: UiBanRequest => UiBanResponse,
+    banned_wallets: UiBannedWalletsRequest => UiBannedWalletsResponse,
+    change_password: UiChangePasswordRequest => UiChangePasswordResponse,
+    check_password: UiCheckPasswordRequest => UiCheckPasswordResponse,
+    wallet_addresses: UiWalletAddressesRequest => UiWalletAddressesResponse,
+    wallet_balances: UiWalletBalancesRequest => UiWalletBalancesResponse,
+    configuration: UiConfigurationRequest => UiConfigurationResponse,
+    connection_status: UiConnectionStatusRequest => UiConnectionStatusResponse,
+    pending_debuts: UiPendingDebutsRequest => UiPendingDebutsResponse,
+    approve_debut: UiApproveDebutRequest => UiApproveDebutResponse,
+    reject_debut: UiRejectDebutRequest => UiRejectDebutResponse,
+    debut_allow_list: UiDebutAllowListRequest => UiDebutAllowListResponse,
+    descriptor: UiDescriptorRequest => UiDescriptorResponse,
+    dns_bypass: UiDnsBypassRequest => UiDnsBypassResponse,
+    exit_country: UiExitCountryRequest => UiExitCountryResponse,
+    dropped_gossip: UiDroppedGossipRequest => UiDroppedGossipResponse,
+    export_accounts: UiExportAccountsRequest => UiExportAccountsResponse,
+    financials: UiFinancialsRequest => UiFinancialsResponse,
+    generate_wallets: UiGenerateWalletsRequest => UiGenerateWalletsResponse,
+    malefactor_bans: UiMalefactorBansRequest => UiMalefactorBansResponse,
+    malefactor_unban: UiMalefactorUnbanRequest => UiMalefactorUnbanResponse,
+    recover_wallets: UiRecoverWalletsRequest => UiRecoverWalletsResponse,
+    scan: UiScanRequest => UiScanResponse,
+    shutdown: UiShutdownRequest => UiShutdownResponse,
+    unban: UiUnbanRequest => UiUnbanResponse,
+}
+
+struct Shared {
+    daemon_port: u16,
+    next_connection_id: AtomicU64,
+    connection: Mutex<Option<Connection>>,
+    pending: Mutex<HashMap<u64, PendingConversation>>,
+    subscribers: Mutex<Vec<Sender<MessageBody>>>,
+    closed: AtomicBool,
+}
+
+impl Shared {
+    fn pending(&self) -> std::sync::MutexGuard<HashMap<u64, PendingConversation>> {
+        self.pending
+            .lock()
+            .expect("UiClient pending conversations are poisoned")
+    }
+
+    fn route(&self, body: MessageBody) {
+        let context_id_opt = match body.path {
+            MessagePath::Conversation(context_id) => Some(context_id),
+            // A redirect is a broadcast, but it answers a particular conversation.
+            MessagePath::FireAndForget => UiRedirect::fmb(body.clone())
+                .ok()
+                .and_then(|(redirect, _)| redirect.context_id),
+        };
+        let pending_opt = context_id_opt.and_then(|context_id| self.pending().remove(&context_id));
+        match (pending_opt, &body.path) {
+            (Some(pending), _) => {
+                let _ = pending.tx.send(Ok(body));
+            }
+            (None, MessagePath::FireAndForget) => self.publish(body),
+            (None, MessagePath::Conversation(_)) => (), // its requester has given up on it
+        }
+    }
+
+    fn publish(&self, body: MessageBody) {
+        self.subscribers
+            .lock()
+            .expect("UiClient subscribers are poisoned")
+            .retain(|subscriber| subscriber.send(body.clone()).is_ok());
+    }
+}
+
+struct Connection {
+    id: u64,
+    port: u16,
+    writer: Writer<TcpStream>,
+}
+
+impl Connection {
+    fn send(&mut self, body: MessageBody) -> Result<(), UiClientError> {
+        let message = OwnedMessage::Text(UiTrafficConverter::new_marshal(body));
+        self.writer
+            .sender
+            .send_message(&mut self.writer.stream, &message)
+            .map_err(|e| UiClientError::ConnectionDropped(format!("{:?}", e)))
+    }
+
+    fn close(&mut self) {
+        let _ = self
+            .writer
+            .sender
+            .send_message(&mut self.writer.stream, &OwnedMessage::Close(None));
+        let _ = self.writer.shutdown_all();
+    }
+}
+
+struct PendingConversation {
+    connection_id: u64,
+    tx: Sender<Result<MessageBody, UiClientError>>,
+}
+
+// Replaces whatever connection the client had with a new one to the given port.
+fn connect(shared: &Arc<Shared>, port: u16, timeout_millis: u64) -> Result<(), UiClientError> {
+    let client = match connect_insecure_timeout(port, timeout_millis) {
+        Ok(Ok(client)) => client,
+        Ok(Err(e)) => return Err(UiClientError::ConnectionRefused(port, format!("{:?}", e))),
+        Err(_) => {
+            return Err(UiClientError::ConnectionRefused(
+                port,
+                format!("No answer after {}ms", timeout_millis),
+            ))
+        }
+    };
+    let (reader, writer) = client
+        .split()
+        .map_err(|e| UiClientError::ConnectionRefused(port, format!("{:?}", e)))?;
+    let id = shared.next_connection_id.fetch_add(1, Ordering::SeqCst);
+    let old_connection_opt = shared
+        .connection
+        .lock()
+        .expect("UiClient connection is poisoned")
+        .replace(Connection { id, port, writer });
+    if let Some(mut old_connection) = old_connection_opt {
+        old_connection.close();
+    }
+    let listener_shared = shared.clone();
+    thread::spawn(move || listen(listener_shared, id, port, reader));
+    Ok(())
+}
+
+fn connect_insecure_timeout(
+    port: u16,
+    timeout_millis: u64,
+) -> Result<WebSocketResult<Client<TcpStream>>, RecvTimeoutError> {
+    let url = format!("ws://{}:{}", localhost(), port);
+    let mut builder = ClientBuilder::new(url.as_str())
+        .expect("Bad URL")
+        .add_protocol(NODE_UI_PROTOCOL);
+    let (tx, rx) = unbounded();
+    thread::spawn(move || {
+        let _ = tx.send(builder.connect_insecure());
+    });
+    rx.recv_timeout(Duration::from_millis(timeout_millis))
+}
+
+fn listen(shared: Arc<Shared>, connection_id: u64, port: u16, mut reader: Reader<TcpStream>) {
+    loop {
+        match reader.receiver.recv_message(&mut reader.stream) {
+            Ok(OwnedMessage::Text(json)) => {
+                // Unparseable traffic can't be matched to a conversation, so it's dropped.
+                if let Ok(body) = UiTrafficConverter::new_unmarshal(&json) {
+                    shared.route(body)
+                }
+            }
+            Ok(OwnedMessage::Close(_)) | Err(_) => break,
+            Ok(_) => (),
+        }
+    }
+    connection_lost(&shared, connection_id, port);
+}
+
+fn connection_lost(shared: &Arc<Shared>, connection_id: u64, port: u16) {
+    let was_current = {
+        let mut connection_guard = shared
+            .connection
+            .lock()
+            .expect("UiClient connection is poisoned");
+        if connection_guard.as_ref().map(|connection| connection.id) == Some(connection_id) {
+            connection_guard.take();
+            true
+        } else {
+            false
+        }
+    };
+    shared.pending().retain(|_, pending| {
+        if pending.connection_id == connection_id {
+            let _ = pending
+                .tx
+                .send(Err(UiClientError::ConnectionDropped(format!(
+                    "Lost connection to port {}",
+                    port
+                ))));
+            false
+        } else {
+            true
+        }
+    });
+    if !was_current || shared.closed.load(Ordering::SeqCst) {
+        return;
+    }
+    // The Node went away; go back to the Daemon so that subscribers hear about it.
+    if port != shared.daemon_port
+        && connect(shared, shared.daemon_port, FALLBACK_TIMEOUT_MILLIS).is_ok()
+    {
+        return;
+    }
+    shared.publish(
+        UiNodeCrashedBroadcast {
+            process_id: 0,
+            crash_reason: CrashReason::DaemonCrashed,
+        }
+        .tmb(0),
+    );
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_utils::mock_websockets_server::MockWebSocketsServer;
+    use crate::utils::find_free_port;
+
+    #[test]
+    fn every_request_is_paired_with_the_response_of_the_same_opcode() {
+        request_opcode_pairs()
+            .into_iter()
+            .for_each(|(request_opcode, response_opcode)| {
+                assert_eq!(request_opcode, response_opcode)
+            });
+        assert_eq!(request_opcode_pairs().len(), 38);
+    }
+
+    #[test]
+    fn typed_request_gets_typed_response() {
+        let port = find_free_port();
+        let server = MockWebSocketsServer::new(port).queue_response(
+            UiDescriptorResponse {
+                node_descriptor: "booga".to_string(),
+            }
+            .tmb(1),
+        );
+        let stop_handle = server.start();
+        let subject = UiClient::connect(port).unwrap();
+
+        let result = subject.descriptor(UiDescriptorRequest {});
+
+        assert_eq!(
+            result,
+            Ok(UiDescriptorResponse {
+                node_descriptor: "booga".to_string()
+            })
+        );
+        let requests = stop_handle.stop();
+        assert_eq!(requests, vec![Ok(UiDescriptorRequest {}.tmb(1))]);
+    }
+
+    #[test]
+    fn error_response_becomes_payload_error() {
+        let port = find_free_port();
+        let server = MockWebSocketsServer::new(port).queue_response(MessageBody {
+            opcode: "shutdown".to_string(),
+            path: MessagePath::Conversation(1),
+            payload: Err((NODE_NOT_RUNNING_ERROR, "Not running".to_string())),
+        });
+        let stop_handle = server.start();
+        let subject = UiClient::connect(port).unwrap();
+
+        let result = subject.shutdown(UiShutdownRequest {});
+
+        assert_eq!(
+            result,
+            Err(UiClientError::PayloadError(
+                NODE_NOT_RUNNING_ERROR,
+                "Not running".to_string()
+            ))
+        );
+        stop_handle.stop();
+    }
+
+    #[test]
+    fn redirect_is_followed_to_the_node_and_the_request_resent() {
+        let daemon_port = find_free_port();
+        let node_port = find_free_port();
+        let request = UiShutdownRequest {};
+        let daemon = MockWebSocketsServer::new(daemon_port).queue_response(
+            UiRedirect {
+                port: node_port,
+                opcode: "shutdown".to_string(),
+                context_id: Some(1),
+                payload: "{}".to_string(),
+            }
+            .tmb(0),
+        );
+        let node =
+            MockWebSocketsServer::new(node_port).queue_response(UiShutdownResponse {}.tmb(1));
+        let daemon_stop_handle = daemon.start();
+        let node_stop_handle = node.start();
+        let subject = UiClient::connect(daemon_port).unwrap();
+
+        let result = subject.shutdown(request.clone());
+
+        assert_eq!(result, Ok(UiShutdownResponse {}));
+        assert_eq!(subject.active_port(), Some(node_port));
+        subject.close();
+        assert_eq!(node_stop_handle.kill()[0], Ok(request.clone().tmb(1)));
+        assert_eq!(daemon_stop_handle.kill()[0], Ok(request.tmb(1)));
+    }
+
+    #[test]
+    fn unanswered_request_times_out_and_broadcasts_reach_subscribers() {
+        let port = find_free_port();
+        let broadcast = UiSetupBroadcast {
+            running: false,
+            values: vec![],
+            errors: vec![],
+        }
+        .tmb(0);
+        let server = MockWebSocketsServer::new(port).queue_response(broadcast.clone());
+        let stop_handle = server.start();
+        let subject = UiClient::connect_with_timeout(port, 500).unwrap();
+        let subscription = subject.subscribe();
+
+        let result = subject.status(UiStatusRequest {});
+
+        assert_eq!(result, Err(UiClientError::Timeout(500)));
+        assert_eq!(
+            subscription.recv_timeout(Duration::from_millis(1000)),
+            Ok(broadcast)
+        );
+        stop_handle.stop();
+    }
+
+    #[test]
+    fn losing_the_daemon_fails_the_conversation_and_tells_subscribers() {
+        let port = find_free_port();
+        let server = MockWebSocketsServer::new(port).queue_string("close");
+        let stop_handle = server.start();
+        let subject = UiClient::connect(port).unwrap();
+        let subscription = subject.subscribe();
+
+        let result = subject.status(UiStatusRequest {});
+
+        assert_eq!(
+            result,
+            Err(UiClientError::ConnectionDropped(format!(
+                "Lost connection to port {}",
+                port
+            )))
+        );
+        assert_eq!(
+            subscription.recv_timeout(Duration::from_millis(1000)),
+            Ok(UiNodeCrashedBroadcast {
+                process_id: 0,
+                crash_reason: CrashReason::DaemonCrashed,
+            }
+            .tmb(0))
+        );
+        assert_eq!(subject.active_port(), None);
+        stop_handle.kill();
+    }
+
+    #[test]
+    fn connecting_to_nothing_is_refused() {
+        let port = find_free_port();
+
+        let result = UiClient::connect_with_timeout(port, 500);
+
+        match result {
+            Err(UiClientError::ConnectionRefused(refused_port, _)) => {
+                assert_eq!(refused_port, port)
+            }
+            Err(e) => panic!("Expected ConnectionRefused; got {:?}", e),
+            Ok(_) => panic!("Expected ConnectionRefused; got a client"),
+        }
+    }
+}
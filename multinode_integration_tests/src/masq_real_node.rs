@@ -789,7 +789,7 @@ impl MASQRealNode {
         let chain_id = real_startup_config
             .clone()
             .chain_opt
-            .map(|chain_name| chain_id_from_name(chain_name.as_str()))
+            .map(|chain_name| chain_id_from_name(chain_name.as_str()).expect("Unknown chain"))
             .unwrap_or(DEFAULT_CHAIN_ID);
         let cryptde_null_opt = real_startup_config
             .fake_public_key_opt
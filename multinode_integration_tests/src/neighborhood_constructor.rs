@@ -68,7 +68,7 @@ pub fn construct_neighborhood(
             .consuming_wallet_info(make_consuming_wallet_info(
                 model_db.root().public_key().to_string().as_str(),
             ))
-            .chain(chain_name_from_id(cluster.chain_id))
+            .chain(&chain_name_from_id(cluster.chain_id))
             .build(),
     );
     let (mock_node_map, adjacent_mock_node_keys) =
@@ -1,7 +1,7 @@
 // Copyright (c) 2017-2019, Substratum LLC (https://substratum.net) and/or its affiliates. All rights reserved.
 
 use crate::masq_node::MASQNode;
-use node_lib::neighborhood::node_record::NodeRecordInner_0v1;
+use node_lib::neighborhood::node_record::NodeRecordInner_0v2;
 use node_lib::neighborhood::AccessibleGossipRecord;
 use node_lib::sub_lib::cryptde::{CryptData, PlainData};
 use std::collections::BTreeSet;
@@ -68,7 +68,7 @@ impl From<&dyn MASQNode> for AccessibleGossipRecord {
     fn from(masq_node: &dyn MASQNode) -> Self {
         let cryptde = masq_node.signing_cryptde().unwrap_or_else (|| panic! ("You can only make an AccessibleGossipRecord from a MASQRealNode if it has a CryptDENull, not a CryptDEReal."));
         let mut agr = AccessibleGossipRecord {
-            inner: NodeRecordInner_0v1 {
+            inner: NodeRecordInner_0v2 {
                 public_key: masq_node.main_public_key().clone(),
                 earning_wallet: masq_node.earning_wallet(),
                 rate_pack: masq_node.rate_pack(),
@@ -76,6 +76,11 @@ impl From<&dyn MASQNode> for AccessibleGossipRecord {
                 accepts_connections: masq_node.accepts_connections(),
                 routes_data: masq_node.routes_data(),
                 version: 0,
+                proxy_protocols_opt: None,
+                masqueraders_opt: None,
+                max_bandwidth_opt: None,
+                software_version_opt: None,
+                country_code_opt: None,
             },
             node_addr_opt: Some(masq_node.node_addr()),
             signed_gossip: PlainData::new(b""),
@@ -17,7 +17,7 @@ fn blockchain_bridge_logs_when_started() {
     let subject = cluster.start_real_node(
         NodeStartupConfigBuilder::zero_hop()
             .consuming_wallet_info(ConsumingWalletInfo::PrivateKey(private_key.to_string()))
-            .chain(chain_name_from_id(cluster.chain_id))
+            .chain(&chain_name_from_id(cluster.chain_id))
             .build(),
     );
 
@@ -111,7 +111,7 @@ pub fn start_lonely_real_node(cluster: &mut MASQNodeCluster) -> MASQRealNode {
         NodeStartupConfigBuilder::standard()
             .earning_wallet_info(make_earning_wallet_info(&index.to_string()))
             .consuming_wallet_info(make_consuming_wallet_info(&index.to_string()))
-            .chain(chain_name_from_id(cluster.chain_id))
+            .chain(&chain_name_from_id(cluster.chain_id))
             .build(),
     )
 }
@@ -122,7 +122,7 @@ pub fn start_real_node(cluster: &mut MASQNodeCluster, neighbor: NodeReference) -
         NodeStartupConfigBuilder::standard()
             .neighbor(neighbor)
             .earning_wallet_info(make_earning_wallet_info(&index.to_string()))
-            .chain(chain_name_from_id(cluster.chain_id))
+            .chain(&chain_name_from_id(cluster.chain_id))
             .build(),
     )
 }
@@ -18,28 +18,28 @@ fn neighborhood_notified_of_newly_missing_node() {
     let neighbor = cluster.start_real_node(
         NodeStartupConfigBuilder::standard()
             .fake_public_key(&PublicKey::new(&[1, 2, 3, 4]))
-            .chain(chain_name_from_id(cluster.chain_id))
+            .chain(&chain_name_from_id(cluster.chain_id))
             .build(),
     );
     let originating_node = cluster.start_real_node(
         NodeStartupConfigBuilder::standard()
             .neighbor(neighbor.node_reference())
             .fake_public_key(&PublicKey::new(&[2, 3, 4, 5]))
-            .chain(chain_name_from_id(cluster.chain_id))
+            .chain(&chain_name_from_id(cluster.chain_id))
             .build(),
     );
     let _staying_up_node = cluster.start_real_node(
         NodeStartupConfigBuilder::standard()
             .neighbor(neighbor.node_reference())
             .fake_public_key(&PublicKey::new(&[3, 4, 5, 6]))
-            .chain(chain_name_from_id(cluster.chain_id))
+            .chain(&chain_name_from_id(cluster.chain_id))
             .build(),
     );
     let disappearing_node = cluster.start_real_node(
         NodeStartupConfigBuilder::standard()
             .neighbor(neighbor.node_reference())
             .fake_public_key(&PublicKey::new(&[4, 5, 6, 7]))
-            .chain(chain_name_from_id(cluster.chain_id))
+            .chain(&chain_name_from_id(cluster.chain_id))
             .build(),
     );
     let witness_node = cluster
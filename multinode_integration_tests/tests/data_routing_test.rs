@@ -26,7 +26,7 @@ fn http_end_to_end_routing_test() {
     let mut cluster = MASQNodeCluster::start().unwrap();
     let first_node = cluster.start_real_node(
         NodeStartupConfigBuilder::standard()
-            .chain(chain_name_from_id(cluster.chain_id))
+            .chain(&chain_name_from_id(cluster.chain_id))
             .build(),
     );
 
@@ -35,7 +35,7 @@ fn http_end_to_end_routing_test() {
             cluster.start_real_node(
                 NodeStartupConfigBuilder::standard()
                     .neighbor(first_node.node_reference())
-                    .chain(chain_name_from_id(cluster.chain_id))
+                    .chain(&chain_name_from_id(cluster.chain_id))
                     .build(),
             )
         })
@@ -47,7 +47,7 @@ fn http_end_to_end_routing_test() {
         NodeStartupConfigBuilder::standard()
             .neighbor(nodes.last().unwrap().node_reference())
             .consuming_wallet_info(make_consuming_wallet_info("last_node"))
-            .chain(chain_name_from_id(cluster.chain_id))
+            .chain(&chain_name_from_id(cluster.chain_id))
             // This line is commented out because for some reason the installation of iptables-persistent hangs forever on
             // bullseye-slim. Its absence means that the NodeStartupConfigBuilder::open_firewall_port() function won't work, but
             // at the time of this comment it's used only in this one place, where it adds no value. So we decided to
@@ -75,19 +75,19 @@ fn http_end_to_end_routing_test_with_consume_and_originate_only_nodes() {
     let mut cluster = MASQNodeCluster::start().unwrap();
     let first_node = cluster.start_real_node(
         NodeStartupConfigBuilder::standard()
-            .chain(chain_name_from_id(cluster.chain_id))
+            .chain(&chain_name_from_id(cluster.chain_id))
             .build(),
     );
     let _second_node = cluster.start_real_node(
         NodeStartupConfigBuilder::standard()
             .neighbor(first_node.node_reference())
-            .chain(chain_name_from_id(cluster.chain_id))
+            .chain(&chain_name_from_id(cluster.chain_id))
             .build(),
     );
     let originating_node = cluster.start_real_node(
         NodeStartupConfigBuilder::consume_only()
             .neighbor(first_node.node_reference())
-            .chain(chain_name_from_id(cluster.chain_id))
+            .chain(&chain_name_from_id(cluster.chain_id))
             .build(),
     );
     let _potential_exit_nodes = vec![0, 1, 2, 3, 4]
@@ -96,7 +96,7 @@ fn http_end_to_end_routing_test_with_consume_and_originate_only_nodes() {
             cluster.start_real_node(
                 NodeStartupConfigBuilder::originate_only()
                     .neighbor(first_node.node_reference())
-                    .chain(chain_name_from_id(cluster.chain_id))
+                    .chain(&chain_name_from_id(cluster.chain_id))
                     .build(),
             )
         })
@@ -121,7 +121,7 @@ fn tls_end_to_end_routing_test() {
     let mut cluster = MASQNodeCluster::start().unwrap();
     let first_node = cluster.start_real_node(
         NodeStartupConfigBuilder::standard()
-            .chain(chain_name_from_id(cluster.chain_id))
+            .chain(&chain_name_from_id(cluster.chain_id))
             .build(),
     );
 
@@ -131,7 +131,7 @@ fn tls_end_to_end_routing_test() {
                 NodeStartupConfigBuilder::standard()
                     .consuming_wallet_info(make_consuming_wallet_info(&format!("{}", n)))
                     .neighbor(first_node.node_reference())
-                    .chain(chain_name_from_id(cluster.chain_id))
+                    .chain(&chain_name_from_id(cluster.chain_id))
                     .build(),
             )
         })
@@ -204,14 +204,14 @@ fn http_routing_failure_produces_internal_error_response() {
     let mut cluster = MASQNodeCluster::start().unwrap();
     let neighbor_node = cluster.start_real_node(
         NodeStartupConfigBuilder::standard()
-            .chain(chain_name_from_id(cluster.chain_id))
+            .chain(&chain_name_from_id(cluster.chain_id))
             .build(),
     );
     let originating_node = cluster.start_real_node(
         NodeStartupConfigBuilder::standard()
             .consuming_wallet_info(default_consuming_wallet_info())
             .neighbor(neighbor_node.node_reference())
-            .chain(chain_name_from_id(cluster.chain_id))
+            .chain(&chain_name_from_id(cluster.chain_id))
             .build(),
     );
     thread::sleep(Duration::from_millis(1000));
@@ -238,14 +238,14 @@ fn tls_routing_failure_produces_internal_error_response() {
     let mut cluster = MASQNodeCluster::start().unwrap();
     let neighbor = cluster.start_real_node(
         NodeStartupConfigBuilder::standard()
-            .chain(chain_name_from_id(cluster.chain_id))
+            .chain(&chain_name_from_id(cluster.chain_id))
             .build(),
     );
     let originating_node = cluster.start_real_node(
         NodeStartupConfigBuilder::standard()
             .consuming_wallet_info(default_consuming_wallet_info())
             .neighbor(neighbor.node_reference())
-            .chain(chain_name_from_id(cluster.chain_id))
+            .chain(&chain_name_from_id(cluster.chain_id))
             .build(),
     );
     let mut client = originating_node.make_client(8443);
@@ -292,7 +292,7 @@ fn multiple_stream_zero_hop_test() {
     let zero_hop_node = cluster.start_real_node(
         NodeStartupConfigBuilder::zero_hop()
             .consuming_wallet_info(default_consuming_wallet_info())
-            .chain(chain_name_from_id(cluster.chain_id))
+            .chain(&chain_name_from_id(cluster.chain_id))
             .build(),
     );
     let mut one_client = zero_hop_node.make_client(8080);
@@ -22,7 +22,7 @@ fn graph_connects_but_does_not_over_connect() {
     let first_node = cluster.start_real_node(
         NodeStartupConfigBuilder::standard()
             .fake_public_key(&PublicKey::new(&[4, 3, 2, 0]))
-            .chain(chain_name_from_id(cluster.chain_id))
+            .chain(&chain_name_from_id(cluster.chain_id))
             .build(),
     );
     let real_nodes = (1..neighborhood_size)
@@ -31,7 +31,7 @@ fn graph_connects_but_does_not_over_connect() {
                 NodeStartupConfigBuilder::standard()
                     .neighbor(first_node.node_reference())
                     .fake_public_key(&PublicKey::new(&[4, 3, 2, index as u8]))
-                    .chain(chain_name_from_id(cluster.chain_id))
+                    .chain(&chain_name_from_id(cluster.chain_id))
                     .build(),
             )
         })
@@ -8,10 +8,11 @@ pub mod test_utils;
 
 use crate::accountant::payable_dao::{PayableAccount, PayableDaoFactory, Payment};
 use crate::accountant::receivable_dao::{ReceivableAccount, ReceivableDaoFactory};
-use crate::banned_dao::{BannedDao, BannedDaoFactory};
+use crate::banned_dao::{BannedDao, BannedDaoFactory, BannedWallet};
 use crate::blockchain::blockchain_bridge::RetrieveTransactions;
 use crate::blockchain::blockchain_interface::{BlockchainError, Transaction};
 use crate::bootstrapper::BootstrapperConfig;
+use crate::database::dao_utils::to_time_t;
 use crate::db_config::config_dao::ConfigDaoFactory;
 use crate::db_config::persistent_configuration::{
     PersistentConfiguration, PersistentConfigurationReal,
@@ -38,23 +39,39 @@ use futures::future::Future;
 use itertools::Itertools;
 use lazy_static::lazy_static;
 use masq_lib::messages::UiMessageError::UnexpectedMessage;
-use masq_lib::messages::{FromMessageBody, ToMessageBody, UiFinancialsRequest, UiMessageError};
+use masq_lib::messages::{
+    FromMessageBody, ScanType, ToMessageBody, UiBanChangedBroadcast, UiBanRequest, UiBanResponse,
+    UiBannedWallet, UiBannedWalletsRequest, UiBannedWalletsResponse, UiExportAccountsRequest,
+    UiExportAccountsResponse, UiExportedAccount, UiFinancialsRequest, UiMessageError,
+    UiScanRequest, UiScanResponse, UiScanResultsBroadcast, UiUnbanRequest, UiUnbanResponse,
+};
 use masq_lib::messages::{UiFinancialsResponse, UiPayableAccount, UiReceivableAccount};
-use masq_lib::ui_gateway::MessageTarget::ClientId;
-use masq_lib::ui_gateway::{NodeFromUiMessage, NodeToUiMessage};
+use masq_lib::ui_gateway::MessagePath::Conversation;
+use masq_lib::ui_gateway::MessageTarget::{AllClients, ClientId};
+use masq_lib::ui_gateway::{MessageBody, MessageTarget, NodeFromUiMessage, NodeToUiMessage};
 use payable_dao::PayableDao;
 use receivable_dao::ReceivableDao;
+use std::collections::{HashMap, HashSet};
+use std::fmt::{Display, Formatter};
+use std::str::FromStr;
 use std::thread;
-use std::time::{Duration, SystemTime};
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
 
 pub const CRASH_KEY: &str = "ACCOUNTANT";
 pub const DEFAULT_PAYABLE_SCAN_INTERVAL: u64 = 3600; // one hour
 pub const DEFAULT_PAYMENT_RECEIVED_SCAN_INTERVAL: u64 = 3600; // one hour
 
+pub const ACCOUNTANT_PREFIX: u64 = 0x0003_0000_0000_0000;
+pub const BAD_WALLET_ADDRESS_ERROR: u64 = ACCOUNTANT_PREFIX | 1;
+pub const ALREADY_BANNED_ERROR: u64 = ACCOUNTANT_PREFIX | 2;
+pub const NOT_BANNED_ERROR: u64 = ACCOUNTANT_PREFIX | 3;
+
+pub const DELINQUENCY_BAN_REASON: &str = "Delinquency";
+
 const SECONDS_PER_DAY: i64 = 86_400;
 
 lazy_static! {
-    pub static ref PAYMENT_CURVES: PaymentCurves = PaymentCurves {
+    pub static ref DEFAULT_PAYMENT_CURVES: PaymentCurves = PaymentCurves {
         payment_suggested_after_sec: SECONDS_PER_DAY,
         payment_grace_before_ban_sec: SECONDS_PER_DAY,
         permanent_debt_allowed_gwub: 10_000_000,
@@ -79,7 +96,77 @@ pub struct PaymentCurves {
     pub unban_when_balance_below_gwub: i64,
 }
 
+impl FromStr for PaymentCurves {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let values = s
+            .split('|')
+            .map(|value| match value.parse::<i64>() {
+                Ok(n) if n >= 0 => Ok(n),
+                _ => Err(format!(
+                    "Payment curve value '{}' is not a nonnegative integer",
+                    value
+                )),
+            })
+            .collect::<Result<Vec<i64>, String>>()?;
+        if values.len() != 6 {
+            return Err(format!(
+                "Payment curves need six values separated by '|', not {}",
+                values.len()
+            ));
+        }
+        let payment_curves = PaymentCurves {
+            payment_suggested_after_sec: values[0],
+            payment_grace_before_ban_sec: values[1],
+            permanent_debt_allowed_gwub: values[2],
+            balance_to_decrease_from_gwub: values[3],
+            balance_decreases_for_sec: values[4],
+            unban_when_balance_below_gwub: values[5],
+        };
+        payment_curves.validate()?;
+        Ok(payment_curves)
+    }
+}
+
+impl Display for PaymentCurves {
+    fn fmt(&self, f: &mut Formatter) -> std::fmt::Result {
+        write!(
+            f,
+            "{}|{}|{}|{}|{}|{}",
+            self.payment_suggested_after_sec,
+            self.payment_grace_before_ban_sec,
+            self.permanent_debt_allowed_gwub,
+            self.balance_to_decrease_from_gwub,
+            self.balance_decreases_for_sec,
+            self.unban_when_balance_below_gwub
+        )
+    }
+}
+
 impl PaymentCurves {
+    pub fn validate(&self) -> Result<(), String> {
+        if self.balance_decreases_for_sec <= self.payment_suggested_after_sec {
+            return Err(format!(
+                "Balance decrease period ({} sec) must be longer than payment suggestion period ({} sec)",
+                self.balance_decreases_for_sec, self.payment_suggested_after_sec
+            ));
+        }
+        if self.balance_to_decrease_from_gwub <= self.permanent_debt_allowed_gwub {
+            return Err(format!(
+                "Balance to decrease from ({} Gwub) must be larger than permanent debt allowed ({} Gwub)",
+                self.balance_to_decrease_from_gwub, self.permanent_debt_allowed_gwub
+            ));
+        }
+        if self.unban_when_balance_below_gwub > self.permanent_debt_allowed_gwub {
+            return Err(format!(
+                "Unban balance ({} Gwub) must not be larger than permanent debt allowed ({} Gwub)",
+                self.unban_when_balance_below_gwub, self.permanent_debt_allowed_gwub
+            ));
+        }
+        Ok(())
+    }
+
     pub fn sugg_and_grace(&self, now: i64) -> i64 {
         now - self.payment_suggested_after_sec - self.payment_grace_before_ban_sec
     }
@@ -117,6 +204,7 @@ pub struct ReceivedPayments {
 #[derive(Debug, Eq, Message, PartialEq)]
 pub struct SentPayments {
     pub payments: Vec<Result<Payment, BlockchainError>>,
+    pub requested_by_ui: bool,
 }
 
 impl Handler<BindMessage> for Accountant {
@@ -135,7 +223,7 @@ impl Handler<StartMessage> for Accountant {
         self.handle_start_message();
 
         ctx.run_interval(self.config.payable_scan_interval, |accountant, _ctx| {
-            accountant.scan_for_payables();
+            accountant.scan_for_payables(false);
         });
 
         ctx.run_interval(
@@ -269,7 +357,9 @@ impl Accountant {
         }
     }
 
-    fn scan_for_payables(&mut self) {
+    // Periodic scans are broadcast only when they pay something; scans a UI asked for are always
+    // broadcast, so that it hears about empty and failed ones too.
+    fn scan_for_payables(&mut self, requested_by_ui: bool) {
         debug!(self.logger, "Scanning for payables");
         let future_logger = self.logger.clone();
 
@@ -277,11 +367,23 @@ impl Accountant {
             .payable_dao
             .non_pending_payables()
             .into_iter()
-            .filter(Accountant::should_pay)
+            .filter(|payable| Accountant::should_pay(payable, &self.config.payment_curves))
             .collect::<Vec<PayableAccount>>();
 
-        if !payables.is_empty() {
+        if payables.is_empty() {
+            if requested_by_ui {
+                self.broadcast(
+                    UiScanResultsBroadcast {
+                        scan_type: ScanType::Payables,
+                        transaction_count: 0,
+                        error_opt: None,
+                    }
+                    .tmb(0),
+                );
+            }
+        } else {
             let report_sent_payments = self.report_sent_payments_sub.clone();
+            let ui_message_sub = self.ui_message_sub.clone();
             let future = self
                 .report_accounts_payable_sub
                 .as_ref()
@@ -291,12 +393,28 @@ impl Accountant {
                     Ok(Ok(results)) => {
                         report_sent_payments
                             .expect("Accountant is unbound")
-                            .try_send(SentPayments { payments: results })
+                            .try_send(SentPayments {
+                                payments: results,
+                                requested_by_ui,
+                            })
                             .expect("Accountant is dead");
                         Ok(())
                     }
                     Ok(Err(e)) => {
                         warning!(future_logger, "{}", e);
+                        if let Some(ui_message_sub) = ui_message_sub.filter(|_| requested_by_ui) {
+                            ui_message_sub
+                                .try_send(NodeToUiMessage {
+                                    target: AllClients,
+                                    body: UiScanResultsBroadcast {
+                                        scan_type: ScanType::Payables,
+                                        transaction_count: 0,
+                                        error_opt: Some(e),
+                                    }
+                                    .tmb(0),
+                                })
+                                .expect("UiGateway is dead");
+                        }
                         Ok(())
                     }
                     Err(e) => {
@@ -317,10 +435,15 @@ impl Accountant {
 
         let now = SystemTime::now();
         self.receivable_dao
-            .new_delinquencies(now, &PAYMENT_CURVES)
+            .new_delinquencies(now, &self.config.payment_curves)
             .into_iter()
             .for_each(|account| {
-                self.banned_dao.ban(&account.wallet);
+                self.banned_dao.ban(&account.wallet, DELINQUENCY_BAN_REASON);
+                self.broadcast_ban_change(
+                    &account.wallet,
+                    true,
+                    Some(DELINQUENCY_BAN_REASON.to_string()),
+                );
                 let (balance, age) = Self::balance_and_age(&account);
                 info!(
                     self.logger,
@@ -332,10 +455,11 @@ impl Accountant {
             });
 
         self.receivable_dao
-            .paid_delinquencies(&PAYMENT_CURVES)
+            .paid_delinquencies(&self.config.payment_curves)
             .into_iter()
             .for_each(|account| {
                 self.banned_dao.unban(&account.wallet);
+                self.broadcast_ban_change(&account.wallet, false, None);
                 let (balance, age) = Self::balance_and_age(&account);
                 info!(
                     self.logger,
@@ -424,32 +548,33 @@ impl Accountant {
         (balance, age)
     }
 
-    fn should_pay(payable: &PayableAccount) -> bool {
+    fn should_pay(payable: &PayableAccount, payment_curves: &PaymentCurves) -> bool {
         // TODO: This calculation should be done in the database, if possible
         let time_since_last_paid = SystemTime::now()
             .duration_since(payable.last_paid_timestamp)
             .expect("Internal error")
             .as_secs();
 
-        if time_since_last_paid <= PAYMENT_CURVES.payment_suggested_after_sec as u64 {
+        if time_since_last_paid <= payment_curves.payment_suggested_after_sec as u64 {
             return false;
         }
 
-        if payable.balance <= PAYMENT_CURVES.permanent_debt_allowed_gwub {
+        if payable.balance <= payment_curves.permanent_debt_allowed_gwub {
             return false;
         }
 
-        let threshold = Accountant::calculate_payout_threshold(time_since_last_paid);
+        let threshold =
+            Accountant::calculate_payout_threshold(time_since_last_paid, payment_curves);
         payable.balance as f64 > threshold
     }
 
-    fn calculate_payout_threshold(x: u64) -> f64 {
-        let m = -((PAYMENT_CURVES.balance_to_decrease_from_gwub as f64
-            - PAYMENT_CURVES.permanent_debt_allowed_gwub as f64)
-            / (PAYMENT_CURVES.balance_decreases_for_sec as f64
-                - PAYMENT_CURVES.payment_suggested_after_sec as f64));
-        let b = PAYMENT_CURVES.balance_to_decrease_from_gwub as f64
-            - m * PAYMENT_CURVES.payment_suggested_after_sec as f64;
+    fn calculate_payout_threshold(x: u64, payment_curves: &PaymentCurves) -> f64 {
+        let m = -((payment_curves.balance_to_decrease_from_gwub as f64
+            - payment_curves.permanent_debt_allowed_gwub as f64)
+            / (payment_curves.balance_decreases_for_sec as f64
+                - payment_curves.payment_suggested_after_sec as f64));
+        let b = payment_curves.balance_to_decrease_from_gwub as f64
+            - m * payment_curves.payment_suggested_after_sec as f64;
         m * x as f64 + b
     }
 
@@ -535,18 +660,32 @@ impl Accountant {
     }
 
     fn handle_start_message(&mut self) {
-        self.scan_for_payables();
+        self.scan_for_payables(false);
         self.scan_for_received_payments();
         self.scan_for_delinquencies();
     }
 
     fn handle_received_payments(&mut self, received_payments: ReceivedPayments) {
+        let transaction_count = received_payments.payments.len();
         self.receivable_dao
             .as_mut()
             .more_money_received(received_payments.payments);
+        self.broadcast(
+            UiScanResultsBroadcast {
+                scan_type: ScanType::Receivables,
+                transaction_count,
+                error_opt: None,
+            }
+            .tmb(0),
+        );
     }
 
     fn handle_sent_payments(&mut self, sent_payments: SentPayments) {
+        let transaction_count = sent_payments
+            .payments
+            .iter()
+            .filter(|payment| payment.is_ok())
+            .count();
         sent_payments
             .payments
             .iter()
@@ -566,7 +705,17 @@ impl Accountant {
                     "{} Please check your blockchain service URL configuration.",
                     e
                 ),
-            })
+            });
+        if transaction_count > 0 || sent_payments.requested_by_ui {
+            self.broadcast(
+                UiScanResultsBroadcast {
+                    scan_type: ScanType::Payables,
+                    transaction_count,
+                    error_opt: None,
+                }
+                .tmb(0),
+            );
+        }
     }
 
     fn handle_report_routing_service_provided_message(
@@ -643,18 +792,237 @@ impl Accountant {
 
     fn handle_node_from_ui_message(&mut self, msg: NodeFromUiMessage) {
         let client_id = msg.client_id;
-        let result: Result<(UiFinancialsRequest, u64), UiMessageError> =
-            UiFinancialsRequest::fmb(msg.body);
-        match result {
-            Ok((payload, context_id)) => self.handle_financials(client_id, context_id, payload),
-            Err(UnexpectedMessage(opcode, path)) => debug!(
-                &self.logger,
-                "Ignoring {:?} request from client {} with opcode '{}'", path, client_id, opcode
-            ),
-            Err(e) => panic!("Received obsolete error: {:?}", e),
+        if let Ok((body, context_id)) = UiScanRequest::fmb(msg.body.clone()) {
+            let response = self.handle_scan(body, client_id, context_id);
+            self.send_to_ui_gateway(ClientId(client_id), response);
+        } else if let Ok((_, context_id)) = UiBannedWalletsRequest::fmb(msg.body.clone()) {
+            let response = self.handle_banned_wallets(context_id);
+            self.send_to_ui_gateway(ClientId(client_id), response);
+        } else if let Ok((body, context_id)) = UiBanRequest::fmb(msg.body.clone()) {
+            let response = self.handle_ban(body, client_id, context_id);
+            self.send_to_ui_gateway(ClientId(client_id), response);
+        } else if let Ok((body, context_id)) = UiUnbanRequest::fmb(msg.body.clone()) {
+            let response = self.handle_unban(body, client_id, context_id);
+            self.send_to_ui_gateway(ClientId(client_id), response);
+        } else if let Ok((body, context_id)) = UiExportAccountsRequest::fmb(msg.body.clone()) {
+            let response = self.handle_export_accounts(body, context_id);
+            self.send_to_ui_gateway(ClientId(client_id), response);
+        } else {
+            let result: Result<(UiFinancialsRequest, u64), UiMessageError> =
+                UiFinancialsRequest::fmb(msg.body);
+            match result {
+                Ok((payload, context_id)) => self.handle_financials(client_id, context_id, payload),
+                Err(UnexpectedMessage(opcode, path)) => debug!(
+                    &self.logger,
+                    "Ignoring {:?} request from client {} with opcode '{}'",
+                    path,
+                    client_id,
+                    opcode
+                ),
+                Err(e) => panic!("Received obsolete error: {:?}", e),
+            }
+        }
+    }
+
+    fn handle_scan(
+        &mut self,
+        request: UiScanRequest,
+        client_id: u64,
+        context_id: u64,
+    ) -> MessageBody {
+        info!(
+            self.logger,
+            "Client {} requested an immediate {:?} scan", client_id, request.scan_type
+        );
+        match request.scan_type {
+            ScanType::Payables => self.scan_for_payables(true),
+            ScanType::Receivables => {
+                self.scan_for_received_payments();
+                self.scan_for_delinquencies();
+            }
+        }
+        UiScanResponse {}.tmb(context_id)
+    }
+
+    fn handle_export_accounts(
+        &self,
+        request: UiExportAccountsRequest,
+        context_id: u64,
+    ) -> MessageBody {
+        let since = UNIX_EPOCH + Duration::from_secs(request.since_opt.unwrap_or(0));
+        let ban_list = self.banned_dao.ban_list();
+        let ban_reasons = ban_list
+            .iter()
+            .map(|banned| (banned.wallet.address(), banned.reason_opt.clone()))
+            .collect::<HashMap<_, _>>();
+        let payables = self
+            .payable_dao
+            .records_since(since)
+            .into_iter()
+            .map(|account| UiExportedAccount {
+                wallet: account.wallet.to_string(),
+                balance_gwub: account.balance,
+                last_transaction_time: to_time_t(account.last_paid_timestamp) as u64,
+                pending_transaction_opt: account
+                    .pending_payment_transaction
+                    .map(|ppt| format!("0x{:0X}", ppt)),
+                banned: ban_reasons.contains_key(&account.wallet.address()),
+                ban_reason_opt: ban_reasons
+                    .get(&account.wallet.address())
+                    .cloned()
+                    .flatten(),
+            })
+            .collect_vec();
+        let receivables = self
+            .receivable_dao
+            .records_since(since)
+            .into_iter()
+            .map(|account| UiExportedAccount {
+                wallet: account.wallet.to_string(),
+                balance_gwub: account.balance,
+                last_transaction_time: to_time_t(account.last_received_timestamp) as u64,
+                pending_transaction_opt: None,
+                banned: ban_reasons.contains_key(&account.wallet.address()),
+                ban_reason_opt: ban_reasons
+                    .get(&account.wallet.address())
+                    .cloned()
+                    .flatten(),
+            })
+            .collect_vec();
+        // A ban outlives the accounts that led to it, so report bans that no exported account shows
+        let exported_wallets = payables
+            .iter()
+            .chain(receivables.iter())
+            .map(|account| account.wallet.clone())
+            .collect::<HashSet<_>>();
+        let banned_wallets = ban_list
+            .into_iter()
+            .map(|banned| UiBannedWallet {
+                wallet: banned.wallet.to_string(),
+                reason_opt: banned.reason_opt,
+            })
+            .filter(|banned| !exported_wallets.contains(&banned.wallet))
+            .collect_vec();
+        UiExportAccountsResponse {
+            payables,
+            receivables,
+            banned_wallets,
+        }
+        .tmb(context_id)
+    }
+
+    fn handle_banned_wallets(&self, context_id: u64) -> MessageBody {
+        let banned_wallets = self
+            .banned_dao
+            .ban_list()
+            .into_iter()
+            .map(|banned| UiBannedWallet {
+                wallet: banned.wallet.to_string(),
+                reason_opt: banned.reason_opt,
+            })
+            .collect_vec();
+        UiBannedWalletsResponse { banned_wallets }.tmb(context_id)
+    }
+
+    fn handle_ban(
+        &mut self,
+        request: UiBanRequest,
+        client_id: u64,
+        context_id: u64,
+    ) -> MessageBody {
+        let wallet = match Self::parse_wallet("ban", &request.wallet, context_id) {
+            Ok(wallet) => wallet,
+            Err(body) => return body,
+        };
+        if self.find_banned_wallet(&wallet).is_some() {
+            return MessageBody {
+                opcode: "ban".to_string(),
+                path: Conversation(context_id),
+                payload: Err((
+                    ALREADY_BANNED_ERROR,
+                    format!("Wallet {} is already banned", wallet),
+                )),
+            };
+        }
+        self.banned_dao.ban(&wallet, &request.reason);
+        info!(
+            self.logger,
+            "Wallet {} banned at the request of client {}: {}", wallet, client_id, request.reason
+        );
+        self.broadcast_ban_change(&wallet, true, Some(request.reason));
+        UiBanResponse {}.tmb(context_id)
+    }
+
+    fn handle_unban(
+        &mut self,
+        request: UiUnbanRequest,
+        client_id: u64,
+        context_id: u64,
+    ) -> MessageBody {
+        let wallet = match Self::parse_wallet("unban", &request.wallet, context_id) {
+            Ok(wallet) => wallet,
+            Err(body) => return body,
+        };
+        if self.find_banned_wallet(&wallet).is_none() {
+            return MessageBody {
+                opcode: "unban".to_string(),
+                path: Conversation(context_id),
+                payload: Err((NOT_BANNED_ERROR, format!("Wallet {} is not banned", wallet))),
+            };
+        }
+        self.banned_dao.unban(&wallet);
+        info!(
+            self.logger,
+            "Wallet {} unbanned at the request of client {}", wallet, client_id
+        );
+        self.broadcast_ban_change(&wallet, false, None);
+        UiUnbanResponse {}.tmb(context_id)
+    }
+
+    fn parse_wallet(opcode: &str, address: &str, context_id: u64) -> Result<Wallet, MessageBody> {
+        Wallet::from_str(address).map_err(|e| MessageBody {
+            opcode: opcode.to_string(),
+            path: Conversation(context_id),
+            payload: Err((
+                BAD_WALLET_ADDRESS_ERROR,
+                format!("Wallet address '{}' is not valid: {}", address, e),
+            )),
+        })
+    }
+
+    fn find_banned_wallet(&self, wallet: &Wallet) -> Option<BannedWallet> {
+        self.banned_dao
+            .ban_list()
+            .into_iter()
+            .find(|banned| banned.wallet.address() == wallet.address())
+    }
+
+    fn broadcast_ban_change(&self, wallet: &Wallet, banned: bool, reason_opt: Option<String>) {
+        self.broadcast(
+            UiBanChangedBroadcast {
+                wallet: wallet.to_string(),
+                banned,
+                reason_opt,
+            }
+            .tmb(0),
+        );
+    }
+
+    fn broadcast(&self, body: MessageBody) {
+        // Scans may finish before the UiGateway is bound; nobody is listening then anyway.
+        if self.ui_message_sub.is_some() {
+            self.send_to_ui_gateway(AllClients, body);
         }
     }
 
+    fn send_to_ui_gateway(&self, target: MessageTarget, body: MessageBody) {
+        self.ui_message_sub
+            .as_ref()
+            .expect("UiGateway not bound")
+            .try_send(NodeToUiMessage { target, body })
+            .expect("UiGateway is dead");
+    }
+
     fn handle_financials(&mut self, client_id: u64, context_id: u64, request: UiFinancialsRequest) {
         let payables = self
             .payable_dao
@@ -741,6 +1109,7 @@ pub mod tests {
     use crate::test_utils::recorder::make_recorder;
     use crate::test_utils::recorder::peer_actors_builder;
     use crate::test_utils::recorder::Recorder;
+    use crate::test_utils::recorder::Recording;
     use actix::System;
     use ethereum_types::BigEndianHash;
     use ethsign_crypto::Keccak256;
@@ -769,6 +1138,8 @@ pub mod tests {
         payment_sent_results: RefCell<Vec<Result<(), PaymentError>>>,
         top_records_parameters: Arc<Mutex<Vec<(u64, u64)>>>,
         top_records_results: RefCell<Vec<Vec<PayableAccount>>>,
+        records_since_parameters: Arc<Mutex<Vec<SystemTime>>>,
+        records_since_results: RefCell<Vec<Vec<PayableAccount>>>,
         total_results: RefCell<Vec<u64>>,
     }
 
@@ -823,6 +1194,11 @@ pub mod tests {
             self.top_records_results.borrow_mut().remove(0)
         }
 
+        fn records_since(&self, since: SystemTime) -> Vec<PayableAccount> {
+            self.records_since_parameters.lock().unwrap().push(since);
+            self.records_since_results.borrow_mut().remove(0)
+        }
+
         fn total(&self) -> u64 {
             self.total_results.borrow_mut().remove(0)
         }
@@ -871,6 +1247,16 @@ pub mod tests {
             self
         }
 
+        fn records_since_parameters(mut self, parameters: &Arc<Mutex<Vec<SystemTime>>>) -> Self {
+            self.records_since_parameters = parameters.clone();
+            self
+        }
+
+        fn records_since_result(self, result: Vec<PayableAccount>) -> Self {
+            self.records_since_results.borrow_mut().push(result);
+            self
+        }
+
         fn total_result(self, result: u64) -> Self {
             self.total_results.borrow_mut().push(result);
             self
@@ -918,6 +1304,8 @@ pub mod tests {
         paid_delinquencies_results: RefCell<Vec<Vec<ReceivableAccount>>>,
         top_records_parameters: Arc<Mutex<Vec<(u64, u64)>>>,
         top_records_results: RefCell<Vec<Vec<ReceivableAccount>>>,
+        records_since_parameters: Arc<Mutex<Vec<SystemTime>>>,
+        records_since_results: RefCell<Vec<Vec<ReceivableAccount>>>,
         total_results: RefCell<Vec<u64>>,
     }
 
@@ -986,6 +1374,11 @@ pub mod tests {
             self.top_records_results.borrow_mut().remove(0)
         }
 
+        fn records_since(&self, since: SystemTime) -> Vec<ReceivableAccount> {
+            self.records_since_parameters.lock().unwrap().push(since);
+            self.records_since_results.borrow_mut().remove(0)
+        }
+
         fn total(&self) -> u64 {
             self.total_results.borrow_mut().remove(0)
         }
@@ -1058,6 +1451,16 @@ pub mod tests {
             self
         }
 
+        fn records_since_parameters(mut self, parameters: &Arc<Mutex<Vec<SystemTime>>>) -> Self {
+            self.records_since_parameters = parameters.clone();
+            self
+        }
+
+        fn records_since_result(self, result: Vec<ReceivableAccount>) -> Self {
+            self.records_since_results.borrow_mut().push(result);
+            self
+        }
+
         fn total_result(self, result: u64) -> Self {
             self.total_results.borrow_mut().push(result);
             self
@@ -1093,19 +1496,22 @@ pub mod tests {
     #[derive(Debug, Default)]
     struct BannedDaoMock {
         ban_list_parameters: Arc<Mutex<Vec<()>>>,
-        ban_list_results: RefCell<Vec<Vec<Wallet>>>,
-        ban_parameters: Arc<Mutex<Vec<Wallet>>>,
+        ban_list_results: RefCell<Vec<Vec<BannedWallet>>>,
+        ban_parameters: Arc<Mutex<Vec<(Wallet, String)>>>,
         unban_parameters: Arc<Mutex<Vec<Wallet>>>,
     }
 
     impl BannedDao for BannedDaoMock {
-        fn ban_list(&self) -> Vec<Wallet> {
+        fn ban_list(&self) -> Vec<BannedWallet> {
             self.ban_list_parameters.lock().unwrap().push(());
             self.ban_list_results.borrow_mut().remove(0)
         }
 
-        fn ban(&self, wallet: &Wallet) {
-            self.ban_parameters.lock().unwrap().push(wallet.clone());
+        fn ban(&self, wallet: &Wallet, reason: &str) {
+            self.ban_parameters
+                .lock()
+                .unwrap()
+                .push((wallet.clone(), reason.to_string()));
         }
 
         fn unban(&self, wallet: &Wallet) {
@@ -1123,12 +1529,12 @@ pub mod tests {
             }
         }
 
-        pub fn ban_list_result(self, result: Vec<Wallet>) -> Self {
+        pub fn ban_list_result(self, result: Vec<BannedWallet>) -> Self {
             self.ban_list_results.borrow_mut().push(result);
             self
         }
 
-        pub fn ban_parameters(mut self, parameters: &Arc<Mutex<Vec<Wallet>>>) -> Self {
+        pub fn ban_parameters(mut self, parameters: &Arc<Mutex<Vec<(Wallet, String)>>>) -> Self {
             self.ban_parameters = parameters.clone();
             self
         }
@@ -1225,6 +1631,99 @@ pub mod tests {
         assert_eq!(config_dao_factory_called.as_ref(), &RefCell::new(true));
     }
 
+    #[test]
+    fn default_payment_curves_match_default_string() {
+        assert_eq!(
+            DEFAULT_PAYMENT_CURVES.to_string(),
+            masq_lib::constants::DEFAULT_PAYMENT_CURVES
+        );
+        assert_eq!(
+            PaymentCurves::from_str(masq_lib::constants::DEFAULT_PAYMENT_CURVES),
+            Ok(DEFAULT_PAYMENT_CURVES.clone())
+        );
+    }
+
+    #[test]
+    fn payment_curves_can_be_parsed() {
+        let result = PaymentCurves::from_str("1000|2000|3000|4000|5000|2500");
+
+        assert_eq!(
+            result,
+            Ok(PaymentCurves {
+                payment_suggested_after_sec: 1000,
+                payment_grace_before_ban_sec: 2000,
+                permanent_debt_allowed_gwub: 3000,
+                balance_to_decrease_from_gwub: 4000,
+                balance_decreases_for_sec: 5000,
+                unban_when_balance_below_gwub: 2500,
+            })
+        );
+    }
+
+    #[test]
+    fn payment_curves_reject_bad_syntax() {
+        assert_eq!(
+            PaymentCurves::from_str("1000|2000|3000|4000|5000"),
+            Err("Payment curves need six values separated by '|', not 5".to_string())
+        );
+        assert_eq!(
+            PaymentCurves::from_str("1000|2000|-3000|4000|5000|2500"),
+            Err("Payment curve value '-3000' is not a nonnegative integer".to_string())
+        );
+    }
+
+    #[test]
+    fn payment_curves_reject_inconsistent_values() {
+        assert_eq!(
+            PaymentCurves::from_str("5000|2000|3000|4000|5000|2500"),
+            Err("Balance decrease period (5000 sec) must be longer than payment suggestion period (5000 sec)".to_string())
+        );
+        assert_eq!(
+            PaymentCurves::from_str("1000|2000|4000|4000|5000|2500"),
+            Err("Balance to decrease from (4000 Gwub) must be larger than permanent debt allowed (4000 Gwub)".to_string())
+        );
+        assert_eq!(
+            PaymentCurves::from_str("1000|2000|3000|4000|5000|3001"),
+            Err("Unban balance (3001 Gwub) must not be larger than permanent debt allowed (3000 Gwub)".to_string())
+        );
+    }
+
+    #[test]
+    fn should_pay_uses_the_payment_curves_it_is_given() {
+        let payment_curves = PaymentCurves::from_str("100|100|1000|10000|1100|1000").unwrap();
+        let payable = |balance: i64, age: u64| PayableAccount {
+            wallet: make_wallet("wallet"),
+            balance,
+            last_paid_timestamp: SystemTime::now().sub(Duration::from_secs(age)),
+            pending_payment_transaction: None,
+        };
+
+        assert!(!Accountant::should_pay(
+            &payable(20000, 50),
+            &payment_curves
+        ));
+        assert!(!Accountant::should_pay(
+            &payable(1000, 2000),
+            &payment_curves
+        ));
+        assert!(Accountant::should_pay(
+            &payable(1001, 2000),
+            &payment_curves
+        ));
+        assert!(!Accountant::should_pay(
+            &payable(5000, 200),
+            &payment_curves
+        ));
+        assert!(Accountant::should_pay(
+            &payable(10001, 200),
+            &payment_curves
+        ));
+        assert!(!Accountant::should_pay(
+            &payable(1001, 2000),
+            &DEFAULT_PAYMENT_CURVES
+        ));
+    }
+
     #[test]
     fn financials_request_produces_financials_response() {
         let payable_top_records_parameters_arc = Arc::new(Mutex::new(vec![]));
@@ -1267,6 +1766,7 @@ pub mod tests {
                 AccountantConfig {
                     payable_scan_interval: Duration::from_millis(10_000),
                     payment_received_scan_interval: Duration::from_millis(10_000),
+                    payment_curves: DEFAULT_PAYMENT_CURVES.clone(),
                 },
                 make_wallet("some_wallet_address"),
             )),
@@ -1352,6 +1852,7 @@ pub mod tests {
                 AccountantConfig {
                     payable_scan_interval: Duration::from_millis(10_000),
                     payment_received_scan_interval: Duration::from_millis(10_000),
+                    payment_curves: DEFAULT_PAYMENT_CURVES.clone(),
                 },
                 make_wallet("some_wallet_address"),
             )),
@@ -1385,6 +1886,559 @@ pub mod tests {
         );
     }
 
+    #[test]
+    fn export_accounts_request_produces_export_accounts_response() {
+        let payable_records_since_parameters_arc = Arc::new(Mutex::new(vec![]));
+        let payable_dao = PayableDaoMock::new()
+            .records_since_parameters(&payable_records_since_parameters_arc)
+            .records_since_result(vec![PayableAccount {
+                wallet: make_wallet("earning"),
+                balance: 12345678,
+                last_paid_timestamp: from_time_t(1_600_000_000),
+                pending_payment_transaction: Some(H256::from_uint(&U256::from(123))),
+            }]);
+        let receivable_records_since_parameters_arc = Arc::new(Mutex::new(vec![]));
+        let receivable_dao = ReceivableDaoMock::new()
+            .records_since_parameters(&receivable_records_since_parameters_arc)
+            .records_since_result(vec![
+                ReceivableAccount {
+                    wallet: make_wallet("consuming 1"),
+                    balance: 87654321,
+                    last_received_timestamp: from_time_t(1_600_000_001),
+                },
+                ReceivableAccount {
+                    wallet: make_wallet("consuming 2"),
+                    balance: -5,
+                    last_received_timestamp: from_time_t(1_600_000_002),
+                },
+            ]);
+        let banned_dao = BannedDaoMock::new().ban_list_result(vec![
+            BannedWallet {
+                wallet: make_wallet("consuming 1"),
+                reason_opt: Some("Delinquency".to_string()),
+            },
+            BannedWallet {
+                wallet: make_wallet("accountless"),
+                reason_opt: Some("Manual".to_string()),
+            },
+        ]);
+        let subject = make_subject(
+            None,
+            Some(payable_dao),
+            Some(receivable_dao),
+            Some(banned_dao),
+            None,
+        );
+
+        let ui_gateway_recording_arc = run_ui_request(
+            subject,
+            UiExportAccountsRequest {
+                since_opt: Some(1_500_000_000),
+            }
+            .tmb(2222),
+        );
+
+        assert_eq!(
+            *payable_records_since_parameters_arc.lock().unwrap(),
+            vec![from_time_t(1_500_000_000)]
+        );
+        assert_eq!(
+            *receivable_records_since_parameters_arc.lock().unwrap(),
+            vec![from_time_t(1_500_000_000)]
+        );
+        let ui_gateway_recording = ui_gateway_recording_arc.lock().unwrap();
+        let response = ui_gateway_recording.get_record::<NodeToUiMessage>(0);
+        assert_eq!(response.target, MessageTarget::ClientId(1234));
+        assert_eq!(
+            UiExportAccountsResponse::fmb(response.body.clone()).unwrap(),
+            (
+                UiExportAccountsResponse {
+                    payables: vec![UiExportedAccount {
+                        wallet: make_wallet("earning").to_string(),
+                        balance_gwub: 12345678,
+                        last_transaction_time: 1_600_000_000,
+                        pending_transaction_opt: Some(
+                            "0x000000000000000000000000000000000000000000000000000000000000007B"
+                                .to_string()
+                        ),
+                        banned: false,
+                        ban_reason_opt: None,
+                    }],
+                    receivables: vec![
+                        UiExportedAccount {
+                            wallet: make_wallet("consuming 1").to_string(),
+                            balance_gwub: 87654321,
+                            last_transaction_time: 1_600_000_001,
+                            pending_transaction_opt: None,
+                            banned: true,
+                            ban_reason_opt: Some("Delinquency".to_string()),
+                        },
+                        UiExportedAccount {
+                            wallet: make_wallet("consuming 2").to_string(),
+                            balance_gwub: -5,
+                            last_transaction_time: 1_600_000_002,
+                            pending_transaction_opt: None,
+                            banned: false,
+                            ban_reason_opt: None,
+                        },
+                    ],
+                    banned_wallets: vec![UiBannedWallet {
+                        wallet: make_wallet("accountless").to_string(),
+                        reason_opt: Some("Manual".to_string()),
+                    }],
+                },
+                2222
+            )
+        );
+    }
+
+    #[test]
+    fn export_accounts_request_without_since_exports_everything() {
+        let payable_records_since_parameters_arc = Arc::new(Mutex::new(vec![]));
+        let payable_dao = PayableDaoMock::new()
+            .records_since_parameters(&payable_records_since_parameters_arc)
+            .records_since_result(vec![]);
+        let receivable_dao = ReceivableDaoMock::new().records_since_result(vec![]);
+        let banned_dao = BannedDaoMock::new().ban_list_result(vec![]);
+        let subject = make_subject(
+            None,
+            Some(payable_dao),
+            Some(receivable_dao),
+            Some(banned_dao),
+            None,
+        );
+
+        run_ui_request(
+            subject,
+            UiExportAccountsRequest { since_opt: None }.tmb(2222),
+        );
+
+        assert_eq!(
+            *payable_records_since_parameters_arc.lock().unwrap(),
+            vec![UNIX_EPOCH]
+        );
+    }
+
+    #[test]
+    fn scan_request_for_payables_triggers_payable_scan() {
+        init_test_logging();
+        let subject = make_subject(None, None, None, None, None);
+
+        let ui_gateway_recording_arc = run_ui_request(
+            subject,
+            UiScanRequest {
+                scan_type: ScanType::Payables,
+            }
+            .tmb(2222),
+        );
+
+        let ui_gateway_recording = ui_gateway_recording_arc.lock().unwrap();
+        let broadcast = ui_gateway_recording.get_record::<NodeToUiMessage>(0);
+        assert_eq!(broadcast.target, MessageTarget::AllClients);
+        assert_eq!(
+            UiScanResultsBroadcast::fmb(broadcast.body.clone())
+                .unwrap()
+                .0,
+            UiScanResultsBroadcast {
+                scan_type: ScanType::Payables,
+                transaction_count: 0,
+                error_opt: None,
+            }
+        );
+        let response = ui_gateway_recording.get_record::<NodeToUiMessage>(1);
+        assert_eq!(response.target, MessageTarget::ClientId(1234));
+        assert_eq!(
+            UiScanResponse::fmb(response.body.clone()).unwrap(),
+            (UiScanResponse {}, 2222)
+        );
+        let tlh = TestLogHandler::new();
+        tlh.exists_log_containing(
+            "INFO: Accountant: Client 1234 requested an immediate Payables scan",
+        );
+        tlh.exists_log_containing("DEBUG: Accountant: Scanning for payables");
+    }
+
+    #[test]
+    fn scan_request_for_receivables_triggers_received_payment_and_delinquency_scans() {
+        let new_delinquencies_parameters_arc = Arc::new(Mutex::new(vec![]));
+        let receivable_dao = ReceivableDaoMock::new()
+            .new_delinquencies_parameters(&new_delinquencies_parameters_arc)
+            .new_delinquencies_result(vec![])
+            .paid_delinquencies_result(vec![]);
+        let subject = make_subject(
+            Some(bc_from_ac_plus_earning_wallet(
+                AccountantConfig {
+                    payable_scan_interval: Duration::from_millis(10_000),
+                    payment_received_scan_interval: Duration::from_millis(10_000),
+                    payment_curves: DEFAULT_PAYMENT_CURVES.clone(),
+                },
+                make_wallet("earning_wallet"),
+            )),
+            None,
+            Some(receivable_dao),
+            None,
+            Some(PersistentConfigurationMock::new().start_block_result(Ok(Some(5)))),
+        );
+        let system = System::new("test");
+        let (ui_gateway, _, ui_gateway_recording_arc) = make_recorder();
+        let blockchain_bridge = Recorder::new().retrieve_transactions_response(Ok(vec![]));
+        let blockchain_bridge_recording_arc = blockchain_bridge.get_recording();
+        let subject_addr = subject.start();
+        let peer_actors = peer_actors_builder()
+            .ui_gateway(ui_gateway)
+            .blockchain_bridge(blockchain_bridge)
+            .build();
+        subject_addr.try_send(BindMessage { peer_actors }).unwrap();
+
+        subject_addr
+            .try_send(NodeFromUiMessage {
+                client_id: 1234,
+                body: UiScanRequest {
+                    scan_type: ScanType::Receivables,
+                }
+                .tmb(2222),
+            })
+            .unwrap();
+
+        System::current().stop();
+        system.run();
+        let ui_gateway_recording = ui_gateway_recording_arc.lock().unwrap();
+        let response = ui_gateway_recording.get_record::<NodeToUiMessage>(0);
+        assert_eq!(
+            UiScanResponse::fmb(response.body.clone()).unwrap(),
+            (UiScanResponse {}, 2222)
+        );
+        let blockchain_bridge_recording = blockchain_bridge_recording_arc.lock().unwrap();
+        assert_eq!(
+            blockchain_bridge_recording.get_record::<RetrieveTransactions>(0),
+            &RetrieveTransactions {
+                start_block: 5,
+                recipient: make_wallet("earning_wallet"),
+            }
+        );
+        assert_eq!(new_delinquencies_parameters_arc.lock().unwrap().len(), 1);
+    }
+
+    #[test]
+    fn banned_wallets_request_produces_banned_wallets_response() {
+        let banned_dao = BannedDaoMock::new().ban_list_result(vec![
+            BannedWallet {
+                wallet: make_wallet("deadbeat"),
+                reason_opt: Some("Delinquency".to_string()),
+            },
+            BannedWallet {
+                wallet: make_wallet("legacy"),
+                reason_opt: None,
+            },
+        ]);
+        let subject = make_subject(None, None, None, Some(banned_dao), None);
+
+        let ui_gateway_recording_arc = run_ui_request(subject, UiBannedWalletsRequest {}.tmb(2222));
+
+        let ui_gateway_recording = ui_gateway_recording_arc.lock().unwrap();
+        let response = ui_gateway_recording.get_record::<NodeToUiMessage>(0);
+        assert_eq!(response.target, MessageTarget::ClientId(1234));
+        assert_eq!(
+            UiBannedWalletsResponse::fmb(response.body.clone()).unwrap(),
+            (
+                UiBannedWalletsResponse {
+                    banned_wallets: vec![
+                        UiBannedWallet {
+                            wallet: make_wallet("deadbeat").to_string(),
+                            reason_opt: Some("Delinquency".to_string()),
+                        },
+                        UiBannedWallet {
+                            wallet: make_wallet("legacy").to_string(),
+                            reason_opt: None,
+                        },
+                    ]
+                },
+                2222
+            )
+        );
+    }
+
+    #[test]
+    fn ban_request_bans_wallet_and_broadcasts_the_change() {
+        let ban_parameters_arc = Arc::new(Mutex::new(vec![]));
+        let banned_dao = BannedDaoMock::new()
+            .ban_list_result(vec![])
+            .ban_parameters(&ban_parameters_arc);
+        let subject = make_subject(None, None, None, Some(banned_dao), None);
+        let wallet = make_wallet("troublemaker");
+
+        let ui_gateway_recording_arc = run_ui_request(
+            subject,
+            UiBanRequest {
+                wallet: wallet.to_string(),
+                reason: "Spamming".to_string(),
+            }
+            .tmb(2222),
+        );
+
+        let ban_parameters = ban_parameters_arc.lock().unwrap();
+        assert_eq!(
+            *ban_parameters,
+            vec![(wallet.clone(), "Spamming".to_string())]
+        );
+        let ui_gateway_recording = ui_gateway_recording_arc.lock().unwrap();
+        let broadcast = ui_gateway_recording.get_record::<NodeToUiMessage>(0);
+        assert_eq!(broadcast.target, MessageTarget::AllClients);
+        assert_eq!(
+            UiBanChangedBroadcast::fmb(broadcast.body.clone())
+                .unwrap()
+                .0,
+            UiBanChangedBroadcast {
+                wallet: wallet.to_string(),
+                banned: true,
+                reason_opt: Some("Spamming".to_string()),
+            }
+        );
+        let response = ui_gateway_recording.get_record::<NodeToUiMessage>(1);
+        assert_eq!(response.target, MessageTarget::ClientId(1234));
+        assert_eq!(
+            UiBanResponse::fmb(response.body.clone()).unwrap(),
+            (UiBanResponse {}, 2222)
+        );
+    }
+
+    #[test]
+    fn ban_request_for_banned_wallet_is_rejected() {
+        let ban_parameters_arc = Arc::new(Mutex::new(vec![]));
+        let wallet = make_wallet("troublemaker");
+        let banned_dao = BannedDaoMock::new()
+            .ban_list_result(vec![BannedWallet {
+                wallet: wallet.clone(),
+                reason_opt: None,
+            }])
+            .ban_parameters(&ban_parameters_arc);
+        let subject = make_subject(None, None, None, Some(banned_dao), None);
+
+        let ui_gateway_recording_arc = run_ui_request(
+            subject,
+            UiBanRequest {
+                wallet: wallet.to_string(),
+                reason: "Spamming".to_string(),
+            }
+            .tmb(2222),
+        );
+
+        assert_eq!(ban_parameters_arc.lock().unwrap().len(), 0);
+        let ui_gateway_recording = ui_gateway_recording_arc.lock().unwrap();
+        assert_eq!(ui_gateway_recording.len(), 1);
+        let response = ui_gateway_recording.get_record::<NodeToUiMessage>(0);
+        assert_eq!(
+            response.body,
+            MessageBody {
+                opcode: "ban".to_string(),
+                path: Conversation(2222),
+                payload: Err((
+                    ALREADY_BANNED_ERROR,
+                    format!("Wallet {} is already banned", wallet)
+                ))
+            }
+        );
+    }
+
+    #[test]
+    fn ban_request_with_bad_wallet_address_is_rejected() {
+        let subject = make_subject(None, None, None, None, None);
+
+        let ui_gateway_recording_arc = run_ui_request(
+            subject,
+            UiBanRequest {
+                wallet: "booga".to_string(),
+                reason: "Spamming".to_string(),
+            }
+            .tmb(2222),
+        );
+
+        let ui_gateway_recording = ui_gateway_recording_arc.lock().unwrap();
+        let response = ui_gateway_recording.get_record::<NodeToUiMessage>(0);
+        assert_eq!(
+            response.body,
+            MessageBody {
+                opcode: "ban".to_string(),
+                path: Conversation(2222),
+                payload: Err((
+                    BAD_WALLET_ADDRESS_ERROR,
+                    "Wallet address 'booga' is not valid: Invalid address".to_string()
+                ))
+            }
+        );
+    }
+
+    #[test]
+    fn unban_request_unbans_wallet_and_broadcasts_the_change() {
+        let unban_parameters_arc = Arc::new(Mutex::new(vec![]));
+        let wallet = make_wallet("reformed");
+        let banned_dao = BannedDaoMock::new()
+            .ban_list_result(vec![BannedWallet {
+                wallet: wallet.clone(),
+                reason_opt: Some("Delinquency".to_string()),
+            }])
+            .unban_parameters(&unban_parameters_arc);
+        let subject = make_subject(None, None, None, Some(banned_dao), None);
+
+        let ui_gateway_recording_arc = run_ui_request(
+            subject,
+            UiUnbanRequest {
+                wallet: wallet.to_string(),
+            }
+            .tmb(2222),
+        );
+
+        let unban_parameters = unban_parameters_arc.lock().unwrap();
+        assert_eq!(*unban_parameters, vec![wallet.clone()]);
+        let ui_gateway_recording = ui_gateway_recording_arc.lock().unwrap();
+        let broadcast = ui_gateway_recording.get_record::<NodeToUiMessage>(0);
+        assert_eq!(broadcast.target, MessageTarget::AllClients);
+        assert_eq!(
+            UiBanChangedBroadcast::fmb(broadcast.body.clone())
+                .unwrap()
+                .0,
+            UiBanChangedBroadcast {
+                wallet: wallet.to_string(),
+                banned: false,
+                reason_opt: None,
+            }
+        );
+        let response = ui_gateway_recording.get_record::<NodeToUiMessage>(1);
+        assert_eq!(
+            UiUnbanResponse::fmb(response.body.clone()).unwrap(),
+            (UiUnbanResponse {}, 2222)
+        );
+    }
+
+    #[test]
+    fn unban_request_for_unbanned_wallet_is_rejected() {
+        let unban_parameters_arc = Arc::new(Mutex::new(vec![]));
+        let banned_dao = BannedDaoMock::new()
+            .ban_list_result(vec![])
+            .unban_parameters(&unban_parameters_arc);
+        let subject = make_subject(None, None, None, Some(banned_dao), None);
+        let wallet = make_wallet("innocent");
+
+        let ui_gateway_recording_arc = run_ui_request(
+            subject,
+            UiUnbanRequest {
+                wallet: wallet.to_string(),
+            }
+            .tmb(2222),
+        );
+
+        assert_eq!(unban_parameters_arc.lock().unwrap().len(), 0);
+        let ui_gateway_recording = ui_gateway_recording_arc.lock().unwrap();
+        let response = ui_gateway_recording.get_record::<NodeToUiMessage>(0);
+        assert_eq!(
+            response.body,
+            MessageBody {
+                opcode: "unban".to_string(),
+                path: Conversation(2222),
+                payload: Err((NOT_BANNED_ERROR, format!("Wallet {} is not banned", wallet)))
+            }
+        );
+    }
+
+    #[test]
+    fn sent_payments_are_broadcast_as_scan_results() {
+        let payable_dao = PayableDaoMock::new().payment_sent_result(Ok(()));
+        let mut subject = make_subject(None, Some(payable_dao), None, None, None);
+        let system = System::new("test");
+        let (ui_gateway, _, ui_gateway_recording_arc) = make_recorder();
+        subject.ui_message_sub = Some(ui_gateway.start().recipient::<NodeToUiMessage>());
+
+        subject.handle_sent_payments(SentPayments {
+            payments: vec![
+                Ok(Payment::new(
+                    make_wallet("paid"),
+                    1234,
+                    H256::from_uint(&U256::from(1)),
+                )),
+                Err(BlockchainError::TransactionFailed("Booga".to_string())),
+            ],
+            requested_by_ui: false,
+        });
+
+        System::current().stop();
+        system.run();
+        let ui_gateway_recording = ui_gateway_recording_arc.lock().unwrap();
+        let broadcast = ui_gateway_recording.get_record::<NodeToUiMessage>(0);
+        assert_eq!(broadcast.target, MessageTarget::AllClients);
+        assert_eq!(
+            UiScanResultsBroadcast::fmb(broadcast.body.clone())
+                .unwrap()
+                .0,
+            UiScanResultsBroadcast {
+                scan_type: ScanType::Payables,
+                transaction_count: 1,
+                error_opt: None,
+            }
+        );
+    }
+
+    #[test]
+    fn failed_payable_scan_is_broadcast_as_scan_results() {
+        let now = to_time_t(SystemTime::now());
+        let payable_dao = PayableDaoMock::new().non_pending_payables_result(vec![PayableAccount {
+            wallet: make_wallet("creditor"),
+            balance: DEFAULT_PAYMENT_CURVES.permanent_debt_allowed_gwub + 1000,
+            last_paid_timestamp: from_time_t(
+                now - DEFAULT_PAYMENT_CURVES.balance_decreases_for_sec - 10,
+            ),
+            pending_payment_transaction: None,
+        }]);
+        let mut subject = make_subject(None, Some(payable_dao), None, None, None);
+        let system = System::new("test");
+        let (ui_gateway, _, ui_gateway_recording_arc) = make_recorder();
+        let blockchain_bridge = Recorder::new()
+            .report_accounts_payable_response(Err("Failed to send transaction".to_string()));
+        subject.ui_message_sub = Some(ui_gateway.start().recipient::<NodeToUiMessage>());
+        subject.report_accounts_payable_sub = Some(
+            blockchain_bridge
+                .start()
+                .recipient::<ReportAccountsPayable>(),
+        );
+
+        subject.scan_for_payables(true);
+
+        System::current().stop();
+        system.run();
+        let ui_gateway_recording = ui_gateway_recording_arc.lock().unwrap();
+        let broadcast = ui_gateway_recording.get_record::<NodeToUiMessage>(0);
+        assert_eq!(broadcast.target, MessageTarget::AllClients);
+        assert_eq!(
+            UiScanResultsBroadcast::fmb(broadcast.body.clone())
+                .unwrap()
+                .0,
+            UiScanResultsBroadcast {
+                scan_type: ScanType::Payables,
+                transaction_count: 0,
+                error_opt: Some("Failed to send transaction".to_string()),
+            }
+        );
+    }
+
+    #[test]
+    fn periodic_payable_scan_that_pays_nothing_is_not_broadcast() {
+        let payable_dao = PayableDaoMock::new().non_pending_payables_result(vec![]);
+        let mut subject = make_subject(None, Some(payable_dao), None, None, None);
+        let system = System::new("test");
+        let (ui_gateway, _, ui_gateway_recording_arc) = make_recorder();
+        subject.ui_message_sub = Some(ui_gateway.start().recipient::<NodeToUiMessage>());
+
+        subject.scan_for_payables(false);
+        subject.handle_sent_payments(SentPayments {
+            payments: vec![Err(BlockchainError::TransactionFailed("Booga".to_string()))],
+            requested_by_ui: false,
+        });
+
+        System::current().stop();
+        system.run();
+        let ui_gateway_recording = ui_gateway_recording_arc.lock().unwrap();
+        assert_eq!(ui_gateway_recording.len(), 0);
+    }
+
     #[test]
     fn accountant_calls_payable_dao_payment_sent_when_sent_payments() {
         let payment_sent_parameters = Arc::new(Mutex::new(vec![]));
@@ -1402,6 +2456,7 @@ pub mod tests {
                 AccountantConfig {
                     payable_scan_interval: Duration::from_millis(100),
                     payment_received_scan_interval: Duration::from_secs(10_000),
+                    payment_curves: DEFAULT_PAYMENT_CURVES.clone(),
                 },
                 make_wallet("some_wallet_address"),
             )),
@@ -1421,6 +2476,7 @@ pub mod tests {
         );
         let send_payments = SentPayments {
             payments: vec![Ok(expected_payment.clone())],
+            requested_by_ui: false,
         };
 
         let subject = accountant.start();
@@ -1450,6 +2506,7 @@ pub mod tests {
                 AccountantConfig {
                     payable_scan_interval: Duration::from_millis(100),
                     payment_received_scan_interval: Duration::from_secs(10_000),
+                    payment_curves: DEFAULT_PAYMENT_CURVES.clone(),
                 },
                 make_wallet("some_wallet_address"),
             )),
@@ -1463,6 +2520,7 @@ pub mod tests {
             payments: vec![Err(BlockchainError::TransactionFailed(
                 "Payment attempt failed".to_string(),
             ))],
+            requested_by_ui: false,
         };
 
         let subject = accountant.start();
@@ -1486,7 +2544,7 @@ pub mod tests {
         let expected_wallet = make_wallet("blah");
         let expected_wallet_inner = expected_wallet.clone();
         let expected_amount =
-            u64::try_from(PAYMENT_CURVES.permanent_debt_allowed_gwub + 1000).unwrap();
+            u64::try_from(DEFAULT_PAYMENT_CURVES.permanent_debt_allowed_gwub + 1000).unwrap();
 
         let expected_pending_payment_transaction = H256::from("transaction_hash".keccak256());
         let expected_pending_payment_transaction_inner =
@@ -1495,9 +2553,9 @@ pub mod tests {
         let payable_dao = PayableDaoMock::new()
             .non_pending_payables_result(vec![PayableAccount {
                 wallet: expected_wallet.clone(),
-                balance: PAYMENT_CURVES.permanent_debt_allowed_gwub + 1000,
+                balance: DEFAULT_PAYMENT_CURVES.permanent_debt_allowed_gwub + 1000,
                 last_paid_timestamp: from_time_t(
-                    now - PAYMENT_CURVES.balance_decreases_for_sec - 10,
+                    now - DEFAULT_PAYMENT_CURVES.balance_decreases_for_sec - 10,
                 ),
                 pending_payment_transaction: None,
             }])
@@ -1527,6 +2585,7 @@ pub mod tests {
                     AccountantConfig {
                         payable_scan_interval: Duration::from_millis(100),
                         payment_received_scan_interval: Duration::from_secs(10_000),
+                        payment_curves: DEFAULT_PAYMENT_CURVES.clone(),
                     },
                     earning_wallet.clone(),
                 )),
@@ -1560,7 +2619,8 @@ pub mod tests {
         assert_eq!(
             actual_payments,
             &SentPayments {
-                payments: vec![Ok(expected_payment)]
+                payments: vec![Ok(expected_payment)],
+                requested_by_ui: false,
             }
         );
     }
@@ -1575,9 +2635,9 @@ pub mod tests {
         let payable_dao = PayableDaoMock::new()
             .non_pending_payables_result(vec![PayableAccount {
                 wallet: expected_wallet.clone(),
-                balance: PAYMENT_CURVES.permanent_debt_allowed_gwub + 1000,
+                balance: DEFAULT_PAYMENT_CURVES.permanent_debt_allowed_gwub + 1000,
                 last_paid_timestamp: from_time_t(
-                    now - PAYMENT_CURVES.balance_decreases_for_sec - 10,
+                    now - DEFAULT_PAYMENT_CURVES.balance_decreases_for_sec - 10,
                 ),
                 pending_payment_transaction: None,
             }])
@@ -1603,6 +2663,7 @@ pub mod tests {
                     AccountantConfig {
                         payable_scan_interval: Duration::from_millis(100),
                         payment_received_scan_interval: Duration::from_secs(10_000),
+                        payment_curves: DEFAULT_PAYMENT_CURVES.clone(),
                     },
                     earning_wallet.clone(),
                 )),
@@ -1646,6 +2707,7 @@ pub mod tests {
             AccountantConfig {
                 payable_scan_interval: Duration::from_secs(10_000),
                 payment_received_scan_interval: Duration::from_millis(100),
+                payment_curves: DEFAULT_PAYMENT_CURVES.clone(),
             },
             earning_wallet.clone(),
         );
@@ -1715,6 +2777,7 @@ pub mod tests {
             AccountantConfig {
                 payable_scan_interval: Duration::from_secs(10_000),
                 payment_received_scan_interval: Duration::from_millis(100),
+                payment_curves: DEFAULT_PAYMENT_CURVES.clone(),
             },
             earning_wallet.clone(),
         );
@@ -1776,6 +2839,7 @@ pub mod tests {
             AccountantConfig {
                 payable_scan_interval: Duration::from_secs(10_000),
                 payment_received_scan_interval: Duration::from_millis(100),
+                payment_curves: DEFAULT_PAYMENT_CURVES.clone(),
             },
             earning_wallet.clone(),
         );
@@ -1838,6 +2902,7 @@ pub mod tests {
                 AccountantConfig {
                     payable_scan_interval: Duration::from_secs(10_000),
                     payment_received_scan_interval: Duration::from_secs(10_000),
+                    payment_curves: DEFAULT_PAYMENT_CURVES.clone(),
                 },
                 earning_wallet.clone(),
             )),
@@ -1886,6 +2951,7 @@ pub mod tests {
                 AccountantConfig {
                     payable_scan_interval: Duration::from_millis(100),
                     payment_received_scan_interval: Duration::from_secs(100),
+                    payment_curves: DEFAULT_PAYMENT_CURVES.clone(),
                 },
                 make_wallet("hi"),
             );
@@ -1893,17 +2959,17 @@ pub mod tests {
             // slightly above minimum balance, to the right of the curve (time intersection)
             let account0 = PayableAccount {
                 wallet: make_wallet("wallet0"),
-                balance: PAYMENT_CURVES.permanent_debt_allowed_gwub + 1,
+                balance: DEFAULT_PAYMENT_CURVES.permanent_debt_allowed_gwub + 1,
                 last_paid_timestamp: from_time_t(
-                    now - PAYMENT_CURVES.balance_decreases_for_sec - 10,
+                    now - DEFAULT_PAYMENT_CURVES.balance_decreases_for_sec - 10,
                 ),
                 pending_payment_transaction: None,
             };
             let account1 = PayableAccount {
                 wallet: make_wallet("wallet1"),
-                balance: PAYMENT_CURVES.permanent_debt_allowed_gwub + 2,
+                balance: DEFAULT_PAYMENT_CURVES.permanent_debt_allowed_gwub + 2,
                 last_paid_timestamp: from_time_t(
-                    now - PAYMENT_CURVES.balance_decreases_for_sec - 12,
+                    now - DEFAULT_PAYMENT_CURVES.balance_decreases_for_sec - 12,
                 ),
                 pending_payment_transaction: None,
             };
@@ -1937,6 +3003,7 @@ pub mod tests {
             AccountantConfig {
                 payable_scan_interval: Duration::from_secs(1000),
                 payment_received_scan_interval: Duration::from_secs(1000),
+                payment_curves: DEFAULT_PAYMENT_CURVES.clone(),
             },
             make_wallet("buy"),
             make_wallet("hi"),
@@ -1970,6 +3037,7 @@ pub mod tests {
             AccountantConfig {
                 payable_scan_interval: Duration::from_secs(100),
                 payment_received_scan_interval: Duration::from_secs(1000),
+                payment_curves: DEFAULT_PAYMENT_CURVES.clone(),
             },
             make_wallet("mine"),
         );
@@ -1978,27 +3046,27 @@ pub mod tests {
             // below minimum balance, to the right of time intersection (inside buffer zone)
             PayableAccount {
                 wallet: make_wallet("wallet0"),
-                balance: PAYMENT_CURVES.permanent_debt_allowed_gwub - 1,
+                balance: DEFAULT_PAYMENT_CURVES.permanent_debt_allowed_gwub - 1,
                 last_paid_timestamp: from_time_t(
-                    now - PAYMENT_CURVES.balance_decreases_for_sec - 10,
+                    now - DEFAULT_PAYMENT_CURVES.balance_decreases_for_sec - 10,
                 ),
                 pending_payment_transaction: None,
             },
             // above balance intersection, to the left of minimum time (inside buffer zone)
             PayableAccount {
                 wallet: make_wallet("wallet1"),
-                balance: PAYMENT_CURVES.balance_to_decrease_from_gwub + 1,
+                balance: DEFAULT_PAYMENT_CURVES.balance_to_decrease_from_gwub + 1,
                 last_paid_timestamp: from_time_t(
-                    now - PAYMENT_CURVES.payment_suggested_after_sec + 10,
+                    now - DEFAULT_PAYMENT_CURVES.payment_suggested_after_sec + 10,
                 ),
                 pending_payment_transaction: None,
             },
             // above minimum balance, to the right of minimum time (not in buffer zone, below the curve)
             PayableAccount {
                 wallet: make_wallet("wallet2"),
-                balance: PAYMENT_CURVES.balance_to_decrease_from_gwub - 1000,
+                balance: DEFAULT_PAYMENT_CURVES.balance_to_decrease_from_gwub - 1000,
                 last_paid_timestamp: from_time_t(
-                    now - PAYMENT_CURVES.payment_suggested_after_sec - 1,
+                    now - DEFAULT_PAYMENT_CURVES.payment_suggested_after_sec - 1,
                 ),
                 pending_payment_transaction: None,
             },
@@ -2016,7 +3084,7 @@ pub mod tests {
         let mut subject = make_subject(Some(config), Some(payable_dao), None, None, None);
         subject.report_accounts_payable_sub = Some(report_accounts_payable_sub);
 
-        subject.scan_for_payables();
+        subject.scan_for_payables(false);
 
         System::current().stop_with_code(0);
         system.run();
@@ -2032,6 +3100,7 @@ pub mod tests {
             AccountantConfig {
                 payable_scan_interval: Duration::from_millis(100),
                 payment_received_scan_interval: Duration::from_millis(1_000),
+                payment_curves: DEFAULT_PAYMENT_CURVES.clone(),
             },
             make_wallet("mine"),
         );
@@ -2040,18 +3109,18 @@ pub mod tests {
             // slightly above minimum balance, to the right of the curve (time intersection)
             PayableAccount {
                 wallet: make_wallet("wallet0"),
-                balance: PAYMENT_CURVES.permanent_debt_allowed_gwub + 1,
+                balance: DEFAULT_PAYMENT_CURVES.permanent_debt_allowed_gwub + 1,
                 last_paid_timestamp: from_time_t(
-                    now - PAYMENT_CURVES.balance_decreases_for_sec - 10,
+                    now - DEFAULT_PAYMENT_CURVES.balance_decreases_for_sec - 10,
                 ),
                 pending_payment_transaction: None,
             },
             // slightly above the curve (balance intersection), to the right of minimum time
             PayableAccount {
                 wallet: make_wallet("wallet1"),
-                balance: PAYMENT_CURVES.balance_to_decrease_from_gwub + 1,
+                balance: DEFAULT_PAYMENT_CURVES.balance_to_decrease_from_gwub + 1,
                 last_paid_timestamp: from_time_t(
-                    now - PAYMENT_CURVES.payment_suggested_after_sec - 10,
+                    now - DEFAULT_PAYMENT_CURVES.payment_suggested_after_sec - 10,
                 ),
                 pending_payment_transaction: None,
             },
@@ -2102,6 +3171,7 @@ pub mod tests {
                 AccountantConfig {
                     payable_scan_interval: Duration::from_secs(10_000),
                     payment_received_scan_interval: Duration::from_millis(100),
+                    payment_curves: DEFAULT_PAYMENT_CURVES.clone(),
                 },
                 make_wallet("hi"),
             );
@@ -2137,7 +3207,7 @@ pub mod tests {
         let ban_parameters = ban_parameters_arc.lock().unwrap();
         assert_eq!(
             "0x00000000000000000077616c6c65743132333464",
-            &format!("{:#x}", &ban_parameters[0].address())
+            &format!("{:#x}", &ban_parameters[0].0.address())
         );
     }
 
@@ -2171,10 +3241,12 @@ pub mod tests {
     #[test]
     fn scan_for_delinquencies_triggers_bans_and_unbans() {
         init_test_logging();
+        let payment_curves = PaymentCurves::from_str("1000|2000|3000|4000|5000|2500").unwrap();
         let config = bc_from_ac_plus_earning_wallet(
             AccountantConfig {
                 payable_scan_interval: Duration::from_secs(100),
                 payment_received_scan_interval: Duration::from_secs(1000),
+                payment_curves: payment_curves.clone(),
             },
             make_wallet("mine"),
         );
@@ -2208,13 +3280,17 @@ pub mod tests {
 
         let new_delinquencies_parameters: MutexGuard<Vec<(SystemTime, PaymentCurves)>> =
             new_delinquencies_parameters_arc.lock().unwrap();
-        assert_eq!(PAYMENT_CURVES.clone(), new_delinquencies_parameters[0].1);
+        assert_eq!(payment_curves, new_delinquencies_parameters[0].1);
         let paid_delinquencies_parameters: MutexGuard<Vec<PaymentCurves>> =
             paid_delinquencies_parameters_arc.lock().unwrap();
-        assert_eq!(PAYMENT_CURVES.clone(), paid_delinquencies_parameters[0]);
+        assert_eq!(payment_curves, paid_delinquencies_parameters[0]);
         let ban_parameters = ban_parameters_arc.lock().unwrap();
-        assert!(ban_parameters.contains(&newly_banned_1.wallet));
-        assert!(ban_parameters.contains(&newly_banned_2.wallet));
+        assert!(
+            ban_parameters.contains(&(newly_banned_1.wallet.clone(), "Delinquency".to_string()))
+        );
+        assert!(
+            ban_parameters.contains(&(newly_banned_2.wallet.clone(), "Delinquency".to_string()))
+        );
         assert_eq!(2, ban_parameters.len());
         let unban_parameters = unban_parameters_arc.lock().unwrap();
         assert!(unban_parameters.contains(&newly_unbanned_1.wallet));
@@ -2234,6 +3310,7 @@ pub mod tests {
             AccountantConfig {
                 payable_scan_interval: Duration::from_secs(100),
                 payment_received_scan_interval: Duration::from_secs(100),
+                payment_curves: DEFAULT_PAYMENT_CURVES.clone(),
             },
             make_wallet("hi"),
         );
@@ -2288,6 +3365,7 @@ pub mod tests {
             AccountantConfig {
                 payable_scan_interval: Duration::from_secs(100),
                 payment_received_scan_interval: Duration::from_secs(100),
+                payment_curves: DEFAULT_PAYMENT_CURVES.clone(),
             },
             consuming_wallet.clone(),
             make_wallet("our earning wallet"),
@@ -2341,6 +3419,7 @@ pub mod tests {
             AccountantConfig {
                 payable_scan_interval: Duration::from_secs(100),
                 payment_received_scan_interval: Duration::from_secs(100),
+                payment_curves: DEFAULT_PAYMENT_CURVES.clone(),
             },
             earning_wallet.clone(),
         );
@@ -2392,6 +3471,7 @@ pub mod tests {
             AccountantConfig {
                 payable_scan_interval: Duration::from_secs(100),
                 payment_received_scan_interval: Duration::from_secs(100),
+                payment_curves: DEFAULT_PAYMENT_CURVES.clone(),
             },
             make_wallet("hi"),
         );
@@ -2439,6 +3519,7 @@ pub mod tests {
             AccountantConfig {
                 payable_scan_interval: Duration::from_secs(100),
                 payment_received_scan_interval: Duration::from_secs(100),
+                payment_curves: DEFAULT_PAYMENT_CURVES.clone(),
             },
             consuming_wallet.clone(),
             make_wallet("the earning wallet"),
@@ -2483,6 +3564,7 @@ pub mod tests {
             AccountantConfig {
                 payable_scan_interval: Duration::from_secs(100),
                 payment_received_scan_interval: Duration::from_secs(100),
+                payment_curves: DEFAULT_PAYMENT_CURVES.clone(),
             },
             earning_wallet.clone(),
         );
@@ -2525,6 +3607,7 @@ pub mod tests {
             AccountantConfig {
                 payable_scan_interval: Duration::from_secs(100),
                 payment_received_scan_interval: Duration::from_secs(100),
+                payment_curves: DEFAULT_PAYMENT_CURVES.clone(),
             },
             make_wallet("hi"),
         );
@@ -2579,6 +3662,7 @@ pub mod tests {
             AccountantConfig {
                 payable_scan_interval: Duration::from_secs(100),
                 payment_received_scan_interval: Duration::from_secs(100),
+                payment_curves: DEFAULT_PAYMENT_CURVES.clone(),
             },
             consuming_wallet.clone(),
             make_wallet("my earning wallet"),
@@ -2632,6 +3716,7 @@ pub mod tests {
             AccountantConfig {
                 payable_scan_interval: Duration::from_secs(100),
                 payment_received_scan_interval: Duration::from_secs(100),
+                payment_curves: DEFAULT_PAYMENT_CURVES.clone(),
             },
             earning_wallet.clone(),
         );
@@ -2683,6 +3768,7 @@ pub mod tests {
             AccountantConfig {
                 payable_scan_interval: Duration::from_secs(100),
                 payment_received_scan_interval: Duration::from_secs(100),
+                payment_curves: DEFAULT_PAYMENT_CURVES.clone(),
             },
             make_wallet("hi"),
         );
@@ -2731,6 +3817,7 @@ pub mod tests {
             AccountantConfig {
                 payable_scan_interval: Duration::from_secs(100),
                 payment_received_scan_interval: Duration::from_secs(100),
+                payment_curves: DEFAULT_PAYMENT_CURVES.clone(),
             },
             consuming_wallet.clone(),
             make_wallet("own earning wallet"),
@@ -2775,6 +3862,7 @@ pub mod tests {
             AccountantConfig {
                 payable_scan_interval: Duration::from_secs(100),
                 payment_received_scan_interval: Duration::from_secs(100),
+                payment_curves: DEFAULT_PAYMENT_CURVES.clone(),
             },
             earning_wallet.clone(),
         );
@@ -2868,6 +3956,7 @@ pub mod tests {
                 std::u64::MAX,
                 H256::from_uint(&U256::from(1)),
             ))],
+            requested_by_ui: false,
         };
         let mut subject = make_subject(
             None,
@@ -2933,6 +4022,25 @@ pub mod tests {
         bc
     }
 
+    fn run_ui_request(subject: Accountant, body: MessageBody) -> Arc<Mutex<Recording>> {
+        let system = System::new("test");
+        let (ui_gateway, _, ui_gateway_recording_arc) = make_recorder();
+        let subject_addr = subject.start();
+        let peer_actors = peer_actors_builder().ui_gateway(ui_gateway).build();
+        subject_addr.try_send(BindMessage { peer_actors }).unwrap();
+
+        subject_addr
+            .try_send(NodeFromUiMessage {
+                client_id: 1234,
+                body,
+            })
+            .unwrap();
+
+        System::current().stop();
+        system.run();
+        ui_gateway_recording_arc
+    }
+
     fn make_subject(
         config_opt: Option<BootstrapperConfig>,
         payable_dao_opt: Option<PayableDaoMock>,
@@ -5,7 +5,7 @@ use crate::database::dao_utils;
 use crate::database::dao_utils::DaoFactoryReal;
 use crate::sub_lib::wallet::Wallet;
 use rusqlite::types::{ToSql, Type};
-use rusqlite::{Error, OptionalExtension, NO_PARAMS};
+use rusqlite::{Error, OptionalExtension, Row, NO_PARAMS};
 use serde_json::{self, json};
 use std::fmt::Debug;
 use std::time::SystemTime;
@@ -57,6 +57,8 @@ pub trait PayableDao: Debug + Send {
 
     fn top_records(&self, minimum_amount: u64, maximum_age: u64) -> Vec<PayableAccount>;
 
+    fn records_since(&self, since: SystemTime) -> Vec<PayableAccount>;
+
     fn total(&self) -> u64;
 }
 
@@ -195,40 +197,37 @@ impl PayableDao for PayableDaoReal {
             )
             .expect("Internal error");
         let params: &[&dyn ToSql] = &[&min_amt, &min_timestamp];
-        stmt.query_map(params, |row| {
-            let balance_result = row.get(0);
-            let last_paid_timestamp_result = row.get(1);
-            let wallet_result: Result<Wallet, rusqlite::Error> = row.get(2);
-            let pending_payment_transaction_result: Result<Option<String>, Error> = row.get(3);
-            match (
-                balance_result,
-                last_paid_timestamp_result,
-                wallet_result,
-                pending_payment_transaction_result,
-            ) {
-                (
-                    Ok(balance),
-                    Ok(last_paid_timestamp),
-                    Ok(wallet),
-                    Ok(pending_payment_transaction),
-                ) => Ok(PayableAccount {
-                    wallet,
+        stmt.query_map(params, Self::row_to_account)
+            .expect("Database is corrupt")
+            .flatten()
+            .collect()
+    }
+
+    fn records_since(&self, since: SystemTime) -> Vec<PayableAccount> {
+        let min_timestamp = dao_utils::to_time_t(since);
+        let mut stmt = self
+            .conn
+            .prepare(
+                r#"
+                select
                     balance,
-                    last_paid_timestamp: dao_utils::from_time_t(last_paid_timestamp),
-                    pending_payment_transaction: match pending_payment_transaction {
-                        Some(tx) => match serde_json::from_value(json!(tx)) {
-                            Ok(transaction) => Some(transaction),
-                            Err(e) => panic!("{:?}", e),
-                        },
-                        None => None,
-                    },
-                }),
-                _ => panic!("Database is corrupt: PAYABLE table columns and/or types"),
-            }
-        })
-        .expect("Database is corrupt")
-        .flatten()
-        .collect()
+                    last_paid_timestamp,
+                    wallet_address,
+                    pending_payment_transaction
+                from
+                    payable
+                where
+                    last_paid_timestamp >= ?
+                order by
+                    wallet_address
+            "#,
+            )
+            .expect("Internal error");
+        let params: &[&dyn ToSql] = &[&min_timestamp];
+        stmt.query_map(params, Self::row_to_account)
+            .expect("Database is corrupt")
+            .flatten()
+            .collect()
     }
 
     fn total(&self) -> u64 {
@@ -266,6 +265,35 @@ impl PayableDaoReal {
         PayableDaoReal { conn }
     }
 
+    fn row_to_account(row: &Row) -> rusqlite::Result<PayableAccount> {
+        let balance_result = row.get(0);
+        let last_paid_timestamp_result = row.get(1);
+        let wallet_result: Result<Wallet, rusqlite::Error> = row.get(2);
+        let pending_payment_transaction_result: Result<Option<String>, Error> = row.get(3);
+        match (
+            balance_result,
+            last_paid_timestamp_result,
+            wallet_result,
+            pending_payment_transaction_result,
+        ) {
+            (Ok(balance), Ok(last_paid_timestamp), Ok(wallet), Ok(pending_payment_transaction)) => {
+                Ok(PayableAccount {
+                    wallet,
+                    balance,
+                    last_paid_timestamp: dao_utils::from_time_t(last_paid_timestamp),
+                    pending_payment_transaction: match pending_payment_transaction {
+                        Some(tx) => match serde_json::from_value(json!(tx)) {
+                            Ok(transaction) => Some(transaction),
+                            Err(e) => panic!("{:?}", e),
+                        },
+                        None => None,
+                    },
+                })
+            }
+            _ => panic!("Database is corrupt: PAYABLE table columns and/or types"),
+        }
+    }
+
     fn try_increase_balance(&self, wallet: &Wallet, amount: i64) -> Result<bool, String> {
         let mut stmt = self
             .conn
@@ -724,6 +752,74 @@ mod tests {
         assert_eq!(total, 4_000_000_000)
     }
 
+    #[test]
+    fn records_since_includes_every_recent_account_regardless_of_balance() {
+        let home_dir = ensure_node_home_directory_exists(
+            "payable_dao",
+            "records_since_includes_every_recent_account_regardless_of_balance",
+        );
+        let conn = DbInitializerReal::new()
+            .initialize(&home_dir, DEFAULT_CHAIN_ID, true)
+            .unwrap();
+        let insert = |wallet: &str,
+                      balance: i64,
+                      timestamp: i64,
+                      pending_payment_transaction: Option<&str>| {
+            let params: &[&dyn ToSql] =
+                &[&wallet, &balance, &timestamp, &pending_payment_transaction];
+            conn
+                .prepare("insert into payable (wallet_address, balance, last_paid_timestamp, pending_payment_transaction) values (?, ?, ?, ?)")
+                .unwrap()
+                .execute(params)
+                .unwrap();
+        };
+        let since = dao_utils::now_time_t() - 10_000;
+        insert(
+            "0x3333333333333333333333333333333333333333",
+            0,
+            since,
+            Some("0x1111111122222222333333334444444455555555666666667777777788888888"),
+        );
+        insert(
+            "0x2222222222222222222222222222222222222222",
+            1_000_000_000,
+            since - 1, // too old - reject
+            None,
+        );
+        insert(
+            "0x1111111111111111111111111111111111111111",
+            1,
+            since + 1,
+            None,
+        );
+        let subject = PayableDaoReal::new(conn);
+
+        let result = subject.records_since(dao_utils::from_time_t(since));
+
+        assert_eq!(
+            result,
+            vec![
+                PayableAccount {
+                    wallet: Wallet::new("0x1111111111111111111111111111111111111111"),
+                    balance: 1,
+                    last_paid_timestamp: dao_utils::from_time_t(since + 1),
+                    pending_payment_transaction: None
+                },
+                PayableAccount {
+                    wallet: Wallet::new("0x3333333333333333333333333333333333333333"),
+                    balance: 0,
+                    last_paid_timestamp: dao_utils::from_time_t(since),
+                    pending_payment_transaction: Some(
+                        H256::from_str(
+                            "1111111122222222333333334444444455555555666666667777777788888888"
+                        )
+                        .unwrap()
+                    )
+                },
+            ]
+        );
+    }
+
     #[test]
     fn correctly_totals_zero_records() {
         let home_dir =
@@ -58,6 +58,8 @@ pub trait ReceivableDao: Send {
 
     fn top_records(&self, minimum_amount: u64, maximum_age: u64) -> Vec<ReceivableAccount>;
 
+    fn records_since(&self, since: SystemTime) -> Vec<ReceivableAccount>;
+
     fn total(&self) -> u64;
 }
 
@@ -250,6 +252,31 @@ impl ReceivableDao for ReceivableDaoReal {
         .collect()
     }
 
+    fn records_since(&self, since: SystemTime) -> Vec<ReceivableAccount> {
+        let mut stmt = self
+            .conn
+            .prepare(
+                r#"
+                select
+                    wallet_address,
+                    balance,
+                    last_received_timestamp
+                from
+                    receivable
+                where
+                    last_received_timestamp >= ?
+                order by
+                    wallet_address
+            "#,
+            )
+            .expect("Internal error");
+        let params: &[&dyn ToSql] = &[&to_time_t(since)];
+        stmt.query_map(params, Self::row_to_account)
+            .expect("Database is corrupt")
+            .flatten()
+            .collect()
+    }
+
     fn total(&self) -> u64 {
         let mut stmt = self
             .conn
@@ -1086,6 +1113,41 @@ mod tests {
         assert_eq!(total, 4_000_000_000)
     }
 
+    #[test]
+    fn records_since_includes_every_recent_account_regardless_of_balance() {
+        let home_dir = ensure_node_home_directory_exists(
+            "receivable_dao",
+            "records_since_includes_every_recent_account_regardless_of_balance",
+        );
+        let conn = DbInitializerReal::new()
+            .initialize(&home_dir, DEFAULT_CHAIN_ID, true)
+            .unwrap();
+        let since = dao_utils::now_time_t() - 10_000;
+        let account_1 = ReceivableAccount {
+            wallet: Wallet::new("0x1111111111111111111111111111111111111111"),
+            balance: -500, // overpaid
+            last_received_timestamp: dao_utils::from_time_t(since + 1),
+        };
+        let account_2 = ReceivableAccount {
+            wallet: Wallet::new("0x2222222222222222222222222222222222222222"),
+            balance: 1_000_000_000,
+            last_received_timestamp: dao_utils::from_time_t(since - 1), // too old - reject
+        };
+        let account_3 = ReceivableAccount {
+            wallet: Wallet::new("0x3333333333333333333333333333333333333333"),
+            balance: 0,
+            last_received_timestamp: dao_utils::from_time_t(since),
+        };
+        add_receivable_account(&conn, &account_3);
+        add_receivable_account(&conn, &account_2);
+        add_receivable_account(&conn, &account_1);
+        let subject = ReceivableDaoReal::new(conn);
+
+        let result = subject.records_since(dao_utils::from_time_t(since));
+
+        assert_eq!(result, vec![account_1, account_3]);
+    }
+
     #[test]
     fn correctly_totals_zero_records() {
         let home_dir =
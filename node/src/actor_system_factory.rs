@@ -23,6 +23,10 @@ use crate::database::db_initializer::{
 };
 use crate::db_config::config_dao::ConfigDaoReal;
 use crate::db_config::persistent_configuration::PersistentConfigurationReal;
+use crate::neighborhood::debut_allow_list_dao::{DebutAllowListLoader, DebutAllowListLoaderReal};
+use crate::neighborhood::malefactor_ban_dao::{
+    MalefactorBanCacheLoader, MalefactorBanCacheLoaderReal,
+};
 use crate::node_configurator::configurator::Configurator;
 use crate::sub_lib::accountant::AccountantSubs;
 use crate::sub_lib::blockchain_bridge::BlockchainBridgeSubs;
@@ -131,7 +135,13 @@ impl ActorSystemFactoryReal {
         });
         let blockchain_bridge_subs =
             actor_factory.make_and_start_blockchain_bridge(&config, &db_initializer);
-        let neighborhood_subs = actor_factory.make_and_start_neighborhood(main_cryptde, &config);
+        let neighborhood_subs = actor_factory.make_and_start_neighborhood(
+            main_cryptde,
+            &config,
+            &db_initializer,
+            &MalefactorBanCacheLoaderReal {},
+            &DebutAllowListLoaderReal {},
+        );
         let accountant_subs = actor_factory.make_and_start_accountant(
             &config,
             &config.data_directory.clone(),
@@ -208,6 +218,9 @@ pub trait ActorFactory: Send {
         &self,
         cryptde: &'static dyn CryptDE,
         config: &BootstrapperConfig,
+        db_initializer: &dyn DbInitializer,
+        malefactor_ban_cache_loader: &dyn MalefactorBanCacheLoader,
+        debut_allow_list_loader: &dyn DebutAllowListLoader,
     ) -> NeighborhoodSubs;
     fn make_and_start_accountant(
         &self,
@@ -274,7 +287,22 @@ impl ActorFactory for ActorFactoryReal {
         &self,
         cryptde: &'static dyn CryptDE,
         config: &BootstrapperConfig,
+        db_initializer: &dyn DbInitializer,
+        malefactor_ban_cache_loader: &dyn MalefactorBanCacheLoader,
+        debut_allow_list_loader: &dyn DebutAllowListLoader,
     ) -> NeighborhoodSubs {
+        malefactor_ban_cache_loader.load(connection_or_panic(
+            db_initializer,
+            &config.data_directory,
+            config.blockchain_bridge_config.chain_id,
+            false,
+        ));
+        debut_allow_list_loader.load(connection_or_panic(
+            db_initializer,
+            &config.data_directory,
+            config.blockchain_bridge_config.chain_id,
+            false,
+        ));
         let config_clone = config.clone();
         let addr: Addr<Neighborhood> =
             Arbiter::start(move |_| Neighborhood::new(cryptde, &config_clone));
@@ -411,7 +439,7 @@ impl ActorFactory for ActorFactoryReal {
 #[cfg(test)]
 mod tests {
     use super::*;
-    use crate::accountant::{ReceivedPayments, SentPayments};
+    use crate::accountant::{ReceivedPayments, SentPayments, DEFAULT_PAYMENT_CURVES};
     use crate::blockchain::blockchain_bridge::RetrieveTransactions;
     use crate::bootstrapper::{Bootstrapper, RealUser};
     use crate::database::connection_wrapper::ConnectionWrapper;
@@ -432,18 +460,20 @@ mod tests {
     use crate::sub_lib::hopper::IncipientCoresPackage;
     use crate::sub_lib::hopper::{ExpiredCoresPackage, NoLookupIncipientCoresPackage};
     use crate::sub_lib::neighborhood::RouteQueryMessage;
+    use crate::sub_lib::neighborhood::{
+        BootstrapListMessage, DebutApproval, NeighborhoodMode, RemoveNeighborMessage,
+    };
     use crate::sub_lib::neighborhood::{
         DispatcherNodeQueryMessage, GossipFailure_0v1, NodeRecordMetadataMessage,
     };
     use crate::sub_lib::neighborhood::{NeighborhoodConfig, NodeQueryMessage};
-    use crate::sub_lib::neighborhood::{NeighborhoodMode, RemoveNeighborMessage};
     use crate::sub_lib::node_addr::NodeAddr;
     use crate::sub_lib::peer_actors::StartMessage;
     use crate::sub_lib::proxy_client::{
         ClientResponsePayload_0v1, DnsResolveFailure_0v1, InboundServerData,
     };
     use crate::sub_lib::proxy_server::{
-        AddReturnRouteMessage, AddRouteMessage, ClientRequestPayload_0v1,
+        AddReturnRouteMessage, AddRouteMessage, ClientRequestPayload_0v1, SetExitCountryMessage,
     };
     use crate::sub_lib::set_consuming_wallet_message::SetConsumingWalletMessage;
     use crate::sub_lib::stream_handler_pool::DispatcherNodeQueryResponse;
@@ -542,6 +572,8 @@ mod tests {
                 add_route: recipient!(addr, AddRouteMessage),
                 stream_shutdown_sub: recipient!(addr, StreamShutdownMsg),
                 set_consuming_wallet_sub: recipient!(addr, SetConsumingWalletMessage),
+                set_exit_country_sub: recipient!(addr, SetExitCountryMessage),
+                remove_neighbor_sub: recipient!(addr, RemoveNeighborMessage),
             }
         }
 
@@ -566,6 +598,9 @@ mod tests {
             &self,
             cryptde: &'a dyn CryptDE,
             config: &BootstrapperConfig,
+            _db_initializer: &dyn DbInitializer,
+            _malefactor_ban_cache_loader: &dyn MalefactorBanCacheLoader,
+            _debut_allow_list_loader: &dyn DebutAllowListLoader,
         ) -> NeighborhoodSubs {
             self.parameters
                 .neighborhood_params
@@ -589,6 +624,7 @@ mod tests {
                 set_consuming_wallet_sub: recipient!(addr, SetConsumingWalletMessage),
                 from_ui_message_sub: addr.clone().recipient::<NodeFromUiMessage>(),
                 new_password_sub: addr.clone().recipient::<NewPasswordMessage>(),
+                bootstrap_list_sub: recipient!(addr, BootstrapListMessage),
             }
         }
 
@@ -824,10 +860,14 @@ mod tests {
         let config = BootstrapperConfig {
             log_level: LevelFilter::Off,
             crash_point: CrashPoint::None,
+            debut_approval: DebutApproval::Automatic,
+            exit_country_opt: None,
+            bootstrap_source_opt: None,
             dns_servers: vec![],
             accountant_config: AccountantConfig {
                 payable_scan_interval: Duration::from_secs(100),
                 payment_received_scan_interval: Duration::from_secs(100),
+                payment_curves: DEFAULT_PAYMENT_CURVES.clone(),
             },
             clandestine_discriminator_factories: Vec::new(),
             ui_gateway_config: UiGatewayConfig {
@@ -889,10 +929,14 @@ mod tests {
         let config = BootstrapperConfig {
             log_level: LevelFilter::Off,
             crash_point: CrashPoint::None,
+            debut_approval: DebutApproval::Automatic,
+            exit_country_opt: None,
+            bootstrap_source_opt: None,
             dns_servers: vec![],
             accountant_config: AccountantConfig {
                 payable_scan_interval: Duration::from_secs(100),
                 payment_received_scan_interval: Duration::from_secs(100),
+                payment_curves: DEFAULT_PAYMENT_CURVES.clone(),
             },
             clandestine_discriminator_factories: Vec::new(),
             ui_gateway_config: UiGatewayConfig {
@@ -999,10 +1043,14 @@ mod tests {
         let config = BootstrapperConfig {
             log_level: LevelFilter::Off,
             crash_point: CrashPoint::None,
+            debut_approval: DebutApproval::Automatic,
+            exit_country_opt: None,
+            bootstrap_source_opt: None,
             dns_servers: vec![],
             accountant_config: AccountantConfig {
                 payable_scan_interval: Duration::from_secs(100),
                 payment_received_scan_interval: Duration::from_secs(100),
+                payment_curves: DEFAULT_PAYMENT_CURVES.clone(),
             },
             clandestine_discriminator_factories: Vec::new(),
             ui_gateway_config: UiGatewayConfig {
@@ -62,9 +62,15 @@ impl BannedCacheLoader for BannedCacheLoaderReal {
     }
 }
 
+#[derive(Clone, Debug, PartialEq)]
+pub struct BannedWallet {
+    pub wallet: Wallet,
+    pub reason_opt: Option<String>,
+}
+
 pub trait BannedDao: Send {
-    fn ban_list(&self) -> Vec<Wallet>;
-    fn ban(&self, wallet: &Wallet);
+    fn ban_list(&self) -> Vec<BannedWallet>;
+    fn ban(&self, wallet: &Wallet, reason: &str);
     fn unban(&self, wallet: &Wallet);
 }
 
@@ -89,27 +95,32 @@ impl BannedDaoReal {
 }
 
 impl BannedDao for BannedDaoReal {
-    fn ban_list(&self) -> Vec<Wallet> {
+    fn ban_list(&self) -> Vec<BannedWallet> {
         let mut stmt = self
             .conn
-            .prepare("select wallet_address from banned")
+            .prepare("select wallet_address, reason from banned")
             .expect("Failed to prepare a statement");
-        stmt.query_map(NO_PARAMS, |row| row.get(0))
-            .expect("Couldn't retrieve delinquency-ban list: database corrupt")
-            .flatten()
-            .collect()
+        stmt.query_map(NO_PARAMS, |row| {
+            Ok(BannedWallet {
+                wallet: row.get(0)?,
+                reason_opt: row.get(1)?,
+            })
+        })
+        .expect("Couldn't retrieve delinquency-ban list: database corrupt")
+        .flatten()
+        .collect()
     }
 
-    fn ban(&self, wallet: &Wallet) {
+    fn ban(&self, wallet: &Wallet, reason: &str) {
         if BAN_CACHE.is_banned(wallet) {
             return;
         }
 
         let mut stmt = self
             .conn
-            .prepare("insert into banned (wallet_address) values (?)")
+            .prepare("insert into banned (wallet_address, reason) values (?, ?)")
             .expect("Failed to prepare a statement");
-        let params: &[&dyn ToSql] = &[&wallet];
+        let params: &[&dyn ToSql] = &[&wallet, &reason];
         match stmt.execute(params) {
             Ok(_) => BAN_CACHE.insert(wallet.clone()),
             Err(e) => match e {
@@ -154,6 +165,7 @@ mod tests {
         DEFAULT_CHAIN_ID,
     };
     use rusqlite::NO_PARAMS;
+    use std::str::FromStr;
 
     #[test]
     fn banned_dao_can_ban_a_wallet_address() {
@@ -169,21 +181,21 @@ mod tests {
             BannedDaoReal::new(conn)
         };
 
-        subject.ban(&make_wallet("donalddrumph"));
+        subject.ban(&make_wallet("donalddrumph"), "Stiffed us");
 
         let conn = db_initializer
             .initialize(&home_dir, DEFAULT_CHAIN_ID, true)
             .unwrap();
-        let mut stmt = conn.prepare("select wallet_address from banned").unwrap();
+        let mut stmt = conn
+            .prepare("select wallet_address, reason from banned")
+            .unwrap();
         let mut banned_addresses = stmt.query(NO_PARAMS).unwrap();
+        let row = banned_addresses.next().unwrap().unwrap();
         assert_eq!(
             "0x0000000000000000646f6e616c646472756d7068",
-            banned_addresses
-                .next()
-                .unwrap()
-                .unwrap()
-                .get_unwrap::<usize, String>(0)
+            row.get_unwrap::<usize, String>(0)
         );
+        assert_eq!("Stiffed us", row.get_unwrap::<usize, String>(1));
     }
 
     #[test]
@@ -197,11 +209,17 @@ mod tests {
             BannedDaoReal::new(conn)
         };
 
-        subject.ban(&make_wallet("no_duplicate_wallets"));
-        subject.ban(&make_wallet("no_duplicate_wallets"));
+        subject.ban(&make_wallet("no_duplicate_wallets"), "First");
+        subject.ban(&make_wallet("no_duplicate_wallets"), "Second");
 
         let ban_list = subject.ban_list();
-        assert_eq!(vec![make_wallet("no_duplicate_wallets")], ban_list);
+        assert_eq!(
+            vec![BannedWallet {
+                wallet: make_wallet("no_duplicate_wallets"),
+                reason_opt: Some("First".to_string())
+            }],
+            ban_list
+        );
     }
 
     #[test]
@@ -216,7 +234,34 @@ mod tests {
             BannedDaoReal::new(conn)
         };
 
-        subject.ban(&make_wallet("forgot_to_init"));
+        subject.ban(&make_wallet("forgot_to_init"), "Delinquency");
+    }
+
+    #[test]
+    fn ban_list_includes_wallets_banned_without_a_reason() {
+        let home_dir = ensure_node_home_directory_does_not_exist(
+            "banned_dao",
+            "ban_list_includes_wallets_banned_without_a_reason",
+        );
+        let db_initializer = DbInitializerReal::new();
+        let conn = db_initializer
+            .initialize(&home_dir, DEFAULT_CHAIN_ID, true)
+            .unwrap();
+        conn.prepare("insert into banned (wallet_address) values ('0x0000000000000000000000000000000000000123')")
+            .unwrap()
+            .execute(NO_PARAMS)
+            .unwrap();
+        let subject = BannedDaoReal::new(conn);
+
+        let result = subject.ban_list();
+
+        assert_eq!(
+            result,
+            vec![BannedWallet {
+                wallet: Wallet::from_str("0x0000000000000000000000000000000000000123").unwrap(),
+                reason_opt: None
+            }]
+        );
     }
 
     #[test]
@@ -308,7 +353,7 @@ mod tests {
         let subject = BannedDaoReal::new(conn);
 
         let ban_me_baby = make_wallet("BAN_ME_BABY");
-        subject.ban(&ban_me_baby.clone());
+        subject.ban(&ban_me_baby.clone(), "Delinquency");
 
         assert!(BAN_CACHE.is_banned(&ban_me_baby))
     }
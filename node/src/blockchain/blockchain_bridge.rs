@@ -19,18 +19,31 @@ use actix::Message;
 use actix::{Actor, MessageResult};
 use actix::{Addr, Recipient};
 use masq_lib::crash_point::CrashPoint;
-use masq_lib::messages::{FromMessageBody, UiCrashRequest};
-use masq_lib::ui_gateway::NodeFromUiMessage;
+use masq_lib::messages::{
+    FromMessageBody, ToMessageBody, UiCrashRequest, UiWalletBalances, UiWalletBalancesRequest,
+    UiWalletBalancesResponse,
+};
+use masq_lib::ui_gateway::MessageTarget::ClientId;
+use masq_lib::ui_gateway::{
+    MessageBody, MessagePath, MessageTarget, NodeFromUiMessage, NodeToUiMessage,
+};
 use std::convert::TryFrom;
 
 pub const CRASH_KEY: &str = "BLOCKCHAINBRIDGE";
 
+pub const BLOCKCHAIN_BRIDGE_PREFIX: u64 = 0x0002_0000_0000_0000;
+pub const NO_BLOCKCHAIN_SERVICE_URL_ERROR: u64 = BLOCKCHAIN_BRIDGE_PREFIX | 1;
+pub const BALANCE_QUERY_ERROR: u64 = BLOCKCHAIN_BRIDGE_PREFIX | 2;
+
 pub struct BlockchainBridge {
     consuming_wallet: Option<Wallet>,
+    earning_wallet: Wallet,
+    blockchain_service_url_opt: Option<String>,
     blockchain_interface: Box<dyn BlockchainInterface>,
     logger: Logger,
     persistent_config: Box<dyn PersistentConfiguration>,
     set_consuming_wallet_subs: Option<Vec<Recipient<SetConsumingWalletMessage>>>,
+    ui_message_sub: Option<Recipient<NodeToUiMessage>>,
     crashable: bool,
 }
 
@@ -49,6 +62,7 @@ impl Handler<BindMessage> for BlockchainBridge {
                 .clone(),
             msg.peer_actors.proxy_server.set_consuming_wallet_sub,
         ]);
+        self.ui_message_sub = Some(msg.peer_actors.ui_gateway.node_to_ui_message_sub);
         match self.consuming_wallet.as_ref() {
             Some(wallet) => debug!(
                 self.logger,
@@ -137,12 +151,21 @@ impl Handler<NodeFromUiMessage> for BlockchainBridge {
     type Result = ();
 
     fn handle(&mut self, msg: NodeFromUiMessage, _ctx: &mut Self::Context) -> Self::Result {
-        if let Ok((crash_request, _)) = UiCrashRequest::fmb(msg.body) {
+        if let Ok((crash_request, _)) = UiCrashRequest::fmb(msg.body.clone()) {
             handle_ui_crash_request(crash_request, &self.logger, self.crashable, CRASH_KEY)
+        } else if let Ok((body, context_id)) = UiWalletBalancesRequest::fmb(msg.body) {
+            debug!(
+                &self.logger,
+                "Handling walletBalances message from client {}", msg.client_id
+            );
+            let response = self.handle_wallet_balances(body, context_id);
+            self.send_to_ui_gateway(ClientId(msg.client_id), response);
         }
     }
 }
 
+type MessageError = (u64, String);
+
 impl BlockchainBridge {
     pub fn new(
         config: &BootstrapperConfig,
@@ -151,10 +174,16 @@ impl BlockchainBridge {
     ) -> BlockchainBridge {
         BlockchainBridge {
             consuming_wallet: config.consuming_wallet.clone(),
+            earning_wallet: config.earning_wallet.clone(),
+            blockchain_service_url_opt: config
+                .blockchain_bridge_config
+                .blockchain_service_url
+                .clone(),
             blockchain_interface,
             logger: Logger::new("BlockchainBridge"),
             persistent_config,
             set_consuming_wallet_subs: None,
+            ui_message_sub: None,
             crashable: config.crash_point == CrashPoint::Message,
         }
     }
@@ -167,6 +196,66 @@ impl BlockchainBridge {
             ui_sub: recipient!(addr, NodeFromUiMessage),
         }
     }
+
+    fn handle_wallet_balances(&self, msg: UiWalletBalancesRequest, context_id: u64) -> MessageBody {
+        match self.get_wallet_balances() {
+            Ok(response) => response.tmb(context_id),
+            Err((code, e_msg)) => {
+                warning!(
+                    self.logger,
+                    "Failed to obtain wallet balances: {}, {}",
+                    code,
+                    e_msg
+                );
+                MessageBody {
+                    opcode: msg.opcode().to_string(),
+                    path: MessagePath::Conversation(context_id),
+                    payload: Err((code, e_msg)),
+                }
+            }
+        }
+    }
+
+    fn get_wallet_balances(&self) -> Result<UiWalletBalancesResponse, MessageError> {
+        if self.blockchain_service_url_opt.is_none() {
+            return Err((
+                NO_BLOCKCHAIN_SERVICE_URL_ERROR,
+                "No blockchain service URL is configured; wallet balances cannot be retrieved"
+                    .to_string(),
+            ));
+        }
+        let consuming_wallet_opt = match self.consuming_wallet.as_ref() {
+            Some(wallet) => Some(self.get_balances_of(wallet)?),
+            None => None,
+        };
+        let earning_wallet = self.get_balances_of(&self.earning_wallet)?;
+        Ok(UiWalletBalancesResponse {
+            consuming_wallet_opt,
+            earning_wallet,
+        })
+    }
+
+    fn get_balances_of(&self, wallet: &Wallet) -> Result<UiWalletBalances, MessageError> {
+        match self.blockchain_interface.get_balances(wallet) {
+            (Ok(eth_balance), Ok(masq_balance)) => Ok(UiWalletBalances {
+                address: wallet.to_string(),
+                eth_balance_wei: eth_balance.to_string(),
+                masq_balance_wei: masq_balance.to_string(),
+            }),
+            (Err(e), _) | (_, Err(e)) => Err((
+                BALANCE_QUERY_ERROR,
+                format!("Could not retrieve balances of wallet {}: {}", wallet, e),
+            )),
+        }
+    }
+
+    fn send_to_ui_gateway(&self, target: MessageTarget, body: MessageBody) {
+        self.ui_message_sub
+            .as_ref()
+            .expect("BlockchainBridge is unbound")
+            .try_send(NodeToUiMessage { target, body })
+            .expect("UiGateway is dead");
+    }
 }
 
 #[cfg(test)]
@@ -181,7 +270,7 @@ mod tests {
     use crate::test_utils::logging::init_test_logging;
     use crate::test_utils::logging::TestLogHandler;
     use crate::test_utils::persistent_configuration_mock::PersistentConfigurationMock;
-    use crate::test_utils::recorder::peer_actors_builder;
+    use crate::test_utils::recorder::{make_recorder, peer_actors_builder};
     use crate::test_utils::{
         make_default_persistent_configuration, make_paying_wallet, make_wallet,
     };
@@ -267,6 +356,10 @@ mod tests {
         pub contract_address_results: RefCell<Vec<Address>>,
         pub get_transaction_count_parameters: Arc<Mutex<Vec<Wallet>>>,
         pub get_transaction_count_results: RefCell<Vec<BlockchainResult<U256>>>,
+        pub get_eth_balance_parameters: Arc<Mutex<Vec<Wallet>>>,
+        pub get_eth_balance_results: RefCell<Vec<Balance>>,
+        pub get_token_balance_parameters: Arc<Mutex<Vec<Wallet>>>,
+        pub get_token_balance_results: RefCell<Vec<Balance>>,
     }
 
     impl BlockchainInterfaceMock {
@@ -292,6 +385,16 @@ mod tests {
             self.get_transaction_count_results.borrow_mut().push(result);
             self
         }
+
+        fn get_eth_balance_result(self, result: Balance) -> Self {
+            self.get_eth_balance_results.borrow_mut().push(result);
+            self
+        }
+
+        fn get_token_balance_result(self, result: Balance) -> Self {
+            self.get_token_balance_results.borrow_mut().push(result);
+            self
+        }
     }
 
     impl BlockchainInterface for BlockchainInterfaceMock {
@@ -325,12 +428,20 @@ mod tests {
             self.send_transaction_results.borrow_mut().remove(0)
         }
 
-        fn get_eth_balance(&self, _address: &Wallet) -> Balance {
-            unimplemented!()
+        fn get_eth_balance(&self, address: &Wallet) -> Balance {
+            self.get_eth_balance_parameters
+                .lock()
+                .unwrap()
+                .push(address.clone());
+            self.get_eth_balance_results.borrow_mut().remove(0)
         }
 
-        fn get_token_balance(&self, _address: &Wallet) -> Balance {
-            unimplemented!()
+        fn get_token_balance(&self, address: &Wallet) -> Balance {
+            self.get_token_balance_parameters
+                .lock()
+                .unwrap()
+                .push(address.clone());
+            self.get_token_balance_results.borrow_mut().remove(0)
         }
 
         fn get_transaction_count(&self, wallet: &Wallet) -> Nonce {
@@ -591,6 +702,172 @@ mod tests {
         assert_eq!(result, &Err("No consuming wallet specified".to_string()));
     }
 
+    #[test]
+    fn wallet_balances_request_is_answered_with_balances_of_both_wallets() {
+        let system =
+            System::new("wallet_balances_request_is_answered_with_balances_of_both_wallets");
+        let blockchain_interface_mock = BlockchainInterfaceMock::default()
+            .get_eth_balance_result(Ok(U256::from(1_000_000_000_000_000_000u64)))
+            .get_token_balance_result(Ok(U256::from(2345)))
+            .get_eth_balance_result(Ok(U256::from(3456)))
+            .get_token_balance_result(Ok(U256::from(4567)));
+        let get_eth_balance_parameters_arc =
+            blockchain_interface_mock.get_eth_balance_parameters.clone();
+        let get_token_balance_parameters_arc = blockchain_interface_mock
+            .get_token_balance_parameters
+            .clone();
+        let consuming_wallet = make_paying_wallet(b"consuming");
+        let earning_wallet = make_wallet("earning");
+        let mut config = bc_from_wallet(Some(consuming_wallet.clone()));
+        config.earning_wallet = earning_wallet.clone();
+        config.blockchain_bridge_config.blockchain_service_url =
+            Some("http://127.0.0.1:8545".to_string());
+        let subject = BlockchainBridge::new(
+            &config,
+            Box::new(blockchain_interface_mock),
+            Box::new(PersistentConfigurationMock::default()),
+        );
+        let (ui_gateway, _, ui_gateway_recording_arc) = make_recorder();
+        let addr: Addr<BlockchainBridge> = subject.start();
+        addr.try_send(BindMessage {
+            peer_actors: peer_actors_builder().ui_gateway(ui_gateway).build(),
+        })
+        .unwrap();
+
+        addr.try_send(NodeFromUiMessage {
+            client_id: 1234,
+            body: UiWalletBalancesRequest {}.tmb(4321),
+        })
+        .unwrap();
+
+        System::current().stop();
+        system.run();
+        let get_eth_balance_parameters = get_eth_balance_parameters_arc.lock().unwrap();
+        assert_eq!(
+            *get_eth_balance_parameters,
+            vec![consuming_wallet.clone(), earning_wallet.clone()]
+        );
+        let get_token_balance_parameters = get_token_balance_parameters_arc.lock().unwrap();
+        assert_eq!(
+            *get_token_balance_parameters,
+            vec![consuming_wallet.clone(), earning_wallet.clone()]
+        );
+        let ui_gateway_recording = ui_gateway_recording_arc.lock().unwrap();
+        assert_eq!(
+            ui_gateway_recording.get_record::<NodeToUiMessage>(0),
+            &NodeToUiMessage {
+                target: ClientId(1234),
+                body: UiWalletBalancesResponse {
+                    consuming_wallet_opt: Some(UiWalletBalances {
+                        address: consuming_wallet.to_string(),
+                        eth_balance_wei: "1000000000000000000".to_string(),
+                        masq_balance_wei: "2345".to_string(),
+                    }),
+                    earning_wallet: UiWalletBalances {
+                        address: earning_wallet.to_string(),
+                        eth_balance_wei: "3456".to_string(),
+                        masq_balance_wei: "4567".to_string(),
+                    },
+                }
+                .tmb(4321)
+            }
+        );
+    }
+
+    #[test]
+    fn wallet_balances_request_without_consuming_wallet_reports_earning_wallet_only() {
+        let blockchain_interface_mock = BlockchainInterfaceMock::default()
+            .get_eth_balance_result(Ok(U256::from(3456)))
+            .get_token_balance_result(Ok(U256::from(4567)));
+        let earning_wallet = make_wallet("earning");
+        let mut config = bc_from_wallet(None);
+        config.earning_wallet = earning_wallet.clone();
+        config.blockchain_bridge_config.blockchain_service_url =
+            Some("http://127.0.0.1:8545".to_string());
+        let subject = BlockchainBridge::new(
+            &config,
+            Box::new(blockchain_interface_mock),
+            Box::new(PersistentConfigurationMock::default()),
+        );
+
+        let result = subject.handle_wallet_balances(UiWalletBalancesRequest {}, 4321);
+
+        assert_eq!(
+            result,
+            UiWalletBalancesResponse {
+                consuming_wallet_opt: None,
+                earning_wallet: UiWalletBalances {
+                    address: earning_wallet.to_string(),
+                    eth_balance_wei: "3456".to_string(),
+                    masq_balance_wei: "4567".to_string(),
+                },
+            }
+            .tmb(4321)
+        );
+    }
+
+    #[test]
+    fn wallet_balances_request_complains_if_there_is_no_blockchain_service_url() {
+        init_test_logging();
+        let subject = BlockchainBridge::new(
+            &bc_from_wallet(Some(make_wallet("consuming"))),
+            Box::new(BlockchainInterfaceMock::default()),
+            Box::new(PersistentConfigurationMock::default()),
+        );
+
+        let result = subject.handle_wallet_balances(UiWalletBalancesRequest {}, 4321);
+
+        assert_eq!(
+            result,
+            MessageBody {
+                opcode: "walletBalances".to_string(),
+                path: MessagePath::Conversation(4321),
+                payload: Err((
+                    NO_BLOCKCHAIN_SERVICE_URL_ERROR,
+                    "No blockchain service URL is configured; wallet balances cannot be retrieved"
+                        .to_string()
+                ))
+            }
+        );
+        TestLogHandler::new().exists_log_containing(
+            "WARN: BlockchainBridge: Failed to obtain wallet balances: 562949953421313, No blockchain service URL is configured; wallet balances cannot be retrieved",
+        );
+    }
+
+    #[test]
+    fn wallet_balances_request_reports_blockchain_errors() {
+        let blockchain_interface_mock = BlockchainInterfaceMock::default()
+            .get_eth_balance_result(Ok(U256::from(3456)))
+            .get_token_balance_result(Err(BlockchainError::QueryFailed));
+        let earning_wallet = make_wallet("earning");
+        let mut config = bc_from_wallet(None);
+        config.earning_wallet = earning_wallet.clone();
+        config.blockchain_bridge_config.blockchain_service_url =
+            Some("http://127.0.0.1:8545".to_string());
+        let subject = BlockchainBridge::new(
+            &config,
+            Box::new(blockchain_interface_mock),
+            Box::new(PersistentConfigurationMock::default()),
+        );
+
+        let result = subject.handle_wallet_balances(UiWalletBalancesRequest {}, 4321);
+
+        assert_eq!(
+            result,
+            MessageBody {
+                opcode: "walletBalances".to_string(),
+                path: MessagePath::Conversation(4321),
+                payload: Err((
+                    BALANCE_QUERY_ERROR,
+                    format!(
+                        "Could not retrieve balances of wallet {}: Blockchain QueryFailed.",
+                        earning_wallet
+                    )
+                ))
+            }
+        );
+    }
+
     #[test]
     fn cant_be_crashed_if_key_doesnt_match() {
         let system = System::new("test");
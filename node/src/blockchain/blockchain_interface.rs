@@ -1,5 +1,6 @@
 // Copyright (c) 2017-2019, Substratum LLC (https://substratum.net) and/or its affiliates. All rights reserved.
 
+use crate::blockchain::chain_definitions::{user_defined_chain_by_id, user_defined_chain_by_name};
 use crate::blockchain::raw_transaction::RawTransaction;
 use crate::sub_lib::logger::Logger;
 use crate::sub_lib::wallet::Wallet;
@@ -69,38 +70,58 @@ pub const CHAIN_NAMES: [&str; 5] = ["", "mainnet", "dev", "ropsten", "rinkeby"];
 pub fn contract_address(chain_id: u8) -> Address {
     match chain_id {
         1u8 | 2u8 | 3u8 | 4u8 => CONTRACTS[usize::from(chain_id)], // IDEA/CLion is wrong: This is copy
-        _ => CONTRACTS[0], // IDEA/CLion is wrong: This is copy
+        _ => match user_defined_chain_by_id(chain_id) {
+            Some(definition) => definition.contract_address,
+            None => CONTRACTS[0], // IDEA/CLion is wrong: This is copy
+        },
     }
 }
 
-pub fn chain_name(chain_id: u8) -> &'static str {
-    match chain_id {
-        1u8 | 2u8 | 3u8 | 4u8 => CHAIN_NAMES[usize::from(chain_id)],
-        _ => CHAIN_NAMES[3],
-    }
+pub fn chain_name(chain_id: u8) -> String {
+    chain_name_from_id(chain_id)
 }
 
-pub fn chain_id_from_name(name: &str) -> u8 {
+pub fn chain_id_from_name(name: &str) -> Result<u8, String> {
     match name.to_lowercase().as_str() {
-        "mainnet" => 1u8,
-        "dev" => 2u8,
-        "ropsten" => 3u8,
-        "rinkeby" => 4u8,
-        _ => 3u8,
+        "mainnet" => Ok(1u8),
+        "dev" => Ok(2u8),
+        "ropsten" => Ok(3u8),
+        "rinkeby" => Ok(4u8),
+        other => match user_defined_chain_by_name(other) {
+            Some(definition) => Ok(definition.chain_id),
+            None => Err(format!("Unknown chain '{}'", name)),
+        },
     }
 }
 
-pub fn chain_name_from_id(chain_id: u8) -> &'static str {
+pub fn chain_name_from_id(chain_id: u8) -> String {
     match chain_id {
-        1u8 | 2u8 | 3u8 | 4u8 => CHAIN_NAMES[usize::from(chain_id)],
-        _ => CHAIN_NAMES[3],
+        1u8 | 2u8 | 3u8 | 4u8 => CHAIN_NAMES[usize::from(chain_id)].to_string(),
+        _ => match user_defined_chain_by_id(chain_id) {
+            Some(definition) => definition.name,
+            None => CHAIN_NAMES[3].to_string(),
+        },
     }
 }
 
 pub fn contract_creation_block_from_chain_id(chain_id: u8) -> u64 {
     match chain_id {
         1u8 | 2u8 | 3u8 | 4u8 => CONTRACT_CREATION_BLOCK[usize::from(chain_id)],
-        _ => CONTRACT_CREATION_BLOCK[3],
+        _ => match user_defined_chain_by_id(chain_id) {
+            Some(definition) => definition.contract_creation_block,
+            None => CONTRACT_CREATION_BLOCK[3],
+        },
+    }
+}
+
+pub fn is_mainnet_chain(chain_id: u8) -> bool {
+    match chain_id {
+        1u8 => true,
+        2u8 | 3u8 | 4u8 => false,
+        _ => match user_defined_chain_by_id(chain_id) {
+            Some(definition) => definition.mainnet,
+            None => false,
+        },
     }
 }
 
@@ -198,7 +219,7 @@ impl BlockchainInterfaceClandestine {
 
 impl Default for BlockchainInterfaceClandestine {
     fn default() -> Self {
-        Self::new(chain_id_from_name(DEFAULT_CHAIN_NAME))
+        Self::new(chain_id_from_name(DEFAULT_CHAIN_NAME).expect("Default chain is not built in"))
     }
 }
 
@@ -974,4 +995,19 @@ mod tests {
             "transfer(address,uint256)".keccak256()[0..4]
         );
     }
+
+    #[test]
+    fn chain_id_from_name_finds_built_in_chains() {
+        assert_eq!(chain_id_from_name("mainnet"), Ok(1));
+        assert_eq!(chain_id_from_name("DEV"), Ok(2));
+        assert_eq!(chain_id_from_name("ropsten"), Ok(3));
+        assert_eq!(chain_id_from_name("Rinkeby"), Ok(4));
+    }
+
+    #[test]
+    fn chain_id_from_name_rejects_unknown_chain() {
+        let result = chain_id_from_name("booga");
+
+        assert_eq!(result, Err("Unknown chain 'booga'".to_string()));
+    }
 }
@@ -0,0 +1,392 @@
+// Copyright (c) 2019-2020, MASQ (https://masq.ai) and/or its affiliates. All rights reserved.
+
+use crate::blockchain::blockchain_interface::CHAIN_NAMES;
+use lazy_static::lazy_static;
+use rustc_hex::FromHex;
+use serde_derive::Deserialize;
+use std::collections::HashSet;
+use std::convert::TryFrom;
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::sync::RwLock;
+use web3::types::Address;
+
+pub const CHAIN_DEFINITIONS_FILE: &str = "chains.toml";
+
+// Chain IDs 0 through 4 are reserved for the built-in chains.
+const HIGHEST_RESERVED_CHAIN_ID: u8 = 4;
+
+lazy_static! {
+    static ref USER_DEFINED_CHAINS: RwLock<Vec<ChainDefinition>> = RwLock::new(vec![]);
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub struct ChainDefinition {
+    pub name: String,
+    pub chain_id: u8,
+    pub contract_address: Address,
+    pub contract_creation_block: u64,
+    pub mainnet: bool,
+}
+
+#[derive(Deserialize)]
+#[serde(rename_all = "kebab-case", deny_unknown_fields)]
+struct ChainDefinitionsFile {
+    #[serde(default)]
+    chain: Vec<ChainDefinitionEntry>,
+}
+
+#[derive(Deserialize)]
+#[serde(rename_all = "kebab-case", deny_unknown_fields)]
+struct ChainDefinitionEntry {
+    name: String,
+    chain_id: u64,
+    contract_address: String,
+    contract_creation_block: u64,
+    #[serde(default)]
+    mainnet: bool,
+}
+
+pub fn chain_definitions_path(data_directory: &Path) -> PathBuf {
+    data_directory.join(CHAIN_DEFINITIONS_FILE)
+}
+
+pub fn load_chain_definitions(data_directory: &Path) -> Result<Vec<ChainDefinition>, String> {
+    let path = chain_definitions_path(data_directory);
+    if !path.exists() {
+        return Ok(vec![]);
+    }
+    let contents = match fs::read_to_string(&path) {
+        Ok(contents) => contents,
+        Err(e) => return Err(format!("Couldn't read {:?}: {}", path, e)),
+    };
+    parse_chain_definitions(&contents).map_err(|e| format!("Error in {:?}: {}", path, e))
+}
+
+pub fn parse_chain_definitions(contents: &str) -> Result<Vec<ChainDefinition>, String> {
+    let file: ChainDefinitionsFile = match toml::de::from_str(contents) {
+        Ok(file) => file,
+        Err(e) => return Err(format!("{}", e)),
+    };
+    let mut names_so_far: HashSet<String> = HashSet::new();
+    let mut chain_ids_so_far: HashSet<u8> = HashSet::new();
+    file.chain
+        .into_iter()
+        .map(|entry| {
+            let name = entry.name.to_lowercase();
+            if name.is_empty() {
+                return Err("Chain name must not be empty".to_string());
+            }
+            if CHAIN_NAMES.contains(&name.as_str()) {
+                return Err(format!("Chain name '{}' is reserved", name));
+            }
+            if !names_so_far.insert(name.clone()) {
+                return Err(format!("Chain name '{}' is defined more than once", name));
+            }
+            let chain_id = u8::try_from(entry.chain_id).map_err(|_| {
+                format!(
+                    "Chain '{}': chain ID {} is too large; chain IDs above {} are not supported",
+                    name,
+                    entry.chain_id,
+                    u8::MAX
+                )
+            })?;
+            if chain_id <= HIGHEST_RESERVED_CHAIN_ID {
+                return Err(format!(
+                    "Chain '{}': chain ID {} is reserved; use a value above {}",
+                    name, chain_id, HIGHEST_RESERVED_CHAIN_ID
+                ));
+            }
+            if !chain_ids_so_far.insert(chain_id) {
+                return Err(format!(
+                    "Chain ID {} is used by more than one chain",
+                    chain_id
+                ));
+            }
+            let contract_address = match parse_contract_address(&entry.contract_address) {
+                Some(address) => address,
+                None => {
+                    return Err(format!(
+                        "Chain '{}': contract address '{}' is not 0x followed by 40 hex digits",
+                        name, entry.contract_address
+                    ))
+                }
+            };
+            Ok(ChainDefinition {
+                name,
+                chain_id,
+                contract_address,
+                contract_creation_block: entry.contract_creation_block,
+                mainnet: entry.mainnet,
+            })
+        })
+        .collect()
+}
+
+// The registry is process-wide, so a later registration replaces any earlier definition with the
+// same name or chain ID rather than discarding everything registered so far.
+pub fn register_chain_definitions(definitions: Vec<ChainDefinition>) {
+    let mut registry = USER_DEFINED_CHAINS
+        .write()
+        .expect("Chain definitions are poisoned");
+    definitions.into_iter().for_each(|definition| {
+        registry.retain(|existing| {
+            existing.name != definition.name && existing.chain_id != definition.chain_id
+        });
+        registry.push(definition);
+    });
+}
+
+pub fn user_defined_chain_by_id(chain_id: u8) -> Option<ChainDefinition> {
+    USER_DEFINED_CHAINS
+        .read()
+        .expect("Chain definitions are poisoned")
+        .iter()
+        .find(|definition| definition.chain_id == chain_id)
+        .cloned()
+}
+
+pub fn user_defined_chain_by_name(name: &str) -> Option<ChainDefinition> {
+    let name = name.to_lowercase();
+    USER_DEFINED_CHAINS
+        .read()
+        .expect("Chain definitions are poisoned")
+        .iter()
+        .find(|definition| definition.name == name)
+        .cloned()
+}
+
+pub fn user_defined_chain_names() -> Vec<String> {
+    USER_DEFINED_CHAINS
+        .read()
+        .expect("Chain definitions are poisoned")
+        .iter()
+        .map(|definition| definition.name.clone())
+        .collect()
+}
+
+fn parse_contract_address(address_str: &str) -> Option<Address> {
+    if !address_str.starts_with("0x") || address_str.len() != 42 {
+        return None;
+    }
+    match address_str[2..].from_hex::<Vec<u8>>() {
+        Ok(bytes) => Some(Address::from_slice(&bytes)),
+        Err(_) => None,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use masq_lib::test_utils::utils::ensure_node_home_directory_exists;
+    use std::fs::File;
+    use std::io::Write;
+
+    const GOOD_DEFINITIONS: &str = r#"
+        [[chain]]
+        name = "DevNet-A"
+        chain-id = 42
+        contract-address = "0x0102030405060708090a0b0c0d0e0f1011121314"
+        contract-creation-block = 1234
+
+        [[chain]]
+        name = "privmain"
+        chain-id = 43
+        contract-address = "0x1112131415161718191a1b1c1d1e1f2021222324"
+        contract-creation-block = 0
+        mainnet = true
+    "#;
+
+    #[test]
+    fn parse_chain_definitions_works() {
+        let result = parse_chain_definitions(GOOD_DEFINITIONS).unwrap();
+
+        assert_eq!(
+            result,
+            vec![
+                ChainDefinition {
+                    name: "devnet-a".to_string(),
+                    chain_id: 42,
+                    contract_address: Address::from_slice(&[
+                        1, 2, 3, 4, 5, 6, 7, 8, 9, 10, 11, 12, 13, 14, 15, 16, 17, 18, 19, 20
+                    ]),
+                    contract_creation_block: 1234,
+                    mainnet: false,
+                },
+                ChainDefinition {
+                    name: "privmain".to_string(),
+                    chain_id: 43,
+                    contract_address: Address::from_slice(&[
+                        17, 18, 19, 20, 21, 22, 23, 24, 25, 26, 27, 28, 29, 30, 31, 32, 33, 34, 35,
+                        36
+                    ]),
+                    contract_creation_block: 0,
+                    mainnet: true,
+                },
+            ]
+        );
+    }
+
+    #[test]
+    fn parse_chain_definitions_accepts_empty_file() {
+        let result = parse_chain_definitions("").unwrap();
+
+        assert_eq!(result, vec![]);
+    }
+
+    fn assert_rejected(contents: &str, expected_message: &str) {
+        let result = parse_chain_definitions(contents).err().unwrap();
+
+        assert_eq!(
+            result.contains(expected_message),
+            true,
+            "'{}' should contain '{}'",
+            result,
+            expected_message
+        );
+    }
+
+    fn definition(name: &str, chain_id: u64, contract_address: &str) -> String {
+        format!(
+            "[[chain]]\nname = \"{}\"\nchain-id = {}\ncontract-address = \"{}\"\ncontract-creation-block = 0\n",
+            name, chain_id, contract_address
+        )
+    }
+
+    #[test]
+    fn parse_chain_definitions_rejects_bad_definitions() {
+        let good_address = "0x0102030405060708090a0b0c0d0e0f1011121314";
+        assert_rejected("booga", "expected");
+        assert_rejected(
+            &definition("", 42, good_address),
+            "Chain name must not be empty",
+        );
+        assert_rejected(
+            &definition("Ropsten", 42, good_address),
+            "Chain name 'ropsten' is reserved",
+        );
+        assert_rejected(
+            &definition("devnet", 4, good_address),
+            "Chain 'devnet': chain ID 4 is reserved; use a value above 4",
+        );
+        assert_rejected(
+            &definition("devnet", 1337, good_address),
+            "Chain 'devnet': chain ID 1337 is too large; chain IDs above 255 are not supported",
+        );
+        assert_rejected(
+            &format!(
+                "{}{}",
+                definition("devnet", 42, good_address),
+                definition("DEVNET", 43, good_address)
+            ),
+            "Chain name 'devnet' is defined more than once",
+        );
+        assert_rejected(
+            &format!(
+                "{}{}",
+                definition("devnet", 42, good_address),
+                definition("othernet", 42, good_address)
+            ),
+            "Chain ID 42 is used by more than one chain",
+        );
+        assert_rejected(
+            &definition("devnet", 42, "0102030405060708090a0b0c0d0e0f1011121314"),
+            "Chain 'devnet': contract address '0102030405060708090a0b0c0d0e0f1011121314' is not 0x followed by 40 hex digits",
+        );
+        assert_rejected(
+            &definition("devnet", 42, "0xbooga"),
+            "Chain 'devnet': contract address '0xbooga' is not 0x followed by 40 hex digits",
+        );
+    }
+
+    #[test]
+    fn load_chain_definitions_returns_nothing_if_there_is_no_file() {
+        let data_directory = ensure_node_home_directory_exists(
+            "chain_definitions",
+            "load_chain_definitions_returns_nothing_if_there_is_no_file",
+        );
+
+        let result = load_chain_definitions(&data_directory);
+
+        assert_eq!(result, Ok(vec![]));
+    }
+
+    #[test]
+    fn load_chain_definitions_reads_file_from_data_directory() {
+        let data_directory = ensure_node_home_directory_exists(
+            "chain_definitions",
+            "load_chain_definitions_reads_file_from_data_directory",
+        );
+        let mut file = File::create(chain_definitions_path(&data_directory)).unwrap();
+        file.write_all(GOOD_DEFINITIONS.as_bytes()).unwrap();
+
+        let result = load_chain_definitions(&data_directory).unwrap();
+
+        assert_eq!(
+            result
+                .iter()
+                .map(|d| d.name.as_str())
+                .collect::<Vec<&str>>(),
+            vec!["devnet-a", "privmain"]
+        );
+    }
+
+    #[test]
+    fn load_chain_definitions_identifies_bad_file() {
+        let data_directory = ensure_node_home_directory_exists(
+            "chain_definitions",
+            "load_chain_definitions_identifies_bad_file",
+        );
+        let path = chain_definitions_path(&data_directory);
+        let mut file = File::create(&path).unwrap();
+        file.write_all(definition("dev", 42, "0x00").as_bytes())
+            .unwrap();
+
+        let result = load_chain_definitions(&data_directory);
+
+        assert_eq!(
+            result,
+            Err(format!("Error in {:?}: Chain name 'dev' is reserved", path))
+        );
+    }
+
+    #[test]
+    fn registered_chain_definitions_can_be_found() {
+        register_chain_definitions(parse_chain_definitions(GOOD_DEFINITIONS).unwrap());
+
+        assert_eq!(user_defined_chain_by_id(43).unwrap().name, "privmain");
+        assert_eq!(user_defined_chain_by_name("DEVNET-A").unwrap().chain_id, 42);
+        assert_eq!(user_defined_chain_by_id(44), None);
+        assert_eq!(user_defined_chain_by_name("booga"), None);
+        let names = user_defined_chain_names();
+        assert!(names.contains(&"devnet-a".to_string()), "{:?}", names);
+        assert!(names.contains(&"privmain".to_string()), "{:?}", names);
+    }
+
+    #[test]
+    fn registering_a_chain_again_replaces_its_earlier_definition() {
+        register_chain_definitions(
+            parse_chain_definitions(&definition(
+                "redefined",
+                47,
+                "0x1111111111111111111111111111111111111111",
+            ))
+            .unwrap(),
+        );
+
+        register_chain_definitions(
+            parse_chain_definitions(&definition(
+                "redefined",
+                48,
+                "0x2222222222222222222222222222222222222222",
+            ))
+            .unwrap(),
+        );
+
+        assert_eq!(user_defined_chain_by_id(47), None);
+        assert_eq!(
+            user_defined_chain_by_name("redefined").unwrap().chain_id,
+            48
+        );
+    }
+}
@@ -1,5 +1,7 @@
 // Copyright (c) 2017-2019, Substratum LLC (https://substratum.net) and/or its affiliates. All rights reserved.
-use crate::accountant::{DEFAULT_PAYABLE_SCAN_INTERVAL, DEFAULT_PAYMENT_RECEIVED_SCAN_INTERVAL};
+use crate::accountant::{
+    DEFAULT_PAYABLE_SCAN_INTERVAL, DEFAULT_PAYMENT_CURVES, DEFAULT_PAYMENT_RECEIVED_SCAN_INTERVAL,
+};
 use crate::actor_system_factory::ActorFactoryReal;
 use crate::actor_system_factory::ActorSystemFactory;
 use crate::actor_system_factory::ActorSystemFactoryReal;
@@ -11,6 +13,7 @@ use crate::db_config::persistent_configuration::{
     PersistentConfiguration, PersistentConfigurationReal,
 };
 use crate::discriminator::DiscriminatorFactory;
+use crate::entry_dns::bypass::{OtherRecordsPolicy, DNS_BYPASS};
 use crate::json_discriminator_factory::JsonDiscriminatorFactory;
 use crate::listener_handler::ListenerHandler;
 use crate::listener_handler::ListenerHandlerFactory;
@@ -29,7 +32,9 @@ use crate::sub_lib::cryptde_null::CryptDENull;
 use crate::sub_lib::cryptde_real::CryptDEReal;
 use crate::sub_lib::logger::Logger;
 use crate::sub_lib::neighborhood::NodeDescriptor;
-use crate::sub_lib::neighborhood::{NeighborhoodConfig, NeighborhoodMode};
+use crate::sub_lib::neighborhood::{
+    BootstrapSource, DebutApproval, NeighborhoodConfig, NeighborhoodMode,
+};
 use crate::sub_lib::node_addr::NodeAddr;
 use crate::sub_lib::socket_server::SocketServer;
 use crate::sub_lib::ui_gateway::UiGatewayConfig;
@@ -40,6 +45,7 @@ use log::LevelFilter;
 use masq_lib::command::StdStreams;
 use masq_lib::constants::{DEFAULT_CHAIN_NAME, DEFAULT_UI_PORT};
 use masq_lib::crash_point::CrashPoint;
+use masq_lib::exit_country::ExitCountryPreference;
 use masq_lib::shared_schema::ConfiguratorError;
 use std::collections::HashMap;
 use std::env::var;
@@ -288,8 +294,14 @@ pub struct BootstrapperConfig {
     // These fields can be set while privileged without penalty
     pub log_level: LevelFilter,
     pub dns_servers: Vec<SocketAddr>,
+    pub dns_bypass_domains: Vec<String>,
+    pub dns_bypass_servers: Vec<SocketAddr>,
+    pub dns_other_records: OtherRecordsPolicy,
     pub accountant_config: AccountantConfig,
     pub crash_point: CrashPoint,
+    pub debut_approval: DebutApproval,
+    pub exit_country_opt: Option<ExitCountryPreference>,
+    pub bootstrap_source_opt: Option<BootstrapSource>,
     pub clandestine_discriminator_factories: Vec<Box<dyn DiscriminatorFactory>>,
     pub ui_gateway_config: UiGatewayConfig,
     pub blockchain_bridge_config: BlockchainBridgeConfig,
@@ -319,13 +331,20 @@ impl BootstrapperConfig {
             // These fields can be set while privileged without penalty
             log_level: LevelFilter::Off,
             dns_servers: vec![],
+            dns_bypass_domains: vec![],
+            dns_bypass_servers: vec![],
+            dns_other_records: OtherRecordsPolicy::Empty,
             accountant_config: AccountantConfig {
                 payable_scan_interval: Duration::from_secs(DEFAULT_PAYABLE_SCAN_INTERVAL),
                 payment_received_scan_interval: Duration::from_secs(
                     DEFAULT_PAYMENT_RECEIVED_SCAN_INTERVAL,
                 ),
+                payment_curves: DEFAULT_PAYMENT_CURVES.clone(),
             },
             crash_point: CrashPoint::None,
+            debut_approval: DebutApproval::Automatic,
+            exit_country_opt: None,
+            bootstrap_source_opt: None,
             clandestine_discriminator_factories: vec![],
             ui_gateway_config: UiGatewayConfig {
                 ui_port: DEFAULT_UI_PORT,
@@ -398,6 +417,13 @@ impl SocketServer<BootstrapperConfig> for Bootstrapper {
                 Ok(config) => config,
                 Err(e) => return Err(e),
             };
+        DNS_BYPASS
+            .configure(
+                &self.config.dns_bypass_domains,
+                &self.config.dns_bypass_servers,
+            )
+            .expect("Bad clap validation for dns-bypass");
+        DNS_BYPASS.set_other_records(self.config.dns_other_records);
 
         self.logger_initializer.init(
             self.config.data_directory.clone(),
@@ -512,7 +538,9 @@ impl Bootstrapper {
                 let node_descriptor = NodeDescriptor::from((
                     cryptde.public_key(),
                     &node_addr,
-                    chain_id == chain_id_from_name(DEFAULT_CHAIN_NAME),
+                    chain_id
+                        == chain_id_from_name(DEFAULT_CHAIN_NAME)
+                            .expect("Default chain is not built in"),
                     cryptde,
                 ));
                 node_descriptor.to_string(cryptde)
@@ -1591,7 +1619,7 @@ mod tests {
                 vec![NodeDescriptor::from((
                     cryptde.public_key(),
                     &NodeAddr::new(&IpAddr::from_str("1.2.3.4").unwrap(), &[1234]),
-                    DEFAULT_CHAIN_ID == chain_id_from_name(DEFAULT_CHAIN_NAME),
+                    DEFAULT_CHAIN_ID == chain_id_from_name(DEFAULT_CHAIN_NAME).unwrap(),
                     cryptde,
                 ))],
                 rate_pack(100),
@@ -1663,7 +1691,7 @@ mod tests {
                 vec![NodeDescriptor::from((
                     cryptde.public_key(),
                     &NodeAddr::new(&IpAddr::from_str("1.2.3.4").unwrap(), &[1234]),
-                    DEFAULT_CHAIN_ID == chain_id_from_name(DEFAULT_CHAIN_NAME),
+                    DEFAULT_CHAIN_ID == chain_id_from_name(DEFAULT_CHAIN_NAME).unwrap(),
                     cryptde,
                 ))],
                 rate_pack(100),
@@ -1714,7 +1742,7 @@ mod tests {
                 vec![NodeDescriptor::from((
                     cryptde.public_key(),
                     &NodeAddr::new(&IpAddr::from_str("1.2.3.4").unwrap(), &[1234]),
-                    DEFAULT_CHAIN_ID == chain_id_from_name(DEFAULT_CHAIN_NAME),
+                    DEFAULT_CHAIN_ID == chain_id_from_name(DEFAULT_CHAIN_NAME).unwrap(),
                     cryptde,
                 ))],
                 rate_pack(100),
@@ -1751,7 +1779,7 @@ mod tests {
             mode: NeighborhoodMode::ConsumeOnly(vec![NodeDescriptor::from((
                 cryptde.public_key(),
                 &NodeAddr::new(&IpAddr::from_str("1.2.3.4").unwrap(), &[1234]),
-                DEFAULT_CHAIN_ID == chain_id_from_name(DEFAULT_CHAIN_NAME),
+                DEFAULT_CHAIN_ID == chain_id_from_name(DEFAULT_CHAIN_NAME).unwrap(),
                 cryptde,
             ))]),
         };
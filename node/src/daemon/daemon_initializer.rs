@@ -10,6 +10,8 @@ use crate::node_configurator::{port_is_busy, DirsWrapper};
 use crate::server_initializer::LoggerInitializerWrapper;
 use crate::sub_lib::main_tools::main_with_args;
 use crate::sub_lib::ui_gateway::UiGatewayConfig;
+use crate::systemd;
+use crate::systemd::UI_SOCKET_NAME;
 use crate::ui_gateway::UiGateway;
 use actix::{Actor, System, SystemRunner};
 use crossbeam_channel::{unbounded, Receiver, Sender};
@@ -70,7 +72,8 @@ pub struct DaemonInitializer {
 
 impl Command for DaemonInitializer {
     fn go(&mut self, streams: &mut StdStreams<'_>, _args: &[String]) -> u8 {
-        if port_is_busy(self.config.ui_port) {
+        // A socket-activated UI port is busy because systemd is holding it for us.
+        if !systemd::is_socket_activated(UI_SOCKET_NAME) && port_is_busy(self.config.ui_port) {
             writeln! (streams.stderr, "There appears to be a process already listening on port {}; are you sure there's not a Daemon already running?", self.config.ui_port).unwrap();
             return 1;
         }
@@ -78,6 +81,8 @@ impl Command for DaemonInitializer {
         let (sender, receiver) = self.channel_factory.make();
 
         self.bind(sender);
+        systemd::notify_ready();
+        systemd::start_watchdog();
 
         self.split(system, receiver);
         0
@@ -6,6 +6,7 @@ use crate::daemon::launch_verifier::LaunchVerification::{
 };
 use crate::daemon::launch_verifier::{LaunchVerifier, LaunchVerifierReal};
 use crate::daemon::{LaunchSuccess, Launcher};
+use crate::systemd::NOTIFICATION_VARIABLES;
 use actix::Recipient;
 use crossbeam_channel::Sender;
 use itertools::Itertools;
@@ -64,7 +65,13 @@ impl SpawnWrapper for SpawnWrapperReal {
         exe_path: PathBuf,
         params: Vec<String>,
     ) -> std::io::Result<Box<dyn ChildWrapper>> {
-        match Command::new(exe_path).args(params).spawn() {
+        let mut command = Command::new(exe_path);
+        command.args(params);
+        // The Daemon is the service systemd is watching; the Node it launches is not.
+        NOTIFICATION_VARIABLES.iter().for_each(|variable| {
+            command.env_remove(variable);
+        });
+        match command.spawn() {
             Ok(child) => Ok(Box::new(ChildWrapperReal::new(child))),
             Err(e) => Err(e),
         }
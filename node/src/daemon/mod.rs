@@ -4,6 +4,7 @@ pub mod crash_notification;
 pub mod daemon_initializer;
 pub mod launch_verifier;
 mod launcher;
+mod setup_profiles;
 mod setup_reporter;
 
 #[cfg(test)]
@@ -11,6 +12,7 @@ mod mocks;
 
 use crate::daemon::crash_notification::CrashNotification;
 use crate::daemon::launch_verifier::{VerifierTools, VerifierToolsReal};
+use crate::daemon::setup_profiles::{SetupProfileStore, SetupProfileStoreReal};
 use crate::daemon::setup_reporter::{SetupCluster, SetupReporter, SetupReporterReal};
 use crate::sub_lib::logger::Logger;
 use crate::sub_lib::utils::NODE_MAILBOX_CAPACITY;
@@ -21,15 +23,29 @@ use itertools::Itertools;
 use lazy_static::lazy_static;
 use masq_lib::messages::UiSetupResponseValueStatus::{Configured, Set};
 use masq_lib::messages::{
-    FromMessageBody, ToMessageBody, UiNodeCrashedBroadcast, UiRedirect, UiSetupBroadcast,
-    UiSetupRequest, UiSetupResponse, UiSetupResponseValue, UiStartOrder, UiStartResponse,
-    NODE_ALREADY_RUNNING_ERROR, NODE_LAUNCH_ERROR, NODE_NOT_RUNNING_ERROR,
+    CrashReason, FromMessageBody, RestartOutcome, ToMessageBody, UiCrashHistoryRequest,
+    UiCrashHistoryResponse, UiCrashRecord, UiDeleteSetupProfileRequest,
+    UiDeleteSetupProfileResponse, UiInstancesRequest, UiInstancesResponse,
+    UiLoadSetupProfileRequest, UiLoadSetupProfileResponse, UiNodeCrashedBroadcast, UiNodeInstance,
+    UiNodeRestartAbandonedBroadcast, UiNodeRestartedBroadcast, UiNodeRestartingBroadcast,
+    UiRedirect, UiRestartPolicy, UiRestartPolicyRequest, UiRestartPolicyResponse,
+    UiSaveSetupProfileRequest, UiSaveSetupProfileResponse, UiSelectInstanceRequest,
+    UiSelectInstanceResponse, UiSetupBroadcast, UiSetupProfilesRequest, UiSetupProfilesResponse,
+    UiSetupRequest, UiSetupRequestValue, UiSetupResponse, UiSetupResponseValue, UiStartOrder,
+    UiStartResponse, UiStatusRequest, UiStatusResponse, NODE_ALREADY_RUNNING_ERROR,
+    NODE_INSTANCE_ERROR, NODE_LAUNCH_ERROR, NODE_NOT_RUNNING_ERROR, RESTART_POLICY_ERROR,
+    SETUP_PROFILE_ERROR,
 };
 use masq_lib::shared_schema::ConfiguratorError;
 use masq_lib::ui_gateway::MessagePath::{Conversation, FireAndForget};
 use masq_lib::ui_gateway::MessageTarget::ClientId;
 use masq_lib::ui_gateway::{MessageBody, MessageTarget, NodeFromUiMessage, NodeToUiMessage};
-use std::collections::{HashMap, HashSet};
+use std::collections::{BTreeMap, HashMap, HashSet, VecDeque};
+use std::time::{Duration, Instant, SystemTime, UNIX_EPOCH};
+
+pub const MAX_CRASH_HISTORY_LEN: usize = 100;
+pub const DEFAULT_INSTANCE_NAME: &str = "default";
+pub const MAX_INSTANCE_NAME_LEN: usize = 64;
 
 pub struct Recipients {
     ui_gateway_from_sub: Recipient<NodeFromUiMessage>,
@@ -50,6 +66,16 @@ lazy_static! {
     };
 }
 
+// Restarting is opt-in: with max_restarts at zero, a crashed Node stays down until the next start order.
+pub fn default_restart_policy() -> UiRestartPolicy {
+    UiRestartPolicy {
+        max_restarts: 0,
+        initial_backoff_ms: 1_000,
+        max_backoff_ms: 60_000,
+        crash_loop_window_secs: 300,
+    }
+}
+
 #[allow(clippy::type_complexity)]
 pub trait ChannelFactory {
     fn make(
@@ -91,15 +117,28 @@ pub struct DaemonBindMessage {
     pub crash_notification_recipient: Recipient<CrashNotification>, // the Daemon itself, for crash notifications
 }
 
+// Everything the Daemon knows about one of the Nodes it manages. The Daemon always has an
+// instance named DEFAULT_INSTANCE_NAME; UIs that never select an instance talk to that one.
+#[derive(Default)]
+struct NodeInstance {
+    params: SetupCluster,
+    node_process_id: Option<u32>,
+    node_ui_port: Option<u16>,
+    last_launch_params: Option<HashMap<String, String>>,
+    recent_restarts: VecDeque<Instant>,
+}
+
 pub struct Daemon {
     launcher: Box<dyn Launcher>,
-    params: SetupCluster,
+    instances: HashMap<String, NodeInstance>,
+    client_instances: HashMap<u64, String>,
     ui_gateway_sub: Option<Recipient<NodeToUiMessage>>,
     crash_notification_sub: Option<Recipient<CrashNotification>>,
-    node_process_id: Option<u32>,
-    node_ui_port: Option<u16>,
     verifier_tools: Box<dyn VerifierTools>,
     setup_reporter: Box<dyn SetupReporter>,
+    profile_store: Box<dyn SetupProfileStore>,
+    restart_policy: UiRestartPolicy,
+    crash_history: VecDeque<UiCrashRecord>,
     logger: Logger,
 }
 
@@ -132,6 +171,25 @@ impl Handler<NodeFromUiMessage> for Daemon {
             self.handle_setup(client_id, context_id, setup_request);
         } else if let Ok((_, context_id)) = UiStartOrder::fmb(msg.body.clone()) {
             self.handle_start_order(client_id, context_id);
+        } else if let Ok((request, context_id)) = UiSelectInstanceRequest::fmb(msg.body.clone()) {
+            self.handle_select_instance(client_id, context_id, request);
+        } else if let Ok((_, context_id)) = UiInstancesRequest::fmb(msg.body.clone()) {
+            self.handle_instances_request(client_id, context_id);
+        } else if let Ok((_, context_id)) = UiStatusRequest::fmb(msg.body.clone()) {
+            self.handle_status_request(client_id, context_id, msg.body);
+        } else if let Ok((request, context_id)) = UiRestartPolicyRequest::fmb(msg.body.clone()) {
+            self.handle_restart_policy_request(client_id, context_id, request);
+        } else if let Ok((_, context_id)) = UiCrashHistoryRequest::fmb(msg.body.clone()) {
+            self.handle_crash_history_request(client_id, context_id);
+        } else if let Ok((request, context_id)) = UiSaveSetupProfileRequest::fmb(msg.body.clone()) {
+            self.handle_save_setup_profile(client_id, context_id, request);
+        } else if let Ok((request, context_id)) = UiLoadSetupProfileRequest::fmb(msg.body.clone()) {
+            self.handle_load_setup_profile(client_id, context_id, request);
+        } else if let Ok((_, context_id)) = UiSetupProfilesRequest::fmb(msg.body.clone()) {
+            self.handle_setup_profiles(client_id, context_id);
+        } else if let Ok((request, context_id)) = UiDeleteSetupProfileRequest::fmb(msg.body.clone())
+        {
+            self.handle_delete_setup_profile(client_id, context_id, request);
         } else {
             self.handle_unexpected_message(client_id, msg.body);
         }
@@ -142,9 +200,11 @@ impl Handler<NodeFromUiMessage> for Daemon {
 impl Handler<CrashNotification> for Daemon {
     type Result = ();
 
-    fn handle(&mut self, msg: CrashNotification, _ctx: &mut Self::Context) -> Self::Result {
+    fn handle(&mut self, msg: CrashNotification, ctx: &mut Self::Context) -> Self::Result {
         debug!(&self.logger, "Handling CrashNotification");
-        self.handle_crash_notification(msg);
+        if let Some((instance_name, delay)) = self.handle_crash_notification(msg) {
+            ctx.run_later(delay, move |daemon, _| daemon.restart_node(&instance_name));
+        }
         debug!(&self.logger, "CrashNotification handled");
     }
 }
@@ -153,146 +213,717 @@ impl Daemon {
     pub fn new(launcher: Box<dyn Launcher>) -> Daemon {
         Daemon {
             launcher,
-            params: HashMap::new(),
+            instances: vec![(DEFAULT_INSTANCE_NAME.to_string(), NodeInstance::default())]
+                .into_iter()
+                .collect(),
+            client_instances: HashMap::new(),
             ui_gateway_sub: None,
             crash_notification_sub: None,
-            node_process_id: None,
-            node_ui_port: None,
             verifier_tools: Box::new(VerifierToolsReal::new()),
             setup_reporter: Box::new(SetupReporterReal::new()),
+            profile_store: Box::new(SetupProfileStoreReal::default()),
+            restart_policy: default_restart_policy(),
+            crash_history: VecDeque::new(),
             logger: Logger::new("Daemon"),
         }
     }
 
+    fn selected_instance(&self, client_id: u64) -> String {
+        self.client_instances
+            .get(&client_id)
+            .cloned()
+            .unwrap_or_else(|| DEFAULT_INSTANCE_NAME.to_string())
+    }
+
+    fn instance(&self, name: &str) -> &NodeInstance {
+        self.instances
+            .get(name)
+            .unwrap_or_else(|| panic!("Node instance '{}' does not exist", name))
+    }
+
+    fn instance_mut(&mut self, name: &str) -> &mut NodeInstance {
+        self.instances
+            .get_mut(name)
+            .unwrap_or_else(|| panic!("Node instance '{}' does not exist", name))
+    }
+
     fn handle_setup(&mut self, client_id: u64, context_id: u64, payload: UiSetupRequest) {
-        if self.port_if_node_is_running().is_some() {
-            let body =
-                UiSetupResponse::new(true, self.censored_params(), ConfiguratorError::new(vec![]))
-                    .tmb(context_id);
+        let name = self.selected_instance(client_id);
+        if self.port_if_node_is_running(&name).is_some() {
+            let body = UiSetupResponse::new(
+                true,
+                self.censored_params(&name),
+                ConfiguratorError::new(vec![]),
+            )
+            .tmb(context_id);
             let target = MessageTarget::ClientId(client_id);
             self.send_ui_message(body, target);
         } else {
-            let incoming_setup = payload.values;
-            let existing_setup = self.params.clone();
-            match self
-                .setup_reporter
-                .get_modified_setup(existing_setup, incoming_setup)
-            {
-                Ok(setup) => self.change_setup_and_notify(
-                    setup,
-                    ConfiguratorError::new(vec![]),
-                    client_id,
-                    context_id,
-                ),
-                Err((lame_cluster, errors)) => {
-                    self.change_setup_and_notify(lame_cluster, errors, client_id, context_id)
+            self.modify_setup(&name, payload.values, client_id, &|values, errors| {
+                UiSetupResponse::new(false, values, errors).tmb(context_id)
+            })
+        };
+    }
+
+    fn modify_setup(
+        &mut self,
+        name: &str,
+        incoming_setup: Vec<UiSetupRequestValue>,
+        client_id: u64,
+        make_response: &dyn Fn(SetupCluster, ConfiguratorError) -> MessageBody,
+    ) {
+        let existing_setup = self.instance(name).params.clone();
+        match self
+            .setup_reporter
+            .get_modified_setup(existing_setup, incoming_setup)
+        {
+            Ok(setup) => self.change_setup_and_notify(
+                name,
+                setup,
+                ConfiguratorError::new(vec![]),
+                client_id,
+                make_response,
+            ),
+            Err((lame_cluster, errors)) => {
+                self.change_setup_and_notify(name, lame_cluster, errors, client_id, make_response)
+            }
+        }
+    }
+
+    fn handle_select_instance(
+        &mut self,
+        client_id: u64,
+        context_id: u64,
+        request: UiSelectInstanceRequest,
+    ) {
+        if let Err(msg) = Self::validate_instance_name(&request.name) {
+            self.respond_to_ui(
+                client_id,
+                MessageBody {
+                    opcode: "selectInstance".to_string(),
+                    path: Conversation(context_id),
+                    payload: Err((NODE_INSTANCE_ERROR, msg)),
+                },
+            );
+            return;
+        }
+        let created = !self.instances.contains_key(&request.name);
+        if created {
+            info!(&self.logger, "Creating Node instance '{}'", request.name);
+            self.instances
+                .insert(request.name.clone(), NodeInstance::default());
+        }
+        if request.name == DEFAULT_INSTANCE_NAME {
+            self.client_instances.remove(&client_id);
+        } else {
+            self.client_instances.insert(client_id, request.name);
+        }
+        self.respond_to_ui(
+            client_id,
+            UiSelectInstanceResponse { created }.tmb(context_id),
+        );
+    }
+
+    fn validate_instance_name(name: &str) -> Result<(), String> {
+        if name.is_empty() || name.len() > MAX_INSTANCE_NAME_LEN {
+            Err(format!(
+                "Node instance names must be between 1 and {} characters long",
+                MAX_INSTANCE_NAME_LEN
+            ))
+        } else if !name
+            .chars()
+            .all(|c| c.is_ascii_alphanumeric() || c == '-' || c == '_')
+        {
+            Err(format!(
+                "Node instance name '{}' may contain only letters, digits, '-', and '_'",
+                name
+            ))
+        } else {
+            Ok(())
+        }
+    }
+
+    fn handle_instances_request(&mut self, client_id: u64, context_id: u64) {
+        let names = self.instances.keys().cloned().sorted().collect_vec();
+        let instances = names
+            .into_iter()
+            .map(|name| {
+                let running = self.port_if_node_is_running(&name).is_some();
+                let instance = self.instance(&name);
+                let param_value =
+                    |param: &str| instance.params.get(param).map(|value| value.value.clone());
+                UiNodeInstance {
+                    running,
+                    process_id_opt: instance.node_process_id,
+                    redirect_ui_port_opt: instance.node_ui_port,
+                    chain_opt: param_value("chain"),
+                    data_directory_opt: param_value("data-directory"),
+                    name,
                 }
+            })
+            .collect();
+        self.respond_to_ui(
+            client_id,
+            UiInstancesResponse {
+                selected: self.selected_instance(client_id),
+                instances,
             }
-        };
+            .tmb(context_id),
+        );
     }
 
-    fn handle_start_order(&mut self, client_id: u64, context_id: u64) {
-        match self.port_if_node_is_running() {
-            Some(_) => self.respond_to_ui(
+    fn handle_save_setup_profile(
+        &mut self,
+        client_id: u64,
+        context_id: u64,
+        request: UiSaveSetupProfileRequest,
+    ) {
+        let name = self.selected_instance(client_id);
+        let (excluded, saved): (Vec<&UiSetupResponseValue>, Vec<&UiSetupResponseValue>) = self
+            .instance(&name)
+            .params
+            .values()
+            .filter(|value| value.status == Set)
+            .partition(|value| CENSORABLES.contains_key(&value.name));
+        let values: BTreeMap<String, String> = saved
+            .into_iter()
+            .map(|value| (value.name.clone(), value.value.clone()))
+            .collect();
+        let excluded = excluded
+            .into_iter()
+            .map(|value| value.name.clone())
+            .sorted()
+            .collect();
+        match self.profile_store.save(&request.name, &values) {
+            Ok(()) => {
+                info!(
+                    &self.logger,
+                    "Saved {} setup values as profile '{}'",
+                    values.len(),
+                    request.name
+                );
+                self.respond_to_ui(
+                    client_id,
+                    UiSaveSetupProfileResponse { excluded }.tmb(context_id),
+                )
+            }
+            Err(e) => self.send_setup_profile_error(client_id, context_id, "saveSetupProfile", e),
+        }
+    }
+
+    fn handle_load_setup_profile(
+        &mut self,
+        client_id: u64,
+        context_id: u64,
+        request: UiLoadSetupProfileRequest,
+    ) {
+        let name = self.selected_instance(client_id);
+        if self.port_if_node_is_running(&name).is_some() {
+            self.respond_to_ui(
                 client_id,
                 MessageBody {
-                    opcode: "start".to_string(),
+                    opcode: "loadSetupProfile".to_string(),
                     path: Conversation(context_id),
                     payload: Err((
                         NODE_ALREADY_RUNNING_ERROR,
-                        "Could not launch Node: already running".to_string(),
+                        "Cannot load a setup profile while the Node is running".to_string(),
                     )),
                 },
-            ),
-            None => match self.launcher.launch(
-                self.params
+            );
+            return;
+        }
+        let profile = match self.profile_store.load(&request.name) {
+            Ok(profile) => profile,
+            Err(e) => {
+                self.send_setup_profile_error(client_id, context_id, "loadSetupProfile", e);
+                return;
+            }
+        };
+        // Values the profile doesn't mention are cleared, except for the sensitive ones that are
+        // never saved in profiles: those stay as they are.
+        let incoming_setup: Vec<UiSetupRequestValue> = self
+            .instance(&name)
+            .params
+            .values()
+            .filter(|value| value.status == Set)
+            .filter(|value| !CENSORABLES.contains_key(&value.name))
+            .filter(|value| !profile.contains_key(&value.name))
+            .map(|value| UiSetupRequestValue::clear(&value.name))
+            .chain(
+                profile
                     .iter()
-                    .filter(|(_, v)| v.status == Set || v.status == Configured)
-                    .map(|(k, v)| (k.to_string(), v.value.to_string()))
-                    .collect(),
-                self.crash_notification_sub.clone().expect("Daemon unbound"),
-            ) {
-                Ok(Some(success)) => {
-                    self.node_process_id = Some(success.new_process_id);
-                    self.node_ui_port = Some(success.redirect_ui_port);
-                    self.respond_to_ui(
-                        client_id,
-                        UiStartResponse {
-                            new_process_id: success.new_process_id,
-                            redirect_ui_port: success.redirect_ui_port,
-                        }
-                        .tmb(context_id),
-                    )
-                }
-                Ok(None) => (),
-                Err(s) => self.respond_to_ui(
+                    .map(|(name, value)| UiSetupRequestValue::new(name, value)),
+            )
+            .sorted_by(|a, b| Ord::cmp(&a.name, &b.name))
+            .collect();
+        info!(&self.logger, "Loading setup profile '{}'", request.name);
+        self.modify_setup(&name, incoming_setup, client_id, &|values, errors| {
+            let response = UiSetupResponse::new(false, values, errors);
+            UiLoadSetupProfileResponse {
+                values: response.values,
+                errors: response.errors,
+            }
+            .tmb(context_id)
+        })
+    }
+
+    fn handle_setup_profiles(&mut self, client_id: u64, context_id: u64) {
+        match self.profile_store.list() {
+            Ok(profiles) => self.respond_to_ui(
+                client_id,
+                UiSetupProfilesResponse { profiles }.tmb(context_id),
+            ),
+            Err(e) => self.send_setup_profile_error(client_id, context_id, "setupProfiles", e),
+        }
+    }
+
+    fn handle_delete_setup_profile(
+        &mut self,
+        client_id: u64,
+        context_id: u64,
+        request: UiDeleteSetupProfileRequest,
+    ) {
+        match self.profile_store.delete(&request.name) {
+            Ok(()) => {
+                info!(&self.logger, "Deleted setup profile '{}'", request.name);
+                self.respond_to_ui(client_id, UiDeleteSetupProfileResponse {}.tmb(context_id))
+            }
+            Err(e) => self.send_setup_profile_error(client_id, context_id, "deleteSetupProfile", e),
+        }
+    }
+
+    fn send_setup_profile_error(&self, client_id: u64, context_id: u64, opcode: &str, msg: String) {
+        self.respond_to_ui(
+            client_id,
+            MessageBody {
+                opcode: opcode.to_string(),
+                path: Conversation(context_id),
+                payload: Err((SETUP_PROFILE_ERROR, msg)),
+            },
+        )
+    }
+
+    fn handle_start_order(&mut self, client_id: u64, context_id: u64) {
+        let name = self.selected_instance(client_id);
+        let launch_params: HashMap<String, String> = self
+            .instance(&name)
+            .params
+            .iter()
+            .filter(|(_, v)| v.status == Set || v.status == Configured)
+            .map(|(k, v)| (k.to_string(), v.value.to_string()))
+            .collect();
+        if self.port_if_node_is_running(&name).is_some() {
+            self.send_start_error(
+                client_id,
+                context_id,
+                NODE_ALREADY_RUNNING_ERROR,
+                "Could not launch Node: already running".to_string(),
+            );
+            return;
+        }
+        if let Some(msg) = self.find_instance_conflict(&name) {
+            self.send_start_error(
+                client_id,
+                context_id,
+                NODE_INSTANCE_ERROR,
+                format!("Could not launch Node: {}", msg),
+            );
+            return;
+        }
+        match self.launcher.launch(
+            launch_params.clone(),
+            self.crash_notification_sub.clone().expect("Daemon unbound"),
+        ) {
+            Ok(Some(success)) => {
+                let instance = self.instance_mut(&name);
+                instance.node_process_id = Some(success.new_process_id);
+                instance.node_ui_port = Some(success.redirect_ui_port);
+                instance.last_launch_params = Some(launch_params);
+                self.respond_to_ui(
+                    client_id,
+                    UiStartResponse {
+                        new_process_id: success.new_process_id,
+                        redirect_ui_port: success.redirect_ui_port,
+                    }
+                    .tmb(context_id),
+                )
+            }
+            Ok(None) => (),
+            Err(s) => self.send_start_error(
+                client_id,
+                context_id,
+                NODE_LAUNCH_ERROR,
+                format!("Could not launch Node: {}", s),
+            ),
+        }
+    }
+
+    fn send_start_error(&self, client_id: u64, context_id: u64, code: u64, msg: String) {
+        self.respond_to_ui(
+            client_id,
+            MessageBody {
+                opcode: "start".to_string(),
+                path: Conversation(context_id),
+                payload: Err((code, msg)),
+            },
+        )
+    }
+
+    // Every Node listens on ports 80 and 443 for the browser and on port 53 for DNS, and no two
+    // processes can have those at once; so while one instance runs, no other may be started.
+    fn find_instance_conflict(&mut self, name: &str) -> Option<String> {
+        let others = self
+            .instances
+            .keys()
+            .filter(|other| other.as_str() != name)
+            .cloned()
+            .sorted()
+            .collect_vec();
+        others
+            .into_iter()
+            .find(|other| self.port_if_node_is_running(other).is_some())
+            .map(|other| {
+                format!(
+                    "instance '{}' is already running, and only one Node at a time can use ports 80, 443 and 53",
+                    other
+                )
+            })
+    }
+
+    fn handle_unexpected_message(&mut self, client_id: u64, body: MessageBody) {
+        let name = self.selected_instance(client_id);
+        match self.port_if_node_is_running(&name) {
+            Some(port) => self.redirect_to_node(client_id, port, body),
+            None => self.send_node_is_not_running_error(client_id, body.opcode),
+        }
+    }
+
+    fn redirect_to_node(&self, client_id: u64, port: u16, body: MessageBody) {
+        info!(
+            &self.logger,
+            "Daemon is redirecting {} message from UI {} Node at port {}",
+            body.opcode,
+            client_id,
+            port
+        );
+        self.send_ui_message(
+            UiRedirect {
+                port,
+                opcode: body.opcode,
+                context_id: match body.path {
+                    FireAndForget => None,
+                    Conversation(context_id) => Some(context_id),
+                },
+                payload: match body.payload {
+                    Ok(json) => json,
+                    Err((_code, _message)) => unimplemented!(),
+                },
+            }
+            .tmb(0),
+            ClientId(client_id),
+        );
+    }
+
+    // A running Node knows more about itself than the Daemon does, so it gets to answer for itself.
+    fn handle_status_request(&mut self, client_id: u64, context_id: u64, body: MessageBody) {
+        let name = self.selected_instance(client_id);
+        if let Some(port) = self.port_if_node_is_running(&name) {
+            self.redirect_to_node(client_id, port, body);
+            return;
+        }
+        let instance = self.instance(&name);
+        let param_value = |param: &str| {
+            instance
+                .params
+                .get(param)
+                .filter(|value| !value.value.is_empty())
+                .map(|value| value.value.clone())
+        };
+        let last_crash_reason_opt = self
+            .crash_history
+            .iter()
+            .rev()
+            .find(|record| record.instance == name)
+            .map(|record| record.crash_reason.clone());
+        let response = UiStatusResponse {
+            running: false,
+            version: env!("CARGO_PKG_VERSION").to_string(),
+            process_id_opt: None,
+            uptime_secs_opt: None,
+            chain_opt: param_value("chain"),
+            neighborhood_mode_opt: param_value("neighborhood-mode"),
+            ui_port_opt: None,
+            last_crash_reason_opt,
+        };
+        self.respond_to_ui(client_id, response.tmb(context_id));
+    }
+
+    fn handle_restart_policy_request(
+        &mut self,
+        client_id: u64,
+        context_id: u64,
+        request: UiRestartPolicyRequest,
+    ) {
+        if let Some(policy) = request.policy {
+            if let Err(msg) = Self::validate_restart_policy(&policy) {
+                self.respond_to_ui(
                     client_id,
                     MessageBody {
-                        opcode: "start".to_string(),
+                        opcode: "restartPolicy".to_string(),
                         path: Conversation(context_id),
-                        payload: Err((NODE_LAUNCH_ERROR, format!("Could not launch Node: {}", s))),
+                        payload: Err((RESTART_POLICY_ERROR, msg)),
                     },
-                ),
-            },
+                );
+                return;
+            }
+            info!(&self.logger, "Restart policy changed to {:?}", policy);
+            self.restart_policy = policy;
+            self.instances
+                .values_mut()
+                .for_each(|instance| instance.recent_restarts.clear());
         }
+        self.respond_to_ui(
+            client_id,
+            UiRestartPolicyResponse {
+                policy: self.restart_policy.clone(),
+            }
+            .tmb(context_id),
+        );
     }
 
-    fn handle_unexpected_message(&mut self, client_id: u64, body: MessageBody) {
-        match self.port_if_node_is_running() {
-            Some(port) => {
+    fn validate_restart_policy(policy: &UiRestartPolicy) -> Result<(), String> {
+        if policy.max_restarts == 0 {
+            Ok(())
+        } else if policy.initial_backoff_ms > policy.max_backoff_ms {
+            Err(format!(
+                "initialBackoffMs ({}) cannot be greater than maxBackoffMs ({})",
+                policy.initial_backoff_ms, policy.max_backoff_ms
+            ))
+        } else if policy.crash_loop_window_secs == 0 {
+            Err("crashLoopWindowSecs must be greater than zero".to_string())
+        } else {
+            Ok(())
+        }
+    }
+
+    fn handle_crash_history_request(&mut self, client_id: u64, context_id: u64) {
+        self.respond_to_ui(
+            client_id,
+            UiCrashHistoryResponse {
+                crashes: self.crash_history.iter().cloned().collect(),
+            }
+            .tmb(context_id),
+        );
+    }
+
+    fn handle_crash_notification(&mut self, msg: CrashNotification) -> Option<(String, Duration)> {
+        let name = match self
+            .instances
+            .iter()
+            .find(|(_, instance)| instance.node_process_id == Some(msg.process_id))
+        {
+            Some((name, _)) => name.clone(),
+            None => return None,
+        };
+        let instance = self.instance_mut(&name);
+        instance.node_process_id = None;
+        instance.node_ui_port = None;
+        let crash_reason = msg.analyze();
+        self.broadcast_to_instance(
+            &name,
+            UiNodeCrashedBroadcast {
+                process_id: msg.process_id,
+                crash_reason: crash_reason.clone(),
+            }
+            .tmb(0),
+            None,
+        );
+        let (outcome, delay_opt) = self.plan_restart(&name, &msg, Instant::now());
+        self.record_crash(&name, msg.process_id, crash_reason, outcome);
+        delay_opt.map(|delay| (name, delay))
+    }
+
+    fn plan_restart(
+        &mut self,
+        name: &str,
+        msg: &CrashNotification,
+        now: Instant,
+    ) -> (RestartOutcome, Option<Duration>) {
+        if self.restart_policy.max_restarts == 0 || self.instance(name).last_launch_params.is_none()
+        {
+            return (RestartOutcome::NotAttempted, None);
+        }
+        if msg.exit_code == Some(0) {
+            info!(
+                &self.logger,
+                "Node process {} exited normally; not restarting it", msg.process_id
+            );
+            return (RestartOutcome::NotAttempted, None);
+        }
+        let window = Duration::from_secs(self.restart_policy.crash_loop_window_secs);
+        let recent_restarts = &mut self.instance_mut(name).recent_restarts;
+        while let Some(oldest) = recent_restarts.front() {
+            if now.duration_since(*oldest) >= window {
+                recent_restarts.pop_front();
+            } else {
+                break;
+            }
+        }
+        let prior_restarts = recent_restarts.len() as u32;
+        if prior_restarts >= self.restart_policy.max_restarts {
+            let reason = format!(
+                "Node crashed {} times within {} seconds",
+                prior_restarts + 1,
+                window.as_secs()
+            );
+            self.abandon_restart(name, msg.process_id, reason);
+            return (RestartOutcome::Abandoned, None);
+        }
+        let delay = Self::restart_delay(&self.restart_policy, prior_restarts);
+        self.instance_mut(name).recent_restarts.push_back(now);
+        info!(
+            &self.logger,
+            "Restarting Node in {}ms after process {} crashed (attempt {} of {})",
+            delay.as_millis(),
+            msg.process_id,
+            prior_restarts + 1,
+            self.restart_policy.max_restarts
+        );
+        self.broadcast_to_instance(
+            name,
+            UiNodeRestartingBroadcast {
+                process_id: msg.process_id,
+                attempt: prior_restarts + 1,
+                max_restarts: self.restart_policy.max_restarts,
+                delay_ms: delay.as_millis() as u64,
+            }
+            .tmb(0),
+            None,
+        );
+        (RestartOutcome::Restarting, Some(delay))
+    }
+
+    fn restart_delay(policy: &UiRestartPolicy, prior_restarts: u32) -> Duration {
+        let multiplier = 1u64.checked_shl(prior_restarts).unwrap_or(u64::MAX);
+        Duration::from_millis(
+            policy
+                .initial_backoff_ms
+                .saturating_mul(multiplier)
+                .min(policy.max_backoff_ms),
+        )
+    }
+
+    fn restart_node(&mut self, name: &str) {
+        if self.port_if_node_is_running(name).is_some() {
+            info!(
+                &self.logger,
+                "Node was started while a restart was pending; not restarting it again"
+            );
+            return;
+        }
+        if let Some(msg) = self.find_instance_conflict(name) {
+            self.give_up_restart(name, format!("Could not relaunch Node: {}", msg));
+            return;
+        }
+        let launch_params = self
+            .instance(name)
+            .last_launch_params
+            .clone()
+            .expect("Restart scheduled without launch parameters");
+        match self.launcher.launch(
+            launch_params,
+            self.crash_notification_sub.clone().expect("Daemon unbound"),
+        ) {
+            Ok(Some(success)) => {
+                let instance = self.instance_mut(name);
+                instance.node_process_id = Some(success.new_process_id);
+                instance.node_ui_port = Some(success.redirect_ui_port);
                 info!(
                     &self.logger,
-                    "Daemon is redirecting {} message from UI {} Node at port {}",
-                    body.opcode,
-                    client_id,
-                    port
+                    "Restarted Node as process {}", success.new_process_id
                 );
-                self.send_ui_message(
-                    UiRedirect {
-                        port,
-                        opcode: body.opcode,
-                        context_id: match body.path {
-                            FireAndForget => None,
-                            Conversation(context_id) => Some(context_id),
-                        },
-                        payload: match body.payload {
-                            Ok(json) => json,
-                            Err((_code, _message)) => unimplemented!(),
-                        },
+                self.broadcast_to_instance(
+                    name,
+                    UiNodeRestartedBroadcast {
+                        new_process_id: success.new_process_id,
+                        redirect_ui_port: success.redirect_ui_port,
                     }
                     .tmb(0),
-                    ClientId(client_id),
+                    None,
                 );
             }
-            None => self.send_node_is_not_running_error(client_id, body.opcode),
+            Ok(None) => (),
+            Err(e) => self.give_up_restart(name, format!("Could not relaunch Node: {}", e)),
         }
     }
 
-    fn handle_crash_notification(&mut self, msg: CrashNotification) {
-        if self.node_ui_port.is_some() || self.node_process_id.is_some() {
-            self.node_process_id = None;
-            self.node_ui_port = None;
-            self.send_ui_message(
-                UiNodeCrashedBroadcast {
-                    process_id: msg.process_id,
-                    crash_reason: msg.analyze(),
-                }
-                .tmb(0),
-                MessageTarget::AllClients,
-            );
+    fn give_up_restart(&mut self, name: &str, reason: String) {
+        let process_id = match self
+            .crash_history
+            .iter_mut()
+            .rev()
+            .find(|record| record.instance == name)
+        {
+            Some(record) => {
+                record.outcome = RestartOutcome::Abandoned;
+                record.process_id
+            }
+            None => 0,
+        };
+        self.abandon_restart(name, process_id, reason);
+    }
+
+    fn abandon_restart(&mut self, name: &str, process_id: u32, reason: String) {
+        warning!(
+            &self.logger,
+            "Giving up on restarting Node after process {} crashed: {}",
+            process_id,
+            reason
+        );
+        self.instance_mut(name).recent_restarts.clear();
+        self.broadcast_to_instance(
+            name,
+            UiNodeRestartAbandonedBroadcast { process_id, reason }.tmb(0),
+            None,
+        );
+    }
+
+    fn record_crash(
+        &mut self,
+        name: &str,
+        process_id: u32,
+        crash_reason: CrashReason,
+        outcome: RestartOutcome,
+    ) {
+        let timestamp = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .expect("System clock is before the epoch")
+            .as_secs();
+        if self.crash_history.len() >= MAX_CRASH_HISTORY_LEN {
+            self.crash_history.pop_front();
         }
+        self.crash_history.push_back(UiCrashRecord {
+            instance: name.to_string(),
+            process_id,
+            crash_reason,
+            timestamp,
+            outcome,
+        });
     }
 
-    fn port_if_node_is_running(&mut self) -> Option<u16> {
-        if let Some(process_id) = self.node_process_id {
+    fn port_if_node_is_running(&mut self, name: &str) -> Option<u16> {
+        let process_id_opt = self.instance(name).node_process_id;
+        if let Some(process_id) = process_id_opt {
             if self.verifier_tools.process_is_running(process_id) {
                 Some(
-                    self.node_ui_port
+                    self.instance(name)
+                        .node_ui_port
                         .expect("Internal error: node_process_id is set but node_ui_port is not"),
                 )
             } else {
-                self.node_process_id = None;
-                self.node_ui_port = None;
+                let instance = self.instance_mut(name);
+                instance.node_process_id = None;
+                instance.node_ui_port = None;
                 None
             }
         } else {
@@ -323,42 +954,59 @@ impl Daemon {
         self.send_ui_message(body, ClientId(client_id));
     }
 
+    // Broadcasts about the default instance go to every client, as they always have; broadcasts
+    // about a named instance go only to the clients that have selected it.
+    fn broadcast_to_instance(&self, name: &str, body: MessageBody, except_opt: Option<u64>) {
+        if name == DEFAULT_INSTANCE_NAME {
+            let target = match except_opt {
+                Some(client_id) => MessageTarget::AllExcept(client_id),
+                None => MessageTarget::AllClients,
+            };
+            self.send_ui_message(body, target);
+        } else {
+            self.client_instances
+                .iter()
+                .filter(|(client_id, selected)| {
+                    selected.as_str() == name && Some(**client_id) != except_opt
+                })
+                .map(|(client_id, _)| *client_id)
+                .sorted()
+                .for_each(|client_id| self.send_ui_message(body.clone(), ClientId(client_id)));
+        }
+    }
+
     fn change_setup_and_notify(
         &mut self,
+        name: &str,
         new_setup: SetupCluster,
         errors: ConfiguratorError,
         client_id: u64,
-        context_id: u64,
+        make_response: &dyn Fn(SetupCluster, ConfiguratorError) -> MessageBody,
     ) {
-        let body_target_pairs = match Self::compare_setup_clusters(&self.params, &new_setup) {
-            Err(_) => {
-                let originally_empty = self.params.is_empty();
-                self.params = new_setup;
-                let mut pairs = vec![(
-                    UiSetupResponse::new(false, self.censored_params(), errors.clone())
-                        .tmb(context_id),
-                    MessageTarget::ClientId(client_id),
-                )];
-                if !originally_empty {
-                    pairs.push((
-                        UiSetupBroadcast::new(false, self.censored_params(), errors).tmb(0),
-                        MessageTarget::AllExcept(client_id),
-                    ));
-                };
-                pairs
+        let changed =
+            Self::compare_setup_clusters(&self.instance(name).params, &new_setup).is_err();
+        if changed {
+            let originally_empty = self.instance(name).params.is_empty();
+            self.instance_mut(name).params = new_setup;
+            self.respond_to_ui(
+                client_id,
+                make_response(self.censored_params(name), errors.clone()),
+            );
+            if !originally_empty {
+                self.broadcast_to_instance(
+                    name,
+                    UiSetupBroadcast::new(false, self.censored_params(name), errors).tmb(0),
+                    Some(client_id),
+                );
             }
-            Ok(_) => vec![(
-                UiSetupResponse::new(false, self.censored_params(), errors).tmb(context_id),
-                MessageTarget::ClientId(client_id),
-            )],
-        };
-        body_target_pairs
-            .into_iter()
-            .for_each(|(body, target)| self.send_ui_message(body, target));
+        } else {
+            self.respond_to_ui(client_id, make_response(self.censored_params(name), errors));
+        }
     }
 
-    fn censored_params(&self) -> SetupCluster {
-        self.params
+    fn censored_params(&self, name: &str) -> SetupCluster {
+        self.instance(name)
+            .params
             .clone()
             .into_iter()
             .map(|(name, uisrv)| match CENSORABLES.get(&name) {
@@ -425,20 +1073,32 @@ mod tests {
     use super::*;
     use crate::daemon::crash_notification::CrashNotification;
     use crate::daemon::mocks::VerifierToolsMock;
+    use crate::daemon::setup_profiles::SetupProfileStoreReal;
     use crate::daemon::setup_reporter::{setup_cluster_from, SetupCluster};
     use crate::daemon::LaunchSuccess;
     use crate::test_utils::recorder::{make_recorder, Recorder};
     use actix::System;
-    use masq_lib::messages::UiSetupResponseValueStatus::{Blank, Required, Set};
+    use masq_lib::messages::UiSetupResponseValueStatus::{Blank, Configured, Required, Set};
     use masq_lib::messages::{
-        CrashReason, UiFinancialsRequest, UiNodeCrashedBroadcast, UiRedirect, UiSetupBroadcast,
-        UiSetupRequest, UiSetupRequestValue, UiSetupResponse, UiSetupResponseValue,
-        UiSetupResponseValueStatus, UiShutdownRequest, UiStartOrder, UiStartResponse,
-        NODE_ALREADY_RUNNING_ERROR, NODE_LAUNCH_ERROR, NODE_NOT_RUNNING_ERROR,
+        CrashReason, RestartOutcome, UiCrashHistoryRequest, UiCrashHistoryResponse, UiCrashRecord,
+        UiDeleteSetupProfileRequest, UiDeleteSetupProfileResponse, UiFinancialsRequest,
+        UiInstancesRequest, UiInstancesResponse, UiLoadSetupProfileRequest,
+        UiLoadSetupProfileResponse, UiNodeCrashedBroadcast, UiNodeInstance,
+        UiNodeRestartAbandonedBroadcast, UiNodeRestartedBroadcast, UiNodeRestartingBroadcast,
+        UiRedirect, UiRestartPolicy, UiRestartPolicyRequest, UiRestartPolicyResponse,
+        UiSaveSetupProfileRequest, UiSaveSetupProfileResponse, UiSelectInstanceRequest,
+        UiSelectInstanceResponse, UiSetupBroadcast, UiSetupProfilesRequest,
+        UiSetupProfilesResponse, UiSetupRequest, UiSetupRequestValue, UiSetupResponse,
+        UiSetupResponseValue, UiSetupResponseValueStatus, UiShutdownRequest, UiStartOrder,
+        UiStartResponse, UiStatusRequest, UiStatusResponse, NODE_ALREADY_RUNNING_ERROR,
+        NODE_INSTANCE_ERROR, NODE_LAUNCH_ERROR, NODE_NOT_RUNNING_ERROR, RESTART_POLICY_ERROR,
+        SETUP_PROFILE_ERROR,
     };
     use masq_lib::shared_schema::ConfiguratorError;
     use masq_lib::test_utils::environment_guard::{ClapGuard, EnvironmentGuard};
-    use masq_lib::test_utils::utils::{ensure_node_home_directory_exists, TEST_DEFAULT_CHAIN_NAME};
+    use masq_lib::test_utils::utils::{
+        ensure_node_home_directory_exists, node_home_directory, TEST_DEFAULT_CHAIN_NAME,
+    };
     use masq_lib::ui_gateway::MessageTarget::AllExcept;
     use masq_lib::ui_gateway::{MessagePath, MessageTarget};
     use std::cell::RefCell;
@@ -515,7 +1175,7 @@ mod tests {
             }
         }
 
-        fn _get_modified_setup_params(
+        fn get_modified_setup_params(
             mut self,
             params: &Arc<Mutex<Vec<(SetupCluster, Vec<UiSetupRequestValue>)>>>,
         ) -> Self {
@@ -561,14 +1221,14 @@ mod tests {
     #[test]
     fn censorship_works() {
         let mut subject = Daemon::new(Box::new(LauncherMock::new()));
-        subject.params = make_setup_cluster(vec![
+        subject.instance_mut(DEFAULT_INSTANCE_NAME).params = make_setup_cluster(vec![
             ("one-non-censorable", "one value", Set),
             ("db-password", "super-secret value", Configured),
             ("consuming-private-key", "another super-secret value", Blank),
             ("another-non-censorable", "another value", Required),
         ]);
 
-        let result = subject.censored_params();
+        let result = subject.censored_params(DEFAULT_INSTANCE_NAME);
 
         assert_eq!(
             result,
@@ -594,13 +1254,13 @@ mod tests {
         let mut subject = Daemon::new(Box::new(LauncherMock::new()));
         subject.verifier_tools = Box::new(verifier_tools);
         subject.setup_reporter = Box::new(setup_reporter);
-        subject.params = make_setup_cluster(vec![
+        subject.instance_mut(DEFAULT_INSTANCE_NAME).params = make_setup_cluster(vec![
             ("neighborhood-mode", "zero-hop", Set),
             ("consuming-private-key", "secret value", Set),
             ("db-password", "secret value", Set),
         ]);
-        subject.node_process_id = Some(12345);
-        subject.node_ui_port = Some(54321);
+        subject.instance_mut(DEFAULT_INSTANCE_NAME).node_process_id = Some(12345);
+        subject.instance_mut(DEFAULT_INSTANCE_NAME).node_ui_port = Some(54321);
         let subject_addr = subject.start();
         subject_addr
             .try_send(make_bind_message(ui_gateway))
@@ -659,12 +1319,12 @@ mod tests {
         let mut subject = Daemon::new(Box::new(LauncherMock::new()));
         subject.verifier_tools = Box::new(verifier_tools);
         subject.setup_reporter = Box::new(setup_reporter);
-        subject.params = make_setup_cluster(vec![
+        subject.instance_mut(DEFAULT_INSTANCE_NAME).params = make_setup_cluster(vec![
             ("neighborhood-mode", "zero-hop", Set),
             ("db-password", "secret value", Set),
         ]);
-        subject.node_process_id = None;
-        subject.node_ui_port = None;
+        subject.instance_mut(DEFAULT_INSTANCE_NAME).node_process_id = None;
+        subject.instance_mut(DEFAULT_INSTANCE_NAME).node_ui_port = None;
         let subject_addr = subject.start();
         subject_addr
             .try_send(make_bind_message(ui_gateway))
@@ -726,8 +1386,8 @@ mod tests {
         let system = System::new("test");
         let verifier_tools = VerifierToolsMock::new();
         let mut subject = Daemon::new(Box::new(LauncherMock::new()));
-        subject.node_ui_port = None;
-        subject.node_process_id = None;
+        subject.instance_mut(DEFAULT_INSTANCE_NAME).node_ui_port = None;
+        subject.instance_mut(DEFAULT_INSTANCE_NAME).node_process_id = None;
         subject.verifier_tools = Box::new(verifier_tools);
         let subject_addr = subject.start();
         subject_addr
@@ -787,8 +1447,8 @@ mod tests {
         let system = System::new("test");
         let verifier_tools = VerifierToolsMock::new().process_is_running_result(false); // only consulted once; second time, we already know
         let mut subject = Daemon::new(Box::new(LauncherMock::new()));
-        subject.node_ui_port = Some(1234);
-        subject.node_process_id = Some(4321);
+        subject.instance_mut(DEFAULT_INSTANCE_NAME).node_ui_port = Some(1234);
+        subject.instance_mut(DEFAULT_INSTANCE_NAME).node_process_id = Some(4321);
         subject.verifier_tools = Box::new(verifier_tools);
         let subject_addr = subject.start();
         subject_addr
@@ -887,8 +1547,8 @@ mod tests {
     fn handle_setup_responds_but_does_not_broadcast_if_setup_changes_from_nothing() {
         let (ui_gateway, _, ui_gateway_recording_arc) = make_recorder();
         let mut subject = Daemon::new(Box::new(LauncherMock::new()));
-        subject.params.clear(); // nothing
-        let existing_setup = subject.params.clone();
+        subject.instance_mut(DEFAULT_INSTANCE_NAME).params.clear(); // nothing
+        let existing_setup = subject.instance(DEFAULT_INSTANCE_NAME).params.clone();
         let modified_setup = {
             let mut modified_setup = existing_setup.clone();
             modified_setup.insert(
@@ -932,7 +1592,7 @@ mod tests {
     fn handle_setup_responds_but_does_not_broadcast_if_setup_is_not_changed() {
         let (ui_gateway, _, ui_gateway_recording_arc) = make_recorder();
         let mut subject = Daemon::new(Box::new(LauncherMock::new()));
-        subject.params.insert(
+        subject.instance_mut(DEFAULT_INSTANCE_NAME).params.insert(
             "db-password".to_string(),
             UiSetupResponseValue::new("db-password", "secret value", Configured),
         ); // not nothing
@@ -979,11 +1639,11 @@ mod tests {
     fn handle_setup_responds_and_broadcasts_if_setup_is_changed() {
         let (ui_gateway, _, ui_gateway_recording_arc) = make_recorder();
         let mut subject = Daemon::new(Box::new(LauncherMock::new()));
-        subject.params.insert(
+        subject.instance_mut(DEFAULT_INSTANCE_NAME).params.insert(
             "booga".to_string(),
             UiSetupResponseValue::new("booga", "agoob", Configured),
         ); // not nothing
-        let existing_setup = subject.params.clone();
+        let existing_setup = subject.instance(DEFAULT_INSTANCE_NAME).params.clone();
         let modified_setup = {
             let mut modified_setup = existing_setup.clone();
             modified_setup.insert(
@@ -1052,7 +1712,7 @@ mod tests {
         let verifier_tools = VerifierToolsMock::new();
         let system = System::new("test");
         let mut subject = Daemon::new(Box::new(launcher));
-        subject.params.insert(
+        subject.instance_mut(DEFAULT_INSTANCE_NAME).params.insert(
             "db-password".to_string(),
             UiSetupResponseValue::new("db-password", "goober", Set),
         );
@@ -1107,7 +1767,7 @@ mod tests {
         let verifier_tools = VerifierToolsMock::new();
         let system = System::new("test");
         let mut subject = Daemon::new(Box::new(launcher));
-        subject.params.insert(
+        subject.instance_mut(DEFAULT_INSTANCE_NAME).params.insert(
             "db-password".to_string(),
             UiSetupResponseValue::new("db-password", "goober", Set),
         );
@@ -1149,15 +1809,15 @@ mod tests {
             .process_is_running_result(false);
         let system = System::new("test");
         let mut subject = Daemon::new(Box::new(launcher));
-        subject.params.insert(
+        subject.instance_mut(DEFAULT_INSTANCE_NAME).params.insert(
             "db-password".to_string(),
             UiSetupResponseValue::new("db-password", "goober", Set),
         );
-        subject.params.insert(
+        subject.instance_mut(DEFAULT_INSTANCE_NAME).params.insert(
             "neighborhood-mode".to_string(),
             UiSetupResponseValue::new("neighborhood-mode", "zero-hop", Set),
         );
-        subject.params.insert(
+        subject.instance_mut(DEFAULT_INSTANCE_NAME).params.insert(
             "data-directory".to_string(),
             UiSetupResponseValue::new(
                 "data-directory",
@@ -1221,7 +1881,7 @@ mod tests {
         let verifier_tools = VerifierToolsMock::new();
         let system = System::new("test");
         let mut subject = Daemon::new(Box::new(launcher));
-        subject.params.insert(
+        subject.instance_mut(DEFAULT_INSTANCE_NAME).params.insert(
             "db-password".to_string(),
             UiSetupResponseValue::new("db-password", "goober", Set),
         );
@@ -1257,12 +1917,12 @@ mod tests {
         let verifier_tools = VerifierToolsMock::new().process_is_running_result(true);
         let system = System::new("test");
         let mut subject = Daemon::new(Box::new(launcher));
-        subject.params.insert(
+        subject.instance_mut(DEFAULT_INSTANCE_NAME).params.insert(
             "db-password".to_string(),
             UiSetupResponseValue::new("db-password", "goober", Set),
         );
-        subject.node_ui_port = Some(1234);
-        subject.node_process_id = Some(3421);
+        subject.instance_mut(DEFAULT_INSTANCE_NAME).node_ui_port = Some(1234);
+        subject.instance_mut(DEFAULT_INSTANCE_NAME).node_process_id = Some(3421);
         subject.verifier_tools = Box::new(verifier_tools);
         let subject_addr = subject.start();
         subject_addr
@@ -1310,12 +1970,29 @@ mod tests {
         subject.ui_gateway_sub = Some(gateway_recipient.clone());
         subject.crash_notification_sub = Some(crash_notification_recipient);
         subject.verifier_tools = Box::new(verifier_tools);
-        subject.params = setup_cluster_from(vec![("data-directory", "bigglesworth", Set)]);
+        subject.instance_mut(DEFAULT_INSTANCE_NAME).params =
+            setup_cluster_from(vec![("data-directory", "bigglesworth", Set)]);
 
         subject.handle_start_order(1234, 2345);
 
-        assert_eq!(subject.node_process_id, Some(54321));
-        assert_eq!(subject.node_ui_port, Some(7777));
+        assert_eq!(
+            subject.instance(DEFAULT_INSTANCE_NAME).node_process_id,
+            Some(54321)
+        );
+        assert_eq!(
+            subject.instance(DEFAULT_INSTANCE_NAME).node_ui_port,
+            Some(7777)
+        );
+        assert_eq!(
+            subject
+                .instance_mut(DEFAULT_INSTANCE_NAME)
+                .last_launch_params,
+            Some(
+                vec![("data-directory".to_string(), "bigglesworth".to_string())]
+                    .into_iter()
+                    .collect()
+            )
+        );
         let launch_params = launch_params_arc.lock().unwrap();
         assert_eq!(
             launch_params
@@ -1365,8 +2042,8 @@ mod tests {
             .process_is_running_params(&process_is_running_params_arc)
             .process_is_running_result(true);
         let mut subject = Daemon::new(Box::new(LauncherMock::new()));
-        subject.node_ui_port = Some(7777);
-        subject.node_process_id = Some(8888);
+        subject.instance_mut(DEFAULT_INSTANCE_NAME).node_ui_port = Some(7777);
+        subject.instance_mut(DEFAULT_INSTANCE_NAME).node_process_id = Some(8888);
         subject.verifier_tools = Box::new(verifier_tools);
         let subject_addr = subject.start();
         subject_addr
@@ -1413,8 +2090,8 @@ mod tests {
             .process_is_running_params(&process_is_running_params_arc)
             .process_is_running_result(false); // only consulted once; second time, we already know
         let mut subject = Daemon::new(Box::new(LauncherMock::new()));
-        subject.node_ui_port = Some(7777);
-        subject.node_process_id = Some(8888);
+        subject.instance_mut(DEFAULT_INSTANCE_NAME).node_ui_port = Some(7777);
+        subject.instance_mut(DEFAULT_INSTANCE_NAME).node_process_id = Some(8888);
         subject.verifier_tools = Box::new(verifier_tools);
         let subject_addr = subject.start();
         subject_addr
@@ -1446,8 +2123,8 @@ mod tests {
         let system = System::new("test");
         let verifier_tools = VerifierToolsMock::new();
         let mut subject = Daemon::new(Box::new(LauncherMock::new()));
-        subject.node_ui_port = None;
-        subject.node_process_id = None;
+        subject.instance_mut(DEFAULT_INSTANCE_NAME).node_ui_port = None;
+        subject.instance_mut(DEFAULT_INSTANCE_NAME).node_process_id = None;
         subject.verifier_tools = Box::new(verifier_tools);
         let subject_addr = subject.start();
         subject_addr
@@ -1490,8 +2167,8 @@ mod tests {
         let system = System::new("test");
         let verifier_tools = VerifierToolsMock::new();
         let mut subject = Daemon::new(Box::new(LauncherMock::new()));
-        subject.node_ui_port = Some(1234);
-        subject.node_process_id = Some(12345);
+        subject.instance_mut(DEFAULT_INSTANCE_NAME).node_ui_port = Some(1234);
+        subject.instance_mut(DEFAULT_INSTANCE_NAME).node_process_id = Some(54321);
         subject.verifier_tools = Box::new(verifier_tools);
         let subject_addr = subject.start();
         subject_addr
@@ -1553,8 +2230,8 @@ mod tests {
         let mut subject = Daemon::new(Box::new(LauncherMock::new()));
         subject.ui_gateway_sub = Some(ui_gateway_sub);
         subject.verifier_tools = Box::new(verifier_tools);
-        subject.node_ui_port = None;
-        subject.node_process_id = None;
+        subject.instance_mut(DEFAULT_INSTANCE_NAME).node_ui_port = None;
+        subject.instance_mut(DEFAULT_INSTANCE_NAME).node_process_id = None;
 
         subject.handle_crash_notification(CrashNotification {
             process_id: 54321,
@@ -1567,4 +2244,1140 @@ mod tests {
         let ui_gateway_recording = ui_gateway_recording_arc.lock().unwrap();
         assert_eq!(ui_gateway_recording.len(), 0);
     }
+
+    fn make_restart_policy(max_restarts: u32) -> UiRestartPolicy {
+        UiRestartPolicy {
+            max_restarts,
+            initial_backoff_ms: 1_000,
+            max_backoff_ms: 3_000,
+            crash_loop_window_secs: 300,
+        }
+    }
+
+    fn make_crashed_subject(launcher: LauncherMock, ui_gateway: Recorder) -> Daemon {
+        let (daemon, _, _) = make_recorder();
+        let mut subject = Daemon::new(Box::new(launcher));
+        subject.ui_gateway_sub = Some(ui_gateway.start().recipient());
+        subject.crash_notification_sub = Some(daemon.start().recipient());
+        subject.verifier_tools = Box::new(VerifierToolsMock::new());
+        subject
+            .instance_mut(DEFAULT_INSTANCE_NAME)
+            .last_launch_params = Some(
+            vec![("data-directory".to_string(), "bigglesworth".to_string())]
+                .into_iter()
+                .collect(),
+        );
+        subject
+    }
+
+    fn make_crash(process_id: u32, exit_code: Option<i32>) -> CrashNotification {
+        CrashNotification {
+            process_id,
+            exit_code,
+            stderr: Some("Standard error".to_string()),
+        }
+    }
+
+    #[test]
+    fn default_restart_policy_is_disabled() {
+        let result = default_restart_policy();
+
+        assert_eq!(
+            result,
+            UiRestartPolicy {
+                max_restarts: 0,
+                initial_backoff_ms: 1_000,
+                max_backoff_ms: 60_000,
+                crash_loop_window_secs: 300,
+            }
+        );
+    }
+
+    #[test]
+    fn crash_is_recorded_but_not_restarted_under_default_policy() {
+        let (ui_gateway, _, ui_gateway_recording_arc) = make_recorder();
+        let system = System::new("test");
+        let mut subject = make_crashed_subject(LauncherMock::new(), ui_gateway);
+        subject.instance_mut(DEFAULT_INSTANCE_NAME).node_process_id = Some(12345);
+        subject.instance_mut(DEFAULT_INSTANCE_NAME).node_ui_port = Some(1234);
+
+        let result = subject.handle_crash_notification(make_crash(12345, Some(1)));
+
+        System::current().stop();
+        system.run();
+        assert_eq!(result, None);
+        let ui_gateway_recording = ui_gateway_recording_arc.lock().unwrap();
+        assert_eq!(ui_gateway_recording.len(), 1);
+        let record = ui_gateway_recording.get_record::<NodeToUiMessage>(0);
+        assert_eq!(
+            UiNodeCrashedBroadcast::fmb(record.body.clone()).unwrap().0,
+            UiNodeCrashedBroadcast {
+                process_id: 12345,
+                crash_reason: CrashReason::Unrecognized("Standard error".to_string()),
+            }
+        );
+        assert_eq!(subject.crash_history.len(), 1);
+        assert_eq!(subject.crash_history[0].process_id, 12345);
+        assert_eq!(
+            subject.crash_history[0].outcome,
+            RestartOutcome::NotAttempted
+        );
+    }
+
+    #[test]
+    fn crashes_are_restarted_with_exponential_backoff_until_crash_loop_is_detected() {
+        let (ui_gateway, _, ui_gateway_recording_arc) = make_recorder();
+        let system = System::new("test");
+        let mut subject = make_crashed_subject(LauncherMock::new(), ui_gateway);
+        subject.restart_policy = make_restart_policy(3);
+
+        let results = (0..4)
+            .map(|index| {
+                subject.instance_mut(DEFAULT_INSTANCE_NAME).node_process_id = Some(100 + index);
+                subject.instance_mut(DEFAULT_INSTANCE_NAME).node_ui_port = Some(1234);
+                subject
+                    .handle_crash_notification(make_crash(100 + index, Some(1)))
+                    .map(|(_, delay)| delay)
+            })
+            .collect::<Vec<Option<Duration>>>();
+
+        System::current().stop();
+        system.run();
+        assert_eq!(
+            results,
+            vec![
+                Some(Duration::from_millis(1_000)),
+                Some(Duration::from_millis(2_000)),
+                Some(Duration::from_millis(3_000)),
+                None,
+            ]
+        );
+        let ui_gateway_recording = ui_gateway_recording_arc.lock().unwrap();
+        assert_eq!(ui_gateway_recording.len(), 8);
+        let restarting = (0..3)
+            .map(|index| {
+                let record = ui_gateway_recording.get_record::<NodeToUiMessage>(index * 2 + 1);
+                assert_eq!(record.target, MessageTarget::AllClients);
+                UiNodeRestartingBroadcast::fmb(record.body.clone())
+                    .unwrap()
+                    .0
+            })
+            .collect::<Vec<UiNodeRestartingBroadcast>>();
+        assert_eq!(
+            restarting,
+            vec![
+                UiNodeRestartingBroadcast {
+                    process_id: 100,
+                    attempt: 1,
+                    max_restarts: 3,
+                    delay_ms: 1_000,
+                },
+                UiNodeRestartingBroadcast {
+                    process_id: 101,
+                    attempt: 2,
+                    max_restarts: 3,
+                    delay_ms: 2_000,
+                },
+                UiNodeRestartingBroadcast {
+                    process_id: 102,
+                    attempt: 3,
+                    max_restarts: 3,
+                    delay_ms: 3_000,
+                },
+            ]
+        );
+        let record = ui_gateway_recording.get_record::<NodeToUiMessage>(7);
+        assert_eq!(
+            UiNodeRestartAbandonedBroadcast::fmb(record.body.clone())
+                .unwrap()
+                .0,
+            UiNodeRestartAbandonedBroadcast {
+                process_id: 103,
+                reason: "Node crashed 4 times within 300 seconds".to_string(),
+            }
+        );
+        assert_eq!(
+            subject
+                .crash_history
+                .iter()
+                .map(|record| record.outcome.clone())
+                .collect::<Vec<RestartOutcome>>(),
+            vec![
+                RestartOutcome::Restarting,
+                RestartOutcome::Restarting,
+                RestartOutcome::Restarting,
+                RestartOutcome::Abandoned,
+            ]
+        );
+        assert!(subject
+            .instance(DEFAULT_INSTANCE_NAME)
+            .recent_restarts
+            .is_empty());
+    }
+
+    #[test]
+    fn restarts_outside_the_crash_loop_window_are_forgotten() {
+        let (ui_gateway, _, _) = make_recorder();
+        let system = System::new("test");
+        let mut subject = make_crashed_subject(LauncherMock::new(), ui_gateway);
+        subject.restart_policy = make_restart_policy(1);
+        let start = Instant::now();
+
+        let first = subject.plan_restart(DEFAULT_INSTANCE_NAME, &make_crash(100, Some(1)), start);
+        let second = subject.plan_restart(
+            DEFAULT_INSTANCE_NAME,
+            &make_crash(101, Some(1)),
+            start + Duration::from_secs(300),
+        );
+
+        System::current().stop();
+        system.run();
+        assert_eq!(
+            first,
+            (
+                RestartOutcome::Restarting,
+                Some(Duration::from_millis(1_000))
+            )
+        );
+        assert_eq!(
+            second,
+            (
+                RestartOutcome::Restarting,
+                Some(Duration::from_millis(1_000))
+            )
+        );
+    }
+
+    #[test]
+    fn normal_exit_is_not_restarted() {
+        let (ui_gateway, _, ui_gateway_recording_arc) = make_recorder();
+        let system = System::new("test");
+        let mut subject = make_crashed_subject(LauncherMock::new(), ui_gateway);
+        subject.restart_policy = make_restart_policy(3);
+        subject.instance_mut(DEFAULT_INSTANCE_NAME).node_process_id = Some(12345);
+        subject.instance_mut(DEFAULT_INSTANCE_NAME).node_ui_port = Some(1234);
+
+        let result = subject.handle_crash_notification(make_crash(12345, Some(0)));
+
+        System::current().stop();
+        system.run();
+        assert_eq!(result, None);
+        assert_eq!(ui_gateway_recording_arc.lock().unwrap().len(), 1);
+        assert_eq!(
+            subject.crash_history[0].outcome,
+            RestartOutcome::NotAttempted
+        );
+    }
+
+    #[test]
+    fn restart_node_relaunches_with_last_launched_params() {
+        let (ui_gateway, _, ui_gateway_recording_arc) = make_recorder();
+        let system = System::new("test");
+        let launch_params_arc = Arc::new(Mutex::new(vec![]));
+        let launcher = LauncherMock::new()
+            .launch_params(&launch_params_arc)
+            .launch_result(Ok(Some(LaunchSuccess {
+                new_process_id: 54321,
+                redirect_ui_port: 7777,
+            })));
+        let mut subject = make_crashed_subject(launcher, ui_gateway);
+        subject.instance_mut(DEFAULT_INSTANCE_NAME).params =
+            setup_cluster_from(vec![("data-directory", "changed since", Set)]);
+
+        subject.restart_node(DEFAULT_INSTANCE_NAME);
+
+        System::current().stop();
+        system.run();
+        assert_eq!(
+            subject.instance(DEFAULT_INSTANCE_NAME).node_process_id,
+            Some(54321)
+        );
+        assert_eq!(
+            subject.instance(DEFAULT_INSTANCE_NAME).node_ui_port,
+            Some(7777)
+        );
+        let launch_params = launch_params_arc.lock().unwrap();
+        assert_eq!(
+            launch_params[0].0,
+            vec![("data-directory".to_string(), "bigglesworth".to_string())]
+                .into_iter()
+                .collect::<HashMap<String, String>>()
+        );
+        let ui_gateway_recording = ui_gateway_recording_arc.lock().unwrap();
+        let record = ui_gateway_recording.get_record::<NodeToUiMessage>(0);
+        assert_eq!(record.target, MessageTarget::AllClients);
+        assert_eq!(
+            UiNodeRestartedBroadcast::fmb(record.body.clone())
+                .unwrap()
+                .0,
+            UiNodeRestartedBroadcast {
+                new_process_id: 54321,
+                redirect_ui_port: 7777,
+            }
+        );
+    }
+
+    #[test]
+    fn restart_node_gives_up_if_relaunch_fails() {
+        let (ui_gateway, _, ui_gateway_recording_arc) = make_recorder();
+        let system = System::new("test");
+        let launcher = LauncherMock::new().launch_result(Err("Bad launch".to_string()));
+        let mut subject = make_crashed_subject(launcher, ui_gateway);
+        subject.restart_policy = make_restart_policy(3);
+        subject
+            .instance_mut(DEFAULT_INSTANCE_NAME)
+            .recent_restarts
+            .push_back(Instant::now());
+        subject.record_crash(
+            DEFAULT_INSTANCE_NAME,
+            12345,
+            CrashReason::NoInformation,
+            RestartOutcome::Restarting,
+        );
+
+        subject.restart_node(DEFAULT_INSTANCE_NAME);
+
+        System::current().stop();
+        system.run();
+        assert_eq!(
+            subject.instance(DEFAULT_INSTANCE_NAME).node_process_id,
+            None
+        );
+        assert!(subject
+            .instance(DEFAULT_INSTANCE_NAME)
+            .recent_restarts
+            .is_empty());
+        assert_eq!(subject.crash_history[0].outcome, RestartOutcome::Abandoned);
+        let ui_gateway_recording = ui_gateway_recording_arc.lock().unwrap();
+        let record = ui_gateway_recording.get_record::<NodeToUiMessage>(0);
+        assert_eq!(
+            UiNodeRestartAbandonedBroadcast::fmb(record.body.clone())
+                .unwrap()
+                .0,
+            UiNodeRestartAbandonedBroadcast {
+                process_id: 12345,
+                reason: "Could not relaunch Node: Bad launch".to_string(),
+            }
+        );
+    }
+
+    #[test]
+    fn restart_policy_request_changes_and_reports_policy() {
+        let (ui_gateway, _, ui_gateway_recording_arc) = make_recorder();
+        let system = System::new("test");
+        let subject = Daemon::new(Box::new(LauncherMock::new()));
+        let subject_addr = subject.start();
+        subject_addr
+            .try_send(make_bind_message(ui_gateway))
+            .unwrap();
+
+        subject_addr
+            .try_send(NodeFromUiMessage {
+                client_id: 1234,
+                body: UiRestartPolicyRequest {
+                    policy: Some(make_restart_policy(5)),
+                }
+                .tmb(4321),
+            })
+            .unwrap();
+        subject_addr
+            .try_send(NodeFromUiMessage {
+                client_id: 2345,
+                body: UiRestartPolicyRequest { policy: None }.tmb(5432),
+            })
+            .unwrap();
+
+        System::current().stop();
+        system.run();
+        let ui_gateway_recording = ui_gateway_recording_arc.lock().unwrap();
+        let record = ui_gateway_recording.get_record::<NodeToUiMessage>(0);
+        assert_eq!(record.target, ClientId(1234));
+        assert_eq!(
+            UiRestartPolicyResponse::fmb(record.body.clone()).unwrap(),
+            (
+                UiRestartPolicyResponse {
+                    policy: make_restart_policy(5)
+                },
+                4321
+            )
+        );
+        let record = ui_gateway_recording.get_record::<NodeToUiMessage>(1);
+        assert_eq!(record.target, ClientId(2345));
+        assert_eq!(
+            UiRestartPolicyResponse::fmb(record.body.clone()).unwrap(),
+            (
+                UiRestartPolicyResponse {
+                    policy: make_restart_policy(5)
+                },
+                5432
+            )
+        );
+    }
+
+    #[test]
+    fn restart_policy_request_rejects_inconsistent_policy() {
+        let (ui_gateway, _, ui_gateway_recording_arc) = make_recorder();
+        let system = System::new("test");
+        let mut subject = make_crashed_subject(LauncherMock::new(), ui_gateway);
+        let mut policy = make_restart_policy(5);
+        policy.initial_backoff_ms = 5_000;
+
+        subject.handle_restart_policy_request(
+            1234,
+            4321,
+            UiRestartPolicyRequest {
+                policy: Some(policy),
+            },
+        );
+
+        System::current().stop();
+        system.run();
+        assert_eq!(subject.restart_policy, default_restart_policy());
+        let ui_gateway_recording = ui_gateway_recording_arc.lock().unwrap();
+        let record = ui_gateway_recording.get_record::<NodeToUiMessage>(0);
+        assert_eq!(
+            record.body,
+            MessageBody {
+                opcode: "restartPolicy".to_string(),
+                path: MessagePath::Conversation(4321),
+                payload: Err((
+                    RESTART_POLICY_ERROR,
+                    "initialBackoffMs (5000) cannot be greater than maxBackoffMs (3000)"
+                        .to_string()
+                )),
+            }
+        );
+    }
+
+    #[test]
+    fn validate_restart_policy_requires_a_crash_loop_window_only_when_enabled() {
+        let mut policy = make_restart_policy(0);
+        policy.crash_loop_window_secs = 0;
+
+        let disabled_result = Daemon::validate_restart_policy(&policy);
+        policy.max_restarts = 1;
+        let enabled_result = Daemon::validate_restart_policy(&policy);
+
+        assert_eq!(disabled_result, Ok(()));
+        assert_eq!(
+            enabled_result,
+            Err("crashLoopWindowSecs must be greater than zero".to_string())
+        );
+    }
+
+    #[test]
+    fn crash_history_request_returns_recorded_crashes() {
+        let (ui_gateway, _, ui_gateway_recording_arc) = make_recorder();
+        let system = System::new("test");
+        let mut subject = make_crashed_subject(LauncherMock::new(), ui_gateway);
+        (0..(MAX_CRASH_HISTORY_LEN as u32 + 1)).for_each(|process_id| {
+            subject.record_crash(
+                DEFAULT_INSTANCE_NAME,
+                process_id,
+                CrashReason::NoInformation,
+                RestartOutcome::NotAttempted,
+            )
+        });
+
+        subject.handle_crash_history_request(1234, 4321);
+
+        System::current().stop();
+        system.run();
+        let ui_gateway_recording = ui_gateway_recording_arc.lock().unwrap();
+        let record = ui_gateway_recording.get_record::<NodeToUiMessage>(0);
+        assert_eq!(record.target, ClientId(1234));
+        let (response, context_id) = UiCrashHistoryResponse::fmb(record.body.clone()).unwrap();
+        assert_eq!(context_id, 4321);
+        assert_eq!(response.crashes.len(), MAX_CRASH_HISTORY_LEN);
+        let first = &response.crashes[0];
+        assert_eq!(
+            first,
+            &UiCrashRecord {
+                instance: DEFAULT_INSTANCE_NAME.to_string(),
+                process_id: 1,
+                crash_reason: CrashReason::NoInformation,
+                timestamp: first.timestamp,
+                outcome: RestartOutcome::NotAttempted,
+            }
+        );
+        assert!(first.timestamp > 0);
+        assert_eq!(
+            response.crashes.last().unwrap().process_id,
+            MAX_CRASH_HISTORY_LEN as u32
+        );
+    }
+
+    #[test]
+    fn crash_history_request_is_answered_by_daemon_even_when_node_is_running() {
+        let (ui_gateway, _, ui_gateway_recording_arc) = make_recorder();
+        let system = System::new("test");
+        let mut subject = Daemon::new(Box::new(LauncherMock::new()));
+        subject.verifier_tools = Box::new(VerifierToolsMock::new());
+        subject.instance_mut(DEFAULT_INSTANCE_NAME).node_process_id = Some(12345);
+        subject.instance_mut(DEFAULT_INSTANCE_NAME).node_ui_port = Some(7777);
+        let subject_addr = subject.start();
+        subject_addr
+            .try_send(make_bind_message(ui_gateway))
+            .unwrap();
+
+        subject_addr
+            .try_send(NodeFromUiMessage {
+                client_id: 1234,
+                body: UiCrashHistoryRequest {}.tmb(4321),
+            })
+            .unwrap();
+
+        System::current().stop();
+        system.run();
+        let ui_gateway_recording = ui_gateway_recording_arc.lock().unwrap();
+        let record = ui_gateway_recording.get_record::<NodeToUiMessage>(0);
+        assert_eq!(
+            UiCrashHistoryResponse::fmb(record.body.clone()).unwrap(),
+            (UiCrashHistoryResponse { crashes: vec![] }, 4321)
+        );
+    }
+
+    fn make_profile_store(test_name: &str) -> SetupProfileStoreReal {
+        let home_dir = ensure_node_home_directory_exists("daemon", test_name);
+        SetupProfileStoreReal::new(Some(home_dir.join("setup-profiles")))
+    }
+
+    fn reopen_profile_store(test_name: &str) -> SetupProfileStoreReal {
+        SetupProfileStoreReal::new(Some(
+            node_home_directory("daemon", test_name).join("setup-profiles"),
+        ))
+    }
+
+    #[test]
+    fn save_setup_profile_saves_set_values_but_not_sensitive_ones() {
+        let (ui_gateway, _, ui_gateway_recording_arc) = make_recorder();
+        let system = System::new("test");
+        let test_name = "save_setup_profile_saves_set_values_but_not_sensitive_ones";
+        let mut subject = make_crashed_subject(LauncherMock::new(), ui_gateway);
+        subject.profile_store = Box::new(make_profile_store(test_name));
+        subject.instance_mut(DEFAULT_INSTANCE_NAME).params = make_setup_cluster(vec![
+            ("chain", "mainnet", Set),
+            ("db-password", "secret value", Set),
+            ("consuming-private-key", "another secret value", Set),
+            ("log-level", "debug", Configured),
+            (
+                "neighborhood-mode",
+                "standard",
+                UiSetupResponseValueStatus::Default,
+            ),
+        ]);
+
+        subject.handle_save_setup_profile(
+            1234,
+            4321,
+            UiSaveSetupProfileRequest {
+                name: "mainnet".to_string(),
+            },
+        );
+
+        System::current().stop();
+        system.run();
+        let ui_gateway_recording = ui_gateway_recording_arc.lock().unwrap();
+        let record = ui_gateway_recording.get_record::<NodeToUiMessage>(0);
+        assert_eq!(record.target, ClientId(1234));
+        assert_eq!(
+            UiSaveSetupProfileResponse::fmb(record.body.clone()).unwrap(),
+            (
+                UiSaveSetupProfileResponse {
+                    excluded: vec![
+                        "consuming-private-key".to_string(),
+                        "db-password".to_string()
+                    ]
+                },
+                4321
+            )
+        );
+        assert_eq!(
+            reopen_profile_store(test_name).load("mainnet"),
+            Ok(vec![("chain".to_string(), "mainnet".to_string())]
+                .into_iter()
+                .collect())
+        );
+    }
+
+    #[test]
+    fn load_setup_profile_replaces_set_values_and_keeps_sensitive_ones() {
+        let (ui_gateway, _, ui_gateway_recording_arc) = make_recorder();
+        let system = System::new("test");
+        let store =
+            make_profile_store("load_setup_profile_replaces_set_values_and_keeps_sensitive_ones");
+        store
+            .save(
+                "mainnet",
+                &vec![("chain".to_string(), "mainnet".to_string())]
+                    .into_iter()
+                    .collect(),
+            )
+            .unwrap();
+        let get_modified_setup_params_arc = Arc::new(Mutex::new(vec![]));
+        let loaded_setup = make_setup_cluster(vec![
+            ("chain", "mainnet", Set),
+            ("db-password", "secret value", Set),
+        ]);
+        let setup_reporter = SetupReporterMock::new()
+            .get_modified_setup_params(&get_modified_setup_params_arc)
+            .get_modified_setup_result(Ok(loaded_setup));
+        let mut subject = make_crashed_subject(LauncherMock::new(), ui_gateway);
+        subject.profile_store = Box::new(store);
+        subject.setup_reporter = Box::new(setup_reporter);
+        subject.instance_mut(DEFAULT_INSTANCE_NAME).params = make_setup_cluster(vec![
+            ("chain", "ropsten", Set),
+            ("db-password", "secret value", Set),
+            ("log-level", "trace", Set),
+        ]);
+
+        subject.handle_load_setup_profile(
+            1234,
+            4321,
+            UiLoadSetupProfileRequest {
+                name: "mainnet".to_string(),
+            },
+        );
+
+        System::current().stop();
+        system.run();
+        let get_modified_setup_params = get_modified_setup_params_arc.lock().unwrap();
+        assert_eq!(
+            get_modified_setup_params[0].1,
+            vec![
+                UiSetupRequestValue::new("chain", "mainnet"),
+                UiSetupRequestValue::clear("log-level"),
+            ]
+        );
+        let ui_gateway_recording = ui_gateway_recording_arc.lock().unwrap();
+        let record = ui_gateway_recording.get_record::<NodeToUiMessage>(0);
+        assert_eq!(record.target, ClientId(1234));
+        assert_eq!(
+            UiLoadSetupProfileResponse::fmb(record.body.clone()).unwrap(),
+            (
+                UiLoadSetupProfileResponse {
+                    values: vec![
+                        UiSetupResponseValue::new("chain", "mainnet", Set),
+                        UiSetupResponseValue::new("db-password", "****************", Set),
+                    ],
+                    errors: vec![],
+                },
+                4321
+            )
+        );
+        let record = ui_gateway_recording.get_record::<NodeToUiMessage>(1);
+        assert_eq!(record.target, AllExcept(1234));
+        assert_eq!(
+            UiSetupBroadcast::fmb(record.body.clone()).unwrap().0.values[0],
+            UiSetupResponseValue::new("chain", "mainnet", Set)
+        );
+    }
+
+    #[test]
+    fn load_setup_profile_is_rejected_while_node_is_running() {
+        let (ui_gateway, _, ui_gateway_recording_arc) = make_recorder();
+        let system = System::new("test");
+        let mut subject = make_crashed_subject(LauncherMock::new(), ui_gateway);
+        subject.verifier_tools = Box::new(VerifierToolsMock::new().process_is_running_result(true));
+        subject.instance_mut(DEFAULT_INSTANCE_NAME).node_process_id = Some(12345);
+        subject.instance_mut(DEFAULT_INSTANCE_NAME).node_ui_port = Some(7777);
+
+        subject.handle_load_setup_profile(
+            1234,
+            4321,
+            UiLoadSetupProfileRequest {
+                name: "mainnet".to_string(),
+            },
+        );
+
+        System::current().stop();
+        system.run();
+        let ui_gateway_recording = ui_gateway_recording_arc.lock().unwrap();
+        let record = ui_gateway_recording.get_record::<NodeToUiMessage>(0);
+        assert_eq!(
+            record.body.payload,
+            Err((
+                NODE_ALREADY_RUNNING_ERROR,
+                "Cannot load a setup profile while the Node is running".to_string()
+            ))
+        );
+    }
+
+    #[test]
+    fn load_setup_profile_reports_missing_profile() {
+        let (ui_gateway, _, ui_gateway_recording_arc) = make_recorder();
+        let system = System::new("test");
+        let mut subject = make_crashed_subject(LauncherMock::new(), ui_gateway);
+        subject.profile_store = Box::new(make_profile_store(
+            "load_setup_profile_reports_missing_profile",
+        ));
+
+        subject.handle_load_setup_profile(
+            1234,
+            4321,
+            UiLoadSetupProfileRequest {
+                name: "testnet".to_string(),
+            },
+        );
+
+        System::current().stop();
+        system.run();
+        let ui_gateway_recording = ui_gateway_recording_arc.lock().unwrap();
+        let record = ui_gateway_recording.get_record::<NodeToUiMessage>(0);
+        assert_eq!(
+            record.body,
+            MessageBody {
+                opcode: "loadSetupProfile".to_string(),
+                path: MessagePath::Conversation(4321),
+                payload: Err((
+                    SETUP_PROFILE_ERROR,
+                    "No setup profile named 'testnet'".to_string()
+                )),
+            }
+        );
+    }
+
+    #[test]
+    fn setup_profiles_can_be_listed_and_deleted_through_messages() {
+        let (ui_gateway, _, ui_gateway_recording_arc) = make_recorder();
+        let system = System::new("test");
+        let store = make_profile_store("setup_profiles_can_be_listed_and_deleted_through_messages");
+        store.save("mainnet", &BTreeMap::new()).unwrap();
+        store.save("testnet", &BTreeMap::new()).unwrap();
+        let mut subject = Daemon::new(Box::new(LauncherMock::new()));
+        subject.profile_store = Box::new(store);
+        let subject_addr = subject.start();
+        subject_addr
+            .try_send(make_bind_message(ui_gateway))
+            .unwrap();
+
+        subject_addr
+            .try_send(NodeFromUiMessage {
+                client_id: 1234,
+                body: UiDeleteSetupProfileRequest {
+                    name: "testnet".to_string(),
+                }
+                .tmb(1),
+            })
+            .unwrap();
+        subject_addr
+            .try_send(NodeFromUiMessage {
+                client_id: 1234,
+                body: UiSetupProfilesRequest {}.tmb(2),
+            })
+            .unwrap();
+
+        System::current().stop();
+        system.run();
+        let ui_gateway_recording = ui_gateway_recording_arc.lock().unwrap();
+        let record = ui_gateway_recording.get_record::<NodeToUiMessage>(0);
+        assert_eq!(
+            UiDeleteSetupProfileResponse::fmb(record.body.clone()).unwrap(),
+            (UiDeleteSetupProfileResponse {}, 1)
+        );
+        let record = ui_gateway_recording.get_record::<NodeToUiMessage>(1);
+        assert_eq!(
+            UiSetupProfilesResponse::fmb(record.body.clone()).unwrap(),
+            (
+                UiSetupProfilesResponse {
+                    profiles: vec!["mainnet".to_string()]
+                },
+                2
+            )
+        );
+    }
+
+    #[test]
+    fn selecting_an_instance_creates_it_and_instances_are_listed() {
+        let (ui_gateway, _, ui_gateway_recording_arc) = make_recorder();
+        let system = System::new("test");
+        let mut subject = Daemon::new(Box::new(LauncherMock::new()));
+        subject.verifier_tools = Box::new(VerifierToolsMock::new().process_is_running_result(true));
+        subject.instance_mut(DEFAULT_INSTANCE_NAME).params =
+            setup_cluster_from(vec![("chain", "mainnet", Set)]);
+        subject.instance_mut(DEFAULT_INSTANCE_NAME).node_process_id = Some(12345);
+        subject.instance_mut(DEFAULT_INSTANCE_NAME).node_ui_port = Some(5333);
+        let subject_addr = subject.start();
+        subject_addr
+            .try_send(make_bind_message(ui_gateway))
+            .unwrap();
+        let select = |name: &str, context_id: u64| NodeFromUiMessage {
+            client_id: 1234,
+            body: UiSelectInstanceRequest {
+                name: name.to_string(),
+            }
+            .tmb(context_id),
+        };
+
+        subject_addr.try_send(select("testnet", 1)).unwrap();
+        subject_addr.try_send(select("testnet", 2)).unwrap();
+        subject_addr
+            .try_send(NodeFromUiMessage {
+                client_id: 1234,
+                body: UiInstancesRequest {}.tmb(3),
+            })
+            .unwrap();
+
+        System::current().stop();
+        system.run();
+        let ui_gateway_recording = ui_gateway_recording_arc.lock().unwrap();
+        let record = ui_gateway_recording.get_record::<NodeToUiMessage>(0);
+        assert_eq!(
+            UiSelectInstanceResponse::fmb(record.body.clone()).unwrap(),
+            (UiSelectInstanceResponse { created: true }, 1)
+        );
+        let record = ui_gateway_recording.get_record::<NodeToUiMessage>(1);
+        assert_eq!(
+            UiSelectInstanceResponse::fmb(record.body.clone()).unwrap(),
+            (UiSelectInstanceResponse { created: false }, 2)
+        );
+        let record = ui_gateway_recording.get_record::<NodeToUiMessage>(2);
+        assert_eq!(record.target, ClientId(1234));
+        assert_eq!(
+            UiInstancesResponse::fmb(record.body.clone()).unwrap(),
+            (
+                UiInstancesResponse {
+                    selected: "testnet".to_string(),
+                    instances: vec![
+                        UiNodeInstance {
+                            name: "default".to_string(),
+                            running: true,
+                            process_id_opt: Some(12345),
+                            redirect_ui_port_opt: Some(5333),
+                            chain_opt: Some("mainnet".to_string()),
+                            data_directory_opt: None,
+                        },
+                        UiNodeInstance {
+                            name: "testnet".to_string(),
+                            running: false,
+                            process_id_opt: None,
+                            redirect_ui_port_opt: None,
+                            chain_opt: None,
+                            data_directory_opt: None,
+                        },
+                    ]
+                },
+                3
+            )
+        );
+    }
+
+    #[test]
+    fn selecting_an_instance_with_a_bad_name_is_refused() {
+        let (ui_gateway, _, ui_gateway_recording_arc) = make_recorder();
+        let system = System::new("test");
+        let mut subject = Daemon::new(Box::new(LauncherMock::new()));
+        subject.ui_gateway_sub = Some(ui_gateway.start().recipient());
+
+        subject.handle_select_instance(
+            1234,
+            4321,
+            UiSelectInstanceRequest {
+                name: "../booga".to_string(),
+            },
+        );
+
+        System::current().stop();
+        system.run();
+        assert_eq!(subject.instances.len(), 1);
+        assert_eq!(subject.selected_instance(1234), DEFAULT_INSTANCE_NAME);
+        let ui_gateway_recording = ui_gateway_recording_arc.lock().unwrap();
+        let record = ui_gateway_recording.get_record::<NodeToUiMessage>(0);
+        assert_eq!(
+            record.body,
+            MessageBody {
+                opcode: "selectInstance".to_string(),
+                path: MessagePath::Conversation(4321),
+                payload: Err((
+                    NODE_INSTANCE_ERROR,
+                    "Node instance name '../booga' may contain only letters, digits, '-', and '_'"
+                        .to_string()
+                )),
+            }
+        );
+    }
+
+    #[test]
+    fn setup_and_start_act_on_the_selected_instance_only() {
+        let (ui_gateway, _, ui_gateway_recording_arc) = make_recorder();
+        let system = System::new("test");
+        let launch_params_arc = Arc::new(Mutex::new(vec![]));
+        let launcher = LauncherMock::new()
+            .launch_params(&launch_params_arc)
+            .launch_result(Ok(Some(LaunchSuccess {
+                new_process_id: 2222,
+                redirect_ui_port: 5444,
+            })));
+        let (daemon, _, _) = make_recorder();
+        let mut subject = Daemon::new(Box::new(launcher));
+        subject.ui_gateway_sub = Some(ui_gateway.start().recipient());
+        subject.crash_notification_sub = Some(daemon.start().recipient());
+        subject.verifier_tools = Box::new(VerifierToolsMock::new());
+        subject.setup_reporter = Box::new(SetupReporterMock::new().get_modified_setup_result(Ok(
+            setup_cluster_from(vec![("data-directory", "/home/testnet", Set)]),
+        )));
+        subject.handle_select_instance(
+            1234,
+            1,
+            UiSelectInstanceRequest {
+                name: "testnet".to_string(),
+            },
+        );
+
+        subject.handle_setup(
+            1234,
+            2,
+            UiSetupRequest::new(vec![("data-directory", Some("/home/testnet"))]),
+        );
+        subject.handle_start_order(1234, 3);
+
+        System::current().stop();
+        system.run();
+        assert!(subject.instance(DEFAULT_INSTANCE_NAME).params.is_empty());
+        assert_eq!(
+            subject.instance(DEFAULT_INSTANCE_NAME).node_process_id,
+            None
+        );
+        let testnet = subject.instance("testnet");
+        assert_eq!(testnet.node_process_id, Some(2222));
+        assert_eq!(testnet.node_ui_port, Some(5444));
+        let launch_params = launch_params_arc.lock().unwrap();
+        assert_eq!(
+            launch_params[0].0,
+            HashMap::from_iter(vec![(
+                "data-directory".to_string(),
+                "/home/testnet".to_string()
+            )])
+        );
+        let ui_gateway_recording = ui_gateway_recording_arc.lock().unwrap();
+        let record = ui_gateway_recording.get_record::<NodeToUiMessage>(2);
+        assert_eq!(
+            UiStartResponse::fmb(record.body.clone()).unwrap(),
+            (
+                UiStartResponse {
+                    new_process_id: 2222,
+                    redirect_ui_port: 5444,
+                },
+                3
+            )
+        );
+    }
+
+    #[test]
+    fn start_order_is_refused_while_another_instance_is_running() {
+        let (ui_gateway, _, ui_gateway_recording_arc) = make_recorder();
+        let system = System::new("test");
+        let launch_params_arc = Arc::new(Mutex::new(vec![]));
+        let launcher = LauncherMock::new().launch_params(&launch_params_arc);
+        let mut subject = Daemon::new(Box::new(launcher));
+        subject.ui_gateway_sub = Some(ui_gateway.start().recipient());
+        subject.verifier_tools = Box::new(VerifierToolsMock::new().process_is_running_result(true));
+        let default = subject.instance_mut(DEFAULT_INSTANCE_NAME);
+        default.node_process_id = Some(12345);
+        default.node_ui_port = Some(5333);
+        subject.instances.insert(
+            "testnet".to_string(),
+            NodeInstance {
+                params: setup_cluster_from(vec![("data-directory", "/home/testnet", Set)]),
+                ..NodeInstance::default()
+            },
+        );
+        subject.client_instances.insert(1234, "testnet".to_string());
+
+        subject.handle_start_order(1234, 4321);
+
+        System::current().stop();
+        system.run();
+        assert!(launch_params_arc.lock().unwrap().is_empty());
+        let ui_gateway_recording = ui_gateway_recording_arc.lock().unwrap();
+        let record = ui_gateway_recording.get_record::<NodeToUiMessage>(0);
+        assert_eq!(
+            record.body,
+            MessageBody {
+                opcode: "start".to_string(),
+                path: MessagePath::Conversation(4321),
+                payload: Err((
+                    NODE_INSTANCE_ERROR,
+                    "Could not launch Node: instance 'default' is already running, and only one Node at a time can use ports 80, 443 and 53"
+                        .to_string()
+                )),
+            }
+        );
+    }
+
+    #[test]
+    fn restart_node_gives_up_while_another_instance_is_running() {
+        let (ui_gateway, _, ui_gateway_recording_arc) = make_recorder();
+        let system = System::new("test");
+        let launch_params_arc = Arc::new(Mutex::new(vec![]));
+        let launcher = LauncherMock::new().launch_params(&launch_params_arc);
+        let mut subject = make_crashed_subject(launcher, ui_gateway);
+        subject.verifier_tools = Box::new(VerifierToolsMock::new().process_is_running_result(true));
+        subject.instances.insert(
+            "testnet".to_string(),
+            NodeInstance {
+                node_process_id: Some(2222),
+                node_ui_port: Some(5444),
+                ..NodeInstance::default()
+            },
+        );
+        subject.record_crash(
+            DEFAULT_INSTANCE_NAME,
+            12345,
+            CrashReason::NoInformation,
+            RestartOutcome::Restarting,
+        );
+
+        subject.restart_node(DEFAULT_INSTANCE_NAME);
+
+        System::current().stop();
+        system.run();
+        assert!(launch_params_arc.lock().unwrap().is_empty());
+        assert_eq!(subject.crash_history[0].outcome, RestartOutcome::Abandoned);
+        let ui_gateway_recording = ui_gateway_recording_arc.lock().unwrap();
+        let record = ui_gateway_recording.get_record::<NodeToUiMessage>(0);
+        assert_eq!(
+            UiNodeRestartAbandonedBroadcast::fmb(record.body.clone())
+                .unwrap()
+                .0,
+            UiNodeRestartAbandonedBroadcast {
+                process_id: 12345,
+                reason: "Could not relaunch Node: instance 'testnet' is already running, and only one Node at a time can use ports 80, 443 and 53".to_string(),
+            }
+        );
+    }
+
+    #[test]
+    fn redirects_and_crash_broadcasts_follow_the_selected_instance() {
+        let (ui_gateway, _, ui_gateway_recording_arc) = make_recorder();
+        let system = System::new("test");
+        let mut subject = Daemon::new(Box::new(LauncherMock::new()));
+        subject.ui_gateway_sub = Some(ui_gateway.start().recipient());
+        subject.verifier_tools = Box::new(VerifierToolsMock::new().process_is_running_result(true));
+        subject.instances.insert(
+            "testnet".to_string(),
+            NodeInstance {
+                node_process_id: Some(2222),
+                node_ui_port: Some(5444),
+                ..NodeInstance::default()
+            },
+        );
+        subject.client_instances.insert(1234, "testnet".to_string());
+        subject.client_instances.insert(2345, "testnet".to_string());
+        subject.client_instances.insert(3456, "other".to_string());
+
+        subject.handle_unexpected_message(1234, UiShutdownRequest {}.tmb(4321));
+        let result = subject.handle_crash_notification(make_crash(2222, Some(1)));
+
+        System::current().stop();
+        system.run();
+        assert_eq!(result, None);
+        assert_eq!(subject.instance("testnet").node_process_id, None);
+        assert_eq!(subject.crash_history[0].instance, "testnet".to_string());
+        let ui_gateway_recording = ui_gateway_recording_arc.lock().unwrap();
+        assert_eq!(ui_gateway_recording.len(), 3);
+        let record = ui_gateway_recording.get_record::<NodeToUiMessage>(0);
+        assert_eq!(record.target, ClientId(1234));
+        assert_eq!(UiRedirect::fmb(record.body.clone()).unwrap().0.port, 5444);
+        let crash_broadcast = UiNodeCrashedBroadcast {
+            process_id: 2222,
+            crash_reason: CrashReason::Unrecognized("Standard error".to_string()),
+        }
+        .tmb(0);
+        let record = ui_gateway_recording.get_record::<NodeToUiMessage>(1);
+        assert_eq!(
+            (record.target.clone(), record.body.clone()),
+            (ClientId(1234), crash_broadcast.clone())
+        );
+        let record = ui_gateway_recording.get_record::<NodeToUiMessage>(2);
+        assert_eq!(
+            (record.target.clone(), record.body.clone()),
+            (ClientId(2345), crash_broadcast)
+        );
+    }
+
+    #[test]
+    fn status_request_is_answered_by_daemon_with_last_crash_when_node_is_down() {
+        let (ui_gateway, _, ui_gateway_recording_arc) = make_recorder();
+        let system = System::new("test");
+        let mut subject = Daemon::new(Box::new(LauncherMock::new()));
+        subject.ui_gateway_sub = Some(ui_gateway.start().recipient());
+        subject.instance_mut(DEFAULT_INSTANCE_NAME).params = setup_cluster_from(vec![
+            ("chain", "ropsten", Set),
+            ("neighborhood-mode", "zero-hop", Configured),
+            ("ip", "", Blank),
+        ]);
+        subject.record_crash(
+            DEFAULT_INSTANCE_NAME,
+            1111,
+            CrashReason::NoInformation,
+            RestartOutcome::NotAttempted,
+        );
+        subject.record_crash(
+            DEFAULT_INSTANCE_NAME,
+            2222,
+            CrashReason::Unrecognized("Out of memory".to_string()),
+            RestartOutcome::NotAttempted,
+        );
+        subject.record_crash(
+            "other",
+            3333,
+            CrashReason::NoInformation,
+            RestartOutcome::NotAttempted,
+        );
+
+        subject.handle_status_request(1234, 4321, UiStatusRequest {}.tmb(4321));
+
+        System::current().stop();
+        system.run();
+        let ui_gateway_recording = ui_gateway_recording_arc.lock().unwrap();
+        let record = ui_gateway_recording.get_record::<NodeToUiMessage>(0);
+        assert_eq!(record.target, ClientId(1234));
+        assert_eq!(
+            UiStatusResponse::fmb(record.body.clone()).unwrap(),
+            (
+                UiStatusResponse {
+                    running: false,
+                    version: env!("CARGO_PKG_VERSION").to_string(),
+                    process_id_opt: None,
+                    uptime_secs_opt: None,
+                    chain_opt: Some("ropsten".to_string()),
+                    neighborhood_mode_opt: Some("zero-hop".to_string()),
+                    ui_port_opt: None,
+                    last_crash_reason_opt: Some(CrashReason::Unrecognized(
+                        "Out of memory".to_string()
+                    )),
+                },
+                4321
+            )
+        );
+    }
+
+    #[test]
+    fn status_request_is_redirected_to_node_when_node_is_running() {
+        let (ui_gateway, _, ui_gateway_recording_arc) = make_recorder();
+        let system = System::new("test");
+        let mut subject = Daemon::new(Box::new(LauncherMock::new()));
+        subject.ui_gateway_sub = Some(ui_gateway.start().recipient());
+        subject.verifier_tools = Box::new(VerifierToolsMock::new().process_is_running_result(true));
+        subject.instance_mut(DEFAULT_INSTANCE_NAME).node_process_id = Some(12345);
+        subject.instance_mut(DEFAULT_INSTANCE_NAME).node_ui_port = Some(5333);
+
+        subject.handle_status_request(1234, 4321, UiStatusRequest {}.tmb(4321));
+
+        System::current().stop();
+        system.run();
+        let ui_gateway_recording = ui_gateway_recording_arc.lock().unwrap();
+        let record = ui_gateway_recording.get_record::<NodeToUiMessage>(0);
+        assert_eq!(record.target, ClientId(1234));
+        assert_eq!(
+            UiRedirect::fmb(record.body.clone()).unwrap().0,
+            UiRedirect {
+                port: 5333,
+                opcode: "status".to_string(),
+                context_id: Some(4321),
+                payload: "{}".to_string(),
+            }
+        );
+    }
 }
@@ -0,0 +1,252 @@
+// Copyright (c) 2019-2020, MASQ (https://masq.ai) and/or its affiliates. All rights reserved.
+
+use crate::node_configurator::{DirsWrapper, RealDirsWrapper};
+use std::collections::BTreeMap;
+use std::fs;
+use std::io::ErrorKind;
+use std::path::PathBuf;
+
+pub const SETUP_PROFILES_DIRECTORY: &str = "setup-profiles";
+pub const MAX_PROFILE_NAME_LEN: usize = 64;
+const PROFILE_EXTENSION: &str = "json";
+
+pub trait SetupProfileStore {
+    fn save(&self, name: &str, values: &BTreeMap<String, String>) -> Result<(), String>;
+    fn load(&self, name: &str) -> Result<BTreeMap<String, String>, String>;
+    fn list(&self) -> Result<Vec<String>, String>;
+    fn delete(&self, name: &str) -> Result<(), String>;
+}
+
+// Each profile is a JSON object of parameter names and values, kept in its own file so that
+// operators can inspect, copy, or remove profiles by hand.
+pub struct SetupProfileStoreReal {
+    directory_opt: Option<PathBuf>,
+}
+
+impl SetupProfileStore for SetupProfileStoreReal {
+    fn save(&self, name: &str, values: &BTreeMap<String, String>) -> Result<(), String> {
+        let path = self.profile_path(name)?;
+        let directory = self.directory()?;
+        fs::create_dir_all(directory)
+            .map_err(|e| format!("Couldn't create {}: {}", directory.display(), e))?;
+        let json = serde_json::to_string_pretty(values).expect("Serialization failed");
+        fs::write(&path, json).map_err(|e| format!("Couldn't write {}: {}", path.display(), e))
+    }
+
+    fn load(&self, name: &str) -> Result<BTreeMap<String, String>, String> {
+        let path = self.profile_path(name)?;
+        match fs::read_to_string(&path) {
+            Ok(json) => serde_json::from_str(&json)
+                .map_err(|e| format!("Profile '{}' is corrupt: {}", name, e)),
+            Err(e) if e.kind() == ErrorKind::NotFound => {
+                Err(format!("No setup profile named '{}'", name))
+            }
+            Err(e) => Err(format!("Couldn't read {}: {}", path.display(), e)),
+        }
+    }
+
+    fn list(&self) -> Result<Vec<String>, String> {
+        let directory = self.directory()?;
+        let entries = match fs::read_dir(directory) {
+            Ok(entries) => entries,
+            Err(e) if e.kind() == ErrorKind::NotFound => return Ok(vec![]),
+            Err(e) => return Err(format!("Couldn't read {}: {}", directory.display(), e)),
+        };
+        let mut names = entries
+            .filter_map(|entry| entry.ok())
+            .map(|entry| entry.path())
+            .filter(|path| {
+                let extension_opt = path.extension().and_then(|ext| ext.to_str());
+                extension_opt == Some(PROFILE_EXTENSION)
+            })
+            .filter_map(|path| {
+                path.file_stem()
+                    .and_then(|stem| stem.to_str())
+                    .map(|stem| stem.to_string())
+            })
+            .filter(|name| validate_profile_name(name).is_ok())
+            .collect::<Vec<String>>();
+        names.sort();
+        Ok(names)
+    }
+
+    fn delete(&self, name: &str) -> Result<(), String> {
+        let path = self.profile_path(name)?;
+        match fs::remove_file(&path) {
+            Ok(()) => Ok(()),
+            Err(e) if e.kind() == ErrorKind::NotFound => {
+                Err(format!("No setup profile named '{}'", name))
+            }
+            Err(e) => Err(format!("Couldn't delete {}: {}", path.display(), e)),
+        }
+    }
+}
+
+impl Default for SetupProfileStoreReal {
+    fn default() -> Self {
+        Self::new(
+            RealDirsWrapper {}
+                .data_dir()
+                .map(|data_dir| data_dir.join("MASQ").join(SETUP_PROFILES_DIRECTORY)),
+        )
+    }
+}
+
+impl SetupProfileStoreReal {
+    pub fn new(directory_opt: Option<PathBuf>) -> Self {
+        Self { directory_opt }
+    }
+
+    fn directory(&self) -> Result<&PathBuf, String> {
+        self.directory_opt
+            .as_ref()
+            .ok_or_else(|| "No data directory is available for setup profiles".to_string())
+    }
+
+    fn profile_path(&self, name: &str) -> Result<PathBuf, String> {
+        validate_profile_name(name)?;
+        Ok(self
+            .directory()?
+            .join(format!("{}.{}", name, PROFILE_EXTENSION)))
+    }
+}
+
+pub fn validate_profile_name(name: &str) -> Result<(), String> {
+    if name.is_empty() || name.len() > MAX_PROFILE_NAME_LEN {
+        Err(format!(
+            "Setup profile names must be between 1 and {} characters long",
+            MAX_PROFILE_NAME_LEN
+        ))
+    } else if !name
+        .chars()
+        .all(|c| c.is_ascii_alphanumeric() || c == '-' || c == '_')
+    {
+        Err(format!(
+            "Setup profile name '{}' may contain only letters, digits, '-', and '_'",
+            name
+        ))
+    } else {
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use masq_lib::test_utils::utils::ensure_node_home_directory_exists;
+
+    fn make_values(pairs: Vec<(&str, &str)>) -> BTreeMap<String, String> {
+        pairs
+            .into_iter()
+            .map(|(name, value)| (name.to_string(), value.to_string()))
+            .collect()
+    }
+
+    #[test]
+    fn profiles_can_be_saved_loaded_listed_and_deleted() {
+        let home_dir = ensure_node_home_directory_exists(
+            "setup_profiles",
+            "profiles_can_be_saved_loaded_listed_and_deleted",
+        );
+        let subject = SetupProfileStoreReal::new(Some(home_dir.join("profiles")));
+        let mainnet = make_values(vec![("chain", "mainnet"), ("log-level", "info")]);
+        let testnet = make_values(vec![("chain", "ropsten")]);
+
+        subject.save("mainnet", &mainnet).unwrap();
+        subject.save("testnet", &testnet).unwrap();
+        fs::write(home_dir.join("profiles").join("stray.txt"), "not a profile").unwrap();
+
+        assert_eq!(
+            subject.list(),
+            Ok(vec!["mainnet".to_string(), "testnet".to_string()])
+        );
+        assert_eq!(subject.load("mainnet"), Ok(mainnet));
+        assert_eq!(subject.delete("testnet"), Ok(()));
+        assert_eq!(subject.list(), Ok(vec!["mainnet".to_string()]));
+        assert_eq!(
+            subject.load("testnet"),
+            Err("No setup profile named 'testnet'".to_string())
+        );
+        assert_eq!(
+            subject.delete("testnet"),
+            Err("No setup profile named 'testnet'".to_string())
+        );
+    }
+
+    #[test]
+    fn saving_over_an_existing_profile_replaces_it() {
+        let home_dir = ensure_node_home_directory_exists(
+            "setup_profiles",
+            "saving_over_an_existing_profile_replaces_it",
+        );
+        let subject = SetupProfileStoreReal::new(Some(home_dir));
+        let replacement = make_values(vec![("chain", "ropsten")]);
+
+        subject
+            .save("work", &make_values(vec![("log-level", "trace")]))
+            .unwrap();
+        subject.save("work", &replacement).unwrap();
+
+        assert_eq!(subject.load("work"), Ok(replacement));
+    }
+
+    #[test]
+    fn missing_directory_lists_no_profiles() {
+        let home_dir = ensure_node_home_directory_exists(
+            "setup_profiles",
+            "missing_directory_lists_no_profiles",
+        );
+        let subject = SetupProfileStoreReal::new(Some(home_dir.join("nonexistent")));
+
+        let result = subject.list();
+
+        assert_eq!(result, Ok(vec![]));
+    }
+
+    #[test]
+    fn corrupt_profile_is_reported() {
+        let home_dir =
+            ensure_node_home_directory_exists("setup_profiles", "corrupt_profile_is_reported");
+        fs::write(home_dir.join("broken.json"), "{ booga").unwrap();
+        let subject = SetupProfileStoreReal::new(Some(home_dir));
+
+        let result = subject.load("broken");
+
+        let msg = result.err().unwrap();
+        assert!(msg.starts_with("Profile 'broken' is corrupt: "), "{}", msg);
+    }
+
+    #[test]
+    fn store_without_directory_complains() {
+        let subject = SetupProfileStoreReal::new(None);
+
+        let result = subject.list();
+
+        assert_eq!(
+            result,
+            Err("No data directory is available for setup profiles".to_string())
+        );
+    }
+
+    #[test]
+    fn profile_names_are_validated() {
+        let too_long = "x".repeat(MAX_PROFILE_NAME_LEN + 1);
+
+        assert_eq!(validate_profile_name("main-net_2"), Ok(()));
+        assert_eq!(
+            validate_profile_name(""),
+            Err("Setup profile names must be between 1 and 64 characters long".to_string())
+        );
+        assert_eq!(
+            validate_profile_name(&too_long),
+            Err("Setup profile names must be between 1 and 64 characters long".to_string())
+        );
+        assert_eq!(
+            validate_profile_name("../etc/passwd"),
+            Err(
+                "Setup profile name '../etc/passwd' may contain only letters, digits, '-', and '_'"
+                    .to_string()
+            )
+        );
+    }
+}
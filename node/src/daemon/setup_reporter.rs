@@ -1,6 +1,6 @@
 // Copyright (c) 2019-2020, MASQ (https://masq.ai). All rights reserved.
 
-use crate::blockchain::blockchain_interface::{chain_id_from_name, chain_name_from_id};
+use crate::blockchain::blockchain_interface::chain_name_from_id;
 use crate::bootstrapper::BootstrapperConfig;
 use crate::database::db_initializer::{DbInitializer, DbInitializerReal};
 use crate::db_config::persistent_configuration::{
@@ -10,7 +10,8 @@ use crate::node_configurator::node_configurator_standard::standard::{
     privileged_parse_args, unprivileged_parse_args,
 };
 use crate::node_configurator::{
-    app_head, data_directory_from_context, determine_config_file_path, DirsWrapper, RealDirsWrapper,
+    app_head, chain_id_from_context, data_directory_from_context, determine_config_file_path,
+    DirsWrapper, RealDirsWrapper,
 };
 use crate::sub_lib::neighborhood::NodeDescriptor;
 use crate::sub_lib::utils::make_new_multi_config;
@@ -275,12 +276,8 @@ impl SetupReporterReal {
                 Ok(mc) => mc,
                 Err(ce) => return (HashMap::new(), Some(ce)),
             };
-        let ((bootstrapper_config, persistent_config_opt), error_opt) = Self::run_configuration(
-            dirs_wrapper,
-            &multi_config,
-            data_directory,
-            chain_id_from_name(chain_name),
-        );
+        let ((bootstrapper_config, persistent_config_opt), error_opt) =
+            Self::run_configuration(dirs_wrapper, &multi_config, data_directory, chain_name);
         if let Some(error) = error_opt {
             error_so_far.extend(error);
         }
@@ -394,7 +391,7 @@ impl SetupReporterReal {
         dirs_wrapper: &dyn DirsWrapper,
         multi_config: &MultiConfig,
         data_directory: &PathBuf,
-        chain_id: u8,
+        chain_name: &str,
     ) -> (
         (BootstrapperConfig, Option<Box<dyn PersistentConfiguration>>),
         Option<ConfiguratorError>,
@@ -418,6 +415,28 @@ impl SetupReporterReal {
                 error_so_far.extend(ce);
             }
         };
+        let chain_id = match chain_id_from_context(chain_name, data_directory) {
+            Ok(chain_id) => chain_id,
+            Err(ce) => {
+                // There's no database to open for a chain we don't know
+                if !error_so_far
+                    .param_errors
+                    .iter()
+                    .any(|param_error| param_error.parameter == "chain")
+                {
+                    error_so_far.extend(ce);
+                }
+                if let Err(ce) = unprivileged_parse_args(
+                    multi_config,
+                    &mut bootstrapper_config,
+                    &mut streams,
+                    None,
+                ) {
+                    error_so_far.extend(ce);
+                }
+                return ((bootstrapper_config, None), Some(error_so_far));
+            }
+        };
         let initializer = DbInitializerReal::new();
         match initializer.initialize(data_directory, chain_id, false) {
             Ok(conn) => {
@@ -507,6 +526,20 @@ impl ValueRetriever for BlockchainServiceUrl {
     }
 }
 
+struct BootstrapPublicKey {}
+impl ValueRetriever for BootstrapPublicKey {
+    fn value_name(&self) -> &'static str {
+        "bootstrap-public-key"
+    }
+}
+
+struct BootstrapSource {}
+impl ValueRetriever for BootstrapSource {
+    fn value_name(&self) -> &'static str {
+        "bootstrap-source"
+    }
+}
+
 struct Chain {}
 impl ValueRetriever for Chain {
     fn value_name(&self) -> &'static str {
@@ -599,7 +632,7 @@ impl ValueRetriever for DataDirectory {
                 self.dirs_wrapper.as_ref(),
                 &real_user,
                 &data_directory_opt,
-                chain_name,
+                &chain_name,
             )
             .to_string_lossy()
             .to_string(),
@@ -635,6 +668,60 @@ impl ValueRetriever for DbPassword {
     }
 }
 
+struct DebutApproval {}
+impl ValueRetriever for DebutApproval {
+    fn value_name(&self) -> &'static str {
+        "debut-approval"
+    }
+
+    fn computed_default(
+        &self,
+        _bootstrapper_config: &BootstrapperConfig,
+        _persistent_config_opt: &Option<Box<dyn PersistentConfiguration>>,
+        _db_password_opt: &Option<String>,
+    ) -> Option<(String, UiSetupResponseValueStatus)> {
+        Some(("automatic".to_string(), Default))
+    }
+
+    fn is_required(&self, _params: &SetupCluster) -> bool {
+        true
+    }
+}
+
+struct DnsBypass {}
+impl ValueRetriever for DnsBypass {
+    fn value_name(&self) -> &'static str {
+        "dns-bypass"
+    }
+}
+
+struct DnsBypassServers {}
+impl ValueRetriever for DnsBypassServers {
+    fn value_name(&self) -> &'static str {
+        "dns-bypass-servers"
+    }
+}
+
+struct DnsOtherRecords {}
+impl ValueRetriever for DnsOtherRecords {
+    fn value_name(&self) -> &'static str {
+        "dns-other-records"
+    }
+
+    fn computed_default(
+        &self,
+        _bootstrapper_config: &BootstrapperConfig,
+        _persistent_config_opt: &Option<Box<dyn PersistentConfiguration>>,
+        _db_password_opt: &Option<String>,
+    ) -> Option<(String, UiSetupResponseValueStatus)> {
+        Some(("empty".to_string(), Default))
+    }
+
+    fn is_required(&self, _params: &SetupCluster) -> bool {
+        true
+    }
+}
+
 struct DnsServers {}
 impl ValueRetriever for DnsServers {
     fn value_name(&self) -> &'static str {
@@ -662,6 +749,13 @@ impl ValueRetriever for EarningWallet {
     }
 }
 
+struct ExitCountry {}
+impl ValueRetriever for ExitCountry {
+    fn value_name(&self) -> &'static str {
+        "exit-country"
+    }
+}
+
 struct GasPrice {}
 impl ValueRetriever for GasPrice {
     fn value_name(&self) -> &'static str {
@@ -781,6 +875,28 @@ impl ValueRetriever for Neighbors {
     }
 }
 
+struct PaymentCurves {}
+impl ValueRetriever for PaymentCurves {
+    fn value_name(&self) -> &'static str {
+        "payment-curves"
+    }
+
+    fn computed_default(
+        &self,
+        bootstrapper_config: &BootstrapperConfig,
+        _persistent_config_opt: &Option<Box<dyn PersistentConfiguration>>,
+        _db_password_opt: &Option<String>,
+    ) -> Option<(String, UiSetupResponseValueStatus)> {
+        Some((
+            bootstrapper_config
+                .accountant_config
+                .payment_curves
+                .to_string(),
+            Default,
+        ))
+    }
+}
+
 struct RealUser {
     #[allow(dead_code)]
     dirs_wrapper: Box<dyn DirsWrapper>,
@@ -827,6 +943,8 @@ impl RealUser {
 fn value_retrievers(dirs_wrapper: &dyn DirsWrapper) -> Vec<Box<dyn ValueRetriever>> {
     vec![
         Box::new(BlockchainServiceUrl {}),
+        Box::new(BootstrapPublicKey {}),
+        Box::new(BootstrapSource {}),
         Box::new(Chain {}),
         Box::new(ClandestinePort {}),
         Box::new(ConfigFile {}),
@@ -834,13 +952,19 @@ fn value_retrievers(dirs_wrapper: &dyn DirsWrapper) -> Vec<Box<dyn ValueRetrieve
         Box::new(CrashPoint {}),
         Box::new(DataDirectory::new(dirs_wrapper)),
         Box::new(DbPassword {}),
+        Box::new(DebutApproval {}),
+        Box::new(DnsBypass {}),
+        Box::new(DnsBypassServers {}),
+        Box::new(DnsOtherRecords {}),
         Box::new(DnsServers {}),
         Box::new(EarningWallet {}),
+        Box::new(ExitCountry {}),
         Box::new(GasPrice {}),
         Box::new(Ip {}),
         Box::new(LogLevel {}),
         Box::new(NeighborhoodMode {}),
         Box::new(Neighbors {}),
+        Box::new(PaymentCurves {}),
         #[cfg(not(target_os = "windows"))]
         Box::new(RealUser::new(dirs_wrapper)),
     ]
@@ -850,8 +974,11 @@ fn value_retrievers(dirs_wrapper: &dyn DirsWrapper) -> Vec<Box<dyn ValueRetrieve
 mod tests {
     use super::*;
     use crate::blockchain::blockchain_interface::chain_id_from_name;
+    use crate::blockchain::chain_definitions::{
+        load_chain_definitions, register_chain_definitions, CHAIN_DEFINITIONS_FILE,
+    };
     use crate::bootstrapper::RealUser;
-    use crate::database::db_initializer::{DbInitializer, DbInitializerReal};
+    use crate::database::db_initializer::{DbInitializer, DbInitializerReal, DATABASE_FILE};
     use crate::db_config::persistent_configuration::{
         PersistentConfigError, PersistentConfiguration, PersistentConfigurationReal,
     };
@@ -862,7 +989,9 @@ mod tests {
     use crate::sub_lib::wallet::Wallet;
     use crate::test_utils::assert_string_contains;
     use crate::test_utils::persistent_configuration_mock::PersistentConfigurationMock;
+    use masq_lib::constants::DEFAULT_PAYMENT_CURVES;
     use masq_lib::messages::UiSetupResponseValueStatus::{Blank, Configured, Required, Set};
+    use masq_lib::shared_schema::ParamError;
     use masq_lib::test_utils::environment_guard::{ClapGuard, EnvironmentGuard};
     use masq_lib::test_utils::utils::{ensure_node_home_directory_exists, TEST_DEFAULT_CHAIN_NAME};
     #[cfg(not(target_os = "windows"))]
@@ -902,7 +1031,11 @@ mod tests {
         );
         let db_initializer = DbInitializerReal::new();
         let conn = db_initializer
-            .initialize(&home_dir, chain_id_from_name(DEFAULT_CHAIN_NAME), true)
+            .initialize(
+                &home_dir,
+                chain_id_from_name(DEFAULT_CHAIN_NAME).unwrap(),
+                true,
+            )
             .unwrap();
         let mut config = PersistentConfigurationReal::from(conn);
         config.change_password(None, "password").unwrap();
@@ -919,6 +1052,12 @@ mod tests {
             )
             .unwrap();
         config.set_gas_price(1234567890).unwrap();
+        config
+            .set_payment_curves(
+                &crate::accountant::PaymentCurves::from_str("1000|2000|3000|4000|5000|2500")
+                    .unwrap(),
+            )
+            .unwrap();
         let neighbor1 = NodeDescriptor {
             encryption_public_key: PublicKey::new(b"ABCD"),
             mainnet: true,
@@ -955,6 +1094,8 @@ mod tests {
 
         let expected_result = vec![
             ("blockchain-service-url", "", Required),
+            ("bootstrap-public-key", "", Blank),
+            ("bootstrap-source", "", Blank),
             ("chain", DEFAULT_CHAIN_NAME, Default),
             ("clandestine-port", "1234", Default),
             ("config-file", "config.toml", Default),
@@ -962,8 +1103,13 @@ mod tests {
             ("crash-point", "", Blank),
             ("data-directory", home_dir.to_str().unwrap(), Set),
             ("db-password", "password", Set),
+            ("debut-approval", "automatic", Default),
+            ("dns-bypass", "", Blank),
+            ("dns-bypass-servers", "", Blank),
+            ("dns-other-records", "empty", Default),
             ("dns-servers", "1.1.1.1", Default),
             ("earning-wallet", "", Blank),
+            ("exit-country", "", Blank),
             ("gas-price", "1234567890", Default),
             ("ip", "4.3.2.1", Set),
             ("log-level", "warn", Default),
@@ -973,6 +1119,7 @@ mod tests {
                 "QUJDRA@1.2.3.4:1234,RUZHSA@5.6.7.8:5678",
                 Configured,
             ),
+            ("payment-curves", "1000|2000|3000|4000|5000|2500", Default),
             #[cfg(not(target_os = "windows"))]
             (
                 "real-user",
@@ -1028,6 +1175,8 @@ mod tests {
 
         let expected_result = vec![
             ("blockchain-service-url", "https://example.com", Set),
+            ("bootstrap-public-key", "", Blank),
+            ("bootstrap-source", "", Blank),
             ("chain", TEST_DEFAULT_CHAIN_NAME, Set),
             ("clandestine-port", "1234", Set),
             ("config-file", "config.toml", Default),
@@ -1035,13 +1184,19 @@ mod tests {
             ("crash-point", "Message", Set),
             ("data-directory", home_dir.to_str().unwrap(), Set),
             ("db-password", "password", Set),
+            ("debut-approval", "automatic", Default),
+            ("dns-bypass", "", Blank),
+            ("dns-bypass-servers", "", Blank),
+            ("dns-other-records", "empty", Default),
             ("dns-servers", "8.8.8.8", Set),
             ("earning-wallet", "0x0123456789012345678901234567890123456789", Set),
+            ("exit-country", "", Blank),
             ("gas-price", "50", Set),
             ("ip", "4.3.2.1", Set),
             ("log-level", "error", Set),
             ("neighborhood-mode", "originate-only", Set),
             ("neighbors", "MTIzNDU2Nzg5MTEyMzQ1Njc4OTIxMjM0NTY3ODkzMTI:1.2.3.4:1234,MTIzNDU2Nzg5MTEyMzQ1Njc4OTIxMjM0NTY3ODkzMTI:5.6.7.8:5678", Set),
+            ("payment-curves", DEFAULT_PAYMENT_CURVES, Default),
             #[cfg(not(target_os = "windows"))]
             ("real-user", "9999:9999:booga", Set),
         ].into_iter()
@@ -1089,6 +1244,8 @@ mod tests {
 
         let expected_result = vec![
             ("blockchain-service-url", "https://example.com", Set),
+            ("bootstrap-public-key", "", Blank),
+            ("bootstrap-source", "", Blank),
             ("chain", TEST_DEFAULT_CHAIN_NAME, Set),
             ("clandestine-port", "1234", Set),
             ("config-file", "config.toml", Default),
@@ -1096,13 +1253,19 @@ mod tests {
             ("crash-point", "Message", Set),
             ("data-directory", home_dir.to_str().unwrap(), Set),
             ("db-password", "password", Set),
+            ("debut-approval", "automatic", Default),
+            ("dns-bypass", "", Blank),
+            ("dns-bypass-servers", "", Blank),
+            ("dns-other-records", "empty", Default),
             ("dns-servers", "8.8.8.8", Set),
             ("earning-wallet", "0x0123456789012345678901234567890123456789", Set),
+            ("exit-country", "", Blank),
             ("gas-price", "50", Set),
             ("ip", "4.3.2.1", Set),
             ("log-level", "error", Set),
             ("neighborhood-mode", "originate-only", Set),
             ("neighbors", "MTIzNDU2Nzg5MTEyMzQ1Njc4OTIxMjM0NTY3ODkzMTI:1.2.3.4:1234,MTIzNDU2Nzg5MTEyMzQ1Njc4OTIxMjM0NTY3ODkzMTI:5.6.7.8:5678", Set),
+            ("payment-curves", DEFAULT_PAYMENT_CURVES, Default),
             #[cfg(not(target_os = "windows"))]
             ("real-user", "9999:9999:booga", Set),
         ].into_iter()
@@ -1149,6 +1312,8 @@ mod tests {
 
         let expected_result = vec![
             ("blockchain-service-url", "https://example.com", Configured),
+            ("bootstrap-public-key", "", Blank),
+            ("bootstrap-source", "", Blank),
             ("chain", TEST_DEFAULT_CHAIN_NAME, Configured),
             ("clandestine-port", "1234", Configured),
             ("config-file", "config.toml", Default),
@@ -1156,13 +1321,19 @@ mod tests {
             ("crash-point", "Error", Configured),
             ("data-directory", home_dir.to_str().unwrap(), Configured),
             ("db-password", "password", Configured),
+            ("debut-approval", "automatic", Default),
+            ("dns-bypass", "", Blank),
+            ("dns-bypass-servers", "", Blank),
+            ("dns-other-records", "empty", Default),
             ("dns-servers", "8.8.8.8", Configured),
             ("earning-wallet", "0x0123456789012345678901234567890123456789", Configured),
+            ("exit-country", "", Blank),
             ("gas-price", "50", Configured),
             ("ip", "4.3.2.1", Configured),
             ("log-level", "error", Configured),
             ("neighborhood-mode", "originate-only", Configured),
             ("neighbors", "MTIzNDU2Nzg5MTEyMzQ1Njc4OTIxMjM0NTY3ODkzMTI:1.2.3.4:1234,MTIzNDU2Nzg5MTEyMzQ1Njc4OTIxMjM0NTY3ODkzMTI:5.6.7.8:5678", Configured),
+            ("payment-curves", DEFAULT_PAYMENT_CURVES, Default),
             #[cfg(not(target_os = "windows"))]
             ("real-user", "9999:9999:booga", Configured),
         ].into_iter()
@@ -1255,6 +1426,8 @@ mod tests {
                 "https://www.ropsten.com",
                 Configured,
             ),
+            ("bootstrap-public-key", "", Blank),
+            ("bootstrap-source", "", Blank),
             ("chain", TEST_DEFAULT_CHAIN_NAME, Set),
             ("clandestine-port", "8877", Configured),
             ("config-file", "config.toml", Default),
@@ -1270,17 +1443,23 @@ mod tests {
                 Default,
             ),
             ("db-password", TEST_DEFAULT_CHAIN_NAME, Configured),
+            ("debut-approval", "automatic", Default),
+            ("dns-bypass", "", Blank),
+            ("dns-bypass-servers", "", Blank),
+            ("dns-other-records", "empty", Default),
             ("dns-servers", "8.7.6.5", Configured),
             (
                 "earning-wallet",
                 "0xbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbb",
                 Configured,
             ),
+            ("exit-country", "", Blank),
             ("gas-price", "88", Configured),
             ("ip", "", Blank),
             ("log-level", "debug", Configured),
             ("neighborhood-mode", "zero-hop", Configured),
             ("neighbors", "", Blank),
+            ("payment-curves", DEFAULT_PAYMENT_CURVES, Default),
             #[cfg(not(target_os = "windows"))]
             (
                 "real-user",
@@ -1385,6 +1564,8 @@ mod tests {
 
         let expected_result = vec![
             ("blockchain-service-url", "https://example.com", Configured),
+            ("bootstrap-public-key", "", Blank),
+            ("bootstrap-source", "", Blank),
             ("chain", TEST_DEFAULT_CHAIN_NAME, Configured),
             ("clandestine-port", "1234", Configured),
             ("config-file", "config.toml", Default),
@@ -1392,17 +1573,23 @@ mod tests {
             ("crash-point", "Panic", Configured),
             ("data-directory", home_dir.to_str().unwrap(), Configured),
             ("db-password", "password", Configured),
+            ("debut-approval", "automatic", Default),
+            ("dns-bypass", "", Blank),
+            ("dns-bypass-servers", "", Blank),
+            ("dns-other-records", "empty", Default),
             ("dns-servers", "8.8.8.8", Configured),
             (
                 "earning-wallet",
                 "0x0123456789012345678901234567890123456789",
                 Configured,
             ),
+            ("exit-country", "", Blank),
             ("gas-price", "50", Configured),
             ("ip", "4.3.2.1", Configured),
             ("log-level", "error", Configured),
             ("neighborhood-mode", "originate-only", Configured),
             ("neighbors", "MTIzNDU2Nzg5MTEyMzQ1Njc4OTIxMjM0NTY3ODkzMTI:1.2.3.4:1234,MTIzNDU2Nzg5MTEyMzQ1Njc4OTIxMjM0NTY3ODkzMTI:5.6.7.8:5678", Configured),
+            ("payment-curves", DEFAULT_PAYMENT_CURVES, Default),
             #[cfg(not(target_os = "windows"))]
             ("real-user", "9999:9999:booga", Configured),
         ]
@@ -1526,6 +1713,104 @@ mod tests {
         assert_eq!(actual_data_directory, expected_data_directory);
     }
 
+    #[test]
+    fn run_configuration_rejects_misspelled_chain_without_opening_database() {
+        let _guard = EnvironmentGuard::new();
+        let home_dir = ensure_node_home_directory_exists(
+            "setup_reporter",
+            "run_configuration_rejects_misspelled_chain_without_opening_database",
+        );
+        let command_line = vec![
+            "program_name".to_string(),
+            "--data-directory".to_string(),
+            home_dir.to_string_lossy().to_string(),
+            "--chain".to_string(),
+            "mainet".to_string(),
+        ];
+        let multi_config = SetupReporterReal::make_multi_config(
+            &RealDirsWrapper {},
+            Some(command_line),
+            false,
+            false,
+        )
+        .unwrap();
+
+        let ((_, persistent_config_opt), error_opt) = SetupReporterReal::run_configuration(
+            &RealDirsWrapper {},
+            &multi_config,
+            &home_dir,
+            "mainet",
+        );
+
+        assert_eq!(persistent_config_opt.is_none(), true);
+        let chain_errors = error_opt
+            .unwrap()
+            .param_errors
+            .into_iter()
+            .filter(|param_error| param_error.parameter == "chain")
+            .collect_vec();
+        assert_eq!(
+            chain_errors,
+            vec![ParamError::new(
+                "chain",
+                &format!(
+                    "Unknown chain 'mainet': not built in, and not defined in {:?}",
+                    home_dir.join(CHAIN_DEFINITIONS_FILE)
+                )
+            )]
+        );
+        assert_eq!(home_dir.join(DATABASE_FILE).exists(), false);
+    }
+
+    #[test]
+    fn run_configuration_uses_chain_defined_in_data_directory() {
+        let _guard = EnvironmentGuard::new();
+        let home_dir = ensure_node_home_directory_exists(
+            "setup_reporter",
+            "run_configuration_uses_chain_defined_in_data_directory",
+        );
+        File::create(home_dir.join(CHAIN_DEFINITIONS_FILE))
+            .unwrap()
+            .write_all(
+                b"[[chain]]\n\
+                  name = \"setup-devnet\"\n\
+                  chain-id = 49\n\
+                  contract-address = \"0x0123456789012345678901234567890123456789\"\n\
+                  contract-creation-block = 4321\n",
+            )
+            .unwrap();
+        // The Daemon only opens databases that already exist
+        register_chain_definitions(load_chain_definitions(&home_dir).unwrap());
+        DbInitializerReal::new()
+            .initialize(&home_dir, 49, true)
+            .unwrap();
+        let command_line = vec![
+            "program_name".to_string(),
+            "--data-directory".to_string(),
+            home_dir.to_string_lossy().to_string(),
+            "--chain".to_string(),
+            "setup-devnet".to_string(),
+        ];
+        let multi_config = SetupReporterReal::make_multi_config(
+            &RealDirsWrapper {},
+            Some(command_line),
+            false,
+            false,
+        )
+        .unwrap();
+
+        let ((bootstrapper_config, persistent_config_opt), _) =
+            SetupReporterReal::run_configuration(
+                &RealDirsWrapper {},
+                &multi_config,
+                &home_dir,
+                "setup-devnet",
+            );
+
+        assert_eq!(bootstrapper_config.blockchain_bridge_config.chain_id, 49);
+        assert_eq!(persistent_config_opt.unwrap().start_block(), Ok(Some(4321)));
+    }
+
     #[test]
     fn get_modified_blanking_something_that_shouldnt_be_blanked_fails_properly() {
         let _guard = EnvironmentGuard::new();
@@ -2044,7 +2329,7 @@ mod tests {
             .to_string();
         let mut config = BootstrapperConfig::new();
         config.real_user = real_user;
-        config.blockchain_bridge_config.chain_id = chain_id_from_name("dev");
+        config.blockchain_bridge_config.chain_id = chain_id_from_name("dev").unwrap();
 
         let subject = DataDirectory::default();
 
@@ -2053,6 +2338,35 @@ mod tests {
         assert_eq!(result, Some((expected, Default)))
     }
 
+    #[test]
+    fn debut_approval_computed_default() {
+        let subject = DebutApproval {};
+
+        let result = subject.computed_default(&BootstrapperConfig::new(), &None, &None);
+
+        assert_eq!(result, Some(("automatic".to_string(), Default)))
+    }
+
+    #[test]
+    fn dns_bypass_computed_defaults_are_none() {
+        let config = BootstrapperConfig::new();
+
+        assert_eq!(DnsBypass {}.computed_default(&config, &None, &None), None);
+        assert_eq!(
+            DnsBypassServers {}.computed_default(&config, &None, &None),
+            None
+        );
+    }
+
+    #[test]
+    fn dns_other_records_computed_default() {
+        let subject = DnsOtherRecords {};
+
+        let result = subject.computed_default(&BootstrapperConfig::new(), &None, &None);
+
+        assert_eq!(result, Some(("empty".to_string(), Default)))
+    }
+
     #[test]
     fn dns_servers_computed_default() {
         let subject = DnsServers {};
@@ -2077,6 +2391,33 @@ mod tests {
         assert_eq!(result, None)
     }
 
+    #[test]
+    fn bootstrap_public_key_computed_default_is_none() {
+        let subject = BootstrapPublicKey {};
+
+        let result = subject.computed_default(&BootstrapperConfig::new(), &None, &None);
+
+        assert_eq!(result, None)
+    }
+
+    #[test]
+    fn bootstrap_source_computed_default_is_none() {
+        let subject = BootstrapSource {};
+
+        let result = subject.computed_default(&BootstrapperConfig::new(), &None, &None);
+
+        assert_eq!(result, None)
+    }
+
+    #[test]
+    fn exit_country_computed_default_is_none() {
+        let subject = ExitCountry {};
+
+        let result = subject.computed_default(&BootstrapperConfig::new(), &None, &None);
+
+        assert_eq!(result, None)
+    }
+
     #[test]
     fn earning_wallet_computed_default_with_nothing_configured_is_still_none() {
         let config = BootstrapperConfig::new();
@@ -2107,6 +2448,30 @@ mod tests {
         assert_eq!(result, Some(("1".to_string(), Default)))
     }
 
+    #[test]
+    fn payment_curves_computed_default_present() {
+        let mut bootstrapper_config = BootstrapperConfig::new();
+        bootstrapper_config.accountant_config.payment_curves =
+            crate::accountant::PaymentCurves::from_str("1000|2000|3000|4000|5000|2500").unwrap();
+        let subject = PaymentCurves {};
+
+        let result = subject.computed_default(&bootstrapper_config, &None, &None);
+
+        assert_eq!(
+            result,
+            Some(("1000|2000|3000|4000|5000|2500".to_string(), Default))
+        )
+    }
+
+    #[test]
+    fn payment_curves_computed_default_absent() {
+        let subject = PaymentCurves {};
+
+        let result = subject.computed_default(&BootstrapperConfig::new(), &None, &None);
+
+        assert_eq!(result, Some((DEFAULT_PAYMENT_CURVES.to_string(), Default)))
+    }
+
     #[test]
     fn log_level_computed_default() {
         let subject = LogLevel {};
@@ -2299,14 +2664,21 @@ mod tests {
     fn dumb_requirements() {
         let params = HashMap::new();
         assert_eq!(BlockchainServiceUrl {}.is_required(&params), true);
+        assert_eq!(BootstrapPublicKey {}.is_required(&params), false);
+        assert_eq!(BootstrapSource {}.is_required(&params), false);
         assert_eq!(Chain {}.is_required(&params), true);
         assert_eq!(ClandestinePort {}.is_required(&params), true);
         assert_eq!(ConfigFile {}.is_required(&params), false);
         assert_eq!(ConsumingPrivateKey {}.is_required(&params), false);
         assert_eq!(DataDirectory::default().is_required(&params), true);
         assert_eq!(DbPassword {}.is_required(&params), true);
+        assert_eq!(DebutApproval {}.is_required(&params), true);
+        assert_eq!(DnsBypass {}.is_required(&params), false);
+        assert_eq!(DnsBypassServers {}.is_required(&params), false);
+        assert_eq!(DnsOtherRecords {}.is_required(&params), true);
         assert_eq!(DnsServers {}.is_required(&params), true);
         assert_eq!(EarningWallet {}.is_required(&params), false);
+        assert_eq!(ExitCountry {}.is_required(&params), false);
         assert_eq!(GasPrice {}.is_required(&params), true);
         assert_eq!(Ip {}.is_required(&params), true);
         assert_eq!(LogLevel {}.is_required(&params), true);
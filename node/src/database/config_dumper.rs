@@ -1,14 +1,14 @@
 // Copyright (c) 2017-2019, Substratum LLC (https://substratum.net) and/or its affiliates. All rights reserved.
 
 use crate::blockchain::bip39::Bip39;
-use crate::blockchain::blockchain_interface::chain_id_from_name;
 use crate::bootstrapper::RealUser;
 use crate::database::db_initializer::{DbInitializer, DbInitializerReal, DATABASE_FILE};
 use crate::db_config::config_dao::{ConfigDaoRead, ConfigDaoReal, ConfigDaoRecord};
 use crate::db_config::typed_config_layer::{decode_bytes, encode_bytes};
 use crate::node_configurator::RealDirsWrapper;
 use crate::node_configurator::{
-    app_head, data_directory_from_context, real_user_data_directory_opt_and_chain_name, DirsWrapper,
+    app_head, chain_id_from_context, data_directory_from_context,
+    real_user_data_directory_opt_and_chain_name, DirsWrapper,
 };
 use crate::privilege_drop::{PrivilegeDropper, PrivilegeDropperReal};
 use crate::sub_lib::cryptde::{CryptDE, PlainData};
@@ -147,13 +147,9 @@ fn distill_args(
         real_user_data_directory_opt_and_chain_name(dirs_wrapper, &multi_config);
     let directory =
         data_directory_from_context(dirs_wrapper, &real_user, &data_directory_opt, &chain_name);
+    let chain_id = chain_id_from_context(&chain_name, &directory)?;
     let password_opt = value_m!(multi_config, "db-password", String);
-    Ok((
-        real_user,
-        directory,
-        chain_id_from_name(&chain_name),
-        password_opt,
-    ))
+    Ok((real_user, directory, chain_id, password_opt))
 }
 
 #[cfg(test)]
@@ -163,6 +159,7 @@ mod tests {
     use crate::blockchain::blockchain_interface::{
         chain_id_from_name, contract_creation_block_from_chain_id,
     };
+    use crate::blockchain::chain_definitions::CHAIN_DEFINITIONS_FILE;
     use crate::database::db_initializer::CURRENT_SCHEMA_VERSION;
     use crate::db_config::persistent_configuration::{
         PersistentConfiguration, PersistentConfigurationReal,
@@ -172,12 +169,15 @@ mod tests {
     use crate::sub_lib::neighborhood::NodeDescriptor;
     use crate::test_utils::{main_cryptde, ArgsBuilder};
     use bip39::{Language, MnemonicType, Seed};
+    use masq_lib::constants::DEFAULT_PAYMENT_CURVES;
     use masq_lib::test_utils::environment_guard::ClapGuard;
     use masq_lib::test_utils::fake_stream_holder::FakeStreamHolder;
     use masq_lib::test_utils::utils::{
         ensure_node_home_directory_exists, DEFAULT_CHAIN_ID, TEST_DEFAULT_CHAIN_NAME,
     };
     use masq_lib::utils::derivation_path;
+    use std::fs::File;
+    use std::io::Write;
 
     #[test]
     fn dump_config_creates_database_if_nonexistent() {
@@ -212,13 +212,81 @@ mod tests {
            "exampleEncrypted": null,
            "gasPrice": "1",
            "pastNeighbors": null,
+           "paymentCurves": DEFAULT_PAYMENT_CURVES,
            "schemaVersion": CURRENT_SCHEMA_VERSION,
            "seed": null,
-           "startBlock": &contract_creation_block_from_chain_id(chain_id_from_name(TEST_DEFAULT_CHAIN_NAME)).to_string(),
+           "startBlock": &contract_creation_block_from_chain_id(chain_id_from_name(TEST_DEFAULT_CHAIN_NAME).unwrap()).to_string(),
         });
         assert_eq!(actual_value, expected_value);
     }
 
+    #[test]
+    fn dump_config_rejects_misspelled_chain_without_creating_database() {
+        let data_dir = ensure_node_home_directory_exists(
+            "config_dumper",
+            "dump_config_rejects_misspelled_chain_without_creating_database",
+        );
+        let mut holder = FakeStreamHolder::new();
+        let args_vec: Vec<String> = ArgsBuilder::new()
+            .param("--data-directory", data_dir.to_str().unwrap())
+            .param("--real-user", "123::")
+            .param("--chain", "mainet")
+            .opt("--dump-config")
+            .into();
+
+        let result = dump_config(args_vec.as_slice(), &mut holder.streams());
+
+        assert_eq!(
+            result,
+            Err(ConfiguratorError::required(
+                "chain",
+                &format!(
+                    "Unknown chain 'mainet': not built in, and not defined in {:?}",
+                    data_dir.join(CHAIN_DEFINITIONS_FILE)
+                )
+            ))
+        );
+        assert_eq!(data_dir.join(DATABASE_FILE).exists(), false);
+    }
+
+    #[test]
+    fn dump_config_uses_chain_defined_in_data_directory() {
+        let data_dir = ensure_node_home_directory_exists(
+            "config_dumper",
+            "dump_config_uses_chain_defined_in_data_directory",
+        );
+        File::create(data_dir.join(CHAIN_DEFINITIONS_FILE))
+            .unwrap()
+            .write_all(
+                b"[[chain]]\n\
+                  name = \"dumper-devnet\"\n\
+                  chain-id = 47\n\
+                  contract-address = \"0x0123456789012345678901234567890123456789\"\n\
+                  contract-creation-block = 4321\n",
+            )
+            .unwrap();
+        let mut holder = FakeStreamHolder::new();
+        let args_vec: Vec<String> = ArgsBuilder::new()
+            .param("--data-directory", data_dir.to_str().unwrap())
+            .param("--real-user", "123::")
+            .param("--chain", "dumper-devnet")
+            .opt("--dump-config")
+            .into();
+
+        let result = dump_config(args_vec.as_slice(), &mut holder.streams()).unwrap();
+
+        assert_eq!(result, 0);
+        let output = holder.stdout.get_string();
+        let map = match serde_json::from_str(&output).unwrap() {
+            Value::Object(map) => map,
+            x => panic!("Expected JSON object; found {:?}", x),
+        };
+        assert_eq!(
+            map.get("startBlock"),
+            Some(&Value::String("4321".to_string()))
+        );
+    }
+
     #[test]
     fn dump_config_dumps_existing_database_without_password() {
         let _clap_guard = ClapGuard::new();
@@ -293,6 +361,7 @@ mod tests {
             "0x0123456789012345678901234567890123456789",
         );
         check("gasPrice", "1");
+        check("paymentCurves", DEFAULT_PAYMENT_CURVES);
         check(
             "pastNeighbors",
             &dao.get("past_neighbors").unwrap().value_opt.unwrap(),
@@ -300,8 +369,10 @@ mod tests {
         check("schemaVersion", CURRENT_SCHEMA_VERSION);
         check(
             "startBlock",
-            &contract_creation_block_from_chain_id(chain_id_from_name(TEST_DEFAULT_CHAIN_NAME))
-                .to_string(),
+            &contract_creation_block_from_chain_id(
+                chain_id_from_name(TEST_DEFAULT_CHAIN_NAME).unwrap(),
+            )
+            .to_string(),
         );
         check(
             "exampleEncrypted",
@@ -391,12 +462,15 @@ mod tests {
             "0x0123456789012345678901234567890123456789",
         );
         check("gasPrice", "1");
+        check("paymentCurves", DEFAULT_PAYMENT_CURVES);
         check("pastNeighbors", "QUJDREVGR0hJSktMTU5PUFFSU1RVVldYWVowMTIzNDU@1.2.3.4:1234,QkNERUZHSElKS0xNTk9QUVJTVFVWV1hZWjAxMjM0NTY@2.3.4.5:2345");
         check("schemaVersion", CURRENT_SCHEMA_VERSION);
         check(
             "startBlock",
-            &contract_creation_block_from_chain_id(chain_id_from_name(TEST_DEFAULT_CHAIN_NAME))
-                .to_string(),
+            &contract_creation_block_from_chain_id(
+                chain_id_from_name(TEST_DEFAULT_CHAIN_NAME).unwrap(),
+            )
+            .to_string(),
         );
         let expected_ee_entry = dao.get("example_encrypted").unwrap().value_opt.unwrap();
         let expected_ee_decrypted = Bip39::decrypt_bytes(&expected_ee_entry, "password").unwrap();
@@ -491,6 +565,7 @@ mod tests {
             "0x0123456789012345678901234567890123456789",
         );
         check("gasPrice", "1");
+        check("paymentCurves", DEFAULT_PAYMENT_CURVES);
         check(
             "pastNeighbors",
             &dao.get("past_neighbors").unwrap().value_opt.unwrap(),
@@ -498,8 +573,10 @@ mod tests {
         check("schemaVersion", CURRENT_SCHEMA_VERSION);
         check(
             "startBlock",
-            &contract_creation_block_from_chain_id(chain_id_from_name(TEST_DEFAULT_CHAIN_NAME))
-                .to_string(),
+            &contract_creation_block_from_chain_id(
+                chain_id_from_name(TEST_DEFAULT_CHAIN_NAME).unwrap(),
+            )
+            .to_string(),
         );
         check(
             "exampleEncrypted",
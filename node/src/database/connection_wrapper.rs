@@ -41,7 +41,7 @@ mod tests {
     fn commit_works() {
         let data_dir = ensure_node_home_directory_exists("connection_wrapper", "commit_works");
         let conn = DbInitializerReal::new()
-            .initialize(&data_dir, chain_id_from_name("dev"), true)
+            .initialize(&data_dir, chain_id_from_name("dev").unwrap(), true)
             .unwrap();
         let mut config_dao = ConfigDaoReal::new(conn);
         {
@@ -61,7 +61,7 @@ mod tests {
     fn drop_works() {
         let data_dir = ensure_node_home_directory_exists("connection_wrapper", "drop_works");
         let conn = DbInitializerReal::new()
-            .initialize(&data_dir, chain_id_from_name("dev"), true)
+            .initialize(&data_dir, chain_id_from_name("dev").unwrap(), true)
             .unwrap();
         let mut config_dao = ConfigDaoReal::new(conn);
         {
@@ -5,7 +5,8 @@ use crate::blockchain::blockchain_interface::{
 use crate::database::connection_wrapper::{ConnectionWrapper, ConnectionWrapperReal};
 use crate::db_config::secure_config_layer::EXAMPLE_ENCRYPTED;
 use masq_lib::constants::{
-    DEFAULT_GAS_PRICE, HIGHEST_RANDOM_CLANDESTINE_PORT, LOWEST_USABLE_INSECURE_PORT,
+    DEFAULT_GAS_PRICE, DEFAULT_PAYMENT_CURVES, HIGHEST_RANDOM_CLANDESTINE_PORT,
+    LOWEST_USABLE_INSECURE_PORT,
 };
 use rand::prelude::*;
 use rusqlite::Error::InvalidColumnType;
@@ -19,7 +20,7 @@ use std::path::PathBuf;
 use tokio::net::TcpListener;
 
 pub const DATABASE_FILE: &str = "node-data.db";
-pub const CURRENT_SCHEMA_VERSION: &str = "0.0.10";
+pub const CURRENT_SCHEMA_VERSION: &str = "0.0.15";
 
 #[derive(Debug, PartialEq)]
 pub enum InitializationError {
@@ -121,7 +122,10 @@ impl DbInitializerReal {
         self.initialize_config(conn, chain_id)?;
         self.create_payable_table(conn)?;
         self.create_receivable_table(conn)?;
-        self.create_banned_table(conn)
+        self.create_banned_table(conn)?;
+        self.create_malefactor_banned_table(conn)?;
+        self.create_debut_allowed_table(conn)?;
+        self.create_neighborhood_snapshot_table(conn)
     }
 
     fn create_config_table(&self, conn: &Connection) -> Result<(), InitializationError> {
@@ -199,6 +203,13 @@ impl DbInitializerReal {
             "gas price",
         );
         Self::set_config_value(conn, "past_neighbors", None, true, "past neighbors");
+        Self::set_config_value(
+            conn,
+            "payment_curves",
+            Some(DEFAULT_PAYMENT_CURVES),
+            false,
+            "payment curves",
+        );
         Ok(())
     }
 
@@ -241,7 +252,7 @@ impl DbInitializerReal {
 
     fn create_banned_table(&self, conn: &Connection) -> Result<(), InitializationError> {
         conn.execute(
-            "create table banned ( wallet_address text primary key )",
+            "create table banned ( wallet_address text primary key, reason text null )",
             NO_PARAMS,
         )
         .expect("Can't create banned table");
@@ -253,6 +264,53 @@ impl DbInitializerReal {
         Ok(())
     }
 
+    fn create_malefactor_banned_table(&self, conn: &Connection) -> Result<(), InitializationError> {
+        conn.execute(
+            "create table malefactor_banned (
+                id integer primary key,
+                public_key text null,
+                ip_address text null,
+                earning_wallet text null,
+                reason text not null,
+                banned_at integer not null,
+                expires_at integer null
+            )",
+            NO_PARAMS,
+        )
+        .expect("Can't create malefactor_banned table");
+        Ok(())
+    }
+
+    fn create_debut_allowed_table(&self, conn: &Connection) -> Result<(), InitializationError> {
+        conn.execute(
+            "create table debut_allowed (
+                public_key text primary key
+            )",
+            NO_PARAMS,
+        )
+        .expect("Can't create debut_allowed table");
+        Ok(())
+    }
+
+    fn create_neighborhood_snapshot_table(
+        &self,
+        conn: &Connection,
+    ) -> Result<(), InitializationError> {
+        conn.execute(
+            "create table neighborhood_snapshot (
+                public_key text primary key,
+                signed_gossip blob not null,
+                signature blob not null,
+                node_addr text null,
+                desirable integer not null,
+                last_update integer not null
+            )",
+            NO_PARAMS,
+        )
+        .expect("Can't create neighborhood_snapshot table");
+        Ok(())
+    }
+
     fn extract_configurations(&self, conn: &Connection) -> HashMap<String, Option<String>> {
         let mut stmt = conn.prepare("select name, value from config").unwrap();
         let query_result = stmt.query_map(NO_PARAMS, |row| Ok((row.get(0), row.get(1))));
@@ -550,11 +608,82 @@ mod tests {
         flags.insert(OpenFlags::SQLITE_OPEN_READ_ONLY);
         let conn = Connection::open_with_flags(&home_dir.join(DATABASE_FILE), flags).unwrap();
 
-        let mut stmt = conn.prepare("select wallet_address from banned").unwrap();
+        let mut stmt = conn
+            .prepare("select wallet_address, reason from banned")
+            .unwrap();
         let mut banned_contents = stmt.query_map(NO_PARAMS, |_| Ok(42)).unwrap();
         assert!(banned_contents.next().is_none());
     }
 
+    #[test]
+    fn db_initialize_creates_malefactor_banned_table() {
+        let home_dir = ensure_node_home_directory_does_not_exist(
+            "db_initializer",
+            "db_initialize_creates_malefactor_banned_table",
+        );
+        let subject = DbInitializerReal::new();
+
+        subject
+            .initialize(&home_dir, DEFAULT_CHAIN_ID, true)
+            .unwrap();
+
+        let mut flags = OpenFlags::empty();
+        flags.insert(OpenFlags::SQLITE_OPEN_READ_ONLY);
+        let conn = Connection::open_with_flags(&home_dir.join(DATABASE_FILE), flags).unwrap();
+
+        let mut stmt = conn
+            .prepare("select id, public_key, ip_address, earning_wallet, reason, banned_at, expires_at from malefactor_banned")
+            .unwrap();
+        let mut malefactor_banned_contents = stmt.query_map(NO_PARAMS, |_| Ok(42)).unwrap();
+        assert!(malefactor_banned_contents.next().is_none());
+    }
+
+    #[test]
+    fn db_initialize_creates_debut_allowed_table() {
+        let home_dir = ensure_node_home_directory_does_not_exist(
+            "db_initializer",
+            "db_initialize_creates_debut_allowed_table",
+        );
+        let subject = DbInitializerReal::new();
+
+        subject
+            .initialize(&home_dir, DEFAULT_CHAIN_ID, true)
+            .unwrap();
+
+        let mut flags = OpenFlags::empty();
+        flags.insert(OpenFlags::SQLITE_OPEN_READ_ONLY);
+        let conn = Connection::open_with_flags(&home_dir.join(DATABASE_FILE), flags).unwrap();
+
+        let mut stmt = conn
+            .prepare("select public_key from debut_allowed")
+            .unwrap();
+        let mut debut_allowed_contents = stmt.query_map(NO_PARAMS, |_| Ok(42)).unwrap();
+        assert!(debut_allowed_contents.next().is_none());
+    }
+
+    #[test]
+    fn db_initialize_creates_neighborhood_snapshot_table() {
+        let home_dir = ensure_node_home_directory_does_not_exist(
+            "db_initializer",
+            "db_initialize_creates_neighborhood_snapshot_table",
+        );
+        let subject = DbInitializerReal::new();
+
+        subject
+            .initialize(&home_dir, DEFAULT_CHAIN_ID, true)
+            .unwrap();
+
+        let mut flags = OpenFlags::empty();
+        flags.insert(OpenFlags::SQLITE_OPEN_READ_ONLY);
+        let conn = Connection::open_with_flags(&home_dir.join(DATABASE_FILE), flags).unwrap();
+
+        let mut stmt = conn
+            .prepare("select public_key, signed_gossip, signature, node_addr, desirable, last_update from neighborhood_snapshot")
+            .unwrap();
+        let mut neighborhood_snapshot_contents = stmt.query_map(NO_PARAMS, |_| Ok(42)).unwrap();
+        assert!(neighborhood_snapshot_contents.next().is_none());
+    }
+
     #[test]
     fn existing_database_with_correct_version_is_accepted_without_changes() {
         let home_dir = ensure_node_home_directory_exists(
@@ -608,6 +737,11 @@ mod tests {
         verify(&mut config_vec, EXAMPLE_ENCRYPTED, None);
         verify(&mut config_vec, "gas_price", Some(DEFAULT_GAS_PRICE));
         verify(&mut config_vec, "past_neighbors", None);
+        verify(
+            &mut config_vec,
+            "payment_curves",
+            Some(DEFAULT_PAYMENT_CURVES),
+        );
         verify(&mut config_vec, "preexisting", Some("yes")); // makes sure we just created this database
         verify(
             &mut config_vec,
@@ -620,7 +754,9 @@ mod tests {
             "start_block",
             Some(&format!(
                 "{}",
-                contract_creation_block_from_chain_id(chain_id_from_name(TEST_DEFAULT_CHAIN_NAME))
+                contract_creation_block_from_chain_id(
+                    chain_id_from_name(TEST_DEFAULT_CHAIN_NAME).unwrap()
+                )
             )),
         );
         assert_eq!(config_vec, vec![]);
@@ -1,4 +1,5 @@
 // Copyright (c) 2017-2019, Substratum LLC (https://substratum.net) and/or its affiliates. All rights reserved.
+use crate::accountant::PaymentCurves;
 use crate::blockchain::bip32::Bip32ECKeyPair;
 use crate::blockchain::bip39::Bip39;
 use crate::database::connection_wrapper::ConnectionWrapper;
@@ -27,6 +28,7 @@ pub enum PersistentConfigError {
     BadMnemonicSeed(PlainData),
     BadDerivationPathFormat(String),
     BadAddressFormat(String),
+    BadPaymentCurvesFormat(String),
     Collision(String),
 }
 
@@ -81,6 +83,11 @@ pub trait PersistentConfiguration {
     fn set_clandestine_port(&mut self, port: u16) -> Result<(), PersistentConfigError>;
     fn gas_price(&self) -> Result<Option<u64>, PersistentConfigError>;
     fn set_gas_price(&mut self, gas_price: u64) -> Result<(), PersistentConfigError>;
+    fn payment_curves(&self) -> Result<Option<PaymentCurves>, PersistentConfigError>;
+    fn set_payment_curves(
+        &mut self,
+        payment_curves: &PaymentCurves,
+    ) -> Result<(), PersistentConfigError>;
     fn mnemonic_seed(&self, db_password: &str) -> Result<Option<PlainData>, PersistentConfigError>;
     fn mnemonic_seed_exists(&self) -> Result<bool, PersistentConfigError>;
     // WARNING: Actors should get consuming-wallet information from their startup config, not from here
@@ -194,6 +201,25 @@ impl PersistentConfiguration for PersistentConfigurationReal {
         Ok(writer.commit()?)
     }
 
+    fn payment_curves(&self) -> Result<Option<PaymentCurves>, PersistentConfigError> {
+        match self.dao.get("payment_curves")?.value_opt {
+            None => Ok(None),
+            Some(string) => match PaymentCurves::from_str(&string) {
+                Ok(payment_curves) => Ok(Some(payment_curves)),
+                Err(e) => Err(PersistentConfigError::BadPaymentCurvesFormat(e)),
+            },
+        }
+    }
+
+    fn set_payment_curves(
+        &mut self,
+        payment_curves: &PaymentCurves,
+    ) -> Result<(), PersistentConfigError> {
+        let mut writer = self.dao.start_transaction()?;
+        writer.set("payment_curves", Some(payment_curves.to_string()))?;
+        Ok(writer.commit()?)
+    }
+
     fn mnemonic_seed(&self, db_password: &str) -> Result<Option<PlainData>, PersistentConfigError> {
         Ok(decode_bytes(self.scl.decrypt(
             self.dao.get("seed")?,
@@ -1409,6 +1435,68 @@ mod tests {
         )
     }
 
+    #[test]
+    fn payment_curves() {
+        let config_dao = Box::new(ConfigDaoMock::new().get_result(Ok(ConfigDaoRecord::new(
+            "payment_curves",
+            Some("1000|2000|3000|4000|5000|2500"),
+            false,
+        ))));
+        let subject = PersistentConfigurationReal::new(config_dao);
+
+        let payment_curves = subject.payment_curves().unwrap();
+
+        assert_eq!(
+            payment_curves,
+            Some(PaymentCurves::from_str("1000|2000|3000|4000|5000|2500").unwrap())
+        );
+    }
+
+    #[test]
+    fn payment_curves_complains_about_corrupt_value() {
+        let config_dao = Box::new(ConfigDaoMock::new().get_result(Ok(ConfigDaoRecord::new(
+            "payment_curves",
+            Some("1000|2000"),
+            false,
+        ))));
+        let subject = PersistentConfigurationReal::new(config_dao);
+
+        let result = subject.payment_curves();
+
+        assert_eq!(
+            result,
+            Err(PersistentConfigError::BadPaymentCurvesFormat(
+                "Payment curves need six values separated by '|', not 2".to_string()
+            ))
+        );
+    }
+
+    #[test]
+    fn set_payment_curves_succeeds() {
+        let set_params_arc = Arc::new(Mutex::new(vec![]));
+        let writer = Box::new(
+            ConfigDaoWriteableMock::new()
+                .set_params(&set_params_arc)
+                .set_result(Ok(()))
+                .commit_result(Ok(())),
+        );
+        let config_dao = Box::new(ConfigDaoMock::new().start_transaction_result(Ok(writer)));
+        let mut subject = PersistentConfigurationReal::new(config_dao);
+
+        let result = subject
+            .set_payment_curves(&PaymentCurves::from_str("1000|2000|3000|4000|5000|2500").unwrap());
+
+        assert_eq!(result, Ok(()));
+        let set_params = set_params_arc.lock().unwrap();
+        assert_eq!(
+            *set_params,
+            vec![(
+                "payment_curves".to_string(),
+                Some("1000|2000|3000|4000|5000|2500".to_string())
+            )]
+        )
+    }
+
     #[test]
     fn past_neighbors_success() {
         let example = "Aside from that, Mrs. Lincoln, how was the play?".as_bytes();
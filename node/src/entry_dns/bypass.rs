@@ -0,0 +1,285 @@
+// Copyright (c) 2019-2020, MASQ (https://masq.ai) and/or its affiliates. All rights reserved.
+
+use lazy_static::lazy_static;
+use masq_lib::shared_schema::common_validators::validate_dns_bypass_domain;
+use std::fmt::{Display, Formatter};
+use std::net::SocketAddr;
+use std::str::FromStr;
+use std::sync::{Arc, RwLock};
+
+lazy_static! {
+    // The DNS server consults this list; the Configurator edits it on behalf of UIs.
+    pub static ref DNS_BYPASS: DnsBypass = DnsBypass::new();
+}
+
+// What entry DNS does with queries it has no synthetic answer for: record types other than
+// A, AAAA, HTTPS and SVCB, and classes other than IN.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum OtherRecordsPolicy {
+    Empty,
+    Forward,
+    NxDomain,
+}
+
+impl Default for OtherRecordsPolicy {
+    fn default() -> Self {
+        OtherRecordsPolicy::Empty
+    }
+}
+
+impl Display for OtherRecordsPolicy {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            OtherRecordsPolicy::Empty => write!(f, "empty"),
+            OtherRecordsPolicy::Forward => write!(f, "forward"),
+            OtherRecordsPolicy::NxDomain => write!(f, "nxdomain"),
+        }
+    }
+}
+
+impl FromStr for OtherRecordsPolicy {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.to_lowercase().as_str() {
+            "empty" => Ok(OtherRecordsPolicy::Empty),
+            "forward" => Ok(OtherRecordsPolicy::Forward),
+            "nxdomain" => Ok(OtherRecordsPolicy::NxDomain),
+            _ => Err(format!(
+                "DNS other-records policy must be 'empty', 'forward', or 'nxdomain', not '{}'",
+                s
+            )),
+        }
+    }
+}
+
+#[derive(Default)]
+struct DnsBypassInner {
+    domains: Vec<String>,
+    servers: Vec<SocketAddr>,
+    other_records: OtherRecordsPolicy,
+}
+
+// Domains that entry DNS doesn't claim for the MASQ Network, and the upstream servers that
+// answer for them instead.
+#[derive(Clone, Default)]
+pub struct DnsBypass {
+    inner: Arc<RwLock<DnsBypassInner>>,
+}
+
+impl DnsBypass {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn configure(&self, domains: &[String], servers: &[SocketAddr]) -> Result<(), String> {
+        let domains = Self::normalize_all(domains)?;
+        let mut inner = self.inner.write().expect("DNS bypass list is poisoned");
+        inner.domains = vec![];
+        Self::add_domains(&mut inner.domains, domains);
+        inner.servers = servers.to_vec();
+        Ok(())
+    }
+
+    pub fn domains(&self) -> Vec<String> {
+        self.inner
+            .read()
+            .expect("DNS bypass list is poisoned")
+            .domains
+            .clone()
+    }
+
+    pub fn set_other_records(&self, policy: OtherRecordsPolicy) {
+        self.inner
+            .write()
+            .expect("DNS bypass list is poisoned")
+            .other_records = policy;
+    }
+
+    pub fn other_records(&self) -> OtherRecordsPolicy {
+        self.inner
+            .read()
+            .expect("DNS bypass list is poisoned")
+            .other_records
+    }
+
+    pub fn servers(&self) -> Vec<SocketAddr> {
+        self.inner
+            .read()
+            .expect("DNS bypass list is poisoned")
+            .servers
+            .clone()
+    }
+
+    // Either every change is made or, if any domain is malformed, none is.
+    pub fn edit(&self, add: &[String], remove: &[String]) -> Result<Vec<String>, String> {
+        let add = Self::normalize_all(add)?;
+        let remove = Self::normalize_all(remove)?;
+        let mut inner = self.inner.write().expect("DNS bypass list is poisoned");
+        inner.domains.retain(|domain| !remove.contains(domain));
+        Self::add_domains(&mut inner.domains, add);
+        Ok(inner.domains.clone())
+    }
+
+    pub fn bypasses(&self, name: &str) -> bool {
+        let name = name.trim_end_matches('.').to_lowercase();
+        self.inner
+            .read()
+            .expect("DNS bypass list is poisoned")
+            .domains
+            .iter()
+            .any(|domain| Self::matches(domain, &name))
+    }
+
+    fn matches(domain: &str, name: &str) -> bool {
+        if domain.starts_with("*.") {
+            name.ends_with(&domain[1..])
+        } else {
+            name == domain || name.ends_with(&format!(".{}", domain))
+        }
+    }
+
+    fn normalize_all(domains: &[String]) -> Result<Vec<String>, String> {
+        domains
+            .iter()
+            .map(|domain| Self::normalize(domain))
+            .collect()
+    }
+
+    fn normalize(domain: &str) -> Result<String, String> {
+        let trimmed = domain.trim();
+        match validate_dns_bypass_domain(trimmed.to_string()) {
+            Ok(()) => Ok(trimmed.trim_end_matches('.').to_lowercase()),
+            Err(_) => Err(format!("'{}' is not a domain name", domain)),
+        }
+    }
+
+    fn add_domains(domains: &mut Vec<String>, additions: Vec<String>) {
+        domains.extend(additions);
+        domains.sort();
+        domains.dedup();
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn strings(strs: Vec<&str>) -> Vec<String> {
+        strs.into_iter().map(|s| s.to_string()).collect()
+    }
+
+    #[test]
+    fn configure_normalizes_domains_and_remembers_servers() {
+        let subject = DnsBypass::new();
+        let servers = vec![SocketAddr::from_str("192.168.1.1:53").unwrap()];
+
+        let result = subject.configure(&strings(vec!["Corp.Example.COM.", "lan", "lan"]), &servers);
+
+        assert_eq!(result, Ok(()));
+        assert_eq!(subject.domains(), strings(vec!["corp.example.com", "lan"]));
+        assert_eq!(subject.servers(), servers);
+    }
+
+    #[test]
+    fn a_plain_domain_bypasses_itself_and_its_subdomains() {
+        let subject = DnsBypass::new();
+        subject
+            .configure(&strings(vec!["corp.example.com"]), &[])
+            .unwrap();
+
+        assert_eq!(subject.bypasses("corp.example.com"), true);
+        assert_eq!(subject.bypasses("WWW.Corp.Example.com."), true);
+        assert_eq!(subject.bypasses("example.com"), false);
+        assert_eq!(subject.bypasses("notcorp.example.com"), false);
+    }
+
+    #[test]
+    fn a_wildcard_domain_bypasses_only_its_subdomains() {
+        let subject = DnsBypass::new();
+        subject.configure(&strings(vec!["*.lan"]), &[]).unwrap();
+
+        assert_eq!(subject.bypasses("printer.lan"), true);
+        assert_eq!(subject.bypasses("a.b.lan"), true);
+        assert_eq!(subject.bypasses("lan"), false);
+        assert_eq!(subject.bypasses("printerlan"), false);
+    }
+
+    #[test]
+    fn edit_removes_then_adds() {
+        let subject = DnsBypass::new();
+        subject
+            .configure(&strings(vec!["lan", "corp.example.com"]), &[])
+            .unwrap();
+
+        let result = subject.edit(&strings(vec!["intranet", "LAN"]), &strings(vec!["lan"]));
+
+        assert_eq!(
+            result,
+            Ok(strings(vec!["corp.example.com", "intranet", "lan"]))
+        );
+        assert_eq!(subject.bypasses("printer.intranet"), true);
+    }
+
+    #[test]
+    fn edit_with_a_malformed_domain_changes_nothing() {
+        let subject = DnsBypass::new();
+        subject.configure(&strings(vec!["lan"]), &[]).unwrap();
+
+        let result = subject.edit(
+            &strings(vec!["intranet", "bad domain"]),
+            &strings(vec!["lan"]),
+        );
+
+        assert_eq!(result, Err("'bad domain' is not a domain name".to_string()));
+        assert_eq!(subject.domains(), strings(vec!["lan"]));
+    }
+
+    #[test]
+    fn other_records_policy_starts_out_empty_and_can_be_changed() {
+        let subject = DnsBypass::new();
+        let clone = subject.clone();
+        assert_eq!(subject.other_records(), OtherRecordsPolicy::Empty);
+
+        subject.set_other_records(OtherRecordsPolicy::Forward);
+
+        assert_eq!(clone.other_records(), OtherRecordsPolicy::Forward);
+    }
+
+    #[test]
+    fn other_records_policy_round_trips_through_strings() {
+        vec![
+            OtherRecordsPolicy::Empty,
+            OtherRecordsPolicy::Forward,
+            OtherRecordsPolicy::NxDomain,
+        ]
+        .into_iter()
+        .for_each(|policy| {
+            assert_eq!(
+                OtherRecordsPolicy::from_str(&policy.to_string()),
+                Ok(policy)
+            )
+        });
+        assert_eq!(
+            OtherRecordsPolicy::from_str("NXDomain"),
+            Ok(OtherRecordsPolicy::NxDomain)
+        );
+        assert_eq!(
+            OtherRecordsPolicy::from_str("refuse"),
+            Err(
+                "DNS other-records policy must be 'empty', 'forward', or 'nxdomain', not 'refuse'"
+                    .to_string()
+            )
+        );
+    }
+
+    #[test]
+    fn clones_share_the_same_list() {
+        let subject = DnsBypass::new();
+        let clone = subject.clone();
+
+        subject.edit(&strings(vec!["lan"]), &[]).unwrap();
+
+        assert_eq!(clone.bypasses("printer.lan"), true);
+    }
+}
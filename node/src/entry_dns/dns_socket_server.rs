@@ -1,22 +1,37 @@
 // Copyright (c) 2017-2019, Substratum LLC (https://substratum.net) and/or its affiliates. All rights reserved.
 use crate::sub_lib::logger::Logger;
 use crate::sub_lib::socket_server::SocketServer;
+use futures::sync::mpsc::{unbounded, UnboundedReceiver};
+use futures::Stream;
 use masq_lib::command::StdStreams;
-use std::net::SocketAddr;
+use std::net::{SocketAddr, TcpListener};
 use tokio::prelude::Async;
 use tokio::prelude::Future;
 
 const DNS_PORT: u16 = 53;
 
+use crate::entry_dns::bypass::{DnsBypass, DNS_BYPASS};
+use crate::entry_dns::forwarder::{DnsForwarder, DnsForwarderReal, ForwardedAnswer};
 use crate::entry_dns::processing;
+use crate::entry_dns::statistics::QueryStatistics;
+use crate::entry_dns::tcp_server::DnsTcpServer;
 use crate::sub_lib::udp_socket_wrapper::UdpSocketWrapperReal;
 use crate::sub_lib::udp_socket_wrapper::UdpSocketWrapperTrait;
+use crate::systemd;
+use crate::systemd::{DNS_SOCKET_NAME, DNS_TCP_SOCKET_NAME};
 use masq_lib::shared_schema::ConfiguratorError;
 use masq_lib::utils::localhost;
 
 pub struct DnsSocketServer {
     socket_wrapper: Box<dyn UdpSocketWrapperTrait>,
     buf: [u8; 65536],
+    bypass: DnsBypass,
+    forwarder: Box<dyn DnsForwarder>,
+    forwarded_answer_rx: UnboundedReceiver<ForwardedAnswer>,
+    statistics: QueryStatistics,
+    // DNS_PORT, except in tests
+    tcp_port: u16,
+    tcp_listener_opt: Option<TcpListener>,
 }
 
 impl Future for DnsSocketServer {
@@ -29,7 +44,10 @@ impl Future for DnsSocketServer {
             let mut buffer = self.buf;
             let (len, socket_addr) = match self.socket_wrapper.recv_from(&mut buffer) {
                 Ok(Async::Ready((len, socket_addr))) => (len, socket_addr),
-                Ok(Async::NotReady) => return Ok(Async::NotReady),
+                Ok(Async::NotReady) => {
+                    self.relay_forwarded_answers(&logger)?;
+                    return Ok(Async::NotReady);
+                }
                 Err(e) => {
                     error!(
                         logger,
@@ -38,7 +56,25 @@ impl Future for DnsSocketServer {
                     return Err(());
                 }
             };
-            let response_length = processing::process(&mut buffer, len, &socket_addr, &logger);
+            self.statistics
+                .record_query(&mut buffer, len, false, &logger);
+            if processing::bypass_requested(&mut buffer, len, &self.bypass) {
+                trace!(logger, "{} Query bypasses the MASQ Network", socket_addr);
+                self.statistics.record_forwarded();
+                self.forwarder
+                    .forward(buffer[0..len].to_vec(), socket_addr, self.bypass.servers());
+                continue;
+            }
+            let response_length = processing::process(
+                &mut buffer,
+                len,
+                processing::UDP_PAYLOAD_LIMIT,
+                self.bypass.other_records(),
+                &socket_addr,
+                &logger,
+            );
+            self.statistics
+                .record_response(&mut buffer, response_length);
             if let Err(e) = self
                 .socket_wrapper
                 .send_to(&buffer[0..response_length], socket_addr)
@@ -60,10 +96,37 @@ impl SocketServer<()> for DnsSocketServer {
         _args: &[String],
         _streams: &mut StdStreams<'_>,
     ) -> Result<(), ConfiguratorError> {
-        let socket_addr = SocketAddr::new(localhost(), DNS_PORT);
-        self.socket_wrapper
-            .bind(socket_addr)
-            .unwrap_or_else(|e| panic!("Cannot bind socket to {:?}: {:?}", socket_addr, e));
+        match systemd::take_activated_udp_socket(DNS_SOCKET_NAME) {
+            Some(socket) => self
+                .socket_wrapper
+                .adopt(socket)
+                .unwrap_or_else(|e| panic!("Cannot adopt socket-activated DNS socket: {:?}", e)),
+            None => {
+                let socket_addr = SocketAddr::new(localhost(), DNS_PORT);
+                self.socket_wrapper
+                    .bind(socket_addr)
+                    .unwrap_or_else(|e| panic!("Cannot bind socket to {:?}: {:?}", socket_addr, e));
+            }
+        }
+        // TCP is only a fallback for oversized answers, so its absence isn't fatal
+        self.tcp_listener_opt = match systemd::take_activated_tcp_listener(DNS_TCP_SOCKET_NAME) {
+            Some(listener) => Some(listener),
+            None => {
+                let socket_addr = SocketAddr::new(localhost(), self.tcp_port);
+                match TcpListener::bind(socket_addr) {
+                    Ok(listener) => Some(listener),
+                    Err(e) => {
+                        warning!(
+                            Logger::new("EntryDnsServer"),
+                            "Cannot answer DNS over TCP at {}: {}",
+                            socket_addr,
+                            e
+                        );
+                        None
+                    }
+                }
+            }
+        };
         Ok(())
     }
 
@@ -73,17 +136,38 @@ impl SocketServer<()> for DnsSocketServer {
         _streams: &mut StdStreams<'_>,
     ) -> Result<(), ConfiguratorError> {
         self.buf = [0; 65536];
+        // Started only now, so that its threads never run with privilege
+        if let Some(listener) = self.tcp_listener_opt.take() {
+            DnsTcpServer::new(listener, self.bypass.clone(), self.statistics.clone()).start();
+        }
         Ok(())
     }
 }
 
 impl DnsSocketServer {
     pub fn new() -> DnsSocketServer {
+        let (forwarded_answer_tx, forwarded_answer_rx) = unbounded();
         DnsSocketServer {
             socket_wrapper: Box::new(UdpSocketWrapperReal::new()),
             buf: [0; 65536],
+            bypass: DNS_BYPASS.clone(),
+            forwarder: Box::new(DnsForwarderReal::new(forwarded_answer_tx)),
+            forwarded_answer_rx,
+            statistics: QueryStatistics::new(),
+            tcp_port: DNS_PORT,
+            tcp_listener_opt: None,
         }
     }
+
+    fn relay_forwarded_answers(&mut self, logger: &Logger) -> Result<(), ()> {
+        while let Ok(Async::Ready(Some((answer, client_addr)))) = self.forwarded_answer_rx.poll() {
+            if let Err(e) = self.socket_wrapper.send_to(&answer, client_addr) {
+                error!(logger, "Unrecoverable error sending to UdpSocket: {}", e);
+                return Err(());
+            }
+        }
+        Ok(())
+    }
 }
 
 impl Default for DnsSocketServer {
@@ -96,9 +180,12 @@ impl Default for DnsSocketServer {
 mod tests {
     use super::super::packet_facade::PacketFacade;
     use super::*;
+    use crate::entry_dns::forwarder::{read_tcp_message, write_tcp_message};
     use crate::sub_lib::udp_socket_wrapper::UdpSocketWrapperTrait;
     use crate::test_utils::logging::init_test_logging;
     use crate::test_utils::logging::TestLogHandler;
+    use futures::future::lazy;
+    use futures::sync::mpsc::UnboundedSender;
     use masq_lib::test_utils::fake_stream_holder::FakeStreamHolder;
     use std::borrow::Borrow;
     use std::borrow::BorrowMut;
@@ -107,12 +194,41 @@ mod tests {
     use std::io;
     use std::io::Error;
     use std::io::ErrorKind;
+    use std::net::TcpStream;
     use std::ops::DerefMut;
     use std::str::FromStr;
     use std::sync::Arc;
     use std::sync::Mutex;
     use tokio;
-    use trust_dns::op::ResponseCode;
+    use trust_dns::op::{OpCode, ResponseCode};
+    use trust_dns::rr::{DNSClass, RecordType};
+
+    #[derive(Clone)]
+    struct DnsForwarderMock {
+        forward_params: Arc<Mutex<Vec<(Vec<u8>, SocketAddr, Vec<SocketAddr>)>>>,
+        answer_tx_opt: Option<UnboundedSender<ForwardedAnswer>>,
+    }
+
+    impl DnsForwarder for DnsForwarderMock {
+        fn forward(&self, query: Vec<u8>, client_addr: SocketAddr, servers: Vec<SocketAddr>) {
+            self.forward_params
+                .lock()
+                .unwrap()
+                .push((query.clone(), client_addr, servers));
+            if let Some(answer_tx) = self.answer_tx_opt.as_ref() {
+                answer_tx.unbounded_send((query, client_addr)).unwrap();
+            }
+        }
+    }
+
+    impl DnsForwarderMock {
+        fn new() -> Self {
+            Self {
+                forward_params: Arc::new(Mutex::new(vec![])),
+                answer_tx_opt: None,
+            }
+        }
+    }
 
     struct UdpSocketWrapperMockGuts {
         log: Vec<String>,
@@ -135,6 +251,15 @@ mod tests {
             Ok(true)
         }
 
+        fn adopt(&mut self, socket: std::net::UdpSocket) -> io::Result<()> {
+            let mut unwrapped_guts = self.guts.lock().unwrap();
+            let guts_ref = unwrapped_guts.borrow_mut();
+            let guts: &mut UdpSocketWrapperMockGuts = guts_ref.deref_mut();
+            guts.log
+                .push(format!("adopt ('{:?}')", socket.local_addr()?));
+            Ok(())
+        }
+
         fn recv_from(&mut self, buf: &mut [u8]) -> Result<Async<(usize, SocketAddr)>, Error> {
             let mut unwrapped_guts = self.guts.lock().unwrap();
             let guts_ref = unwrapped_guts.borrow_mut();
@@ -210,6 +335,46 @@ mod tests {
         assert_eq!(log[0], "bind ('127.0.0.1:53')")
     }
 
+    #[test]
+    fn answers_dns_over_tcp_once_unprivileged() {
+        let socket_wrapper = make_socket_wrapper_mock();
+        let mut subject = make_instrumented_subject(socket_wrapper);
+        let mut holder = FakeStreamHolder::new();
+
+        subject
+            .initialize_as_privileged(&[], &mut holder.streams())
+            .unwrap();
+
+        let tcp_addr = subject
+            .tcp_listener_opt
+            .as_ref()
+            .unwrap()
+            .local_addr()
+            .unwrap();
+        assert_eq!(tcp_addr.ip(), localhost());
+        subject
+            .initialize_as_unprivileged(&[], &mut holder.streams())
+            .unwrap();
+        assert_eq!(subject.tcp_listener_opt.is_none(), true);
+        let mut query = [0u8; 100];
+        let query_length = {
+            let mut facade = PacketFacade::new(&mut query, 100);
+            facade.set_transaction_id(0x1234);
+            facade.set_query(true);
+            facade.set_opcode(OpCode::Query.into());
+            facade.clear();
+            facade.add_query("ooga.com", RecordType::A.into(), DNSClass::IN.into());
+            facade.get_length()
+        };
+        let mut stream = TcpStream::connect(tcp_addr).unwrap();
+        write_tcp_message(&mut stream, &query[0..query_length]).unwrap();
+        let mut answer = read_tcp_message(&mut stream).unwrap();
+        let answer_length = answer.len();
+        let facade = PacketFacade::new(&mut answer, answer_length);
+        assert_eq!(facade.get_transaction_id(), Some(0x1234));
+        assert_eq!(facade.get_answers().unwrap().len(), 1);
+    }
+
     #[test]
     fn serves_multiple_requests_then_short_circuit_on_error() {
         init_test_logging();
@@ -359,6 +524,74 @@ mod tests {
         );
     }
 
+    #[test]
+    fn queries_for_bypassed_domains_are_forwarded_and_their_answers_relayed() {
+        let mut query = [0u8; 100];
+        let query_length = {
+            let mut facade = PacketFacade::new(&mut query, 100);
+            facade.set_transaction_id(0x1234);
+            facade.set_query(true);
+            facade.set_opcode(OpCode::Query.into());
+            facade.add_query("printer.lan", RecordType::A.into(), DNSClass::IN.into());
+            facade.get_length()
+        };
+        let socket_wrapper = make_socket_wrapper_mock();
+        let client_addr = SocketAddr::from_str("1.2.3.4:5678").unwrap();
+        socket_wrapper
+            .recv_from_results
+            .lock()
+            .unwrap()
+            .push(Ok(Async::Ready((query_length, client_addr))));
+        socket_wrapper
+            .recv_from_results
+            .lock()
+            .unwrap()
+            .push(Ok(Async::NotReady));
+        socket_wrapper
+            .send_to_results
+            .lock()
+            .unwrap()
+            .push(Ok(Async::Ready(query_length)));
+        let (answer_tx, answer_rx) = unbounded();
+        let mut forwarder = DnsForwarderMock::new();
+        forwarder.answer_tx_opt = Some(answer_tx);
+        let forward_params_arc = forwarder.forward_params.clone();
+        let bypass_servers = vec![SocketAddr::from_str("192.168.1.1:53").unwrap()];
+        let bypass = DnsBypass::new();
+        bypass
+            .configure(&["lan".to_string()], &bypass_servers)
+            .unwrap();
+        let mut subject = make_instrumented_subject(socket_wrapper.clone());
+        subject.bypass = bypass;
+        subject.forwarder = Box::new(forwarder);
+        subject.forwarded_answer_rx = answer_rx;
+        // The mock delivers only a 12-byte header; the rest of the query is already in the buffer
+        subject.buf[0..query_length].copy_from_slice(&query[0..query_length]);
+        socket_wrapper
+            .guts
+            .lock()
+            .unwrap()
+            .buf
+            .copy_from_slice(&query[0..12]);
+
+        let result = lazy(move || Ok::<_, ()>(subject.poll())).wait().unwrap();
+
+        assert_eq!(result, Ok(Async::NotReady));
+        let forward_params = forward_params_arc.lock().unwrap();
+        assert_eq!(forward_params.len(), 1);
+        assert_eq!(forward_params[0].1, client_addr);
+        assert_eq!(forward_params[0].2, bypass_servers);
+        let log = socket_wrapper.guts.lock().unwrap().log.clone();
+        assert_eq!(
+            log,
+            vec![
+                format!("recv_from (Ok(Ready(({}, 1.2.3.4:5678))))", query_length),
+                "recv_from (Ok(NotReady))".to_string(),
+                "send_to (buf, 1.2.3.4:5678)".to_string(),
+            ]
+        );
+    }
+
     fn make_socket_wrapper_mock() -> Box<UdpSocketWrapperMock> {
         Box::new(UdpSocketWrapperMock::new(&[
             0x12, 0x34, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00,
@@ -366,9 +599,16 @@ mod tests {
     }
 
     fn make_instrumented_subject(socket_wrapper: Box<UdpSocketWrapperMock>) -> DnsSocketServer {
+        let (_, forwarded_answer_rx) = unbounded();
         DnsSocketServer {
             socket_wrapper,
             buf: [0; 65536],
+            bypass: DnsBypass::new(),
+            forwarder: Box::new(DnsForwarderMock::new()),
+            forwarded_answer_rx,
+            statistics: QueryStatistics::new(),
+            tcp_port: 0,
+            tcp_listener_opt: None,
         }
     }
 }
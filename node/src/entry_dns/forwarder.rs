@@ -0,0 +1,395 @@
+// Copyright (c) 2019-2020, MASQ (https://masq.ai) and/or its affiliates. All rights reserved.
+
+use crate::entry_dns::processing;
+use crate::sub_lib::logger::Logger;
+use futures::sync::mpsc::UnboundedSender;
+use std::io;
+use std::io::{Read, Write};
+use std::net::{SocketAddr, TcpStream, UdpSocket};
+use std::sync::mpsc::{sync_channel, Receiver, SyncSender, TrySendError};
+use std::sync::{Arc, Mutex};
+use std::thread;
+use std::time::Duration;
+
+pub const FORWARD_TIMEOUT_MILLIS: u64 = 2000;
+pub const FORWARDER_WORKER_COUNT: usize = 4;
+pub const FORWARDER_QUEUE_SIZE: usize = 256;
+
+// An answer from upstream, and the client it's meant for
+pub type ForwardedAnswer = (Vec<u8>, SocketAddr);
+
+pub trait DnsForwarder: Send {
+    fn forward(&self, query: Vec<u8>, client_addr: SocketAddr, servers: Vec<SocketAddr>);
+}
+
+struct ForwardRequest {
+    query: Vec<u8>,
+    client_addr: SocketAddr,
+    servers: Vec<SocketAddr>,
+}
+
+// Hands each query to a small, fixed pool of worker threads, so that the DNS server keeps
+// answering other queries meanwhile. A worker asks each upstream server in turn; if none of them
+// answers, or if too many queries are already waiting for a worker, the client gets a server
+// failure. The workers stop when the forwarder is dropped.
+pub struct DnsForwarderReal {
+    request_tx: SyncSender<ForwardRequest>,
+    answer_tx: UnboundedSender<ForwardedAnswer>,
+    logger: Logger,
+}
+
+impl DnsForwarder for DnsForwarderReal {
+    fn forward(&self, query: Vec<u8>, client_addr: SocketAddr, servers: Vec<SocketAddr>) {
+        let request = ForwardRequest {
+            query,
+            client_addr,
+            servers,
+        };
+        match self.request_tx.try_send(request) {
+            Ok(()) => (),
+            Err(TrySendError::Full(request)) => {
+                warning!(
+                    self.logger,
+                    "Too many bypassed queries are waiting for an answer; failing the one from {}",
+                    request.client_addr
+                );
+                // If the DNS server is gone, there's nobody to tell
+                let _ = self
+                    .answer_tx
+                    .unbounded_send((server_failure(&request.query), request.client_addr));
+            }
+            Err(TrySendError::Disconnected(_)) => panic!("DNS forwarder workers are dead"),
+        }
+    }
+}
+
+impl DnsForwarderReal {
+    pub fn new(answer_tx: UnboundedSender<ForwardedAnswer>) -> Self {
+        Self::make(
+            answer_tx,
+            Duration::from_millis(FORWARD_TIMEOUT_MILLIS),
+            FORWARDER_WORKER_COUNT,
+            FORWARDER_QUEUE_SIZE,
+        )
+    }
+
+    fn make(
+        answer_tx: UnboundedSender<ForwardedAnswer>,
+        timeout: Duration,
+        worker_count: usize,
+        queue_size: usize,
+    ) -> Self {
+        let logger = Logger::new("EntryDnsServer");
+        let (request_tx, request_rx) = sync_channel(queue_size);
+        let request_rx = Arc::new(Mutex::new(request_rx));
+        (0..worker_count).for_each(|_| {
+            let request_rx = request_rx.clone();
+            let answer_tx = answer_tx.clone();
+            let logger = logger.clone();
+            thread::spawn(move || Self::work(&request_rx, &answer_tx, timeout, &logger));
+        });
+        Self {
+            request_tx,
+            answer_tx,
+            logger,
+        }
+    }
+
+    fn work(
+        request_rx: &Mutex<Receiver<ForwardRequest>>,
+        answer_tx: &UnboundedSender<ForwardedAnswer>,
+        timeout: Duration,
+        logger: &Logger,
+    ) {
+        loop {
+            let received = request_rx
+                .lock()
+                .expect("DNS forwarder queue is poisoned")
+                .recv();
+            let request = match received {
+                Ok(request) => request,
+                Err(_) => break,
+            };
+            let answer = ask_servers(
+                &request.query,
+                &request.servers,
+                timeout,
+                logger,
+                ask_server_over_udp,
+            );
+            // If the DNS server is gone, there's nobody to tell
+            let _ = answer_tx.unbounded_send((answer, request.client_addr));
+        }
+    }
+}
+
+// For queries that arrived over TCP: the answer may be too big for UDP, so ask over TCP as well.
+// Blocks until an answer arrives or every server has failed.
+pub fn forward_over_tcp(query: &[u8], servers: &[SocketAddr], logger: &Logger) -> Vec<u8> {
+    ask_servers(
+        query,
+        servers,
+        Duration::from_millis(FORWARD_TIMEOUT_MILLIS),
+        logger,
+        ask_server_over_tcp,
+    )
+}
+
+type AskServer = fn(&[u8], SocketAddr, Duration) -> io::Result<Vec<u8>>;
+
+// Asks each server in turn; if none of them answers, the answer is a server failure.
+fn ask_servers(
+    query: &[u8],
+    servers: &[SocketAddr],
+    timeout: Duration,
+    logger: &Logger,
+    ask_server: AskServer,
+) -> Vec<u8> {
+    let answer_opt = servers
+        .iter()
+        .find_map(|server| match ask_server(query, *server, timeout) {
+            Ok(answer) => Some(answer),
+            Err(e) => {
+                warning!(logger, "Bypass DNS server {} didn't answer: {}", server, e);
+                None
+            }
+        });
+    match answer_opt {
+        Some(answer) => answer,
+        None => server_failure(query),
+    }
+}
+
+fn server_failure(query: &[u8]) -> Vec<u8> {
+    let mut failure = query.to_vec();
+    let length = processing::make_server_failure(&mut failure, query.len());
+    failure.truncate(length);
+    failure
+}
+
+fn ask_server_over_udp(query: &[u8], server: SocketAddr, timeout: Duration) -> io::Result<Vec<u8>> {
+    let local_addr = if server.is_ipv4() {
+        "0.0.0.0:0"
+    } else {
+        "[::]:0"
+    };
+    let socket = UdpSocket::bind(local_addr)?;
+    socket.set_read_timeout(Some(timeout))?;
+    socket.connect(server)?;
+    socket.send(query)?;
+    let mut buf = vec![0u8; 65536];
+    loop {
+        let length = socket.recv(&mut buf)?;
+        // Anything that isn't an answer to our query is a stray; keep waiting
+        if is_answer_to(&buf[0..length], query) {
+            buf.truncate(length);
+            return Ok(buf);
+        }
+    }
+}
+
+fn ask_server_over_tcp(query: &[u8], server: SocketAddr, timeout: Duration) -> io::Result<Vec<u8>> {
+    let mut stream = TcpStream::connect_timeout(&server, timeout)?;
+    stream.set_read_timeout(Some(timeout))?;
+    stream.set_write_timeout(Some(timeout))?;
+    write_tcp_message(&mut stream, query)?;
+    let answer = read_tcp_message(&mut stream)?;
+    if is_answer_to(&answer, query) {
+        Ok(answer)
+    } else {
+        Err(io::Error::new(
+            io::ErrorKind::InvalidData,
+            "answer has the wrong transaction ID",
+        ))
+    }
+}
+
+// Over TCP, each DNS message is preceded by its length as a two-byte big-endian number.
+pub fn read_tcp_message(stream: &mut dyn Read) -> io::Result<Vec<u8>> {
+    let mut length_bytes = [0u8; 2];
+    stream.read_exact(&mut length_bytes)?;
+    let mut message = vec![0u8; u16::from_be_bytes(length_bytes) as usize];
+    stream.read_exact(&mut message)?;
+    Ok(message)
+}
+
+pub fn write_tcp_message(stream: &mut dyn Write, message: &[u8]) -> io::Result<()> {
+    let mut framed = (message.len() as u16).to_be_bytes().to_vec();
+    framed.extend_from_slice(message);
+    stream.write_all(&framed)?;
+    stream.flush()
+}
+
+fn is_answer_to(answer: &[u8], query: &[u8]) -> bool {
+    answer.len() >= 2 && query.len() >= 2 && answer[0..2] == query[0..2]
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::entry_dns::packet_facade::PacketFacade;
+    use crate::test_utils::logging::init_test_logging;
+    use crate::test_utils::logging::TestLogHandler;
+    use futures::sync::mpsc::unbounded;
+    use futures::Stream;
+    use std::io::Cursor;
+    use std::net::TcpListener;
+    use trust_dns::op::ResponseCode;
+
+    #[test]
+    fn forward_relays_the_answer_from_the_first_server_that_gives_one() {
+        init_test_logging();
+        let silent_server = UdpSocket::bind("127.0.0.1:0").unwrap();
+        let answering_server = UdpSocket::bind("127.0.0.1:0").unwrap();
+        let servers = vec![
+            silent_server.local_addr().unwrap(),
+            answering_server.local_addr().unwrap(),
+        ];
+        let client_addr = SocketAddr::from(([127, 0, 0, 1], 4567));
+        let (answer_tx, answer_rx) = unbounded();
+        let subject = DnsForwarderReal::make(answer_tx, Duration::from_millis(100), 1, 1);
+        let server_thread = thread::spawn(move || {
+            let mut buf = [0u8; 100];
+            let (length, from) = answering_server.recv_from(&mut buf).unwrap();
+            assert_eq!(&buf[0..length], &[0x12, 0x34, 1, 2, 3]);
+            answering_server.send_to(&[0xFF, 0xFF, 9], from).unwrap();
+            answering_server
+                .send_to(&[0x12, 0x34, 4, 5, 6], from)
+                .unwrap();
+        });
+
+        subject.forward(vec![0x12, 0x34, 1, 2, 3], client_addr, servers.clone());
+
+        server_thread.join().unwrap();
+        let answer = answer_rx.wait().next().unwrap().unwrap();
+        assert_eq!(answer, (vec![0x12, 0x34, 4, 5, 6], client_addr));
+        TestLogHandler::new().exists_log_containing(&format!(
+            "WARN: EntryDnsServer: Bypass DNS server {} didn't answer",
+            servers[0]
+        ));
+    }
+
+    #[test]
+    fn forward_produces_server_failure_if_no_server_answers() {
+        let silent_server = UdpSocket::bind("127.0.0.1:0").unwrap();
+        let client_addr = SocketAddr::from(([127, 0, 0, 1], 4567));
+        let (answer_tx, answer_rx) = unbounded();
+        let subject = DnsForwarderReal::make(answer_tx, Duration::from_millis(100), 1, 1);
+        let mut query = vec![0u8; 100];
+        let length = {
+            let mut facade = PacketFacade::new(&mut query, 100);
+            facade.set_transaction_id(0x1234);
+            facade.set_query(true);
+            facade.add_query("corp.example.com", 1, 1);
+            facade.get_length()
+        };
+        query.truncate(length);
+
+        subject.forward(
+            query,
+            client_addr,
+            vec![silent_server.local_addr().unwrap()],
+        );
+
+        let (mut answer, answer_addr) = answer_rx.wait().next().unwrap().unwrap();
+        assert_eq!(answer_addr, client_addr);
+        let answer_length = answer.len();
+        let facade = PacketFacade::new(&mut answer, answer_length);
+        assert_eq!(facade.get_transaction_id(), Some(0x1234));
+        assert_eq!(facade.get_rcode(), Some(ResponseCode::ServFail.low()));
+        assert_eq!(facade.get_queries().unwrap().len(), 0);
+    }
+
+    #[test]
+    fn forward_fails_query_at_once_when_too_many_are_waiting() {
+        init_test_logging();
+        let client_addr = SocketAddr::from(([127, 0, 0, 1], 4567));
+        let (answer_tx, answer_rx) = unbounded();
+        let subject = DnsForwarderReal::make(answer_tx, Duration::from_millis(100), 0, 1);
+        let mut query = vec![0u8; 100];
+        let length = {
+            let mut facade = PacketFacade::new(&mut query, 100);
+            facade.set_transaction_id(0x1234);
+            facade.set_query(true);
+            facade.add_query("corp.example.com", 1, 1);
+            facade.get_length()
+        };
+        query.truncate(length);
+        let servers = vec![SocketAddr::from(([127, 0, 0, 1], 53))];
+
+        subject.forward(query.clone(), client_addr, servers.clone());
+        subject.forward(query, client_addr, servers);
+
+        let (mut answer, answer_addr) = answer_rx.wait().next().unwrap().unwrap();
+        assert_eq!(answer_addr, client_addr);
+        let answer_length = answer.len();
+        let facade = PacketFacade::new(&mut answer, answer_length);
+        assert_eq!(facade.get_transaction_id(), Some(0x1234));
+        assert_eq!(facade.get_rcode(), Some(ResponseCode::ServFail.low()));
+        TestLogHandler::new().exists_log_containing(&format!(
+            "WARN: EntryDnsServer: Too many bypassed queries are waiting for an answer; failing the one from {}",
+            client_addr
+        ));
+    }
+
+    #[test]
+    fn forward_over_tcp_relays_the_answer_from_the_server() {
+        let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+        let server_addr = listener.local_addr().unwrap();
+        let server_thread = thread::spawn(move || {
+            let (mut stream, _) = listener.accept().unwrap();
+            let query = read_tcp_message(&mut stream).unwrap();
+            assert_eq!(query, vec![0x12, 0x34, 1, 2, 3]);
+            write_tcp_message(&mut stream, &[0x12, 0x34, 4, 5, 6]).unwrap();
+        });
+
+        let result = forward_over_tcp(&[0x12, 0x34, 1, 2, 3], &[server_addr], &Logger::new("test"));
+
+        server_thread.join().unwrap();
+        assert_eq!(result, vec![0x12, 0x34, 4, 5, 6]);
+    }
+
+    #[test]
+    fn forward_over_tcp_produces_server_failure_if_no_server_answers() {
+        init_test_logging();
+        let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+        let server_addr = listener.local_addr().unwrap();
+        drop(listener);
+        let mut query = vec![0u8; 100];
+        let length = {
+            let mut facade = PacketFacade::new(&mut query, 100);
+            facade.set_transaction_id(0x1234);
+            facade.set_query(true);
+            facade.add_query("corp.example.com", 15, 1);
+            facade.get_length()
+        };
+        query.truncate(length);
+
+        let mut result = forward_over_tcp(&query, &[server_addr], &Logger::new("test"));
+
+        let result_length = result.len();
+        let facade = PacketFacade::new(&mut result, result_length);
+        assert_eq!(facade.get_transaction_id(), Some(0x1234));
+        assert_eq!(facade.get_rcode(), Some(ResponseCode::ServFail.low()));
+        TestLogHandler::new().exists_log_containing(&format!(
+            "WARN: test: Bypass DNS server {} didn't answer",
+            server_addr
+        ));
+    }
+
+    #[test]
+    fn tcp_messages_are_framed_with_their_lengths() {
+        let mut written = vec![];
+
+        write_tcp_message(&mut written, &[7, 8, 9]).unwrap();
+
+        assert_eq!(written, vec![0x00, 0x03, 7, 8, 9]);
+        let mut reader = Cursor::new(vec![0x00, 0x02, 7, 8, 9]);
+        assert_eq!(read_tcp_message(&mut reader).unwrap(), vec![7, 8]);
+        let mut short_reader = Cursor::new(vec![0x00, 0x05, 7, 8]);
+        assert_eq!(
+            read_tcp_message(&mut short_reader).unwrap_err().kind(),
+            io::ErrorKind::UnexpectedEof
+        );
+    }
+}
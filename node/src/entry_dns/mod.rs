@@ -2,5 +2,9 @@
 
 #[macro_use]
 pub mod packet_facade; // public only so that it can be used by the integration test
+pub mod bypass;
 pub mod dns_socket_server;
+mod forwarder;
 mod processing;
+mod statistics;
+mod tcp_server;
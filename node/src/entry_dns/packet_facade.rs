@@ -484,6 +484,15 @@ impl<'a> PacketFacade<'a> {
         self.length = 12
     }
 
+    pub fn clear_resource_records(&mut self) -> bool {
+        let queries_end = try_flg!(self.find_queries_end());
+        PacketFacade::u16_to(0x0000, &mut self.buf, 6);
+        PacketFacade::u16_to(0x0000, &mut self.buf, 8);
+        PacketFacade::u16_to(0x0000, &mut self.buf, 10);
+        self.length = queries_end;
+        true
+    }
+
     fn establish_high_water(&mut self, candidate: usize) {
         self.length = max(self.length, candidate);
     }
@@ -1440,6 +1449,44 @@ mod tests {
         }
     }
 
+    #[test]
+    fn clears_resource_records_but_keeps_queries() {
+        let mut buf: [u8; 100] = [0; 100];
+        let queries_end = {
+            let mut subject = PacketFacade::new(&mut buf, 12);
+            subject.set_transaction_id(0x1234);
+            subject.add_query("ooga", 0x0001, 0x0001);
+            let queries_end = subject.get_length();
+            subject.add_answer("ooga", 0x0001, 0x0001, 3600, &[1, 2, 3, 4]);
+            subject.add_authority("booga", 0x0002, 0x0001, 3600, &[5, 6]);
+            subject.add_additional("", 0x0029, 0x1000, 0, &[]);
+            queries_end
+        };
+        let mut subject = PacketFacade::new(&mut buf, 100);
+
+        let result = subject.clear_resource_records();
+
+        assert_eq!(result, true);
+        assert_eq!(subject.get_length(), queries_end);
+        assert_eq!(subject.get_transaction_id(), Some(0x1234));
+        assert_eq!(subject.get_queries().unwrap()[0].get_query_name(), "ooga");
+        assert_eq!(subject.get_answers().unwrap().len(), 0);
+        assert_eq!(subject.get_authorities().unwrap().len(), 0);
+        assert_eq!(subject.get_additionals().unwrap().len(), 0);
+    }
+
+    #[test]
+    fn refuses_to_clear_resource_records_if_queries_are_malformed() {
+        let mut buf: [u8; 100] = [0; 100];
+        buf[5] = 0x01; // one query, but no room for it
+        let mut subject = PacketFacade::new(&mut buf, 12);
+
+        let result = subject.clear_resource_records();
+
+        assert_eq!(result, false);
+        assert_eq!(subject.get_length(), 12);
+    }
+
     #[test]
     fn returns_none_if_getting_transaction_id_busts_length() {
         let mut buf: [u8; 100] = [0; 100];
@@ -1,8 +1,10 @@
 // Copyright (c) 2017-2019, Substratum LLC (https://substratum.net) and/or its affiliates. All rights reserved.
+use super::bypass::{DnsBypass, OtherRecordsPolicy};
 use super::packet_facade::PacketFacade;
 use super::packet_facade::Query;
 use super::packet_facade::ResourceRecord;
 use crate::sub_lib::logger::Logger;
+use std::cmp::{max, min};
 use std::convert::From;
 use std::convert::TryFrom;
 use std::net::SocketAddr;
@@ -15,8 +17,35 @@ use trust_dns::rr::RecordType;
 
 const HEADER_BYTES: usize = 12;
 const UNKNOWN: &str = "<unknown>";
+const TIME_TO_LIVE: u32 = 3600;
+// Largest responses we may send without EDNS0, over UDP and over TCP
+pub const UDP_PAYLOAD_LIMIT: usize = 512;
+pub const TCP_PAYLOAD_LIMIT: usize = 65535;
+// The UDP payload size we advertise to EDNS0 clients: small enough to avoid IP fragmentation
+const EDNS_PAYLOAD_SIZE: u16 = 1232;
+// An OPT record with the root name and no options
+const OPT_RECORD_BYTES: usize = 11;
+// BADVERS is 16: 1 in the extended RCODE bits of the OPT record, 0 in the header
+const EDNS_BAD_VERSION_TTL: u32 = 0x0100_0000;
+// trust_dns doesn't know these yet
+pub const SVCB_RECORD_TYPE: u16 = 64;
+pub const HTTPS_RECORD_TYPE: u16 = 65;
 
-pub fn process(buf: &mut [u8], length: usize, addr: &SocketAddr, logger: &Logger) -> usize {
+struct Edns {
+    payload_size: u16,
+    version: u8,
+}
+
+// transport_limit is the largest response the transport allows without EDNS0: UDP_PAYLOAD_LIMIT
+// or TCP_PAYLOAD_LIMIT. An EDNS0 client can raise it, but never beyond EDNS_PAYLOAD_SIZE.
+pub fn process(
+    buf: &mut [u8],
+    length: usize,
+    transport_limit: usize,
+    policy: OtherRecordsPolicy,
+    addr: &SocketAddr,
+    logger: &Logger,
+) -> usize {
     let mut facade = PacketFacade::new(buf, length);
     let request_record = RequestRecord {
         timestamp: Instant::now(),
@@ -24,7 +53,7 @@ pub fn process(buf: &mut [u8], length: usize, addr: &SocketAddr, logger: &Logger
         queries: facade.get_queries().unwrap_or_default(),
     };
 
-    let response_size = make_response(&mut facade);
+    let response_size = make_response(&mut facade, transport_limit, policy);
 
     let latency = request_record.timestamp.elapsed();
     let response_record = ResponseRecord {
@@ -36,7 +65,44 @@ pub fn process(buf: &mut [u8], length: usize, addr: &SocketAddr, logger: &Logger
     response_size
 }
 
-fn make_response(mut facade: &mut PacketFacade) -> usize {
+// A well-formed query goes to the upstream servers instead of being answered here if it's for a
+// bypassed domain, or if it's for records we can't make up and the policy is to forward those.
+pub fn bypass_requested(buf: &mut [u8], length: usize, bypass: &DnsBypass) -> bool {
+    let facade = PacketFacade::new(buf, length);
+    match (facade.get_opcode(), facade.get_queries()) {
+        (Some(opcode), Some(queries)) if opcode == u8::from(OpCode::Query) => {
+            let forward_others = bypass.other_records() == OtherRecordsPolicy::Forward
+                && !bypass.servers().is_empty();
+            queries.iter().any(|query| {
+                bypass.bypasses(&query.get_query_name())
+                    || (forward_others && !is_synthesized(query))
+            })
+        }
+        _ => false,
+    }
+}
+
+pub fn record_type_name(record_type: u16) -> String {
+    match record_type {
+        SVCB_RECORD_TYPE => "SVCB".to_string(),
+        HTTPS_RECORD_TYPE => "HTTPS".to_string(),
+        _ => match RecordType::from(record_type) {
+            RecordType::Unknown(_) => format!("TYPE{}", record_type),
+            known => known.to_string(),
+        },
+    }
+}
+
+pub fn make_server_failure(buf: &mut [u8], length: usize) -> usize {
+    let mut facade = PacketFacade::new(buf, length);
+    make_error(&mut facade, ResponseCode::ServFail.low())
+}
+
+fn make_response(
+    facade: &mut PacketFacade,
+    transport_limit: usize,
+    policy: OtherRecordsPolicy,
+) -> usize {
     match facade.get_opcode() {
         None => return make_format_error(facade),
         Some(opcode) if opcode == u8::from(OpCode::Query) => (),
@@ -55,33 +121,120 @@ fn make_response(mut facade: &mut PacketFacade) -> usize {
         None => return make_format_error(facade),
         Some(q) => q,
     };
-    for query in queries {
-        if query.get_query_class() != u16::from(DNSClass::IN) {
-            return make_not_implemented_error(facade);
+    let edns_opt = find_edns(facade);
+    if !facade.clear_resource_records() {
+        return make_format_error(facade);
+    }
+    facade.set_rcode(ResponseCode::NoError.low());
+    if let Some(edns) = edns_opt.as_ref() {
+        if edns.version > 0 {
+            add_opt_record(facade, EDNS_BAD_VERSION_TTL);
+            return facade.get_length();
         }
-
+    }
+    for query in queries {
         let resource_type = query.get_query_type();
-        match RecordType::from(resource_type) {
-            RecordType::A => facade.add_answer(
-                &query.get_query_name(),
-                resource_type,
-                DNSClass::IN.into(),
-                3600,
-                &Ipv4Addr::LOCALHOST.octets(),
-            ),
-            RecordType::AAAA => facade.add_answer(
-                &query.get_query_name(),
-                resource_type,
-                DNSClass::IN.into(),
-                3600,
-                &Ipv6Addr::LOCALHOST.octets(),
-            ),
-            _ => return make_not_implemented_error(&mut facade),
+        let rdata = match synthesized_rdata(&query) {
+            Some(rdata) => rdata,
+            None => {
+                if policy == OtherRecordsPolicy::NxDomain {
+                    facade.set_rcode(ResponseCode::NXDomain.low());
+                }
+                continue;
+            }
         };
+        facade.add_answer(
+            &query.get_query_name(),
+            resource_type,
+            DNSClass::IN.into(),
+            TIME_TO_LIVE,
+            &rdata,
+        );
+    }
+    let (payload_limit, opt_bytes) = match edns_opt.as_ref() {
+        Some(edns) => (
+            max(transport_limit, edns_payload_limit(edns.payload_size)),
+            OPT_RECORD_BYTES,
+        ),
+        None => (transport_limit, 0),
+    };
+    if facade.get_length() + opt_bytes > payload_limit {
+        facade.clear_resource_records();
+        facade.set_truncated(true);
+    }
+    if edns_opt.is_some() {
+        add_opt_record(facade, 0);
     }
     facade.get_length()
 }
 
+// The client's advertised size, but no more than we advertise ourselves, and no less than RFC 6891
+// says any client must accept.
+fn edns_payload_limit(client_payload_size: u16) -> usize {
+    max(
+        UDP_PAYLOAD_LIMIT,
+        min(client_payload_size, EDNS_PAYLOAD_SIZE) as usize,
+    )
+}
+
+fn is_synthesized(query: &Query) -> bool {
+    query.get_query_class() == u16::from(DNSClass::IN)
+        && match query.get_query_type() {
+            SVCB_RECORD_TYPE | HTTPS_RECORD_TYPE => true,
+            other => match RecordType::from(other) {
+                RecordType::A | RecordType::AAAA => true,
+                _ => false,
+            },
+        }
+}
+
+fn synthesized_rdata(query: &Query) -> Option<Vec<u8>> {
+    if !is_synthesized(query) {
+        return None;
+    }
+    match query.get_query_type() {
+        SVCB_RECORD_TYPE | HTTPS_RECORD_TYPE => Some(service_binding_rdata()),
+        other => match RecordType::from(other) {
+            RecordType::A => Some(Ipv4Addr::LOCALHOST.octets().to_vec()),
+            _ => Some(Ipv6Addr::LOCALHOST.octets().to_vec()),
+        },
+    }
+}
+
+// A service-mode binding for the queried name itself, with address hints that agree with our A and
+// AAAA answers. There's deliberately no alpn parameter: advertising h3 would send browsers to
+// QUIC over UDP, which the proxy can't carry, and the default of HTTP/1.1 over TCP it can.
+fn service_binding_rdata() -> Vec<u8> {
+    let mut rdata = vec![0x00, 0x01, 0x00]; // priority 1, target name "."
+    rdata.extend_from_slice(&[0x00, 0x04, 0x00, 0x04]); // ipv4hint
+    rdata.extend_from_slice(&Ipv4Addr::LOCALHOST.octets());
+    rdata.extend_from_slice(&[0x00, 0x06, 0x00, 0x10]); // ipv6hint
+    rdata.extend_from_slice(&Ipv6Addr::LOCALHOST.octets());
+    rdata
+}
+
+// Malformed additional records are treated as if the client hadn't used EDNS0.
+fn find_edns(facade: &PacketFacade) -> Option<Edns> {
+    facade
+        .get_additionals()?
+        .into_iter()
+        .find(|record| record.get_resource_type() == u16::from(RecordType::OPT))
+        .map(|record| Edns {
+            payload_size: record.get_resource_class(),
+            version: ((record.get_time_to_live() >> 16) & 0xFF) as u8,
+        })
+}
+
+fn add_opt_record(facade: &mut PacketFacade, time_to_live: u32) {
+    facade.add_additional(
+        "",
+        RecordType::OPT.into(),
+        EDNS_PAYLOAD_SIZE,
+        time_to_live,
+        &[],
+    );
+}
+
 fn display(opcode: u8) -> &'static str {
     match OpCode::from_u8(opcode) {
         Ok(OpCode::Notify) => "Notify",
@@ -126,7 +279,7 @@ fn write_log(from: &RequestRecord, to: &ResponseRecord, addr: &SocketAddr, logge
             };
             query_list += &format!(
                 "{}/{}/{}",
-                RecordType::from(query.get_query_type()),
+                record_type_name(query.get_query_type()),
                 class_string,
                 query.get_query_name()
             );
@@ -188,6 +341,52 @@ mod tests {
     use std::net::SocketAddrV4;
     use std::time::Instant;
 
+    #[test]
+    fn bypass_is_requested_only_for_queries_naming_bypassed_domains() {
+        let bypass = DnsBypass::new();
+        bypass
+            .configure(&["corp.example.com".to_string()], &[])
+            .unwrap();
+        let mut buf: [u8; 500] = [0; 500];
+        let make_query = |buf: &mut [u8], opcode: OpCode, name: &str| {
+            let mut facade = PacketFacade::new(buf, 500);
+            facade.set_transaction_id(0x1234);
+            facade.set_query(true);
+            facade.set_opcode(opcode.into());
+            facade.add_query(name, RecordType::A.into(), DNSClass::IN.into());
+            facade.get_length()
+        };
+
+        let length = make_query(&mut buf, OpCode::Query, "www.corp.example.com");
+        assert_eq!(bypass_requested(&mut buf, length, &bypass), true);
+        let length = make_query(&mut buf, OpCode::Query, "www.example.com");
+        assert_eq!(bypass_requested(&mut buf, length, &bypass), false);
+        let length = make_query(&mut buf, OpCode::Status, "www.corp.example.com");
+        assert_eq!(bypass_requested(&mut buf, length, &bypass), false);
+        assert_eq!(bypass_requested(&mut buf, 5, &bypass), false);
+    }
+
+    #[test]
+    fn make_server_failure_keeps_transaction_id_and_drops_queries() {
+        let mut buf: [u8; 500] = [0; 500];
+        let length = {
+            let mut facade = PacketFacade::new(&mut buf, 500);
+            facade.set_transaction_id(0x1234);
+            facade.set_query(true);
+            facade.set_opcode(OpCode::Query.into());
+            facade.add_query("name", RecordType::A.into(), DNSClass::IN.into());
+            facade.get_length()
+        };
+
+        let result = make_server_failure(&mut buf, length);
+
+        assert_eq!(result, HEADER_BYTES);
+        let facade = PacketFacade::new(&mut buf, result);
+        assert_eq!(facade.get_transaction_id(), Some(0x1234));
+        assert_eq!(facade.is_query(), Some(false));
+        assert_eq!(facade.get_rcode(), Some(ResponseCode::ServFail.low()));
+    }
+
     #[test]
     fn returns_format_error_if_queries_overrun() {
         let memory: [u8; 100] = [0; 100];
@@ -206,7 +405,14 @@ mod tests {
         let truncated_buf = &mut correct_buf[0..truncated_length];
         let addr = SocketAddr::from(SocketAddrV4::new(Ipv4Addr::new(101, 102, 103, 104), 53));
 
-        let result = process(truncated_buf, truncated_length, &addr, &Logger::new(""));
+        let result = process(
+            truncated_buf,
+            truncated_length,
+            UDP_PAYLOAD_LIMIT,
+            OtherRecordsPolicy::Empty,
+            &addr,
+            &Logger::new(""),
+        );
 
         check_format_error_message(truncated_buf, 0x1234);
         assert_eq!(result, HEADER_BYTES);
@@ -228,52 +434,274 @@ mod tests {
         };
         let addr = SocketAddr::from(SocketAddrV4::new(Ipv4Addr::new(101, 102, 103, 104), 53));
 
-        let rsp_length = process(&mut buf, req_length, &addr, &Logger::new(""));
+        let rsp_length = process(
+            &mut buf,
+            req_length,
+            UDP_PAYLOAD_LIMIT,
+            OtherRecordsPolicy::Empty,
+            &addr,
+            &Logger::new(""),
+        );
 
         check_not_implemented_error_message(&mut buf, 0x1234, OpCode::Status.into());
         assert_eq!(rsp_length, HEADER_BYTES);
     }
 
     #[test]
-    fn returns_not_implemented_error_if_query_type_is_other_than_a_or_aaaa() {
+    fn other_record_types_get_empty_answers_by_default() {
         let mut buf: [u8; 500] = [0; 500];
-        let req_length = {
-            let mut facade = PacketFacade::new(&mut buf, 500);
-            facade.set_transaction_id(0x1234);
-            facade.set_query(true);
-            facade.set_recursion_available(false);
-            facade.set_recursion_desired(false);
-            facade.set_opcode(OpCode::Query.into());
-            facade.add_query("name", RecordType::NS.into(), DNSClass::IN.into());
-            facade.get_length()
-        };
-        let addr = SocketAddr::from(SocketAddrV4::new(Ipv4Addr::new(101, 102, 103, 104), 53));
+        let req_length = make_request(&mut buf, vec![("name", RecordType::MX.into())], None);
 
-        let rsp_length = process(&mut buf, req_length, &addr, &Logger::new(""));
+        let rsp_length = process_udp(&mut buf, req_length, OtherRecordsPolicy::Empty);
 
-        check_not_implemented_error_message(&mut buf, 0x1234, OpCode::Query.into());
-        assert_eq!(rsp_length, HEADER_BYTES);
+        let response = PacketFacade::new(&mut buf, rsp_length);
+        assert_eq!(response.get_transaction_id(), Some(0x1234));
+        assert_eq!(response.is_query(), Some(false));
+        assert_eq!(response.get_rcode(), Some(ResponseCode::NoError.low()));
+        assert_eq!(response.get_queries().unwrap()[0].get_query_name(), "name");
+        assert_eq!(response.get_answers().unwrap().len(), 0);
+    }
+
+    #[test]
+    fn other_record_types_get_name_errors_if_policy_says_so() {
+        let mut buf: [u8; 500] = [0; 500];
+        let req_length = make_request(&mut buf, vec![("name", RecordType::TXT.into())], None);
+
+        let rsp_length = process_udp(&mut buf, req_length, OtherRecordsPolicy::NxDomain);
+
+        let response = PacketFacade::new(&mut buf, rsp_length);
+        assert_eq!(response.get_rcode(), Some(ResponseCode::NXDomain.low()));
+        assert_eq!(response.get_queries().unwrap().len(), 1);
+        assert_eq!(response.get_answers().unwrap().len(), 0);
     }
 
     #[test]
-    fn returns_not_implemented_error_if_query_class_is_other_than_in() {
+    fn classes_other_than_in_are_treated_like_other_record_types() {
         let mut buf: [u8; 500] = [0; 500];
         let req_length = {
             let mut facade = PacketFacade::new(&mut buf, 500);
             facade.set_transaction_id(0x1234);
             facade.set_query(true);
-            facade.set_recursion_available(false);
-            facade.set_recursion_desired(false);
             facade.set_opcode(OpCode::Query.into());
+            facade.add_query("version.bind", RecordType::TXT.into(), DNSClass::CH.into());
             facade.add_query("name", RecordType::A.into(), DNSClass::CH.into());
             facade.get_length()
         };
+
+        let rsp_length = process_udp(&mut buf, req_length, OtherRecordsPolicy::NxDomain);
+
+        let response = PacketFacade::new(&mut buf, rsp_length);
+        assert_eq!(response.get_rcode(), Some(ResponseCode::NXDomain.low()));
+        assert_eq!(response.get_answers().unwrap().len(), 0);
+    }
+
+    #[test]
+    fn https_and_svcb_queries_get_service_bindings_without_alpn() {
+        let mut buf: [u8; 500] = [0; 500];
+        let req_length = make_request(
+            &mut buf,
+            vec![
+                ("www.example.com", HTTPS_RECORD_TYPE),
+                ("_dns.resolver.arpa", SVCB_RECORD_TYPE),
+            ],
+            None,
+        );
+
+        let rsp_length = process_udp(&mut buf, req_length, OtherRecordsPolicy::Empty);
+
+        let response = PacketFacade::new(&mut buf, rsp_length);
+        assert_eq!(response.get_rcode(), Some(ResponseCode::NoError.low()));
+        let answers = response.get_answers().unwrap();
+        assert_eq!(answers.len(), 2);
+        assert_eq!(answers[0].get_name(), "www.example.com");
+        assert_eq!(answers[0].get_resource_type(), HTTPS_RECORD_TYPE);
+        assert_eq!(answers[1].get_resource_type(), SVCB_RECORD_TYPE);
+        let expected_rdata: Vec<u8> = vec![
+            0x00, 0x01, // priority
+            0x00, // target name
+            0x00, 0x04, 0x00, 0x04, 127, 0, 0, 1, // ipv4hint
+            0x00, 0x06, 0x00, 0x10, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0,
+            1, // ipv6hint
+        ];
+        assert_eq!(answers[0].get_rdata(), expected_rdata.as_slice());
+        assert_eq!(answers[1].get_rdata(), expected_rdata.as_slice());
+    }
+
+    #[test]
+    fn edns_queries_get_opt_record_in_response() {
+        let mut buf: [u8; 500] = [0; 500];
+        let req_length = make_request(
+            &mut buf,
+            vec![("name", RecordType::A.into())],
+            Some((4096, 0)),
+        );
+
+        let rsp_length = process_udp(&mut buf, req_length, OtherRecordsPolicy::Empty);
+
+        let response = PacketFacade::new(&mut buf, rsp_length);
+        assert_eq!(response.get_rcode(), Some(ResponseCode::NoError.low()));
+        assert_eq!(response.get_answers().unwrap().len(), 1);
+        let additionals = response.get_additionals().unwrap();
+        assert_eq!(additionals.len(), 1);
+        assert_eq!(additionals[0].get_name(), "");
+        assert_eq!(
+            additionals[0].get_resource_type(),
+            u16::from(RecordType::OPT)
+        );
+        assert_eq!(additionals[0].get_resource_class(), EDNS_PAYLOAD_SIZE);
+        assert_eq!(additionals[0].get_time_to_live(), 0);
+    }
+
+    #[test]
+    fn edns_queries_of_unknown_version_get_bad_version_errors() {
+        let mut buf: [u8; 500] = [0; 500];
+        let req_length = make_request(
+            &mut buf,
+            vec![("name", RecordType::A.into())],
+            Some((4096, 0x0001_0000)),
+        );
+
+        let rsp_length = process_udp(&mut buf, req_length, OtherRecordsPolicy::Empty);
+
+        let response = PacketFacade::new(&mut buf, rsp_length);
+        assert_eq!(response.get_rcode(), Some(ResponseCode::NoError.low()));
+        assert_eq!(response.get_answers().unwrap().len(), 0);
+        let additionals = response.get_additionals().unwrap();
+        assert_eq!(additionals.len(), 1);
+        assert_eq!(additionals[0].get_time_to_live(), EDNS_BAD_VERSION_TTL);
+    }
+
+    #[test]
+    fn oversized_udp_responses_are_truncated_but_tcp_responses_are_not() {
+        let names = (0..10)
+            .map(|index| format!("host-{:02}.example.com", index))
+            .collect::<Vec<String>>();
+        let queries = names
+            .iter()
+            .map(|name| (name.as_str(), RecordType::AAAA.into()))
+            .collect::<Vec<(&str, u16)>>();
+        let mut udp_buf: [u8; 4096] = [0; 4096];
+        let udp_req_length = make_request(&mut udp_buf, queries.clone(), None);
+        let mut tcp_buf: [u8; 4096] = [0; 4096];
+        let tcp_req_length = make_request(&mut tcp_buf, queries, None);
         let addr = SocketAddr::from(SocketAddrV4::new(Ipv4Addr::new(101, 102, 103, 104), 53));
 
-        let rsp_length = process(&mut buf, req_length, &addr, &Logger::new(""));
+        let udp_rsp_length = process_udp(&mut udp_buf, udp_req_length, OtherRecordsPolicy::Empty);
+        let tcp_rsp_length = process(
+            &mut tcp_buf,
+            tcp_req_length,
+            TCP_PAYLOAD_LIMIT,
+            OtherRecordsPolicy::Empty,
+            &addr,
+            &Logger::new(""),
+        );
 
-        check_not_implemented_error_message(&mut buf, 0x1234, OpCode::Query.into());
-        assert_eq!(rsp_length, HEADER_BYTES);
+        assert_eq!(udp_rsp_length, udp_req_length);
+        let udp_response = PacketFacade::new(&mut udp_buf, udp_rsp_length);
+        assert_eq!(udp_response.is_truncated(), Some(true));
+        assert_eq!(udp_response.get_queries().unwrap().len(), 10);
+        assert_eq!(udp_response.get_answers().unwrap().len(), 0);
+        assert_eq!(tcp_rsp_length > UDP_PAYLOAD_LIMIT, true);
+        let tcp_response = PacketFacade::new(&mut tcp_buf, tcp_rsp_length);
+        assert_eq!(tcp_response.is_truncated(), Some(false));
+        assert_eq!(tcp_response.get_answers().unwrap().len(), 10);
+    }
+
+    #[test]
+    fn edns_raises_the_udp_size_limit() {
+        let names = (0..10)
+            .map(|index| format!("host-{:02}.example.com", index))
+            .collect::<Vec<String>>();
+        let queries = names
+            .iter()
+            .map(|name| (name.as_str(), RecordType::AAAA.into()))
+            .collect::<Vec<(&str, u16)>>();
+        let mut buf: [u8; 4096] = [0; 4096];
+        let req_length = make_request(&mut buf, queries, Some((4096, 0)));
+
+        let rsp_length = process_udp(&mut buf, req_length, OtherRecordsPolicy::Empty);
+
+        let response = PacketFacade::new(&mut buf, rsp_length);
+        assert_eq!(response.is_truncated(), Some(false));
+        assert_eq!(response.get_answers().unwrap().len(), 10);
+        assert_eq!(response.get_additionals().unwrap().len(), 1);
+    }
+
+    #[test]
+    fn edns_udp_size_limit_is_capped_at_our_payload_size() {
+        let names = (0..30)
+            .map(|index| format!("host-{:02}.example.com", index))
+            .collect::<Vec<String>>();
+        let queries = names
+            .iter()
+            .map(|name| (name.as_str(), RecordType::AAAA.into()))
+            .collect::<Vec<(&str, u16)>>();
+        let mut buf: [u8; 4096] = [0; 4096];
+        let req_length = make_request(&mut buf, queries.clone(), None);
+        let mut tcp_buf = buf;
+        let tcp_rsp_length = process(
+            &mut tcp_buf,
+            req_length,
+            TCP_PAYLOAD_LIMIT,
+            OtherRecordsPolicy::Empty,
+            &SocketAddr::from(SocketAddrV4::new(Ipv4Addr::new(101, 102, 103, 104), 53)),
+            &Logger::new(""),
+        );
+        assert_eq!(tcp_rsp_length > EDNS_PAYLOAD_SIZE as usize, true);
+        let req_length = make_request(&mut buf, queries, Some((4096, 0)));
+
+        let rsp_length = process_udp(&mut buf, req_length, OtherRecordsPolicy::Empty);
+
+        let response = PacketFacade::new(&mut buf, rsp_length);
+        assert_eq!(response.is_truncated(), Some(true));
+        assert_eq!(response.get_answers().unwrap().len(), 0);
+        assert_eq!(response.get_additionals().unwrap().len(), 1);
+    }
+
+    #[test]
+    fn edns_udp_size_limit_is_never_below_512() {
+        let names = (0..5)
+            .map(|index| format!("host-{:02}.example.com", index))
+            .collect::<Vec<String>>();
+        let queries = names
+            .iter()
+            .map(|name| (name.as_str(), RecordType::AAAA.into()))
+            .collect::<Vec<(&str, u16)>>();
+        let mut buf: [u8; 4096] = [0; 4096];
+        let req_length = make_request(&mut buf, queries, Some((200, 0)));
+
+        let rsp_length = process_udp(&mut buf, req_length, OtherRecordsPolicy::Empty);
+
+        assert_eq!(rsp_length > 200 && rsp_length <= UDP_PAYLOAD_LIMIT, true);
+        let response = PacketFacade::new(&mut buf, rsp_length);
+        assert_eq!(response.is_truncated(), Some(false));
+        assert_eq!(response.get_answers().unwrap().len(), 5);
+    }
+
+    #[test]
+    fn other_record_types_are_forwarded_only_if_policy_says_so_and_there_are_servers() {
+        let bypass = DnsBypass::new();
+        let servers = vec![SocketAddr::from(SocketAddrV4::new(
+            Ipv4Addr::new(192, 168, 1, 1),
+            53,
+        ))];
+        let mut buf: [u8; 500] = [0; 500];
+        let mx_length = make_request(&mut buf, vec![("name", RecordType::MX.into())], None);
+        assert_eq!(bypass_requested(&mut buf, mx_length, &bypass), false);
+        bypass.set_other_records(OtherRecordsPolicy::Forward);
+        assert_eq!(bypass_requested(&mut buf, mx_length, &bypass), false);
+        bypass.configure(&[], &servers).unwrap();
+        assert_eq!(bypass_requested(&mut buf, mx_length, &bypass), true);
+        let https_length = make_request(&mut buf, vec![("name", HTTPS_RECORD_TYPE)], None);
+        assert_eq!(bypass_requested(&mut buf, https_length, &bypass), false);
+    }
+
+    #[test]
+    fn record_type_names_cover_service_bindings_and_unknown_types() {
+        assert_eq!(record_type_name(RecordType::AAAA.into()), "AAAA");
+        assert_eq!(record_type_name(HTTPS_RECORD_TYPE), "HTTPS");
+        assert_eq!(record_type_name(SVCB_RECORD_TYPE), "SVCB");
+        assert_eq!(record_type_name(65280), "TYPE65280");
     }
 
     #[test]
@@ -303,6 +731,8 @@ mod tests {
             process(
                 &mut buf,
                 req_length,
+                UDP_PAYLOAD_LIMIT,
+                OtherRecordsPolicy::Empty,
                 &addr,
                 &Logger::new("two_queries_are_answered"),
             )
@@ -538,6 +968,40 @@ mod tests {
         );
     }
 
+    // Each query is a name and a record type in class IN; edns_opt is the OPT record's payload
+    // size and TTL.
+    fn make_request(
+        buf: &mut [u8],
+        queries: Vec<(&str, u16)>,
+        edns_opt: Option<(u16, u32)>,
+    ) -> usize {
+        let buf_length = buf.len();
+        let mut facade = PacketFacade::new(buf, buf_length);
+        facade.set_transaction_id(0x1234);
+        facade.set_query(true);
+        facade.set_opcode(OpCode::Query.into());
+        facade.clear();
+        queries.into_iter().for_each(|(name, record_type)| {
+            facade.add_query(name, record_type, DNSClass::IN.into());
+        });
+        if let Some((payload_size, time_to_live)) = edns_opt {
+            facade.add_additional("", RecordType::OPT.into(), payload_size, time_to_live, &[]);
+        }
+        facade.get_length()
+    }
+
+    fn process_udp(buf: &mut [u8], length: usize, policy: OtherRecordsPolicy) -> usize {
+        let addr = SocketAddr::from(SocketAddrV4::new(Ipv4Addr::new(101, 102, 103, 104), 53));
+        process(
+            buf,
+            length,
+            UDP_PAYLOAD_LIMIT,
+            policy,
+            &addr,
+            &Logger::new(""),
+        )
+    }
+
     fn check_format_error_message(mut buf: &mut [u8], transaction_id: u16) {
         let facade = PacketFacade::new(&mut buf, HEADER_BYTES);
         assert_eq!(facade.get_transaction_id(), Some(transaction_id));
@@ -0,0 +1,226 @@
+// Copyright (c) 2019-2020, MASQ (https://masq.ai) and/or its affiliates. All rights reserved.
+
+use crate::entry_dns::packet_facade::PacketFacade;
+use crate::entry_dns::processing;
+use crate::sub_lib::logger::Logger;
+use std::collections::BTreeMap;
+use std::sync::{Arc, Mutex, MutexGuard};
+use std::time::{Duration, Instant};
+use trust_dns::op::ResponseCode;
+
+pub const STATISTICS_INTERVAL_SECS: u64 = 600;
+
+struct Counts {
+    started: Instant,
+    by_type: BTreeMap<String, u64>,
+    queries: u64,
+    answered: u64,
+    forwarded: u64,
+    errors: u64,
+    truncated: u64,
+    over_tcp: u64,
+}
+
+impl Counts {
+    fn new() -> Self {
+        Self {
+            started: Instant::now(),
+            by_type: BTreeMap::new(),
+            queries: 0,
+            answered: 0,
+            forwarded: 0,
+            errors: 0,
+            truncated: 0,
+            over_tcp: 0,
+        }
+    }
+
+    fn summary(&self, elapsed: Duration) -> String {
+        let by_type = self
+            .by_type
+            .iter()
+            .map(|(record_type, count)| format!("{} {}", record_type, count))
+            .collect::<Vec<String>>()
+            .join(", ");
+        format!(
+            "{} queries in the last {}s ({}): {} answered, {} forwarded, {} errors, {} truncated, {} over TCP",
+            self.queries,
+            elapsed.as_secs(),
+            by_type,
+            self.answered,
+            self.forwarded,
+            self.errors,
+            self.truncated,
+            self.over_tcp
+        )
+    }
+}
+
+// Counts what entry DNS has been asked and how it responded, and logs a summary every so often.
+// Clones share the same counts, so the UDP and TCP servers contribute to one summary.
+#[derive(Clone)]
+pub struct QueryStatistics {
+    counts: Arc<Mutex<Counts>>,
+    interval: Duration,
+}
+
+impl Default for QueryStatistics {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl QueryStatistics {
+    pub fn new() -> Self {
+        Self {
+            counts: Arc::new(Mutex::new(Counts::new())),
+            interval: Duration::from_secs(STATISTICS_INTERVAL_SECS),
+        }
+    }
+
+    // Logs the summary first if it's due, so that each summary covers whole exchanges.
+    pub fn record_query(&self, buf: &mut [u8], length: usize, over_tcp: bool, logger: &Logger) {
+        let facade = PacketFacade::new(buf, length);
+        let mut counts = self.lock();
+        let elapsed = counts.started.elapsed();
+        if elapsed >= self.interval {
+            if counts.queries > 0 {
+                info!(logger, "{}", counts.summary(elapsed));
+            }
+            *counts = Counts::new();
+        }
+        counts.queries += 1;
+        if over_tcp {
+            counts.over_tcp += 1;
+        }
+        facade
+            .get_queries()
+            .unwrap_or_default()
+            .iter()
+            .for_each(|query| {
+                *counts
+                    .by_type
+                    .entry(processing::record_type_name(query.get_query_type()))
+                    .or_insert(0) += 1
+            });
+    }
+
+    pub fn record_forwarded(&self) {
+        self.lock().forwarded += 1;
+    }
+
+    pub fn record_response(&self, buf: &mut [u8], length: usize) {
+        let facade = PacketFacade::new(buf, length);
+        let mut counts = self.lock();
+        if facade.get_rcode() == Some(ResponseCode::NoError.low()) {
+            counts.answered += 1;
+        } else {
+            counts.errors += 1;
+        }
+        if facade.is_truncated() == Some(true) {
+            counts.truncated += 1;
+        }
+    }
+
+    fn lock(&self) -> MutexGuard<Counts> {
+        self.counts.lock().expect("DNS statistics are poisoned")
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_utils::logging::init_test_logging;
+    use crate::test_utils::logging::TestLogHandler;
+    use trust_dns::op::OpCode;
+    use trust_dns::rr::{DNSClass, RecordType};
+
+    fn make_query(buf: &mut [u8], record_types: Vec<u16>) -> usize {
+        let mut facade = PacketFacade::new(buf, 12);
+        facade.clear();
+        facade.set_transaction_id(0x1234);
+        facade.set_query(true);
+        facade.set_opcode(OpCode::Query.into());
+        record_types.into_iter().for_each(|record_type| {
+            facade.add_query("name", record_type, DNSClass::IN.into());
+        });
+        facade.get_length()
+    }
+
+    fn make_response(buf: &mut [u8], rcode: ResponseCode, truncated: bool) -> usize {
+        let mut facade = PacketFacade::new(buf, 12);
+        facade.clear();
+        facade.set_query(false);
+        facade.set_rcode(rcode.low());
+        facade.set_truncated(truncated);
+        facade.get_length()
+    }
+
+    #[test]
+    fn counts_queries_by_type_and_responses_by_outcome() {
+        let subject = QueryStatistics::new();
+        let logger = Logger::new("test");
+        let mut buf = [0u8; 100];
+
+        let length = make_query(
+            &mut buf,
+            vec![RecordType::A.into(), RecordType::AAAA.into()],
+        );
+        subject.record_query(&mut buf, length, false, &logger);
+        let length = make_query(&mut buf, vec![processing::HTTPS_RECORD_TYPE]);
+        subject
+            .clone()
+            .record_query(&mut buf, length, true, &logger);
+        let length = make_query(&mut buf, vec![RecordType::A.into()]);
+        subject.record_query(&mut buf, length, false, &logger);
+        subject.record_forwarded();
+        let length = make_response(&mut buf, ResponseCode::NoError, false);
+        subject.record_response(&mut buf, length);
+        let length = make_response(&mut buf, ResponseCode::NoError, true);
+        subject.record_response(&mut buf, length);
+        let length = make_response(&mut buf, ResponseCode::NXDomain, false);
+        subject.record_response(&mut buf, length);
+
+        assert_eq!(
+            subject.lock().summary(Duration::from_secs(600)),
+            "3 queries in the last 600s (A 2, AAAA 1, HTTPS 1): 2 answered, 1 forwarded, \
+             1 errors, 1 truncated, 1 over TCP"
+        );
+    }
+
+    #[test]
+    fn logs_and_starts_over_when_interval_has_passed() {
+        init_test_logging();
+        let logger = Logger::new("interval_test");
+        let mut subject = QueryStatistics::new();
+        let mut buf = [0u8; 100];
+        let length = make_query(&mut buf, vec![RecordType::MX.into()]);
+        subject.record_query(&mut buf, length, false, &logger);
+        assert_eq!(subject.lock().queries, 1);
+        subject.interval = Duration::from_millis(0);
+        let length = make_query(&mut buf, vec![RecordType::A.into()]);
+
+        subject.record_query(&mut buf, length, false, &logger);
+
+        assert_eq!(subject.lock().queries, 1);
+        assert_eq!(subject.lock().by_type.len(), 1);
+        TestLogHandler::new().exists_log_matching(
+            r"INFO: interval_test: 1 queries in the last \d+s \(MX 1\): 0 answered, 0 forwarded, 0 errors, 0 truncated, 0 over TCP",
+        );
+    }
+
+    #[test]
+    fn logs_nothing_before_the_interval_has_passed() {
+        init_test_logging();
+        let logger = Logger::new("early_test");
+        let subject = QueryStatistics::new();
+        let mut buf = [0u8; 100];
+        let length = make_query(&mut buf, vec![RecordType::MX.into()]);
+
+        subject.record_query(&mut buf, length, false, &logger);
+        subject.record_query(&mut buf, length, false, &logger);
+
+        assert_eq!(subject.lock().queries, 2);
+        TestLogHandler::new().exists_no_log_containing("INFO: early_test:");
+    }
+}
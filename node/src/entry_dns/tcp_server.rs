@@ -0,0 +1,168 @@
+// Copyright (c) 2019-2020, MASQ (https://masq.ai) and/or its affiliates. All rights reserved.
+
+use crate::entry_dns::bypass::DnsBypass;
+use crate::entry_dns::forwarder::{forward_over_tcp, read_tcp_message, write_tcp_message};
+use crate::entry_dns::processing;
+use crate::entry_dns::statistics::QueryStatistics;
+use crate::sub_lib::logger::Logger;
+use std::net::{SocketAddr, TcpListener, TcpStream};
+use std::thread;
+use std::time::Duration;
+
+// Clients that stay quiet this long are disconnected
+pub const IDLE_TIMEOUT_MILLIS: u64 = 10000;
+
+// DNS over TCP, for clients whose answers came back truncated over UDP. Each connection gets a
+// thread of its own, since there are only ever a few of them.
+pub struct DnsTcpServer {
+    listener: TcpListener,
+    bypass: DnsBypass,
+    statistics: QueryStatistics,
+    logger: Logger,
+}
+
+impl DnsTcpServer {
+    pub fn new(listener: TcpListener, bypass: DnsBypass, statistics: QueryStatistics) -> Self {
+        Self {
+            listener,
+            bypass,
+            statistics,
+            logger: Logger::new("EntryDnsServer"),
+        }
+    }
+
+    pub fn start(self) {
+        thread::spawn(move || {
+            for stream_result in self.listener.incoming() {
+                match stream_result {
+                    Ok(stream) => {
+                        let bypass = self.bypass.clone();
+                        let statistics = self.statistics.clone();
+                        let logger = self.logger.clone();
+                        thread::spawn(move || serve(stream, bypass, statistics, logger));
+                    }
+                    Err(e) => warning!(self.logger, "Couldn't accept DNS over TCP: {}", e),
+                }
+            }
+        });
+    }
+}
+
+fn serve(mut stream: TcpStream, bypass: DnsBypass, statistics: QueryStatistics, logger: Logger) {
+    let peer_addr = match stream.peer_addr() {
+        Ok(addr) => addr,
+        Err(_) => return,
+    };
+    if let Err(e) = stream.set_read_timeout(Some(Duration::from_millis(IDLE_TIMEOUT_MILLIS))) {
+        warning!(logger, "Couldn't set timeout for {}: {}", peer_addr, e);
+        return;
+    }
+    // The client closing the connection or going quiet ends the loop
+    while let Ok(query) = read_tcp_message(&mut stream) {
+        let response = respond(query, &peer_addr, &bypass, &statistics, &logger);
+        if let Err(e) = write_tcp_message(&mut stream, &response) {
+            debug!(logger, "Couldn't send DNS answer to {}: {}", peer_addr, e);
+            return;
+        }
+    }
+}
+
+fn respond(
+    mut query: Vec<u8>,
+    peer_addr: &SocketAddr,
+    bypass: &DnsBypass,
+    statistics: &QueryStatistics,
+    logger: &Logger,
+) -> Vec<u8> {
+    let length = query.len();
+    statistics.record_query(&mut query, length, true, logger);
+    if processing::bypass_requested(&mut query, length, bypass) {
+        statistics.record_forwarded();
+        trace!(logger, "{} Query bypasses the MASQ Network", peer_addr);
+        return forward_over_tcp(&query, &bypass.servers(), logger);
+    }
+    // Room for the answers to grow past the query
+    query.resize(processing::TCP_PAYLOAD_LIMIT, 0);
+    let response_length = processing::process(
+        &mut query,
+        length,
+        processing::TCP_PAYLOAD_LIMIT,
+        bypass.other_records(),
+        peer_addr,
+        logger,
+    );
+    query.truncate(response_length);
+    statistics.record_response(&mut query, response_length);
+    query
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::entry_dns::bypass::OtherRecordsPolicy;
+    use crate::entry_dns::packet_facade::PacketFacade;
+    use trust_dns::op::{OpCode, ResponseCode};
+    use trust_dns::rr::{DNSClass, RecordType};
+
+    fn make_query(name: &str, record_type: u16) -> Vec<u8> {
+        let mut query = vec![0u8; 500];
+        let length = {
+            let mut facade = PacketFacade::new(&mut query, 12);
+            facade.set_transaction_id(0x1234);
+            facade.set_query(true);
+            facade.set_opcode(OpCode::Query.into());
+            facade.add_query(name, record_type, DNSClass::IN.into());
+            facade.get_length()
+        };
+        query.truncate(length);
+        query
+    }
+
+    #[test]
+    fn answers_several_queries_on_one_connection() {
+        let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+        let server_addr = listener.local_addr().unwrap();
+        let statistics = QueryStatistics::new();
+        DnsTcpServer::new(listener, DnsBypass::new(), statistics).start();
+        let mut stream = TcpStream::connect(server_addr).unwrap();
+
+        write_tcp_message(&mut stream, &make_query("ooga.com", RecordType::A.into())).unwrap();
+        let mut first = read_tcp_message(&mut stream).unwrap();
+        write_tcp_message(&mut stream, &make_query("booga.com", RecordType::MX.into())).unwrap();
+        let mut second = read_tcp_message(&mut stream).unwrap();
+
+        let first_length = first.len();
+        let first_facade = PacketFacade::new(&mut first, first_length);
+        assert_eq!(first_facade.get_transaction_id(), Some(0x1234));
+        assert_eq!(first_facade.get_rcode(), Some(ResponseCode::NoError.low()));
+        let answers = first_facade.get_answers().unwrap();
+        assert_eq!(answers.len(), 1);
+        assert_eq!(answers[0].get_rdata(), vec![127, 0, 0, 1].as_slice());
+        let second_length = second.len();
+        let second_facade = PacketFacade::new(&mut second, second_length);
+        assert_eq!(second_facade.get_rcode(), Some(ResponseCode::NoError.low()));
+        assert_eq!(second_facade.get_answers().unwrap().len(), 0);
+    }
+
+    #[test]
+    fn respond_follows_other_records_policy() {
+        let bypass = DnsBypass::new();
+        bypass.set_other_records(OtherRecordsPolicy::NxDomain);
+        let statistics = QueryStatistics::new();
+        let peer_addr = SocketAddr::from(([127, 0, 0, 1], 4567));
+
+        let mut result = respond(
+            make_query("booga.com", RecordType::TXT.into()),
+            &peer_addr,
+            &bypass,
+            &statistics,
+            &Logger::new("test"),
+        );
+
+        let result_length = result.len();
+        let facade = PacketFacade::new(&mut result, result_length);
+        assert_eq!(facade.get_transaction_id(), Some(0x1234));
+        assert_eq!(facade.get_rcode(), Some(ResponseCode::NXDomain.low()));
+        assert_eq!(facade.get_queries().unwrap().len(), 1);
+    }
+}
@@ -45,6 +45,7 @@ mod stream_messages;
 mod stream_reader;
 mod stream_writer_sorted;
 mod stream_writer_unsorted;
+pub mod systemd;
 pub mod test_utils;
 pub mod tls_discriminator_factory;
 pub mod ui_gateway;
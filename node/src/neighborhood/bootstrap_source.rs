@@ -0,0 +1,465 @@
+// Copyright (c) 2019-2020, MASQ (https://masq.ai) and/or its affiliates. All rights reserved.
+use crate::sub_lib::cryptde::{CryptDE, CryptData, PlainData, PublicKey};
+use crate::sub_lib::neighborhood::NodeDescriptor;
+use native_tls::TlsConnector;
+use std::fs::File;
+use std::io;
+use std::io::{ErrorKind, Read, Write};
+use std::net::{TcpStream, ToSocketAddrs};
+use std::str::FromStr;
+use std::time::{Duration, Instant};
+
+pub const BOOTSTRAP_SIGNATURE_PREFIX: &str = "signature:";
+pub const BOOTSTRAP_LIST_MAX_BYTES: usize = 64 * 1024;
+// The whole fetch, from connecting to reading the last byte, must be done within this time.
+const BOOTSTRAP_FETCH_TIMEOUT: Duration = Duration::from_secs(30);
+
+// A bootstrap list is a text file with one Node descriptor per line. Blank lines and lines
+// starting with '#' are ignored. If the list is signed, its last line is "signature:" followed
+// by the base64 signature of the descriptor lines joined with newlines.
+pub fn fetch_bootstrap_list(location: &str) -> Result<String, String> {
+    if location.starts_with("http://") || location.starts_with("https://") {
+        fetch_url(location, Instant::now() + BOOTSTRAP_FETCH_TIMEOUT)
+    } else {
+        read_file(location)
+    }
+}
+
+pub fn parse_bootstrap_list(
+    text: &str,
+    cryptde: &dyn CryptDE,
+    public_key_opt: Option<&PublicKey>,
+) -> Result<Vec<NodeDescriptor>, String> {
+    let mut descriptor_lines: Vec<&str> = vec![];
+    let mut signature_opt: Option<&str> = None;
+    for line in text
+        .lines()
+        .map(|line| line.trim())
+        .filter(|line| !line.is_empty() && !line.starts_with('#'))
+    {
+        if line.starts_with(BOOTSTRAP_SIGNATURE_PREFIX) {
+            if signature_opt.is_some() {
+                return Err("Bootstrap list has more than one signature".to_string());
+            }
+            signature_opt = Some(line[BOOTSTRAP_SIGNATURE_PREFIX.len()..].trim());
+        } else if signature_opt.is_some() {
+            return Err("Bootstrap list has descriptors after its signature".to_string());
+        } else {
+            descriptor_lines.push(line);
+        }
+    }
+    if let Some(public_key) = public_key_opt {
+        let signature_str =
+            signature_opt.ok_or_else(|| "Bootstrap list is not signed".to_string())?;
+        let signature = base64::decode(signature_str)
+            .map_err(|_| "Bootstrap list signature is not valid base64".to_string())?;
+        if !cryptde.verify_signature(
+            &signed_data(&descriptor_lines),
+            &CryptData::new(&signature),
+            public_key,
+        ) {
+            return Err(
+                "Bootstrap list signature doesn't match the bootstrap public key".to_string(),
+            );
+        }
+    }
+    descriptor_lines
+        .into_iter()
+        .map(|line| {
+            NodeDescriptor::from_str(cryptde, line)
+                .map_err(|e| format!("Bad descriptor '{}' in bootstrap list: {}", line, e))
+        })
+        .collect()
+}
+
+pub fn make_signed_bootstrap_list(descriptors: &[String], cryptde: &dyn CryptDE) -> String {
+    let lines = descriptors
+        .iter()
+        .map(|descriptor| descriptor.as_str())
+        .collect::<Vec<&str>>();
+    let signature = cryptde
+        .sign(&signed_data(&lines))
+        .expect("Couldn't sign bootstrap list");
+    format!(
+        "{}\n{} {}\n",
+        lines.join("\n"),
+        BOOTSTRAP_SIGNATURE_PREFIX,
+        base64::encode(signature.as_slice())
+    )
+}
+
+fn signed_data(descriptor_lines: &[&str]) -> PlainData {
+    PlainData::from(descriptor_lines.join("\n").into_bytes())
+}
+
+fn read_file(location: &str) -> Result<String, String> {
+    let mut contents = vec![];
+    File::open(location)
+        .and_then(|file| {
+            file.take(BOOTSTRAP_LIST_MAX_BYTES as u64 + 1)
+                .read_to_end(&mut contents)
+        })
+        .map_err(|e| format!("Couldn't read {}: {}", location, e))?;
+    if contents.len() > BOOTSTRAP_LIST_MAX_BYTES {
+        return Err(too_large_message(location));
+    }
+    String::from_utf8(contents).map_err(|_| format!("Bootstrap list at {} is not text", location))
+}
+
+fn too_large_message(location: &str) -> String {
+    format!(
+        "Bootstrap list at {} is larger than {} bytes",
+        location, BOOTSTRAP_LIST_MAX_BYTES
+    )
+}
+
+fn fetch_url(url: &str, deadline: Instant) -> Result<String, String> {
+    let uri =
+        http::Uri::from_str(url).map_err(|e| format!("'{}' is not a valid URL: {}", url, e))?;
+    let host = uri.host().ok_or_else(|| format!("'{}' has no host", url))?;
+    let is_https = uri.scheme_str() == Some("https");
+    let port = uri.port_u16().unwrap_or(if is_https { 443 } else { 80 });
+    let path = uri
+        .path_and_query()
+        .map(|path_and_query| path_and_query.as_str())
+        .unwrap_or("/");
+    let address = (host, port)
+        .to_socket_addrs()
+        .map_err(|e| format!("Couldn't look up {}: {}", host, e))?
+        .next()
+        .ok_or_else(|| format!("Couldn't look up {}: no addresses", host))?;
+    let mut stream = time_left(deadline)
+        .and_then(|time_left| TcpStream::connect_timeout(&address, time_left))
+        .map_err(|e| format!("Couldn't connect to {}: {}", url, e))?;
+    // The socket options are shared with the clone, so it can keep shortening the timeouts even
+    // after the stream itself has been handed over to TLS.
+    let socket = stream
+        .try_clone()
+        .map_err(|e| format!("Couldn't connect to {}: {}", url, e))?;
+    let request = format!(
+        "GET {} HTTP/1.0\r\nHost: {}\r\nConnection: close\r\n\r\n",
+        path, host
+    );
+    let response = if is_https {
+        let connector = TlsConnector::new().map_err(|e| format!("TLS unavailable: {}", e))?;
+        apply_deadline(&socket, deadline)
+            .map_err(|e| format!("TLS handshake with {} failed: {}", host, e))?;
+        let mut tls_stream = connector
+            .connect(host, stream)
+            .map_err(|e| format!("TLS handshake with {} failed: {}", host, e))?;
+        exchange(&mut tls_stream, &socket, deadline, &request)
+    } else {
+        exchange(&mut stream, &socket, deadline, &request)
+    }
+    .map_err(|e| format!("Couldn't retrieve {}: {}", url, e))?;
+    if response_body_len(&response) > BOOTSTRAP_LIST_MAX_BYTES {
+        return Err(too_large_message(url));
+    }
+    http_response_body(url, &response)
+}
+
+fn exchange<S: Read + Write>(
+    stream: &mut S,
+    socket: &TcpStream,
+    deadline: Instant,
+    request: &str,
+) -> Result<Vec<u8>, String> {
+    apply_deadline(socket, deadline)
+        .and_then(|_| stream.write_all(request.as_bytes()))
+        .map_err(|e| e.to_string())?;
+    let mut response = vec![];
+    let mut buffer = [0u8; 4096];
+    loop {
+        if let Some(expected_len) = expected_response_len(&response) {
+            if response.len() >= expected_len {
+                break;
+            }
+        }
+        // Once the list is known to be too large, there's no point reading the rest of it.
+        if response_body_len(&response) > BOOTSTRAP_LIST_MAX_BYTES {
+            break;
+        }
+        match apply_deadline(socket, deadline).and_then(|_| stream.read(&mut buffer)) {
+            Ok(0) => break,
+            Ok(len) => response.extend_from_slice(&buffer[..len]),
+            Err(e) => return Err(e.to_string()),
+        }
+    }
+    Ok(response)
+}
+
+fn time_left(deadline: Instant) -> io::Result<Duration> {
+    let now = Instant::now();
+    if now < deadline {
+        Ok(deadline - now)
+    } else {
+        Err(io::Error::new(
+            ErrorKind::TimedOut,
+            format!(
+                "took longer than {} seconds",
+                BOOTSTRAP_FETCH_TIMEOUT.as_secs()
+            ),
+        ))
+    }
+}
+
+fn apply_deadline(socket: &TcpStream, deadline: Instant) -> io::Result<()> {
+    let time_left = time_left(deadline)?;
+    socket.set_read_timeout(Some(time_left))?;
+    socket.set_write_timeout(Some(time_left))
+}
+
+// Until the headers are complete, everything received counts against the limit.
+fn response_body_len(response: &[u8]) -> usize {
+    match find_header_end(response) {
+        Some(header_end) => response.len() - (header_end + 4),
+        None => response.len(),
+    }
+}
+
+// Some servers don't close the connection after responding, so if the response declares its
+// length we stop reading once we have it all.
+fn expected_response_len(response: &[u8]) -> Option<usize> {
+    let header_end = find_header_end(response)?;
+    let headers = String::from_utf8_lossy(&response[..header_end]);
+    headers.lines().find_map(|line| {
+        let mut pieces = line.splitn(2, ':');
+        let name = pieces.next()?.trim();
+        let value = pieces.next()?.trim();
+        if name.eq_ignore_ascii_case("content-length") {
+            value
+                .parse::<usize>()
+                .ok()
+                .map(|content_len| header_end + 4 + content_len)
+        } else {
+            None
+        }
+    })
+}
+
+fn find_header_end(response: &[u8]) -> Option<usize> {
+    response.windows(4).position(|window| window == b"\r\n\r\n")
+}
+
+fn http_response_body(url: &str, response: &[u8]) -> Result<String, String> {
+    let header_end =
+        find_header_end(response).ok_or_else(|| format!("Malformed HTTP response from {}", url))?;
+    let headers = String::from_utf8_lossy(&response[..header_end]);
+    let status_line = headers.lines().next().unwrap_or("");
+    match status_line.split_whitespace().nth(1) {
+        Some("200") => String::from_utf8(response[(header_end + 4)..].to_vec())
+            .map_err(|_| format!("Bootstrap list at {} is not text", url)),
+        _ => Err(format!("{} responded '{}'", url, status_line)),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::sub_lib::cryptde_null::CryptDENull;
+    use crate::test_utils::main_cryptde;
+    use masq_lib::test_utils::utils::{ensure_node_home_directory_exists, DEFAULT_CHAIN_ID};
+    use masq_lib::utils::find_free_port;
+    use simple_server::Server;
+    use std::fs;
+    use std::net::{Ipv4Addr, TcpListener};
+    use std::thread;
+
+    fn make_descriptors() -> Vec<String> {
+        vec![
+            "AQIDBA:1.2.3.4:1234".to_string(),
+            "AgMEBQ:2.3.4.5:2345/3456".to_string(),
+        ]
+    }
+
+    #[test]
+    fn signed_list_round_trips() {
+        let publisher = CryptDENull::new(DEFAULT_CHAIN_ID);
+        let text = make_signed_bootstrap_list(&make_descriptors(), &publisher);
+        let text = format!("# Bootstrap Nodes\n\n{}", text);
+
+        let result =
+            parse_bootstrap_list(&text, main_cryptde(), Some(publisher.public_key())).unwrap();
+
+        let expected = make_descriptors()
+            .iter()
+            .map(|descriptor| NodeDescriptor::from_str(main_cryptde(), descriptor).unwrap())
+            .collect::<Vec<NodeDescriptor>>();
+        assert_eq!(result, expected);
+    }
+
+    #[test]
+    fn unsigned_list_is_accepted_without_public_key_and_rejected_with_one() {
+        let text = make_descriptors().join("\n");
+
+        let without_key = parse_bootstrap_list(&text, main_cryptde(), None);
+        let with_key = parse_bootstrap_list(&text, main_cryptde(), Some(&PublicKey::new(b"key")));
+
+        assert_eq!(without_key.unwrap().len(), 2);
+        assert_eq!(with_key, Err("Bootstrap list is not signed".to_string()));
+    }
+
+    #[test]
+    fn tampered_list_is_rejected() {
+        let publisher = CryptDENull::new(DEFAULT_CHAIN_ID);
+        let text = make_signed_bootstrap_list(&make_descriptors(), &publisher)
+            .replace("1.2.3.4", "6.6.6.6");
+
+        let result = parse_bootstrap_list(&text, main_cryptde(), Some(publisher.public_key()));
+
+        assert_eq!(
+            result,
+            Err("Bootstrap list signature doesn't match the bootstrap public key".to_string())
+        );
+    }
+
+    #[test]
+    fn malformed_lists_are_rejected() {
+        let parse = |text: &str| parse_bootstrap_list(text, main_cryptde(), None);
+
+        assert_eq!(
+            parse("AQIDBA:1.2.3.4:1234\nsignature: AAAA\nsignature: BBBB"),
+            Err("Bootstrap list has more than one signature".to_string())
+        );
+        assert_eq!(
+            parse("signature: AAAA\nAQIDBA:1.2.3.4:1234"),
+            Err("Bootstrap list has descriptors after its signature".to_string())
+        );
+        match parse("booga") {
+            Err(msg) => assert_eq!(
+                msg.starts_with("Bad descriptor 'booga' in bootstrap list: "),
+                true,
+                "{}",
+                msg
+            ),
+            x => panic!("Expected error, got {:?}", x),
+        }
+    }
+
+    #[test]
+    fn fetch_bootstrap_list_reads_file() {
+        let home_dir = ensure_node_home_directory_exists(
+            "bootstrap_source",
+            "fetch_bootstrap_list_reads_file",
+        );
+        let path = home_dir.join("bootstrap.txt");
+        fs::write(&path, "AQIDBA:1.2.3.4:1234\n").unwrap();
+
+        let result = fetch_bootstrap_list(path.to_str().unwrap());
+
+        assert_eq!(result, Ok("AQIDBA:1.2.3.4:1234\n".to_string()));
+    }
+
+    #[test]
+    fn fetch_bootstrap_list_rejects_oversized_file() {
+        let home_dir = ensure_node_home_directory_exists(
+            "bootstrap_source",
+            "fetch_bootstrap_list_rejects_oversized_file",
+        );
+        let path = home_dir.join("bootstrap.txt");
+        fs::write(&path, vec![b'#'; BOOTSTRAP_LIST_MAX_BYTES + 1]).unwrap();
+        let location = path.to_str().unwrap();
+
+        let result = fetch_bootstrap_list(location);
+
+        assert_eq!(
+            result,
+            Err(format!(
+                "Bootstrap list at {} is larger than 65536 bytes",
+                location
+            ))
+        );
+    }
+
+    #[test]
+    fn fetch_bootstrap_list_rejects_oversized_url() {
+        let port = find_free_port();
+        thread::spawn(move || {
+            Server::new(|_, mut response| {
+                Ok(response.body(vec![b'#'; BOOTSTRAP_LIST_MAX_BYTES + 1])?)
+            })
+            .listen(&Ipv4Addr::LOCALHOST.to_string(), &format!("{}", port));
+        });
+        thread::sleep(Duration::from_millis(500));
+        let url = format!("http://127.0.0.1:{}/bootstrap.txt", port);
+
+        let result = fetch_bootstrap_list(&url);
+
+        assert_eq!(
+            result,
+            Err(format!(
+                "Bootstrap list at {} is larger than 65536 bytes",
+                url
+            ))
+        );
+    }
+
+    #[test]
+    fn fetch_url_gives_up_at_deadline_even_while_data_trickles_in() {
+        let listener = TcpListener::bind((Ipv4Addr::LOCALHOST, 0)).unwrap();
+        let port = listener.local_addr().unwrap().port();
+        thread::spawn(move || {
+            let (mut stream, _) = listener.accept().unwrap();
+            for _ in 0..100 {
+                if stream.write_all(b"H").is_err() {
+                    break;
+                }
+                thread::sleep(Duration::from_millis(50));
+            }
+        });
+        let start = Instant::now();
+
+        let result = fetch_url(
+            &format!("http://127.0.0.1:{}/bootstrap.txt", port),
+            start + Duration::from_millis(500),
+        );
+
+        let elapsed = start.elapsed();
+        match result {
+            Err(msg) => assert_eq!(msg.starts_with("Couldn't retrieve "), true, "{}", msg),
+            x => panic!("Expected error, got {:?}", x),
+        }
+        assert!(elapsed < Duration::from_secs(2), "{:?}", elapsed);
+    }
+
+    #[test]
+    fn fetch_bootstrap_list_retrieves_url() {
+        let port = find_free_port();
+        thread::spawn(move || {
+            Server::new(|request, mut response| {
+                if request.uri().path() == "/bootstrap.txt" {
+                    Ok(response.body(b"AQIDBA:1.2.3.4:1234\n".to_vec())?)
+                } else {
+                    Ok(response.status(404).body(b"Not here".to_vec())?)
+                }
+            })
+            .listen(&Ipv4Addr::LOCALHOST.to_string(), &format!("{}", port));
+        });
+        thread::sleep(Duration::from_millis(500));
+
+        let found = fetch_bootstrap_list(&format!("http://127.0.0.1:{}/bootstrap.txt", port));
+        let missing = fetch_bootstrap_list(&format!("http://127.0.0.1:{}/other.txt", port));
+
+        assert_eq!(found, Ok("AQIDBA:1.2.3.4:1234\n".to_string()));
+        match missing {
+            Err(msg) => assert_eq!(msg.contains("404"), true, "{}", msg),
+            x => panic!("Expected error, got {:?}", x),
+        }
+    }
+
+    #[test]
+    fn http_response_body_complains_about_bad_responses() {
+        assert_eq!(
+            http_response_body("http://booga", b"HTTP/1.1 200 OK"),
+            Err("Malformed HTTP response from http://booga".to_string())
+        );
+        assert_eq!(
+            http_response_body("http://booga", b"HTTP/1.1 500 Oops\r\n\r\nbody"),
+            Err("http://booga responded 'HTTP/1.1 500 Oops'".to_string())
+        );
+        assert_eq!(
+            expected_response_len(b"HTTP/1.1 200 OK\r\nContent-Length: 5\r\n\r\nab"),
+            Some(43)
+        );
+    }
+}
@@ -0,0 +1,230 @@
+// Copyright (c) 2019-2020, MASQ (https://masq.ai) and/or its affiliates. All rights reserved.
+use crate::database::connection_wrapper::ConnectionWrapper;
+use crate::sub_lib::cryptde::PublicKey;
+use lazy_static::lazy_static;
+use rusqlite::{ToSql, NO_PARAMS};
+use std::collections::HashSet;
+use std::sync::RwLock;
+
+lazy_static! {
+    pub static ref DEBUT_ALLOW_LIST: DebutAllowListCache = DebutAllowListCache::default();
+}
+
+// Public keys of Nodes whose debuts are accepted without manual approval. Contains the persistent
+// allow-list, plus any keys approved manually during this run.
+#[derive(Default)]
+pub struct DebutAllowListCache {
+    cache: RwLock<HashSet<PublicKey>>,
+}
+
+impl DebutAllowListCache {
+    pub fn insert(&self, public_key: PublicKey) {
+        self.cache
+            .write()
+            .expect("Failed to insert public key into debut allow-list cache")
+            .insert(public_key);
+    }
+
+    pub fn remove(&self, public_key: &PublicKey) {
+        self.cache
+            .write()
+            .expect("Failed to remove public key from debut allow-list cache")
+            .remove(public_key);
+    }
+
+    pub fn is_allowed(&self, public_key: &PublicKey) -> bool {
+        self.cache
+            .read()
+            .expect("Failed to read from debut allow-list cache")
+            .contains(public_key)
+    }
+}
+
+pub trait DebutAllowListLoader {
+    fn load(&self, conn: Box<dyn ConnectionWrapper>);
+}
+
+pub struct DebutAllowListLoaderReal {}
+
+impl DebutAllowListLoader for DebutAllowListLoaderReal {
+    fn load(&self, conn: Box<dyn ConnectionWrapper>) {
+        DebutAllowListDaoReal::new(conn)
+            .allow_list()
+            .into_iter()
+            .for_each(|public_key| DEBUT_ALLOW_LIST.insert(public_key));
+    }
+}
+
+pub trait DebutAllowListDao: Send {
+    fn allow_list(&self) -> Vec<PublicKey>;
+    fn allow(&self, public_key: &PublicKey);
+    fn disallow(&self, public_key: &PublicKey);
+}
+
+pub struct DebutAllowListDaoReal {
+    conn: Box<dyn ConnectionWrapper>,
+}
+
+impl DebutAllowListDaoReal {
+    pub fn new(conn: Box<dyn ConnectionWrapper>) -> Self {
+        Self { conn }
+    }
+}
+
+impl DebutAllowListDao for DebutAllowListDaoReal {
+    fn allow_list(&self) -> Vec<PublicKey> {
+        let mut stmt = self
+            .conn
+            .prepare("select public_key from debut_allowed order by public_key")
+            .expect("Failed to prepare a statement");
+        stmt.query_map(NO_PARAMS, |row| {
+            let encoded: String = row.get(0)?;
+            Ok(PublicKey::new(
+                &base64::decode_config(&encoded, base64::STANDARD_NO_PAD).unwrap_or_else(|_| {
+                    panic!("Database corrupt: bad allowed debut public key {}", encoded)
+                }),
+            ))
+        })
+        .expect("Couldn't retrieve debut allow-list: database corrupt")
+        .flatten()
+        .collect()
+    }
+
+    fn allow(&self, public_key: &PublicKey) {
+        let mut stmt = self
+            .conn
+            .prepare("insert or ignore into debut_allowed (public_key) values (?)")
+            .expect("Failed to prepare a statement");
+        let params: &[&dyn ToSql] = &[&public_key.to_string()];
+        if let Err(e) = stmt.execute(params) {
+            panic!(
+                "Could not add {} to debut allow-list because of database corruption: {}",
+                public_key, e
+            )
+        }
+        DEBUT_ALLOW_LIST.insert(public_key.clone());
+    }
+
+    fn disallow(&self, public_key: &PublicKey) {
+        let mut stmt = self
+            .conn
+            .prepare("delete from debut_allowed where public_key = ?")
+            .expect("Failed to prepare a statement");
+        let params: &[&dyn ToSql] = &[&public_key.to_string()];
+        if let Err(e) = stmt.execute(params) {
+            panic!(
+                "Could not remove {} from debut allow-list because of database corruption: {}",
+                public_key, e
+            )
+        }
+        DEBUT_ALLOW_LIST.remove(public_key);
+    }
+}
+
+#[cfg(test)]
+pub mod test_utils {
+    use super::DEBUT_ALLOW_LIST;
+    use crate::sub_lib::cryptde::PublicKey;
+
+    // The allow-list cache is shared by every test in the process, so a test that puts keys into
+    // it holds one of these to take them out again when it ends, whether it passes or not.
+    pub struct DebutAllowListGuard {
+        public_keys: Vec<PublicKey>,
+    }
+
+    impl DebutAllowListGuard {
+        // For keys that the code under test will allow
+        pub fn new(public_keys: &[PublicKey]) -> Self {
+            Self {
+                public_keys: public_keys.to_vec(),
+            }
+        }
+
+        pub fn allowing(public_keys: &[PublicKey]) -> Self {
+            public_keys
+                .iter()
+                .for_each(|public_key| DEBUT_ALLOW_LIST.insert(public_key.clone()));
+            Self::new(public_keys)
+        }
+    }
+
+    impl Drop for DebutAllowListGuard {
+        fn drop(&mut self) {
+            self.public_keys
+                .iter()
+                .for_each(|public_key| DEBUT_ALLOW_LIST.remove(public_key));
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::test_utils::DebutAllowListGuard;
+    use super::*;
+    use crate::database::db_initializer::{DbInitializer, DbInitializerReal};
+    use masq_lib::test_utils::utils::{ensure_node_home_directory_exists, DEFAULT_CHAIN_ID};
+
+    fn make_subject(test_name: &str) -> DebutAllowListDaoReal {
+        let home_dir = ensure_node_home_directory_exists("debut_allow_list_dao", test_name);
+        let conn = DbInitializerReal::new()
+            .initialize(&home_dir, DEFAULT_CHAIN_ID, true)
+            .unwrap();
+        DebutAllowListDaoReal::new(conn)
+    }
+
+    #[test]
+    fn allow_persists_and_caches_public_key() {
+        let subject = make_subject("allow_persists_and_caches_public_key");
+        let first = PublicKey::new(&[1, 7, 3, 1]);
+        let second = PublicKey::new(&[2, 7, 3, 1]);
+        let _guard = DebutAllowListGuard::new(&[first.clone(), second.clone()]);
+
+        subject.allow(&second);
+        subject.allow(&first);
+        subject.allow(&first);
+
+        assert_eq!(subject.allow_list(), vec![first.clone(), second.clone()]);
+        assert!(DEBUT_ALLOW_LIST.is_allowed(&first));
+        assert!(DEBUT_ALLOW_LIST.is_allowed(&second));
+    }
+
+    #[test]
+    fn disallow_removes_public_key_from_database_and_cache() {
+        let subject = make_subject("disallow_removes_public_key_from_database_and_cache");
+        let public_key = PublicKey::new(&[3, 7, 3, 1]);
+        let _guard = DebutAllowListGuard::new(&[public_key.clone()]);
+        subject.allow(&public_key);
+
+        subject.disallow(&public_key);
+
+        assert_eq!(subject.allow_list(), vec![]);
+        assert!(!DEBUT_ALLOW_LIST.is_allowed(&public_key));
+    }
+
+    #[test]
+    fn loader_loads_allow_list_into_cache() {
+        let home_dir = ensure_node_home_directory_exists(
+            "debut_allow_list_dao",
+            "loader_loads_allow_list_into_cache",
+        );
+        let db_initializer = DbInitializerReal::new();
+        let public_key = PublicKey::new(&[4, 7, 3, 1]);
+        let _guard = DebutAllowListGuard::new(&[public_key.clone()]);
+        DebutAllowListDaoReal::new(
+            db_initializer
+                .initialize(&home_dir, DEFAULT_CHAIN_ID, true)
+                .unwrap(),
+        )
+        .allow(&public_key);
+        DEBUT_ALLOW_LIST.remove(&public_key);
+        let subject = DebutAllowListLoaderReal {};
+
+        subject.load(
+            db_initializer
+                .initialize(&home_dir, DEFAULT_CHAIN_ID, true)
+                .unwrap(),
+        );
+
+        assert!(DEBUT_ALLOW_LIST.is_allowed(&public_key));
+    }
+}
@@ -0,0 +1,216 @@
+// Copyright (c) 2019-2020, MASQ (https://masq.ai) and/or its affiliates. All rights reserved.
+use masq_lib::exit_country::ExitCountryPreference;
+use std::fs;
+use std::io::ErrorKind;
+use std::net::IpAddr;
+use std::path::Path;
+use std::str::FromStr;
+
+pub const GEOIP_DATABASE_FILENAME: &str = "geoip.csv";
+
+// An offline IP-to-country table, read from a CSV file in the data directory. Each line holds
+// the first address of a range, the last address of the range, and the two-letter country code
+// for the range: for example, 1.0.0.0,1.0.0.255,AU. This is the layout of the freely-available
+// "IP to Country Lite" databases; IPv4 and IPv6 ranges may be mixed.
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct GeoIpDatabase {
+    ranges: Vec<(u128, u128, String)>,
+}
+
+impl GeoIpDatabase {
+    pub fn load(data_directory: &Path) -> Result<Option<GeoIpDatabase>, String> {
+        let path = data_directory.join(GEOIP_DATABASE_FILENAME);
+        match fs::read_to_string(&path) {
+            Ok(text) => GeoIpDatabase::from_csv(&text)
+                .map(Some)
+                .map_err(|e| format!("{}: {}", path.display(), e)),
+            Err(e) if e.kind() == ErrorKind::NotFound => Ok(None),
+            Err(e) => Err(format!("{}: {}", path.display(), e)),
+        }
+    }
+
+    pub fn from_csv(text: &str) -> Result<GeoIpDatabase, String> {
+        let mut ranges = text
+            .lines()
+            .enumerate()
+            .filter(|(_, line)| !line.trim().is_empty())
+            .map(|(index, line)| {
+                Self::parse_line(line).map_err(|e| format!("line {}: {}", index + 1, e))
+            })
+            .collect::<Result<Vec<(u128, u128, String)>, String>>()?;
+        ranges.sort_by_key(|(first, _, _)| *first);
+        Ok(GeoIpDatabase { ranges })
+    }
+
+    pub fn len(&self) -> usize {
+        self.ranges.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.ranges.is_empty()
+    }
+
+    pub fn country_of(&self, ip_addr: IpAddr) -> Option<&str> {
+        let key = Self::key(ip_addr);
+        let index = match self
+            .ranges
+            .binary_search_by_key(&key, |(first, _, _)| *first)
+        {
+            Ok(index) => index,
+            Err(0) => return None,
+            Err(insertion_point) => insertion_point - 1,
+        };
+        let (_, last, country) = &self.ranges[index];
+        if key <= *last {
+            Some(country.as_str())
+        } else {
+            None
+        }
+    }
+
+    fn parse_line(line: &str) -> Result<(u128, u128, String), String> {
+        let fields = line
+            .split(',')
+            .map(|field| field.trim().trim_matches('"'))
+            .collect::<Vec<&str>>();
+        if fields.len() != 3 {
+            return Err(format!(
+                "expected first address, last address, and country code; found '{}'",
+                line
+            ));
+        }
+        let parse_ip = |field: &str| {
+            IpAddr::from_str(field).map_err(|_| format!("'{}' is not an IP address", field))
+        };
+        let first = parse_ip(fields[0])?;
+        let last = parse_ip(fields[1])?;
+        if first.is_ipv4() != last.is_ipv4() {
+            return Err(format!(
+                "range {} - {} mixes IPv4 and IPv6 addresses",
+                first, last
+            ));
+        }
+        let (first, last) = (Self::key(first), Self::key(last));
+        if first > last {
+            return Err(format!("range {} - {} is backwards", fields[0], fields[1]));
+        }
+        let country = ExitCountryPreference::normalize_country_code(fields[2])?;
+        Ok((first, last, country))
+    }
+
+    // IPv4 addresses are looked up as IPv4-mapped IPv6 addresses, so both kinds share one table.
+    fn key(ip_addr: IpAddr) -> u128 {
+        match ip_addr {
+            IpAddr::V4(ipv4) => u128::from(ipv4.to_ipv6_mapped()),
+            IpAddr::V6(ipv6) => u128::from(ipv6),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use masq_lib::test_utils::utils::ensure_node_home_directory_exists;
+
+    const CSV: &str = "\
+1.0.0.0,1.0.0.255,AU
+\"2.16.0.0\",\"2.16.5.255\",\"cz\"
+
+1.0.4.0,1.0.7.255,AU
+2001:200::,2001:200:ffff:ffff:ffff:ffff:ffff:ffff,JP
+";
+
+    #[test]
+    fn country_of_finds_countries_inside_ranges() {
+        let subject = GeoIpDatabase::from_csv(CSV).unwrap();
+
+        assert_eq!(subject.len(), 4);
+        assert_eq!(
+            subject.country_of(IpAddr::from_str("1.0.0.0").unwrap()),
+            Some("AU")
+        );
+        assert_eq!(
+            subject.country_of(IpAddr::from_str("2.16.3.4").unwrap()),
+            Some("CZ")
+        );
+        assert_eq!(
+            subject.country_of(IpAddr::from_str("1.0.7.255").unwrap()),
+            Some("AU")
+        );
+        assert_eq!(
+            subject.country_of(IpAddr::from_str("2001:200::1234").unwrap()),
+            Some("JP")
+        );
+    }
+
+    #[test]
+    fn country_of_finds_nothing_outside_ranges() {
+        let subject = GeoIpDatabase::from_csv(CSV).unwrap();
+
+        assert_eq!(
+            subject.country_of(IpAddr::from_str("0.255.255.255").unwrap()),
+            None
+        );
+        assert_eq!(
+            subject.country_of(IpAddr::from_str("1.0.2.0").unwrap()),
+            None
+        );
+        assert_eq!(
+            subject.country_of(IpAddr::from_str("2.16.6.0").unwrap()),
+            None
+        );
+        assert_eq!(subject.country_of(IpAddr::from_str("::1").unwrap()), None);
+    }
+
+    #[test]
+    fn from_csv_complains_about_bad_lines() {
+        assert_eq!(
+            GeoIpDatabase::from_csv("1.0.0.0,1.0.0.255,AU\n1.0.1.0,1.0.1.255\n"),
+            Err("line 2: expected first address, last address, and country code; found '1.0.1.0,1.0.1.255'".to_string())
+        );
+        assert_eq!(
+            GeoIpDatabase::from_csv("1.0.0.0,booga,AU"),
+            Err("line 1: 'booga' is not an IP address".to_string())
+        );
+        assert_eq!(
+            GeoIpDatabase::from_csv("1.0.0.255,1.0.0.0,AU"),
+            Err("line 1: range 1.0.0.255 - 1.0.0.0 is backwards".to_string())
+        );
+        assert_eq!(
+            GeoIpDatabase::from_csv("1.0.0.0,::1,AU"),
+            Err("line 1: range 1.0.0.0 - ::1 mixes IPv4 and IPv6 addresses".to_string())
+        );
+        assert_eq!(
+            GeoIpDatabase::from_csv("1.0.0.0,1.0.0.255,Australia"),
+            Err(
+                "line 1: Exit country must be a two-letter ISO 3166 country code, not 'Australia'"
+                    .to_string()
+            )
+        );
+    }
+
+    #[test]
+    fn load_returns_none_when_there_is_no_database_file() {
+        let data_dir = ensure_node_home_directory_exists(
+            "geoip",
+            "load_returns_none_when_there_is_no_database_file",
+        );
+
+        let result = GeoIpDatabase::load(&data_dir);
+
+        assert_eq!(result, Ok(None));
+    }
+
+    #[test]
+    fn load_reads_database_file_from_data_directory() {
+        let data_dir = ensure_node_home_directory_exists(
+            "geoip",
+            "load_reads_database_file_from_data_directory",
+        );
+        fs::write(data_dir.join(GEOIP_DATABASE_FILENAME), CSV).unwrap();
+
+        let result = GeoIpDatabase::load(&data_dir);
+
+        assert_eq!(result, Ok(Some(GeoIpDatabase::from_csv(CSV).unwrap())));
+    }
+}
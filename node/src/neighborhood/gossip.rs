@@ -1,6 +1,6 @@
 // Copyright (c) 2017-2019, Substratum LLC (https://substratum.net) and/or its affiliates. All rights reserved.
 use super::node_record::NodeRecord;
-use super::node_record::NodeRecordInner_0v1;
+use super::node_record::NodeRecordInner_0v2;
 use crate::neighborhood::dot_graph::{
     render_dot_graph, DotRenderable, EdgeRenderable, NodeRenderable, NodeRenderableInner,
 };
@@ -48,8 +48,8 @@ impl From<(&NeighborhoodDatabase, &PublicKey, bool)> for GossipNodeRecord {
     }
 }
 
-impl From<(NodeRecordInner_0v1, Option<NodeAddr>, &dyn CryptDE)> for GossipNodeRecord {
-    fn from(triple: (NodeRecordInner_0v1, Option<NodeAddr>, &dyn CryptDE)) -> Self {
+impl From<(NodeRecordInner_0v2, Option<NodeAddr>, &dyn CryptDE)> for GossipNodeRecord {
+    fn from(triple: (NodeRecordInner_0v2, Option<NodeAddr>, &dyn CryptDE)) -> Self {
         let (inner, node_addr_opt, cryptde) = triple;
         let signed_data =
             PlainData::from(serde_cbor::to_vec(&inner).expect("Serialization failed"));
@@ -98,9 +98,9 @@ impl GossipNodeRecord {
     fn to_human_readable(&self) -> String {
         let mut human_readable = String::new();
         human_readable.push_str("\nGossipNodeRecord {");
-        match NodeRecordInner_0v1::try_from(self) {
+        match NodeRecordInner_0v2::try_from(self) {
             Ok(nri) => {
-                human_readable.push_str("\n\tinner: NodeRecordInner_0v1 {");
+                human_readable.push_str("\n\tinner: NodeRecordInner_0v2 {");
                 human_readable.push_str(&format!("\n\t\tpublic_key: {:?},", &nri.public_key));
                 human_readable.push_str(&format!("\n\t\tnode_addr_opt: {:?},", self.node_addr_opt));
                 human_readable
@@ -341,11 +341,11 @@ impl Gossip_0v1 {
         let mut present: HashSet<PublicKey> = HashSet::new();
         let mut node_renderables: Vec<NodeRenderable> = vec![];
         let mut edge_renderables: Vec<EdgeRenderable> = vec![];
-        let inners_and_addrs: Vec<(NodeRecordInner_0v1, Option<NodeAddr>)> = self
+        let inners_and_addrs: Vec<(NodeRecordInner_0v2, Option<NodeAddr>)> = self
             .node_records
             .iter()
             .map(|gnr| {
-                let nri = match NodeRecordInner_0v1::try_from(gnr) {
+                let nri = match NodeRecordInner_0v2::try_from(gnr) {
                     Ok(nri) => nri,
                     Err(e) => unimplemented!("{:?}", e),
                 };
@@ -597,7 +597,7 @@ mod tests {
 
         assert_eq!(
             Err(String::from(
-                "invalid type: integer `1`, expected struct NodeRecordInner_0v1"
+                "invalid type: integer `1`, expected struct NodeRecordInner_0v2"
             )),
             result
         );
@@ -609,13 +609,16 @@ mod tests {
         let mut db = db_from_node(&node);
         db.root_mut().increment_version();
         db.root_mut().increment_version();
+        // The software version changes with every release, and would change the dump with it
+        db.root_mut().inner.proxy_protocols_opt = None;
+        db.root_mut().inner.software_version_opt = None;
         db.root_mut().resign();
         let gossip = GossipNodeRecord::from((&db, node.public_key(), true));
 
         let result = format!("{:?}", gossip);
         let expected = format!(
             "\nGossipNodeRecord {{{}{}{}{}\n}}",
-            "\n\tinner: NodeRecordInner_0v1 {\n\t\tpublic_key: AQIDBA,\n\t\tnode_addr_opt: Some(1.2.3.4:[1234]),\n\t\tearning_wallet: Wallet { kind: Address(0x546900db8d6e0937497133d1ae6fdf5f4b75bcd0) },\n\t\trate_pack: RatePack { routing_byte_rate: 1235, routing_service_rate: 1236, exit_byte_rate: 1237, exit_service_rate: 1238 },\n\t\tneighbors: [],\n\t\tversion: 2,\n\t},",
+            "\n\tinner: NodeRecordInner_0v2 {\n\t\tpublic_key: AQIDBA,\n\t\tnode_addr_opt: Some(1.2.3.4:[1234]),\n\t\tearning_wallet: Wallet { kind: Address(0x546900db8d6e0937497133d1ae6fdf5f4b75bcd0) },\n\t\trate_pack: RatePack { routing_byte_rate: 1235, routing_service_rate: 1236, exit_byte_rate: 1237, exit_service_rate: 1238 },\n\t\tneighbors: [],\n\t\tversion: 2,\n\t},",
             "\n\tnode_addr_opt: Some(1.2.3.4:[1234]),",
             "\n\tsigned_data:
 Length: 229 (0xe5) bytes
@@ -1,12 +1,14 @@
 // Copyright (c) 2017-2019, Substratum LLC (https://substratum.net) and/or its affiliates. All rights reserved.
 
+use crate::neighborhood::debut_allow_list_dao::DEBUT_ALLOW_LIST;
 use crate::neighborhood::gossip::{GossipBuilder, Gossip_0v1};
+use crate::neighborhood::malefactor_ban_dao::{Malefactor, MALEFACTOR_BAN_CACHE};
 use crate::neighborhood::neighborhood_database::{NeighborhoodDatabase, NeighborhoodDatabaseError};
 use crate::neighborhood::node_record::NodeRecord;
 use crate::neighborhood::AccessibleGossipRecord;
 use crate::sub_lib::cryptde::{CryptDE, PublicKey};
 use crate::sub_lib::logger::Logger;
-use crate::sub_lib::neighborhood::GossipFailure_0v1;
+use crate::sub_lib::neighborhood::{DebutApproval, GossipFailure_0v1};
 use crate::sub_lib::node_addr::NodeAddr;
 use std::collections::HashSet;
 use std::net::{IpAddr, SocketAddr};
@@ -28,6 +30,8 @@ pub enum GossipAcceptanceResult {
     Ignored,
     // Gossip was ignored because it was evil: ban the sender of the Gossip as a malefactor.
     Ban(String),
+    // The incoming Gossip was a debut that must be approved manually before it can be accepted.
+    AwaitingApproval(AccessibleGossipRecord, SocketAddr),
 }
 
 #[derive(Clone, PartialEq, Debug)]
@@ -58,6 +62,7 @@ trait GossipHandler: NamedType + Send /* Send because lazily-written tests requi
 }
 
 struct DebutHandler {
+    debut_approval: DebutApproval,
     logger: Logger,
 }
 
@@ -156,6 +161,16 @@ impl GossipHandler for DebutHandler {
                 source_node_addr,
             );
         }
+        if self.debut_approval == DebutApproval::Manual && !DEBUT_ALLOW_LIST.is_allowed(&source_key)
+        {
+            debug!(
+                self.logger,
+                "DebutHandler is holding debut from {} at {} for manual approval",
+                source_key,
+                source_node_addr
+            );
+            return GossipAcceptanceResult::AwaitingApproval(source_agr, gossip_source);
+        }
         if let Ok(result) = self.try_accept_debut(cryptde, database, &source_agr, gossip_source) {
             return result;
         }
@@ -205,8 +220,21 @@ impl GossipHandler for DebutHandler {
 }
 
 impl DebutHandler {
-    fn new(logger: Logger) -> DebutHandler {
-        DebutHandler { logger }
+    fn new(debut_approval: DebutApproval, logger: Logger) -> DebutHandler {
+        DebutHandler {
+            debut_approval,
+            logger,
+        }
+    }
+
+    fn find_poor_quality_neighbor(database: &NeighborhoodDatabase) -> Option<PublicKey> {
+        database
+            .root()
+            .full_neighbors(database)
+            .into_iter()
+            .filter(|node| node.quality().is_poor())
+            .max_by_key(|node| (node.quality().penalty(), node.public_key().clone()))
+            .map(|node| node.public_key().clone())
     }
 
     fn find_more_appropriate_neighbor<'b>(
@@ -259,8 +287,21 @@ impl DebutHandler {
         gossip_source: SocketAddr,
     ) -> Result<GossipAcceptanceResult, ()> {
         if database.gossip_target_degree(database.root().public_key()) >= MAX_DEGREE {
-            debug!(self.logger, "Neighbor count already at maximum");
-            return Err(());
+            match Self::find_poor_quality_neighbor(database) {
+                Some(poor_key) => {
+                    debug!(
+                        self.logger,
+                        "Neighbor count already at maximum; dropping poor-quality neighbor {} to make room for {}",
+                        poor_key,
+                        debuting_agr.inner.public_key
+                    );
+                    database.root_mut().remove_half_neighbor_key(&poor_key);
+                }
+                None => {
+                    debug!(self.logger, "Neighbor count already at maximum");
+                    return Err(());
+                }
+            }
         }
         let debut_node_addr_opt = debuting_agr.node_addr_opt.clone();
         let debuting_node = NodeRecord::from(debuting_agr);
@@ -1042,6 +1083,13 @@ impl<'a> GossipAcceptor for GossipAcceptorReal<'a> {
         agrs: Vec<AccessibleGossipRecord>,
         gossip_source: SocketAddr,
     ) -> GossipAcceptanceResult {
+        if MALEFACTOR_BAN_CACHE.is_banned(&Malefactor::from_gossip_source(&agrs, gossip_source)) {
+            debug!(
+                self.logger,
+                "Ignoring Gossip from banned malefactor at {}", gossip_source
+            );
+            return GossipAcceptanceResult::Ignored;
+        }
         let (qualification, handler_ref) = self
             .gossip_handlers
             .iter()
@@ -1066,11 +1114,11 @@ impl<'a> GossipAcceptor for GossipAcceptorReal<'a> {
 }
 
 impl<'a> GossipAcceptorReal<'a> {
-    pub fn new(cryptde: &'a dyn CryptDE) -> GossipAcceptorReal {
+    pub fn new(cryptde: &'a dyn CryptDE, debut_approval: DebutApproval) -> GossipAcceptorReal {
         let logger = Logger::new("GossipAcceptor");
         GossipAcceptorReal {
             gossip_handlers: vec![
-                Box::new(DebutHandler::new(logger.clone())),
+                Box::new(DebutHandler::new(debut_approval, logger.clone())),
                 Box::new(PassHandler::new()),
                 Box::new(IntroductionHandler::new(logger.clone())),
                 Box::new(StandardGossipHandler::new(logger.clone())),
@@ -1117,8 +1165,10 @@ impl<'a> GossipAcceptorReal<'a> {
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::neighborhood::debut_allow_list_dao::test_utils::DebutAllowListGuard;
     use crate::neighborhood::gossip_producer::GossipProducer;
     use crate::neighborhood::gossip_producer::GossipProducerReal;
+    use crate::neighborhood::malefactor_ban_dao::MalefactorBan;
     use crate::neighborhood::node_record::NodeRecord;
     use crate::sub_lib::cryptde_null::CryptDENull;
     use crate::sub_lib::utils::time_t_timestamp;
@@ -1129,6 +1179,7 @@ mod tests {
     use masq_lib::test_utils::utils::DEFAULT_CHAIN_ID;
     use std::convert::TryInto;
     use std::str::FromStr;
+    use std::time::SystemTime;
 
     #[derive(Clone, Copy, Debug, PartialEq)]
     enum Mode {
@@ -1147,7 +1198,7 @@ mod tests {
         db.add_arbitrary_full_neighbor(root_node.public_key(), neighbor_key);
         let cryptde = CryptDENull::from(db.root().public_key(), DEFAULT_CHAIN_ID);
         let agrs_vec: Vec<AccessibleGossipRecord> = gossip.try_into().unwrap();
-        let subject = DebutHandler::new(Logger::new("test"));
+        let subject = DebutHandler::new(DebutApproval::Automatic, Logger::new("test"));
 
         let qualifies_result =
             subject.qualifies(&db, &agrs_vec.as_slice(), gossip_source_opt.clone());
@@ -1168,6 +1219,41 @@ mod tests {
         );
     }
 
+    #[test]
+    fn debut_is_held_for_approval_when_approval_is_manual() {
+        let (gossip, new_node, gossip_source) = make_debut(5757, Mode::Standard);
+        let root_node = make_node_record(1234, true);
+        let mut db = db_from_node(&root_node);
+        let cryptde = CryptDENull::from(db.root().public_key(), DEFAULT_CHAIN_ID);
+        let agrs_vec: Vec<AccessibleGossipRecord> = gossip.try_into().unwrap();
+        let expected_agr = agrs_vec[0].clone();
+        let subject = DebutHandler::new(DebutApproval::Manual, Logger::new("test"));
+
+        let result = subject.handle(&cryptde, &mut db, agrs_vec, gossip_source);
+
+        assert_eq!(
+            result,
+            GossipAcceptanceResult::AwaitingApproval(expected_agr, gossip_source)
+        );
+        assert_eq!(db.node_by_key(new_node.public_key()), None);
+    }
+
+    #[test]
+    fn debut_from_allowed_node_is_accepted_when_approval_is_manual() {
+        let (gossip, new_node, gossip_source) = make_debut(5858, Mode::Standard);
+        let _guard = DebutAllowListGuard::allowing(&[new_node.public_key().clone()]);
+        let root_node = make_node_record(1234, true);
+        let mut db = db_from_node(&root_node);
+        let cryptde = CryptDENull::from(db.root().public_key(), DEFAULT_CHAIN_ID);
+        let agrs_vec: Vec<AccessibleGossipRecord> = gossip.try_into().unwrap();
+        let subject = DebutHandler::new(DebutApproval::Manual, Logger::new("test"));
+
+        let result = subject.handle(&cryptde, &mut db, agrs_vec, gossip_source);
+
+        assert_eq!(result, GossipAcceptanceResult::Accepted);
+        assert!(db.has_half_neighbor(root_node.public_key(), new_node.public_key()));
+    }
+
     #[test]
     fn proper_debut_of_non_accepting_node_with_populated_database_is_identified_and_handled() {
         let (gossip, new_node, gossip_source) = make_debut(2345, Mode::OriginateOnly);
@@ -1177,7 +1263,7 @@ mod tests {
         db.add_arbitrary_full_neighbor(root_node.public_key(), neighbor_key);
         let cryptde = CryptDENull::from(db.root().public_key(), DEFAULT_CHAIN_ID);
         let agrs_vec: Vec<AccessibleGossipRecord> = gossip.try_into().unwrap();
-        let subject = DebutHandler::new(Logger::new("test"));
+        let subject = DebutHandler::new(DebutApproval::Automatic, Logger::new("test"));
 
         let qualifies_result = subject.qualifies(&db, agrs_vec.as_slice(), gossip_source.clone());
         let handle_result = subject.handle(&cryptde, &mut db, agrs_vec, gossip_source);
@@ -1225,7 +1311,7 @@ mod tests {
             .node(src_db.root().public_key(), true)
             .build();
         let agrs_vec: Vec<AccessibleGossipRecord> = gossip.try_into().unwrap();
-        let subject = DebutHandler::new(Logger::new("test"));
+        let subject = DebutHandler::new(DebutApproval::Automatic, Logger::new("test"));
 
         let result = subject.handle(
             &cryptde,
@@ -1256,7 +1342,7 @@ mod tests {
             .node(src_db.root().public_key(), true)
             .build();
         let agrs_vec: Vec<AccessibleGossipRecord> = gossip.try_into().unwrap();
-        let subject = DebutHandler::new(Logger::new("test"));
+        let subject = DebutHandler::new(DebutApproval::Automatic, Logger::new("test"));
 
         let result = subject.handle(
             &cryptde,
@@ -1278,7 +1364,7 @@ mod tests {
     #[test]
     fn debut_with_node_addr_not_accepting_connections_is_rejected() {
         let (mut gossip, _j, gossip_source) = make_debut(2345, Mode::OriginateOnly);
-        let subject = DebutHandler::new(Logger::new("test"));
+        let subject = DebutHandler::new(DebutApproval::Automatic, Logger::new("test"));
         gossip.node_records[0].node_addr_opt = Some(NodeAddr::new(
             &IpAddr::from_str("1.2.3.4").unwrap(),
             &[1234],
@@ -1299,7 +1385,7 @@ mod tests {
     fn debut_without_node_addr_accepting_connections_is_rejected() {
         let (mut gossip, _j, gossip_source) = make_debut(2345, Mode::Standard);
         gossip.node_records[0].node_addr_opt = None;
-        let subject = DebutHandler::new(Logger::new("test"));
+        let subject = DebutHandler::new(DebutApproval::Automatic, Logger::new("test"));
         let agrs_vec: Vec<AccessibleGossipRecord> = gossip.try_into().unwrap();
 
         let result = subject.qualifies(&make_meaningless_db(), agrs_vec.as_slice(), gossip_source);
@@ -1317,7 +1403,7 @@ mod tests {
         let (mut gossip, _, gossip_source) = make_debut(2345, Mode::Standard);
         gossip.node_records[0].node_addr_opt =
             Some(NodeAddr::new(&IpAddr::from_str("1.2.3.4").unwrap(), &[]));
-        let subject = DebutHandler::new(Logger::new("test"));
+        let subject = DebutHandler::new(DebutApproval::Automatic, Logger::new("test"));
         let agrs_vec: Vec<AccessibleGossipRecord> = gossip.try_into().unwrap();
 
         let result = subject.qualifies(&make_meaningless_db(), agrs_vec.as_slice(), gossip_source);
@@ -1339,7 +1425,7 @@ mod tests {
         db.add_arbitrary_full_neighbor(root_node.public_key(), neighbor_key);
         db.add_node(new_node.clone()).unwrap();
         let agrs_vec: Vec<AccessibleGossipRecord> = gossip.try_into().unwrap();
-        let subject = DebutHandler::new(Logger::new("test"));
+        let subject = DebutHandler::new(DebutApproval::Automatic, Logger::new("test"));
 
         let result = subject.qualifies(&db, agrs_vec.as_slice(), gossip_source);
 
@@ -1367,7 +1453,7 @@ mod tests {
             .build()
             .try_into()
             .unwrap();
-        let subject = DebutHandler::new(Logger::new("test"));
+        let subject = DebutHandler::new(DebutApproval::Automatic, Logger::new("test"));
 
         let result = subject.handle(
             &dest_cryptde,
@@ -2016,7 +2102,7 @@ mod tests {
         let gossip = GossipProducerReal::new()
             .produce(&mut src_db, dest_root.public_key())
             .unwrap();
-        let subject = GossipAcceptorReal::new(&dest_cryptde);
+        let subject = GossipAcceptorReal::new(&dest_cryptde, DebutApproval::Automatic);
 
         let result = subject.handle(
             &mut dest_db,
@@ -2027,9 +2113,35 @@ mod tests {
         assert_eq!(result, GossipAcceptanceResult::Ignored);
     }
 
+    #[test]
+    fn gossip_from_banned_malefactor_is_ignored() {
+        let (gossip, debut_node, gossip_source) = make_debut(4747, Mode::Standard);
+        let root_node = make_node_record(1234, true);
+        let mut db = db_from_node(&root_node);
+        let cryptde = CryptDENull::from(db.root().public_key(), DEFAULT_CHAIN_ID);
+        let ban = MalefactorBan {
+            id: 4747,
+            malefactor: Malefactor {
+                public_key_opt: Some(debut_node.public_key().clone()),
+                ..Malefactor::default()
+            },
+            reason: "Bad Gossip".to_string(),
+            banned_at: SystemTime::now(),
+            expires_at_opt: None,
+        };
+        MALEFACTOR_BAN_CACHE.insert(ban.clone());
+        let subject = GossipAcceptorReal::new(&cryptde, DebutApproval::Automatic);
+
+        let result = subject.handle(&mut db, gossip.try_into().unwrap(), gossip_source);
+
+        MALEFACTOR_BAN_CACHE.remove(&ban);
+        assert_eq!(result, GossipAcceptanceResult::Ignored);
+        assert!(db.node_by_key(debut_node.public_key()).is_none());
+    }
+
     #[test]
     fn last_gossip_handler_rejects_everything() {
-        let subject = GossipAcceptorReal::new(main_cryptde());
+        let subject = GossipAcceptorReal::new(main_cryptde(), DebutApproval::Automatic);
         let reject_handler = subject.gossip_handlers.last().unwrap();
         let db = make_meaningless_db();
         let (debut, _, debut_gossip_source) = make_debut(1234, Mode::Standard);
@@ -2099,7 +2211,7 @@ mod tests {
             .node(node_a.public_key(), false)
             .node(node_b.public_key(), false)
             .build();
-        let subject = GossipAcceptorReal::new(main_cryptde());
+        let subject = GossipAcceptorReal::new(main_cryptde(), DebutApproval::Automatic);
 
         let result = subject.handle(
             &mut dest_db,
@@ -2116,7 +2228,7 @@ mod tests {
         let root_node_cryptde = CryptDENull::from(&root_node.public_key(), DEFAULT_CHAIN_ID);
         let mut dest_db = db_from_node(&root_node);
         let (gossip, debut_node, gossip_source) = make_debut(2345, Mode::Standard);
-        let subject = GossipAcceptorReal::new(&root_node_cryptde);
+        let subject = GossipAcceptorReal::new(&root_node_cryptde, DebutApproval::Automatic);
 
         let result = subject.handle(&mut dest_db, gossip.try_into().unwrap(), gossip_source);
 
@@ -2146,7 +2258,7 @@ mod tests {
             .resign();
         dest_db.node_by_key_mut(existing_node_key).unwrap().resign();
         let (gossip, debut_node, gossip_source) = make_debut(2345, Mode::Standard);
-        let subject = GossipAcceptorReal::new(&root_node_cryptde);
+        let subject = GossipAcceptorReal::new(&root_node_cryptde, DebutApproval::Automatic);
 
         let result = subject.handle(&mut dest_db, gossip.try_into().unwrap(), gossip_source);
 
@@ -2208,7 +2320,7 @@ mod tests {
             .resign();
 
         let (gossip, debut_node, gossip_source) = make_debut(2345, Mode::Standard);
-        let subject = GossipAcceptorReal::new(&root_node_cryptde);
+        let subject = GossipAcceptorReal::new(&root_node_cryptde, DebutApproval::Automatic);
 
         let result = subject.handle(&mut dest_db, gossip.try_into().unwrap(), gossip_source);
 
@@ -2296,7 +2408,7 @@ mod tests {
             .resign();
 
         let (gossip, debut_node, gossip_source) = make_debut(2345, Mode::Standard);
-        let subject = GossipAcceptorReal::new(&root_node_cryptde);
+        let subject = GossipAcceptorReal::new(&root_node_cryptde, DebutApproval::Automatic);
 
         let result = subject.handle(&mut dest_db, gossip.try_into().unwrap(), gossip_source);
 
@@ -2380,7 +2492,7 @@ mod tests {
             .resign();
 
         let (gossip, debut_node, gossip_source) = make_debut(2345, Mode::Standard);
-        let subject = GossipAcceptorReal::new(&root_node_cryptde);
+        let subject = GossipAcceptorReal::new(&root_node_cryptde, DebutApproval::Automatic);
 
         let result = subject.handle(&mut dest_db, gossip.try_into().unwrap(), gossip_source);
 
@@ -2414,6 +2526,52 @@ mod tests {
         assert_eq!(&root_node, dest_db.root());
     }
 
+    #[test]
+    fn debut_when_degree_is_five_replaces_poor_quality_neighbor() {
+        let root_node = make_node_record(1234, true);
+        let root_node_cryptde = CryptDENull::from(&root_node.public_key(), DEFAULT_CHAIN_ID);
+        let mut dest_db = db_from_node(&root_node);
+        let existing_keys = vec![3456, 4567, 5678, 6789, 7890]
+            .into_iter()
+            .map(|n| {
+                let key = dest_db.add_node(make_node_record(n, true)).unwrap();
+                dest_db.add_arbitrary_full_neighbor(root_node.public_key(), &key);
+                key
+            })
+            .collect::<Vec<PublicKey>>();
+        let poor_key = &existing_keys[2];
+        let merely_slow_key = &existing_keys[3];
+        {
+            let poor_node = dest_db.node_by_key_mut(poor_key).unwrap();
+            (0..3).for_each(|_| poor_node.quality_mut().record_failure());
+        }
+        dest_db
+            .node_by_key_mut(merely_slow_key)
+            .unwrap()
+            .quality_mut()
+            .record_round_trip(2_000);
+        dest_db.root_mut().resign();
+        let (gossip, debut_node, gossip_source) = make_debut(2345, Mode::Standard);
+        let subject = GossipAcceptorReal::new(&root_node_cryptde, DebutApproval::Automatic);
+
+        let result = subject.handle(&mut dest_db, gossip.try_into().unwrap(), gossip_source);
+
+        match result {
+            GossipAcceptanceResult::Reply(_, key, _) => assert_eq!(&key, debut_node.public_key()),
+            GossipAcceptanceResult::Accepted => (),
+            x => panic!("Expected debut to be accepted, but got {:?}", x),
+        }
+        let mut expected_neighbor_keys = existing_keys.clone();
+        expected_neighbor_keys.remove(2);
+        expected_neighbor_keys.push(debut_node.public_key().clone());
+        assert_eq!(
+            dest_db.root().half_neighbor_keys(),
+            expected_neighbor_keys
+                .iter()
+                .collect::<HashSet<&PublicKey>>()
+        );
+    }
+
     #[test]
     fn redebut_is_passed_to_standard_gossip_handler_and_ignored_if_it_is_not_a_new_version() {
         let src_node = make_node_record(1234, true);
@@ -2430,7 +2588,7 @@ mod tests {
             .node(src_node.public_key(), true)
             .build();
         let gossip_source: SocketAddr = src_node.node_addr_opt().unwrap().into();
-        let subject = GossipAcceptorReal::new(main_cryptde());
+        let subject = GossipAcceptorReal::new(main_cryptde(), DebutApproval::Automatic);
 
         let result = subject.handle(&mut dest_db, debut.try_into().unwrap(), gossip_source);
 
@@ -2463,7 +2621,7 @@ mod tests {
             .build();
         let debut_agrs = debut.try_into().unwrap();
         let gossip_source: SocketAddr = src_node.node_addr_opt().unwrap().into();
-        let subject = GossipAcceptorReal::new(main_cryptde());
+        let subject = GossipAcceptorReal::new(main_cryptde(), DebutApproval::Automatic);
 
         let begin_at = time_t_timestamp();
         let result = subject.handle(&mut dest_db, debut_agrs, gossip_source);
@@ -2492,7 +2650,7 @@ mod tests {
             .build();
         let debut_agrs = debut.try_into().unwrap();
         let gossip_source = src_node.node_addr_opt().unwrap().into();
-        let subject = GossipAcceptorReal::new(main_cryptde());
+        let subject = GossipAcceptorReal::new(main_cryptde(), DebutApproval::Automatic);
 
         let result = subject.handle(&mut dest_db, debut_agrs, gossip_source);
 
@@ -2522,7 +2680,7 @@ mod tests {
             .build();
         let debut_agrs = debut.try_into().unwrap();
         let gossip_source = src_node.node_addr_opt().unwrap().into();
-        let subject = GossipAcceptorReal::new(main_cryptde());
+        let subject = GossipAcceptorReal::new(main_cryptde(), DebutApproval::Automatic);
 
         let result = subject.handle(&mut dest_db, debut_agrs, gossip_source);
 
@@ -2541,7 +2699,7 @@ mod tests {
         let root_node = make_node_record(1234, true);
         let mut db = db_from_node(&root_node);
         let (gossip, pass_target, gossip_source) = make_pass(2345);
-        let subject = GossipAcceptorReal::new(main_cryptde());
+        let subject = GossipAcceptorReal::new(main_cryptde(), DebutApproval::Automatic);
 
         let result = subject.handle(&mut db, gossip.try_into().unwrap(), gossip_source);
 
@@ -2607,7 +2765,7 @@ mod tests {
             .node(node_e.public_key(), true)
             .node(node_f.public_key(), true)
             .build();
-        let subject = GossipAcceptorReal::new(main_cryptde());
+        let subject = GossipAcceptorReal::new(main_cryptde(), DebutApproval::Automatic);
 
         let result = subject.handle(
             &mut dest_db,
@@ -2689,7 +2847,7 @@ mod tests {
             .node(third_node.public_key(), true)
             .node(disconnected_node.public_key(), false)
             .build();
-        let subject = GossipAcceptorReal::new(&dest_node_cryptde);
+        let subject = GossipAcceptorReal::new(&dest_node_cryptde, DebutApproval::Automatic);
 
         let result = subject.handle(
             &mut dest_db,
@@ -2770,7 +2928,7 @@ mod tests {
             .node(current_node.public_key(), false)
             .node(obsolete_node.public_key(), false)
             .build();
-        let subject = GossipAcceptorReal::new(main_cryptde());
+        let subject = GossipAcceptorReal::new(main_cryptde(), DebutApproval::Automatic);
         let original_dest_db = dest_db.clone();
 
         let result = subject.handle(
@@ -2879,7 +3037,7 @@ mod tests {
         db.add_arbitrary_full_neighbor(root_node.public_key(), other_neighbor_3_key);
         db.add_arbitrary_full_neighbor(less_connected_neighbor_key, other_neighbor_1_key);
         db.add_arbitrary_full_neighbor(less_connected_neighbor_key, other_neighbor_2_key);
-        let subject = DebutHandler::new(Logger::new("test"));
+        let subject = DebutHandler::new(DebutApproval::Automatic, Logger::new("test"));
         let excluded = AccessibleGossipRecord::from((&db, excluded_key, true));
 
         let result = subject.find_more_appropriate_neighbor(&db, &excluded);
@@ -2903,7 +3061,7 @@ mod tests {
         db.add_arbitrary_full_neighbor(less_connected_neighbor_key, other_neighbor_1_key);
         db.add_arbitrary_full_neighbor(less_connected_neighbor_key, other_neighbor_2_key);
         db.add_arbitrary_full_neighbor(less_connected_neighbor_key, other_neighbor_3_key);
-        let subject = DebutHandler::new(Logger::new("test"));
+        let subject = DebutHandler::new(DebutApproval::Automatic, Logger::new("test"));
         let excluded = AccessibleGossipRecord::from((&db, excluded_key, true));
 
         let result = subject.find_more_appropriate_neighbor(&db, &excluded);
@@ -2930,7 +3088,7 @@ mod tests {
         db.add_arbitrary_full_neighbor(root_node.public_key(), other_neighbor_4_key);
         db.add_arbitrary_full_neighbor(less_connected_neighbor_key, other_neighbor_1_key);
         db.add_arbitrary_full_neighbor(less_connected_neighbor_key, other_neighbor_2_key);
-        let subject = DebutHandler::new(Logger::new("test"));
+        let subject = DebutHandler::new(DebutApproval::Automatic, Logger::new("test"));
         let excluded = AccessibleGossipRecord::from((&db, excluded_key, true));
 
         let result = subject.find_more_appropriate_neighbor(&db, &excluded);
@@ -2952,7 +3110,7 @@ mod tests {
         db.add_arbitrary_full_neighbor(root_node.public_key(), other_neighbor_3_key);
         db.add_arbitrary_full_neighbor(less_connected_neighbor_key, other_neighbor_1_key);
         db.add_arbitrary_full_neighbor(less_connected_neighbor_key, other_neighbor_2_key);
-        let subject = DebutHandler::new(Logger::new("test"));
+        let subject = DebutHandler::new(DebutApproval::Automatic, Logger::new("test"));
         let less_connected_neighbor_agr =
             AccessibleGossipRecord::from((&db, less_connected_neighbor_key, true));
 
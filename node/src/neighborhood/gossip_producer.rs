@@ -103,10 +103,11 @@ mod tests {
     use super::super::gossip::GossipNodeRecord;
     use super::*;
     use crate::neighborhood::neighborhood_database::ISOLATED_NODE_GRACE_PERIOD_SECS;
-    use crate::neighborhood::node_record::{NodeRecord, NodeRecordInner_0v1};
+    use crate::neighborhood::node_record::{NodeRecord, NodeRecordInner_0v2};
     use crate::neighborhood::AccessibleGossipRecord;
     use crate::sub_lib::cryptde::CryptDE;
     use crate::sub_lib::cryptde_null::CryptDENull;
+    use crate::sub_lib::proxy_server::ProxyProtocol;
     use crate::sub_lib::utils::time_t_timestamp;
     use crate::test_utils::assert_contains;
     use crate::test_utils::neighborhood_test_utils::{db_from_node, make_node_record};
@@ -165,7 +166,7 @@ mod tests {
         type Digest = (PublicKey, Vec<u8>, bool, BTreeSet<PublicKey>);
         let gnr_digest = |gnr: GossipNodeRecord| {
             let has_ip = gnr.node_addr_opt.is_some();
-            let nri = NodeRecordInner_0v1::try_from(gnr).unwrap();
+            let nri = NodeRecordInner_0v2::try_from(gnr).unwrap();
             (
                 nri.public_key.clone(),
                 nri.public_key.into(),
@@ -230,6 +231,32 @@ mod tests {
         assert_eq!(gossip_root.node_addr_opt, None);
     }
 
+    #[test]
+    fn produce_advertises_root_node_capabilities() {
+        let root_node: NodeRecord = make_node_record(1234, true);
+        let mut db: NeighborhoodDatabase = db_from_node(&root_node);
+        let target_node_key = &db.add_node(make_node_record(1235, true)).unwrap();
+        db.add_arbitrary_full_neighbor(root_node.public_key(), target_node_key);
+        let subject = GossipProducerReal::new();
+
+        let gossip = subject.produce(&mut db, target_node_key).unwrap();
+
+        let gossip_root = gossip
+            .node_records
+            .into_iter()
+            .map(|gnr| AccessibleGossipRecord::try_from(gnr).unwrap())
+            .find(|agr| &agr.inner.public_key == root_node.public_key())
+            .unwrap();
+        assert_eq!(
+            gossip_root.inner.proxy_protocols_opt,
+            Some(vec![ProxyProtocol::HTTP, ProxyProtocol::TLS])
+        );
+        assert_eq!(
+            gossip_root.inner.software_version_opt,
+            Some(env!("CARGO_PKG_VERSION").to_string())
+        );
+    }
+
     #[test]
     fn produce_does_not_make_gossip_about_nonexistent_or_removed_nodes() {
         let root_node: NodeRecord = make_node_record(1234, true);
@@ -343,7 +370,7 @@ mod tests {
             result_gossip_record.node_addr_opt,
             Some(our_node_record.metadata.node_addr_opt.clone().unwrap())
         );
-        let result_node_record_inner = NodeRecordInner_0v1::try_from(result_gossip_record).unwrap();
+        let result_node_record_inner = NodeRecordInner_0v2::try_from(result_gossip_record).unwrap();
         assert_eq!(result_node_record_inner, our_node_record.inner);
         let our_cryptde = CryptDENull::from(our_node_record.public_key(), DEFAULT_CHAIN_ID);
         assert_eq!(
@@ -368,7 +395,7 @@ mod tests {
         assert_eq!(result_gossip.node_records.len(), 1);
         let result_gossip_record = result_gossip.node_records.first().unwrap();
         assert_eq!(result_gossip_record.node_addr_opt, None);
-        let result_node_record_inner = NodeRecordInner_0v1::try_from(result_gossip_record).unwrap();
+        let result_node_record_inner = NodeRecordInner_0v2::try_from(result_gossip_record).unwrap();
         assert_eq!(result_node_record_inner, our_node_record.inner);
         let our_cryptde = CryptDENull::from(our_node_record.public_key(), DEFAULT_CHAIN_ID);
         assert_eq!(
@@ -0,0 +1,420 @@
+// Copyright (c) 2019-2020, MASQ (https://masq.ai) and/or its affiliates. All rights reserved.
+use crate::neighborhood::gossip::Gossip_0v1;
+use std::collections::hash_map::DefaultHasher;
+use std::collections::{HashMap, VecDeque};
+use std::fmt;
+use std::hash::{Hash, Hasher};
+use std::net::IpAddr;
+use std::time::{Duration, Instant};
+
+pub const PER_SOURCE_GOSSIP_LIMIT: usize = 10;
+pub const GLOBAL_GOSSIP_LIMIT: usize = 100;
+// Every record in Gossip costs a signature verification, so the work is limited by counting records
+// rather than by capping the size of any one Gossip: legitimate Gossip may describe the whole
+// Network, however big that grows.
+pub const PER_SOURCE_GOSSIP_RECORD_LIMIT: usize = 5_000;
+pub const GLOBAL_GOSSIP_RECORD_LIMIT: usize = 20_000;
+pub const GOSSIP_RATE_WINDOW: Duration = Duration::from_secs(10);
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum GossipDropReason {
+    TooManyRecords(usize, usize),
+    Duplicate,
+    SourceRateExceeded,
+    GlobalRateExceeded,
+}
+
+impl fmt::Display for GossipDropReason {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            GossipDropReason::TooManyRecords(count, limit) => {
+                write!(f, "{} records is more than the limit of {}", count, limit)
+            }
+            GossipDropReason::Duplicate => write!(f, "identical Gossip was received recently"),
+            GossipDropReason::SourceRateExceeded => {
+                write!(f, "too much Gossip from this source recently")
+            }
+            GossipDropReason::GlobalRateExceeded => write!(f, "too much Gossip recently"),
+        }
+    }
+}
+
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct DroppedGossipCounts {
+    pub too_many_records: u64,
+    pub duplicate: u64,
+    pub source_rate_exceeded: u64,
+    pub global_rate_exceeded: u64,
+}
+
+impl DroppedGossipCounts {
+    pub fn total(&self) -> u64 {
+        self.too_many_records
+            + self.duplicate
+            + self.source_rate_exceeded
+            + self.global_rate_exceeded
+    }
+
+    fn count(&mut self, reason: GossipDropReason) {
+        match reason {
+            GossipDropReason::TooManyRecords(_, _) => self.too_many_records += 1,
+            GossipDropReason::Duplicate => self.duplicate += 1,
+            GossipDropReason::SourceRateExceeded => self.source_rate_exceeded += 1,
+            GossipDropReason::GlobalRateExceeded => self.global_rate_exceeded += 1,
+        }
+    }
+}
+
+impl fmt::Display for DroppedGossipCounts {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(
+            f,
+            "{} oversized, {} duplicate, {} over source limit, {} over global limit",
+            self.too_many_records,
+            self.duplicate,
+            self.source_rate_exceeded,
+            self.global_rate_exceeded
+        )
+    }
+}
+
+// Decides whether incoming Gossip is worth the trouble of verifying and processing. The checks
+// are ordered from cheapest to most expensive, and only Gossip that passes all of them counts
+// against the rate limits.
+pub struct GossipRateLimiter {
+    per_source_limit: usize,
+    global_limit: usize,
+    per_source_record_limit: usize,
+    global_record_limit: usize,
+    window: Duration,
+    // The time each admitted Gossip arrived, and how many records it had
+    per_source: HashMap<IpAddr, VecDeque<(Instant, usize)>>,
+    global: VecDeque<(Instant, usize)>,
+    recent_digests: HashMap<u64, Instant>,
+    dropped: DroppedGossipCounts,
+}
+
+impl Default for GossipRateLimiter {
+    fn default() -> Self {
+        GossipRateLimiter::new(
+            PER_SOURCE_GOSSIP_LIMIT,
+            GLOBAL_GOSSIP_LIMIT,
+            PER_SOURCE_GOSSIP_RECORD_LIMIT,
+            GLOBAL_GOSSIP_RECORD_LIMIT,
+            GOSSIP_RATE_WINDOW,
+        )
+    }
+}
+
+impl GossipRateLimiter {
+    pub fn new(
+        per_source_limit: usize,
+        global_limit: usize,
+        per_source_record_limit: usize,
+        global_record_limit: usize,
+        window: Duration,
+    ) -> Self {
+        GossipRateLimiter {
+            per_source_limit,
+            global_limit,
+            per_source_record_limit,
+            global_record_limit,
+            window,
+            per_source: HashMap::new(),
+            global: VecDeque::new(),
+            recent_digests: HashMap::new(),
+            dropped: DroppedGossipCounts::default(),
+        }
+    }
+
+    pub fn admit(
+        &mut self,
+        gossip: &Gossip_0v1,
+        source: IpAddr,
+        now: Instant,
+    ) -> Result<(), GossipDropReason> {
+        let result = self.check(gossip, source, now);
+        if let Err(reason) = result {
+            self.dropped.count(reason);
+        }
+        result
+    }
+
+    pub fn dropped(&self) -> &DroppedGossipCounts {
+        &self.dropped
+    }
+
+    fn check(
+        &mut self,
+        gossip: &Gossip_0v1,
+        source: IpAddr,
+        now: Instant,
+    ) -> Result<(), GossipDropReason> {
+        let record_count = gossip.node_records.len();
+        // Gossip this big would exceed the record limits even if nothing else had arrived
+        let max_records = self.per_source_record_limit.min(self.global_record_limit);
+        if record_count > max_records {
+            return Err(GossipDropReason::TooManyRecords(record_count, max_records));
+        }
+        self.expire(now);
+        let digest = Self::digest(gossip);
+        if self.recent_digests.contains_key(&digest) {
+            return Err(GossipDropReason::Duplicate);
+        }
+        let source_arrivals = self.per_source.entry(source).or_insert_with(VecDeque::new);
+        if source_arrivals.len() >= self.per_source_limit
+            || Self::records_in(source_arrivals) + record_count > self.per_source_record_limit
+        {
+            return Err(GossipDropReason::SourceRateExceeded);
+        }
+        if self.global.len() >= self.global_limit
+            || Self::records_in(&self.global) + record_count > self.global_record_limit
+        {
+            return Err(GossipDropReason::GlobalRateExceeded);
+        }
+        source_arrivals.push_back((now, record_count));
+        self.global.push_back((now, record_count));
+        self.recent_digests.insert(digest, now);
+        Ok(())
+    }
+
+    fn expire(&mut self, now: Instant) {
+        let window = self.window;
+        let is_current = |time: &Instant| now.duration_since(*time) < window;
+        let drop_expired = |arrivals: &mut VecDeque<(Instant, usize)>| {
+            while arrivals
+                .front()
+                .map(|(time, _)| !is_current(time))
+                .unwrap_or(false)
+            {
+                arrivals.pop_front();
+            }
+        };
+        drop_expired(&mut self.global);
+        self.per_source.values_mut().for_each(drop_expired);
+        self.per_source.retain(|_, arrivals| !arrivals.is_empty());
+        self.recent_digests.retain(|_, time| is_current(time));
+    }
+
+    fn records_in(arrivals: &VecDeque<(Instant, usize)>) -> usize {
+        arrivals.iter().map(|(_, record_count)| record_count).sum()
+    }
+
+    fn digest(gossip: &Gossip_0v1) -> u64 {
+        let mut hasher = DefaultHasher::new();
+        gossip.node_records.iter().for_each(|record| {
+            record.signed_data.as_slice().hash(&mut hasher);
+            record.signature.as_slice().hash(&mut hasher);
+            record.node_addr_opt.hash(&mut hasher);
+        });
+        hasher.finish()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::neighborhood::gossip::GossipBuilder;
+    use crate::test_utils::neighborhood_test_utils::{db_from_node, make_node_record};
+    use std::str::FromStr;
+
+    fn make_gossip(n: u16) -> Gossip_0v1 {
+        let node = make_node_record(n, true);
+        let db = db_from_node(&node);
+        GossipBuilder::new(&db)
+            .node(node.public_key(), true)
+            .build()
+    }
+
+    fn make_subject() -> GossipRateLimiter {
+        GossipRateLimiter::new(2, 3, 2, 10, Duration::from_secs(10))
+    }
+
+    fn make_big_gossip(n: u16, record_count: usize) -> Gossip_0v1 {
+        let mut gossip = make_gossip(n);
+        let record = gossip.node_records[0].clone();
+        gossip.node_records = vec![record; record_count];
+        gossip
+    }
+
+    #[test]
+    fn normal_gossip_about_a_big_network_is_admitted() {
+        let mut subject = GossipRateLimiter::default();
+        let gossip = make_big_gossip(1234, 500);
+
+        let result = subject.admit(
+            &gossip,
+            IpAddr::from_str("1.2.3.4").unwrap(),
+            Instant::now(),
+        );
+
+        assert_eq!(result, Ok(()));
+        assert_eq!(subject.dropped().total(), 0);
+    }
+
+    #[test]
+    fn gossip_over_per_source_record_limit_is_dropped() {
+        let mut subject = GossipRateLimiter::new(10, 10, 5, 100, Duration::from_secs(10));
+        let source = IpAddr::from_str("1.2.3.4").unwrap();
+        let start = Instant::now();
+
+        let first = subject.admit(&make_big_gossip(1000, 3), source, start);
+        let second = subject.admit(&make_big_gossip(1001, 3), source, start);
+        let third = subject.admit(&make_big_gossip(1002, 2), source, start);
+        let other_source = subject.admit(
+            &make_big_gossip(1003, 3),
+            IpAddr::from_str("2.3.4.5").unwrap(),
+            start,
+        );
+        let later = subject.admit(
+            &make_big_gossip(1004, 3),
+            source,
+            start + Duration::from_secs(10),
+        );
+
+        assert_eq!(first, Ok(()));
+        assert_eq!(second, Err(GossipDropReason::SourceRateExceeded));
+        assert_eq!(third, Ok(()));
+        assert_eq!(other_source, Ok(()));
+        assert_eq!(later, Ok(()));
+        assert_eq!(subject.dropped().source_rate_exceeded, 1);
+    }
+
+    #[test]
+    fn gossip_over_global_record_limit_is_dropped() {
+        let mut subject = GossipRateLimiter::new(10, 10, 5, 8, Duration::from_secs(10));
+        let start = Instant::now();
+
+        let results = (0..3)
+            .map(|n| {
+                subject.admit(
+                    &make_big_gossip(1000 + n, 4),
+                    IpAddr::from_str(&format!("1.2.3.{}", n)).unwrap(),
+                    start,
+                )
+            })
+            .collect::<Vec<Result<(), GossipDropReason>>>();
+
+        assert_eq!(
+            results,
+            vec![Ok(()), Ok(()), Err(GossipDropReason::GlobalRateExceeded)]
+        );
+        assert_eq!(subject.dropped().global_rate_exceeded, 1);
+    }
+
+    #[test]
+    fn gossip_with_too_many_records_is_dropped() {
+        let mut subject = make_subject();
+        let gossip = make_big_gossip(1234, 3);
+
+        let result = subject.admit(
+            &gossip,
+            IpAddr::from_str("1.2.3.4").unwrap(),
+            Instant::now(),
+        );
+
+        assert_eq!(result, Err(GossipDropReason::TooManyRecords(3, 2)));
+        assert_eq!(subject.dropped().too_many_records, 1);
+    }
+
+    #[test]
+    fn identical_gossip_is_dropped_within_window_and_admitted_after() {
+        let mut subject = make_subject();
+        let gossip = make_gossip(1234);
+        let start = Instant::now();
+
+        let first = subject.admit(&gossip, IpAddr::from_str("1.2.3.4").unwrap(), start);
+        let second = subject.admit(
+            &gossip,
+            IpAddr::from_str("2.3.4.5").unwrap(),
+            start + Duration::from_secs(9),
+        );
+        let third = subject.admit(
+            &gossip,
+            IpAddr::from_str("2.3.4.5").unwrap(),
+            start + Duration::from_secs(10),
+        );
+
+        assert_eq!(first, Ok(()));
+        assert_eq!(second, Err(GossipDropReason::Duplicate));
+        assert_eq!(third, Ok(()));
+        assert_eq!(subject.dropped().duplicate, 1);
+    }
+
+    #[test]
+    fn gossip_over_per_source_limit_is_dropped() {
+        let mut subject = make_subject();
+        let source = IpAddr::from_str("1.2.3.4").unwrap();
+        let start = Instant::now();
+
+        let results = (0..3)
+            .map(|n| subject.admit(&make_gossip(1000 + n), source, start))
+            .collect::<Vec<Result<(), GossipDropReason>>>();
+        let other_source_result = subject.admit(
+            &make_gossip(2000),
+            IpAddr::from_str("2.3.4.5").unwrap(),
+            start,
+        );
+        let later_result =
+            subject.admit(&make_gossip(3000), source, start + Duration::from_secs(10));
+
+        assert_eq!(
+            results,
+            vec![Ok(()), Ok(()), Err(GossipDropReason::SourceRateExceeded)]
+        );
+        assert_eq!(other_source_result, Ok(()));
+        assert_eq!(later_result, Ok(()));
+        assert_eq!(subject.dropped().source_rate_exceeded, 1);
+    }
+
+    #[test]
+    fn gossip_over_global_limit_is_dropped() {
+        let mut subject = make_subject();
+        let start = Instant::now();
+
+        let results = (0..4)
+            .map(|n| {
+                subject.admit(
+                    &make_gossip(1000 + n),
+                    IpAddr::from_str(&format!("1.2.3.{}", n)).unwrap(),
+                    start,
+                )
+            })
+            .collect::<Vec<Result<(), GossipDropReason>>>();
+
+        assert_eq!(
+            results,
+            vec![
+                Ok(()),
+                Ok(()),
+                Ok(()),
+                Err(GossipDropReason::GlobalRateExceeded)
+            ]
+        );
+        assert_eq!(
+            subject.dropped(),
+            &DroppedGossipCounts {
+                too_many_records: 0,
+                duplicate: 0,
+                source_rate_exceeded: 0,
+                global_rate_exceeded: 1,
+            }
+        );
+        assert_eq!(subject.dropped().total(), 1);
+    }
+
+    #[test]
+    fn dropped_gossip_counts_display_each_reason() {
+        let subject = DroppedGossipCounts {
+            too_many_records: 1,
+            duplicate: 2,
+            source_rate_exceeded: 3,
+            global_rate_exceeded: 4,
+        };
+
+        assert_eq!(
+            subject.to_string(),
+            "1 oversized, 2 duplicate, 3 over source limit, 4 over global limit"
+        );
+    }
+}
@@ -0,0 +1,433 @@
+// Copyright (c) 2019-2020, MASQ (https://masq.ai) and/or its affiliates. All rights reserved.
+use crate::database::connection_wrapper::ConnectionWrapper;
+use crate::database::dao_utils::{from_time_t, now_time_t, to_time_t};
+use crate::neighborhood::AccessibleGossipRecord;
+use crate::sub_lib::cryptde::PublicKey;
+use crate::sub_lib::wallet::Wallet;
+use lazy_static::lazy_static;
+use rusqlite::{Row, ToSql, NO_PARAMS};
+use std::net::{IpAddr, SocketAddr};
+use std::str::FromStr;
+use std::sync::RwLock;
+use std::time::{Duration, SystemTime};
+
+pub const MALEFACTOR_BAN_DURATION: Duration = Duration::from_secs(24 * 60 * 60);
+
+lazy_static! {
+    pub static ref MALEFACTOR_BAN_CACHE: MalefactorBanCache = MalefactorBanCache::default();
+}
+
+// Whatever we know about a Node that misbehaved. A ban matches on any of the fields it has.
+#[derive(Clone, Debug, PartialEq, Default)]
+pub struct Malefactor {
+    pub public_key_opt: Option<PublicKey>,
+    pub ip_address_opt: Option<IpAddr>,
+    pub earning_wallet_opt: Option<Wallet>,
+}
+
+impl Malefactor {
+    pub fn from_gossip_source(
+        agrs: &[AccessibleGossipRecord],
+        gossip_source: SocketAddr,
+    ) -> Malefactor {
+        let source_agr_opt = agrs.iter().find(|agr| match &agr.node_addr_opt {
+            Some(node_addr) => node_addr.ip_addr() == gossip_source.ip(),
+            None => false,
+        });
+        Malefactor {
+            public_key_opt: source_agr_opt.map(|agr| agr.inner.public_key.clone()),
+            ip_address_opt: Some(gossip_source.ip()),
+            earning_wallet_opt: source_agr_opt.map(|agr| agr.inner.earning_wallet.clone()),
+        }
+    }
+
+    fn matches(&self, other: &Malefactor) -> bool {
+        fn same<T: PartialEq>(a: &Option<T>, b: &Option<T>) -> bool {
+            match (a, b) {
+                (Some(a), Some(b)) => a == b,
+                _ => false,
+            }
+        }
+        same(&self.public_key_opt, &other.public_key_opt)
+            || same(&self.ip_address_opt, &other.ip_address_opt)
+            || same(
+                &self
+                    .earning_wallet_opt
+                    .as_ref()
+                    .map(|w| w.as_address_wallet()),
+                &other
+                    .earning_wallet_opt
+                    .as_ref()
+                    .map(|w| w.as_address_wallet()),
+            )
+    }
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub struct MalefactorBan {
+    pub id: u64,
+    pub malefactor: Malefactor,
+    pub reason: String,
+    pub banned_at: SystemTime,
+    pub expires_at_opt: Option<SystemTime>,
+}
+
+impl MalefactorBan {
+    pub fn is_expired(&self, now: SystemTime) -> bool {
+        match self.expires_at_opt {
+            Some(expires_at) => expires_at <= now,
+            None => false,
+        }
+    }
+}
+
+#[derive(Default)]
+pub struct MalefactorBanCache {
+    cache: RwLock<Vec<MalefactorBan>>,
+}
+
+impl MalefactorBanCache {
+    pub fn insert(&self, ban: MalefactorBan) {
+        let mut cache = self
+            .cache
+            .write()
+            .expect("Failed to insert malefactor ban into cache");
+        if !cache.contains(&ban) {
+            cache.push(ban);
+        }
+    }
+
+    pub fn remove(&self, ban: &MalefactorBan) {
+        self.cache
+            .write()
+            .expect("Failed to remove malefactor ban from cache")
+            .retain(|existing| existing != ban);
+    }
+
+    pub fn is_banned(&self, malefactor: &Malefactor) -> bool {
+        let now = SystemTime::now();
+        self.cache
+            .read()
+            .expect("Failed to read from malefactor ban cache")
+            .iter()
+            .any(|ban| !ban.is_expired(now) && ban.malefactor.matches(malefactor))
+    }
+
+    pub fn is_ip_banned(&self, ip_address: IpAddr) -> bool {
+        self.is_banned(&Malefactor {
+            ip_address_opt: Some(ip_address),
+            ..Malefactor::default()
+        })
+    }
+}
+
+pub trait MalefactorBanCacheLoader {
+    fn load(&self, conn: Box<dyn ConnectionWrapper>);
+}
+
+pub struct MalefactorBanCacheLoaderReal {}
+
+impl MalefactorBanCacheLoader for MalefactorBanCacheLoaderReal {
+    fn load(&self, conn: Box<dyn ConnectionWrapper>) {
+        MalefactorBanDaoReal::new(conn)
+            .ban_list()
+            .into_iter()
+            .for_each(|ban| MALEFACTOR_BAN_CACHE.insert(ban));
+    }
+}
+
+pub trait MalefactorBanDao: Send {
+    fn ban_list(&self) -> Vec<MalefactorBan>;
+    fn ban(
+        &self,
+        malefactor: &Malefactor,
+        reason: &str,
+        expires_at_opt: Option<SystemTime>,
+    ) -> MalefactorBan;
+    fn unban(&self, id: u64) -> bool;
+}
+
+pub struct MalefactorBanDaoReal {
+    conn: Box<dyn ConnectionWrapper>,
+}
+
+impl MalefactorBanDaoReal {
+    pub fn new(conn: Box<dyn ConnectionWrapper>) -> Self {
+        Self { conn }
+    }
+
+    fn ban_by_id(&self, id: u64) -> Option<MalefactorBan> {
+        let mut stmt = self
+            .conn
+            .prepare(
+                "select id, public_key, ip_address, earning_wallet, reason, banned_at, expires_at \
+                 from malefactor_banned where id = ?",
+            )
+            .expect("Failed to prepare a statement");
+        let params: &[&dyn ToSql] = &[&(id as i64)];
+        stmt.query_row(params, Self::row_to_ban).ok()
+    }
+
+    fn row_to_ban(row: &Row) -> rusqlite::Result<MalefactorBan> {
+        let id: i64 = row.get(0)?;
+        let public_key_opt: Option<String> = row.get(1)?;
+        let ip_address_opt: Option<String> = row.get(2)?;
+        let earning_wallet_opt: Option<Wallet> = row.get(3)?;
+        let reason: String = row.get(4)?;
+        let banned_at: i64 = row.get(5)?;
+        let expires_at_opt: Option<i64> = row.get(6)?;
+        Ok(MalefactorBan {
+            id: id as u64,
+            malefactor: Malefactor {
+                public_key_opt: public_key_opt.map(|encoded| {
+                    PublicKey::new(
+                        &base64::decode_config(&encoded, base64::STANDARD_NO_PAD).unwrap_or_else(
+                            |_| panic!("Database corrupt: bad malefactor public key {}", encoded),
+                        ),
+                    )
+                }),
+                ip_address_opt: ip_address_opt.map(|ip_str| {
+                    IpAddr::from_str(&ip_str).unwrap_or_else(|_| {
+                        panic!("Database corrupt: bad malefactor IP address {}", ip_str)
+                    })
+                }),
+                earning_wallet_opt,
+            },
+            reason,
+            banned_at: from_time_t(banned_at),
+            expires_at_opt: expires_at_opt.map(from_time_t),
+        })
+    }
+}
+
+impl MalefactorBanDao for MalefactorBanDaoReal {
+    fn ban_list(&self) -> Vec<MalefactorBan> {
+        let mut stmt = self
+            .conn
+            .prepare(
+                "select id, public_key, ip_address, earning_wallet, reason, banned_at, expires_at \
+                 from malefactor_banned where expires_at is null or expires_at > ? order by id",
+            )
+            .expect("Failed to prepare a statement");
+        let params: &[&dyn ToSql] = &[&now_time_t()];
+        stmt.query_map(params, Self::row_to_ban)
+            .expect("Couldn't retrieve malefactor-ban list: database corrupt")
+            .flatten()
+            .collect()
+    }
+
+    fn ban(
+        &self,
+        malefactor: &Malefactor,
+        reason: &str,
+        expires_at_opt: Option<SystemTime>,
+    ) -> MalefactorBan {
+        let banned_at = now_time_t();
+        let expires_at_opt = expires_at_opt.map(to_time_t);
+        let public_key_opt = malefactor
+            .public_key_opt
+            .as_ref()
+            .map(|public_key| public_key.to_string());
+        let ip_address_opt = malefactor
+            .ip_address_opt
+            .map(|ip_address| ip_address.to_string());
+        let mut stmt = self
+            .conn
+            .prepare(
+                "insert into malefactor_banned \
+                 (public_key, ip_address, earning_wallet, reason, banned_at, expires_at) \
+                 values (?, ?, ?, ?, ?, ?)",
+            )
+            .expect("Failed to prepare a statement");
+        let params: &[&dyn ToSql] = &[
+            &public_key_opt,
+            &ip_address_opt,
+            &malefactor.earning_wallet_opt,
+            &reason,
+            &banned_at,
+            &expires_at_opt,
+        ];
+        if let Err(e) = stmt.execute(params) {
+            panic!(
+                "Could not ban malefactor {:?} because of database corruption: {}",
+                malefactor, e
+            )
+        }
+        let id: i64 = self
+            .conn
+            .prepare("select last_insert_rowid()")
+            .expect("Failed to prepare a statement")
+            .query_row(NO_PARAMS, |row| row.get(0))
+            .expect("Could not retrieve ID of new malefactor ban");
+        let ban = MalefactorBan {
+            id: id as u64,
+            malefactor: malefactor.clone(),
+            reason: reason.to_string(),
+            banned_at: from_time_t(banned_at),
+            expires_at_opt: expires_at_opt.map(from_time_t),
+        };
+        MALEFACTOR_BAN_CACHE.insert(ban.clone());
+        ban
+    }
+
+    fn unban(&self, id: u64) -> bool {
+        let ban = match self.ban_by_id(id) {
+            Some(ban) => ban,
+            None => return false,
+        };
+        let mut stmt = self
+            .conn
+            .prepare("delete from malefactor_banned where id = ?")
+            .expect("Failed to prepare a statement");
+        let params: &[&dyn ToSql] = &[&(id as i64)];
+        match stmt.execute(params) {
+            Ok(_) => {
+                MALEFACTOR_BAN_CACHE.remove(&ban);
+                true
+            }
+            Err(e) => panic!(
+                "Could not lift malefactor ban {} because of database corruption: {}",
+                id, e
+            ),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::database::db_initializer::{DbInitializer, DbInitializerReal};
+    use crate::test_utils::make_wallet;
+    use masq_lib::test_utils::utils::{ensure_node_home_directory_exists, DEFAULT_CHAIN_ID};
+    use std::net::Ipv4Addr;
+
+    fn make_subject(test_name: &str) -> MalefactorBanDaoReal {
+        let home_dir = ensure_node_home_directory_exists("malefactor_ban_dao", test_name);
+        let conn = DbInitializerReal::new()
+            .initialize(&home_dir, DEFAULT_CHAIN_ID, true)
+            .unwrap();
+        MalefactorBanDaoReal::new(conn)
+    }
+
+    fn make_malefactor(index: u8) -> Malefactor {
+        Malefactor {
+            public_key_opt: Some(PublicKey::new(&[index, 1, 2, 3])),
+            ip_address_opt: Some(IpAddr::V4(Ipv4Addr::new(10, 44, 0, index))),
+            earning_wallet_opt: Some(make_wallet(&format!("malefactor{}", index))),
+        }
+    }
+
+    #[test]
+    fn ban_persists_and_caches_malefactor() {
+        let subject = make_subject("ban_persists_and_caches_malefactor");
+        let malefactor = make_malefactor(1);
+        let expires_at = SystemTime::now() + MALEFACTOR_BAN_DURATION;
+
+        let ban = subject.ban(&malefactor, "Bad Gossip", Some(expires_at));
+
+        assert_eq!(ban.malefactor, malefactor);
+        assert_eq!(ban.reason, "Bad Gossip".to_string());
+        assert_eq!(ban.expires_at_opt, Some(from_time_t(to_time_t(expires_at))));
+        assert_eq!(subject.ban_list(), vec![ban.clone()]);
+        assert!(MALEFACTOR_BAN_CACHE.is_banned(&malefactor));
+        MALEFACTOR_BAN_CACHE.remove(&ban);
+    }
+
+    #[test]
+    fn ban_list_handles_partial_identities_and_omits_expired_bans() {
+        let subject = make_subject("ban_list_handles_partial_identities_and_omits_expired_bans");
+        let ip_only = Malefactor {
+            ip_address_opt: Some(IpAddr::V4(Ipv4Addr::new(10, 44, 0, 2))),
+            ..Malefactor::default()
+        };
+        let expired = make_malefactor(3);
+        let permanent = subject.ban(&ip_only, "Permanent", None);
+        subject.ban(
+            &expired,
+            "Expired",
+            Some(SystemTime::now() - Duration::from_secs(60)),
+        );
+
+        let result = subject.ban_list();
+
+        assert_eq!(result, vec![permanent.clone()]);
+        assert!(MALEFACTOR_BAN_CACHE.is_ip_banned(IpAddr::V4(Ipv4Addr::new(10, 44, 0, 2))));
+        assert!(!MALEFACTOR_BAN_CACHE.is_banned(&expired));
+        MALEFACTOR_BAN_CACHE.remove(&permanent);
+    }
+
+    #[test]
+    fn unban_removes_ban_from_database_and_cache() {
+        let subject = make_subject("unban_removes_ban_from_database_and_cache");
+        let malefactor = make_malefactor(4);
+        let ban = subject.ban(&malefactor, "Bad Gossip", None);
+
+        let result = subject.unban(ban.id);
+
+        assert_eq!(result, true);
+        assert_eq!(subject.ban_list(), vec![]);
+        assert!(!MALEFACTOR_BAN_CACHE.is_banned(&malefactor));
+    }
+
+    #[test]
+    fn unban_reports_unknown_ban() {
+        let subject = make_subject("unban_reports_unknown_ban");
+
+        let result = subject.unban(12345);
+
+        assert_eq!(result, false);
+    }
+
+    #[test]
+    fn cache_matches_any_known_characteristic() {
+        let malefactor = make_malefactor(5);
+        let ban = MalefactorBan {
+            id: 1_000_005,
+            malefactor: malefactor.clone(),
+            reason: "Bad Gossip".to_string(),
+            banned_at: SystemTime::now(),
+            expires_at_opt: None,
+        };
+        MALEFACTOR_BAN_CACHE.insert(ban.clone());
+
+        assert!(MALEFACTOR_BAN_CACHE.is_banned(&Malefactor {
+            public_key_opt: malefactor.public_key_opt.clone(),
+            ..Malefactor::default()
+        }));
+        assert!(MALEFACTOR_BAN_CACHE.is_banned(&Malefactor {
+            earning_wallet_opt: malefactor.earning_wallet_opt.clone(),
+            ..Malefactor::default()
+        }));
+        assert!(MALEFACTOR_BAN_CACHE.is_ip_banned(malefactor.ip_address_opt.unwrap()));
+        assert!(!MALEFACTOR_BAN_CACHE.is_banned(&Malefactor::default()));
+        MALEFACTOR_BAN_CACHE.remove(&ban);
+        assert!(!MALEFACTOR_BAN_CACHE.is_banned(&malefactor));
+    }
+
+    #[test]
+    fn cache_loader_loads_unexpired_bans() {
+        let home_dir = ensure_node_home_directory_exists(
+            "malefactor_ban_dao",
+            "cache_loader_loads_unexpired_bans",
+        );
+        let db_initializer = DbInitializerReal::new();
+        let malefactor = make_malefactor(6);
+        let ban = MalefactorBanDaoReal::new(
+            db_initializer
+                .initialize(&home_dir, DEFAULT_CHAIN_ID, true)
+                .unwrap(),
+        )
+        .ban(&malefactor, "Bad Gossip", None);
+        MALEFACTOR_BAN_CACHE.remove(&ban);
+        assert!(!MALEFACTOR_BAN_CACHE.is_banned(&malefactor));
+
+        MalefactorBanCacheLoaderReal {}.load(
+            db_initializer
+                .initialize(&home_dir, DEFAULT_CHAIN_ID, true)
+                .unwrap(),
+        );
+
+        assert!(MALEFACTOR_BAN_CACHE.is_banned(&malefactor));
+        MALEFACTOR_BAN_CACHE.remove(&ban);
+    }
+}
@@ -1,24 +1,47 @@
 // Copyright (c) 2017-2019, Substratum LLC (https://substratum.net) and/or its affiliates. All rights reserved.
 
+pub mod bootstrap_source;
+pub mod debut_allow_list_dao;
 mod dot_graph;
+pub mod geoip;
 pub mod gossip;
 pub mod gossip_acceptor;
 #[cfg(not(feature = "expose_test_privates"))]
 mod gossip_producer;
 #[cfg(feature = "expose_test_privates")]
 pub mod gossip_producer;
+pub mod gossip_rate_limiter;
+pub mod malefactor_ban_dao;
 pub mod neighborhood_database;
+pub mod neighborhood_snapshot_dao;
 pub mod node_record;
 
-use crate::blockchain::blockchain_interface::{chain_id_from_name, contract_address};
+use crate::blockchain::blockchain_interface::{
+    chain_name_from_id, contract_address, is_mainnet_chain,
+};
 use crate::bootstrapper::BootstrapperConfig;
+use crate::database::connection_wrapper::ConnectionWrapper;
+use crate::database::dao_utils::to_time_t;
 use crate::database::db_initializer::{DbInitializer, DbInitializerReal};
 use crate::db_config::persistent_configuration::{
     PersistentConfiguration, PersistentConfigurationReal,
 };
+use crate::neighborhood::bootstrap_source::{fetch_bootstrap_list, parse_bootstrap_list};
+use crate::neighborhood::debut_allow_list_dao::{
+    DebutAllowListDao, DebutAllowListDaoReal, DEBUT_ALLOW_LIST,
+};
+use crate::neighborhood::geoip::GeoIpDatabase;
 use crate::neighborhood::gossip::{DotGossipEndpoint, GossipNodeRecord, Gossip_0v1};
 use crate::neighborhood::gossip_acceptor::GossipAcceptanceResult;
-use crate::neighborhood::node_record::NodeRecordInner_0v1;
+use crate::neighborhood::gossip_rate_limiter::GossipRateLimiter;
+use crate::neighborhood::malefactor_ban_dao::{
+    Malefactor, MalefactorBan, MalefactorBanDao, MalefactorBanDaoReal, MALEFACTOR_BAN_DURATION,
+};
+use crate::neighborhood::neighborhood_database::SNAPSHOT_RECORD_GRACE_PERIOD_SECS;
+use crate::neighborhood::neighborhood_snapshot_dao::{
+    NeighborhoodSnapshotDao, NeighborhoodSnapshotDaoReal, NeighborhoodSnapshotRecord,
+};
+use crate::neighborhood::node_record::NodeRecordInner_0v2;
 use crate::stream_messages::RemovedStreamType;
 use crate::sub_lib::configurator::NewPasswordMessage;
 use crate::sub_lib::cryptde::PublicKey;
@@ -27,8 +50,11 @@ use crate::sub_lib::dispatcher::{Component, StreamShutdownMsg};
 use crate::sub_lib::hopper::{ExpiredCoresPackage, NoLookupIncipientCoresPackage};
 use crate::sub_lib::hopper::{IncipientCoresPackage, MessageType};
 use crate::sub_lib::logger::Logger;
+use crate::sub_lib::neighborhood::BootstrapListMessage;
+use crate::sub_lib::neighborhood::BootstrapSource;
 use crate::sub_lib::neighborhood::ExpectedService;
 use crate::sub_lib::neighborhood::ExpectedServices;
+use crate::sub_lib::neighborhood::NeighborhoodMode;
 use crate::sub_lib::neighborhood::NeighborhoodSubs;
 use crate::sub_lib::neighborhood::NodeDescriptor;
 use crate::sub_lib::neighborhood::NodeQueryMessage;
@@ -40,12 +66,12 @@ use crate::sub_lib::neighborhood::RouteQueryResponse;
 use crate::sub_lib::neighborhood::{DispatcherNodeQueryMessage, GossipFailure_0v1};
 use crate::sub_lib::node_addr::NodeAddr;
 use crate::sub_lib::peer_actors::{BindMessage, StartMessage};
-use crate::sub_lib::proxy_server::DEFAULT_MINIMUM_HOP_COUNT;
+use crate::sub_lib::proxy_server::{SetExitCountryMessage, DEFAULT_MINIMUM_HOP_COUNT};
 use crate::sub_lib::route::Route;
 use crate::sub_lib::route::RouteSegment;
 use crate::sub_lib::set_consuming_wallet_message::SetConsumingWalletMessage;
 use crate::sub_lib::stream_handler_pool::DispatcherNodeQueryResponse;
-use crate::sub_lib::utils::NODE_MAILBOX_CAPACITY;
+use crate::sub_lib::utils::{time_t_timestamp, NODE_MAILBOX_CAPACITY};
 use crate::sub_lib::versioned_data::VersionedData;
 use crate::sub_lib::wallet::Wallet;
 use actix::Addr;
@@ -59,37 +85,84 @@ use gossip_acceptor::GossipAcceptorReal;
 use gossip_producer::GossipProducer;
 use gossip_producer::GossipProducerReal;
 use itertools::Itertools;
-use masq_lib::constants::DEFAULT_CHAIN_NAME;
-use masq_lib::messages::FromMessageBody;
+use masq_lib::exit_country::ExitCountryPreference;
 use masq_lib::messages::UiShutdownRequest;
-use masq_lib::ui_gateway::{NodeFromUiMessage, NodeToUiMessage};
+use masq_lib::messages::{
+    FromMessageBody, ToMessageBody, UiApproveDebutRequest, UiApproveDebutResponse,
+    UiConnectionChangeBroadcast, UiConnectionStage, UiConnectionStatusRequest,
+    UiConnectionStatusResponse, UiDebutAllowListRequest, UiDebutAllowListResponse,
+    UiDebutPendingBroadcast, UiDroppedGossipRequest, UiDroppedGossipResponse, UiExitCountryRequest,
+    UiExitCountryResponse, UiMalefactorBan, UiMalefactorBansRequest, UiMalefactorBansResponse,
+    UiMalefactorUnbanRequest, UiMalefactorUnbanResponse, UiPendingDebut, UiPendingDebutsRequest,
+    UiPendingDebutsResponse, UiRejectDebutRequest, UiRejectDebutResponse, UiStatusRequest,
+    UiStatusResponse,
+};
+use masq_lib::ui_gateway::MessagePath::Conversation;
+use masq_lib::ui_gateway::{MessageBody, MessageTarget, NodeFromUiMessage, NodeToUiMessage};
 use masq_lib::utils::exit_process;
 use neighborhood_database::NeighborhoodDatabase;
 use node_record::NodeRecord;
+use rand::seq::SliceRandom;
+use rand::thread_rng;
 use std::cmp::Ordering;
 use std::convert::TryFrom;
 use std::net::SocketAddr;
 use std::path::PathBuf;
+use std::thread;
+use std::time::{Duration, Instant, SystemTime};
 
 pub const CRASH_KEY: &str = "NEIGHBORHOOD";
+pub const NEIGHBORHOOD_PREFIX: u64 = 0x0004_0000_0000_0000;
+pub const MALEFACTOR_BAN_NOT_FOUND_ERROR: u64 = NEIGHBORHOOD_PREFIX | 1;
+pub const PENDING_DEBUT_NOT_FOUND_ERROR: u64 = NEIGHBORHOOD_PREFIX | 2;
+pub const BAD_PUBLIC_KEY_ERROR: u64 = NEIGHBORHOOD_PREFIX | 3;
+pub const BAD_EXIT_COUNTRY_ERROR: u64 = NEIGHBORHOOD_PREFIX | 4;
+pub const NEIGHBORHOOD_SNAPSHOT_INTERVAL_SECS: u64 = 300;
+pub const BOOTSTRAP_DEBUT_RETRY_INTERVAL_SECS: u64 = 30;
+// Debuting Nodes keep retrying, so a debut nobody has looked at for this long is stale
+pub const PENDING_DEBUT_EXPIRY_SECS: u64 = 3600;
+pub const MAX_PENDING_DEBUTS: usize = 100;
+
+#[derive(Clone, Debug, PartialEq)]
+struct PendingDebut {
+    agr: AccessibleGossipRecord,
+    gossip_source: SocketAddr,
+    received_at: SystemTime,
+}
 
 pub struct Neighborhood {
     cryptde: &'static dyn CryptDE,
     hopper: Option<Recipient<IncipientCoresPackage>>,
     hopper_no_lookup: Option<Recipient<NoLookupIncipientCoresPackage>>,
     is_connected: bool,
+    connection_stage: UiConnectionStage,
     connected_signal: Option<Recipient<StartMessage>>,
-    _to_ui_message_sub: Option<Recipient<NodeToUiMessage>>,
+    to_ui_message_sub: Option<Recipient<NodeToUiMessage>>,
+    exit_country_sub_opt: Option<Recipient<SetExitCountryMessage>>,
+    remove_neighbor_sub_opt: Option<Recipient<RemoveNeighborMessage>>,
+    bootstrap_list_sub_opt: Option<Recipient<BootstrapListMessage>>,
     gossip_acceptor: Box<dyn GossipAcceptor>,
     gossip_producer: Box<dyn GossipProducer>,
     neighborhood_database: NeighborhoodDatabase,
     consuming_wallet_opt: Option<Wallet>,
     next_return_route_id: u32,
     initial_neighbors: Vec<NodeDescriptor>,
+    bootstrap_source_opt: Option<BootstrapSource>,
+    bootstrap_candidates_opt: Option<Vec<NodeDescriptor>>,
     chain_id: u8,
     data_directory: PathBuf,
     persistent_config_opt: Option<Box<dyn PersistentConfiguration>>,
+    malefactor_ban_dao_opt: Option<Box<dyn MalefactorBanDao>>,
+    debut_allow_list_dao_opt: Option<Box<dyn DebutAllowListDao>>,
+    neighborhood_snapshot_dao_opt: Option<Box<dyn NeighborhoodSnapshotDao>>,
+    pending_debuts: Vec<PendingDebut>,
+    exit_country_opt: Option<ExitCountryPreference>,
+    geoip_database_opt: Option<GeoIpDatabase>,
+    gossip_rate_limiter: GossipRateLimiter,
     db_password_opt: Option<String>,
+    neighborhood_mode: NeighborhoodMode,
+    ui_port: u16,
+    start_time: Instant,
     logger: Logger,
 }
 
@@ -105,14 +178,37 @@ impl Handler<BindMessage> for Neighborhood {
         self.hopper = Some(msg.peer_actors.hopper.from_hopper_client);
         self.hopper_no_lookup = Some(msg.peer_actors.hopper.from_hopper_client_no_lookup);
         self.connected_signal = Some(msg.peer_actors.accountant.start);
+        self.to_ui_message_sub = Some(msg.peer_actors.ui_gateway.node_to_ui_message_sub);
+        self.exit_country_sub_opt = Some(msg.peer_actors.proxy_server.set_exit_country_sub);
+        self.remove_neighbor_sub_opt = Some(msg.peer_actors.proxy_server.remove_neighbor_sub);
+        self.bootstrap_list_sub_opt = Some(msg.peer_actors.neighborhood.bootstrap_list_sub);
     }
 }
 
 impl Handler<StartMessage> for Neighborhood {
     type Result = ();
 
-    fn handle(&mut self, _msg: StartMessage, _ctx: &mut Self::Context) -> Self::Result {
+    fn handle(&mut self, _msg: StartMessage, ctx: &mut Self::Context) -> Self::Result {
         self.handle_start_message();
+
+        ctx.run_interval(
+            Duration::from_secs(NEIGHBORHOOD_SNAPSHOT_INTERVAL_SECS),
+            |neighborhood, _ctx| neighborhood.save_neighborhood_snapshot(),
+        );
+    }
+}
+
+impl Handler<BootstrapListMessage> for Neighborhood {
+    type Result = ();
+
+    fn handle(&mut self, msg: BootstrapListMessage, ctx: &mut Self::Context) -> Self::Result {
+        self.handle_bootstrap_list_message(msg);
+        if self.bootstrap_candidates_opt.is_some() {
+            ctx.run_interval(
+                Duration::from_secs(BOOTSTRAP_DEBUT_RETRY_INTERVAL_SECS),
+                |neighborhood, _ctx| neighborhood.retry_bootstrap_debut(),
+            );
+        }
     }
 }
 
@@ -239,10 +335,17 @@ impl Handler<RemoveNeighborMessage> for Neighborhood {
                     info!(
                         self.logger,
                         "removed neighbor by public key: {}", public_key
-                    )
+                    );
+                    self.check_connectedness();
                 }
             }
         }
+        // Streams routed through the departed Node need new routes
+        if let Some(remove_neighbor_sub) = &self.remove_neighbor_sub_opt {
+            remove_neighbor_sub
+                .try_send(msg)
+                .expect("ProxyServer is dead");
+        }
     }
 }
 
@@ -250,13 +353,7 @@ impl Handler<NodeRecordMetadataMessage> for Neighborhood {
     type Result = ();
 
     fn handle(&mut self, msg: NodeRecordMetadataMessage, _ctx: &mut Self::Context) -> Self::Result {
-        match msg {
-            NodeRecordMetadataMessage::Desirable(public_key, desirable) => {
-                if let Some(node_record) = self.neighborhood_database.node_by_key_mut(&public_key) {
-                    node_record.set_desirable(desirable);
-                };
-            }
-        };
+        self.handle_node_record_metadata_message(msg);
     }
 }
 
@@ -273,8 +370,41 @@ impl Handler<NodeFromUiMessage> for Neighborhood {
 
     fn handle(&mut self, msg: NodeFromUiMessage, _ctx: &mut Self::Context) -> Self::Result {
         let client_id = msg.client_id;
-        if let Ok((body, _)) = UiShutdownRequest::fmb(msg.body) {
+        if let Ok((body, _)) = UiShutdownRequest::fmb(msg.body.clone()) {
             self.handle_shutdown_order(client_id, body);
+        } else if let Ok((_, context_id)) = UiMalefactorBansRequest::fmb(msg.body.clone()) {
+            let response = self.handle_malefactor_bans(context_id);
+            self.send_to_ui_gateway(MessageTarget::ClientId(client_id), response);
+        } else if let Ok((body, context_id)) = UiMalefactorUnbanRequest::fmb(msg.body.clone()) {
+            let response = self.handle_malefactor_unban(body, client_id, context_id);
+            self.send_to_ui_gateway(MessageTarget::ClientId(client_id), response);
+        } else if let Ok((_, context_id)) = UiPendingDebutsRequest::fmb(msg.body.clone()) {
+            let response = self.handle_pending_debuts(context_id);
+            self.send_to_ui_gateway(MessageTarget::ClientId(client_id), response);
+        } else if let Ok((body, context_id)) = UiApproveDebutRequest::fmb(msg.body.clone()) {
+            let response = self.handle_approve_debut(body, client_id, context_id);
+            self.send_to_ui_gateway(MessageTarget::ClientId(client_id), response);
+        } else if let Ok((body, context_id)) = UiRejectDebutRequest::fmb(msg.body.clone()) {
+            let response = self.handle_reject_debut(body, client_id, context_id);
+            self.send_to_ui_gateway(MessageTarget::ClientId(client_id), response);
+        } else if let Ok((body, context_id)) = UiDebutAllowListRequest::fmb(msg.body.clone()) {
+            let response = self.handle_debut_allow_list(body, context_id);
+            self.send_to_ui_gateway(MessageTarget::ClientId(client_id), response);
+        } else if let Ok((body, context_id)) = UiExitCountryRequest::fmb(msg.body.clone()) {
+            let response = self.handle_exit_country(body, client_id, context_id);
+            self.send_to_ui_gateway(MessageTarget::ClientId(client_id), response);
+        } else if let Ok((_, context_id)) = UiDroppedGossipRequest::fmb(msg.body.clone()) {
+            let response = self.handle_dropped_gossip(context_id);
+            self.send_to_ui_gateway(MessageTarget::ClientId(client_id), response);
+        } else if let Ok((_, context_id)) = UiStatusRequest::fmb(msg.body.clone()) {
+            let response = self.handle_status(context_id);
+            self.send_to_ui_gateway(MessageTarget::ClientId(client_id), response);
+        } else if let Ok((_, context_id)) = UiConnectionStatusRequest::fmb(msg.body) {
+            let response = UiConnectionStatusResponse {
+                stage: self.connection_stage,
+            }
+            .tmb(context_id);
+            self.send_to_ui_gateway(MessageTarget::ClientId(client_id), response);
         }
     }
 }
@@ -292,7 +422,7 @@ pub struct AccessibleGossipRecord {
     pub signed_gossip: PlainData,
     pub signature: CryptData,
     pub node_addr_opt: Option<NodeAddr>,
-    pub inner: NodeRecordInner_0v1,
+    pub inner: NodeRecordInner_0v2,
 }
 
 impl AccessibleGossipRecord {
@@ -335,7 +465,8 @@ impl Neighborhood {
                 "A zero-hop MASQ Node is not decentralized and cannot have a --neighbors setting"
             )
         }
-        let gossip_acceptor: Box<dyn GossipAcceptor> = Box::new(GossipAcceptorReal::new(cryptde));
+        let gossip_acceptor: Box<dyn GossipAcceptor> =
+            Box::new(GossipAcceptorReal::new(cryptde, config.debut_approval));
         let gossip_producer = Box::new(GossipProducerReal::new());
         let neighborhood_database = NeighborhoodDatabase::new(
             &cryptde.public_key(),
@@ -343,8 +474,7 @@ impl Neighborhood {
             config.earning_wallet.clone(),
             cryptde,
         );
-        let is_mainnet =
-            || config.blockchain_bridge_config.chain_id == chain_id_from_name(DEFAULT_CHAIN_NAME);
+        let is_mainnet = || is_mainnet_chain(config.blockchain_bridge_config.chain_id);
         let initial_neighbors: Vec<NodeDescriptor> = neighborhood_config
             .mode
             .neighbor_configs()
@@ -366,18 +496,38 @@ impl Neighborhood {
             hopper: None,
             hopper_no_lookup: None,
             connected_signal: None,
-            _to_ui_message_sub: None,
+            to_ui_message_sub: None,
+            exit_country_sub_opt: None,
+            remove_neighbor_sub_opt: None,
+            bootstrap_list_sub_opt: None,
             is_connected: false,
+            connection_stage: UiConnectionStage::NotConnected,
             gossip_acceptor,
             gossip_producer,
             neighborhood_database,
             consuming_wallet_opt: config.consuming_wallet.clone(),
             next_return_route_id: 0,
             initial_neighbors,
+            bootstrap_source_opt: if neighborhood_config.mode.is_zero_hop() {
+                None
+            } else {
+                config.bootstrap_source_opt.clone()
+            },
+            bootstrap_candidates_opt: None,
             chain_id: config.blockchain_bridge_config.chain_id,
             data_directory: config.data_directory.clone(),
             persistent_config_opt: None,
+            malefactor_ban_dao_opt: None,
+            debut_allow_list_dao_opt: None,
+            neighborhood_snapshot_dao_opt: None,
+            pending_debuts: vec![],
+            exit_country_opt: config.exit_country_opt.clone(),
+            geoip_database_opt: None,
+            gossip_rate_limiter: GossipRateLimiter::default(),
             db_password_opt: config.db_password_opt.clone(),
+            neighborhood_mode: neighborhood_config.mode.clone(),
+            ui_port: config.ui_gateway_config.ui_port,
+            start_time: Instant::now(),
             logger: Logger::new("Neighborhood"),
         }
     }
@@ -399,11 +549,16 @@ impl Neighborhood {
             set_consuming_wallet_sub: addr.clone().recipient::<SetConsumingWalletMessage>(),
             from_ui_message_sub: addr.clone().recipient::<NodeFromUiMessage>(),
             new_password_sub: addr.clone().recipient::<NewPasswordMessage>(),
+            bootstrap_list_sub: addr.clone().recipient::<BootstrapListMessage>(),
         }
     }
 
     fn handle_start_message(&mut self) {
         self.connect_database();
+        self.load_geoip_database();
+        self.announce_exit_country();
+        self.load_neighborhood_snapshot();
+        self.consult_bootstrap_source();
         self.send_debut_gossip();
     }
 
@@ -433,12 +588,119 @@ impl Neighborhood {
 
     fn connect_database(&mut self) {
         if self.persistent_config_opt.is_none() {
-            let db_initializer = DbInitializerReal::new();
-            let conn = db_initializer
-                .initialize(&self.data_directory, self.chain_id, true) // TODO: Probably should be false
-                .expect("Neighborhood could not connect to database");
+            let conn = self.make_connection();
             self.persistent_config_opt = Some(Box::new(PersistentConfigurationReal::from(conn)));
         }
+        if self.malefactor_ban_dao_opt.is_none() {
+            let conn = self.make_connection();
+            self.malefactor_ban_dao_opt = Some(Box::new(MalefactorBanDaoReal::new(conn)));
+        }
+        if self.debut_allow_list_dao_opt.is_none() {
+            let conn = self.make_connection();
+            self.debut_allow_list_dao_opt = Some(Box::new(DebutAllowListDaoReal::new(conn)));
+        }
+        if self.neighborhood_snapshot_dao_opt.is_none() {
+            let conn = self.make_connection();
+            self.neighborhood_snapshot_dao_opt =
+                Some(Box::new(NeighborhoodSnapshotDaoReal::new(conn)));
+        }
+    }
+
+    fn load_neighborhood_snapshot(&mut self) {
+        let records = self
+            .neighborhood_snapshot_dao_opt
+            .as_ref()
+            .expect("NeighborhoodSnapshotDao was not set by StartMessage")
+            .load();
+        let record_count = records.len();
+        let deadline = time_t_timestamp().saturating_sub(SNAPSHOT_RECORD_GRACE_PERIOD_SECS);
+        let mut restored_count = 0;
+        for record in records {
+            let public_key = record.public_key.clone();
+            match self.node_record_from_snapshot(record, deadline) {
+                Ok(node_record) => match self.neighborhood_database.add_node(node_record) {
+                    Ok(_) => restored_count += 1,
+                    Err(e) => debug!(
+                        self.logger,
+                        "Discarding snapshot record for {}: {:?}", public_key, e
+                    ),
+                },
+                Err(msg) => debug!(
+                    self.logger,
+                    "Discarding snapshot record for {}: {}", public_key, msg
+                ),
+            }
+        }
+        if record_count > 0 {
+            info!(
+                self.logger,
+                "Restored {} of {} Nodes from the neighborhood snapshot",
+                restored_count,
+                record_count
+            );
+        }
+    }
+
+    fn node_record_from_snapshot(
+        &self,
+        record: NeighborhoodSnapshotRecord,
+        deadline: u32,
+    ) -> Result<NodeRecord, String> {
+        if record.last_update < deadline {
+            return Err(format!(
+                "not updated in the last {} seconds",
+                SNAPSHOT_RECORD_GRACE_PERIOD_SECS
+            ));
+        }
+        let agr = AccessibleGossipRecord::try_from(GossipNodeRecord {
+            signed_data: record.signed_gossip,
+            signature: record.signature,
+            node_addr_opt: record.node_addr_opt,
+        })?;
+        if agr.inner.public_key != record.public_key {
+            return Err("public key does not match signed Gossip".to_string());
+        }
+        if &agr.inner.public_key == self.cryptde.public_key() {
+            return Err("record describes this Node".to_string());
+        }
+        if !self
+            .cryptde
+            .verify_signature(&agr.signed_gossip, &agr.signature, &agr.inner.public_key)
+        {
+            return Err("invalid signature".to_string());
+        }
+        let mut node_record = NodeRecord::from(agr);
+        node_record.metadata.desirable = record.desirable;
+        node_record.metadata.last_update = record.last_update;
+        Ok(node_record)
+    }
+
+    fn save_neighborhood_snapshot(&mut self) {
+        let root_key = self.cryptde.public_key();
+        let records = self
+            .neighborhood_database
+            .keys()
+            .into_iter()
+            .filter(|key| *key != root_key)
+            .flat_map(|key| self.neighborhood_database.node_by_key(key))
+            .map(NeighborhoodSnapshotRecord::from)
+            .collect_vec();
+        let record_count = records.len();
+        if let Some(dao) = self.neighborhood_snapshot_dao_opt.as_mut() {
+            match dao.save(records) {
+                Ok(_) => debug!(
+                    self.logger,
+                    "Saved {} Nodes to the neighborhood snapshot", record_count
+                ),
+                Err(e) => error!(self.logger, "Could not save neighborhood snapshot: {}", e),
+            }
+        }
+    }
+
+    fn make_connection(&self) -> Box<dyn ConnectionWrapper> {
+        DbInitializerReal::new()
+            .initialize(&self.data_directory, self.chain_id, true) // TODO: Probably should be false
+            .expect("Neighborhood could not connect to database")
     }
 
     fn send_debut_gossip(&mut self) {
@@ -485,6 +747,123 @@ impl Neighborhood {
         });
     }
 
+    // With no --neighbors and no past neighbors, the only way into the Network is through the
+    // bootstrap source. Fetching it can take a while, so it's done on its own thread, and the
+    // Debut waits until the list comes back in a BootstrapListMessage.
+    fn consult_bootstrap_source(&mut self) {
+        if !self.initial_neighbors.is_empty() {
+            return;
+        }
+        let bootstrap_source = match &self.bootstrap_source_opt {
+            Some(bootstrap_source) => bootstrap_source.clone(),
+            None => return,
+        };
+        let bootstrap_list_sub = self
+            .bootstrap_list_sub_opt
+            .clone()
+            .expect("Neighborhood unbound");
+        let cryptde = self.cryptde;
+        thread::spawn(move || {
+            let descriptors_result =
+                fetch_bootstrap_list(&bootstrap_source.location).and_then(|text| {
+                    parse_bootstrap_list(&text, cryptde, bootstrap_source.public_key_opt.as_ref())
+                });
+            bootstrap_list_sub
+                .try_send(BootstrapListMessage { descriptors_result })
+                .expect("Neighborhood is dead");
+        });
+    }
+
+    fn handle_bootstrap_list_message(&mut self, msg: BootstrapListMessage) {
+        let bootstrap_source = match &self.bootstrap_source_opt {
+            Some(bootstrap_source) => bootstrap_source.clone(),
+            None => return,
+        };
+        if !self
+            .neighborhood_database
+            .root()
+            .half_neighbor_keys()
+            .is_empty()
+        {
+            debug!(
+                self.logger,
+                "Joined the Network while the bootstrap source was being fetched; ignoring it"
+            );
+            return;
+        }
+        let descriptors = match msg.descriptors_result {
+            Ok(descriptors) => descriptors,
+            Err(msg) => {
+                error!(
+                    self.logger,
+                    "Couldn't use bootstrap source {}: {}", bootstrap_source.location, msg
+                );
+                return;
+            }
+        };
+        let is_mainnet = is_mainnet_chain(self.chain_id);
+        let own_key = NodeDescriptor::from((self.cryptde.public_key(), is_mainnet, self.cryptde))
+            .encryption_public_key;
+        let mut candidates = descriptors
+            .into_iter()
+            .filter(|descriptor| {
+                descriptor.mainnet == is_mainnet
+                    && descriptor.node_addr_opt.is_some()
+                    && descriptor.encryption_public_key != own_key
+            })
+            .collect_vec();
+        candidates.shuffle(&mut thread_rng());
+        info!(
+            self.logger,
+            "Bootstrap source {} offered {} usable Nodes",
+            bootstrap_source.location,
+            candidates.len()
+        );
+        self.bootstrap_candidates_opt = Some(candidates);
+        if self.take_next_bootstrap_candidate() {
+            self.send_debut_gossip();
+        }
+    }
+
+    fn take_next_bootstrap_candidate(&mut self) -> bool {
+        let candidate = match self
+            .bootstrap_candidates_opt
+            .as_mut()
+            .and_then(|candidates| candidates.pop())
+        {
+            Some(candidate) => candidate,
+            None => return false,
+        };
+        info!(
+            self.logger,
+            "Debuting to {} from the bootstrap source",
+            candidate.to_string(self.cryptde)
+        );
+        self.initial_neighbors = vec![candidate];
+        true
+    }
+
+    // A bootstrap Node that neither accepts nor refuses our Debut is given up on after a while.
+    fn retry_bootstrap_debut(&mut self) {
+        if self.bootstrap_candidates_opt.is_none() {
+            return;
+        }
+        if !self
+            .neighborhood_database
+            .root()
+            .half_neighbor_keys()
+            .is_empty()
+        {
+            debug!(
+                self.logger,
+                "Debut succeeded; done with the bootstrap source"
+            );
+            self.bootstrap_candidates_opt = None;
+        } else if self.take_next_bootstrap_candidate() {
+            self.send_debut_gossip();
+        }
+    }
+
     fn log_incoming_gossip(&self, incoming_gossip: &Gossip_0v1, gossip_source: SocketAddr) {
         let source = match self.neighborhood_database.node_by_ip(&gossip_source.ip()) {
             Some(node) => DotGossipEndpoint::from(node),
@@ -499,6 +878,20 @@ impl Neighborhood {
 
     fn handle_gossip(&mut self, incoming_gossip: Gossip_0v1, gossip_source: SocketAddr) {
         let record_count = incoming_gossip.node_records.len();
+        if let Err(reason) =
+            self.gossip_rate_limiter
+                .admit(&incoming_gossip, gossip_source.ip(), Instant::now())
+        {
+            debug!(
+                self.logger,
+                "Dropping Gossip about {} Nodes from {}: {}; dropped so far: {}",
+                record_count,
+                gossip_source,
+                reason,
+                self.gossip_rate_limiter.dropped()
+            );
+            return;
+        }
         info!(
             self.logger,
             "Processing Gossip about {} Nodes", record_count
@@ -548,6 +941,10 @@ impl Neighborhood {
                 None => false,
                 Some(node_addr) => node_addr.ip_addr() == failure_source.ip(),
             }) {
+            None if self.bootstrap_candidates_opt.is_some() => debug!(
+                self.logger,
+                "Ignoring late Debut refusal from {}: {}", failure_source, failure
+            ),
             None => unimplemented!("TODO: Test-drive me (or replace me with a panic)"),
             Some((position, node_descriptor)) => {
                 warning!(
@@ -561,7 +958,15 @@ impl Neighborhood {
                     failure
                 );
                 self.initial_neighbors.remove(position);
-                if self.initial_neighbors.is_empty() {
+                if !self.initial_neighbors.is_empty() {
+                    return;
+                }
+                if self.take_next_bootstrap_candidate() {
+                    self.send_debut_gossip();
+                } else if self.bootstrap_candidates_opt.is_some() {
+                    error!(self.logger, "None of the Nodes from the bootstrap source could accept your Debut; shutting down");
+                    System::current().stop_with_code(1)
+                } else {
                     error!(self.logger, "None of the Nodes listed in the --neighbors parameter could accept your Debut; shutting down");
                     System::current().stop_with_code(1)
                 }
@@ -576,7 +981,7 @@ impl Neighborhood {
                     self.neighborhood_database
                         .node_by_key(k)
                         .expect("Node disappeared"),
-                    self.chain_id == chain_id_from_name(DEFAULT_CHAIN_NAME),
+                    is_mainnet_chain(self.chain_id),
                     self.cryptde,
                 ))
             })
@@ -602,6 +1007,7 @@ impl Neighborhood {
     fn handle_agrs(&mut self, agrs: Vec<AccessibleGossipRecord>, gossip_source: SocketAddr) {
         let ignored_node_name = self.gossip_source_name(&agrs, gossip_source);
         let gossip_record_count = agrs.len();
+        let source = Malefactor::from_gossip_source(&agrs, gossip_source);
         let acceptance_result =
             self.gossip_acceptor
                 .handle(&mut self.neighborhood_database, agrs, gossip_source);
@@ -618,11 +1024,92 @@ impl Neighborhood {
                 self.handle_gossip_ignored(ignored_node_name, gossip_record_count)
             }
             GossipAcceptanceResult::Ban(reason) => {
-                warning!(self.logger, "Malefactor detected at {}, but malefactor bans not yet implemented; ignoring: {}", gossip_source, reason
-            );
+                self.ban_malefactor(source, &reason);
                 self.handle_gossip_ignored(ignored_node_name, gossip_record_count);
             }
+            GossipAcceptanceResult::AwaitingApproval(agr, gossip_source) => {
+                self.hold_debut(agr, gossip_source)
+            }
+        }
+    }
+
+    fn hold_debut(&mut self, agr: AccessibleGossipRecord, gossip_source: SocketAddr) {
+        let public_key = agr.inner.public_key.clone();
+        self.expire_pending_debuts();
+        self.pending_debuts
+            .retain(|pending| pending.agr.inner.public_key != public_key);
+        if self.pending_debuts.len() >= MAX_PENDING_DEBUTS {
+            let oldest = self.pending_debuts.remove(0);
+            warning!(
+                self.logger,
+                "Too many debuts awaiting manual approval; dropping debut from {} at {}",
+                oldest.agr.inner.public_key,
+                oldest.gossip_source.ip()
+            );
+        }
+        let pending_debut = PendingDebut {
+            agr,
+            gossip_source,
+            received_at: SystemTime::now(),
+        };
+        info!(
+            self.logger,
+            "Debut from {} at {} is awaiting manual approval",
+            public_key,
+            gossip_source.ip()
+        );
+        let broadcast = UiDebutPendingBroadcast {
+            debut: Self::to_ui_pending_debut(&pending_debut),
         }
+        .tmb(0);
+        self.pending_debuts.push(pending_debut);
+        if self.to_ui_message_sub.is_some() {
+            self.send_to_ui_gateway(MessageTarget::AllClients, broadcast);
+        }
+    }
+
+    fn expire_pending_debuts(&mut self) {
+        let now = SystemTime::now();
+        let expiry = Duration::from_secs(PENDING_DEBUT_EXPIRY_SECS);
+        let logger = &self.logger;
+        self.pending_debuts.retain(|pending| {
+            let expired = match now.duration_since(pending.received_at) {
+                Ok(age) => age >= expiry,
+                Err(_) => false,
+            };
+            if expired {
+                debug!(
+                    logger,
+                    "Debut from {} at {} expired without manual approval",
+                    pending.agr.inner.public_key,
+                    pending.gossip_source.ip()
+                );
+            }
+            !expired
+        });
+    }
+
+    fn ban_malefactor(&mut self, malefactor: Malefactor, reason: &str) {
+        let ban = self
+            .malefactor_ban_dao_opt
+            .as_ref()
+            .expect("MalefactorBanDao was not set by StartMessage")
+            .ban(
+                &malefactor,
+                reason,
+                Some(SystemTime::now() + MALEFACTOR_BAN_DURATION),
+            );
+        warning!(
+            self.logger,
+            "Malefactor detected at {}; banned for {} hours as ban {}: {}",
+            malefactor
+                .ip_address_opt
+                .map(|ip_address| ip_address.to_string())
+                .unwrap_or_else(|| "unknown address".to_string()),
+            MALEFACTOR_BAN_DURATION.as_secs() / 3600,
+            ban.id,
+            reason
+        );
     }
 
     fn handle_database_changes(
@@ -631,7 +1118,13 @@ impl Neighborhood {
         neighbor_keys_after: &[PublicKey],
     ) {
         self.curate_past_neighbors(neighbor_keys_before, neighbor_keys_after);
-        self.check_connectedness();
+        // Looking for a route is expensive, and once one has been found, only losing a neighbor
+        // can lose it again
+        if self.connection_stage != UiConnectionStage::ThreeHopsRouteFound
+            || neighbor_keys_after != neighbor_keys_before
+        {
+            self.check_connectedness();
+        }
     }
 
     fn curate_past_neighbors(
@@ -672,22 +1165,52 @@ impl Neighborhood {
     }
 
     fn check_connectedness(&mut self) {
-        if self.is_connected {
-            return;
+        let stage = self.current_connection_stage();
+        if stage == UiConnectionStage::ThreeHopsRouteFound && !self.is_connected {
+            self.is_connected = true;
+            self.connected_signal
+                .as_ref()
+                .expect("Accountant was not bound")
+                .try_send(StartMessage {})
+                .expect("Accountant is dead")
+        }
+        if stage != self.connection_stage {
+            info!(
+                self.logger,
+                "Connection stage changed from {:?} to {:?}", self.connection_stage, stage
+            );
+            self.connection_stage = stage;
+            if self.to_ui_message_sub.is_some() {
+                self.send_to_ui_gateway(
+                    MessageTarget::AllClients,
+                    UiConnectionChangeBroadcast { stage }.tmb(0),
+                );
+            }
         }
+    }
+
+    fn current_connection_stage(&mut self) -> UiConnectionStage {
         let msg = RouteQueryMessage {
             target_key_opt: None,
             target_component: Component::ProxyClient,
             minimum_hop_count: DEFAULT_MINIMUM_HOP_COUNT,
             return_component_opt: Some(Component::ProxyServer),
         };
-        if self.handle_route_query_message(msg).is_some() {
-            self.is_connected = true;
-            self.connected_signal
-                .as_ref()
-                .expect("Accountant was not bound")
-                .try_send(StartMessage {})
-                .expect("Accountant is dead")
+        // This route is only a probe, so it mustn't consume a return route ID
+        let next_return_route_id = self.next_return_route_id;
+        let route_found = self.make_round_trip_route(msg).is_ok();
+        self.next_return_route_id = next_return_route_id;
+        if route_found {
+            UiConnectionStage::ThreeHopsRouteFound
+        } else if !self
+            .neighborhood_database
+            .root()
+            .full_neighbor_keys(&self.neighborhood_database)
+            .is_empty()
+        {
+            UiConnectionStage::ConnectedToNeighbor
+        } else {
+            UiConnectionStage::NotConnected
         }
     }
 
@@ -875,15 +1398,93 @@ impl Neighborhood {
                 minimum_hop_count, origin, target_component, target_str
             ))
         } else {
+            self.sort_routes_by_quality(&mut node_seqs);
             // When the target is Some all exit nodes will be the target and it is not optimal to sort.
             if target.is_none() {
                 self.sort_routes_by_desirable_exit_nodes(node_seqs.as_mut());
+                self.apply_exit_country_preference(&mut node_seqs);
+                if node_seqs.is_empty() {
+                    return Err(format!(
+                        "Couldn't find any routes: at least {}-hop from {} to {:?} through an exit Node in {}",
+                        minimum_hop_count,
+                        origin,
+                        target_component,
+                        self.exit_country_opt
+                            .as_ref()
+                            .map(|preference| preference.countries.join(", "))
+                            .unwrap_or_default()
+                    ));
+                }
             }
             let chosen_node_seq = node_seqs.remove(0);
             Ok(RouteSegment::new(chosen_node_seq, target_component))
         }
     }
 
+    fn handle_node_record_metadata_message(&mut self, msg: NodeRecordMetadataMessage) {
+        match msg {
+            NodeRecordMetadataMessage::Desirable(public_key, desirable) => {
+                if let Some(node_record) = self.neighborhood_database.node_by_key_mut(&public_key) {
+                    node_record.set_desirable(desirable);
+                };
+            }
+            NodeRecordMetadataMessage::RoundTrip(public_keys, round_trip_millis) => {
+                public_keys.iter().for_each(|public_key| {
+                    if let Some(node_record) =
+                        self.neighborhood_database.node_by_key_mut(public_key)
+                    {
+                        node_record
+                            .quality_mut()
+                            .record_round_trip(round_trip_millis);
+                    }
+                });
+            }
+            NodeRecordMetadataMessage::Failure(public_keys) => {
+                public_keys.iter().for_each(|public_key| {
+                    if let Some(node_record) =
+                        self.neighborhood_database.node_by_key_mut(public_key)
+                    {
+                        node_record.quality_mut().record_failure();
+                    }
+                });
+            }
+        };
+    }
+
+    // Routes whose Nodes have carried our traffic quickly and reliably come first; the origin's
+    // own record doesn't count, since every route starts there. A Node we haven't observed yet is
+    // assumed to be typical, so it neither jumps the queue nor gets shunned.
+    fn sort_routes_by_quality(&self, node_seqs: &mut Vec<Vec<&PublicKey>>) {
+        let unobserved_penalty = self.median_observed_penalty();
+        node_seqs.sort_by_key(|node_seq| {
+            node_seq
+                .iter()
+                .skip(1)
+                .filter_map(|key| self.neighborhood_database.node_by_key(key))
+                .map(|node| {
+                    if node.quality().is_observed() {
+                        u64::from(node.quality().penalty())
+                    } else {
+                        u64::from(unobserved_penalty)
+                    }
+                })
+                .sum::<u64>()
+        });
+    }
+
+    fn median_observed_penalty(&self) -> u32 {
+        let mut penalties = self
+            .neighborhood_database
+            .keys()
+            .into_iter()
+            .filter_map(|key| self.neighborhood_database.node_by_key(key))
+            .filter(|node| node.quality().is_observed())
+            .map(|node| node.quality().penalty())
+            .collect::<Vec<u32>>();
+        penalties.sort_unstable();
+        penalties.get(penalties.len() / 2).cloned().unwrap_or(0)
+    }
+
     fn sort_routes_by_desirable_exit_nodes(&self, node_seqs: &mut Vec<Vec<&PublicKey>>) {
         if node_seqs.is_empty() {
             panic!("Unable to sort routes by desirable exit nodes: Missing routes.");
@@ -911,6 +1512,82 @@ impl Neighborhood {
         });
     }
 
+    // A strict preference discards routes whose exit Nodes are in other countries; a non-strict
+    // one moves the routes whose exit Nodes qualify to the front, keeping the existing order
+    // otherwise.
+    fn apply_exit_country_preference(&self, node_seqs: &mut Vec<Vec<&PublicKey>>) {
+        let preference = match &self.exit_country_opt {
+            Some(preference) => preference,
+            None => return,
+        };
+        let exit_qualifies = |node_seq: &Vec<&PublicKey>| -> bool {
+            node_seq
+                .last()
+                .and_then(|key| self.neighborhood_database.node_by_key(key))
+                .and_then(|node| self.country_of(node))
+                .map(|country| preference.includes(&country))
+                .unwrap_or(false)
+        };
+        if preference.strict {
+            node_seqs.retain(|node_seq| exit_qualifies(node_seq));
+        } else {
+            node_seqs.sort_by_key(|node_seq| !exit_qualifies(node_seq));
+        }
+    }
+
+    fn country_of(&self, node: &NodeRecord) -> Option<String> {
+        let geoip_country_opt = match (&self.geoip_database_opt, node.node_addr_opt()) {
+            (Some(geoip_database), Some(node_addr)) => geoip_database
+                .country_of(node_addr.ip_addr())
+                .map(|country| country.to_string()),
+            _ => None,
+        };
+        geoip_country_opt.or_else(|| {
+            node.inner
+                .country_code_opt
+                .as_ref()
+                .map(|country| country.to_ascii_uppercase())
+        })
+    }
+
+    fn load_geoip_database(&mut self) {
+        match GeoIpDatabase::load(&self.data_directory) {
+            Ok(Some(geoip_database)) => {
+                info!(
+                    self.logger,
+                    "Loaded {} address ranges from the GeoIP database",
+                    geoip_database.len()
+                );
+                self.geoip_database_opt = Some(geoip_database);
+                self.declare_root_country();
+            }
+            Ok(None) => debug!(
+                self.logger,
+                "No GeoIP database in the data directory; relying on countries declared in Gossip"
+            ),
+            Err(e) => warning!(self.logger, "Ignoring unreadable GeoIP database {}", e),
+        }
+    }
+
+    fn declare_root_country(&mut self) {
+        let country_opt = self.country_of(self.neighborhood_database.root());
+        if country_opt.is_some() {
+            let root = self.neighborhood_database.root_mut();
+            root.inner.country_code_opt = country_opt;
+            root.regenerate_signed_gossip(self.cryptde);
+        }
+    }
+
+    fn announce_exit_country(&self) {
+        if let Some(exit_country_sub) = &self.exit_country_sub_opt {
+            exit_country_sub
+                .try_send(SetExitCountryMessage {
+                    preference_opt: self.exit_country_opt.clone(),
+                })
+                .expect("ProxyServer is dead");
+        }
+    }
+
     fn make_expected_services(
         &self,
         segment: &RouteSegment,
@@ -1182,7 +1859,8 @@ impl Neighborhood {
                     neighbor_key,
                     peer_addr.ip()
                 );
-                self.gossip_to_neighbors()
+                self.gossip_to_neighbors();
+                self.check_connectedness();
             }
             Ok(false) => {
                 debug!(self.logger, "Received shutdown notification for {} at {}, but that Node is no neighbor - ignoring", neighbor_key, peer_addr.ip());
@@ -1191,11 +1869,12 @@ impl Neighborhood {
     }
 
     #[allow(unreachable_code)]
-    fn handle_shutdown_order(&self, client_id: u64, _msg: UiShutdownRequest) {
+    fn handle_shutdown_order(&mut self, client_id: u64, _msg: UiShutdownRequest) {
         info!(
             self.logger,
             "Received shutdown order from client {}: shutting down hard", client_id
         );
+        self.save_neighborhood_snapshot();
         exit_process(
             0,
             &format!(
@@ -1208,30 +1887,312 @@ impl Neighborhood {
     fn handle_new_password(&mut self, new_password: String) {
         self.db_password_opt = Some(new_password);
     }
-}
 
-pub fn regenerate_signed_gossip(
-    inner: &NodeRecordInner_0v1,
-    cryptde: &dyn CryptDE, // Must be the correct CryptDE for the Node from which inner came: used for signing
-) -> (PlainData, CryptData) {
-    let signed_gossip =
-        PlainData::from(serde_cbor::ser::to_vec(&inner).expect("Serialization failed"));
-    let signature = match cryptde.sign(&signed_gossip) {
-        Ok(sig) => sig,
-        Err(e) => unimplemented!("TODO: Signing error: {:?}", e),
-    };
-    (signed_gossip, signature)
-}
+    fn handle_malefactor_bans(&self, context_id: u64) -> MessageBody {
+        let bans = self
+            .malefactor_ban_dao_opt
+            .as_ref()
+            .expect("MalefactorBanDao was not set by StartMessage")
+            .ban_list()
+            .into_iter()
+            .map(Self::to_ui_malefactor_ban)
+            .collect();
+        UiMalefactorBansResponse { bans }.tmb(context_id)
+    }
 
-#[cfg(test)]
-mod tests {
-    use super::*;
-    use crate::blockchain::blockchain_interface::{chain_id_from_name, contract_address};
-    use crate::db_config::persistent_configuration::PersistentConfigError;
-    use crate::neighborhood::gossip::GossipBuilder;
-    use crate::neighborhood::gossip::Gossip_0v1;
-    use crate::neighborhood::node_record::NodeRecordInner_0v1;
-    use crate::stream_messages::{NonClandestineAttributes, RemovedStreamType};
+    fn handle_malefactor_unban(
+        &self,
+        request: UiMalefactorUnbanRequest,
+        client_id: u64,
+        context_id: u64,
+    ) -> MessageBody {
+        let unbanned = self
+            .malefactor_ban_dao_opt
+            .as_ref()
+            .expect("MalefactorBanDao was not set by StartMessage")
+            .unban(request.id);
+        if !unbanned {
+            return MessageBody {
+                opcode: "malefactorUnban".to_string(),
+                path: Conversation(context_id),
+                payload: Err((
+                    MALEFACTOR_BAN_NOT_FOUND_ERROR,
+                    format!("There is no malefactor ban with ID {}", request.id),
+                )),
+            };
+        }
+        info!(
+            self.logger,
+            "Malefactor ban {} lifted at the request of client {}", request.id, client_id
+        );
+        UiMalefactorUnbanResponse {}.tmb(context_id)
+    }
+
+    fn handle_exit_country(
+        &mut self,
+        request: UiExitCountryRequest,
+        client_id: u64,
+        context_id: u64,
+    ) -> MessageBody {
+        let preference_opt = if request.countries.is_empty() {
+            None
+        } else {
+            match ExitCountryPreference::new(request.countries, request.strict) {
+                Ok(preference) => Some(preference),
+                Err(e) => {
+                    return MessageBody {
+                        opcode: "exitCountry".to_string(),
+                        path: Conversation(context_id),
+                        payload: Err((BAD_EXIT_COUNTRY_ERROR, e)),
+                    }
+                }
+            }
+        };
+        info!(
+            self.logger,
+            "Exit country preference changed to {} at the request of client {}",
+            match &preference_opt {
+                Some(preference) => preference.to_string(),
+                None => "any country".to_string(),
+            },
+            client_id
+        );
+        self.exit_country_opt = preference_opt;
+        self.announce_exit_country();
+        UiExitCountryResponse {}.tmb(context_id)
+    }
+
+    fn handle_dropped_gossip(&self, context_id: u64) -> MessageBody {
+        let dropped = self.gossip_rate_limiter.dropped();
+        UiDroppedGossipResponse {
+            too_many_records: dropped.too_many_records,
+            duplicate: dropped.duplicate,
+            source_rate_exceeded: dropped.source_rate_exceeded,
+            global_rate_exceeded: dropped.global_rate_exceeded,
+        }
+        .tmb(context_id)
+    }
+
+    fn handle_status(&self, context_id: u64) -> MessageBody {
+        UiStatusResponse {
+            running: true,
+            version: env!("CARGO_PKG_VERSION").to_string(),
+            process_id_opt: Some(std::process::id()),
+            uptime_secs_opt: Some(self.start_time.elapsed().as_secs()),
+            chain_opt: Some(chain_name_from_id(self.chain_id)),
+            neighborhood_mode_opt: Some(self.neighborhood_mode.parameter_value().to_string()),
+            ui_port_opt: Some(self.ui_port),
+            last_crash_reason_opt: None,
+        }
+        .tmb(context_id)
+    }
+
+    fn handle_pending_debuts(&mut self, context_id: u64) -> MessageBody {
+        self.expire_pending_debuts();
+        let debuts = self
+            .pending_debuts
+            .iter()
+            .map(Self::to_ui_pending_debut)
+            .collect();
+        UiPendingDebutsResponse { debuts }.tmb(context_id)
+    }
+
+    fn handle_approve_debut(
+        &mut self,
+        request: UiApproveDebutRequest,
+        client_id: u64,
+        context_id: u64,
+    ) -> MessageBody {
+        let pending_debut =
+            match self.take_pending_debut("approveDebut", &request.public_key, context_id) {
+                Ok(pending_debut) => pending_debut,
+                Err(body) => return body,
+            };
+        let public_key = pending_debut.agr.inner.public_key.clone();
+        info!(
+            self.logger,
+            "Debut from {} approved at the request of client {}", public_key, client_id
+        );
+        DEBUT_ALLOW_LIST.insert(public_key);
+        self.handle_gossip_agrs(vec![pending_debut.agr], pending_debut.gossip_source);
+        UiApproveDebutResponse {}.tmb(context_id)
+    }
+
+    fn handle_reject_debut(
+        &mut self,
+        request: UiRejectDebutRequest,
+        client_id: u64,
+        context_id: u64,
+    ) -> MessageBody {
+        let pending_debut =
+            match self.take_pending_debut("rejectDebut", &request.public_key, context_id) {
+                Ok(pending_debut) => pending_debut,
+                Err(body) => return body,
+            };
+        let public_key = pending_debut.agr.inner.public_key;
+        info!(
+            self.logger,
+            "Debut from {} rejected at the request of client {}", public_key, client_id
+        );
+        self.handle_gossip_failed(
+            GossipFailure_0v1::ManualRejection,
+            &public_key,
+            &pending_debut
+                .agr
+                .node_addr_opt
+                .expect("Pending debut lost its NodeAddr"),
+        );
+        UiRejectDebutResponse {}.tmb(context_id)
+    }
+
+    fn handle_debut_allow_list(
+        &self,
+        request: UiDebutAllowListRequest,
+        context_id: u64,
+    ) -> MessageBody {
+        let parse = |public_key_strs: &[String]| {
+            public_key_strs
+                .iter()
+                .map(|public_key_str| {
+                    Self::parse_public_key("debutAllowList", public_key_str, context_id)
+                })
+                .collect::<Result<Vec<PublicKey>, MessageBody>>()
+        };
+        let (additions, removals) = match (parse(&request.add), parse(&request.remove)) {
+            (Ok(additions), Ok(removals)) => (additions, removals),
+            (Err(body), _) => return body,
+            (_, Err(body)) => return body,
+        };
+        let dao = self
+            .debut_allow_list_dao_opt
+            .as_ref()
+            .expect("DebutAllowListDao was not set by StartMessage");
+        additions
+            .iter()
+            .for_each(|public_key| dao.allow(public_key));
+        removals
+            .iter()
+            .for_each(|public_key| dao.disallow(public_key));
+        let public_keys = dao
+            .allow_list()
+            .into_iter()
+            .map(|public_key| public_key.to_string())
+            .collect();
+        UiDebutAllowListResponse { public_keys }.tmb(context_id)
+    }
+
+    fn take_pending_debut(
+        &mut self,
+        opcode: &str,
+        public_key_str: &str,
+        context_id: u64,
+    ) -> Result<PendingDebut, MessageBody> {
+        let public_key = Self::parse_public_key(opcode, public_key_str, context_id)?;
+        self.expire_pending_debuts();
+        match self
+            .pending_debuts
+            .iter()
+            .position(|pending| pending.agr.inner.public_key == public_key)
+        {
+            Some(index) => Ok(self.pending_debuts.remove(index)),
+            None => Err(MessageBody {
+                opcode: opcode.to_string(),
+                path: Conversation(context_id),
+                payload: Err((
+                    PENDING_DEBUT_NOT_FOUND_ERROR,
+                    format!("There is no pending debut from {}", public_key_str),
+                )),
+            }),
+        }
+    }
+
+    fn parse_public_key(
+        opcode: &str,
+        public_key_str: &str,
+        context_id: u64,
+    ) -> Result<PublicKey, MessageBody> {
+        match base64::decode_config(public_key_str, base64::STANDARD_NO_PAD) {
+            Ok(ref data) if !data.is_empty() => Ok(PublicKey::new(data)),
+            _ => Err(MessageBody {
+                opcode: opcode.to_string(),
+                path: Conversation(context_id),
+                payload: Err((
+                    BAD_PUBLIC_KEY_ERROR,
+                    format!("'{}' is not a valid public key", public_key_str),
+                )),
+            }),
+        }
+    }
+
+    fn to_ui_pending_debut(pending_debut: &PendingDebut) -> UiPendingDebut {
+        UiPendingDebut {
+            public_key: pending_debut.agr.inner.public_key.to_string(),
+            node_address: match &pending_debut.agr.node_addr_opt {
+                Some(node_addr) => node_addr.to_string(),
+                None => pending_debut.gossip_source.to_string(),
+            },
+            earning_wallet: pending_debut.agr.inner.earning_wallet.to_string(),
+            received_at: to_time_t(pending_debut.received_at) as u64,
+        }
+    }
+
+    fn to_ui_malefactor_ban(ban: MalefactorBan) -> UiMalefactorBan {
+        UiMalefactorBan {
+            id: ban.id,
+            public_key_opt: ban
+                .malefactor
+                .public_key_opt
+                .map(|public_key| public_key.to_string()),
+            ip_address_opt: ban
+                .malefactor
+                .ip_address_opt
+                .map(|ip_address| ip_address.to_string()),
+            earning_wallet_opt: ban
+                .malefactor
+                .earning_wallet_opt
+                .map(|wallet| wallet.to_string()),
+            reason: ban.reason,
+            banned_at: to_time_t(ban.banned_at) as u64,
+            expires_at_opt: ban
+                .expires_at_opt
+                .map(|expires_at| to_time_t(expires_at) as u64),
+        }
+    }
+
+    fn send_to_ui_gateway(&self, target: MessageTarget, body: MessageBody) {
+        self.to_ui_message_sub
+            .as_ref()
+            .expect("UiGateway not bound")
+            .try_send(NodeToUiMessage { target, body })
+            .expect("UiGateway is dead");
+    }
+}
+
+pub fn regenerate_signed_gossip(
+    inner: &NodeRecordInner_0v2,
+    cryptde: &dyn CryptDE, // Must be the correct CryptDE for the Node from which inner came: used for signing
+) -> (PlainData, CryptData) {
+    let signed_gossip =
+        PlainData::from(serde_cbor::ser::to_vec(&inner).expect("Serialization failed"));
+    let signature = match cryptde.sign(&signed_gossip) {
+        Ok(sig) => sig,
+        Err(e) => unimplemented!("TODO: Signing error: {:?}", e),
+    };
+    (signed_gossip, signature)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::blockchain::blockchain_interface::{chain_id_from_name, contract_address};
+    use crate::database::dao_utils::from_time_t;
+    use crate::db_config::persistent_configuration::PersistentConfigError;
+    use crate::neighborhood::bootstrap_source::make_signed_bootstrap_list;
+    use crate::neighborhood::debut_allow_list_dao::test_utils::DebutAllowListGuard;
+    use crate::neighborhood::gossip::GossipBuilder;
+    use crate::neighborhood::gossip::Gossip_0v1;
+    use crate::neighborhood::node_record::{NodeQuality, NodeRecordInner_0v2};
+    use crate::stream_messages::{NonClandestineAttributes, RemovedStreamType};
     use crate::sub_lib::cryptde::{decodex, encodex, CryptData};
     use crate::sub_lib::cryptde_null::CryptDENull;
     use crate::sub_lib::dispatcher::Endpoint;
@@ -1263,7 +2224,7 @@ mod tests {
     use actix::Recipient;
     use actix::System;
     use itertools::Itertools;
-    use masq_lib::constants::TLS_PORT;
+    use masq_lib::constants::{DEFAULT_CHAIN_NAME, TLS_PORT};
     use masq_lib::test_utils::utils::{
         ensure_node_home_directory_exists, DEFAULT_CHAIN_ID, TEST_DEFAULT_CHAIN_NAME,
     };
@@ -1296,7 +2257,7 @@ mod tests {
             None,
             "cant_create_mainnet_neighborhood_with_non_mainnet_neighbors",
         );
-        bc.blockchain_bridge_config.chain_id = chain_id_from_name(DEFAULT_CHAIN_NAME);
+        bc.blockchain_bridge_config.chain_id = chain_id_from_name(DEFAULT_CHAIN_NAME).unwrap();
 
         let _ = Neighborhood::new(cryptde, &bc);
     }
@@ -1318,7 +2279,7 @@ mod tests {
             None,
             "cant_create_non_mainnet_neighborhood_with_mainnet_neighbors",
         );
-        bc.blockchain_bridge_config.chain_id = chain_id_from_name(TEST_DEFAULT_CHAIN_NAME);
+        bc.blockchain_bridge_config.chain_id = chain_id_from_name(TEST_DEFAULT_CHAIN_NAME).unwrap();
 
         let _ = Neighborhood::new(cryptde, &bc);
     }
@@ -1430,7 +2391,7 @@ mod tests {
                         NodeAddr::new(&IpAddr::from_str("5.4.3.2").unwrap(), &[5678]),
                         vec![NodeDescriptor::from((
                             neighbor_node.public_key(),
-                            DEFAULT_CHAIN_ID == chain_id_from_name(DEFAULT_CHAIN_NAME),
+                            DEFAULT_CHAIN_ID == chain_id_from_name(DEFAULT_CHAIN_NAME).unwrap(),
                             cryptde,
                         ))],
                         rate_pack(100),
@@ -1470,12 +2431,12 @@ mod tests {
                         vec![
                             NodeDescriptor::from((
                                 &one_neighbor_node,
-                                DEFAULT_CHAIN_ID == chain_id_from_name(DEFAULT_CHAIN_NAME),
+                                DEFAULT_CHAIN_ID == chain_id_from_name(DEFAULT_CHAIN_NAME).unwrap(),
                                 cryptde,
                             )),
                             NodeDescriptor::from((
                                 &another_neighbor_node,
-                                DEFAULT_CHAIN_ID == chain_id_from_name(DEFAULT_CHAIN_NAME),
+                                DEFAULT_CHAIN_ID == chain_id_from_name(DEFAULT_CHAIN_NAME).unwrap(),
                                 cryptde,
                             )),
                         ],
@@ -1508,12 +2469,12 @@ mod tests {
             vec![
                 NodeDescriptor::from((
                     &one_neighbor_node,
-                    DEFAULT_CHAIN_ID == chain_id_from_name(DEFAULT_CHAIN_NAME),
+                    DEFAULT_CHAIN_ID == chain_id_from_name(DEFAULT_CHAIN_NAME).unwrap(),
                     cryptde,
                 )),
                 NodeDescriptor::from((
                     &another_neighbor_node,
-                    DEFAULT_CHAIN_ID == chain_id_from_name(DEFAULT_CHAIN_NAME),
+                    DEFAULT_CHAIN_ID == chain_id_from_name(DEFAULT_CHAIN_NAME).unwrap(),
                     cryptde,
                 ))
             ]
@@ -1538,12 +2499,12 @@ mod tests {
                         vec![
                             NodeDescriptor::from((
                                 &one_neighbor_node,
-                                DEFAULT_CHAIN_ID == chain_id_from_name(DEFAULT_CHAIN_NAME),
+                                DEFAULT_CHAIN_ID == chain_id_from_name(DEFAULT_CHAIN_NAME).unwrap(),
                                 cryptde,
                             )),
                             NodeDescriptor::from((
                                 &another_neighbor_node,
-                                DEFAULT_CHAIN_ID == chain_id_from_name(DEFAULT_CHAIN_NAME),
+                                DEFAULT_CHAIN_ID == chain_id_from_name(DEFAULT_CHAIN_NAME).unwrap(),
                                 cryptde,
                             )),
                         ],
@@ -1584,6 +2545,242 @@ mod tests {
         tlh.exists_log_containing ("ERROR: Neighborhood: None of the Nodes listed in the --neighbors parameter could accept your Debut; shutting down");
     }
 
+    fn make_bootstrap_subject(test_name: &str, file_contents_opt: Option<String>) -> Neighborhood {
+        let cryptde: &dyn CryptDE = main_cryptde();
+        let mut config = bc_from_nc_plus(
+            NeighborhoodConfig {
+                mode: NeighborhoodMode::Standard(
+                    NodeAddr::new(&IpAddr::from_str("5.4.3.2").unwrap(), &[5678]),
+                    vec![],
+                    rate_pack(100),
+                ),
+            },
+            make_wallet("earning"),
+            None,
+            test_name,
+        );
+        let location = config.data_directory.join("bootstrap.txt");
+        if let Some(file_contents) = file_contents_opt {
+            std::fs::write(&location, file_contents).unwrap();
+        }
+        config.bootstrap_source_opt = Some(BootstrapSource {
+            location: location.to_string_lossy().to_string(),
+            public_key_opt: Some(bootstrap_publisher().public_key().clone()),
+        });
+        Neighborhood::new(cryptde, &config)
+    }
+
+    fn bootstrap_publisher() -> CryptDENull {
+        CryptDENull::from(&PublicKey::new(b"bootstrap publisher"), DEFAULT_CHAIN_ID)
+    }
+
+    fn bootstrap_descriptor(node: &NodeRecord) -> NodeDescriptor {
+        NodeDescriptor::from((
+            node,
+            DEFAULT_CHAIN_ID == chain_id_from_name(DEFAULT_CHAIN_NAME).unwrap(),
+            main_cryptde(),
+        ))
+    }
+
+    #[test]
+    fn consult_bootstrap_source_sends_verified_list_to_neighborhood_in_background() {
+        let one_node = make_node_record(3456, true);
+        let another_node = make_node_record(4567, true);
+        let descriptors = vec![&one_node, &another_node]
+            .into_iter()
+            .map(|node| bootstrap_descriptor(node).to_string(main_cryptde()))
+            .collect::<Vec<String>>();
+        let list = make_signed_bootstrap_list(&descriptors, &bootstrap_publisher());
+        let (neighborhood, neighborhood_awaiter, neighborhood_recording_arc) = make_recorder();
+        thread::spawn(move || {
+            let system = System::new(
+                "consult_bootstrap_source_sends_verified_list_to_neighborhood_in_background",
+            );
+            let mut subject = make_bootstrap_subject(
+                "consult_bootstrap_source_sends_verified_list_to_neighborhood_in_background",
+                Some(list),
+            );
+            let peer_actors = peer_actors_builder().neighborhood(neighborhood).build();
+            subject.bootstrap_list_sub_opt = Some(peer_actors.neighborhood.bootstrap_list_sub);
+
+            subject.consult_bootstrap_source();
+
+            system.run();
+        });
+
+        neighborhood_awaiter.await_message_count(1);
+        let neighborhood_recording = neighborhood_recording_arc.lock().unwrap();
+        assert_eq!(
+            neighborhood_recording.get_record::<BootstrapListMessage>(0),
+            &BootstrapListMessage {
+                descriptors_result: Ok(vec![
+                    bootstrap_descriptor(&one_node),
+                    bootstrap_descriptor(&another_node)
+                ])
+            }
+        );
+    }
+
+    #[test]
+    fn bootstrap_list_message_chooses_among_other_nodes_and_debuts() {
+        init_test_logging();
+        let one_node = make_node_record(3456, true);
+        let another_node = make_node_record(4567, true);
+        let own_node = make_global_cryptde_node_record(5678, true);
+        let mut subject = make_bootstrap_subject(
+            "bootstrap_list_message_chooses_among_other_nodes_and_debuts",
+            None,
+        );
+        let (hopper, _, hopper_recording_arc) = make_recorder();
+        let peer_actors = peer_actors_builder().hopper(hopper).build();
+        let system = System::new("bootstrap_list_message_chooses_among_other_nodes_and_debuts");
+        subject.hopper_no_lookup = Some(peer_actors.hopper.from_hopper_client_no_lookup);
+
+        subject.handle_bootstrap_list_message(BootstrapListMessage {
+            descriptors_result: Ok(vec![&one_node, &another_node, &own_node]
+                .into_iter()
+                .map(bootstrap_descriptor)
+                .collect()),
+        });
+
+        System::current().stop();
+        system.run();
+        let mut chosen = subject.initial_neighbors.clone();
+        chosen.extend(subject.bootstrap_candidates_opt.clone().unwrap());
+        assert_eq!(subject.initial_neighbors.len(), 1);
+        assert_eq!(
+            vec_to_set(chosen),
+            vec_to_set(vec![
+                bootstrap_descriptor(&one_node),
+                bootstrap_descriptor(&another_node)
+            ])
+        );
+        let hopper_recording = hopper_recording_arc.lock().unwrap();
+        let package = hopper_recording.get_record::<NoLookupIncipientCoresPackage>(0);
+        assert_eq!(hopper_recording.len(), 1);
+        assert_eq!(
+            package.public_key,
+            subject.initial_neighbors[0].encryption_public_key
+        );
+        TestLogHandler::new().exists_log_containing("offered 2 usable Nodes");
+    }
+
+    #[test]
+    fn bootstrap_list_message_logs_unusable_source() {
+        init_test_logging();
+        let mut subject =
+            make_bootstrap_subject("bootstrap_list_message_logs_unusable_source", None);
+        let location = subject.bootstrap_source_opt.clone().unwrap().location;
+
+        subject.handle_bootstrap_list_message(BootstrapListMessage {
+            descriptors_result: Err("booga".to_string()),
+        });
+
+        assert_eq!(subject.initial_neighbors, vec![]);
+        assert_eq!(subject.bootstrap_candidates_opt, None);
+        TestLogHandler::new().exists_log_containing(&format!(
+            "ERROR: Neighborhood: Couldn't use bootstrap source {}: booga",
+            location
+        ));
+    }
+
+    #[test]
+    fn bootstrap_list_message_is_ignored_once_there_is_a_neighbor() {
+        let neighbor = make_node_record(3456, true);
+        let mut subject = make_bootstrap_subject(
+            "bootstrap_list_message_is_ignored_once_there_is_a_neighbor",
+            None,
+        );
+        subject
+            .neighborhood_database
+            .root_mut()
+            .add_half_neighbor_key(neighbor.public_key().clone())
+            .unwrap();
+
+        subject.handle_bootstrap_list_message(BootstrapListMessage {
+            descriptors_result: Ok(vec![bootstrap_descriptor(&make_node_record(4567, true))]),
+        });
+
+        assert_eq!(subject.initial_neighbors, vec![]);
+        assert_eq!(subject.bootstrap_candidates_opt, None);
+    }
+
+    #[test]
+    fn consult_bootstrap_source_is_ignored_when_there_are_neighbors() {
+        let mut subject = make_bootstrap_subject(
+            "consult_bootstrap_source_is_ignored_when_there_are_neighbors",
+            None,
+        );
+        let neighbor = bootstrap_descriptor(&make_node_record(3456, true));
+        subject.initial_neighbors = vec![neighbor.clone()];
+
+        subject.consult_bootstrap_source();
+
+        assert_eq!(subject.initial_neighbors, vec![neighbor]);
+        assert_eq!(subject.bootstrap_candidates_opt, None);
+    }
+
+    #[test]
+    fn gossip_failure_from_bootstrap_node_moves_on_to_next_candidate() {
+        let refusing_node = make_node_record(3456, true);
+        let next_node = make_node_record(4567, true);
+        let mut subject = make_bootstrap_subject(
+            "gossip_failure_from_bootstrap_node_moves_on_to_next_candidate",
+            None,
+        );
+        subject.initial_neighbors = vec![bootstrap_descriptor(&refusing_node)];
+        subject.bootstrap_candidates_opt = Some(vec![bootstrap_descriptor(&next_node)]);
+        let (hopper, _, hopper_recording_arc) = make_recorder();
+        let peer_actors = peer_actors_builder().hopper(hopper).build();
+        let system = System::new("gossip_failure_from_bootstrap_node_moves_on_to_next_candidate");
+        subject.hopper_no_lookup = Some(peer_actors.hopper.from_hopper_client_no_lookup);
+
+        subject.handle_gossip_failure(
+            refusing_node.node_addr_opt().unwrap().into(),
+            GossipFailure_0v1::NoNeighbors,
+        );
+
+        System::current().stop();
+        system.run();
+        assert_eq!(
+            subject.initial_neighbors,
+            vec![bootstrap_descriptor(&next_node)]
+        );
+        assert_eq!(subject.bootstrap_candidates_opt, Some(vec![]));
+        let hopper_recording = hopper_recording_arc.lock().unwrap();
+        let package = hopper_recording.get_record::<NoLookupIncipientCoresPackage>(0);
+        assert_eq!(hopper_recording.len(), 1);
+        assert_eq!(
+            package.public_key,
+            bootstrap_descriptor(&next_node).encryption_public_key
+        );
+    }
+
+    #[test]
+    fn retry_bootstrap_debut_is_done_once_there_is_a_neighbor() {
+        let neighbor = make_node_record(3456, true);
+        let mut subject = make_bootstrap_subject(
+            "retry_bootstrap_debut_is_done_once_there_is_a_neighbor",
+            None,
+        );
+        subject.initial_neighbors = vec![bootstrap_descriptor(&neighbor)];
+        subject.bootstrap_candidates_opt =
+            Some(vec![bootstrap_descriptor(&make_node_record(4567, true))]);
+        subject
+            .neighborhood_database
+            .root_mut()
+            .add_half_neighbor_key(neighbor.public_key().clone())
+            .unwrap();
+
+        subject.retry_bootstrap_debut();
+
+        assert_eq!(
+            subject.initial_neighbors,
+            vec![bootstrap_descriptor(&neighbor)]
+        );
+        assert_eq!(subject.bootstrap_candidates_opt, None);
+    }
+
     #[test]
     fn node_query_responds_with_none_when_initially_configured_with_no_data() {
         let system = System::new("responds_with_none_when_initially_configured_with_no_data");
@@ -1615,7 +2812,7 @@ mod tests {
                         vec![NodeDescriptor::from((
                             &PublicKey::new(&b"booga"[..]),
                             &NodeAddr::new(&IpAddr::from_str("1.2.3.4").unwrap(), &[1234, 2345]),
-                            DEFAULT_CHAIN_ID == chain_id_from_name(DEFAULT_CHAIN_NAME),
+                            DEFAULT_CHAIN_ID == chain_id_from_name(DEFAULT_CHAIN_NAME).unwrap(),
                             cryptde,
                         ))],
                         rate_pack(100),
@@ -1702,7 +2899,7 @@ mod tests {
                         vec![NodeDescriptor::from((
                             &PublicKey::new(&b"booga"[..]),
                             &NodeAddr::new(&IpAddr::from_str("1.2.3.4").unwrap(), &[1234, 2345]),
-                            DEFAULT_CHAIN_ID == chain_id_from_name(DEFAULT_CHAIN_NAME),
+                            DEFAULT_CHAIN_ID == chain_id_from_name(DEFAULT_CHAIN_NAME).unwrap(),
                             cryptde,
                         ))],
                         rate_pack(100),
@@ -1742,7 +2939,7 @@ mod tests {
                         node_record.node_addr_opt().unwrap(),
                         vec![NodeDescriptor::from((
                             &node_record,
-                            DEFAULT_CHAIN_ID == chain_id_from_name(DEFAULT_CHAIN_NAME),
+                            DEFAULT_CHAIN_ID == chain_id_from_name(DEFAULT_CHAIN_NAME).unwrap(),
                             cryptde,
                         ))],
                         rate_pack(100),
@@ -2136,6 +3333,90 @@ mod tests {
         assert_eq!(undesirable_node.public_key(), node_sequences[1][2]);
     }
 
+    #[test]
+    fn node_record_metadata_messages_update_node_quality() {
+        let mut subject = make_standard_subject();
+        let fast = subject
+            .neighborhood_database
+            .add_node(make_node_record(1111, false))
+            .unwrap();
+        let flaky = subject
+            .neighborhood_database
+            .add_node(make_node_record(2222, false))
+            .unwrap();
+        let unknown = PublicKey::new(b"unknown");
+
+        subject.handle_node_record_metadata_message(NodeRecordMetadataMessage::RoundTrip(
+            vec![fast.clone(), flaky.clone(), unknown.clone()],
+            120,
+        ));
+        subject.handle_node_record_metadata_message(NodeRecordMetadataMessage::Failure(vec![
+            flaky.clone(),
+            unknown,
+        ]));
+
+        let quality_of = |key: &PublicKey| {
+            subject
+                .neighborhood_database
+                .node_by_key(key)
+                .unwrap()
+                .quality()
+                .clone()
+        };
+        assert_eq!(
+            quality_of(&fast),
+            NodeQuality {
+                round_trip_millis_opt: Some(120),
+                failure_permille: 0
+            }
+        );
+        assert_eq!(
+            quality_of(&flaky),
+            NodeQuality {
+                round_trip_millis_opt: Some(120),
+                failure_permille: 250
+            }
+        );
+    }
+
+    #[test]
+    fn sort_routes_by_quality_puts_routes_with_lowest_total_penalty_first() {
+        let mut subject = make_standard_subject();
+        let us = subject.neighborhood_database.root().public_key().clone();
+        let mut add_node = |n: u16, round_trip_millis_opt: Option<u32>, failures: usize| {
+            let mut node = make_node_record(n, false);
+            if let Some(round_trip_millis) = round_trip_millis_opt {
+                node.quality_mut().record_round_trip(round_trip_millis);
+            }
+            (0..failures).for_each(|_| node.quality_mut().record_failure());
+            subject.neighborhood_database.add_node(node).unwrap()
+        };
+        let slow = add_node(1111, Some(900), 0);
+        let unobserved = add_node(2222, None, 0);
+        let quick = add_node(3333, Some(50), 0);
+        let failing = add_node(4444, Some(50), 1);
+        let other_unobserved = add_node(5555, None, 0);
+        let mut node_seqs = vec![
+            vec![&us, &slow, &failing],
+            vec![&us, &unobserved, &other_unobserved],
+            vec![&us, &failing, &quick],
+            vec![&us, &quick, &unobserved],
+        ];
+
+        subject.sort_routes_by_quality(&mut node_seqs);
+
+        // Unobserved Nodes are charged the median penalty of the observed ones: slow's 900
+        assert_eq!(
+            node_seqs,
+            vec![
+                vec![&us, &quick, &unobserved],
+                vec![&us, &unobserved, &other_unobserved],
+                vec![&us, &failing, &quick],
+                vec![&us, &slow, &failing],
+            ]
+        );
+    }
+
     #[test]
     #[should_panic(expected = "Unable to sort routes by desirable exit nodes: Missing routes.")]
     fn sort_routes_by_desirable_exit_nodes_panics_with_empty_node_sequences() {
@@ -2525,7 +3806,7 @@ mod tests {
         type Digest = (PublicKey, Vec<u8>, bool, u32, Vec<PublicKey>);
         let to_actual_digest = |gnr: GossipNodeRecord| {
             let node_addr_opt = gnr.node_addr_opt.clone();
-            let inner = NodeRecordInner_0v1::try_from(gnr).unwrap();
+            let inner = NodeRecordInner_0v2::try_from(gnr).unwrap();
             let neighbors_vec = inner.neighbors.into_iter().collect::<Vec<PublicKey>>();
             (
                 inner.public_key.clone(),
@@ -2852,6 +4133,462 @@ mod tests {
         assert_eq!(subject.is_connected, true);
     }
 
+    #[test]
+    fn neighborhood_broadcasts_connection_change_when_first_route_can_be_made() {
+        init_test_logging();
+        let subject_node = make_global_cryptde_node_record(5555, true); // 9e7p7un06eHs6frl5A
+        let relay1 = make_node_record(1111, true);
+        let relay2 = make_node_record(2222, false);
+        let exit = make_node_record(3333, false);
+        let mut subject: Neighborhood = neighborhood_from_nodes(&subject_node, Some(&relay1));
+        let mut replacement_database = subject.neighborhood_database.clone();
+        replacement_database.add_node(relay1.clone()).unwrap();
+        replacement_database.add_node(relay2.clone()).unwrap();
+        replacement_database.add_node(exit.clone()).unwrap();
+        replacement_database
+            .add_arbitrary_full_neighbor(subject_node.public_key(), relay1.public_key());
+        replacement_database.add_arbitrary_full_neighbor(relay1.public_key(), relay2.public_key());
+        replacement_database.add_arbitrary_full_neighbor(relay2.public_key(), exit.public_key());
+        subject.gossip_acceptor = Box::new(DatabaseReplacementGossipAcceptor {
+            replacement_database,
+        });
+        subject.persistent_config_opt = Some(Box::new(
+            PersistentConfigurationMock::new().set_past_neighbors_result(Ok(())),
+        ));
+        let (ui_gateway, _, ui_gateway_recording_arc) = make_recorder();
+        let system =
+            System::new("neighborhood_broadcasts_connection_change_when_first_route_can_be_made");
+        let peer_actors = peer_actors_builder().ui_gateway(ui_gateway).build();
+        subject.to_ui_message_sub = Some(peer_actors.ui_gateway.node_to_ui_message_sub.clone());
+        bind_subject(&mut subject, peer_actors);
+
+        subject.handle_gossip_agrs(vec![], SocketAddr::from_str("1.2.3.4:1234").unwrap());
+
+        System::current().stop();
+        system.run();
+        assert_eq!(
+            subject.connection_stage,
+            UiConnectionStage::ThreeHopsRouteFound
+        );
+        assert_eq!(subject.next_return_route_id, 0);
+        let ui_gateway_recording = ui_gateway_recording_arc.lock().unwrap();
+        assert_eq!(
+            ui_gateway_recording.get_record::<NodeToUiMessage>(0),
+            &NodeToUiMessage {
+                target: MessageTarget::AllClients,
+                body: UiConnectionChangeBroadcast {
+                    stage: UiConnectionStage::ThreeHopsRouteFound
+                }
+                .tmb(0)
+            }
+        );
+        TestLogHandler::new().exists_log_containing(
+            "INFO: Neighborhood: Connection stage changed from NotConnected to ThreeHopsRouteFound",
+        );
+    }
+
+    #[test]
+    fn neighborhood_reports_connection_to_neighbor_when_no_route_can_be_made() {
+        let subject_node = make_global_cryptde_node_record(5555, true); // 9e7p7un06eHs6frl5A
+        let neighbor = make_node_record(1111, true);
+        let mut subject: Neighborhood = neighborhood_from_nodes(&subject_node, Some(&neighbor));
+        let mut replacement_database = subject.neighborhood_database.clone();
+        replacement_database.add_node(neighbor.clone()).unwrap();
+        replacement_database
+            .add_arbitrary_full_neighbor(subject_node.public_key(), neighbor.public_key());
+        subject.gossip_acceptor = Box::new(DatabaseReplacementGossipAcceptor {
+            replacement_database,
+        });
+        subject.persistent_config_opt = Some(Box::new(
+            PersistentConfigurationMock::new().set_past_neighbors_result(Ok(())),
+        ));
+        let (accountant, _, accountant_recording_arc) = make_recorder();
+        let (ui_gateway, _, ui_gateway_recording_arc) = make_recorder();
+        let system =
+            System::new("neighborhood_reports_connection_to_neighbor_when_no_route_can_be_made");
+        let peer_actors = peer_actors_builder()
+            .accountant(accountant)
+            .ui_gateway(ui_gateway)
+            .build();
+        subject.to_ui_message_sub = Some(peer_actors.ui_gateway.node_to_ui_message_sub.clone());
+        bind_subject(&mut subject, peer_actors);
+
+        subject.handle_gossip_agrs(vec![], SocketAddr::from_str("1.2.3.4:1234").unwrap());
+
+        System::current().stop();
+        system.run();
+        assert_eq!(
+            subject.connection_stage,
+            UiConnectionStage::ConnectedToNeighbor
+        );
+        let accountant_recording = accountant_recording_arc.lock().unwrap();
+        assert_eq!(accountant_recording.len(), 0);
+        let ui_gateway_recording = ui_gateway_recording_arc.lock().unwrap();
+        assert_eq!(
+            ui_gateway_recording.get_record::<NodeToUiMessage>(0),
+            &NodeToUiMessage {
+                target: MessageTarget::AllClients,
+                body: UiConnectionChangeBroadcast {
+                    stage: UiConnectionStage::ConnectedToNeighbor
+                }
+                .tmb(0)
+            }
+        );
+    }
+
+    #[test]
+    fn found_route_is_rechecked_only_when_neighbors_change() {
+        let subject_node = make_global_cryptde_node_record(5555, true);
+        let mut subject: Neighborhood = neighborhood_from_nodes(&subject_node, None);
+        subject.connection_stage = UiConnectionStage::ThreeHopsRouteFound;
+        let departed_neighbor_key = make_node_record(1111, true).public_key().clone();
+
+        subject.handle_database_changes(&[], &[]);
+
+        assert_eq!(
+            subject.connection_stage,
+            UiConnectionStage::ThreeHopsRouteFound
+        );
+
+        subject.handle_database_changes(&[departed_neighbor_key], &[]);
+
+        assert_eq!(subject.connection_stage, UiConnectionStage::NotConnected);
+    }
+
+    #[test]
+    fn neighborhood_broadcasts_disconnection_when_last_neighbor_drops() {
+        let subject_node = make_global_cryptde_node_record(5555, true); // 9e7p7un06eHs6frl5A
+        let neighbor = make_node_record(1111, true);
+        let mut subject: Neighborhood = neighborhood_from_nodes(&subject_node, Some(&neighbor));
+        subject
+            .neighborhood_database
+            .add_node(neighbor.clone())
+            .unwrap();
+        subject
+            .neighborhood_database
+            .add_arbitrary_full_neighbor(subject_node.public_key(), neighbor.public_key());
+        subject.connection_stage = UiConnectionStage::ConnectedToNeighbor;
+        let (ui_gateway, _, ui_gateway_recording_arc) = make_recorder();
+        let system = System::new("neighborhood_broadcasts_disconnection_when_last_neighbor_drops");
+        let peer_actors = peer_actors_builder().ui_gateway(ui_gateway).build();
+        subject.to_ui_message_sub = Some(peer_actors.ui_gateway.node_to_ui_message_sub.clone());
+        bind_subject(&mut subject, peer_actors);
+
+        subject.remove_neighbor(
+            neighbor.public_key(),
+            &SocketAddr::from_str("1.2.3.4:1234").unwrap(),
+        );
+
+        System::current().stop();
+        system.run();
+        assert_eq!(subject.connection_stage, UiConnectionStage::NotConnected);
+        let ui_gateway_recording = ui_gateway_recording_arc.lock().unwrap();
+        assert_eq!(
+            ui_gateway_recording.get_record::<NodeToUiMessage>(0),
+            &NodeToUiMessage {
+                target: MessageTarget::AllClients,
+                body: UiConnectionChangeBroadcast {
+                    stage: UiConnectionStage::NotConnected
+                }
+                .tmb(0)
+            }
+        );
+    }
+
+    #[test]
+    fn status_request_reports_node_status() {
+        let subject_node = make_global_cryptde_node_record(5555, true);
+        let mut subject = neighborhood_from_nodes(&subject_node, None);
+        subject.chain_id = chain_id_from_name(TEST_DEFAULT_CHAIN_NAME).unwrap();
+        subject.ui_port = 5333;
+
+        let ui_gateway_recording_arc = run_ui_request(subject, UiStatusRequest {}.tmb(4321));
+
+        let ui_gateway_recording = ui_gateway_recording_arc.lock().unwrap();
+        let record = ui_gateway_recording.get_record::<NodeToUiMessage>(0);
+        assert_eq!(record.target, MessageTarget::ClientId(1234));
+        let (response, context_id) = UiStatusResponse::fmb(record.body.clone()).unwrap();
+        assert_eq!(context_id, 4321);
+        assert_eq!(
+            response,
+            UiStatusResponse {
+                running: true,
+                version: env!("CARGO_PKG_VERSION").to_string(),
+                process_id_opt: Some(std::process::id()),
+                uptime_secs_opt: response.uptime_secs_opt,
+                chain_opt: Some(TEST_DEFAULT_CHAIN_NAME.to_string()),
+                neighborhood_mode_opt: Some("zero-hop".to_string()),
+                ui_port_opt: Some(5333),
+                last_crash_reason_opt: None,
+            }
+        );
+        assert!(response.uptime_secs_opt.unwrap() < 60);
+    }
+
+    #[test]
+    fn connection_status_request_reports_connection_stage() {
+        let subject_node = make_global_cryptde_node_record(5555, true);
+        let mut subject = neighborhood_from_nodes(&subject_node, None);
+        subject.connection_stage = UiConnectionStage::ConnectedToNeighbor;
+
+        let ui_gateway_recording_arc =
+            run_ui_request(subject, UiConnectionStatusRequest {}.tmb(4321));
+
+        let ui_gateway_recording = ui_gateway_recording_arc.lock().unwrap();
+        assert_eq!(
+            ui_gateway_recording.get_record::<NodeToUiMessage>(0),
+            &NodeToUiMessage {
+                target: MessageTarget::ClientId(1234),
+                body: UiConnectionStatusResponse {
+                    stage: UiConnectionStage::ConnectedToNeighbor
+                }
+                .tmb(4321)
+            }
+        );
+    }
+
+    fn make_exit_country_subject() -> (Neighborhood, Vec<PublicKey>) {
+        let mut subject = make_standard_subject();
+        let make_exit = |n: u16, country_opt: Option<&str>| {
+            let mut node = make_node_record(n, false);
+            node.inner.country_code_opt = country_opt.map(|country| country.to_string());
+            node
+        };
+        let keys = vec![
+            make_node_record(1000, false),
+            make_exit(1111, Some("cz")),
+            make_exit(2222, Some("SK")),
+            make_exit(3333, None),
+        ]
+        .into_iter()
+        .map(|node| subject.neighborhood_database.add_node(node).unwrap())
+        .collect();
+        (subject, keys)
+    }
+
+    #[test]
+    fn strict_exit_country_preference_discards_routes_through_other_exit_nodes() {
+        let (mut subject, keys) = make_exit_country_subject();
+        subject.exit_country_opt = Some(ExitCountryPreference::from_str("CZ").unwrap());
+        let us = subject.neighborhood_database.root().public_key().clone();
+        let (r, cz, sk, unknown) = (&keys[0], &keys[1], &keys[2], &keys[3]);
+        let mut node_seqs = vec![vec![&us, r, sk], vec![&us, r, cz], vec![&us, r, unknown]];
+
+        subject.apply_exit_country_preference(&mut node_seqs);
+
+        assert_eq!(node_seqs, vec![vec![&us, r, cz]]);
+    }
+
+    #[test]
+    fn non_strict_exit_country_preference_moves_qualifying_routes_to_the_front() {
+        let (mut subject, keys) = make_exit_country_subject();
+        subject.exit_country_opt = Some(ExitCountryPreference::from_str("prefer:SK,CZ").unwrap());
+        let us = subject.neighborhood_database.root().public_key().clone();
+        let (r, cz, sk, unknown) = (&keys[0], &keys[1], &keys[2], &keys[3]);
+        let mut node_seqs = vec![vec![&us, r, unknown], vec![&us, r, cz], vec![&us, r, sk]];
+
+        subject.apply_exit_country_preference(&mut node_seqs);
+
+        assert_eq!(
+            node_seqs,
+            vec![vec![&us, r, cz], vec![&us, r, sk], vec![&us, r, unknown]]
+        );
+    }
+
+    #[test]
+    fn make_route_segment_reports_when_no_exit_node_is_in_a_required_country() {
+        let (o, _, e, mut subject) = make_o_r_e_subject();
+        subject.exit_country_opt = Some(ExitCountryPreference::from_str("CZ,SK").unwrap());
+
+        let result = subject.make_route_segment(
+            o.public_key(),
+            None,
+            2,
+            Component::ProxyClient,
+            RouteDirection::Over,
+        );
+
+        match result {
+            Err(e) => assert_eq!(
+                e,
+                format!(
+                    "Couldn't find any routes: at least 2-hop from {} to ProxyClient through an exit Node in CZ, SK",
+                    o.public_key()
+                )
+            ),
+            Ok(segment) => panic!("Expected no route, got {:?}", segment),
+        }
+        subject
+            .neighborhood_database
+            .node_by_key_mut(e.public_key())
+            .unwrap()
+            .inner
+            .country_code_opt = Some("SK".to_string());
+
+        let result = subject
+            .make_route_segment(
+                o.public_key(),
+                None,
+                2,
+                Component::ProxyClient,
+                RouteDirection::Over,
+            )
+            .unwrap();
+
+        assert_eq!(result.keys.last(), Some(e.public_key()));
+    }
+
+    #[test]
+    fn country_of_prefers_geoip_database_to_declared_country() {
+        let mut subject = make_standard_subject();
+        let mut with_ip = make_node_record(1111, true);
+        with_ip.inner.country_code_opt = Some("de".to_string());
+        let mut without_ip = make_node_record(2222, false);
+        without_ip.inner.country_code_opt = Some("AT".to_string());
+        let undeclared = make_node_record(3333, true);
+
+        assert_eq!(subject.country_of(&with_ip), Some("DE".to_string()));
+        assert_eq!(subject.country_of(&undeclared), None);
+
+        subject.geoip_database_opt =
+            Some(GeoIpDatabase::from_csv("1.1.1.0,1.1.1.255,CZ\n3.3.3.0,3.3.3.255,SK").unwrap());
+
+        assert_eq!(subject.country_of(&with_ip), Some("CZ".to_string()));
+        assert_eq!(subject.country_of(&without_ip), Some("AT".to_string()));
+        assert_eq!(subject.country_of(&undeclared), Some("SK".to_string()));
+    }
+
+    #[test]
+    fn load_geoip_database_declares_root_country() {
+        init_test_logging();
+        let data_dir = ensure_node_home_directory_exists(
+            "neighborhood",
+            "load_geoip_database_declares_root_country",
+        );
+        std::fs::write(
+            data_dir.join(geoip::GEOIP_DATABASE_FILENAME),
+            "5.5.5.0,5.5.5.255,CZ\n",
+        )
+        .unwrap();
+        let subject_node = make_global_cryptde_node_record(5555, true);
+        let mut subject = neighborhood_from_nodes(&subject_node, None);
+        subject.data_directory = data_dir;
+        let old_signed_gossip = subject.neighborhood_database.root().signed_gossip().clone();
+
+        subject.load_geoip_database();
+
+        let root = subject.neighborhood_database.root();
+        assert_eq!(root.inner.country_code_opt, Some("CZ".to_string()));
+        assert_ne!(root.signed_gossip(), &old_signed_gossip);
+        TestLogHandler::new().exists_log_containing(
+            "INFO: Neighborhood: Loaded 1 address ranges from the GeoIP database",
+        );
+    }
+
+    #[test]
+    fn exit_country_request_changes_preference_and_informs_proxy_server() {
+        let system =
+            System::new("exit_country_request_changes_preference_and_informs_proxy_server");
+        let (ui_gateway, _, ui_gateway_recording_arc) = make_recorder();
+        let (proxy_server, _, proxy_server_recording_arc) = make_recorder();
+        let subject_node = make_global_cryptde_node_record(5555, true);
+        let subject = neighborhood_from_nodes(&subject_node, None);
+        let subject_addr = subject.start();
+        let peer_actors = peer_actors_builder()
+            .ui_gateway(ui_gateway)
+            .proxy_server(proxy_server)
+            .build();
+        subject_addr.try_send(BindMessage { peer_actors }).unwrap();
+
+        subject_addr
+            .try_send(NodeFromUiMessage {
+                client_id: 1234,
+                body: UiExitCountryRequest {
+                    countries: vec!["cz".to_string(), "SK".to_string()],
+                    strict: true,
+                }
+                .tmb(4321),
+            })
+            .unwrap();
+        subject_addr
+            .try_send(NodeFromUiMessage {
+                client_id: 1234,
+                body: UiExitCountryRequest {
+                    countries: vec![],
+                    strict: true,
+                }
+                .tmb(4322),
+            })
+            .unwrap();
+
+        System::current().stop();
+        system.run();
+        let ui_gateway_recording = ui_gateway_recording_arc.lock().unwrap();
+        assert_eq!(
+            ui_gateway_recording.get_record::<NodeToUiMessage>(0),
+            &NodeToUiMessage {
+                target: MessageTarget::ClientId(1234),
+                body: UiExitCountryResponse {}.tmb(4321)
+            }
+        );
+        assert_eq!(
+            ui_gateway_recording.get_record::<NodeToUiMessage>(1),
+            &NodeToUiMessage {
+                target: MessageTarget::ClientId(1234),
+                body: UiExitCountryResponse {}.tmb(4322)
+            }
+        );
+        let proxy_server_recording = proxy_server_recording_arc.lock().unwrap();
+        assert_eq!(
+            proxy_server_recording.get_record::<SetExitCountryMessage>(0),
+            &SetExitCountryMessage {
+                preference_opt: Some(ExitCountryPreference {
+                    countries: vec!["CZ".to_string(), "SK".to_string()],
+                    strict: true,
+                })
+            }
+        );
+        assert_eq!(
+            proxy_server_recording.get_record::<SetExitCountryMessage>(1),
+            &SetExitCountryMessage {
+                preference_opt: None
+            }
+        );
+    }
+
+    #[test]
+    fn exit_country_request_with_bad_country_is_rejected() {
+        let subject_node = make_global_cryptde_node_record(5555, true);
+        let mut subject = neighborhood_from_nodes(&subject_node, None);
+        subject.exit_country_opt = Some(ExitCountryPreference::from_str("DE").unwrap());
+
+        let response = subject.handle_exit_country(
+            UiExitCountryRequest {
+                countries: vec!["CZ".to_string(), "Czechia".to_string()],
+                strict: false,
+            },
+            1234,
+            4321,
+        );
+
+        assert_eq!(
+            response,
+            MessageBody {
+                opcode: "exitCountry".to_string(),
+                path: Conversation(4321),
+                payload: Err((
+                    BAD_EXIT_COUNTRY_ERROR,
+                    "Exit country must be a two-letter ISO 3166 country code, not 'Czechia'"
+                        .to_string()
+                )),
+            }
+        );
+        assert_eq!(
+            subject.exit_country_opt,
+            Some(ExitCountryPreference::from_str("DE").unwrap())
+        );
+    }
+
     struct NeighborReplacementGossipAcceptor {
         pub new_neighbors: Vec<NodeRecord>,
     }
@@ -2914,7 +4651,7 @@ mod tests {
             &neighbors,
             &NodeDescriptor::from((
                 &old_neighbor,
-                DEFAULT_CHAIN_ID == chain_id_from_name(DEFAULT_CHAIN_NAME),
+                DEFAULT_CHAIN_ID == chain_id_from_name(DEFAULT_CHAIN_NAME).unwrap(),
                 cryptde,
             )),
         );
@@ -2922,7 +4659,7 @@ mod tests {
             &neighbors,
             &NodeDescriptor::from((
                 &new_neighbor,
-                DEFAULT_CHAIN_ID == chain_id_from_name(DEFAULT_CHAIN_NAME),
+                DEFAULT_CHAIN_ID == chain_id_from_name(DEFAULT_CHAIN_NAME).unwrap(),
                 cryptde,
             )),
         );
@@ -3209,85 +4946,556 @@ mod tests {
         subject.hopper_no_lookup = Some(peer_actors.hopper.from_hopper_client_no_lookup);
         let gossip_source = SocketAddr::from_str("8.6.5.4:8654").unwrap();
 
-        subject.handle_gossip(
-            // In real life this would be Relay Gossip from gossip_source to debut_node.
-            Gossip_0v1::new(vec![]),
-            gossip_source,
+        subject.handle_gossip(
+            // In real life this would be Relay Gossip from gossip_source to debut_node.
+            Gossip_0v1::new(vec![]),
+            gossip_source,
+        );
+
+        System::current().stop();
+        system.run();
+        let hopper_recording = hopper_recording_arc.lock().unwrap();
+        let package = hopper_recording.get_record::<NoLookupIncipientCoresPackage>(0);
+        assert_eq!(1, hopper_recording.len());
+        assert_eq!(debut_node.public_key(), &package.public_key);
+        assert_eq!(
+            debut_node.node_addr_opt().as_ref().unwrap(),
+            &package.node_addr
+        );
+        assert_eq!(
+            debut_gossip,
+            match decodex::<MessageType>(
+                &CryptDENull::from(debut_node.public_key(), DEFAULT_CHAIN_ID),
+                &package.payload,
+            ) {
+                Ok(MessageType::Gossip(vd)) => Gossip_0v1::try_from(vd).unwrap(),
+                x => panic!("Expected Gossip, but found {:?}", x),
+            },
+        );
+    }
+
+    #[test]
+    fn neighborhood_sends_no_gossip_when_gossip_acceptor_ignores() {
+        let subject_node = make_global_cryptde_node_record(5555, true); // 9e7p7un06eHs6frl5A
+        let neighbor = make_node_record(1111, true);
+        let mut subject = neighborhood_from_nodes(&subject_node, Some(&neighbor));
+        let gossip_acceptor =
+            GossipAcceptorMock::new().handle_result(GossipAcceptanceResult::Ignored);
+        subject.gossip_acceptor = Box::new(gossip_acceptor);
+        let subject_node = subject.neighborhood_database.root().clone();
+        let (hopper, _, hopper_recording_arc) = make_recorder();
+        let peer_actors = peer_actors_builder().hopper(hopper).build();
+        let system = System::new("");
+        subject.hopper = Some(peer_actors.hopper.from_hopper_client);
+
+        subject.handle_gossip(
+            Gossip_0v1::new(vec![]),
+            subject_node.node_addr_opt().unwrap().into(),
+        );
+
+        System::current().stop();
+        system.run();
+        let hopper_recording = hopper_recording_arc.lock().unwrap();
+        assert_eq!(0, hopper_recording.len());
+    }
+
+    #[test]
+    fn neighborhood_bans_malefactor_when_gossip_acceptor_requests_it() {
+        init_test_logging();
+        let subject_node = make_global_cryptde_node_record(5555, true); // 9e7p7un06eHs6frl5A
+        let neighbor = make_node_record(1111, true);
+        let mut subject = neighborhood_from_nodes(&subject_node, Some(&neighbor));
+        let gossip_acceptor = GossipAcceptorMock::new()
+            .handle_result(GossipAcceptanceResult::Ban("Bad guy".to_string()));
+        subject.gossip_acceptor = Box::new(gossip_acceptor);
+        let ban_params_arc = Arc::new(Mutex::new(vec![]));
+        let malefactor_ban_dao = MalefactorBanDaoMock::new()
+            .ban_params(&ban_params_arc)
+            .ban_result(make_malefactor_ban(42));
+        subject.malefactor_ban_dao_opt = Some(Box::new(malefactor_ban_dao));
+        let subject_node = subject.neighborhood_database.root().clone();
+        let (hopper, _, hopper_recording_arc) = make_recorder();
+        let peer_actors = peer_actors_builder().hopper(hopper).build();
+        let system = System::new("");
+        subject.hopper = Some(peer_actors.hopper.from_hopper_client);
+        let before = SystemTime::now();
+
+        subject.handle_gossip(
+            Gossip_0v1::new(vec![]),
+            subject_node.node_addr_opt().unwrap().into(),
+        );
+
+        let after = SystemTime::now();
+        System::current().stop();
+        system.run();
+        let hopper_recording = hopper_recording_arc.lock().unwrap();
+        assert_eq!(0, hopper_recording.len());
+        let mut ban_params = ban_params_arc.lock().unwrap();
+        let (malefactor, reason, expires_at_opt) = ban_params.remove(0);
+        assert_eq!(
+            malefactor,
+            Malefactor {
+                public_key_opt: None,
+                ip_address_opt: Some(IpAddr::from_str("5.5.5.5").unwrap()),
+                earning_wallet_opt: None,
+            }
+        );
+        assert_eq!(reason, "Bad guy".to_string());
+        let expires_at = expires_at_opt.unwrap();
+        assert!(expires_at >= before + MALEFACTOR_BAN_DURATION);
+        assert!(expires_at <= after + MALEFACTOR_BAN_DURATION);
+        let tlh = TestLogHandler::new();
+        tlh.exists_log_containing(
+            "WARN: Neighborhood: Malefactor detected at 5.5.5.5; banned for 24 hours as ban 42: Bad guy",
+        );
+    }
+
+    #[test]
+    fn malefactor_bans_request_lists_bans() {
+        let subject_node = make_global_cryptde_node_record(5555, true);
+        let mut subject = neighborhood_from_nodes(&subject_node, None);
+        let ban = make_malefactor_ban(42);
+        subject.malefactor_ban_dao_opt = Some(Box::new(
+            MalefactorBanDaoMock::new().ban_list_result(vec![ban.clone()]),
+        ));
+
+        let ui_gateway_recording_arc =
+            run_ui_request(subject, UiMalefactorBansRequest {}.tmb(4321));
+
+        let ui_gateway_recording = ui_gateway_recording_arc.lock().unwrap();
+        assert_eq!(
+            ui_gateway_recording.get_record::<NodeToUiMessage>(0),
+            &NodeToUiMessage {
+                target: MessageTarget::ClientId(1234),
+                body: UiMalefactorBansResponse {
+                    bans: vec![UiMalefactorBan {
+                        id: 42,
+                        public_key_opt: Some(ban.malefactor.public_key_opt.unwrap().to_string()),
+                        ip_address_opt: Some("1.2.3.4".to_string()),
+                        earning_wallet_opt: Some(
+                            ban.malefactor.earning_wallet_opt.unwrap().to_string()
+                        ),
+                        reason: "Bad Gossip".to_string(),
+                        banned_at: 1_000_000,
+                        expires_at_opt: Some(1_086_400),
+                    }]
+                }
+                .tmb(4321)
+            }
+        );
+    }
+
+    #[test]
+    fn malefactor_unban_request_lifts_ban() {
+        let subject_node = make_global_cryptde_node_record(5555, true);
+        let mut subject = neighborhood_from_nodes(&subject_node, None);
+        let unban_params_arc = Arc::new(Mutex::new(vec![]));
+        subject.malefactor_ban_dao_opt = Some(Box::new(
+            MalefactorBanDaoMock::new()
+                .unban_params(&unban_params_arc)
+                .unban_result(true),
+        ));
+
+        let ui_gateway_recording_arc =
+            run_ui_request(subject, UiMalefactorUnbanRequest { id: 42 }.tmb(4321));
+
+        assert_eq!(*unban_params_arc.lock().unwrap(), vec![42]);
+        let ui_gateway_recording = ui_gateway_recording_arc.lock().unwrap();
+        assert_eq!(
+            ui_gateway_recording.get_record::<NodeToUiMessage>(0),
+            &NodeToUiMessage {
+                target: MessageTarget::ClientId(1234),
+                body: UiMalefactorUnbanResponse {}.tmb(4321)
+            }
+        );
+    }
+
+    #[test]
+    fn malefactor_unban_request_complains_about_unknown_ban() {
+        let subject_node = make_global_cryptde_node_record(5555, true);
+        let mut subject = neighborhood_from_nodes(&subject_node, None);
+        subject.malefactor_ban_dao_opt =
+            Some(Box::new(MalefactorBanDaoMock::new().unban_result(false)));
+
+        let ui_gateway_recording_arc =
+            run_ui_request(subject, UiMalefactorUnbanRequest { id: 42 }.tmb(4321));
+
+        let ui_gateway_recording = ui_gateway_recording_arc.lock().unwrap();
+        assert_eq!(
+            ui_gateway_recording.get_record::<NodeToUiMessage>(0),
+            &NodeToUiMessage {
+                target: MessageTarget::ClientId(1234),
+                body: MessageBody {
+                    opcode: "malefactorUnban".to_string(),
+                    path: Conversation(4321),
+                    payload: Err((
+                        MALEFACTOR_BAN_NOT_FOUND_ERROR,
+                        "There is no malefactor ban with ID 42".to_string()
+                    )),
+                }
+            }
+        );
+    }
+
+    #[test]
+    fn neighborhood_holds_debut_for_approval_and_broadcasts_it() {
+        init_test_logging();
+        let subject_node = make_global_cryptde_node_record(5555, true);
+        let mut subject = neighborhood_from_nodes(&subject_node, None);
+        let pending_debut = make_pending_debut(6767);
+        subject.gossip_acceptor = Box::new(GossipAcceptorMock::new().handle_result(
+            GossipAcceptanceResult::AwaitingApproval(
+                pending_debut.agr.clone(),
+                pending_debut.gossip_source,
+            ),
+        ));
+        let (ui_gateway, _, ui_gateway_recording_arc) = make_recorder();
+        let peer_actors = peer_actors_builder().ui_gateway(ui_gateway).build();
+        let system = System::new("neighborhood_holds_debut_for_approval_and_broadcasts_it");
+        subject.to_ui_message_sub = Some(peer_actors.ui_gateway.node_to_ui_message_sub);
+        let before = SystemTime::now();
+
+        subject.handle_gossip_agrs(vec![], pending_debut.gossip_source);
+
+        let after = SystemTime::now();
+        System::current().stop();
+        system.run();
+        assert_eq!(subject.pending_debuts.len(), 1);
+        let held = subject.pending_debuts[0].clone();
+        assert_eq!(held.agr, pending_debut.agr);
+        assert_eq!(held.gossip_source, pending_debut.gossip_source);
+        assert!(held.received_at >= before);
+        assert!(held.received_at <= after);
+        let ui_gateway_recording = ui_gateway_recording_arc.lock().unwrap();
+        assert_eq!(
+            ui_gateway_recording.get_record::<NodeToUiMessage>(0),
+            &NodeToUiMessage {
+                target: MessageTarget::AllClients,
+                body: UiDebutPendingBroadcast {
+                    debut: Neighborhood::to_ui_pending_debut(&held),
+                }
+                .tmb(0)
+            }
+        );
+        TestLogHandler::new().exists_log_containing(&format!(
+            "INFO: Neighborhood: Debut from {} at {} is awaiting manual approval",
+            held.agr.inner.public_key,
+            held.gossip_source.ip()
+        ));
+    }
+
+    #[test]
+    fn holding_too_many_debuts_drops_the_oldest() {
+        init_test_logging();
+        let subject_node = make_global_cryptde_node_record(5555, true);
+        let mut subject = neighborhood_from_nodes(&subject_node, None);
+        (0..MAX_PENDING_DEBUTS).for_each(|index| {
+            subject
+                .pending_debuts
+                .push(make_pending_debut(1000 + index as u16))
+        });
+        let oldest = subject.pending_debuts[0].clone();
+        let newcomer = make_pending_debut(6969);
+
+        subject.hold_debut(newcomer.agr.clone(), newcomer.gossip_source);
+
+        assert_eq!(subject.pending_debuts.len(), MAX_PENDING_DEBUTS);
+        assert!(!subject.pending_debuts.contains(&oldest));
+        assert_eq!(subject.pending_debuts.last().unwrap().agr, newcomer.agr);
+        TestLogHandler::new().exists_log_containing(&format!(
+            "WARN: Neighborhood: Too many debuts awaiting manual approval; dropping debut from {} at {}",
+            oldest.agr.inner.public_key,
+            oldest.gossip_source.ip()
+        ));
+    }
+
+    #[test]
+    fn stale_pending_debuts_expire() {
+        let subject_node = make_global_cryptde_node_record(5555, true);
+        let mut subject = neighborhood_from_nodes(&subject_node, None);
+        let mut stale = make_pending_debut(6868);
+        stale.received_at = SystemTime::now() - Duration::from_secs(PENDING_DEBUT_EXPIRY_SECS + 1);
+        let fresh = make_pending_debut(6969);
+        subject.pending_debuts.push(stale);
+        subject.pending_debuts.push(fresh.clone());
+
+        let ui_gateway_recording_arc = run_ui_request(subject, UiPendingDebutsRequest {}.tmb(4321));
+
+        let ui_gateway_recording = ui_gateway_recording_arc.lock().unwrap();
+        assert_eq!(
+            ui_gateway_recording.get_record::<NodeToUiMessage>(0),
+            &NodeToUiMessage {
+                target: MessageTarget::ClientId(1234),
+                body: UiPendingDebutsResponse {
+                    debuts: vec![Neighborhood::to_ui_pending_debut(&fresh)]
+                }
+                .tmb(4321)
+            }
+        );
+    }
+
+    #[test]
+    fn pending_debuts_request_lists_pending_debuts() {
+        let subject_node = make_global_cryptde_node_record(5555, true);
+        let mut subject = neighborhood_from_nodes(&subject_node, None);
+        let pending_debut = make_pending_debut(6868);
+        subject.pending_debuts.push(pending_debut.clone());
+
+        let ui_gateway_recording_arc = run_ui_request(subject, UiPendingDebutsRequest {}.tmb(4321));
+
+        let ui_gateway_recording = ui_gateway_recording_arc.lock().unwrap();
+        assert_eq!(
+            ui_gateway_recording.get_record::<NodeToUiMessage>(0),
+            &NodeToUiMessage {
+                target: MessageTarget::ClientId(1234),
+                body: UiPendingDebutsResponse {
+                    debuts: vec![UiPendingDebut {
+                        public_key: pending_debut.agr.inner.public_key.to_string(),
+                        node_address: pending_debut.agr.node_addr_opt.unwrap().to_string(),
+                        earning_wallet: pending_debut.agr.inner.earning_wallet.to_string(),
+                        received_at: to_time_t(pending_debut.received_at) as u64,
+                    }]
+                }
+                .tmb(4321)
+            }
+        );
+    }
+
+    #[test]
+    fn approve_debut_request_allows_debuter_and_resubmits_debut() {
+        let subject_node = make_global_cryptde_node_record(5555, true);
+        let mut subject = neighborhood_from_nodes(&subject_node, None);
+        let pending_debut = make_pending_debut(6969);
+        let public_key = pending_debut.agr.inner.public_key.clone();
+        let _guard = DebutAllowListGuard::new(&[public_key.clone()]);
+        subject.pending_debuts.push(pending_debut.clone());
+        let handle_params_arc = Arc::new(Mutex::new(vec![]));
+        subject.gossip_acceptor = Box::new(
+            GossipAcceptorMock::new()
+                .handle_params(&handle_params_arc)
+                .handle_result(GossipAcceptanceResult::Ignored),
+        );
+
+        let ui_gateway_recording_arc = run_ui_request(
+            subject,
+            UiApproveDebutRequest {
+                public_key: public_key.to_string(),
+            }
+            .tmb(4321),
+        );
+
+        let handle_params = handle_params_arc.lock().unwrap();
+        assert_eq!(handle_params[0].1, vec![pending_debut.agr]);
+        assert_eq!(handle_params[0].2, pending_debut.gossip_source);
+        assert!(DEBUT_ALLOW_LIST.is_allowed(&public_key));
+        let ui_gateway_recording = ui_gateway_recording_arc.lock().unwrap();
+        assert_eq!(
+            ui_gateway_recording.get_record::<NodeToUiMessage>(0),
+            &NodeToUiMessage {
+                target: MessageTarget::ClientId(1234),
+                body: UiApproveDebutResponse {}.tmb(4321)
+            }
+        );
+    }
+
+    #[test]
+    fn approve_debut_request_complains_about_unknown_debut() {
+        let subject_node = make_global_cryptde_node_record(5555, true);
+        let subject = neighborhood_from_nodes(&subject_node, None);
+
+        let ui_gateway_recording_arc = run_ui_request(
+            subject,
+            UiApproveDebutRequest {
+                public_key: "AQIDBA".to_string(),
+            }
+            .tmb(4321),
+        );
+
+        let ui_gateway_recording = ui_gateway_recording_arc.lock().unwrap();
+        assert_eq!(
+            ui_gateway_recording.get_record::<NodeToUiMessage>(0),
+            &NodeToUiMessage {
+                target: MessageTarget::ClientId(1234),
+                body: MessageBody {
+                    opcode: "approveDebut".to_string(),
+                    path: Conversation(4321),
+                    payload: Err((
+                        PENDING_DEBUT_NOT_FOUND_ERROR,
+                        "There is no pending debut from AQIDBA".to_string()
+                    )),
+                }
+            }
+        );
+    }
+
+    #[test]
+    fn reject_debut_request_sends_manual_rejection() {
+        let subject_node = make_global_cryptde_node_record(5555, true);
+        let mut subject = neighborhood_from_nodes(&subject_node, None);
+        let pending_debut = make_pending_debut(7070);
+        let public_key = pending_debut.agr.inner.public_key.clone();
+        subject.pending_debuts.push(pending_debut.clone());
+        let system = System::new("reject_debut_request_sends_manual_rejection");
+        let (hopper, _, hopper_recording_arc) = make_recorder();
+        let (ui_gateway, _, ui_gateway_recording_arc) = make_recorder();
+        let subject_addr = subject.start();
+        let peer_actors = peer_actors_builder()
+            .hopper(hopper)
+            .ui_gateway(ui_gateway)
+            .build();
+        subject_addr.try_send(BindMessage { peer_actors }).unwrap();
+
+        subject_addr
+            .try_send(NodeFromUiMessage {
+                client_id: 1234,
+                body: UiRejectDebutRequest {
+                    public_key: public_key.to_string(),
+                }
+                .tmb(4321),
+            })
+            .unwrap();
+
+        System::current().stop();
+        system.run();
+        let hopper_recording = hopper_recording_arc.lock().unwrap();
+        let package = hopper_recording.get_record::<NoLookupIncipientCoresPackage>(0);
+        assert_eq!(package.node_addr, pending_debut.agr.node_addr_opt.unwrap());
+        let payload = decodex::<MessageType>(
+            &CryptDENull::from(&public_key, DEFAULT_CHAIN_ID),
+            &package.payload,
+        )
+        .unwrap();
+        assert_eq!(
+            payload,
+            MessageType::GossipFailure(VersionedData::new(
+                &crate::sub_lib::migrations::gossip_failure::MIGRATIONS,
+                &GossipFailure_0v1::ManualRejection
+            ))
+        );
+        let ui_gateway_recording = ui_gateway_recording_arc.lock().unwrap();
+        assert_eq!(
+            ui_gateway_recording.get_record::<NodeToUiMessage>(0),
+            &NodeToUiMessage {
+                target: MessageTarget::ClientId(1234),
+                body: UiRejectDebutResponse {}.tmb(4321)
+            }
+        );
+    }
+
+    #[test]
+    fn debut_allow_list_request_changes_and_lists_allow_list() {
+        let subject_node = make_global_cryptde_node_record(5555, true);
+        let mut subject = neighborhood_from_nodes(&subject_node, None);
+        let allow_params_arc = Arc::new(Mutex::new(vec![]));
+        let disallow_params_arc = Arc::new(Mutex::new(vec![]));
+        subject.debut_allow_list_dao_opt = Some(Box::new(
+            DebutAllowListDaoMock::new()
+                .allow_params(&allow_params_arc)
+                .disallow_params(&disallow_params_arc)
+                .allow_list_result(vec![PublicKey::new(&[1, 2, 3, 4])]),
+        ));
+
+        let ui_gateway_recording_arc = run_ui_request(
+            subject,
+            UiDebutAllowListRequest {
+                add: vec!["AQIDBA".to_string()],
+                remove: vec!["BQYHCA".to_string()],
+            }
+            .tmb(4321),
         );
 
-        System::current().stop();
-        system.run();
-        let hopper_recording = hopper_recording_arc.lock().unwrap();
-        let package = hopper_recording.get_record::<NoLookupIncipientCoresPackage>(0);
-        assert_eq!(1, hopper_recording.len());
-        assert_eq!(debut_node.public_key(), &package.public_key);
         assert_eq!(
-            debut_node.node_addr_opt().as_ref().unwrap(),
-            &package.node_addr
+            *allow_params_arc.lock().unwrap(),
+            vec![PublicKey::new(&[1, 2, 3, 4])]
         );
         assert_eq!(
-            debut_gossip,
-            match decodex::<MessageType>(
-                &CryptDENull::from(debut_node.public_key(), DEFAULT_CHAIN_ID),
-                &package.payload,
-            ) {
-                Ok(MessageType::Gossip(vd)) => Gossip_0v1::try_from(vd).unwrap(),
-                x => panic!("Expected Gossip, but found {:?}", x),
-            },
+            *disallow_params_arc.lock().unwrap(),
+            vec![PublicKey::new(&[5, 6, 7, 8])]
+        );
+        let ui_gateway_recording = ui_gateway_recording_arc.lock().unwrap();
+        assert_eq!(
+            ui_gateway_recording.get_record::<NodeToUiMessage>(0),
+            &NodeToUiMessage {
+                target: MessageTarget::ClientId(1234),
+                body: UiDebutAllowListResponse {
+                    public_keys: vec!["AQIDBA".to_string()]
+                }
+                .tmb(4321)
+            }
         );
     }
 
     #[test]
-    fn neighborhood_sends_no_gossip_when_gossip_acceptor_ignores() {
-        let subject_node = make_global_cryptde_node_record(5555, true); // 9e7p7un06eHs6frl5A
-        let neighbor = make_node_record(1111, true);
-        let mut subject = neighborhood_from_nodes(&subject_node, Some(&neighbor));
-        let gossip_acceptor =
-            GossipAcceptorMock::new().handle_result(GossipAcceptanceResult::Ignored);
-        subject.gossip_acceptor = Box::new(gossip_acceptor);
-        let subject_node = subject.neighborhood_database.root().clone();
-        let (hopper, _, hopper_recording_arc) = make_recorder();
-        let peer_actors = peer_actors_builder().hopper(hopper).build();
-        let system = System::new("");
-        subject.hopper = Some(peer_actors.hopper.from_hopper_client);
+    fn debut_allow_list_request_complains_about_bad_public_key() {
+        let subject_node = make_global_cryptde_node_record(5555, true);
+        let mut subject = neighborhood_from_nodes(&subject_node, None);
+        subject.debut_allow_list_dao_opt = Some(Box::new(DebutAllowListDaoMock::new()));
 
-        subject.handle_gossip(
-            Gossip_0v1::new(vec![]),
-            subject_node.node_addr_opt().unwrap().into(),
+        let ui_gateway_recording_arc = run_ui_request(
+            subject,
+            UiDebutAllowListRequest {
+                add: vec!["AQIDBA".to_string()],
+                remove: vec!["not a key!".to_string()],
+            }
+            .tmb(4321),
         );
 
-        System::current().stop();
-        system.run();
-        let hopper_recording = hopper_recording_arc.lock().unwrap();
-        assert_eq!(0, hopper_recording.len());
+        let ui_gateway_recording = ui_gateway_recording_arc.lock().unwrap();
+        assert_eq!(
+            ui_gateway_recording.get_record::<NodeToUiMessage>(0),
+            &NodeToUiMessage {
+                target: MessageTarget::ClientId(1234),
+                body: MessageBody {
+                    opcode: "debutAllowList".to_string(),
+                    path: Conversation(4321),
+                    payload: Err((
+                        BAD_PUBLIC_KEY_ERROR,
+                        "'not a key!' is not a valid public key".to_string()
+                    )),
+                }
+            }
+        );
     }
 
     #[test]
-    fn neighborhood_complains_about_inability_to_ban_when_gossip_acceptor_requests_it() {
+    fn neighborhood_drops_gossip_refused_by_rate_limiter_and_reports_it() {
         init_test_logging();
-        let subject_node = make_global_cryptde_node_record(5555, true); // 9e7p7un06eHs6frl5A
-        let neighbor = make_node_record(1111, true);
-        let mut subject = neighborhood_from_nodes(&subject_node, Some(&neighbor));
-        let gossip_acceptor = GossipAcceptorMock::new()
-            .handle_result(GossipAcceptanceResult::Ban("Bad guy".to_string()));
-        subject.gossip_acceptor = Box::new(gossip_acceptor);
-        let subject_node = subject.neighborhood_database.root().clone();
-        let (hopper, _, hopper_recording_arc) = make_recorder();
-        let peer_actors = peer_actors_builder().hopper(hopper).build();
-        let system = System::new("");
-        subject.hopper = Some(peer_actors.hopper.from_hopper_client);
+        let mut subject = make_standard_subject();
+        subject.gossip_acceptor = Box::new(GossipAcceptorMock::new());
+        subject.gossip_rate_limiter =
+            GossipRateLimiter::new(10, 10, 1, 10, Duration::from_secs(10));
+        let db = &mut subject.neighborhood_database;
+        let one_node_key = &db.add_node(make_node_record(2222, true)).unwrap();
+        let another_node_key = &db.add_node(make_node_record(3333, true)).unwrap();
+        let gossip = GossipBuilder::new(db)
+            .node(one_node_key, true)
+            .node(another_node_key, false)
+            .build();
+        let gossip_source = SocketAddr::from_str("1.2.3.4:1234").unwrap();
 
-        subject.handle_gossip(
-            Gossip_0v1::new(vec![]),
-            subject_node.node_addr_opt().unwrap().into(),
-        );
+        subject.handle_gossip(gossip, gossip_source);
 
-        System::current().stop();
-        system.run();
-        let hopper_recording = hopper_recording_arc.lock().unwrap();
-        assert_eq!(0, hopper_recording.len());
-        let tlh = TestLogHandler::new();
-        tlh.exists_log_containing("WARN: Neighborhood: Malefactor detected at 5.5.5.5:5555, but malefactor bans not yet implemented; ignoring: Bad guy");
+        // No panic means that subject didn't try to invoke the GossipAcceptorMock
+        TestLogHandler::new().exists_log_containing(&format!(
+            "DEBUG: Neighborhood: Dropping Gossip about 2 Nodes from {}: 2 records is more than the limit of 1; \
+             dropped so far: 1 oversized, 0 duplicate, 0 over source limit, 0 over global limit",
+            gossip_source
+        ));
+        let ui_gateway_recording_arc = run_ui_request(subject, UiDroppedGossipRequest {}.tmb(4321));
+        let ui_gateway_recording = ui_gateway_recording_arc.lock().unwrap();
+        assert_eq!(
+            ui_gateway_recording.get_record::<NodeToUiMessage>(0),
+            &NodeToUiMessage {
+                target: MessageTarget::ClientId(1234),
+                body: UiDroppedGossipResponse {
+                    too_many_records: 1,
+                    duplicate: 0,
+                    source_rate_exceeded: 0,
+                    global_rate_exceeded: 0,
+                }
+                .tmb(4321)
+            }
+        );
     }
 
     #[test]
@@ -3441,7 +5649,7 @@ mod tests {
                         NodeAddr::new(&IpAddr::from_str("5.4.3.2").unwrap(), &[1234]),
                         vec![NodeDescriptor::from((
                             &neighbor_inside,
-                            DEFAULT_CHAIN_ID == chain_id_from_name(DEFAULT_CHAIN_NAME),
+                            DEFAULT_CHAIN_ID == chain_id_from_name(DEFAULT_CHAIN_NAME).unwrap(),
                             cryptde,
                         ))],
                         rate_pack(100),
@@ -3555,12 +5763,37 @@ mod tests {
         assert_eq!(None, failed_ip_address_query.wait().unwrap());
     }
 
+    #[test]
+    fn neighborhood_tells_proxy_server_about_removed_neighbor() {
+        let system = System::new("neighborhood_tells_proxy_server_about_removed_neighbor");
+        let (proxy_server, _, proxy_server_recording_arc) = make_recorder();
+        let subject = make_standard_subject();
+        let addr: Addr<Neighborhood> = subject.start();
+        let peer_actors = peer_actors_builder().proxy_server(proxy_server).build();
+        addr.try_send(BindMessage { peer_actors }).unwrap();
+
+        addr.try_send(RemoveNeighborMessage {
+            public_key: PublicKey::new(&[1, 2, 3, 4]),
+        })
+        .unwrap();
+
+        System::current().stop_with_code(0);
+        system.run();
+        let proxy_server_recording = proxy_server_recording_arc.lock().unwrap();
+        assert_eq!(
+            proxy_server_recording.get_record::<RemoveNeighborMessage>(0),
+            &RemoveNeighborMessage {
+                public_key: PublicKey::new(&[1, 2, 3, 4]),
+            }
+        );
+    }
+
     fn node_record_to_neighbor_config(node_record_ref: &NodeRecord) -> NodeDescriptor {
         let cryptde: &dyn CryptDE = main_cryptde();
         NodeDescriptor::from((
             &node_record_ref.public_key().clone(),
             &node_record_ref.node_addr_opt().unwrap().clone(),
-            DEFAULT_CHAIN_ID == chain_id_from_name(DEFAULT_CHAIN_NAME),
+            DEFAULT_CHAIN_ID == chain_id_from_name(DEFAULT_CHAIN_NAME).unwrap(),
             cryptde,
         ))
     }
@@ -3628,7 +5861,7 @@ mod tests {
                                     &IpAddr::from_str("1.2.3.4").unwrap(),
                                     &[1234, 2345],
                                 ),
-                                DEFAULT_CHAIN_ID == chain_id_from_name(DEFAULT_CHAIN_NAME),
+                                DEFAULT_CHAIN_ID == chain_id_from_name(DEFAULT_CHAIN_NAME).unwrap(),
                                 cryptde,
                             ))],
                             rate_pack(100),
@@ -3755,7 +5988,7 @@ mod tests {
                                     &IpAddr::from_str("1.2.3.4").unwrap(),
                                     &[1234, 2345],
                                 ),
-                                DEFAULT_CHAIN_ID == chain_id_from_name(DEFAULT_CHAIN_NAME),
+                                DEFAULT_CHAIN_ID == chain_id_from_name(DEFAULT_CHAIN_NAME).unwrap(),
                                 cryptde,
                             ))],
                             rate_pack(100),
@@ -3818,7 +6051,7 @@ mod tests {
                         node_record.node_addr_opt().unwrap(),
                         vec![NodeDescriptor::from((
                             &node_record,
-                            DEFAULT_CHAIN_ID == chain_id_from_name(DEFAULT_CHAIN_NAME),
+                            DEFAULT_CHAIN_ID == chain_id_from_name(DEFAULT_CHAIN_NAME).unwrap(),
                             cryptde,
                         ))],
                         rate_pack(100),
@@ -4265,6 +6498,320 @@ mod tests {
         )
     }
 
+    fn run_ui_request(subject: Neighborhood, body: MessageBody) -> Arc<Mutex<Recording>> {
+        let system = System::new("test");
+        let (ui_gateway, _, ui_gateway_recording_arc) = make_recorder();
+        let subject_addr = subject.start();
+        let peer_actors = peer_actors_builder().ui_gateway(ui_gateway).build();
+        subject_addr.try_send(BindMessage { peer_actors }).unwrap();
+
+        subject_addr
+            .try_send(NodeFromUiMessage {
+                client_id: 1234,
+                body,
+            })
+            .unwrap();
+
+        System::current().stop();
+        system.run();
+        ui_gateway_recording_arc
+    }
+
+    fn make_malefactor_ban(id: u64) -> MalefactorBan {
+        MalefactorBan {
+            id,
+            malefactor: Malefactor {
+                public_key_opt: Some(PublicKey::new(&[1, 2, 3, 4])),
+                ip_address_opt: Some(IpAddr::from_str("1.2.3.4").unwrap()),
+                earning_wallet_opt: Some(make_wallet("malefactor")),
+            },
+            reason: "Bad Gossip".to_string(),
+            banned_at: from_time_t(1_000_000),
+            expires_at_opt: Some(from_time_t(1_086_400)),
+        }
+    }
+
+    #[test]
+    fn load_neighborhood_snapshot_restores_only_fresh_validly_signed_records() {
+        init_test_logging();
+        let subject_node = make_global_cryptde_node_record(5555, true);
+        let mut subject = neighborhood_from_nodes(&subject_node, None);
+        let restorable = make_node_record(1234, true);
+        let mut restorable_record = NeighborhoodSnapshotRecord::from(&restorable);
+        restorable_record.desirable = false;
+        restorable_record.last_update = time_t_timestamp() - 1000;
+        let stale = make_node_record(2345, true);
+        let mut stale_record = NeighborhoodSnapshotRecord::from(&stale);
+        stale_record.last_update = time_t_timestamp() - SNAPSHOT_RECORD_GRACE_PERIOD_SECS - 2;
+        let forged = make_node_record(3456, true);
+        let mut forged_record = NeighborhoodSnapshotRecord::from(&forged);
+        forged_record.signature = CryptData::new(&[1, 2, 3, 4]);
+        let own_record = NeighborhoodSnapshotRecord::from(&subject_node);
+        subject.neighborhood_snapshot_dao_opt = Some(Box::new(
+            NeighborhoodSnapshotDaoMock::new().load_result(vec![
+                restorable_record.clone(),
+                stale_record,
+                forged_record,
+                own_record,
+            ]),
+        ));
+
+        subject.load_neighborhood_snapshot();
+
+        let restored = subject
+            .neighborhood_database
+            .node_by_key(restorable.public_key())
+            .unwrap();
+        assert_eq!(restored.inner, restorable.inner);
+        assert_eq!(restored.node_addr_opt(), restorable.node_addr_opt());
+        assert_eq!(restored.is_desirable(), false);
+        assert_eq!(restored.last_updated(), restorable_record.last_update);
+        assert_eq!(
+            subject
+                .neighborhood_database
+                .node_by_key(stale.public_key()),
+            None
+        );
+        assert_eq!(
+            subject
+                .neighborhood_database
+                .node_by_key(forged.public_key()),
+            None
+        );
+        assert_eq!(subject.neighborhood_database.keys().len(), 2);
+        TestLogHandler::new().exists_log_containing(
+            "INFO: Neighborhood: Restored 1 of 4 Nodes from the neighborhood snapshot",
+        );
+    }
+
+    #[test]
+    fn save_neighborhood_snapshot_saves_every_node_but_this_one() {
+        let subject_node = make_global_cryptde_node_record(5555, true);
+        let first = make_node_record(1234, true);
+        let second = make_node_record(2345, false);
+        let mut subject = neighborhood_from_nodes(&subject_node, None);
+        subject
+            .neighborhood_database
+            .add_node(first.clone())
+            .unwrap();
+        subject
+            .neighborhood_database
+            .add_node(second.clone())
+            .unwrap();
+        let save_params_arc = Arc::new(Mutex::new(vec![]));
+        subject.neighborhood_snapshot_dao_opt = Some(Box::new(
+            NeighborhoodSnapshotDaoMock::new()
+                .save_params(&save_params_arc)
+                .save_result(Ok(())),
+        ));
+
+        subject.save_neighborhood_snapshot();
+
+        let mut save_params = save_params_arc.lock().unwrap();
+        assert_eq!(save_params.len(), 1);
+        let records = &mut save_params[0];
+        records.sort_by(|a, b| a.public_key.cmp(&b.public_key));
+        assert_eq!(
+            *records,
+            vec![
+                NeighborhoodSnapshotRecord::from(&first),
+                NeighborhoodSnapshotRecord::from(&second)
+            ]
+        );
+    }
+
+    #[test]
+    fn save_neighborhood_snapshot_logs_failure() {
+        init_test_logging();
+        let subject_node = make_global_cryptde_node_record(5555, true);
+        let mut subject = neighborhood_from_nodes(&subject_node, None);
+        subject.neighborhood_snapshot_dao_opt = Some(Box::new(
+            NeighborhoodSnapshotDaoMock::new().save_result(Err("booga".to_string())),
+        ));
+
+        subject.save_neighborhood_snapshot();
+
+        TestLogHandler::new().exists_log_containing(
+            "ERROR: Neighborhood: Could not save neighborhood snapshot: booga",
+        );
+    }
+
+    fn make_pending_debut(node_number: u16) -> PendingDebut {
+        let agr = AccessibleGossipRecord::from(&make_node_record(node_number, true));
+        let node_addr = agr.node_addr_opt.clone().unwrap();
+        PendingDebut {
+            agr,
+            gossip_source: SocketAddr::new(node_addr.ip_addr(), node_addr.ports()[0]),
+            received_at: from_time_t(to_time_t(SystemTime::now())),
+        }
+    }
+
+    pub struct DebutAllowListDaoMock {
+        allow_list_results: RefCell<Vec<Vec<PublicKey>>>,
+        allow_params: Arc<Mutex<Vec<PublicKey>>>,
+        disallow_params: Arc<Mutex<Vec<PublicKey>>>,
+    }
+
+    impl DebutAllowListDao for DebutAllowListDaoMock {
+        fn allow_list(&self) -> Vec<PublicKey> {
+            self.allow_list_results.borrow_mut().remove(0)
+        }
+
+        fn allow(&self, public_key: &PublicKey) {
+            self.allow_params.lock().unwrap().push(public_key.clone());
+        }
+
+        fn disallow(&self, public_key: &PublicKey) {
+            self.disallow_params
+                .lock()
+                .unwrap()
+                .push(public_key.clone());
+        }
+    }
+
+    impl DebutAllowListDaoMock {
+        pub fn new() -> Self {
+            Self {
+                allow_list_results: RefCell::new(vec![]),
+                allow_params: Arc::new(Mutex::new(vec![])),
+                disallow_params: Arc::new(Mutex::new(vec![])),
+            }
+        }
+
+        pub fn allow_list_result(self, result: Vec<PublicKey>) -> Self {
+            self.allow_list_results.borrow_mut().push(result);
+            self
+        }
+
+        pub fn allow_params(mut self, params: &Arc<Mutex<Vec<PublicKey>>>) -> Self {
+            self.allow_params = params.clone();
+            self
+        }
+
+        pub fn disallow_params(mut self, params: &Arc<Mutex<Vec<PublicKey>>>) -> Self {
+            self.disallow_params = params.clone();
+            self
+        }
+    }
+
+    pub struct NeighborhoodSnapshotDaoMock {
+        load_results: RefCell<Vec<Vec<NeighborhoodSnapshotRecord>>>,
+        save_params: Arc<Mutex<Vec<Vec<NeighborhoodSnapshotRecord>>>>,
+        save_results: RefCell<Vec<Result<(), String>>>,
+    }
+
+    impl NeighborhoodSnapshotDao for NeighborhoodSnapshotDaoMock {
+        fn load(&self) -> Vec<NeighborhoodSnapshotRecord> {
+            self.load_results.borrow_mut().remove(0)
+        }
+
+        fn save(&mut self, records: Vec<NeighborhoodSnapshotRecord>) -> Result<(), String> {
+            self.save_params.lock().unwrap().push(records);
+            self.save_results.borrow_mut().remove(0)
+        }
+    }
+
+    impl NeighborhoodSnapshotDaoMock {
+        pub fn new() -> Self {
+            Self {
+                load_results: RefCell::new(vec![]),
+                save_params: Arc::new(Mutex::new(vec![])),
+                save_results: RefCell::new(vec![]),
+            }
+        }
+
+        pub fn load_result(self, result: Vec<NeighborhoodSnapshotRecord>) -> Self {
+            self.load_results.borrow_mut().push(result);
+            self
+        }
+
+        pub fn save_params(
+            mut self,
+            params: &Arc<Mutex<Vec<Vec<NeighborhoodSnapshotRecord>>>>,
+        ) -> Self {
+            self.save_params = params.clone();
+            self
+        }
+
+        pub fn save_result(self, result: Result<(), String>) -> Self {
+            self.save_results.borrow_mut().push(result);
+            self
+        }
+    }
+
+    pub struct MalefactorBanDaoMock {
+        ban_list_results: RefCell<Vec<Vec<MalefactorBan>>>,
+        ban_params: Arc<Mutex<Vec<(Malefactor, String, Option<SystemTime>)>>>,
+        ban_results: RefCell<Vec<MalefactorBan>>,
+        unban_params: Arc<Mutex<Vec<u64>>>,
+        unban_results: RefCell<Vec<bool>>,
+    }
+
+    impl MalefactorBanDao for MalefactorBanDaoMock {
+        fn ban_list(&self) -> Vec<MalefactorBan> {
+            self.ban_list_results.borrow_mut().remove(0)
+        }
+
+        fn ban(
+            &self,
+            malefactor: &Malefactor,
+            reason: &str,
+            expires_at_opt: Option<SystemTime>,
+        ) -> MalefactorBan {
+            self.ban_params.lock().unwrap().push((
+                malefactor.clone(),
+                reason.to_string(),
+                expires_at_opt,
+            ));
+            self.ban_results.borrow_mut().remove(0)
+        }
+
+        fn unban(&self, id: u64) -> bool {
+            self.unban_params.lock().unwrap().push(id);
+            self.unban_results.borrow_mut().remove(0)
+        }
+    }
+
+    impl MalefactorBanDaoMock {
+        pub fn new() -> Self {
+            Self {
+                ban_list_results: RefCell::new(vec![]),
+                ban_params: Arc::new(Mutex::new(vec![])),
+                ban_results: RefCell::new(vec![]),
+                unban_params: Arc::new(Mutex::new(vec![])),
+                unban_results: RefCell::new(vec![]),
+            }
+        }
+
+        pub fn ban_list_result(self, result: Vec<MalefactorBan>) -> Self {
+            self.ban_list_results.borrow_mut().push(result);
+            self
+        }
+
+        pub fn ban_params(
+            mut self,
+            params: &Arc<Mutex<Vec<(Malefactor, String, Option<SystemTime>)>>>,
+        ) -> Self {
+            self.ban_params = params.clone();
+            self
+        }
+
+        pub fn ban_result(self, result: MalefactorBan) -> Self {
+            self.ban_results.borrow_mut().push(result);
+            self
+        }
+
+        pub fn unban_params(mut self, params: &Arc<Mutex<Vec<u64>>>) -> Self {
+            self.unban_params = params.clone();
+            self
+        }
+
+        pub fn unban_result(self, result: bool) -> Self {
+            self.unban_results.borrow_mut().push(result);
+            self
+        }
+    }
+
     pub struct GossipAcceptorMock {
         handle_params: Arc<
             Mutex<
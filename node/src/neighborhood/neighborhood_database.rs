@@ -20,6 +20,7 @@ use std::fmt::Formatter;
 use std::net::IpAddr;
 
 pub const ISOLATED_NODE_GRACE_PERIOD_SECS: u32 = 30;
+pub const SNAPSHOT_RECORD_GRACE_PERIOD_SECS: u32 = 86_400;
 
 #[derive(Clone)]
 pub struct NeighborhoodDatabase {
@@ -0,0 +1,167 @@
+// Copyright (c) 2019-2020, MASQ (https://masq.ai) and/or its affiliates. All rights reserved.
+use crate::database::connection_wrapper::ConnectionWrapper;
+use crate::neighborhood::node_record::NodeRecord;
+use crate::sub_lib::cryptde::{CryptData, PlainData, PublicKey};
+use crate::sub_lib::node_addr::NodeAddr;
+use rusqlite::{ToSql, NO_PARAMS};
+use std::str::FromStr;
+
+// A Node's signed Gossip record, along with the metadata this Node keeps about it, as saved
+// between runs.
+#[derive(Clone, Debug, PartialEq)]
+pub struct NeighborhoodSnapshotRecord {
+    pub public_key: PublicKey,
+    pub signed_gossip: PlainData,
+    pub signature: CryptData,
+    pub node_addr_opt: Option<NodeAddr>,
+    pub desirable: bool,
+    pub last_update: u32,
+}
+
+impl From<&NodeRecord> for NeighborhoodSnapshotRecord {
+    fn from(node_record: &NodeRecord) -> Self {
+        NeighborhoodSnapshotRecord {
+            public_key: node_record.public_key().clone(),
+            signed_gossip: node_record.signed_gossip().clone(),
+            signature: node_record.signature().clone(),
+            node_addr_opt: node_record.node_addr_opt(),
+            desirable: node_record.is_desirable(),
+            last_update: node_record.last_updated(),
+        }
+    }
+}
+
+pub trait NeighborhoodSnapshotDao: Send {
+    fn load(&self) -> Vec<NeighborhoodSnapshotRecord>;
+    fn save(&mut self, records: Vec<NeighborhoodSnapshotRecord>) -> Result<(), String>;
+}
+
+pub struct NeighborhoodSnapshotDaoReal {
+    conn: Box<dyn ConnectionWrapper>,
+}
+
+impl NeighborhoodSnapshotDaoReal {
+    pub fn new(conn: Box<dyn ConnectionWrapper>) -> Self {
+        Self { conn }
+    }
+}
+
+impl NeighborhoodSnapshotDao for NeighborhoodSnapshotDaoReal {
+    fn load(&self) -> Vec<NeighborhoodSnapshotRecord> {
+        let mut stmt = self
+            .conn
+            .prepare(
+                "select public_key, signed_gossip, signature, node_addr, desirable, last_update \
+                 from neighborhood_snapshot order by public_key",
+            )
+            .expect("Failed to prepare a statement");
+        stmt.query_map(NO_PARAMS, |row| {
+            let encoded_key: String = row.get(0)?;
+            let signed_gossip: Vec<u8> = row.get(1)?;
+            let signature: Vec<u8> = row.get(2)?;
+            let node_addr_opt: Option<String> = row.get(3)?;
+            let desirable: bool = row.get(4)?;
+            let last_update: i64 = row.get(5)?;
+            Ok(NeighborhoodSnapshotRecord {
+                public_key: PublicKey::new(
+                    &base64::decode_config(&encoded_key, base64::STANDARD_NO_PAD).unwrap_or_else(
+                        |_| panic!("Database corrupt: bad snapshot public key {}", encoded_key),
+                    ),
+                ),
+                signed_gossip: PlainData::from(signed_gossip),
+                signature: CryptData::from(signature),
+                node_addr_opt: node_addr_opt.map(|node_addr| {
+                    NodeAddr::from_str(&node_addr).unwrap_or_else(|_| {
+                        panic!("Database corrupt: bad snapshot node address {}", node_addr)
+                    })
+                }),
+                desirable,
+                last_update: last_update as u32,
+            })
+        })
+        .expect("Couldn't retrieve neighborhood snapshot: database corrupt")
+        .flatten()
+        .collect()
+    }
+
+    fn save(&mut self, records: Vec<NeighborhoodSnapshotRecord>) -> Result<(), String> {
+        let tx = self.conn.transaction().map_err(|e| e.to_string())?;
+        tx.execute("delete from neighborhood_snapshot", NO_PARAMS)
+            .map_err(|e| e.to_string())?;
+        {
+            let mut stmt = tx
+                .prepare(
+                    "insert into neighborhood_snapshot \
+                     (public_key, signed_gossip, signature, node_addr, desirable, last_update) \
+                     values (?, ?, ?, ?, ?, ?)",
+                )
+                .expect("Internal SQL error");
+            for record in records {
+                let params: &[&dyn ToSql] = &[
+                    &record.public_key.to_string(),
+                    &record.signed_gossip.as_slice().to_vec(),
+                    &record.signature.as_slice().to_vec(),
+                    &record.node_addr_opt.map(|node_addr| node_addr.to_string()),
+                    &record.desirable,
+                    &(record.last_update as i64),
+                ];
+                stmt.execute(params).map_err(|e| e.to_string())?;
+            }
+        }
+        tx.commit().map_err(|e| e.to_string())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::database::db_initializer::{DbInitializer, DbInitializerReal};
+    use crate::test_utils::neighborhood_test_utils::make_node_record;
+    use masq_lib::test_utils::utils::{ensure_node_home_directory_exists, DEFAULT_CHAIN_ID};
+
+    fn make_subject(test_name: &str) -> NeighborhoodSnapshotDaoReal {
+        let home_dir = ensure_node_home_directory_exists("neighborhood_snapshot_dao", test_name);
+        let conn = DbInitializerReal::new()
+            .initialize(&home_dir, DEFAULT_CHAIN_ID, true)
+            .unwrap();
+        NeighborhoodSnapshotDaoReal::new(conn)
+    }
+
+    #[test]
+    fn load_returns_nothing_from_empty_table() {
+        let subject = make_subject("load_returns_nothing_from_empty_table");
+
+        let result = subject.load();
+
+        assert_eq!(result, vec![]);
+    }
+
+    #[test]
+    fn save_and_load_round_trip() {
+        let mut subject = make_subject("save_and_load_round_trip");
+        let with_addr = NeighborhoodSnapshotRecord::from(&make_node_record(1234, true));
+        let mut without_addr = NeighborhoodSnapshotRecord::from(&make_node_record(2345, false));
+        without_addr.desirable = false;
+        without_addr.last_update = 1_000_000;
+
+        subject
+            .save(vec![without_addr.clone(), with_addr.clone()])
+            .unwrap();
+
+        let mut result = subject.load();
+        result.sort_by(|a, b| a.public_key.cmp(&b.public_key));
+        assert_eq!(result, vec![with_addr, without_addr]);
+    }
+
+    #[test]
+    fn save_replaces_previous_snapshot() {
+        let mut subject = make_subject("save_replaces_previous_snapshot");
+        let old_record = NeighborhoodSnapshotRecord::from(&make_node_record(3456, true));
+        let new_record = NeighborhoodSnapshotRecord::from(&make_node_record(4567, true));
+        subject.save(vec![old_record]).unwrap();
+
+        subject.save(vec![new_record.clone()]).unwrap();
+
+        assert_eq!(subject.load(), vec![new_record]);
+    }
+}
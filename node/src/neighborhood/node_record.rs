@@ -8,6 +8,7 @@ use crate::sub_lib::cryptde::{CryptDE, CryptData, PlainData, PublicKey};
 use crate::sub_lib::neighborhood::NodeDescriptor;
 use crate::sub_lib::neighborhood::RatePack;
 use crate::sub_lib::node_addr::NodeAddr;
+use crate::sub_lib::proxy_server::ProxyProtocol;
 use crate::sub_lib::utils::time_t_timestamp;
 use crate::sub_lib::wallet::Wallet;
 use masq_lib::constants::DEFAULT_CHAIN_NAME;
@@ -28,7 +29,51 @@ pub struct NodeRecordInner_0v1 {
     pub version: u32,
 }
 
-impl TryFrom<GossipNodeRecord> for NodeRecordInner_0v1 {
+// Capabilities are optional and left out of the serialized record when absent, so a record that
+// advertises none is byte-for-byte the same as its NodeRecordInner_0v1 equivalent, and Nodes that
+// only know NodeRecordInner_0v1 ignore the fields they don't recognize.
+#[derive(Clone, PartialEq, Eq, Debug, Serialize, Deserialize)]
+#[allow(non_camel_case_types)]
+pub struct NodeRecordInner_0v2 {
+    pub public_key: PublicKey,
+    pub earning_wallet: Wallet,
+    pub rate_pack: RatePack,
+    pub neighbors: BTreeSet<PublicKey>,
+    pub accepts_connections: bool,
+    pub routes_data: bool,
+    pub version: u32,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub proxy_protocols_opt: Option<Vec<ProxyProtocol>>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub masqueraders_opt: Option<Vec<String>>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub max_bandwidth_opt: Option<u64>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub software_version_opt: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub country_code_opt: Option<String>,
+}
+
+impl From<NodeRecordInner_0v1> for NodeRecordInner_0v2 {
+    fn from(inner: NodeRecordInner_0v1) -> Self {
+        NodeRecordInner_0v2 {
+            public_key: inner.public_key,
+            earning_wallet: inner.earning_wallet,
+            rate_pack: inner.rate_pack,
+            neighbors: inner.neighbors,
+            accepts_connections: inner.accepts_connections,
+            routes_data: inner.routes_data,
+            version: inner.version,
+            proxy_protocols_opt: None,
+            masqueraders_opt: None,
+            max_bandwidth_opt: None,
+            software_version_opt: None,
+            country_code_opt: None,
+        }
+    }
+}
+
+impl TryFrom<GossipNodeRecord> for NodeRecordInner_0v2 {
     type Error = String;
 
     fn try_from(gnr: GossipNodeRecord) -> Result<Self, Self::Error> {
@@ -39,11 +84,11 @@ impl TryFrom<GossipNodeRecord> for NodeRecordInner_0v1 {
     }
 }
 
-impl TryFrom<&GossipNodeRecord> for NodeRecordInner_0v1 {
+impl TryFrom<&GossipNodeRecord> for NodeRecordInner_0v2 {
     type Error = String;
 
     fn try_from(gnr_addr_ref: &GossipNodeRecord) -> Result<Self, Self::Error> {
-        NodeRecordInner_0v1::try_from(gnr_addr_ref.clone())
+        NodeRecordInner_0v2::try_from(gnr_addr_ref.clone())
     }
 }
 
@@ -54,7 +99,7 @@ pub enum NodeRecordError {
 
 #[derive(Clone, Debug)]
 pub struct NodeRecord {
-    pub inner: NodeRecordInner_0v1,
+    pub inner: NodeRecordInner_0v2,
     pub metadata: NodeRecordMetadata,
     pub signed_gossip: PlainData,
     pub signature: CryptData,
@@ -72,7 +117,7 @@ impl NodeRecord {
     ) -> NodeRecord {
         let mut node_record = NodeRecord {
             metadata: NodeRecordMetadata::new(),
-            inner: NodeRecordInner_0v1 {
+            inner: NodeRecordInner_0v2 {
                 public_key: public_key.clone(),
                 earning_wallet,
                 rate_pack,
@@ -80,6 +125,16 @@ impl NodeRecord {
                 routes_data,
                 neighbors: BTreeSet::new(),
                 version,
+                // Any Node that routes data can be an exit Node, and every exit Node handles these
+                proxy_protocols_opt: if routes_data {
+                    Some(vec![ProxyProtocol::HTTP, ProxyProtocol::TLS])
+                } else {
+                    None
+                },
+                masqueraders_opt: None,
+                max_bandwidth_opt: None,
+                software_version_opt: Some(env!("CARGO_PKG_VERSION").to_string()),
+                country_code_opt: None,
             },
             signed_gossip: PlainData::new(&[]),
             signature: CryptData::new(&[]),
@@ -99,7 +154,8 @@ impl NodeRecord {
     pub fn node_descriptor(&self, chain_id: u8, cryptde: &dyn CryptDE) -> NodeDescriptor {
         NodeDescriptor::from((
             self,
-            chain_id == chain_id_from_name(DEFAULT_CHAIN_NAME),
+            chain_id
+                == chain_id_from_name(DEFAULT_CHAIN_NAME).expect("Default chain is not built in"),
             cryptde,
         ))
     }
@@ -258,6 +314,14 @@ impl NodeRecord {
         self.metadata.desirable = is_desirable
     }
 
+    pub fn quality(&self) -> &NodeQuality {
+        &self.metadata.quality
+    }
+
+    pub fn quality_mut(&mut self) -> &mut NodeQuality {
+        &mut self.metadata.quality
+    }
+
     pub fn update(&mut self, agr: AccessibleGossipRecord) -> Result<(), String> {
         if &agr.inner.public_key != self.public_key() {
             return Err(format!(
@@ -317,7 +381,7 @@ impl TryFrom<&GossipNodeRecord> for NodeRecord {
     type Error = String;
 
     fn try_from(gnr: &GossipNodeRecord) -> Result<Self, Self::Error> {
-        let inner = NodeRecordInner_0v1::try_from(gnr)?;
+        let inner = NodeRecordInner_0v2::try_from(gnr)?;
         let mut node_record = NodeRecord {
             inner,
             metadata: NodeRecordMetadata::new(),
@@ -335,6 +399,7 @@ pub struct NodeRecordMetadata {
     pub desirable: bool,
     pub last_update: u32,
     pub node_addr_opt: Option<NodeAddr>,
+    pub quality: NodeQuality,
 }
 
 impl NodeRecordMetadata {
@@ -343,10 +408,59 @@ impl NodeRecordMetadata {
             desirable: true,
             last_update: time_t_timestamp(),
             node_addr_opt: None,
+            quality: NodeQuality::default(),
         }
     }
 }
 
+// A penalty at or above this marks a Node as a poor performer: for example, one that has failed
+// half the requests sent through it lately, or one whose round trips take five seconds.
+pub const POOR_QUALITY_PENALTY: u32 = 5_000;
+
+// Each failure rate point (per mille) costs as much as this many milliseconds of round-trip time.
+const FAILURE_PENALTY_MILLIS_PER_PERMILLE: u32 = 10;
+
+// How well a Node has carried our traffic lately. Each observation moves the running averages a
+// quarter of the way toward the observed value, so old observations fade as new ones arrive.
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct NodeQuality {
+    pub round_trip_millis_opt: Option<u32>,
+    pub failure_permille: u32,
+}
+
+impl NodeQuality {
+    pub fn record_round_trip(&mut self, round_trip_millis: u32) {
+        self.round_trip_millis_opt = Some(match self.round_trip_millis_opt {
+            None => round_trip_millis,
+            Some(average) => Self::decay(average, round_trip_millis),
+        });
+        self.failure_permille = Self::decay(self.failure_permille, 0);
+    }
+
+    pub fn record_failure(&mut self) {
+        self.failure_permille = Self::decay(self.failure_permille, 1000);
+    }
+
+    // Lower is better. A Node we haven't observed yet carries no penalty, so callers comparing
+    // Nodes should check is_observed() and substitute a neutral guess for such a Node.
+    pub fn penalty(&self) -> u32 {
+        self.failure_permille * FAILURE_PENALTY_MILLIS_PER_PERMILLE
+            + self.round_trip_millis_opt.unwrap_or(0)
+    }
+
+    pub fn is_observed(&self) -> bool {
+        self != &NodeQuality::default()
+    }
+
+    pub fn is_poor(&self) -> bool {
+        self.penalty() >= POOR_QUALITY_PENALTY
+    }
+
+    fn decay(average: u32, observation: u32) -> u32 {
+        ((u64::from(average) * 3 + u64::from(observation)) / 4) as u32
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -836,6 +950,41 @@ mod tests {
         );
     }
 
+    #[test]
+    fn unobserved_node_has_no_quality_penalty() {
+        let this_node = make_node_record(5432, true);
+
+        assert_eq!(this_node.quality(), &NodeQuality::default());
+        assert_eq!(this_node.quality().penalty(), 0);
+        assert_eq!(this_node.quality().is_poor(), false);
+        assert_eq!(this_node.quality().is_observed(), false);
+    }
+
+    #[test]
+    fn quality_decays_toward_recent_observations() {
+        let mut subject = NodeQuality::default();
+
+        subject.record_round_trip(200);
+        assert_eq!(subject.round_trip_millis_opt, Some(200));
+        assert_eq!(subject.is_observed(), true);
+        subject.record_round_trip(600);
+        assert_eq!(subject.round_trip_millis_opt, Some(300));
+        subject.record_failure();
+        assert_eq!(subject.failure_permille, 250);
+        assert_eq!(subject.penalty(), 2800);
+        subject.record_failure();
+        assert_eq!(subject.failure_permille, 437);
+        assert_eq!(subject.is_poor(), false);
+        subject.record_failure();
+        assert_eq!(subject.failure_permille, 577);
+        assert_eq!(subject.penalty(), 6070);
+        assert_eq!(subject.is_poor(), true);
+        subject.record_round_trip(300);
+        assert_eq!(subject.failure_permille, 432);
+        assert_eq!(subject.penalty(), 4620);
+        assert_eq!(subject.is_poor(), false);
+    }
+
     #[test]
     fn update_works_when_immutable_characteristics_dont_change() {
         let mut subject = make_node_record(1234, true);
@@ -964,9 +1113,9 @@ mod tests {
             node_addr_opt: None,
         };
 
-        let result = NodeRecordInner_0v1::try_from(corrupt_gnr);
+        let result = NodeRecordInner_0v2::try_from(corrupt_gnr);
 
-        assert_eq!(Err(String::from ("ErrorImpl { code: Message(\"invalid type: integer `1`, expected struct NodeRecordInner_0v1\"), offset: 0 }")), result);
+        assert_eq!(Err(String::from ("ErrorImpl { code: Message(\"invalid type: integer `1`, expected struct NodeRecordInner_0v2\"), offset: 0 }")), result);
     }
 
     #[test]
@@ -988,4 +1137,100 @@ mod tests {
         let final_serialized = serde_cbor::ser::to_vec(&subject.inner).unwrap();
         assert_eq!(&final_serialized[..], final_signed_gossip.as_slice());
     }
+
+    fn make_nri_0v1(node_record: &NodeRecord) -> NodeRecordInner_0v1 {
+        NodeRecordInner_0v1 {
+            public_key: node_record.inner.public_key.clone(),
+            earning_wallet: node_record.inner.earning_wallet.clone(),
+            rate_pack: node_record.inner.rate_pack.clone(),
+            neighbors: node_record.inner.neighbors.clone(),
+            accepts_connections: node_record.inner.accepts_connections,
+            routes_data: node_record.inner.routes_data,
+            version: node_record.inner.version,
+        }
+    }
+
+    fn without_capabilities(mut node_record: NodeRecord) -> NodeRecord {
+        node_record.inner.proxy_protocols_opt = None;
+        node_record.inner.masqueraders_opt = None;
+        node_record.inner.max_bandwidth_opt = None;
+        node_record.inner.software_version_opt = None;
+        node_record.inner.country_code_opt = None;
+        node_record
+    }
+
+    #[test]
+    fn new_node_record_advertises_what_this_node_knows_about_itself() {
+        let routing = make_node_record(1234, true);
+        let other = make_node_record(2345, true);
+
+        let non_routing = NodeRecord::new(
+            other.public_key(),
+            other.earning_wallet(),
+            other.rate_pack().clone(),
+            false,
+            false,
+            0,
+            &CryptDENull::from(other.public_key(), DEFAULT_CHAIN_ID),
+        );
+
+        assert_eq!(
+            routing.inner.proxy_protocols_opt,
+            Some(vec![ProxyProtocol::HTTP, ProxyProtocol::TLS])
+        );
+        assert_eq!(
+            routing.inner.software_version_opt,
+            Some(env!("CARGO_PKG_VERSION").to_string())
+        );
+        assert_eq!(non_routing.inner.proxy_protocols_opt, None);
+        assert_eq!(
+            non_routing.inner.software_version_opt,
+            Some(env!("CARGO_PKG_VERSION").to_string())
+        );
+    }
+
+    #[test]
+    fn nri_0v2_without_capabilities_serializes_exactly_like_nri_0v1() {
+        let node_record = without_capabilities(make_node_record(1234, true));
+        let nri_0v1 = make_nri_0v1(&node_record);
+
+        let serialized_0v2 = serde_cbor::ser::to_vec(&node_record.inner).unwrap();
+
+        let serialized_0v1 = serde_cbor::ser::to_vec(&nri_0v1).unwrap();
+        assert_eq!(serialized_0v2, serialized_0v1);
+    }
+
+    #[test]
+    fn nri_0v1_gossip_is_understood_as_nri_0v2_without_capabilities() {
+        let node_record = without_capabilities(make_node_record(1234, true));
+        let nri_0v1 = make_nri_0v1(&node_record);
+        let gnr = GossipNodeRecord {
+            signed_data: PlainData::from(serde_cbor::ser::to_vec(&nri_0v1).unwrap()),
+            signature: CryptData::new(&[]),
+            node_addr_opt: None,
+        };
+
+        let result = NodeRecordInner_0v2::try_from(gnr).unwrap();
+
+        assert_eq!(result, NodeRecordInner_0v2::from(nri_0v1));
+        assert_eq!(result, node_record.inner);
+    }
+
+    #[test]
+    fn nri_0v2_gossip_with_capabilities_is_understood_by_nodes_that_know_only_nri_0v1() {
+        let node_record = make_node_record(1234, true);
+        let mut nri_0v2 = node_record.inner.clone();
+        nri_0v2.proxy_protocols_opt = Some(vec![ProxyProtocol::HTTP, ProxyProtocol::TLS]);
+        nri_0v2.masqueraders_opt = Some(vec!["json".to_string()]);
+        nri_0v2.max_bandwidth_opt = Some(1_000_000);
+        nri_0v2.software_version_opt = Some("1.2.3".to_string());
+        nri_0v2.country_code_opt = Some("CZ".to_string());
+        let serialized = serde_cbor::ser::to_vec(&nri_0v2).unwrap();
+
+        let as_0v1: NodeRecordInner_0v1 = serde_cbor::de::from_slice(&serialized).unwrap();
+        let as_0v2: NodeRecordInner_0v2 = serde_cbor::de::from_slice(&serialized).unwrap();
+
+        assert_eq!(as_0v1, make_nri_0v1(&node_record));
+        assert_eq!(as_0v2, nri_0v2);
+    }
 }
@@ -4,9 +4,10 @@ use actix::{Actor, Context, Handler, Recipient};
 
 use masq_lib::messages::{
     FromMessageBody, ToMessageBody, UiChangePasswordRequest, UiChangePasswordResponse,
-    UiCheckPasswordRequest, UiCheckPasswordResponse, UiGenerateWalletsRequest,
-    UiGenerateWalletsResponse, UiNewPasswordBroadcast, UiRecoverWalletsRequest,
-    UiRecoverWalletsResponse, UiWalletAddressesRequest, UiWalletAddressesResponse,
+    UiCheckPasswordRequest, UiCheckPasswordResponse, UiDnsBypassRequest, UiDnsBypassResponse,
+    UiGenerateWalletsRequest, UiGenerateWalletsResponse, UiNewPasswordBroadcast,
+    UiRecoverWalletsRequest, UiRecoverWalletsResponse, UiWalletAddressesRequest,
+    UiWalletAddressesResponse,
 };
 use masq_lib::ui_gateway::MessageTarget::ClientId;
 use masq_lib::ui_gateway::{
@@ -20,6 +21,7 @@ use crate::db_config::config_dao::ConfigDaoReal;
 use crate::db_config::persistent_configuration::{
     PersistentConfiguration, PersistentConfigurationReal,
 };
+use crate::entry_dns::bypass::{DnsBypass, DNS_BYPASS};
 use crate::sub_lib::configurator::NewPasswordMessage;
 use crate::sub_lib::cryptde::PlainData;
 use crate::sub_lib::logger::Logger;
@@ -39,11 +41,13 @@ pub const ALREADY_INITIALIZED_ERROR: u64 = CONFIGURATOR_PREFIX | 7;
 pub const DERIVATION_PATH_ERROR: u64 = CONFIGURATOR_PREFIX | 8;
 pub const MNEMONIC_PHRASE_ERROR: u64 = CONFIGURATOR_PREFIX | 9;
 pub const EARLY_QUESTIONING_ABOUT_DATA: u64 = CONFIGURATOR_PREFIX | 10;
+pub const BAD_DNS_BYPASS_DOMAIN_ERROR: u64 = CONFIGURATOR_PREFIX | 11;
 
 pub struct Configurator {
     persistent_config: Box<dyn PersistentConfiguration>,
     node_to_ui_sub: Option<Recipient<NodeToUiMessage>>,
     new_password_subs: Option<Vec<Recipient<NewPasswordMessage>>>,
+    dns_bypass: DnsBypass,
     logger: Logger,
 }
 
@@ -119,6 +123,17 @@ impl Handler<NodeFromUiMessage> for Configurator {
                 "Sending response to walletAddresses command:\n{:?}", response
             );
             self.send_to_ui_gateway(ClientId(msg.client_id), response);
+        } else if let Ok((body, context_id)) = UiDnsBypassRequest::fmb(msg.clone().body) {
+            debug!(
+                &self.logger,
+                "Handling {} message from client {}", msg.body.opcode, msg.client_id
+            );
+            let response = self.handle_dns_bypass(body, context_id);
+            debug!(
+                &self.logger,
+                "Sending response to dnsBypass command:\n{:?}", response
+            );
+            self.send_to_ui_gateway(ClientId(msg.client_id), response);
         }
     }
 }
@@ -129,6 +144,7 @@ impl From<Box<dyn PersistentConfiguration>> for Configurator {
             persistent_config,
             node_to_ui_sub: None,
             new_password_subs: None,
+            dns_bypass: DNS_BYPASS.clone(),
             logger: Logger::new("Configurator"),
         }
     }
@@ -494,6 +510,23 @@ impl Configurator {
         Ok(())
     }
 
+    fn handle_dns_bypass(&self, msg: UiDnsBypassRequest, context_id: u64) -> MessageBody {
+        match self.dns_bypass.edit(&msg.add, &msg.remove) {
+            Ok(domains) => {
+                info!(
+                    self.logger,
+                    "DNS look-ups for these domains now bypass the MASQ Network: {:?}", domains
+                );
+                UiDnsBypassResponse { domains }.tmb(context_id)
+            }
+            Err(e) => MessageBody {
+                opcode: msg.opcode().to_string(),
+                path: MessagePath::Conversation(context_id),
+                payload: Err((BAD_DNS_BYPASS_DOMAIN_ERROR, e)),
+            },
+        }
+    }
+
     fn send_to_ui_gateway(&self, target: MessageTarget, body: MessageBody) {
         let msg = NodeToUiMessage { target, body };
         self.node_to_ui_sub
@@ -599,6 +632,74 @@ mod tests {
         assert_eq!(recording.len(), 0);
     }
 
+    #[test]
+    fn dns_bypass_edits_the_list_and_reports_it() {
+        let system = System::new("test");
+        let dns_bypass = DnsBypass::new();
+        dns_bypass.configure(&["lan".to_string()], &[]).unwrap();
+        let mut subject = make_subject(None);
+        subject.dns_bypass = dns_bypass.clone();
+        let subject_addr = subject.start();
+        let (ui_gateway, _, ui_gateway_recording_arc) = make_recorder();
+        let peer_actors = peer_actors_builder().ui_gateway(ui_gateway).build();
+        subject_addr.try_send(BindMessage { peer_actors }).unwrap();
+
+        subject_addr
+            .try_send(NodeFromUiMessage {
+                client_id: 1234,
+                body: UiDnsBypassRequest {
+                    add: vec!["corp.example.com".to_string()],
+                    remove: vec!["lan".to_string()],
+                }
+                .tmb(4321),
+            })
+            .unwrap();
+
+        System::current().stop();
+        system.run();
+        let ui_gateway_recording = ui_gateway_recording_arc.lock().unwrap();
+        assert_eq!(
+            ui_gateway_recording.get_record::<NodeToUiMessage>(0),
+            &NodeToUiMessage {
+                target: MessageTarget::ClientId(1234),
+                body: UiDnsBypassResponse {
+                    domains: vec!["corp.example.com".to_string()]
+                }
+                .tmb(4321)
+            }
+        );
+        assert_eq!(dns_bypass.bypasses("www.corp.example.com"), true);
+        assert_eq!(dns_bypass.bypasses("printer.lan"), false);
+    }
+
+    #[test]
+    fn dns_bypass_rejects_malformed_domains() {
+        let dns_bypass = DnsBypass::new();
+        let mut subject = make_subject(None);
+        subject.dns_bypass = dns_bypass.clone();
+
+        let result = subject.handle_dns_bypass(
+            UiDnsBypassRequest {
+                add: vec!["corp example".to_string()],
+                remove: vec![],
+            },
+            4321,
+        );
+
+        assert_eq!(
+            result,
+            MessageBody {
+                opcode: "dnsBypass".to_string(),
+                path: MessagePath::Conversation(4321),
+                payload: Err((
+                    BAD_DNS_BYPASS_DOMAIN_ERROR,
+                    "'corp example' is not a domain name".to_string()
+                )),
+            }
+        );
+        assert_eq!(dns_bypass.domains(), Vec::<String>::new());
+    }
+
     #[test]
     fn check_password_works() {
         let system = System::new("test");
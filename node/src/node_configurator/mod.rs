@@ -7,6 +7,9 @@ pub mod node_configurator_standard;
 use crate::blockchain::bip32::Bip32ECKeyPair;
 use crate::blockchain::bip39::Bip39;
 use crate::blockchain::blockchain_interface::chain_id_from_name;
+use crate::blockchain::chain_definitions::{
+    chain_definitions_path, load_chain_definitions, register_chain_definitions,
+};
 use crate::bootstrapper::RealUser;
 use crate::database::db_initializer::{DbInitializer, DbInitializerReal, DATABASE_FILE};
 use crate::db_config::persistent_configuration::{
@@ -33,7 +36,7 @@ use std::fmt::Debug;
 use std::io;
 use std::io::Read;
 use std::net::{SocketAddr, TcpListener};
-use std::path::PathBuf;
+use std::path::{Path, PathBuf};
 use std::str::FromStr;
 use tiny_hderive::bip44::DerivationPath;
 
@@ -298,10 +301,34 @@ pub fn prepare_initialization_mode<'a>(
         &data_directory_opt,
         &chain_name,
     );
-    let persistent_config_box = initialize_database(&directory, chain_id_from_name(&chain_name));
+    let chain_id = chain_id_from_context(&chain_name, &directory)?;
+    let persistent_config_box = initialize_database(&directory, chain_id);
     Ok((multi_config, persistent_config_box))
 }
 
+// Chains may be defined in the data directory, so an unknown name is only an error once its
+// definitions have been loaded.
+pub fn chain_id_from_context(
+    chain_name: &str,
+    data_directory: &Path,
+) -> Result<u8, ConfiguratorError> {
+    match load_chain_definitions(data_directory) {
+        Ok(definitions) => register_chain_definitions(definitions),
+        Err(e) => return Err(ConfiguratorError::required("chain", &e)),
+    }
+    match chain_id_from_name(chain_name) {
+        Ok(chain_id) => Ok(chain_id),
+        Err(_) => Err(ConfiguratorError::required(
+            "chain",
+            &format!(
+                "Unknown chain '{}': not built in, and not defined in {:?}",
+                chain_name,
+                chain_definitions_path(data_directory)
+            ),
+        )),
+    }
+}
+
 pub fn check_for_past_initialization(
     persistent_config: &dyn PersistentConfiguration,
 ) -> Result<(), ConfiguratorError> {
@@ -732,6 +759,7 @@ pub trait WalletCreationConfigMaker {
 mod tests {
     use super::*;
     use crate::blockchain::bip32::Bip32ECKeyPair;
+    use crate::blockchain::chain_definitions::CHAIN_DEFINITIONS_FILE;
     use crate::db_config::persistent_configuration::PersistentConfigError;
     use crate::masq_lib::utils::{
         DEFAULT_CONSUMING_DERIVATION_PATH, DEFAULT_EARNING_DERIVATION_PATH,
@@ -747,9 +775,11 @@ mod tests {
     use masq_lib::shared_schema::{db_password_arg, ParamError};
     use masq_lib::test_utils::environment_guard::EnvironmentGuard;
     use masq_lib::test_utils::fake_stream_holder::{ByteArrayWriter, FakeStreamHolder};
-    use masq_lib::test_utils::utils::TEST_DEFAULT_CHAIN_NAME;
+    use masq_lib::test_utils::utils::{ensure_node_home_directory_exists, TEST_DEFAULT_CHAIN_NAME};
     use masq_lib::utils::{find_free_port, running_test};
+    use std::fs::File;
     use std::io::Cursor;
+    use std::io::Write;
     use std::net::{SocketAddr, TcpListener};
     use std::sync::{Arc, Mutex};
     use tiny_hderive::bip44::DerivationPath;
@@ -1000,6 +1030,74 @@ mod tests {
         );
     }
 
+    #[test]
+    fn prepare_initialization_mode_rejects_misspelled_chain_without_creating_database() {
+        let data_dir = ensure_node_home_directory_exists(
+            "node_configurator",
+            "prepare_initialization_mode_rejects_misspelled_chain_without_creating_database",
+        );
+        let args: Vec<String> = ArgsBuilder::new()
+            .param("--data-directory", data_dir.to_str().unwrap())
+            .param("--chain", "mainet")
+            .into();
+        let app = app();
+
+        let result = prepare_initialization_mode(
+            &RealDirsWrapper {},
+            &app,
+            &args,
+            &mut FakeStreamHolder::new().streams(),
+        );
+
+        match result {
+            Err(e) => assert_eq!(
+                e,
+                ConfiguratorError::required(
+                    "chain",
+                    &format!(
+                        "Unknown chain 'mainet': not built in, and not defined in {:?}",
+                        data_dir.join(CHAIN_DEFINITIONS_FILE)
+                    )
+                )
+            ),
+            Ok(_) => panic!("Expected an unknown-chain error"),
+        }
+        assert_eq!(data_dir.join(DATABASE_FILE).exists(), false);
+    }
+
+    #[test]
+    fn prepare_initialization_mode_uses_chain_defined_in_data_directory() {
+        let data_dir = ensure_node_home_directory_exists(
+            "node_configurator",
+            "prepare_initialization_mode_uses_chain_defined_in_data_directory",
+        );
+        File::create(data_dir.join(CHAIN_DEFINITIONS_FILE))
+            .unwrap()
+            .write_all(
+                b"[[chain]]\n\
+                  name = \"initialization-devnet\"\n\
+                  chain-id = 48\n\
+                  contract-address = \"0x0123456789012345678901234567890123456789\"\n\
+                  contract-creation-block = 4321\n",
+            )
+            .unwrap();
+        let args: Vec<String> = ArgsBuilder::new()
+            .param("--data-directory", data_dir.to_str().unwrap())
+            .param("--chain", "initialization-devnet")
+            .into();
+        let app = app();
+
+        let (_, persistent_config) = prepare_initialization_mode(
+            &RealDirsWrapper {},
+            &app,
+            &args,
+            &mut FakeStreamHolder::new().streams(),
+        )
+        .unwrap();
+
+        assert_eq!(persistent_config.start_block(), Ok(Some(4321)));
+    }
+
     #[test]
     fn real_user_data_directory_and_chain_id_picks_correct_directory_for_default_chain() {
         let args = ArgsBuilder::new();
@@ -107,6 +107,11 @@ const HELP_TEXT: &str = indoc!(
 
         MASQNode --help --dump-config
 
+    If you want systemd unit files that run the Node (or, with --initialization, the
+    Daemon) as a service with the rest of your command line, try:
+
+        MASQNode --generate-systemd-unit [your usual parameters]
+
     MASQ Node listens for connections from other Nodes using the computer's
     network interface. Configuring the internet router for port forwarding is a necessary
     step for Node users to permit network communication between Nodes.
@@ -139,15 +144,17 @@ pub mod standard {
     use clap::value_t;
     use log::LevelFilter;
 
+    use crate::accountant::{PaymentCurves, DEFAULT_PAYMENT_CURVES};
     use crate::blockchain::bip32::Bip32ECKeyPair;
-    use crate::blockchain::blockchain_interface::chain_id_from_name;
+    use crate::blockchain::blockchain_interface::{chain_id_from_name, is_mainnet_chain};
     use crate::bootstrapper::PortConfiguration;
     use crate::db_config::persistent_configuration::{
         PersistentConfigError, PersistentConfiguration,
     };
+    use crate::entry_dns::bypass::OtherRecordsPolicy;
     use crate::http_request_start_finder::HttpRequestDiscriminatorFactory;
     use crate::node_configurator::{
-        data_directory_from_context, determine_config_file_path,
+        chain_id_from_context, data_directory_from_context, determine_config_file_path,
         real_user_data_directory_opt_and_chain_name, request_existing_db_password, DirsWrapper,
     };
     use crate::sub_lib::accountant::DEFAULT_EARNING_WALLET;
@@ -155,7 +162,8 @@ pub mod standard {
     use crate::sub_lib::cryptde_null::CryptDENull;
     use crate::sub_lib::cryptde_real::CryptDEReal;
     use crate::sub_lib::neighborhood::{
-        NeighborhoodConfig, NeighborhoodMode, NodeDescriptor, DEFAULT_RATE_PACK,
+        BootstrapSource, DebutApproval, NeighborhoodConfig, NeighborhoodMode, NodeDescriptor,
+        DEFAULT_RATE_PACK,
     };
     use crate::sub_lib::node_addr::NodeAddr;
     use crate::sub_lib::utils::make_new_multi_config;
@@ -165,6 +173,7 @@ pub mod standard {
     use masq_lib::constants::{
         DEFAULT_CHAIN_NAME, DEFAULT_GAS_PRICE, DEFAULT_UI_PORT, HTTP_PORT, TLS_PORT,
     };
+    use masq_lib::exit_country::ExitCountryPreference;
     use masq_lib::multi_config::{CommandLineVcl, ConfigFileVcl, EnvironmentVcl, MultiConfig};
     use masq_lib::shared_schema::{ConfiguratorError, ParamError};
     use masq_lib::test_utils::utils::DEFAULT_CHAIN_ID;
@@ -228,9 +237,10 @@ pub mod standard {
             real_user_data_directory_opt_and_chain_name(dirs_wrapper, &multi_config);
         let directory =
             data_directory_from_context(dirs_wrapper, &real_user, &data_directory_opt, &chain_name);
+        privileged_config.blockchain_bridge_config.chain_id =
+            chain_id_from_context(&chain_name, &directory)?;
         privileged_config.real_user = real_user;
         privileged_config.data_directory = directory;
-        privileged_config.blockchain_bridge_config.chain_id = chain_id_from_name(&chain_name);
 
         let joined_dns_servers_opt = value_m!(multi_config, "dns-servers", String);
         privileged_config.dns_servers = match joined_dns_servers_opt {
@@ -246,6 +256,31 @@ pub mod standard {
             None => vec![SocketAddr::new(IpAddr::V4(Ipv4Addr::new(1, 1, 1, 1)), 53)],
         };
 
+        privileged_config.dns_bypass_domains = match value_m!(multi_config, "dns-bypass", String) {
+            Some(joined_domains) => joined_domains
+                .split(',')
+                .map(|domain| domain.to_string())
+                .collect(),
+            None => vec![],
+        };
+        privileged_config.dns_bypass_servers =
+            match value_m!(multi_config, "dns-bypass-servers", String) {
+                Some(joined_dns_bypass_servers) => joined_dns_bypass_servers
+                    .split(',')
+                    .map(|ip_str| {
+                        SocketAddr::new(
+                            IpAddr::from_str(ip_str)
+                                .expect("Bad clap validation for dns-bypass-servers"),
+                            53,
+                        )
+                    })
+                    .collect(),
+                None => privileged_config.dns_servers.clone(),
+            };
+        privileged_config.dns_other_records =
+            value_m!(multi_config, "dns-other-records", OtherRecordsPolicy)
+                .unwrap_or(OtherRecordsPolicy::Empty);
+
         privileged_config.log_level =
             value_m!(multi_config, "log-level", LevelFilter).unwrap_or(LevelFilter::Warn);
 
@@ -255,6 +290,14 @@ pub mod standard {
         privileged_config.crash_point =
             value_m!(multi_config, "crash-point", CrashPoint).unwrap_or(CrashPoint::None);
 
+        privileged_config.debut_approval = value_m!(multi_config, "debut-approval", DebutApproval)
+            .unwrap_or(DebutApproval::Automatic);
+
+        privileged_config.exit_country_opt =
+            value_m!(multi_config, "exit-country", ExitCountryPreference);
+
+        privileged_config.bootstrap_source_opt = get_bootstrap_source(multi_config)?;
+
         match value_m!(multi_config, "fake-public-key", String) {
             None => (),
             Some(public_key_str) => {
@@ -304,6 +347,21 @@ pub mod standard {
                 None => 1,
             }
         };
+        unprivileged_config.accountant_config.payment_curves =
+            match value_m!(multi_config, "payment-curves", String) {
+                Some(string) => match PaymentCurves::from_str(&string) {
+                    Ok(payment_curves) => payment_curves,
+                    Err(e) => return Err(ConfiguratorError::required("payment-curves", &e)),
+                },
+                None => match persistent_config_opt {
+                    Some(ref persistent_config) => match persistent_config.payment_curves() {
+                        Ok(Some(payment_curves)) => payment_curves,
+                        Ok(None) => DEFAULT_PAYMENT_CURVES.clone(),
+                        Err(pce) => return Err(pce.into_configurator_error("payment-curves")),
+                    },
+                    None => DEFAULT_PAYMENT_CURVES.clone(),
+                },
+            };
         let mnc_result = if let Some(persistent_config) = persistent_config_opt {
             get_wallets(
                 streams,
@@ -343,6 +401,12 @@ pub mod standard {
         {
             return Err(pce.into_configurator_error("gas-price"));
         }
+
+        if let Err(pce) =
+            persistent_config.set_payment_curves(&config.accountant_config.payment_curves)
+        {
+            return Err(pce.into_configurator_error("payment-curves"));
+        }
         Ok(())
     }
 
@@ -458,11 +522,15 @@ pub mod standard {
                     };
                     let chain_name = value_m!(multi_config, "chain", String)
                         .unwrap_or_else(|| DEFAULT_CHAIN_NAME.to_string());
+                    let mainnet_chain = match chain_id_from_name(&chain_name) {
+                        Ok(chain_id) => is_mainnet_chain(chain_id),
+                        Err(e) => return Err(ConfiguratorError::required("chain", &e)),
+                    };
                     let results = cli_configs
                         .into_iter()
                         .map(
                             |s| match NodeDescriptor::from_str(dummy_cryptde.as_ref(), &s) {
-                                Ok(nd) => if chain_name == DEFAULT_CHAIN_NAME {
+                                Ok(nd) => if mainnet_chain {
                                     if nd.mainnet {
                                         Ok(nd)
                                     }
@@ -540,6 +608,40 @@ pub mod standard {
         )
     }
 
+    fn get_bootstrap_source(
+        multi_config: &MultiConfig,
+    ) -> Result<Option<BootstrapSource>, ConfiguratorError> {
+        let public_key_opt = match value_m!(multi_config, "bootstrap-public-key", String) {
+            None => None,
+            Some(public_key_str) => match base64::decode_config(
+                public_key_str.trim_end_matches('='),
+                base64::STANDARD_NO_PAD,
+            ) {
+                Ok(key) => Some(PublicKey::new(&key)),
+                Err(_) => {
+                    return Err(ConfiguratorError::required(
+                        "bootstrap-public-key",
+                        &format!("Not a valid base64 public key: {}", public_key_str),
+                    ))
+                }
+            },
+        };
+        match (
+            value_m!(multi_config, "bootstrap-source", String),
+            public_key_opt,
+        ) {
+            (Some(location), public_key_opt) => Ok(Some(BootstrapSource {
+                location,
+                public_key_opt,
+            })),
+            (None, None) => Ok(None),
+            (None, Some(_)) => Err(ConfiguratorError::required(
+                "bootstrap-public-key",
+                "Cannot use --bootstrap-public-key without --bootstrap-source",
+            )),
+        }
+    }
+
     fn make_neighborhood_mode(
         multi_config: &MultiConfig,
         neighbor_configs: Vec<NodeDescriptor>,
@@ -851,6 +953,22 @@ pub mod standard {
             )
         }
 
+        #[test]
+        fn configure_database_handles_error_during_setting_payment_curves() {
+            let config = BootstrapperConfig::new();
+            let mut persistent_config = PersistentConfigurationMock::new()
+                .set_gas_price_result(Ok(()))
+                .set_payment_curves_result(Err(PersistentConfigError::TransactionError));
+
+            let result = configure_database(&config, &mut persistent_config);
+
+            assert_eq!(
+                result,
+                Err(PersistentConfigError::TransactionError
+                    .into_configurator_error("payment-curves"))
+            )
+        }
+
         #[test]
         fn get_earning_wallet_from_address_handles_error_retrieving_earning_wallet_from_address() {
             let args = ArgsBuilder::new().param(
@@ -1089,10 +1207,12 @@ pub mod standard {
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::accountant::PaymentCurves;
     use crate::blockchain::bip32::Bip32ECKeyPair;
     use crate::blockchain::blockchain_interface::{
         chain_id_from_name, chain_name_from_id, contract_address,
     };
+    use crate::blockchain::chain_definitions::CHAIN_DEFINITIONS_FILE;
     use crate::bootstrapper::RealUser;
     use crate::database::db_initializer::{DbInitializer, DbInitializerReal};
     use crate::db_config::config_dao::{ConfigDao, ConfigDaoReal};
@@ -1100,13 +1220,15 @@ mod tests {
     use crate::db_config::persistent_configuration::{
         PersistentConfigError, PersistentConfigurationReal,
     };
+    use crate::entry_dns::bypass::OtherRecordsPolicy;
     use crate::node_configurator::RealDirsWrapper;
     use crate::sub_lib::accountant::DEFAULT_EARNING_WALLET;
     use crate::sub_lib::cryptde::{CryptDE, PlainData, PublicKey};
     use crate::sub_lib::cryptde_null::CryptDENull;
     use crate::sub_lib::cryptde_real::CryptDEReal;
     use crate::sub_lib::neighborhood::{
-        NeighborhoodConfig, NeighborhoodMode, NodeDescriptor, DEFAULT_RATE_PACK,
+        BootstrapSource, DebutApproval, NeighborhoodConfig, NeighborhoodMode, NodeDescriptor,
+        DEFAULT_RATE_PACK,
     };
     use crate::sub_lib::node_addr::NodeAddr;
     use crate::sub_lib::utils::make_new_test_multi_config;
@@ -1116,6 +1238,7 @@ mod tests {
     use crate::test_utils::persistent_configuration_mock::PersistentConfigurationMock;
     use crate::test_utils::{assert_string_contains, main_cryptde, ArgsBuilder};
     use masq_lib::constants::{DEFAULT_CHAIN_NAME, DEFAULT_GAS_PRICE, DEFAULT_UI_PORT};
+    use masq_lib::exit_country::ExitCountryPreference;
     use masq_lib::multi_config::{
         CommandLineVcl, ConfigFileVcl, NameValueVclArg, VclArg, VirtualCommandLine,
     };
@@ -1997,6 +2120,78 @@ mod tests {
         );
     }
 
+    #[test]
+    fn privileged_parse_args_recognizes_chain_defined_in_data_directory() {
+        running_test();
+        let home_dir = ensure_node_home_directory_exists(
+            "node_configurator_standard",
+            "privileged_parse_args_recognizes_chain_defined_in_data_directory",
+        );
+        let mut file = File::create(home_dir.join(CHAIN_DEFINITIONS_FILE)).unwrap();
+        file.write_all(
+            b"[[chain]]\n\
+              name = \"configurator-chain\"\n\
+              chain-id = 45\n\
+              contract-address = \"0x0123456789012345678901234567890123456789\"\n\
+              contract-creation-block = 1234\n",
+        )
+        .unwrap();
+        let args = ArgsBuilder::new()
+            .param("--ip", "1.2.3.4")
+            .param("--data-directory", home_dir.to_str().unwrap())
+            .param("--chain", "configurator-chain");
+        let mut config = BootstrapperConfig::new();
+        let vcls: Vec<Box<dyn VirtualCommandLine>> =
+            vec![Box::new(CommandLineVcl::new(args.into()))];
+        let multi_config = make_new_test_multi_config(&app(), vcls).unwrap();
+
+        standard::privileged_parse_args(
+            &RealDirsWrapper {},
+            &multi_config,
+            &mut config,
+            &mut FakeStreamHolder::new().streams(),
+        )
+        .unwrap();
+
+        assert_eq!(config.blockchain_bridge_config.chain_id, 45);
+        assert_eq!(chain_name_from_id(45), "configurator-chain");
+    }
+
+    #[test]
+    fn privileged_parse_args_rejects_unknown_chain() {
+        running_test();
+        let home_dir = ensure_node_home_directory_exists(
+            "node_configurator_standard",
+            "privileged_parse_args_rejects_unknown_chain",
+        );
+        let args = ArgsBuilder::new()
+            .param("--ip", "1.2.3.4")
+            .param("--data-directory", home_dir.to_str().unwrap())
+            .param("--chain", "nonexistent-chain");
+        let mut config = BootstrapperConfig::new();
+        let vcls: Vec<Box<dyn VirtualCommandLine>> =
+            vec![Box::new(CommandLineVcl::new(args.into()))];
+        let multi_config = make_new_test_multi_config(&app(), vcls).unwrap();
+
+        let result = standard::privileged_parse_args(
+            &RealDirsWrapper {},
+            &multi_config,
+            &mut config,
+            &mut FakeStreamHolder::new().streams(),
+        );
+
+        assert_eq!(
+            result,
+            Err(ConfiguratorError::required(
+                "chain",
+                &format!(
+                    "Unknown chain 'nonexistent-chain': not built in, and not defined in {:?}",
+                    home_dir.join(CHAIN_DEFINITIONS_FILE)
+                )
+            ))
+        );
+    }
+
     #[test]
     #[cfg(not(target_os = "windows"))]
     fn privileged_parse_args_with_real_user_defaults_data_directory_properly() {
@@ -2621,6 +2816,216 @@ mod tests {
         assert_eq!(config.crash_point, CrashPoint::Panic);
     }
 
+    #[test]
+    fn no_parameters_produces_configuration_for_debut_approval() {
+        running_test();
+        let args = make_default_cli_params();
+        let mut config = BootstrapperConfig::new();
+        let vcl = Box::new(CommandLineVcl::new(args.into()));
+        let multi_config = make_new_test_multi_config(&app(), vec![vcl]).unwrap();
+
+        standard::privileged_parse_args(
+            &RealDirsWrapper {},
+            &multi_config,
+            &mut config,
+            &mut FakeStreamHolder::new().streams(),
+        )
+        .unwrap();
+
+        assert_eq!(config.debut_approval, DebutApproval::Automatic);
+    }
+
+    #[test]
+    fn with_parameters_produces_configuration_for_debut_approval() {
+        running_test();
+        let args = make_default_cli_params().param("--debut-approval", "manual");
+        let mut config = BootstrapperConfig::new();
+        let vcl = Box::new(CommandLineVcl::new(args.into()));
+        let multi_config = make_new_test_multi_config(&app(), vec![vcl]).unwrap();
+
+        standard::privileged_parse_args(
+            &RealDirsWrapper {},
+            &multi_config,
+            &mut config,
+            &mut FakeStreamHolder::new().streams(),
+        )
+        .unwrap();
+
+        assert_eq!(config.debut_approval, DebutApproval::Manual);
+    }
+
+    #[test]
+    fn no_parameters_produces_configuration_for_dns_bypass() {
+        running_test();
+        let args = make_default_cli_params().param("--dns-servers", "12.34.56.78");
+        let mut config = BootstrapperConfig::new();
+        let vcl = Box::new(CommandLineVcl::new(args.into()));
+        let multi_config = make_new_test_multi_config(&app(), vec![vcl]).unwrap();
+
+        standard::privileged_parse_args(
+            &RealDirsWrapper {},
+            &multi_config,
+            &mut config,
+            &mut FakeStreamHolder::new().streams(),
+        )
+        .unwrap();
+
+        assert_eq!(config.dns_bypass_domains, Vec::<String>::new());
+        assert_eq!(
+            config.dns_bypass_servers,
+            vec![SocketAddr::from_str("12.34.56.78:53").unwrap()]
+        );
+        assert_eq!(config.dns_other_records, OtherRecordsPolicy::Empty);
+    }
+
+    #[test]
+    fn with_parameters_produces_configuration_for_dns_bypass() {
+        running_test();
+        let args = make_default_cli_params()
+            .param("--dns-servers", "12.34.56.78")
+            .param("--dns-bypass", "corp.example.com,*.lan")
+            .param("--dns-bypass-servers", "192.168.1.1,10.0.0.1")
+            .param("--dns-other-records", "Forward");
+        let mut config = BootstrapperConfig::new();
+        let vcl = Box::new(CommandLineVcl::new(args.into()));
+        let multi_config = make_new_test_multi_config(&app(), vec![vcl]).unwrap();
+
+        standard::privileged_parse_args(
+            &RealDirsWrapper {},
+            &multi_config,
+            &mut config,
+            &mut FakeStreamHolder::new().streams(),
+        )
+        .unwrap();
+
+        assert_eq!(
+            config.dns_bypass_domains,
+            vec!["corp.example.com".to_string(), "*.lan".to_string()]
+        );
+        assert_eq!(
+            config.dns_bypass_servers,
+            vec![
+                SocketAddr::from_str("192.168.1.1:53").unwrap(),
+                SocketAddr::from_str("10.0.0.1:53").unwrap()
+            ]
+        );
+        assert_eq!(config.dns_other_records, OtherRecordsPolicy::Forward);
+    }
+
+    #[test]
+    fn no_parameters_produces_configuration_for_exit_country() {
+        running_test();
+        let args = make_default_cli_params();
+        let mut config = BootstrapperConfig::new();
+        let vcl = Box::new(CommandLineVcl::new(args.into()));
+        let multi_config = make_new_test_multi_config(&app(), vec![vcl]).unwrap();
+
+        standard::privileged_parse_args(
+            &RealDirsWrapper {},
+            &multi_config,
+            &mut config,
+            &mut FakeStreamHolder::new().streams(),
+        )
+        .unwrap();
+
+        assert_eq!(config.exit_country_opt, None);
+    }
+
+    #[test]
+    fn with_parameters_produces_configuration_for_exit_country() {
+        running_test();
+        let args = make_default_cli_params().param("--exit-country", "prefer:cz,SK");
+        let mut config = BootstrapperConfig::new();
+        let vcl = Box::new(CommandLineVcl::new(args.into()));
+        let multi_config = make_new_test_multi_config(&app(), vec![vcl]).unwrap();
+
+        standard::privileged_parse_args(
+            &RealDirsWrapper {},
+            &multi_config,
+            &mut config,
+            &mut FakeStreamHolder::new().streams(),
+        )
+        .unwrap();
+
+        assert_eq!(
+            config.exit_country_opt,
+            Some(ExitCountryPreference {
+                countries: vec!["CZ".to_string(), "SK".to_string()],
+                strict: false,
+            })
+        );
+    }
+
+    #[test]
+    fn no_parameters_produces_configuration_for_bootstrap_source() {
+        running_test();
+        let args = make_default_cli_params();
+        let mut config = BootstrapperConfig::new();
+        let vcl = Box::new(CommandLineVcl::new(args.into()));
+        let multi_config = make_new_test_multi_config(&app(), vec![vcl]).unwrap();
+
+        standard::privileged_parse_args(
+            &RealDirsWrapper {},
+            &multi_config,
+            &mut config,
+            &mut FakeStreamHolder::new().streams(),
+        )
+        .unwrap();
+
+        assert_eq!(config.bootstrap_source_opt, None);
+    }
+
+    #[test]
+    fn with_parameters_produces_configuration_for_bootstrap_source() {
+        running_test();
+        let args = make_default_cli_params()
+            .param("--bootstrap-source", "https://example.com/bootstrap.txt")
+            .param("--bootstrap-public-key", "AQIDBA==");
+        let mut config = BootstrapperConfig::new();
+        let vcl = Box::new(CommandLineVcl::new(args.into()));
+        let multi_config = make_new_test_multi_config(&app(), vec![vcl]).unwrap();
+
+        standard::privileged_parse_args(
+            &RealDirsWrapper {},
+            &multi_config,
+            &mut config,
+            &mut FakeStreamHolder::new().streams(),
+        )
+        .unwrap();
+
+        assert_eq!(
+            config.bootstrap_source_opt,
+            Some(BootstrapSource {
+                location: "https://example.com/bootstrap.txt".to_string(),
+                public_key_opt: Some(PublicKey::new(&[1, 2, 3, 4])),
+            })
+        );
+    }
+
+    #[test]
+    fn bootstrap_public_key_without_bootstrap_source_is_rejected() {
+        running_test();
+        let args = make_default_cli_params().param("--bootstrap-public-key", "AQIDBA");
+        let mut config = BootstrapperConfig::new();
+        let vcl = Box::new(CommandLineVcl::new(args.into()));
+        let multi_config = make_new_test_multi_config(&app(), vec![vcl]).unwrap();
+
+        let result = standard::privileged_parse_args(
+            &RealDirsWrapper {},
+            &multi_config,
+            &mut config,
+            &mut FakeStreamHolder::new().streams(),
+        );
+
+        assert_eq!(
+            result,
+            Err(ConfiguratorError::required(
+                "bootstrap-public-key",
+                "Cannot use --bootstrap-public-key without --bootstrap-source"
+            ))
+        );
+    }
+
     #[test]
     fn privileged_generate_configuration_senses_when_user_specifies_config_file() {
         running_test();
@@ -2689,7 +3094,7 @@ mod tests {
 
         assert_eq!(
             config.blockchain_bridge_config.chain_id,
-            chain_id_from_name("dev")
+            chain_id_from_name("dev").unwrap()
         );
     }
 
@@ -2708,7 +3113,7 @@ mod tests {
 
         assert_eq!(
             config.blockchain_bridge_config.chain_id,
-            chain_id_from_name(TEST_DEFAULT_CHAIN_NAME)
+            chain_id_from_name(TEST_DEFAULT_CHAIN_NAME).unwrap()
         );
     }
 
@@ -2744,7 +3149,7 @@ mod tests {
             .unwrap();
         assert_eq!(
             bootstrapper_config.blockchain_bridge_config.chain_id,
-            chain_id_from_name(TEST_DEFAULT_CHAIN_NAME)
+            chain_id_from_name(TEST_DEFAULT_CHAIN_NAME).unwrap()
         );
     }
 
@@ -2792,6 +3197,87 @@ mod tests {
         assert_eq!(config.blockchain_bridge_config.gas_price, 1);
     }
 
+    #[test]
+    fn unprivileged_configuration_gets_parameter_payment_curves() {
+        running_test();
+        let _clap_guard = ClapGuard::new();
+        let data_dir = ensure_node_home_directory_exists(
+            "node_configurator_standard",
+            "unprivileged_configuration_gets_parameter_payment_curves",
+        );
+        let mut subject = NodeConfiguratorStandardUnprivileged::new(&BootstrapperConfig::new());
+        subject.privileged_config = BootstrapperConfig::new();
+        subject.privileged_config.data_directory = data_dir;
+        let args = ArgsBuilder::new()
+            .param("--ip", "1.2.3.4")
+            .param("--payment-curves", "1000|2000|3000|4000|5000|2500");
+        let args_vec: Vec<String> = args.into();
+
+        let config = subject
+            .configure(args_vec.as_slice(), &mut FakeStreamHolder::new().streams())
+            .unwrap();
+
+        assert_eq!(
+            config.accountant_config.payment_curves,
+            PaymentCurves::from_str("1000|2000|3000|4000|5000|2500").unwrap()
+        );
+    }
+
+    #[test]
+    fn unprivileged_parse_args_rejects_inconsistent_payment_curves() {
+        let multi_config = test_utils::make_multi_config(
+            ArgsBuilder::new()
+                .param("--ip", "1.2.3.4")
+                .param("--payment-curves", "5000|2000|3000|4000|5000|2500"),
+        );
+        let mut unprivileged_config = BootstrapperConfig::new();
+        let mut holder = FakeStreamHolder::new();
+
+        let result = standard::unprivileged_parse_args(
+            &multi_config,
+            &mut unprivileged_config,
+            &mut holder.streams(),
+            None,
+        );
+
+        assert_eq!(
+            result,
+            Err(ConfiguratorError::required(
+                "payment-curves",
+                "Balance decrease period (5000 sec) must be longer than payment suggestion period (5000 sec)"
+            ))
+        );
+    }
+
+    #[test]
+    fn unprivileged_parse_args_uses_stored_payment_curves_when_not_specified() {
+        let multi_config =
+            test_utils::make_multi_config(ArgsBuilder::new().param("--ip", "1.2.3.4"));
+        let mut unprivileged_config = BootstrapperConfig::new();
+        let mut holder = FakeStreamHolder::new();
+        let payment_curves = PaymentCurves::from_str("1000|2000|3000|4000|5000|2500").unwrap();
+        let mut persistent_config = PersistentConfigurationMock::new()
+            .gas_price_result(Ok(None))
+            .payment_curves_result(Ok(Some(payment_curves.clone())))
+            .earning_wallet_from_address_result(Ok(Some(Wallet::new(
+                "0x0123456789012345678901234567890123456789",
+            ))))
+            .mnemonic_seed_exists_result(Ok(false));
+
+        standard::unprivileged_parse_args(
+            &multi_config,
+            &mut unprivileged_config,
+            &mut holder.streams(),
+            Some(&mut persistent_config),
+        )
+        .unwrap();
+
+        assert_eq!(
+            unprivileged_config.accountant_config.payment_curves,
+            payment_curves
+        );
+    }
+
     #[test]
     fn privileged_configuration_rejects_invalid_gas_price() {
         running_test();
@@ -2825,15 +3311,20 @@ mod tests {
         config.earning_wallet = Wallet::new(earning_address);
         config.consuming_wallet = Some(Wallet::from(keypair));
         config.blockchain_bridge_config.gas_price = gas_price;
+        let payment_curves = PaymentCurves::from_str("1000|2000|3000|4000|5000|2500").unwrap();
+        config.accountant_config.payment_curves = payment_curves.clone();
         let set_clandestine_port_params_arc = Arc::new(Mutex::new(vec![]));
         let set_gas_price_params_arc = Arc::new(Mutex::new(vec![]));
+        let set_payment_curves_params_arc = Arc::new(Mutex::new(vec![]));
         let mut persistent_config = PersistentConfigurationMock::new()
             .earning_wallet_address_result(Ok(None))
             .consuming_wallet_derivation_path_result(Ok(None))
             .set_clandestine_port_params(&set_clandestine_port_params_arc)
             .set_clandestine_port_result(Ok(()))
             .set_gas_price_params(&set_gas_price_params_arc)
-            .set_gas_price_result(Ok(()));
+            .set_gas_price_result(Ok(()))
+            .set_payment_curves_params(&set_payment_curves_params_arc)
+            .set_payment_curves_result(Ok(()));
 
         let result = standard::configure_database(&config, &mut persistent_config);
 
@@ -2842,6 +3333,8 @@ mod tests {
         assert_eq!(*set_clandestine_port_params, vec![1234]);
         let set_gas_price_params = set_gas_price_params_arc.lock().unwrap();
         assert_eq!(*set_gas_price_params, vec![gas_price]);
+        let set_payment_curves_params = set_payment_curves_params_arc.lock().unwrap();
+        assert_eq!(*set_payment_curves_params, vec![payment_curves]);
     }
 
     #[test]
@@ -22,6 +22,7 @@ use crate::sub_lib::dispatcher::{Endpoint, StreamShutdownMsg};
 use crate::sub_lib::hopper::{ExpiredCoresPackage, IncipientCoresPackage};
 use crate::sub_lib::logger::Logger;
 use crate::sub_lib::neighborhood::RatePack;
+use crate::sub_lib::neighborhood::RemoveNeighborMessage;
 use crate::sub_lib::neighborhood::RouteQueryMessage;
 use crate::sub_lib::neighborhood::RouteQueryResponse;
 use crate::sub_lib::neighborhood::{ExpectedService, NodeRecordMetadataMessage};
@@ -29,9 +30,10 @@ use crate::sub_lib::neighborhood::{ExpectedServices, DEFAULT_RATE_PACK};
 use crate::sub_lib::peer_actors::BindMessage;
 use crate::sub_lib::proxy_client::{ClientResponsePayload_0v1, DnsResolveFailure_0v1};
 use crate::sub_lib::proxy_server::ClientRequestPayload_0v1;
+use crate::sub_lib::proxy_server::ProxyProtocol;
 use crate::sub_lib::proxy_server::ProxyServerSubs;
 use crate::sub_lib::proxy_server::{
-    AddReturnRouteMessage, AddRouteMessage, DEFAULT_MINIMUM_HOP_COUNT,
+    AddReturnRouteMessage, AddRouteMessage, SetExitCountryMessage, DEFAULT_MINIMUM_HOP_COUNT,
 };
 use crate::sub_lib::route::Route;
 use crate::sub_lib::set_consuming_wallet_message::SetConsumingWalletMessage;
@@ -49,11 +51,15 @@ use pretty_hex::PrettyHex;
 use std::collections::HashMap;
 use std::net::SocketAddr;
 use std::rc::Rc;
-use std::time::Duration;
+use std::time::{Duration, Instant};
 use tokio::prelude::Future;
 
 pub const CRASH_KEY: &str = "PROXYSERVER";
 pub const RETURN_ROUTE_TTL: Duration = Duration::from_secs(120);
+pub const ROUTE_RESPONSE_TIMEOUT: Duration = Duration::from_secs(20);
+pub const ROUTE_FAILOVER_RETRY_LIMIT: usize = 2;
+pub const MAX_FAILOVER_BUFFER_BYTES: usize = 65_536;
+const IDEMPOTENT_HTTP_METHODS: [&str; 3] = ["GET", "HEAD", "OPTIONS"];
 
 struct ProxyServerOutSubs {
     dispatcher: Recipient<TransmitDataMsg>,
@@ -74,6 +80,12 @@ pub struct ProxyServer {
     keys_and_addrs: BidiHashMap<StreamKey, SocketAddr>,
     tunneled_hosts: HashMap<StreamKey, String>,
     stream_key_routes: HashMap<StreamKey, RouteQueryResponse>,
+    stream_key_first_request_times: HashMap<StreamKey, Instant>,
+    stream_key_unacked_payloads: HashMap<StreamKey, Vec<ClientRequestPayload_0v1>>,
+    stream_key_failover_counts: HashMap<StreamKey, usize>,
+    // Return-route IDs of the routes a stream has been failed over from; whatever still comes
+    // back over them is stale.
+    stream_key_replaced_route_ids: HashMap<StreamKey, Vec<u32>>,
     is_decentralized: bool,
     consuming_wallet_balance: Option<i64>,
     main_cryptde: &'static dyn CryptDE,
@@ -81,6 +93,7 @@ pub struct ProxyServer {
     logger: Logger,
     route_ids_to_return_routes: TtlHashMap<u32, AddReturnRouteMessage>,
     browser_proxy_sequence_offset: bool,
+    required_exit_countries_opt: Option<Vec<String>>,
 }
 
 impl Actor for ProxyServer {
@@ -104,6 +117,9 @@ impl Handler<BindMessage> for ProxyServer {
             stream_shutdown_sub: msg.peer_actors.proxy_server.stream_shutdown_sub,
         };
         self.subs = Some(subs);
+        ctx.run_interval(ROUTE_RESPONSE_TIMEOUT / 4, |proxy_server, _ctx| {
+            proxy_server.fail_over_unanswered_streams(Instant::now())
+        });
     }
 }
 
@@ -119,6 +135,17 @@ impl Handler<SetConsumingWalletMessage> for ProxyServer {
     }
 }
 
+impl Handler<SetExitCountryMessage> for ProxyServer {
+    type Result = ();
+
+    fn handle(&mut self, msg: SetExitCountryMessage, _ctx: &mut Self::Context) -> Self::Result {
+        self.required_exit_countries_opt = match msg.preference_opt {
+            Some(ref preference) if preference.strict => Some(preference.countries.clone()),
+            _ => None,
+        };
+    }
+}
+
 impl Handler<InboundClientData> for ProxyServer {
     type Result = ();
 
@@ -161,6 +188,8 @@ impl Handler<AddRouteMessage> for ProxyServer {
 
     fn handle(&mut self, msg: AddRouteMessage, _ctx: &mut Self::Context) -> Self::Result {
         debug!(self.logger, "Establishing stream key {}", msg.stream_key);
+        self.stream_key_first_request_times
+            .insert(msg.stream_key, Instant::now());
         self.stream_key_routes.insert(msg.stream_key, msg.route);
     }
 }
@@ -189,6 +218,14 @@ impl Handler<ExpiredCoresPackage<ClientResponsePayload_0v1>> for ProxyServer {
     }
 }
 
+impl Handler<RemoveNeighborMessage> for ProxyServer {
+    type Result = ();
+
+    fn handle(&mut self, msg: RemoveNeighborMessage, _ctx: &mut Self::Context) -> Self::Result {
+        self.handle_remove_neighbor_message(msg)
+    }
+}
+
 impl Handler<StreamShutdownMsg> for ProxyServer {
     type Result = ();
 
@@ -211,6 +248,10 @@ impl ProxyServer {
             keys_and_addrs: BidiHashMap::new(),
             tunneled_hosts: HashMap::new(),
             stream_key_routes: HashMap::new(),
+            stream_key_first_request_times: HashMap::new(),
+            stream_key_unacked_payloads: HashMap::new(),
+            stream_key_failover_counts: HashMap::new(),
+            stream_key_replaced_route_ids: HashMap::new(),
             is_decentralized,
             consuming_wallet_balance,
             main_cryptde,
@@ -218,6 +259,7 @@ impl ProxyServer {
             logger: Logger::new("ProxyServer"),
             route_ids_to_return_routes: TtlHashMap::new(RETURN_ROUTE_TTL),
             browser_proxy_sequence_offset: false,
+            required_exit_countries_opt: None,
         }
     }
 
@@ -235,6 +277,8 @@ impl ProxyServer {
             add_route: addr.clone().recipient::<AddRouteMessage>(),
             stream_shutdown_sub: addr.clone().recipient::<StreamShutdownMsg>(),
             set_consuming_wallet_sub: addr.clone().recipient::<SetConsumingWalletMessage>(),
+            set_exit_country_sub: addr.clone().recipient::<SetExitCountryMessage>(),
+            remove_neighbor_sub: addr.clone().recipient::<RemoveNeighborMessage>(),
         }
     }
 
@@ -243,6 +287,14 @@ impl ProxyServer {
             Some(rri) => rri,
             None => return, // TODO: Eventually we'll have to do something better here, but we'll probably need some heuristics.
         };
+        if self.is_from_replaced_route(&msg.payload.stream_key, &return_route_info) {
+            debug!(
+                self.logger,
+                "Discarding DnsResolveFailure on stream {} from a route it has failed over from",
+                msg.payload.stream_key
+            );
+            return;
+        }
         let exit_public_key = {
             // ugly, ugly
             let self_public_key = self.main_cryptde.public_key();
@@ -272,6 +324,18 @@ impl ProxyServer {
                         false,
                     ))
                     .expect("Neighborhood is dead");
+                if self
+                    .stream_key_first_request_times
+                    .remove(&response.stream_key)
+                    .is_some()
+                {
+                    self.out_subs("Neighborhood")
+                        .update_node_record_metadata
+                        .try_send(NodeRecordMetadataMessage::Failure(vec![
+                            exit_public_key.clone()
+                        ]))
+                        .expect("Neighborhood is dead");
+                }
 
                 self.report_response_services_consumed(&return_route_info, 0, msg.payload_len);
 
@@ -332,6 +396,22 @@ impl ProxyServer {
             Some(rri) => rri,
             None => return,
         };
+        if self.is_from_replaced_route(&response.stream_key, &return_route_info) {
+            // The Nodes on the old route still carried it, so they still get paid.
+            self.report_response_services_consumed(
+                &return_route_info,
+                response.sequenced_packet.data.len(),
+                payload_data_len,
+            );
+            debug!(
+                self.logger,
+                "Discarding {}-byte packet {} on stream {} from a route it has failed over from",
+                response.sequenced_packet.data.len(),
+                response.sequenced_packet.sequence_number,
+                response.stream_key
+            );
+            return;
+        }
         match self.keys_and_addrs.a_to_b(&response.stream_key) {
             Some(socket_addr) => {
                 self.report_response_services_consumed(
@@ -339,6 +419,9 @@ impl ProxyServer {
                     response.sequenced_packet.data.len(),
                     payload_data_len,
                 );
+                self.report_first_response_round_trip(&response.stream_key);
+                self.stream_key_unacked_payloads
+                    .remove(&response.stream_key);
 
                 let last_data = response.sequenced_packet.last_data;
                 let sequence_number = Some(
@@ -456,7 +539,9 @@ impl ProxyServer {
         } else {
             0
         };
+        self.remember_unacked_payload(&payload);
         let cryptde = self.main_cryptde.dup();
+        let required_exit_countries_opt = self.required_exit_countries_opt.clone();
         match self.stream_key_routes.get(&stream_key) {
             Some(route_query_response) => {
                 debug!(
@@ -526,6 +611,7 @@ impl ProxyServer {
                                 Ok(None) => {
                                     ProxyServer::handle_route_failure(
                                         payload,
+                                        &required_exit_countries_opt,
                                         &logger,
                                         source_addr,
                                         &dispatcher,
@@ -613,9 +699,272 @@ impl ProxyServer {
     }
 
     fn purge_stream_key(&mut self, stream_key: &StreamKey) {
+        // A stream closed before its first response is usually a browser changing its mind, not
+        // a bad route; route failures are reported only by fail_over_stream().
+        let _ = self.stream_key_first_request_times.remove(stream_key);
         let _ = self.keys_and_addrs.remove_a(stream_key);
         let _ = self.stream_key_routes.remove(stream_key);
         let _ = self.tunneled_hosts.remove(stream_key);
+        let _ = self.stream_key_unacked_payloads.remove(stream_key);
+        let _ = self.stream_key_failover_counts.remove(stream_key);
+        let _ = self.stream_key_replaced_route_ids.remove(stream_key);
+    }
+
+    fn is_from_replaced_route(
+        &self,
+        stream_key: &StreamKey,
+        return_route_info: &AddReturnRouteMessage,
+    ) -> bool {
+        self.stream_key_replaced_route_ids
+            .get(stream_key)
+            .map(|route_ids| route_ids.contains(&return_route_info.return_route_id))
+            .unwrap_or(false)
+    }
+
+    // Until the first response arrives, the requests on a stream can be sent again over a
+    // different route without anyone being the wiser--provided that doing them twice is harmless.
+    fn remember_unacked_payload(&mut self, payload: &ClientRequestPayload_0v1) {
+        let stream_key = payload.stream_key;
+        if payload.sequenced_packet.sequence_number == 0 {
+            if ProxyServer::is_idempotent_request(payload) {
+                self.stream_key_unacked_payloads.insert(stream_key, vec![]);
+            } else {
+                self.stream_key_unacked_payloads.remove(&stream_key);
+            }
+        }
+        let payloads = match self.stream_key_unacked_payloads.get_mut(&stream_key) {
+            Some(payloads) => payloads,
+            None => return,
+        };
+        let buffered_bytes: usize = payloads
+            .iter()
+            .map(|payload| payload.sequenced_packet.data.len())
+            .sum();
+        if buffered_bytes + payload.sequenced_packet.data.len() > MAX_FAILOVER_BUFFER_BYTES {
+            debug!(
+                self.logger,
+                "Stream {} has sent too much to fail over to another route", stream_key
+            );
+            self.stream_key_unacked_payloads.remove(&stream_key);
+        } else {
+            payloads.push(payload.clone());
+        }
+    }
+
+    fn is_idempotent_request(payload: &ClientRequestPayload_0v1) -> bool {
+        if payload.protocol != ProxyProtocol::HTTP {
+            return false;
+        }
+        let data = &payload.sequenced_packet.data;
+        IDEMPOTENT_HTTP_METHODS.iter().any(|method| {
+            data.starts_with(method.as_bytes()) && data.get(method.len()) == Some(&b' ')
+        })
+    }
+
+    fn handle_remove_neighbor_message(&mut self, msg: RemoveNeighborMessage) {
+        let stream_keys = self
+            .stream_key_routes
+            .keys()
+            .filter(|stream_key| self.route_node_keys(stream_key).contains(&msg.public_key))
+            .cloned()
+            .collect::<Vec<StreamKey>>();
+        stream_keys.into_iter().for_each(|stream_key| {
+            self.fail_over_stream(stream_key, &format!("Node {} is gone", msg.public_key))
+        });
+    }
+
+    fn fail_over_unanswered_streams(&mut self, now: Instant) {
+        let stream_keys = self
+            .stream_key_first_request_times
+            .iter()
+            .filter(|(_, first_request_time)| {
+                now.duration_since(**first_request_time) >= ROUTE_RESPONSE_TIMEOUT
+            })
+            .map(|(stream_key, _)| *stream_key)
+            .collect::<Vec<StreamKey>>();
+        stream_keys.into_iter().for_each(|stream_key| {
+            self.fail_over_stream(
+                stream_key,
+                &format!(
+                    "no response in {} seconds",
+                    ROUTE_RESPONSE_TIMEOUT.as_secs()
+                ),
+            )
+        });
+    }
+
+    fn fail_over_stream(&mut self, stream_key: StreamKey, reason: &str) {
+        // The route failed whether or not the stream can be moved to another one
+        if self
+            .stream_key_first_request_times
+            .remove(&stream_key)
+            .is_some()
+        {
+            self.report_route_nodes(&stream_key, NodeRecordMetadataMessage::Failure);
+        }
+        let payloads = match self.stream_key_unacked_payloads.get(&stream_key) {
+            Some(payloads) if !payloads.is_empty() => payloads.clone(),
+            _ => return,
+        };
+        let source_addr = match self.keys_and_addrs.a_to_b(&stream_key) {
+            Some(source_addr) => source_addr,
+            None => return,
+        };
+        if let Some(RouteQueryResponse {
+            expected_services: ExpectedServices::RoundTrip(_, _, return_route_id),
+            ..
+        }) = self.stream_key_routes.remove(&stream_key)
+        {
+            self.stream_key_replaced_route_ids
+                .entry(stream_key)
+                .or_insert_with(Vec::new)
+                .push(return_route_id);
+        }
+        let failover_count = self
+            .stream_key_failover_counts
+            .entry(stream_key)
+            .or_insert(0);
+        if *failover_count >= ROUTE_FAILOVER_RETRY_LIMIT {
+            warning!(
+                self.logger,
+                "Route for stream {} failed ({}) after {} failovers; giving up",
+                stream_key,
+                reason,
+                ROUTE_FAILOVER_RETRY_LIMIT
+            );
+            ProxyServer::handle_route_failure(
+                payloads[0].clone(),
+                &self.required_exit_countries_opt,
+                &self.logger,
+                source_addr,
+                &self.out_subs("Dispatcher").dispatcher,
+            );
+            self.purge_stream_key(&stream_key);
+            return;
+        }
+        *failover_count += 1;
+        info!(
+            self.logger,
+            "Route for stream {} failed ({}); resending {} packets over a new route (failover {} of {})",
+            stream_key,
+            reason,
+            payloads.len(),
+            failover_count,
+            ROUTE_FAILOVER_RETRY_LIMIT
+        );
+        self.reroute_payloads(stream_key, payloads, source_addr);
+    }
+
+    fn reroute_payloads(
+        &self,
+        stream_key: StreamKey,
+        payloads: Vec<ClientRequestPayload_0v1>,
+        source_addr: SocketAddr,
+    ) {
+        let route_source = self.out_subs("Neighborhood").route_source.clone();
+        let hopper = self.out_subs("Hopper").hopper.clone();
+        let accountant_exit_sub = self.out_subs("Accountant").accountant_exit.clone();
+        let accountant_routing_sub = self.out_subs("Accountant").accountant_routing.clone();
+        let dispatcher = self.out_subs("Dispatcher").dispatcher.clone();
+        let add_return_route_sub = self.out_subs("ProxyServer").add_return_route.clone();
+        let add_route_sub = self.out_subs("ProxyServer").add_route.clone();
+        let logger = self.logger.clone();
+        let cryptde = self.main_cryptde.dup();
+        let required_exit_countries_opt = self.required_exit_countries_opt.clone();
+        let minimum_hop_count = if self.is_decentralized {
+            DEFAULT_MINIMUM_HOP_COUNT
+        } else {
+            0
+        };
+        tokio::spawn(
+            route_source
+                .send(RouteQueryMessage::data_indefinite_route_request(
+                    minimum_hop_count,
+                ))
+                .then(move |route_result| {
+                    match route_result {
+                        Ok(Some(route_query_response)) => {
+                            add_route_sub
+                                .try_send(AddRouteMessage {
+                                    stream_key,
+                                    route: route_query_response.clone(),
+                                })
+                                .expect("ProxyServer is dead");
+                            payloads.into_iter().for_each(|payload| {
+                                ProxyServer::try_transmit_to_hopper(
+                                    cryptde.dup(),
+                                    &hopper,
+                                    route_query_response.clone(),
+                                    payload,
+                                    logger.clone(),
+                                    source_addr,
+                                    &dispatcher,
+                                    &accountant_exit_sub,
+                                    &accountant_routing_sub,
+                                    &add_return_route_sub,
+                                    None,
+                                )
+                                .expect("Could not transmit to hopper")
+                            });
+                        }
+                        Ok(None) => ProxyServer::handle_route_failure(
+                            payloads[0].clone(),
+                            &required_exit_countries_opt,
+                            &logger,
+                            source_addr,
+                            &dispatcher,
+                        ),
+                        Err(e) => error!(
+                            logger,
+                            "Neighborhood refused to answer route request: {}", e
+                        ),
+                    };
+                    Ok(())
+                }),
+        );
+    }
+
+    fn report_first_response_round_trip(&mut self, stream_key: &StreamKey) {
+        if let Some(first_request_time) = self.stream_key_first_request_times.remove(stream_key) {
+            let round_trip_millis = first_request_time.elapsed().as_millis() as u32;
+            self.report_route_nodes(stream_key, |keys| {
+                NodeRecordMetadataMessage::RoundTrip(keys, round_trip_millis)
+            });
+        }
+    }
+
+    // Tells the Neighborhood about the Nodes on the outbound route of a stream, so that it can
+    // keep track of how well they've been carrying our traffic.
+    fn report_route_nodes<F>(&self, stream_key: &StreamKey, make_msg: F)
+    where
+        F: FnOnce(Vec<PublicKey>) -> NodeRecordMetadataMessage,
+    {
+        let keys = self.route_node_keys(stream_key);
+        if !keys.is_empty() {
+            self.out_subs("Neighborhood")
+                .update_node_record_metadata
+                .try_send(make_msg(keys))
+                .expect("Neighborhood is dead");
+        }
+    }
+
+    fn route_node_keys(&self, stream_key: &StreamKey) -> Vec<PublicKey> {
+        let expected_services = match self.stream_key_routes.get(stream_key) {
+            Some(route_query_response) => match &route_query_response.expected_services {
+                ExpectedServices::OneWay(services) => services,
+                ExpectedServices::RoundTrip(over, _, _) => over,
+            },
+            None => return vec![],
+        };
+        expected_services
+            .iter()
+            .filter_map(|service| match service {
+                ExpectedService::Routing(key, _, _) | ExpectedService::Exit(key, _, _) => {
+                    Some(key.clone())
+                }
+                ExpectedService::Nothing => None,
+            })
+            .collect()
     }
 
     fn make_payload(
@@ -794,7 +1143,9 @@ impl ProxyServer {
         };
 
         match destination_key_opt {
-            None => ProxyServer::handle_route_failure(payload, &logger, source_addr, dispatcher),
+            None => {
+                ProxyServer::handle_route_failure(payload, &None, &logger, source_addr, dispatcher)
+            }
             Some(payload_destination_key) => {
                 debug!(
                     logger,
@@ -840,23 +1191,43 @@ impl ProxyServer {
 
     fn handle_route_failure(
         payload: ClientRequestPayload_0v1,
+        required_exit_countries_opt: &Option<Vec<String>>,
         logger: &Logger,
         source_addr: SocketAddr,
         dispatcher: &Recipient<TransmitDataMsg>,
     ) {
         let target_hostname = ProxyServer::hostname(&payload);
-        ProxyServer::send_route_failure(payload, source_addr, dispatcher);
-        error!(logger, "Failed to find route to {}", target_hostname);
+        ProxyServer::send_route_failure(
+            payload,
+            required_exit_countries_opt,
+            source_addr,
+            dispatcher,
+        );
+        match required_exit_countries_opt {
+            Some(countries) => error!(
+                logger,
+                "Failed to find route to {} through an exit Node in {}",
+                target_hostname,
+                countries.join(", ")
+            ),
+            None => error!(logger, "Failed to find route to {}", target_hostname),
+        }
     }
 
     fn send_route_failure(
         payload: ClientRequestPayload_0v1,
+        required_exit_countries_opt: &Option<Vec<String>>,
         source_addr: SocketAddr,
         dispatcher: &Recipient<TransmitDataMsg>,
     ) {
-        let data = from_protocol(payload.protocol)
-            .server_impersonator()
-            .route_query_failure_response(&ProxyServer::hostname(&payload));
+        let server_impersonator = from_protocol(payload.protocol).server_impersonator();
+        let hostname = ProxyServer::hostname(&payload);
+        let data = match required_exit_countries_opt {
+            Some(countries) => {
+                server_impersonator.exit_country_failure_response(&hostname, countries)
+            }
+            None => server_impersonator.route_query_failure_response(&hostname),
+        };
         let msg = TransmitDataMsg {
             endpoint: Endpoint::Socket(source_addr),
             last_data: true,
@@ -985,6 +1356,7 @@ mod tests {
     use crate::test_utils::{make_meaningless_route, make_paying_wallet};
     use actix::System;
     use masq_lib::constants::{HTTP_PORT, TLS_PORT};
+    use masq_lib::exit_country::ExitCountryPreference;
     use masq_lib::test_utils::utils::DEFAULT_CHAIN_ID;
     use std::cell::RefCell;
     use std::net::SocketAddr;
@@ -2629,6 +3001,76 @@ mod tests {
             .exists_log_containing("ERROR: ProxyServer: Failed to find route to nowhere.com");
     }
 
+    #[test]
+    fn proxy_server_reports_route_failure_according_to_exit_country_preference() {
+        init_test_logging();
+        vec![
+            (
+                "CZ,SK",
+                ServerImpersonatorHttp {}.exit_country_failure_response(
+                    "nowhere.com",
+                    &["CZ".to_string(), "SK".to_string()],
+                ),
+            ),
+            (
+                "prefer:CZ",
+                ServerImpersonatorHttp {}.route_query_failure_response("nowhere.com"),
+            ),
+        ]
+        .into_iter()
+        .for_each(|(preference, expected_response)| {
+            let cryptde = main_cryptde();
+            let http_request = b"GET /index.html HTTP/1.1\r\nHost: nowhere.com\r\n\r\n";
+            let neighborhood_mock = Recorder::new().route_query_response(None);
+            let dispatcher = Recorder::new();
+            let dispatcher_awaiter = dispatcher.get_awaiter();
+            let dispatcher_recording_arc = dispatcher.get_recording();
+            let msg_from_dispatcher = InboundClientData {
+                peer_addr: SocketAddr::from_str("1.2.3.4:5678").unwrap(),
+                reception_port: Some(HTTP_PORT),
+                sequence_number: Some(0),
+                last_data: true,
+                data: http_request.to_vec(),
+                is_clandestine: false,
+            };
+            thread::spawn(move || {
+                let system = System::new(
+                    "proxy_server_reports_route_failure_according_to_exit_country_preference",
+                );
+                let subject = ProxyServer::new(
+                    cryptde,
+                    alias_cryptde(),
+                    true,
+                    Some(STANDARD_CONSUMING_WALLET_BALANCE),
+                );
+                let subject_addr: Addr<ProxyServer> = subject.start();
+                let mut peer_actors = peer_actors_builder()
+                    .dispatcher(dispatcher)
+                    .neighborhood(neighborhood_mock)
+                    .build();
+                peer_actors.proxy_server = ProxyServer::make_subs_from(&subject_addr);
+                subject_addr.try_send(BindMessage { peer_actors }).unwrap();
+                subject_addr
+                    .try_send(SetExitCountryMessage {
+                        preference_opt: Some(ExitCountryPreference::from_str(preference).unwrap()),
+                    })
+                    .unwrap();
+
+                subject_addr.try_send(msg_from_dispatcher).unwrap();
+
+                system.run();
+            });
+
+            dispatcher_awaiter.await_message_count(1);
+            let recording = dispatcher_recording_arc.lock().unwrap();
+            let record = recording.get_record::<TransmitDataMsg>(0);
+            assert_eq!(record.data, expected_response, "preference {}", preference);
+        });
+        TestLogHandler::new().exists_log_containing(
+            "ERROR: ProxyServer: Failed to find route to nowhere.com through an exit Node in CZ, SK",
+        );
+    }
+
     #[test]
     #[should_panic(expected = "Expected RoundTrip ExpectedServices but got OneWay")]
     fn proxy_server_panics_if_it_receives_a_one_way_route_from_a_request_for_a_round_trip_route() {
@@ -3829,6 +4271,516 @@ mod tests {
         assert!(subject.tunneled_hosts.is_empty());
     }
 
+    fn make_subject_reporting_to_neighborhood(neighborhood: Recorder) -> ProxyServer {
+        let mut subject = ProxyServer::new(
+            main_cryptde(),
+            alias_cryptde(),
+            true,
+            Some(STANDARD_CONSUMING_WALLET_BALANCE),
+        );
+        subject.subs = Some(ProxyServerOutSubs::default());
+        let peer_actors = peer_actors_builder().neighborhood(neighborhood).build();
+        subject.subs.as_mut().unwrap().update_node_record_metadata =
+            peer_actors.neighborhood.update_node_record_metadata;
+        subject
+    }
+
+    fn add_measured_stream(
+        subject: &mut ProxyServer,
+        stream_key: &StreamKey,
+        relay_key: &PublicKey,
+        exit_key: &PublicKey,
+        first_request_time: Instant,
+    ) {
+        subject.stream_key_routes.insert(
+            stream_key.clone(),
+            RouteQueryResponse {
+                route: Route { hops: vec![] },
+                expected_services: ExpectedServices::RoundTrip(
+                    vec![
+                        ExpectedService::Nothing,
+                        ExpectedService::Routing(
+                            relay_key.clone(),
+                            make_wallet("relay"),
+                            rate_pack(101),
+                        ),
+                        ExpectedService::Exit(
+                            exit_key.clone(),
+                            make_wallet("exit"),
+                            rate_pack(102),
+                        ),
+                    ],
+                    vec![],
+                    1234,
+                ),
+            },
+        );
+        subject
+            .stream_key_first_request_times
+            .insert(stream_key.clone(), first_request_time);
+    }
+
+    #[test]
+    fn first_response_on_stream_reports_round_trip_for_route_nodes() {
+        let system = System::new("first_response_on_stream_reports_round_trip_for_route_nodes");
+        let (neighborhood, _, neighborhood_recording_arc) = make_recorder();
+        let mut subject = make_subject_reporting_to_neighborhood(neighborhood);
+        let stream_key = make_meaningless_stream_key();
+        let relay_key = PublicKey::new(b"relay");
+        let exit_key = PublicKey::new(b"exit");
+        add_measured_stream(
+            &mut subject,
+            &stream_key,
+            &relay_key,
+            &exit_key,
+            Instant::now() - Duration::from_millis(250),
+        );
+
+        subject.report_first_response_round_trip(&stream_key);
+        subject.report_first_response_round_trip(&stream_key);
+        subject.purge_stream_key(&stream_key);
+
+        System::current().stop();
+        system.run();
+        let neighborhood_recording = neighborhood_recording_arc.lock().unwrap();
+        assert_eq!(neighborhood_recording.len(), 1);
+        match neighborhood_recording.get_record::<NodeRecordMetadataMessage>(0) {
+            NodeRecordMetadataMessage::RoundTrip(keys, round_trip_millis) => {
+                assert_eq!(keys, &vec![relay_key, exit_key]);
+                assert!(
+                    *round_trip_millis >= 250 && *round_trip_millis < 10_000,
+                    "{}",
+                    round_trip_millis
+                );
+            }
+            x => panic!("Expected RoundTrip, got {:?}", x),
+        }
+    }
+
+    #[test]
+    fn stream_retired_without_response_reports_nothing_for_route_nodes() {
+        let system = System::new("stream_retired_without_response_reports_nothing_for_route_nodes");
+        let (neighborhood, _, neighborhood_recording_arc) = make_recorder();
+        let mut subject = make_subject_reporting_to_neighborhood(neighborhood);
+        let stream_key = make_meaningless_stream_key();
+        let relay_key = PublicKey::new(b"relay");
+        let exit_key = PublicKey::new(b"exit");
+        add_measured_stream(
+            &mut subject,
+            &stream_key,
+            &relay_key,
+            &exit_key,
+            Instant::now(),
+        );
+
+        subject.purge_stream_key(&stream_key);
+
+        System::current().stop();
+        system.run();
+        let neighborhood_recording = neighborhood_recording_arc.lock().unwrap();
+        assert_eq!(neighborhood_recording.len(), 0);
+        assert!(subject.stream_key_first_request_times.is_empty());
+    }
+
+    fn make_failover_payload(
+        stream_key: &StreamKey,
+        data: &[u8],
+        sequence_number: u64,
+        protocol: ProxyProtocol,
+    ) -> ClientRequestPayload_0v1 {
+        ClientRequestPayload_0v1 {
+            stream_key: stream_key.clone(),
+            sequenced_packet: SequencedPacket::new(data.to_vec(), sequence_number, false),
+            target_hostname: Some("nowhere.com".to_string()),
+            target_port: HTTP_PORT,
+            protocol,
+            originator_public_key: alias_cryptde().public_key().clone(),
+        }
+    }
+
+    #[test]
+    fn only_idempotent_http_requests_are_remembered_for_failover() {
+        let _system = System::new("only_idempotent_http_requests_are_remembered_for_failover");
+        let mut subject = make_subject_reporting_to_neighborhood(Recorder::new());
+        let get_key = StreamKey::new(
+            PublicKey::new(&[]),
+            SocketAddr::from_str("1.1.1.1:1111").unwrap(),
+        );
+        let post_key = StreamKey::new(
+            PublicKey::new(&[]),
+            SocketAddr::from_str("2.2.2.2:2222").unwrap(),
+        );
+        let tls_key = StreamKey::new(
+            PublicKey::new(&[]),
+            SocketAddr::from_str("3.3.3.3:3333").unwrap(),
+        );
+        let put_key = StreamKey::new(
+            PublicKey::new(&[]),
+            SocketAddr::from_str("4.4.4.4:4444").unwrap(),
+        );
+        let get_payloads = vec![
+            make_failover_payload(&get_key, b"GET / HTTP/1.1\r\n", 0, ProxyProtocol::HTTP),
+            make_failover_payload(
+                &get_key,
+                b"Host: nowhere.com\r\n\r\n",
+                1,
+                ProxyProtocol::HTTP,
+            ),
+        ];
+
+        get_payloads
+            .iter()
+            .for_each(|payload| subject.remember_unacked_payload(payload));
+        subject.remember_unacked_payload(&make_failover_payload(
+            &post_key,
+            b"POST / HTTP/1.1\r\n\r\n",
+            0,
+            ProxyProtocol::HTTP,
+        ));
+        subject.remember_unacked_payload(&make_failover_payload(
+            &tls_key,
+            b"GET / HTTP/1.1\r\n\r\n",
+            0,
+            ProxyProtocol::TLS,
+        ));
+        // Repeating a PUT is harmless only if the server gets it right, so it isn't risked
+        subject.remember_unacked_payload(&make_failover_payload(
+            &put_key,
+            b"PUT / HTTP/1.1\r\n\r\n",
+            0,
+            ProxyProtocol::HTTP,
+        ));
+
+        assert_eq!(
+            subject.stream_key_unacked_payloads.get(&get_key),
+            Some(&get_payloads)
+        );
+        assert_eq!(subject.stream_key_unacked_payloads.get(&post_key), None);
+        assert_eq!(subject.stream_key_unacked_payloads.get(&tls_key), None);
+        assert_eq!(subject.stream_key_unacked_payloads.get(&put_key), None);
+    }
+
+    #[test]
+    fn response_from_route_replaced_by_failover_is_discarded() {
+        init_test_logging();
+        let main_cryptde = main_cryptde();
+        let system = System::new("response_from_route_replaced_by_failover_is_discarded");
+        let (dispatcher_mock, _, dispatcher_recording_arc) = make_recorder();
+        let neighborhood_mock = Recorder::new().route_query_response(Some(
+            zero_hop_route_response(main_cryptde.public_key(), main_cryptde),
+        ));
+        let stream_key = make_meaningless_stream_key();
+        let relay_key = PublicKey::new(b"relay");
+        let mut subject = ProxyServer::new(
+            main_cryptde,
+            alias_cryptde(),
+            false,
+            Some(STANDARD_CONSUMING_WALLET_BALANCE),
+        );
+        add_measured_stream(
+            &mut subject,
+            &stream_key,
+            &relay_key,
+            &PublicKey::new(b"exit"),
+            Instant::now(),
+        );
+        subject
+            .keys_and_addrs
+            .insert(stream_key, SocketAddr::from_str("1.2.3.4:5678").unwrap());
+        subject.remember_unacked_payload(&make_failover_payload(
+            &stream_key,
+            b"GET / HTTP/1.1\r\n\r\n",
+            0,
+            ProxyProtocol::HTTP,
+        ));
+        subject.route_ids_to_return_routes.insert(
+            1234,
+            AddReturnRouteMessage {
+                return_route_id: 1234,
+                expected_services: vec![ExpectedService::Nothing],
+                protocol: ProxyProtocol::HTTP,
+                server_name: None,
+            },
+        );
+        let subject_addr: Addr<ProxyServer> = subject.start();
+        let mut peer_actors = peer_actors_builder()
+            .dispatcher(dispatcher_mock)
+            .neighborhood(neighborhood_mock)
+            .build();
+        peer_actors.proxy_server = ProxyServer::make_subs_from(&subject_addr);
+        subject_addr.try_send(BindMessage { peer_actors }).unwrap();
+        let stale_response = ExpiredCoresPackage::new(
+            SocketAddr::from_str("1.2.3.4:1234").unwrap(),
+            Some(make_wallet("consuming")),
+            return_route_with_id(main_cryptde, 1234),
+            ClientResponsePayload_0v1 {
+                stream_key,
+                sequenced_packet: SequencedPacket {
+                    data: b"stale".to_vec(),
+                    sequence_number: 0,
+                    last_data: false,
+                },
+            },
+            0,
+        );
+
+        subject_addr
+            .try_send(RemoveNeighborMessage {
+                public_key: relay_key,
+            })
+            .unwrap();
+        subject_addr.try_send(stale_response).unwrap();
+
+        System::current().stop();
+        system.run();
+        let dispatcher_recording = dispatcher_recording_arc.lock().unwrap();
+        assert_eq!(dispatcher_recording.len(), 0);
+        TestLogHandler::new().exists_log_containing(&format!(
+            "DEBUG: ProxyServer: Discarding 5-byte packet 0 on stream {} from a route it has failed over from",
+            stream_key
+        ));
+    }
+
+    #[test]
+    fn stream_that_sends_too_much_is_not_remembered_for_failover() {
+        let _system = System::new("stream_that_sends_too_much_is_not_remembered_for_failover");
+        let mut subject = make_subject_reporting_to_neighborhood(Recorder::new());
+        let stream_key = make_meaningless_stream_key();
+        let mut big_request = b"GET / HTTP/1.1\r\n\r\n".to_vec();
+        big_request.resize(MAX_FAILOVER_BUFFER_BYTES, b'x');
+
+        subject.remember_unacked_payload(&make_failover_payload(
+            &stream_key,
+            &big_request,
+            0,
+            ProxyProtocol::HTTP,
+        ));
+        let remembered_before = subject
+            .stream_key_unacked_payloads
+            .contains_key(&stream_key);
+        subject.remember_unacked_payload(&make_failover_payload(
+            &stream_key,
+            b"x",
+            1,
+            ProxyProtocol::HTTP,
+        ));
+
+        assert_eq!(remembered_before, true);
+        assert_eq!(
+            subject
+                .stream_key_unacked_payloads
+                .contains_key(&stream_key),
+            false
+        );
+    }
+
+    #[test]
+    fn removed_neighbor_reroutes_unanswered_idempotent_stream() {
+        init_test_logging();
+        let main_cryptde = main_cryptde();
+        let alias_cryptde = alias_cryptde();
+        let (hopper_mock, hopper_awaiter, hopper_recording_arc) = make_recorder();
+        let (neighborhood_mock, _, neighborhood_recording_arc) = make_recorder();
+        let neighborhood_mock = neighborhood_mock.route_query_response(Some(
+            zero_hop_route_response(main_cryptde.public_key(), main_cryptde),
+        ));
+        let (proxy_server_mock, _, proxy_server_recording_arc) = make_recorder();
+        let stream_key = make_meaningless_stream_key();
+        let relay_key = PublicKey::new(b"relay");
+        let exit_key = PublicKey::new(b"exit");
+        let payload = make_failover_payload(
+            &stream_key,
+            b"GET / HTTP/1.1\r\nHost: nowhere.com\r\n\r\n",
+            0,
+            ProxyProtocol::HTTP,
+        );
+        let route = zero_hop_route_response(main_cryptde.public_key(), main_cryptde).route;
+        let expected_pkg = IncipientCoresPackage::new(
+            main_cryptde,
+            route,
+            payload.clone().into(),
+            alias_cryptde.public_key(),
+        )
+        .unwrap();
+        let relay_key_inner = relay_key.clone();
+        let exit_key_inner = exit_key.clone();
+        thread::spawn(move || {
+            let system = System::new("removed_neighbor_reroutes_unanswered_idempotent_stream");
+            let mut subject = ProxyServer::new(
+                main_cryptde,
+                alias_cryptde,
+                false,
+                Some(STANDARD_CONSUMING_WALLET_BALANCE),
+            );
+            add_measured_stream(
+                &mut subject,
+                &stream_key,
+                &relay_key_inner,
+                &exit_key_inner,
+                Instant::now(),
+            );
+            subject
+                .keys_and_addrs
+                .insert(stream_key, SocketAddr::from_str("1.2.3.4:5678").unwrap());
+            subject.remember_unacked_payload(&payload);
+            let subject_addr: Addr<ProxyServer> = subject.start();
+            let peer_actors = peer_actors_builder()
+                .hopper(hopper_mock)
+                .neighborhood(neighborhood_mock)
+                .proxy_server(proxy_server_mock)
+                .build();
+            subject_addr.try_send(BindMessage { peer_actors }).unwrap();
+
+            subject_addr
+                .try_send(RemoveNeighborMessage {
+                    public_key: relay_key_inner,
+                })
+                .unwrap();
+
+            system.run();
+        });
+
+        hopper_awaiter.await_message_count(1);
+        let hopper_recording = hopper_recording_arc.lock().unwrap();
+        assert_eq!(
+            hopper_recording.get_record::<IncipientCoresPackage>(0),
+            &expected_pkg
+        );
+        let neighborhood_recording = neighborhood_recording_arc.lock().unwrap();
+        assert_eq!(
+            neighborhood_recording.get_record::<NodeRecordMetadataMessage>(0),
+            &NodeRecordMetadataMessage::Failure(vec![relay_key, exit_key])
+        );
+        assert_eq!(
+            neighborhood_recording.get_record::<RouteQueryMessage>(1),
+            &RouteQueryMessage::data_indefinite_route_request(0)
+        );
+        let proxy_server_recording = proxy_server_recording_arc.lock().unwrap();
+        assert_eq!(
+            proxy_server_recording.get_record::<AddRouteMessage>(0),
+            &AddRouteMessage {
+                stream_key,
+                route: zero_hop_route_response(main_cryptde.public_key(), main_cryptde),
+            }
+        );
+        TestLogHandler::new().exists_log_containing(&format!(
+            "INFO: ProxyServer: Route for stream {} failed (Node {} is gone); resending 1 packets over a new route (failover 1 of {})",
+            stream_key,
+            PublicKey::new(b"relay"),
+            ROUTE_FAILOVER_RETRY_LIMIT
+        ));
+    }
+
+    #[test]
+    fn unanswered_non_idempotent_stream_reports_failure_once_without_failing_over() {
+        let system = System::new(
+            "unanswered_non_idempotent_stream_reports_failure_once_without_failing_over",
+        );
+        let (neighborhood, _, neighborhood_recording_arc) = make_recorder();
+        let mut subject = make_subject_reporting_to_neighborhood(neighborhood);
+        let stream_key = make_meaningless_stream_key();
+        let relay_key = PublicKey::new(b"relay");
+        let exit_key = PublicKey::new(b"exit");
+        let start = Instant::now();
+        add_measured_stream(&mut subject, &stream_key, &relay_key, &exit_key, start);
+        subject
+            .keys_and_addrs
+            .insert(stream_key, SocketAddr::from_str("1.2.3.4:5678").unwrap());
+        subject.remember_unacked_payload(&make_failover_payload(
+            &stream_key,
+            b"POST / HTTP/1.1\r\n\r\n",
+            0,
+            ProxyProtocol::HTTP,
+        ));
+
+        subject.fail_over_unanswered_streams(start + ROUTE_RESPONSE_TIMEOUT);
+        subject.fail_over_unanswered_streams(start + ROUTE_RESPONSE_TIMEOUT * 2);
+
+        System::current().stop();
+        system.run();
+        assert!(subject.stream_key_first_request_times.is_empty());
+        assert_eq!(subject.stream_key_routes.contains_key(&stream_key), true);
+        assert_eq!(subject.stream_key_failover_counts.get(&stream_key), None);
+        let neighborhood_recording = neighborhood_recording_arc.lock().unwrap();
+        assert_eq!(
+            neighborhood_recording.get_record::<NodeRecordMetadataMessage>(0),
+            &NodeRecordMetadataMessage::Failure(vec![relay_key, exit_key])
+        );
+        assert_eq!(neighborhood_recording.len(), 1);
+    }
+
+    #[test]
+    fn unanswered_stream_that_has_failed_over_too_often_gets_route_failure() {
+        init_test_logging();
+        let system =
+            System::new("unanswered_stream_that_has_failed_over_too_often_gets_route_failure");
+        let (dispatcher, _, dispatcher_recording_arc) = make_recorder();
+        let mut subject = make_subject_reporting_to_neighborhood(Recorder::new());
+        let peer_actors = peer_actors_builder().dispatcher(dispatcher).build();
+        subject.subs.as_mut().unwrap().dispatcher = peer_actors.dispatcher.from_dispatcher_client;
+        let stream_key = make_meaningless_stream_key();
+        let unremembered_key = StreamKey::new(
+            PublicKey::new(&[]),
+            SocketAddr::from_str("2.2.2.2:2222").unwrap(),
+        );
+        let source_addr = SocketAddr::from_str("1.2.3.4:5678").unwrap();
+        let start = Instant::now();
+        vec![stream_key, unremembered_key]
+            .iter()
+            .for_each(|stream_key| {
+                add_measured_stream(
+                    &mut subject,
+                    stream_key,
+                    &PublicKey::new(b"relay"),
+                    &PublicKey::new(b"exit"),
+                    start,
+                )
+            });
+        subject.keys_and_addrs.insert(stream_key, source_addr);
+        subject.keys_and_addrs.insert(
+            unremembered_key,
+            SocketAddr::from_str("2.3.4.5:6789").unwrap(),
+        );
+        subject.remember_unacked_payload(&make_failover_payload(
+            &stream_key,
+            b"GET / HTTP/1.1\r\n\r\n",
+            0,
+            ProxyProtocol::HTTP,
+        ));
+        subject
+            .stream_key_failover_counts
+            .insert(stream_key, ROUTE_FAILOVER_RETRY_LIMIT);
+
+        subject.fail_over_unanswered_streams(
+            start + ROUTE_RESPONSE_TIMEOUT - Duration::from_millis(1),
+        );
+        let routes_before_timeout = subject.stream_key_routes.len();
+        subject.fail_over_unanswered_streams(start + ROUTE_RESPONSE_TIMEOUT);
+
+        System::current().stop();
+        system.run();
+        assert_eq!(routes_before_timeout, 2);
+        assert_eq!(subject.stream_key_routes.contains_key(&stream_key), false);
+        assert_eq!(subject.keys_and_addrs.a_to_b(&stream_key), None);
+        assert_eq!(
+            subject.stream_key_routes.contains_key(&unremembered_key),
+            true
+        );
+        let dispatcher_recording = dispatcher_recording_arc.lock().unwrap();
+        let transmit_data_msg = dispatcher_recording.get_record::<TransmitDataMsg>(0);
+        assert_eq!(transmit_data_msg.endpoint, Endpoint::Socket(source_addr));
+        assert_eq!(transmit_data_msg.last_data, true);
+        assert_eq!(
+            transmit_data_msg.data,
+            ServerImpersonatorHttp {}.route_query_failure_response("nowhere.com")
+        );
+        assert_eq!(dispatcher_recording.len(), 1);
+        TestLogHandler::new().exists_log_containing(&format!(
+            "WARN: ProxyServer: Route for stream {} failed (no response in 20 seconds) after 2 failovers; giving up",
+            stream_key
+        ));
+    }
+
     #[test]
     #[should_panic(expected = "Dispatcher unbound in ProxyServer")]
     fn panics_if_dispatcher_is_unbound() {
@@ -65,6 +65,7 @@ pub fn from_ibcd(ibcd: &InboundClientData, logger: &Logger) -> Option<Box<dyn Pr
 
 pub trait ServerImpersonator {
     fn route_query_failure_response(&self, server_name: &str) -> Vec<u8>;
+    fn exit_country_failure_response(&self, server_name: &str, countries: &[String]) -> Vec<u8>;
     fn dns_resolution_failure_response(
         &self,
         exit_key: &PublicKey,
@@ -20,6 +20,27 @@ impl ServerImpersonator for ServerImpersonatorHttp {
         )
     }
 
+    fn exit_country_failure_response(&self, server_name: &str, countries: &[String]) -> Vec<u8> {
+        let country_list = countries.join(", ");
+        ServerImpersonatorHttp::make_error_response(
+            503,
+            "Routing Problem",
+            format!(
+                "Can't find a route to {} through an exit Node in {}",
+                server_name, country_list
+            )
+            .as_str(),
+            format!(
+                "You've required that your traffic leave the MASQ Network in {}, but MASQ \
+                 doesn't know of a route to an exit Node there that can reach {}. Maybe later \
+                 enough will be known about the Network to find such a Node; or you can allow \
+                 other exit countries with the exit-country setting.",
+                country_list, server_name
+            )
+            .as_str(),
+        )
+    }
+
     fn dns_resolution_failure_response(
         &self,
         exit_key: &PublicKey,
@@ -178,6 +199,25 @@ mod tests {
         assert_eq!(expected, result);
     }
 
+    #[test]
+    fn exit_country_failure_response_produces_expected_error_page() {
+        let subject = ServerImpersonatorHttp {};
+
+        let result = subject
+            .exit_country_failure_response("server.com", &["CZ".to_string(), "SK".to_string()]);
+
+        let expected = ServerImpersonatorHttp::make_error_response(
+            503,
+            "Routing Problem",
+            "Can't find a route to server.com through an exit Node in CZ, SK",
+            "You've required that your traffic leave the MASQ Network in CZ, SK, but MASQ \
+             doesn't know of a route to an exit Node there that can reach server.com. Maybe later \
+             enough will be known about the Network to find such a Node; or you can allow \
+             other exit countries with the exit-country setting.",
+        );
+        assert_eq!(expected, result);
+    }
+
     #[test]
     fn dns_resolution_failure_response_with_server_name_produces_expected_error_page() {
         let subject = ServerImpersonatorHttp {};
@@ -9,6 +9,10 @@ impl ServerImpersonator for ServerImpersonatorTls {
         Vec::from(&TLS_INTERNAL_ERROR_ALERT[..])
     }
 
+    fn exit_country_failure_response(&self, _server_name: &str, _countries: &[String]) -> Vec<u8> {
+        Vec::from(&TLS_INTERNAL_ERROR_ALERT[..])
+    }
+
     fn dns_resolution_failure_response(
         &self,
         _exit_key: &PublicKey,
@@ -51,6 +55,15 @@ mod tests {
         assert_eq!(Vec::from(&TLS_INTERNAL_ERROR_ALERT[..]), result);
     }
 
+    #[test]
+    fn exit_country_failure_response_produces_internal_error_alert() {
+        let subject = ServerImpersonatorTls {};
+
+        let result = subject.exit_country_failure_response("ignored", &["CZ".to_string()]);
+
+        assert_eq!(Vec::from(&TLS_INTERNAL_ERROR_ALERT[..]), result);
+    }
+
     #[test]
     fn dns_resolution_failure_response_produces_unrecognized_name_alert() {
         let subject = ServerImpersonatorTls {};
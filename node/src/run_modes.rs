@@ -7,6 +7,8 @@ use crate::node_configurator::node_configurator_initialization::NodeConfigurator
 use crate::node_configurator::{NodeConfigurator, RealDirsWrapper, WalletCreationConfig};
 use crate::privilege_drop::{PrivilegeDropper, PrivilegeDropperReal};
 use crate::server_initializer::{LoggerInitializerWrapperReal, ServerInitializer};
+use crate::systemd;
+use crate::systemd::{DNS_SOCKET_NAME, GENERATE_SYSTEMD_UNIT_FLAG};
 use actix::System;
 use futures::future::Future;
 use masq_lib::command::{Command, StdStreams};
@@ -15,6 +17,7 @@ use masq_lib::shared_schema::ConfiguratorError;
 #[derive(Debug, PartialEq)]
 enum Mode {
     DumpConfig,
+    GenerateSystemdUnit,
     Initialization,
     Service,
 }
@@ -22,6 +25,7 @@ enum Mode {
 pub struct RunModes {
     privilege_dropper: Box<dyn PrivilegeDropper>,
     runner: Box<dyn Runner>,
+    dns_socket_activated: bool,
 }
 
 impl Default for RunModes {
@@ -35,6 +39,7 @@ impl RunModes {
         Self {
             privilege_dropper: Box::new(PrivilegeDropperReal::new()),
             runner: Box::new(RunnerReal::new()),
+            dns_socket_activated: systemd::is_socket_activated(DNS_SOCKET_NAME),
         }
     }
 
@@ -55,6 +60,7 @@ impl RunModes {
         }
         match match mode {
             Mode::DumpConfig => self.runner.dump_config(args, streams),
+            Mode::GenerateSystemdUnit => self.runner.generate_systemd_unit(args, streams),
             Mode::Initialization => self.runner.initialization(args, streams),
             Mode::Service => self.runner.run_service(args, streams),
         } {
@@ -107,10 +113,14 @@ impl RunModes {
     fn determine_mode_and_priv_req(&self, args: &[String]) -> (Mode, bool) {
         if args.contains(&"--dump-config".to_string()) {
             (Mode::DumpConfig, false)
+        } else if args.contains(&GENERATE_SYSTEMD_UNIT_FLAG.to_string()) {
+            (Mode::GenerateSystemdUnit, false)
         } else if args.contains(&"--initialization".to_string()) {
             (Mode::Initialization, true)
         } else {
-            (Mode::Service, true)
+            // Port 53 is the only thing the Node can't get with CAP_NET_BIND_SERVICE alone;
+            // if systemd bound it for us, root is unnecessary.
+            (Mode::Service, !self.dns_socket_activated)
         }
     }
 }
@@ -131,6 +141,11 @@ trait Runner {
         args: &[String],
         streams: &mut StdStreams<'_>,
     ) -> Result<i32, ConfiguratorError>;
+    fn generate_systemd_unit(
+        &self,
+        args: &[String],
+        streams: &mut StdStreams<'_>,
+    ) -> Result<i32, ConfiguratorError>;
     fn configuration_run(
         &self,
         args: &[String],
@@ -156,8 +171,12 @@ impl Runner for RunnerReal {
         actix::spawn(server_initializer.map_err(|_| {
             System::current().stop_with_code(1);
         }));
+        systemd::notify_ready();
+        systemd::start_watchdog();
 
-        Ok(system.run())
+        let exit_code = system.run();
+        systemd::notify_stopping();
+        Ok(exit_code)
     }
 
     fn dump_config(
@@ -187,6 +206,24 @@ impl Runner for RunnerReal {
         Ok(1)
     }
 
+    fn generate_systemd_unit(
+        &self,
+        args: &[String],
+        streams: &mut StdStreams<'_>,
+    ) -> Result<i32, ConfiguratorError> {
+        let exe_path = match std::env::current_exe() {
+            Ok(path) => path.display().to_string(),
+            Err(_) => "/usr/local/bin/MASQNode".to_string(),
+        };
+        write!(
+            streams.stdout,
+            "{}",
+            systemd::generate_units(&exe_path, args)
+        )
+        .expect("write! failed");
+        Ok(0)
+    }
+
     fn configuration_run(
         &self,
         args: &[String],
@@ -222,6 +259,8 @@ mod tests {
         dump_config_results: RefCell<Vec<Result<i32, ConfiguratorError>>>,
         initialization_params: Arc<Mutex<Vec<Vec<String>>>>,
         initialization_results: RefCell<Vec<Result<i32, ConfiguratorError>>>,
+        generate_systemd_unit_params: Arc<Mutex<Vec<Vec<String>>>>,
+        generate_systemd_unit_results: RefCell<Vec<Result<i32, ConfiguratorError>>>,
         configuration_run_params: Arc<Mutex<Vec<Vec<String>>>>,
         configuration_run_results: RefCell<Vec<Result<i32, ConfiguratorError>>>,
     }
@@ -257,6 +296,18 @@ mod tests {
             self.initialization_results.borrow_mut().remove(0)
         }
 
+        fn generate_systemd_unit(
+            &self,
+            args: &[String],
+            _streams: &mut StdStreams<'_>,
+        ) -> Result<i32, ConfiguratorError> {
+            self.generate_systemd_unit_params
+                .lock()
+                .unwrap()
+                .push(args.to_vec());
+            self.generate_systemd_unit_results.borrow_mut().remove(0)
+        }
+
         fn configuration_run(
             &self,
             args: &[String],
@@ -282,6 +333,8 @@ mod tests {
                 dump_config_results: RefCell::new(vec![]),
                 initialization_params: Arc::new(Mutex::new(vec![])),
                 initialization_results: RefCell::new(vec![]),
+                generate_systemd_unit_params: Arc::new(Mutex::new(vec![])),
+                generate_systemd_unit_results: RefCell::new(vec![]),
                 configuration_run_params: Arc::new(Mutex::new(vec![])),
                 configuration_run_results: RefCell::new(vec![]),
             }
@@ -317,6 +370,19 @@ mod tests {
             self
         }
 
+        pub fn generate_systemd_unit_params(
+            mut self,
+            params: &Arc<Mutex<Vec<Vec<String>>>>,
+        ) -> Self {
+            self.generate_systemd_unit_params = params.clone();
+            self
+        }
+
+        pub fn generate_systemd_unit_result(self, result: Result<i32, ConfiguratorError>) -> Self {
+            self.generate_systemd_unit_results.borrow_mut().push(result);
+            self
+        }
+
         pub fn configuration_run_params(mut self, params: &Arc<Mutex<Vec<Vec<String>>>>) -> Self {
             self.configuration_run_params = params.clone();
             self
@@ -363,11 +429,66 @@ mod tests {
             .for_each(|args| check_mode(args, Mode::DumpConfig, false));
     }
 
+    #[test]
+    fn generate_systemd_unit() {
+        check_mode(
+            &["--generate-systemd-unit"],
+            Mode::GenerateSystemdUnit,
+            false,
+        );
+        check_mode(
+            &["--initialization", "--generate-systemd-unit"],
+            Mode::GenerateSystemdUnit,
+            false,
+        );
+        check_mode(
+            &["--generate-systemd-unit", "--dump-config"],
+            Mode::DumpConfig,
+            false,
+        );
+    }
+
     #[test]
     fn run_servers() {
         check_mode(&[], Mode::Service, true)
     }
 
+    #[test]
+    fn run_servers_without_privilege_when_dns_socket_is_activated() {
+        let mut subject = RunModes::new();
+        subject.dns_socket_activated = true;
+
+        let (mode, privilege_required) =
+            subject.determine_mode_and_priv_req(&["--chain".to_string(), "ropsten".to_string()]);
+
+        assert_eq!(mode, Mode::Service);
+        assert_eq!(privilege_required, false);
+    }
+
+    #[test]
+    fn generate_systemd_unit_mode_runs_without_privilege() {
+        let mut subject = RunModes::new();
+        let params_arc = Arc::new(Mutex::new(vec![]));
+        subject.runner = Box::new(
+            RunnerMock::new()
+                .generate_systemd_unit_params(&params_arc)
+                .generate_systemd_unit_result(Ok(0)),
+        );
+        subject.privilege_dropper =
+            Box::new(PrivilegeDropperMock::new().expect_privilege_result(true));
+        let mut holder = FakeStreamHolder::new();
+        let args = vec![
+            "MASQNode".to_string(),
+            "--generate-systemd-unit".to_string(),
+        ];
+
+        let result = subject.go(&args, &mut holder.streams());
+
+        assert_eq!(result, 0);
+        assert_eq!(holder.stderr.get_string(), "");
+        assert_eq!(*params_arc.lock().unwrap(), vec![args]);
+    }
+
     #[cfg(not(target_os = "windows"))]
     #[test]
     fn privilege_mismatch_messages() {
@@ -3,6 +3,7 @@ use crate::bootstrapper::PortConfiguration;
 use crate::discriminator::DiscriminatorFactory;
 use crate::json_masquerader::JsonMasquerader;
 use crate::masquerader::Masquerader;
+use crate::neighborhood::malefactor_ban_dao::MALEFACTOR_BAN_CACHE;
 use crate::stream_messages::*;
 use crate::stream_reader::StreamReaderReal;
 use crate::stream_writer_sorted::StreamWriterSorted;
@@ -308,6 +309,16 @@ impl StreamHandlerPool {
         msg: AddStreamMsg,
     ) -> <Self as Handler<AddStreamMsg>>::Result {
         let port_config = msg.port_configuration.clone();
+        if port_config.is_clandestine
+            && MALEFACTOR_BAN_CACHE.is_ip_banned(msg.connection_info.peer_addr.ip())
+        {
+            info!(
+                self.logger,
+                "Refusing clandestine stream with banned malefactor at {}",
+                msg.connection_info.peer_addr
+            );
+            return;
+        }
         self.set_up_stream_writer(
             msg.connection_info.writer,
             msg.connection_info.peer_addr,
@@ -552,6 +563,7 @@ mod tests {
     use crate::json_discriminator_factory::JsonDiscriminatorFactory;
     use crate::json_masquerader::JsonMasquerader;
     use crate::masquerader::Masquerader;
+    use crate::neighborhood::malefactor_ban_dao::{Malefactor, MalefactorBan};
     use crate::node_test_utils::FailingMasquerader;
     use crate::sub_lib::cryptde::CryptDE;
     use crate::sub_lib::dispatcher::InboundClientData;
@@ -583,6 +595,7 @@ mod tests {
     use std::sync::Arc;
     use std::sync::Mutex;
     use std::thread;
+    use std::time::SystemTime;
     use tokio::prelude::Async;
 
     struct TrafficAnalyzerMock {}
@@ -711,6 +724,42 @@ mod tests {
         assert_eq!(dispatcher_recording.len(), 4);
     }
 
+    #[test]
+    fn clandestine_stream_with_banned_malefactor_is_refused() {
+        init_test_logging();
+        let peer_addr = SocketAddr::from_str("10.45.0.1:5673").unwrap();
+        let ban = MalefactorBan {
+            id: 1,
+            malefactor: Malefactor {
+                ip_address_opt: Some(peer_addr.ip()),
+                ..Malefactor::default()
+            },
+            reason: "Bad Gossip".to_string(),
+            banned_at: SystemTime::now(),
+            expires_at_opt: None,
+        };
+        MALEFACTOR_BAN_CACHE.insert(ban.clone());
+        let mut subject = StreamHandlerPool::new(vec![]);
+        let connection_info = ConnectionInfo {
+            reader: Box::new(ReadHalfWrapperMock::new()),
+            writer: Box::new(WriteHalfWrapperMock::new()),
+            local_addr: SocketAddr::from_str("1.2.3.4:5673").unwrap(),
+            peer_addr,
+        };
+
+        subject.handle_add_stream_msg(AddStreamMsg::new(
+            connection_info,
+            Some(5673),
+            PortConfiguration::new(vec![], true),
+        ));
+
+        MALEFACTOR_BAN_CACHE.remove(&ban);
+        assert!(subject.stream_writers.is_empty());
+        TestLogHandler::new().exists_log_containing(
+            "INFO: Dispatcher: Refusing clandestine stream with banned malefactor at 10.45.0.1:5673",
+        );
+    }
+
     #[test]
     fn stream_handler_pool_writes_data_to_stream_writer() {
         init_test_logging();
@@ -1,5 +1,5 @@
 // Copyright (c) 2017-2019, Substratum LLC (https://substratum.net) and/or its affiliates. All rights reserved.
-use crate::accountant::{ReceivedPayments, SentPayments};
+use crate::accountant::{PaymentCurves, ReceivedPayments, SentPayments};
 use crate::sub_lib::peer_actors::{BindMessage, StartMessage};
 use crate::sub_lib::wallet::Wallet;
 use actix::Message;
@@ -22,6 +22,7 @@ lazy_static! {
 pub struct AccountantConfig {
     pub payable_scan_interval: Duration,
     pub payment_received_scan_interval: Duration,
+    pub payment_curves: PaymentCurves,
 }
 
 #[derive(Clone)]
@@ -1,9 +1,10 @@
 // Copyright (c) 2019-2020, MASQ (https://masq.ai) and/or its affiliates. All rights reserved.
 
-use crate::neighborhood::node_record::NodeRecordInner_0v1;
+use crate::neighborhood::node_record::{NodeRecordInner_0v1, NodeRecordInner_0v2};
 use crate::sub_lib::cryptde::PublicKey;
 use crate::sub_lib::migrations::utils::value_to_type;
 use crate::sub_lib::neighborhood::RatePack;
+use crate::sub_lib::proxy_server::ProxyProtocol;
 use crate::sub_lib::versioned_data::{
     MigrationError, Migrations, StepError, VersionedData, FUTURE_VERSION,
 };
@@ -15,13 +16,18 @@ use std::convert::TryFrom;
 
 lazy_static! {
     pub static ref MIGRATIONS: Migrations = {
-        let current_version = dv!(0, 1);
+        let current_version = dv!(0, 2);
         let mut migrations = Migrations::new(current_version);
 
-        migrate_value!(dv!(0, 1), NodeRecordInner_0v1, NodeRecordInnerMF_0v1, {|value: serde_cbor::Value| {
-            NodeRecordInner_0v1::try_from (&value)
+        migrate_value!(dv!(0, 2), NodeRecordInner_0v2, NodeRecordInnerMF_0v2, {|value: serde_cbor::Value| {
+            NodeRecordInner_0v2::try_from (&value)
         }});
-        migrations.add_step (FUTURE_VERSION, dv!(0, 1), Box::new (NodeRecordInnerMF_0v1{}));
+        migrations.add_step (FUTURE_VERSION, dv!(0, 2), Box::new (NodeRecordInnerMF_0v2{}));
+
+        migrate_value!(dv!(0, 2), NodeRecordInner_0v2, NodeRecordInnerMF_0v1_0v2, {|value: serde_cbor::Value| {
+            NodeRecordInner_0v1::try_from (&value).map (NodeRecordInner_0v2::from)
+        }});
+        migrations.add_step (dv!(0, 1), dv!(0, 2), Box::new (NodeRecordInnerMF_0v1_0v2{}));
 
         // add more steps here
 
@@ -29,20 +35,39 @@ lazy_static! {
     };
 }
 
-impl Into<VersionedData<NodeRecordInner_0v1>> for NodeRecordInner_0v1 {
-    fn into(self) -> VersionedData<NodeRecordInner_0v1> {
+impl Into<VersionedData<NodeRecordInner_0v2>> for NodeRecordInner_0v2 {
+    fn into(self) -> VersionedData<NodeRecordInner_0v2> {
         VersionedData::new(&MIGRATIONS, &self)
     }
 }
 
-impl TryFrom<VersionedData<NodeRecordInner_0v1>> for NodeRecordInner_0v1 {
+impl TryFrom<VersionedData<NodeRecordInner_0v2>> for NodeRecordInner_0v2 {
     type Error = MigrationError;
 
-    fn try_from(vd: VersionedData<NodeRecordInner_0v1>) -> Result<Self, Self::Error> {
+    fn try_from(vd: VersionedData<NodeRecordInner_0v2>) -> Result<Self, Self::Error> {
         vd.extract(&MIGRATIONS)
     }
 }
 
+impl TryFrom<&Value> for NodeRecordInner_0v2 {
+    type Error = StepError;
+
+    fn try_from(value: &Value) -> Result<Self, Self::Error> {
+        let mut nri = NodeRecordInner_0v2::from(NodeRecordInner_0v1::try_from(value)?);
+        if let Value::Map(map) = value {
+            let field = |name: &str| map.get(&Value::Text(name.to_string()));
+            nri.proxy_protocols_opt =
+                field("proxy_protocols_opt").and_then(value_to_type::<Vec<ProxyProtocol>>);
+            nri.masqueraders_opt = field("masqueraders_opt").and_then(value_to_type::<Vec<String>>);
+            nri.max_bandwidth_opt = field("max_bandwidth_opt").and_then(value_to_type::<u64>);
+            nri.software_version_opt =
+                field("software_version_opt").and_then(value_to_type::<String>);
+            nri.country_code_opt = field("country_code_opt").and_then(value_to_type::<String>);
+        }
+        Ok(nri)
+    }
+}
+
 impl TryFrom<&Value> for NodeRecordInner_0v1 {
     type Error = StepError;
 
@@ -164,6 +189,44 @@ mod tests {
     use serde_derive::{Deserialize, Serialize};
     use std::iter::FromIterator;
 
+    fn make_nri_0v1() -> NodeRecordInner_0v1 {
+        NodeRecordInner_0v1 {
+            public_key: PublicKey::new(&[1, 2, 3, 4]),
+            earning_wallet: Wallet::new("0x0123456789012345678901234567890123456789"),
+            rate_pack: DEFAULT_RATE_PACK,
+            neighbors: BTreeSet::from_iter(
+                vec![PublicKey::new(&[2, 3, 4, 5]), PublicKey::new(&[3, 4, 5, 6])].into_iter(),
+            ),
+            accepts_connections: false,
+            routes_data: true,
+            version: 42,
+        }
+    }
+
+    fn make_nri_0v2() -> NodeRecordInner_0v2 {
+        let mut nri = NodeRecordInner_0v2::from(make_nri_0v1());
+        nri.proxy_protocols_opt = Some(vec![ProxyProtocol::TLS]);
+        nri.masqueraders_opt = Some(vec!["json".to_string()]);
+        nri.max_bandwidth_opt = Some(1_000_000);
+        nri.software_version_opt = Some("1.2.3".to_string());
+        nri.country_code_opt = Some("CZ".to_string());
+        nri
+    }
+
+    // The Migrations a Node that knows nothing later than NodeRecordInner_0v1 would use
+    fn make_0v1_migrations() -> Migrations {
+        migrate_value!(dv!(0, 1), NodeRecordInner_0v1, NodeRecordInnerMF_0v1, {
+            |value: serde_cbor::Value| NodeRecordInner_0v1::try_from(&value)
+        });
+        let mut migrations = Migrations::new(dv!(0, 1));
+        migrations.add_step(
+            FUTURE_VERSION,
+            dv!(0, 1),
+            Box::new(NodeRecordInnerMF_0v1 {}),
+        );
+        migrations
+    }
+
     #[test]
     fn can_migrate_from_the_future() {
         #[derive(Serialize, Deserialize)]
@@ -175,20 +238,15 @@ mod tests {
             pub accepts_connections: bool,
             pub routes_data: bool,
             pub version: u32,
+            pub proxy_protocols_opt: Option<Vec<ProxyProtocol>>,
+            pub masqueraders_opt: Option<Vec<String>>,
+            pub max_bandwidth_opt: Option<u64>,
+            pub software_version_opt: Option<String>,
+            pub country_code_opt: Option<String>,
             pub another_field: String,
             pub yet_another_field: u64,
         }
-        let expected_nri = NodeRecordInner_0v1 {
-            public_key: PublicKey::new(&[1, 2, 3, 4]),
-            earning_wallet: Wallet::new("0x0123456789012345678901234567890123456789"),
-            rate_pack: DEFAULT_RATE_PACK,
-            neighbors: BTreeSet::from_iter(
-                vec![PublicKey::new(&[2, 3, 4, 5]), PublicKey::new(&[3, 4, 5, 6])].into_iter(),
-            ),
-            accepts_connections: false,
-            routes_data: true,
-            version: 42,
-        };
+        let expected_nri = make_nri_0v2();
         let future_nri = ExampleFutureNRI {
             public_key: expected_nri.public_key.clone(),
             earning_wallet: expected_nri.earning_wallet.clone(),
@@ -197,6 +255,11 @@ mod tests {
             accepts_connections: expected_nri.accepts_connections,
             routes_data: expected_nri.routes_data,
             version: expected_nri.version,
+            proxy_protocols_opt: expected_nri.proxy_protocols_opt.clone(),
+            masqueraders_opt: expected_nri.masqueraders_opt.clone(),
+            max_bandwidth_opt: expected_nri.max_bandwidth_opt,
+            software_version_opt: expected_nri.software_version_opt.clone(),
+            country_code_opt: expected_nri.country_code_opt.clone(),
             another_field: "These are the times that try men's souls".to_string(),
             yet_another_field: 1234567890,
         };
@@ -204,13 +267,65 @@ mod tests {
         let serialized =
             serde_cbor::ser::to_vec(&VersionedData::new(&future_migrations, &future_nri)).unwrap();
         let future_vd =
-            serde_cbor::de::from_slice::<VersionedData<NodeRecordInner_0v1>>(&serialized).unwrap();
+            serde_cbor::de::from_slice::<VersionedData<NodeRecordInner_0v2>>(&serialized).unwrap();
 
-        let actual_nri = NodeRecordInner_0v1::try_from(future_vd).unwrap();
+        let actual_nri = NodeRecordInner_0v2::try_from(future_vd).unwrap();
 
         assert_eq!(actual_nri, expected_nri);
     }
 
+    #[test]
+    fn can_migrate_from_0v1() {
+        let nri_0v1 = make_nri_0v1();
+        let old_migrations = make_0v1_migrations();
+        let serialized =
+            serde_cbor::ser::to_vec(&VersionedData::new(&old_migrations, &nri_0v1)).unwrap();
+        let old_vd =
+            serde_cbor::de::from_slice::<VersionedData<NodeRecordInner_0v2>>(&serialized).unwrap();
+
+        let actual_nri = NodeRecordInner_0v2::try_from(old_vd).unwrap();
+
+        assert_eq!(actual_nri, NodeRecordInner_0v2::from(nri_0v1));
+        assert_eq!(actual_nri.proxy_protocols_opt, None);
+        assert_eq!(actual_nri.country_code_opt, None);
+    }
+
+    #[test]
+    fn nodes_that_know_only_0v1_can_migrate_from_0v2() {
+        let nri_0v2 = make_nri_0v2();
+        let vd: VersionedData<NodeRecordInner_0v2> = nri_0v2.into();
+        let serialized = serde_cbor::ser::to_vec(&vd).unwrap();
+        let new_vd =
+            serde_cbor::de::from_slice::<VersionedData<NodeRecordInner_0v1>>(&serialized).unwrap();
+
+        let actual_nri = new_vd.extract(&make_0v1_migrations()).unwrap();
+
+        assert_eq!(actual_nri, make_nri_0v1());
+    }
+
+    #[test]
+    fn capabilities_that_cannot_be_understood_are_dropped() {
+        let serialized = serde_cbor::ser::to_vec(&make_nri_0v1()).unwrap();
+        let mut map = match serde_cbor::de::from_slice::<Value>(&serialized).unwrap() {
+            Value::Map(map) => map,
+            x => panic!("Expected Value::Map, found {:?}", x),
+        };
+        map.insert(
+            Value::Text("max_bandwidth_opt".to_string()),
+            Value::Text("lots".to_string()),
+        );
+        map.insert(
+            Value::Text("country_code_opt".to_string()),
+            Value::Text("CZ".to_string()),
+        );
+
+        let result = NodeRecordInner_0v2::try_from(&Value::Map(map)).unwrap();
+
+        let mut expected_nri = NodeRecordInner_0v2::from(make_nri_0v1());
+        expected_nri.country_code_opt = Some("CZ".to_string());
+        assert_eq!(result, expected_nri);
+    }
+
     #[test]
     fn cannot_migrate_from_value_other_than_map() {
         let value = Value::Bool(true);
@@ -92,7 +92,10 @@ impl NeighborhoodMode {
     }
 
     pub fn routes_data(&self) -> bool {
-        matches!(self, NeighborhoodMode::Standard(_, _, _) | NeighborhoodMode::OriginateOnly(_, _))
+        matches!(
+            self,
+            NeighborhoodMode::Standard(_, _, _) | NeighborhoodMode::OriginateOnly(_, _)
+        )
     }
 
     pub fn is_standard(&self) -> bool {
@@ -110,6 +113,16 @@ impl NeighborhoodMode {
     pub fn is_zero_hop(&self) -> bool {
         matches!(self, NeighborhoodMode::ZeroHop)
     }
+
+    // The value of --neighborhood-mode that selects this mode
+    pub fn parameter_value(&self) -> &'static str {
+        match self {
+            NeighborhoodMode::Standard(_, _, _) => "standard",
+            NeighborhoodMode::ZeroHop => "zero-hop",
+            NeighborhoodMode::OriginateOnly(_, _) => "originate-only",
+            NeighborhoodMode::ConsumeOnly(_) => "consume-only",
+        }
+    }
 }
 
 #[derive(Clone, Debug, PartialEq, Eq, Hash, Serialize, Deserialize)]
@@ -167,13 +180,14 @@ impl From<(&NodeRecord, bool, &dyn CryptDE)> for NodeDescriptor {
 impl NodeDescriptor {
     pub fn from_str(cryptde: &dyn CryptDE, s: &str) -> Result<NodeDescriptor, String> {
         let (mainnet, pieces) = {
-            let chain_id = chain_id_from_name(DEFAULT_CHAIN_NAME);
+            let chain_id =
+                chain_id_from_name(DEFAULT_CHAIN_NAME).expect("Default chain is not built in");
             let delimiter = node_descriptor_delimiter(chain_id);
             let pieces: Vec<&str> = s.splitn(2, delimiter).collect();
             if pieces.len() == 2 {
                 (true, pieces)
             } else {
-                let chain_id = chain_id_from_name("ropsten");
+                let chain_id = chain_id_from_name("ropsten").expect("Ropsten is not built in");
                 let delimiter = node_descriptor_delimiter(chain_id);
                 let pieces: Vec<&str> = s.splitn(2, delimiter).collect();
                 if pieces.len() == 2 {
@@ -231,6 +245,44 @@ pub struct NeighborhoodConfig {
     pub mode: NeighborhoodMode,
 }
 
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum DebutApproval {
+    Automatic,
+    Manual,
+}
+
+impl Display for DebutApproval {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            DebutApproval::Automatic => write!(f, "automatic"),
+            DebutApproval::Manual => write!(f, "manual"),
+        }
+    }
+}
+
+impl FromStr for DebutApproval {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.to_lowercase().as_str() {
+            "automatic" => Ok(DebutApproval::Automatic),
+            "manual" => Ok(DebutApproval::Manual),
+            _ => Err(format!(
+                "Debut approval must be 'automatic' or 'manual', not '{}'",
+                s
+            )),
+        }
+    }
+}
+
+// Where to find Node descriptors to debut to when there are no usable neighbors, and the key
+// whose signature the list must carry, if any.
+#[derive(Clone, Debug, PartialEq)]
+pub struct BootstrapSource {
+    pub location: String,
+    pub public_key_opt: Option<PublicKey>,
+}
+
 lazy_static! {
     static ref EMPTY_CONFIGS: Vec<NodeDescriptor> = vec![];
 }
@@ -250,6 +302,7 @@ pub struct NeighborhoodSubs {
     pub set_consuming_wallet_sub: Recipient<SetConsumingWalletMessage>,
     pub from_ui_message_sub: Recipient<NodeFromUiMessage>,
     pub new_password_sub: Recipient<NewPasswordMessage>,
+    pub bootstrap_list_sub: Recipient<BootstrapListMessage>,
 }
 
 impl Debug for NeighborhoodSubs {
@@ -351,9 +404,19 @@ pub struct RemoveNeighborMessage {
     pub public_key: PublicKey,
 }
 
+// The Nodes on the bootstrap list, once it has been fetched in the background and verified
+#[derive(Clone, Debug, Message, PartialEq)]
+pub struct BootstrapListMessage {
+    pub descriptors_result: Result<Vec<NodeDescriptor>, String>,
+}
+
 #[derive(Clone, Debug, Message, PartialEq)]
 pub enum NodeRecordMetadataMessage {
     Desirable(PublicKey, bool),
+    // The first response over a route through these Nodes arrived this many milliseconds after the request
+    RoundTrip(Vec<PublicKey>, u32),
+    // A request over a route through these Nodes failed or was never answered
+    Failure(Vec<PublicKey>),
 }
 
 #[derive(Clone, Debug, Deserialize, Eq, Hash, PartialEq, Serialize)]
@@ -438,6 +501,7 @@ mod tests {
             set_consuming_wallet_sub: recipient!(recorder, SetConsumingWalletMessage),
             from_ui_message_sub: recipient!(recorder, NodeFromUiMessage),
             new_password_sub: recipient!(recorder, NewPasswordMessage),
+            bootstrap_list_sub: recipient!(recorder, BootstrapListMessage),
         };
 
         assert_eq!(format!("{:?}", subject), "NeighborhoodSubs");
@@ -648,6 +712,7 @@ mod tests {
         assert!(!subject.is_originate_only());
         assert!(!subject.is_consume_only());
         assert!(!subject.is_zero_hop());
+        assert_eq!(subject.parameter_value(), "standard");
     }
 
     #[test]
@@ -672,6 +737,7 @@ mod tests {
         assert!(subject.is_originate_only());
         assert!(!subject.is_consume_only());
         assert!(!subject.is_zero_hop());
+        assert_eq!(subject.parameter_value(), "originate-only");
     }
 
     #[test]
@@ -694,6 +760,7 @@ mod tests {
         assert!(!subject.is_originate_only());
         assert!(subject.is_consume_only());
         assert!(!subject.is_zero_hop());
+        assert_eq!(subject.parameter_value(), "consume-only");
     }
 
     #[test]
@@ -709,6 +776,7 @@ mod tests {
         assert!(!subject.is_originate_only());
         assert!(!subject.is_consume_only());
         assert!(subject.is_zero_hop());
+        assert_eq!(subject.parameter_value(), "zero-hop");
     }
 
     #[test]
@@ -733,4 +801,16 @@ mod tests {
             assert_eq!(&gf.to_string(), expected_string);
         });
     }
+
+    #[test]
+    fn debut_approval_round_trips_through_strings() {
+        vec![DebutApproval::Automatic, DebutApproval::Manual]
+            .into_iter()
+            .for_each(|da| assert_eq!(DebutApproval::from_str(&da.to_string()), Ok(da)));
+        assert_eq!(DebutApproval::from_str("MANUAL"), Ok(DebutApproval::Manual));
+        assert_eq!(
+            DebutApproval::from_str("sometimes"),
+            Err("Debut approval must be 'automatic' or 'manual', not 'sometimes'".to_string())
+        );
+    }
 }
@@ -4,7 +4,7 @@ use crate::sub_lib::data_version::DataVersion;
 use crate::sub_lib::dispatcher::InboundClientData;
 use crate::sub_lib::dispatcher::StreamShutdownMsg;
 use crate::sub_lib::hopper::{ExpiredCoresPackage, MessageType};
-use crate::sub_lib::neighborhood::{ExpectedService, RouteQueryResponse};
+use crate::sub_lib::neighborhood::{ExpectedService, RemoveNeighborMessage, RouteQueryResponse};
 use crate::sub_lib::peer_actors::BindMessage;
 use crate::sub_lib::proxy_client::{ClientResponsePayload_0v1, DnsResolveFailure_0v1};
 use crate::sub_lib::sequence_buffer::SequencedPacket;
@@ -13,6 +13,7 @@ use crate::sub_lib::stream_key::StreamKey;
 use crate::sub_lib::versioned_data::VersionedData;
 use actix::Message;
 use actix::Recipient;
+use masq_lib::exit_country::ExitCountryPreference;
 use serde_derive::{Deserialize, Serialize};
 use std::fmt::Debug;
 
@@ -67,6 +68,11 @@ pub struct AddRouteMessage {
     pub route: RouteQueryResponse,
 }
 
+#[derive(Message, Clone, Debug, PartialEq)]
+pub struct SetExitCountryMessage {
+    pub preference_opt: Option<ExitCountryPreference>,
+}
+
 #[derive(Clone)]
 pub struct ProxyServerSubs {
     // ProxyServer will handle these messages:
@@ -78,6 +84,8 @@ pub struct ProxyServerSubs {
     pub add_route: Recipient<AddRouteMessage>,
     pub stream_shutdown_sub: Recipient<StreamShutdownMsg>,
     pub set_consuming_wallet_sub: Recipient<SetConsumingWalletMessage>,
+    pub set_exit_country_sub: Recipient<SetExitCountryMessage>,
+    pub remove_neighbor_sub: Recipient<RemoveNeighborMessage>,
 }
 
 impl Debug for ProxyServerSubs {
@@ -109,6 +117,8 @@ mod tests {
             add_route: recipient!(recorder, AddRouteMessage),
             stream_shutdown_sub: recipient!(recorder, StreamShutdownMsg),
             set_consuming_wallet_sub: recipient!(recorder, SetConsumingWalletMessage),
+            set_exit_country_sub: recipient!(recorder, SetExitCountryMessage),
+            remove_neighbor_sub: recipient!(recorder, RemoveNeighborMessage),
         };
 
         assert_eq!(format!("{:?}", subject), "ProxyServerSubs");
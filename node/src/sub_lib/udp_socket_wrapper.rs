@@ -4,9 +4,11 @@ use std::marker::Send;
 use std::net::SocketAddr;
 use tokio::net::UdpSocket;
 use tokio::prelude::Async;
+use tokio::reactor::Handle;
 
 pub trait UdpSocketWrapperTrait: Sync + Send {
     fn bind(&mut self, addr: SocketAddr) -> io::Result<bool>;
+    fn adopt(&mut self, socket: std::net::UdpSocket) -> io::Result<()>;
     fn recv_from(&mut self, buf: &mut [u8]) -> Result<Async<(usize, SocketAddr)>, io::Error>;
     fn send_to(&mut self, buf: &[u8], addr: SocketAddr) -> Result<Async<usize>, io::Error>;
 }
@@ -29,6 +31,11 @@ impl UdpSocketWrapperTrait for UdpSocketWrapperReal {
         Ok(true)
     }
 
+    fn adopt(&mut self, socket: std::net::UdpSocket) -> io::Result<()> {
+        self.delegate = Some(UdpSocket::from_std(socket, &Handle::default())?);
+        Ok(())
+    }
+
     fn recv_from(&mut self, buf: &mut [u8]) -> Result<Async<(usize, SocketAddr)>, io::Error> {
         match self.delegate {
             Some(ref mut socket) => socket.poll_recv_from(buf),
@@ -0,0 +1,610 @@
+// Copyright (c) 2019-2020, MASQ (https://masq.ai) and/or its affiliates. All rights reserved.
+// Because we have conditional compilation going on in this file:
+#![allow(dead_code)]
+#![allow(unused_imports)]
+
+use futures::{Future, Stream};
+use lazy_static::lazy_static;
+use masq_lib::constants::DEFAULT_UI_PORT;
+use std::collections::HashMap;
+use std::env;
+use std::net::{TcpListener, UdpSocket};
+use std::sync::Mutex;
+use std::time::{Duration, Instant};
+use tokio::timer::Interval;
+
+#[cfg(target_os = "linux")]
+use nix::fcntl::{fcntl, FcntlArg, FdFlag};
+#[cfg(target_os = "linux")]
+use nix::sys::socket::UnixAddr;
+#[cfg(target_os = "linux")]
+use nix::sys::socket::{sendto, socket, AddressFamily, MsgFlags, SockAddr, SockFlag, SockType};
+#[cfg(target_os = "linux")]
+use nix::unistd::close;
+#[cfg(target_os = "linux")]
+use std::os::unix::io::{FromRawFd, RawFd};
+
+pub const GENERATE_SYSTEMD_UNIT_FLAG: &str = "--generate-systemd-unit";
+pub const UI_SOCKET_NAME: &str = "ui";
+pub const DNS_SOCKET_NAME: &str = "dns";
+pub const DNS_TCP_SOCKET_NAME: &str = "dns-tcp";
+pub const WATCHDOG_SECS: u64 = 60;
+
+// The variables systemd uses to talk to the process it started. Children we spawn are not
+// that process, so they shouldn't see them.
+pub const NOTIFICATION_VARIABLES: [&str; 3] = ["NOTIFY_SOCKET", "WATCHDOG_USEC", "WATCHDOG_PID"];
+const LISTEN_VARIABLES: [&str; 3] = ["LISTEN_PID", "LISTEN_FDS", "LISTEN_FDNAMES"];
+const LISTEN_FDS_START: i32 = 3;
+const UNNAMED_SOCKET: &str = "unknown";
+
+#[cfg(target_os = "linux")]
+lazy_static! {
+    // Read once, because the environment is cleared afterward so that children don't try to
+    // adopt sockets that belong to us.
+    static ref ACTIVATED_SOCKETS: Mutex<HashMap<String, RawFd>> = Mutex::new(adopt_listen_fds());
+}
+
+pub fn notify_ready() {
+    notify("READY=1");
+}
+
+pub fn notify_stopping() {
+    notify("STOPPING=1");
+}
+
+pub fn notify_watchdog() {
+    notify("WATCHDOG=1");
+}
+
+// Must be called from inside a running actix System. Pings come from the event loop itself, so
+// if the loop wedges, the pings stop and systemd restarts us.
+pub fn start_watchdog() {
+    if let Some(timeout) = watchdog_timeout() {
+        let period = timeout / 2;
+        actix::spawn(
+            Interval::new(Instant::now() + period, period)
+                .for_each(|_| {
+                    notify_watchdog();
+                    Ok(())
+                })
+                .map_err(|_| ()),
+        );
+    }
+}
+
+pub fn watchdog_timeout() -> Option<Duration> {
+    parse_watchdog_timeout(
+        env::var("WATCHDOG_USEC").ok(),
+        env::var("WATCHDOG_PID").ok(),
+        std::process::id(),
+    )
+}
+
+#[cfg(target_os = "linux")]
+pub fn is_socket_activated(name: &str) -> bool {
+    ACTIVATED_SOCKETS
+        .lock()
+        .expect("Activated sockets are poisoned")
+        .contains_key(name)
+}
+
+#[cfg(not(target_os = "linux"))]
+pub fn is_socket_activated(_name: &str) -> bool {
+    false
+}
+
+#[cfg(target_os = "linux")]
+pub fn take_activated_tcp_listener(name: &str) -> Option<TcpListener> {
+    take_activated_fd(name).map(|fd| unsafe { TcpListener::from_raw_fd(fd) })
+}
+
+#[cfg(not(target_os = "linux"))]
+pub fn take_activated_tcp_listener(_name: &str) -> Option<TcpListener> {
+    None
+}
+
+#[cfg(target_os = "linux")]
+pub fn take_activated_udp_socket(name: &str) -> Option<UdpSocket> {
+    take_activated_fd(name).map(|fd| unsafe { UdpSocket::from_raw_fd(fd) })
+}
+
+#[cfg(not(target_os = "linux"))]
+pub fn take_activated_udp_socket(_name: &str) -> Option<UdpSocket> {
+    None
+}
+
+// args are the arguments MASQNode was started with, program name first. Everything but the
+// program name and the generate flag ends up on the ExecStart line.
+pub fn generate_units(exe_path: &str, args: &[String]) -> String {
+    let exec_args = args
+        .iter()
+        .skip(1)
+        .filter(|arg| arg.as_str() != GENERATE_SYSTEMD_UNIT_FLAG)
+        .cloned()
+        .collect::<Vec<String>>();
+    let daemon = exec_args.contains(&"--initialization".to_string());
+    let (prefix, description) = if daemon {
+        ("masq-daemon", "MASQ Daemon")
+    } else {
+        ("masq-node", "MASQ Node")
+    };
+    let service_name = format!("{}.service", prefix);
+    let mut sockets = vec![(
+        format!("{}-ui.socket", prefix),
+        UI_SOCKET_NAME,
+        format!("ListenStream=127.0.0.1:{}", ui_port_from_args(&exec_args)),
+    )];
+    if !daemon {
+        sockets.push((
+            format!("{}-dns.socket", prefix),
+            DNS_SOCKET_NAME,
+            "ListenDatagram=127.0.0.1:53".to_string(),
+        ));
+        sockets.push((
+            format!("{}-dns-tcp.socket", prefix),
+            DNS_TCP_SOCKET_NAME,
+            "ListenStream=127.0.0.1:53".to_string(),
+        ));
+    }
+    let socket_names = sockets
+        .iter()
+        .map(|(name, _, _)| name.as_str())
+        .collect::<Vec<&str>>()
+        .join(" ");
+    let exec_start = std::iter::once(exe_path.to_string())
+        .chain(exec_args.into_iter())
+        .map(|arg| quote_exec_argument(&arg))
+        .collect::<Vec<String>>()
+        .join(" ");
+    // The Daemon changes the system's DNS settings, so it still has to run as root; the Node
+    // gets its privileged ports from systemd and the one capability it needs.
+    let unprivileged_lines = if daemon {
+        String::new()
+    } else {
+        "User=masq\n\
+         Group=masq\n\
+         AmbientCapabilities=CAP_NET_BIND_SERVICE\n\
+         CapabilityBoundingSet=CAP_NET_BIND_SERVICE\n\
+         NoNewPrivileges=true\n"
+            .to_string()
+    };
+    let mut units = format!(
+        "# /etc/systemd/system/{service_name}\n\
+         [Unit]\n\
+         Description={description}\n\
+         Wants=network-online.target\n\
+         After=network-online.target\n\
+         Requires={socket_names}\n\
+         \n\
+         [Service]\n\
+         Type=notify\n\
+         NotifyAccess=main\n\
+         ExecStart={exec_start}\n\
+         Sockets={socket_names}\n\
+         {unprivileged_lines}\
+         WatchdogSec={watchdog_secs}\n\
+         Restart=on-failure\n\
+         \n\
+         [Install]\n\
+         WantedBy=multi-user.target\n",
+        service_name = service_name,
+        description = description,
+        socket_names = socket_names,
+        exec_start = exec_start,
+        unprivileged_lines = unprivileged_lines,
+        watchdog_secs = WATCHDOG_SECS,
+    );
+    sockets.iter().for_each(|(socket_name, fd_name, listen)| {
+        units.push_str(&format!(
+            "\n# /etc/systemd/system/{socket_name}\n\
+             [Unit]\n\
+             Description={description} {fd_name} socket\n\
+             PartOf={service_name}\n\
+             \n\
+             [Socket]\n\
+             {listen}\n\
+             FileDescriptorName={fd_name}\n\
+             Service={service_name}\n\
+             \n\
+             [Install]\n\
+             WantedBy=sockets.target\n",
+            socket_name = socket_name,
+            description = description,
+            fd_name = fd_name,
+            service_name = service_name,
+            listen = listen,
+        ))
+    });
+    units
+}
+
+fn notify(state: &str) {
+    if let Ok(address) = env::var("NOTIFY_SOCKET") {
+        // Notifications are advisory: if systemd can't hear us, it will time us out on its own.
+        let _ = send_notification(&address, state);
+    }
+}
+
+#[cfg(target_os = "linux")]
+fn send_notification(address: &str, state: &str) -> Result<(), String> {
+    let unix_addr = if address.starts_with('@') {
+        UnixAddr::new_abstract(address[1..].as_bytes())
+    } else {
+        UnixAddr::new(address)
+    }
+    .map_err(|e| format!("Bad notification socket '{}': {}", address, e))?;
+    let fd = socket(
+        AddressFamily::Unix,
+        SockType::Datagram,
+        SockFlag::SOCK_CLOEXEC,
+        None,
+    )
+    .map_err(|e| format!("Couldn't create notification socket: {}", e))?;
+    let result = sendto(
+        fd,
+        state.as_bytes(),
+        &SockAddr::Unix(unix_addr),
+        MsgFlags::empty(),
+    );
+    let _ = close(fd);
+    result
+        .map(|_| ())
+        .map_err(|e| format!("Couldn't notify '{}': {}", address, e))
+}
+
+#[cfg(not(target_os = "linux"))]
+fn send_notification(_address: &str, _state: &str) -> Result<(), String> {
+    Ok(())
+}
+
+#[cfg(target_os = "linux")]
+fn adopt_listen_fds() -> HashMap<String, RawFd> {
+    let fds = parse_listen_fds(
+        env::var("LISTEN_PID").ok(),
+        env::var("LISTEN_FDS").ok(),
+        env::var("LISTEN_FDNAMES").ok(),
+        std::process::id(),
+    );
+    LISTEN_VARIABLES
+        .iter()
+        .for_each(|variable| env::remove_var(variable));
+    fds.into_iter()
+        .map(|(name, fd)| {
+            let _ = fcntl(fd, FcntlArg::F_SETFD(FdFlag::FD_CLOEXEC));
+            (name, fd)
+        })
+        .collect()
+}
+
+#[cfg(target_os = "linux")]
+fn take_activated_fd(name: &str) -> Option<RawFd> {
+    ACTIVATED_SOCKETS
+        .lock()
+        .expect("Activated sockets are poisoned")
+        .remove(name)
+}
+
+fn parse_watchdog_timeout(
+    usec_opt: Option<String>,
+    pid_opt: Option<String>,
+    own_pid: u32,
+) -> Option<Duration> {
+    if let Some(pid) = pid_opt {
+        if pid.parse::<u32>().ok() != Some(own_pid) {
+            return None;
+        }
+    }
+    match usec_opt.and_then(|usec| usec.parse::<u64>().ok()) {
+        Some(0) | None => None,
+        Some(usec) => Some(Duration::from_micros(usec)),
+    }
+}
+
+fn parse_listen_fds(
+    pid_opt: Option<String>,
+    fds_opt: Option<String>,
+    names_opt: Option<String>,
+    own_pid: u32,
+) -> Vec<(String, i32)> {
+    if pid_opt.and_then(|pid| pid.parse::<u32>().ok()) != Some(own_pid) {
+        return vec![];
+    }
+    let count = match fds_opt.and_then(|fds| fds.parse::<usize>().ok()) {
+        Some(count) if count > 0 => count,
+        _ => return vec![],
+    };
+    let names = names_opt
+        .map(|names| {
+            names
+                .split(':')
+                .map(|name| name.to_string())
+                .collect::<Vec<String>>()
+        })
+        .filter(|names| names.len() == count)
+        .unwrap_or_else(|| vec![UNNAMED_SOCKET.to_string(); count]);
+    names.into_iter().zip(LISTEN_FDS_START..).collect()
+}
+
+fn ui_port_from_args(args: &[String]) -> u16 {
+    args.iter()
+        .enumerate()
+        .find_map(|(index, arg)| {
+            if arg == "--ui-port" {
+                args.get(index + 1).and_then(|value| value.parse().ok())
+            } else if arg.starts_with("--ui-port=") {
+                arg["--ui-port=".len()..].parse().ok()
+            } else {
+                None
+            }
+        })
+        .unwrap_or(DEFAULT_UI_PORT)
+}
+
+fn quote_exec_argument(arg: &str) -> String {
+    let escaped = arg.replace('%', "%%").replace('$', "$$");
+    if escaped.is_empty()
+        || escaped
+            .chars()
+            .any(|c| c.is_whitespace() || c == '"' || c == '\'' || c == '\\')
+    {
+        format!("\"{}\"", escaped.replace('\\', "\\\\").replace('"', "\\\""))
+    } else {
+        escaped
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn strings(strs: Vec<&str>) -> Vec<String> {
+        strs.into_iter().map(|s| s.to_string()).collect()
+    }
+
+    #[test]
+    fn watchdog_timeout_is_honored_only_for_this_process() {
+        assert_eq!(
+            parse_watchdog_timeout(Some("30000000".to_string()), None, 1234),
+            Some(Duration::from_secs(30))
+        );
+        assert_eq!(
+            parse_watchdog_timeout(Some("30000000".to_string()), Some("1234".to_string()), 1234),
+            Some(Duration::from_secs(30))
+        );
+        assert_eq!(
+            parse_watchdog_timeout(Some("30000000".to_string()), Some("4321".to_string()), 1234),
+            None
+        );
+        assert_eq!(
+            parse_watchdog_timeout(Some("0".to_string()), None, 1234),
+            None
+        );
+        assert_eq!(
+            parse_watchdog_timeout(Some("booga".to_string()), None, 1234),
+            None
+        );
+        assert_eq!(parse_watchdog_timeout(None, None, 1234), None);
+    }
+
+    #[test]
+    fn listen_fds_are_named_and_numbered_from_three() {
+        let result = parse_listen_fds(
+            Some("1234".to_string()),
+            Some("2".to_string()),
+            Some("ui:dns".to_string()),
+            1234,
+        );
+
+        assert_eq!(result, vec![("ui".to_string(), 3), ("dns".to_string(), 4)]);
+    }
+
+    #[test]
+    fn listen_fds_without_matching_names_are_unknown() {
+        let without_names =
+            parse_listen_fds(Some("1234".to_string()), Some("2".to_string()), None, 1234);
+        let with_too_few_names = parse_listen_fds(
+            Some("1234".to_string()),
+            Some("2".to_string()),
+            Some("ui".to_string()),
+            1234,
+        );
+
+        let expected = vec![("unknown".to_string(), 3), ("unknown".to_string(), 4)];
+        assert_eq!(without_names, expected);
+        assert_eq!(with_too_few_names, expected);
+    }
+
+    #[test]
+    fn listen_fds_for_another_process_or_malformed_are_ignored() {
+        assert!(parse_listen_fds(
+            Some("4321".to_string()),
+            Some("1".to_string()),
+            Some("ui".to_string()),
+            1234
+        )
+        .is_empty());
+        assert!(
+            parse_listen_fds(None, Some("1".to_string()), Some("ui".to_string()), 1234).is_empty()
+        );
+        assert!(parse_listen_fds(
+            Some("1234".to_string()),
+            Some("booga".to_string()),
+            None,
+            1234
+        )
+        .is_empty());
+        assert!(
+            parse_listen_fds(Some("1234".to_string()), Some("0".to_string()), None, 1234)
+                .is_empty()
+        );
+    }
+
+    #[test]
+    fn ui_port_is_found_in_either_form_or_defaulted() {
+        assert_eq!(
+            ui_port_from_args(&strings(vec!["--chain", "ropsten", "--ui-port", "1234"])),
+            1234
+        );
+        assert_eq!(ui_port_from_args(&strings(vec!["--ui-port=2345"])), 2345);
+        assert_eq!(
+            ui_port_from_args(&strings(vec!["--chain", "ropsten"])),
+            DEFAULT_UI_PORT
+        );
+    }
+
+    #[test]
+    fn exec_arguments_are_quoted_only_when_necessary() {
+        assert_eq!(quote_exec_argument("--chain"), "--chain");
+        assert_eq!(
+            quote_exec_argument("/var/lib/masq node"),
+            "\"/var/lib/masq node\""
+        );
+        assert_eq!(quote_exec_argument("say \"hi\""), "\"say \\\"hi\\\"\"");
+        assert_eq!(quote_exec_argument("100%$"), "100%%$$");
+        assert_eq!(quote_exec_argument(""), "\"\"");
+    }
+
+    #[test]
+    fn node_units_activate_ui_and_dns_sockets_and_drop_root() {
+        let args = strings(vec![
+            "MASQNode",
+            "--generate-systemd-unit",
+            "--chain",
+            "ropsten",
+            "--ui-port",
+            "5444",
+        ]);
+
+        let result = generate_units("/usr/local/bin/MASQNode", &args);
+
+        assert_eq!(
+            result,
+            "# /etc/systemd/system/masq-node.service\n\
+             [Unit]\n\
+             Description=MASQ Node\n\
+             Wants=network-online.target\n\
+             After=network-online.target\n\
+             Requires=masq-node-ui.socket masq-node-dns.socket masq-node-dns-tcp.socket\n\
+             \n\
+             [Service]\n\
+             Type=notify\n\
+             NotifyAccess=main\n\
+             ExecStart=/usr/local/bin/MASQNode --chain ropsten --ui-port 5444\n\
+             Sockets=masq-node-ui.socket masq-node-dns.socket masq-node-dns-tcp.socket\n\
+             User=masq\n\
+             Group=masq\n\
+             AmbientCapabilities=CAP_NET_BIND_SERVICE\n\
+             CapabilityBoundingSet=CAP_NET_BIND_SERVICE\n\
+             NoNewPrivileges=true\n\
+             WatchdogSec=60\n\
+             Restart=on-failure\n\
+             \n\
+             [Install]\n\
+             WantedBy=multi-user.target\n\
+             \n\
+             # /etc/systemd/system/masq-node-ui.socket\n\
+             [Unit]\n\
+             Description=MASQ Node ui socket\n\
+             PartOf=masq-node.service\n\
+             \n\
+             [Socket]\n\
+             ListenStream=127.0.0.1:5444\n\
+             FileDescriptorName=ui\n\
+             Service=masq-node.service\n\
+             \n\
+             [Install]\n\
+             WantedBy=sockets.target\n\
+             \n\
+             # /etc/systemd/system/masq-node-dns.socket\n\
+             [Unit]\n\
+             Description=MASQ Node dns socket\n\
+             PartOf=masq-node.service\n\
+             \n\
+             [Socket]\n\
+             ListenDatagram=127.0.0.1:53\n\
+             FileDescriptorName=dns\n\
+             Service=masq-node.service\n\
+             \n\
+             [Install]\n\
+             WantedBy=sockets.target\n\
+             \n\
+             # /etc/systemd/system/masq-node-dns-tcp.socket\n\
+             [Unit]\n\
+             Description=MASQ Node dns-tcp socket\n\
+             PartOf=masq-node.service\n\
+             \n\
+             [Socket]\n\
+             ListenStream=127.0.0.1:53\n\
+             FileDescriptorName=dns-tcp\n\
+             Service=masq-node.service\n\
+             \n\
+             [Install]\n\
+             WantedBy=sockets.target\n"
+        );
+    }
+
+    #[test]
+    fn daemon_units_activate_only_the_ui_socket_and_keep_root() {
+        let args = strings(vec![
+            "MASQNode",
+            "--initialization",
+            "--generate-systemd-unit",
+        ]);
+
+        let result = generate_units("/opt/masq/MASQNode", &args);
+
+        assert!(
+            result.starts_with("# /etc/systemd/system/masq-daemon.service\n"),
+            "{}",
+            result
+        );
+        assert!(
+            result.contains("ExecStart=/opt/masq/MASQNode --initialization\n"),
+            "{}",
+            result
+        );
+        assert!(
+            result.contains("Requires=masq-daemon-ui.socket\n"),
+            "{}",
+            result
+        );
+        assert!(
+            result.contains("ListenStream=127.0.0.1:5333\n"),
+            "{}",
+            result
+        );
+        assert!(!result.contains("User="), "{}", result);
+        assert!(!result.contains("ListenDatagram="), "{}", result);
+        assert!(!result.contains("dns-tcp"), "{}", result);
+    }
+
+    #[cfg(target_os = "linux")]
+    #[test]
+    fn notifications_reach_the_notification_socket() {
+        use masq_lib::test_utils::utils::ensure_node_home_directory_exists;
+        use std::os::unix::net::UnixDatagram;
+
+        let home_dir = ensure_node_home_directory_exists(
+            "systemd",
+            "notifications_reach_the_notification_socket",
+        );
+        let socket_path = home_dir.join("notify");
+        let _ = std::fs::remove_file(&socket_path);
+        let listener = UnixDatagram::bind(&socket_path).unwrap();
+        let mut buf = [0u8; 64];
+
+        let result = send_notification(socket_path.to_str().unwrap(), "READY=1");
+
+        assert_eq!(result, Ok(()));
+        let len = listener.recv(&mut buf).unwrap();
+        assert_eq!(&buf[..len], b"READY=1");
+    }
+
+    #[cfg(target_os = "linux")]
+    #[test]
+    fn notification_to_missing_socket_is_an_error() {
+        let result = send_notification("/nonexistent/notify", "READY=1");
+
+        assert!(result.is_err(), "{:?}", result);
+    }
+}
@@ -3,7 +3,7 @@ use crate::blockchain::blockchain_interface::chain_id_from_name;
 use crate::bootstrapper::BootstrapperConfig;
 use crate::neighborhood::gossip::GossipNodeRecord;
 use crate::neighborhood::neighborhood_database::NeighborhoodDatabase;
-use crate::neighborhood::node_record::{NodeRecord, NodeRecordInner_0v1};
+use crate::neighborhood::node_record::{NodeRecord, NodeRecordInner_0v2};
 use crate::neighborhood::{AccessibleGossipRecord, Neighborhood};
 use crate::sub_lib::cryptde::PublicKey;
 use crate::sub_lib::cryptde::{CryptDE, PlainData};
@@ -92,7 +92,7 @@ pub fn neighborhood_from_nodes(
                 root.node_addr_opt().unwrap(),
                 vec![NodeDescriptor::from((
                     neighbor,
-                    DEFAULT_CHAIN_ID == chain_id_from_name(DEFAULT_CHAIN_NAME),
+                    DEFAULT_CHAIN_ID == chain_id_from_name(DEFAULT_CHAIN_NAME).unwrap(),
                     cryptde,
                 ))],
                 root.rate_pack().clone(),
@@ -198,9 +198,9 @@ impl PartialEq for NodeRecord {
         if self.signature != other.signature {
             return false;
         }
-        let self_nri: NodeRecordInner_0v1 =
+        let self_nri: NodeRecordInner_0v2 =
             serde_cbor::de::from_slice(self.signed_gossip.as_slice()).unwrap();
-        let other_nri: NodeRecordInner_0v1 =
+        let other_nri: NodeRecordInner_0v2 =
             serde_cbor::de::from_slice(other.signed_gossip.as_slice()).unwrap();
         self_nri == other_nri
     }
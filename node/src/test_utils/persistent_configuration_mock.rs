@@ -1,5 +1,6 @@
 // Copyright (c) 2019-2020, MASQ (https://masq.ai) and/or its affiliates. All rights reserved.
 
+use crate::accountant::PaymentCurves;
 use crate::db_config::persistent_configuration::{PersistentConfigError, PersistentConfiguration};
 use crate::sub_lib::cryptde::PlainData;
 use crate::sub_lib::neighborhood::NodeDescriptor;
@@ -21,6 +22,9 @@ pub struct PersistentConfigurationMock {
     gas_price_results: RefCell<Vec<Result<Option<u64>, PersistentConfigError>>>,
     set_gas_price_params: Arc<Mutex<Vec<u64>>>,
     set_gas_price_results: RefCell<Vec<Result<(), PersistentConfigError>>>,
+    payment_curves_results: RefCell<Vec<Result<Option<PaymentCurves>, PersistentConfigError>>>,
+    set_payment_curves_params: Arc<Mutex<Vec<PaymentCurves>>>,
+    set_payment_curves_results: RefCell<Vec<Result<(), PersistentConfigError>>>,
     mnemonic_seed_params: Arc<Mutex<Vec<String>>>,
     mnemonic_seed_results: RefCell<Vec<Result<Option<PlainData>, PersistentConfigError>>>,
     mnemonic_seed_exists_params: Arc<Mutex<Vec<()>>>,
@@ -90,6 +94,27 @@ impl PersistentConfiguration for PersistentConfigurationMock {
         self.set_gas_price_results.borrow_mut().remove(0)
     }
 
+    fn payment_curves(&self) -> Result<Option<PaymentCurves>, PersistentConfigError> {
+        if self.payment_curves_results.borrow().is_empty() {
+            return Ok(None);
+        }
+        Self::result_from(&self.payment_curves_results)
+    }
+
+    fn set_payment_curves(
+        &mut self,
+        payment_curves: &PaymentCurves,
+    ) -> Result<(), PersistentConfigError> {
+        self.set_payment_curves_params
+            .lock()
+            .unwrap()
+            .push(payment_curves.clone());
+        if self.set_payment_curves_results.borrow().is_empty() {
+            return Ok(());
+        }
+        Self::result_from(&self.set_payment_curves_results)
+    }
+
     fn mnemonic_seed(&self, db_password: &str) -> Result<Option<PlainData>, PersistentConfigError> {
         self.mnemonic_seed_params
             .lock()
@@ -310,6 +335,27 @@ impl PersistentConfigurationMock {
         self
     }
 
+    pub fn payment_curves_result(
+        self,
+        result: Result<Option<PaymentCurves>, PersistentConfigError>,
+    ) -> Self {
+        self.payment_curves_results.borrow_mut().push(result);
+        self
+    }
+
+    pub fn set_payment_curves_params(
+        mut self,
+        params: &Arc<Mutex<Vec<PaymentCurves>>>,
+    ) -> PersistentConfigurationMock {
+        self.set_payment_curves_params = params.clone();
+        self
+    }
+
+    pub fn set_payment_curves_result(self, result: Result<(), PersistentConfigError>) -> Self {
+        self.set_payment_curves_results.borrow_mut().push(result);
+        self
+    }
+
     pub fn past_neighbors_params(
         mut self,
         params: &Arc<Mutex<Vec<String>>>,
@@ -20,6 +20,7 @@ use crate::sub_lib::dispatcher::{DispatcherSubs, StreamShutdownMsg};
 use crate::sub_lib::hopper::IncipientCoresPackage;
 use crate::sub_lib::hopper::{ExpiredCoresPackage, NoLookupIncipientCoresPackage};
 use crate::sub_lib::hopper::{HopperSubs, MessageType};
+use crate::sub_lib::neighborhood::BootstrapListMessage;
 use crate::sub_lib::neighborhood::NeighborhoodDotGraphRequest;
 use crate::sub_lib::neighborhood::NeighborhoodSubs;
 use crate::sub_lib::neighborhood::NodeQueryMessage;
@@ -35,7 +36,7 @@ use crate::sub_lib::proxy_client::{ClientResponsePayload_0v1, InboundServerData}
 use crate::sub_lib::proxy_client::{DnsResolveFailure_0v1, ProxyClientSubs};
 use crate::sub_lib::proxy_server::ProxyServerSubs;
 use crate::sub_lib::proxy_server::{
-    AddReturnRouteMessage, AddRouteMessage, ClientRequestPayload_0v1,
+    AddReturnRouteMessage, AddRouteMessage, ClientRequestPayload_0v1, SetExitCountryMessage,
 };
 use crate::sub_lib::set_consuming_wallet_message::SetConsumingWalletMessage;
 use crate::sub_lib::stream_handler_pool::DispatcherNodeQueryResponse;
@@ -93,6 +94,7 @@ recorder_message_handler!(AddReturnRouteMessage);
 recorder_message_handler!(AddRouteMessage);
 recorder_message_handler!(AddStreamMsg);
 recorder_message_handler!(BindMessage);
+recorder_message_handler!(BootstrapListMessage);
 recorder_message_handler!(CrashNotification);
 recorder_message_handler!(DaemonBindMessage);
 recorder_message_handler!(DispatcherNodeQueryMessage);
@@ -125,6 +127,7 @@ recorder_message_handler!(ReportRoutingServiceProvidedMessage);
 recorder_message_handler!(SentPayments);
 recorder_message_handler!(SetConsumingWalletMessage);
 recorder_message_handler!(SetDbPasswordMsg);
+recorder_message_handler!(SetExitCountryMessage);
 recorder_message_handler!(SetGasPriceMsg);
 recorder_message_handler!(StartMessage);
 recorder_message_handler!(StreamShutdownMsg);
@@ -343,6 +346,8 @@ pub fn make_proxy_server_subs_from(addr: &Addr<Recorder>) -> ProxyServerSubs {
         add_route: recipient!(addr, AddRouteMessage),
         stream_shutdown_sub: recipient!(addr, StreamShutdownMsg),
         set_consuming_wallet_sub: recipient!(addr, SetConsumingWalletMessage),
+        set_exit_country_sub: recipient!(addr, SetExitCountryMessage),
+        remove_neighbor_sub: recipient!(addr, RemoveNeighborMessage),
     }
 }
 
@@ -393,6 +398,7 @@ pub fn make_neighborhood_subs_from(addr: &Addr<Recorder>) -> NeighborhoodSubs {
         set_consuming_wallet_sub: recipient!(addr, SetConsumingWalletMessage),
         from_ui_message_sub: addr.clone().recipient::<NodeFromUiMessage>(),
         new_password_sub: addr.clone().recipient::<NewPasswordMessage>(),
+        bootstrap_list_sub: recipient!(addr, BootstrapListMessage),
     }
 }
 
@@ -1,5 +1,7 @@
 // Copyright (c) 2017-2018, Substratum LLC (https://substratum.net) and/or its affiliates. All rights reserved.
 use crate::sub_lib::logger::Logger;
+use crate::systemd;
+use crate::systemd::UI_SOCKET_NAME;
 use actix::Recipient;
 use bytes::BytesMut;
 use futures::future::FutureResult;
@@ -10,25 +12,32 @@ use futures::Future;
 use futures::Sink;
 use futures::Stream;
 use itertools::Itertools;
-use masq_lib::messages::{ToMessageBody, UiUnmarshalError, NODE_UI_PROTOCOL, UNMARSHAL_ERROR};
-use masq_lib::ui_gateway::MessagePath::Conversation;
+use masq_lib::messages::{
+    FromMessageBody, ToMessageBody, UiRedirect, UiSubscribeRequest, UiSubscribeResponse,
+    UiUnmarshalError, UiUnsubscribeRequest, UiUnsubscribeResponse, ALL_BROADCASTS,
+    NODE_UI_PROTOCOL, UNMARSHAL_ERROR,
+};
+use masq_lib::ui_gateway::MessagePath::{Conversation, FireAndForget};
 use masq_lib::ui_gateway::MessageTarget::ClientId;
 use masq_lib::ui_gateway::{MessageBody, MessageTarget, NodeFromUiMessage, NodeToUiMessage};
 use masq_lib::ui_traffic_converter::UiTrafficConverter;
 use masq_lib::ui_traffic_converter::UnmarshalError::{Critical, NonCritical};
 use masq_lib::utils::localhost;
 use std::any::Any;
-use std::collections::HashMap;
+use std::collections::{BTreeSet, HashMap};
 use std::fmt::Debug;
 use std::net::SocketAddr;
 use std::sync::Mutex;
 use std::sync::{Arc, MutexGuard};
+use tokio::net::TcpListener;
 use tokio::reactor::Handle;
 use websocket::client::r#async::Framed;
 use websocket::r#async::MessageCodec;
 use websocket::r#async::TcpStream;
-use websocket::server::r#async::Server;
-use websocket::server::upgrade::WsUpgrade;
+use websocket::server::r#async::{Incoming, Server};
+use websocket::server::upgrade::r#async::IntoWs;
+use websocket::server::upgrade::{HyperIntoWsError, WsUpgrade};
+use websocket::server::InvalidConnection;
 use websocket::OwnedMessage;
 use websocket::WebSocketError;
 
@@ -71,6 +80,93 @@ struct WebSocketSupervisorInner {
     from_ui_message_sub: Recipient<NodeFromUiMessage>,
     client_id_by_socket_addr: HashMap<SocketAddr, u64>,
     client_by_id: HashMap<u64, Box<dyn ClientWrapper>>,
+    subscriptions_by_id: HashMap<u64, BroadcastSubscriptions>,
+}
+
+// Which broadcasts a UI wants to see. Messages addressed to a particular UI are never filtered.
+#[derive(Clone, Debug, PartialEq)]
+struct BroadcastSubscriptions {
+    all_broadcasts: bool,
+    // Unsubscribed opcodes if all_broadcasts is set; subscribed opcodes otherwise
+    opcodes: BTreeSet<String>,
+}
+
+impl Default for BroadcastSubscriptions {
+    fn default() -> Self {
+        Self {
+            all_broadcasts: true,
+            opcodes: BTreeSet::new(),
+        }
+    }
+}
+
+impl BroadcastSubscriptions {
+    // A UI that connects to ws://host:port/?broadcasts=setup,crashed sees only those broadcasts
+    fn from_uri(uri: &str) -> Self {
+        let query = match uri.splitn(2, '?').nth(1) {
+            Some(query) => query,
+            None => return Self::default(),
+        };
+        match query
+            .split('&')
+            .filter_map(|pair| {
+                let mut parts = pair.splitn(2, '=');
+                match (parts.next(), parts.next()) {
+                    (Some("broadcasts"), Some(value)) => Some(value),
+                    _ => None,
+                }
+            })
+            .last()
+        {
+            Some(value) => {
+                let opcodes = value
+                    .split(',')
+                    .filter(|opcode| !opcode.is_empty())
+                    .map(|opcode| opcode.to_string())
+                    .collect_vec();
+                let mut subscriptions = Self {
+                    all_broadcasts: false,
+                    opcodes: BTreeSet::new(),
+                };
+                subscriptions.subscribe(&opcodes);
+                subscriptions
+            }
+            None => Self::default(),
+        }
+    }
+
+    fn subscribe(&mut self, opcodes: &[String]) {
+        if opcodes.iter().any(|opcode| opcode == ALL_BROADCASTS) {
+            *self = Self::default();
+        } else if self.all_broadcasts {
+            opcodes.iter().for_each(|opcode| {
+                self.opcodes.remove(opcode);
+            });
+        } else {
+            self.opcodes.extend(opcodes.iter().cloned());
+        }
+    }
+
+    fn unsubscribe(&mut self, opcodes: &[String]) {
+        if opcodes.iter().any(|opcode| opcode == ALL_BROADCASTS) {
+            self.all_broadcasts = false;
+            self.opcodes.clear();
+        } else if self.all_broadcasts {
+            self.opcodes.extend(opcodes.iter().cloned());
+        } else {
+            opcodes.iter().for_each(|opcode| {
+                self.opcodes.remove(opcode);
+            });
+        }
+    }
+
+    fn accepts(&self, opcode: &str) -> bool {
+        self.all_broadcasts != self.opcodes.contains(opcode)
+    }
+
+    fn opcodes(&self) -> Vec<String> {
+        self.opcodes.iter().cloned().collect_vec()
+    }
 }
 
 impl WebSocketSupervisor for WebSocketSupervisorReal {
@@ -91,13 +187,21 @@ impl WebSocketSupervisorReal {
             from_ui_message_sub,
             client_id_by_socket_addr: HashMap::new(),
             client_by_id: HashMap::new(),
+            subscriptions_by_id: HashMap::new(),
         }));
         let logger = Logger::new("WebSocketSupervisor");
         let logger_1 = logger.clone();
-        let server_address = SocketAddr::new(localhost(), port);
-        let server = Server::bind(server_address, &Handle::default())
-            .unwrap_or_else(|e| panic!("Could not start UI server at {}: {}", server_address, e));
-        let upgrade_tuple_stream = Self::remove_failures(server.incoming(), &logger);
+        let incoming = match systemd::take_activated_tcp_listener(UI_SOCKET_NAME) {
+            Some(listener) => Self::incoming_from_activated_listener(listener),
+            None => {
+                let server_address = SocketAddr::new(localhost(), port);
+                let server = Server::bind(server_address, &Handle::default()).unwrap_or_else(|e| {
+                    panic!("Could not start UI server at {}: {}", server_address, e)
+                });
+                server.incoming()
+            }
+        };
+        let upgrade_tuple_stream = Self::remove_failures(incoming, &logger);
         let inner_clone = inner.clone();
         let foreach_result = upgrade_tuple_stream.for_each(move |(upgrade, socket_addr)| {
             Self::handle_upgrade_request(upgrade, socket_addr, inner_clone.clone(), &logger);
@@ -119,21 +223,71 @@ impl WebSocketSupervisorReal {
         Ok(WebSocketSupervisorReal { inner })
     }
 
+    // Does for a listener systemd bound on our behalf what Server::incoming() does for one we
+    // bind ourselves.
+    fn incoming_from_activated_listener(listener: std::net::TcpListener) -> Incoming<TcpStream> {
+        let listener = TcpListener::from_std(listener, &Handle::default())
+            .unwrap_or_else(|e| panic!("Could not adopt socket-activated UI listener: {}", e));
+        Box::new(
+            listener
+                .incoming()
+                .map_err(|e| InvalidConnection {
+                    stream: None,
+                    parsed: None,
+                    buffer: None,
+                    error: HyperIntoWsError::Io(e),
+                })
+                .and_then(|stream| {
+                    let socket_addr = stream
+                        .peer_addr()
+                        .expect("Socket-activated UI connection has no peer address");
+                    stream.into_ws().map(move |upgrade| (upgrade, socket_addr))
+                }),
+        )
+    }
+
     fn send_msg(locked_inner: &mut MutexGuard<WebSocketSupervisorInner>, msg: NodeToUiMessage) {
         let client_ids = match msg.target {
+            // A broadcast aimed at one client is still a broadcast, but a redirect is the answer
+            // to something the client asked for.
+            MessageTarget::ClientId(n)
+                if msg.body.path == FireAndForget
+                    && msg.body.opcode != UiRedirect::type_opcode()
+                    && !Self::is_subscribed(locked_inner, n, &msg.body.opcode) =>
+            {
+                vec![]
+            }
             MessageTarget::ClientId(n) => vec![n],
-            MessageTarget::AllExcept(n) => locked_inner
-                .client_by_id
-                .keys()
-                .filter(|k| k != &&n)
-                .copied()
+            MessageTarget::AllExcept(n) => Self::subscribers(locked_inner, &msg.body.opcode)
+                .into_iter()
+                .filter(|k| k != &n)
                 .collect_vec(),
-            MessageTarget::AllClients => locked_inner.client_by_id.keys().copied().collect_vec(),
+            MessageTarget::AllClients => Self::subscribers(locked_inner, &msg.body.opcode),
         };
         let json = UiTrafficConverter::new_marshal(msg.body);
         Self::send_to_clients(locked_inner, client_ids, json);
     }
 
+    fn subscribers(locked_inner: &WebSocketSupervisorInner, opcode: &str) -> Vec<u64> {
+        locked_inner
+            .client_by_id
+            .keys()
+            .filter(|client_id| Self::is_subscribed(locked_inner, **client_id, opcode))
+            .copied()
+            .collect_vec()
+    }
+
+    fn is_subscribed(
+        locked_inner: &WebSocketSupervisorInner,
+        client_id: u64,
+        opcode: &str,
+    ) -> bool {
+        match locked_inner.subscriptions_by_id.get(&client_id) {
+            Some(subscriptions) => subscriptions.accepts(opcode),
+            None => true,
+        }
+    }
+
     fn remove_failures<I, E: Debug>(
         stream: impl Stream<Item = I, Error = E>,
         logger: &Logger,
@@ -179,12 +333,20 @@ impl WebSocketSupervisorReal {
     ) {
         let logger_clone = logger.clone();
         info!(logger_clone, "UI connected at {}", socket_addr);
+        let subscriptions =
+            BroadcastSubscriptions::from_uri(&upgrade.request.subject.1.to_string());
         let upgrade_future =
             upgrade
                 .use_protocol(NODE_UI_PROTOCOL)
                 .accept()
                 .map(move |(client, _)| {
-                    Self::handle_connection(client, &inner, &logger_clone, socket_addr);
+                    Self::handle_connection(
+                        client,
+                        &inner,
+                        &logger_clone,
+                        socket_addr,
+                        subscriptions,
+                    );
                 });
         tokio::spawn(upgrade_future.then(|result| {
             match result {
@@ -209,6 +371,7 @@ impl WebSocketSupervisorReal {
         inner: &Arc<Mutex<WebSocketSupervisorInner>>,
         logger: &Logger,
         socket_addr: SocketAddr,
+        subscriptions: BroadcastSubscriptions,
     ) {
         let logger_1 = logger.clone();
         let logger_2 = logger.clone();
@@ -226,6 +389,9 @@ impl WebSocketSupervisorReal {
             .client_id_by_socket_addr
             .insert(socket_addr, client_id);
         locked_inner.client_by_id.insert(client_id, client_wrapper);
+        locked_inner
+            .subscriptions_by_id
+            .insert(client_id, subscriptions);
         let incoming_future = incoming
             .then(move |result| Self::handle_websocket_errors(result, &logger_2, socket_addr))
             .map(move |owned_message| match owned_message {
@@ -265,10 +431,12 @@ impl WebSocketSupervisorReal {
         };
         match UiTrafficConverter::new_unmarshal_from_ui(message, client_id) {
             Ok(from_ui_message) => {
-                locked_inner
-                    .from_ui_message_sub
-                    .try_send(from_ui_message)
-                    .expect("UiGateway is dead");
+                if !Self::handle_subscription_message(&mut locked_inner, &from_ui_message) {
+                    locked_inner
+                        .from_ui_message_sub
+                        .try_send(from_ui_message)
+                        .expect("UiGateway is dead");
+                }
             }
             Err(Critical(e)) => {
                 error!(
@@ -331,6 +499,69 @@ impl WebSocketSupervisorReal {
         ok::<(), ()>(())
     }
 
+    // Subscriptions belong to the connection, so the supervisor answers these itself instead of
+    // passing them on to the UiGateway.
+    fn handle_subscription_message(
+        locked_inner: &mut MutexGuard<WebSocketSupervisorInner>,
+        msg: &NodeFromUiMessage,
+    ) -> bool {
+        let opcode = msg.body.opcode.as_str();
+        if opcode != UiSubscribeRequest::type_opcode()
+            && opcode != UiUnsubscribeRequest::type_opcode()
+        {
+            return false;
+        }
+        let context_id = match msg.body.path {
+            Conversation(context_id) => context_id,
+            FireAndForget => return false,
+        };
+        let subscriptions = locked_inner
+            .subscriptions_by_id
+            .entry(msg.client_id)
+            .or_insert_with(BroadcastSubscriptions::default);
+        let body = if opcode == UiSubscribeRequest::type_opcode() {
+            match UiSubscribeRequest::fmb(msg.body.clone()) {
+                Ok((request, _)) => {
+                    subscriptions.subscribe(&request.opcodes);
+                    UiSubscribeResponse {
+                        all_broadcasts: subscriptions.all_broadcasts,
+                        opcodes: subscriptions.opcodes(),
+                    }
+                    .tmb(context_id)
+                }
+                Err(e) => Self::unmarshal_error_body(opcode, context_id, e.to_string()),
+            }
+        } else {
+            match UiUnsubscribeRequest::fmb(msg.body.clone()) {
+                Ok((request, _)) => {
+                    subscriptions.unsubscribe(&request.opcodes);
+                    UiUnsubscribeResponse {
+                        all_broadcasts: subscriptions.all_broadcasts,
+                        opcodes: subscriptions.opcodes(),
+                    }
+                    .tmb(context_id)
+                }
+                Err(e) => Self::unmarshal_error_body(opcode, context_id, e.to_string()),
+            }
+        };
+        Self::send_msg(
+            locked_inner,
+            NodeToUiMessage {
+                target: ClientId(msg.client_id),
+                body,
+            },
+        );
+        true
+    }
+
+    fn unmarshal_error_body(opcode: &str, context_id: u64, message: String) -> MessageBody {
+        MessageBody {
+            opcode: opcode.to_string(),
+            path: Conversation(context_id),
+            payload: Err((UNMARSHAL_ERROR, message)),
+        }
+    }
+
     fn handle_close_message(
         inner_arc: &Arc<Mutex<WebSocketSupervisorInner>>,
         logger: &Logger,
@@ -423,6 +654,7 @@ impl WebSocketSupervisorReal {
         socket_addr: SocketAddr,
         logger: &Logger,
     ) {
+        locked_inner.subscriptions_by_id.remove(&client_id);
         let mut client = match locked_inner.client_by_id.remove(&client_id) {
             Some(client) => client,
             None => panic!("WebSocketSupervisor got a disconnect from a client that has disappeared from the stable!"),
@@ -455,10 +687,12 @@ mod tests {
     use actix::{Actor, Addr};
     use futures::future::lazy;
     use masq_lib::messages::{
-        FromMessageBody, UiShutdownRequest, UiStartOrder, UiUnmarshalError, NODE_UI_PROTOCOL,
+        FromMessageBody, UiShutdownRequest, UiStartOrder, UiSubscribeRequest, UiSubscribeResponse,
+        UiUnmarshalError, UiUnsubscribeRequest, UiUnsubscribeResponse, NODE_UI_PROTOCOL,
         UNMARSHAL_ERROR,
     };
     use masq_lib::test_utils::ui_connection::UiConnection;
+    use masq_lib::ui_gateway::MessagePath;
     use masq_lib::ui_gateway::MessagePath::FireAndForget;
     use masq_lib::ui_gateway::NodeFromUiMessage;
     use masq_lib::ui_traffic_converter::UiTrafficConverter;
@@ -496,6 +730,14 @@ mod tests {
                 .expect("couldn't downcast");
             result.clone()
         }
+
+        fn set_subscriptions(&self, client_id: u64, subscriptions: BroadcastSubscriptions) {
+            self.inner
+                .lock()
+                .unwrap()
+                .subscriptions_by_id
+                .insert(client_id, subscriptions);
+        }
     }
 
     struct ClientWrapperMock {
@@ -797,6 +1039,7 @@ mod tests {
             from_ui_message_sub: ui_message_sub.start().recipient::<NodeFromUiMessage>(),
             client_id_by_socket_addr: Default::default(),
             client_by_id: Default::default(),
+            subscriptions_by_id: Default::default(),
         };
         let subject = WebSocketSupervisorReal {
             inner: Arc::new(Mutex::new(subject_inner)),
@@ -864,6 +1107,7 @@ mod tests {
             from_ui_message_sub: ui_message_sub.start().recipient::<NodeFromUiMessage>(),
             client_id_by_socket_addr: Default::default(),
             client_by_id: Default::default(),
+            subscriptions_by_id: Default::default(),
         };
         let subject = WebSocketSupervisorReal {
             inner: Arc::new(Mutex::new(subject_inner)),
@@ -931,6 +1175,7 @@ mod tests {
             from_ui_message_sub: ui_message_sub.start().recipient::<NodeFromUiMessage>(),
             client_id_by_socket_addr: Default::default(),
             client_by_id: Default::default(),
+            subscriptions_by_id: Default::default(),
         };
         let subject = WebSocketSupervisorReal {
             inner: Arc::new(Mutex::new(subject_inner)),
@@ -1007,6 +1252,7 @@ mod tests {
             from_ui_message_sub,
             client_id_by_socket_addr: Default::default(),
             client_by_id,
+            subscriptions_by_id: Default::default(),
         }));
 
         WebSocketSupervisorReal::send_to_clients(
@@ -1308,4 +1554,287 @@ mod tests {
         System::current().stop();
         system.run();
     }
+
+    fn subscriptions(all_broadcasts: bool, opcodes: Vec<&str>) -> BroadcastSubscriptions {
+        BroadcastSubscriptions {
+            all_broadcasts,
+            opcodes: opcodes
+                .into_iter()
+                .map(|opcode| opcode.to_string())
+                .collect(),
+        }
+    }
+
+    fn strings(strs: Vec<&str>) -> Vec<String> {
+        strs.into_iter().map(|s| s.to_string()).collect()
+    }
+
+    #[test]
+    fn broadcast_subscriptions_start_out_accepting_everything() {
+        let subject = BroadcastSubscriptions::default();
+
+        assert_eq!(subject.accepts("setup"), true);
+        assert_eq!(subject.accepts("connectionChange"), true);
+    }
+
+    #[test]
+    fn broadcast_subscriptions_are_read_from_the_connection_uri() {
+        assert_eq!(
+            BroadcastSubscriptions::from_uri("/"),
+            BroadcastSubscriptions::default()
+        );
+        assert_eq!(
+            BroadcastSubscriptions::from_uri("/?other=value"),
+            BroadcastSubscriptions::default()
+        );
+        assert_eq!(
+            BroadcastSubscriptions::from_uri("/?other=value&broadcasts=setup,crashed"),
+            subscriptions(false, vec!["crashed", "setup"])
+        );
+        assert_eq!(
+            BroadcastSubscriptions::from_uri("/?broadcasts="),
+            subscriptions(false, vec![])
+        );
+        assert_eq!(
+            BroadcastSubscriptions::from_uri("/?broadcasts=setup,*"),
+            BroadcastSubscriptions::default()
+        );
+    }
+
+    #[test]
+    fn unsubscribing_from_everything_then_subscribing_to_some_accepts_only_those() {
+        let mut subject = BroadcastSubscriptions::default();
+
+        subject.unsubscribe(&strings(vec!["*"]));
+        subject.subscribe(&strings(vec!["setup", "crashed"]));
+        subject.unsubscribe(&strings(vec!["crashed"]));
+
+        assert_eq!(subject, subscriptions(false, vec!["setup"]));
+        assert_eq!(subject.accepts("setup"), true);
+        assert_eq!(subject.accepts("crashed"), false);
+        assert_eq!(subject.accepts("connectionChange"), false);
+    }
+
+    #[test]
+    fn unsubscribing_from_some_then_subscribing_to_everything_accepts_everything() {
+        let mut subject = BroadcastSubscriptions::default();
+
+        subject.unsubscribe(&strings(vec!["connectionChange", "scanResults"]));
+        subject.subscribe(&strings(vec!["scanResults"]));
+
+        assert_eq!(subject, subscriptions(true, vec!["connectionChange"]));
+        assert_eq!(subject.accepts("connectionChange"), false);
+        assert_eq!(subject.accepts("scanResults"), true);
+
+        subject.subscribe(&strings(vec!["*"]));
+
+        assert_eq!(subject, BroadcastSubscriptions::default());
+    }
+
+    #[test]
+    fn send_msg_with_all_clients_skips_clients_not_subscribed_to_the_opcode() {
+        let port = find_free_port();
+        let (ui_gateway, _, _) = make_recorder();
+        let ui_message_sub = subs(ui_gateway);
+        let system =
+            System::new("send_msg_with_all_clients_skips_clients_not_subscribed_to_the_opcode");
+        let lazy_future = lazy(move || {
+            let subject = WebSocketSupervisorReal::new(port, ui_message_sub).unwrap();
+            let subscribed_client_id = subject.inject_mock_client(
+                ClientWrapperMock::new()
+                    .send_result(Ok(()))
+                    .flush_result(Ok(())),
+            );
+            let unsubscribed_client_id = subject.inject_mock_client(ClientWrapperMock::new());
+            let excluding_client_id = subject.inject_mock_client(ClientWrapperMock::new());
+            subject.set_subscriptions(subscribed_client_id, subscriptions(false, vec!["booga"]));
+            subject.set_subscriptions(unsubscribed_client_id, subscriptions(false, vec!["setup"]));
+            subject.set_subscriptions(excluding_client_id, subscriptions(true, vec!["booga"]));
+            let msg = NodeToUiMessage {
+                target: MessageTarget::AllClients,
+                body: MessageBody {
+                    opcode: "booga".to_string(),
+                    path: FireAndForget,
+                    payload: Ok("{}".to_string()),
+                },
+            };
+
+            subject.send_msg(msg);
+
+            let subscribed_client = subject.get_mock_client(subscribed_client_id);
+            assert_eq!(subscribed_client.send_params.lock().unwrap().len(), 1);
+            let unsubscribed_client = subject.get_mock_client(unsubscribed_client_id);
+            assert_eq!(unsubscribed_client.send_params.lock().unwrap().len(), 0);
+            let excluding_client = subject.get_mock_client(excluding_client_id);
+            assert_eq!(excluding_client.send_params.lock().unwrap().len(), 0);
+            Ok(())
+        });
+        actix::spawn(lazy_future);
+        System::current().stop();
+        system.run();
+    }
+
+    #[test]
+    fn send_msg_with_a_client_id_filters_only_unsubscribed_broadcasts() {
+        let port = find_free_port();
+        let (ui_gateway, _, _) = make_recorder();
+        let ui_message_sub = subs(ui_gateway);
+        let system = System::new("send_msg_with_a_client_id_filters_only_unsubscribed_broadcasts");
+        let lazy_future = lazy(move || {
+            let subject = WebSocketSupervisorReal::new(port, ui_message_sub).unwrap();
+            let client_id = subject.inject_mock_client(
+                ClientWrapperMock::new()
+                    .send_result(Ok(()))
+                    .flush_result(Ok(()))
+                    .send_result(Ok(()))
+                    .flush_result(Ok(())),
+            );
+            subject.set_subscriptions(client_id, subscriptions(false, vec![]));
+            let make_msg = |opcode: &str, path: MessagePath| NodeToUiMessage {
+                target: MessageTarget::ClientId(client_id),
+                body: MessageBody {
+                    opcode: opcode.to_string(),
+                    path,
+                    payload: Ok("{}".to_string()),
+                },
+            };
+
+            subject.send_msg(make_msg("booga", FireAndForget));
+            subject.send_msg(make_msg("booga", Conversation(1)));
+            subject.send_msg(make_msg("redirect", FireAndForget));
+
+            let client = subject.get_mock_client(client_id);
+            let sent_redirects = client
+                .send_params
+                .lock()
+                .unwrap()
+                .iter()
+                .map(|message| match message {
+                    OwnedMessage::Text(json) => json.contains("\"redirect\""),
+                    x => panic!("Expected text message, got {:?}", x),
+                })
+                .collect_vec();
+            assert_eq!(sent_redirects, vec![false, true]);
+            Ok(())
+        });
+        actix::spawn(lazy_future);
+        System::current().stop();
+        system.run();
+    }
+
+    #[test]
+    fn subscription_requests_are_answered_by_the_supervisor_and_not_forwarded() {
+        let port = find_free_port();
+        let (ui_gateway, ui_gateway_awaiter, ui_gateway_recording_arc) = make_recorder();
+
+        thread::spawn(move || {
+            let system = System::new(
+                "subscription_requests_are_answered_by_the_supervisor_and_not_forwarded",
+            );
+            let ui_message_sub = subs(ui_gateway);
+            let subject = lazy(move || {
+                let _subject = WebSocketSupervisorReal::new(port, ui_message_sub).unwrap();
+                Ok(())
+            });
+            actix::spawn(subject);
+            system.run();
+        });
+        let mut client = await_value(None, || UiConnection::make(port, NODE_UI_PROTOCOL)).unwrap();
+
+        let unsubscribe_response: UiUnsubscribeResponse = client
+            .transact_with_context_id(
+                UiUnsubscribeRequest {
+                    opcodes: strings(vec!["*"]),
+                },
+                1234,
+            )
+            .unwrap();
+        let subscribe_response: UiSubscribeResponse = client
+            .transact_with_context_id(
+                UiSubscribeRequest {
+                    opcodes: strings(vec!["setup", "crashed"]),
+                },
+                1235,
+            )
+            .unwrap();
+        client.send(UiShutdownRequest {});
+
+        assert_eq!(
+            unsubscribe_response,
+            UiUnsubscribeResponse {
+                all_broadcasts: false,
+                opcodes: vec![],
+            }
+        );
+        assert_eq!(
+            subscribe_response,
+            UiSubscribeResponse {
+                all_broadcasts: false,
+                opcodes: strings(vec!["crashed", "setup"]),
+            }
+        );
+        ui_gateway_awaiter.await_message_count(1);
+        thread::sleep(Duration::from_millis(500)); // make sure there's not another message sent
+        let ui_gateway_recording = ui_gateway_recording_arc.lock().unwrap();
+        assert_eq!(
+            ui_gateway_recording.get_record::<NodeFromUiMessage>(0),
+            &NodeFromUiMessage {
+                client_id: 0,
+                body: UiShutdownRequest {}.tmb(0),
+            }
+        );
+        assert_eq!(ui_gateway_recording.len(), 1);
+        client.shutdown();
+    }
+
+    #[test]
+    fn malformed_subscription_request_gets_unmarshal_error() {
+        let (ui_message_sub, _, _) = make_recorder();
+        let send_params_arc = Arc::new(Mutex::new(vec![]));
+        let client = ClientWrapperMock::new()
+            .send_params(&send_params_arc)
+            .send_result(Ok(()))
+            .flush_result(Ok(()));
+        let mut client_by_id: HashMap<u64, Box<dyn ClientWrapper>> = HashMap::new();
+        client_by_id.insert(0, Box::new(client));
+        let inner_arc = Arc::new(Mutex::new(WebSocketSupervisorInner {
+            port: 0,
+            next_client_id: 1,
+            from_ui_message_sub: subs(ui_message_sub),
+            client_id_by_socket_addr: Default::default(),
+            client_by_id,
+            subscriptions_by_id: Default::default(),
+        }));
+        let msg = NodeFromUiMessage {
+            client_id: 0,
+            body: MessageBody {
+                opcode: "subscribe".to_string(),
+                path: Conversation(4321),
+                payload: Ok(r#"{"opcodes": "setup"}"#.to_string()),
+            },
+        };
+
+        let result = WebSocketSupervisorReal::handle_subscription_message(
+            &mut inner_arc.lock().unwrap(),
+            &msg,
+        );
+
+        assert_eq!(result, true);
+        let mut send_params = send_params_arc.lock().unwrap();
+        let actual_json = match send_params.remove(0) {
+            OwnedMessage::Text(s) => s,
+            x => panic!("Expected OwnedMessage::Text, got {:?}", x),
+        };
+        let actual = UiTrafficConverter::new_unmarshal_to_ui(&actual_json, ClientId(0)).unwrap();
+        assert_eq!(actual.body.opcode, "subscribe".to_string());
+        assert_eq!(actual.body.path, Conversation(4321));
+        match actual.body.payload {
+            Err((UNMARSHAL_ERROR, _)) => (),
+            x => panic!("Expected unmarshal error, got {:?}", x),
+        }
+        assert_eq!(
+            inner_arc.lock().unwrap().subscriptions_by_id.get(&0),
+            Some(&BroadcastSubscriptions::default())
+        );
+    }
 }